@@ -0,0 +1,52 @@
+//! Copy throughput per buffer size, for tuning `fs_op::options::auto_buffer_size`.
+//!
+//! Run with `cargo bench --bench copy_buffer`. Each group copies one file
+//! size with the fixed buffer sizes and with whatever the auto mode picks
+//! for that size, so the auto thresholds can be checked against the
+//! fastest fixed size on the machine at hand.
+
+use std::path::Path;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use fileZoom::fs_op::helpers::atomic_copy_file_with;
+use fileZoom::fs_op::options::{auto_buffer_size, CopyOptions};
+
+const KIB: usize = 1024;
+const MIB: usize = 1024 * KIB;
+
+const FILE_SIZES: &[usize] = &[256 * KIB, 8 * MIB, 128 * MIB];
+const BUFFER_SIZES: &[usize] = &[4 * KIB, 64 * KIB, 256 * KIB, MIB, 4 * MIB];
+
+fn copy_once(src: &Path, dst: &Path, buffer_size: usize) {
+    let opts = CopyOptions { overwrite: true, buffer_size, preserve_metadata: false, ..Default::default() };
+    atomic_copy_file_with(src, dst, &opts).expect("copy");
+}
+
+fn copy_buffer(c: &mut Criterion) {
+    let dir = tempfile::tempdir().expect("temp dir");
+    for &len in FILE_SIZES {
+        let src = dir.path().join(format!("src-{}", len));
+        let dst = dir.path().join(format!("dst-{}", len));
+        let data: Vec<u8> = (0..len).map(|i| (i % 251) as u8).collect();
+        std::fs::write(&src, data).expect("write source");
+
+        let mut group = c.benchmark_group(format!("copy_{}KiB", len / KIB));
+        group.throughput(Throughput::Bytes(len as u64));
+        if len >= 64 * MIB {
+            group.sample_size(10);
+        }
+        for &buffer_size in BUFFER_SIZES {
+            group.bench_with_input(BenchmarkId::new("fixed", buffer_size / KIB), &buffer_size, |b, &size| {
+                b.iter(|| copy_once(&src, &dst, size))
+            });
+        }
+        let auto = auto_buffer_size(len as u64, dir.path());
+        group.bench_with_input(BenchmarkId::new("auto", auto / KIB), &auto, |b, &size| {
+            b.iter(|| copy_once(&src, &dst, size))
+        });
+        group.finish();
+    }
+}
+
+criterion_group!(benches, copy_buffer);
+criterion_main!(benches);
@@ -0,0 +1,168 @@
+//! Terminal colour depth detection and quantization of theme colours.
+//!
+//! Themes use RGB colours. A terminal without truecolor support shows
+//! those as whatever its palette happens to hold at the nearest escape
+//! code, so `colors::current` maps them to the nearest entry of the
+//! 256-colour or 16-colour palette first. The depth is detected from the
+//! environment unless `color_depth` in `settings.toml` overrides it.
+
+use ratatui::style::Color;
+
+/// How many colours the terminal can show.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ColorDepth {
+    /// 24-bit RGB.
+    #[default]
+    TrueColor,
+    /// The xterm 256-colour palette.
+    Ansi256,
+    /// The 16 basic ANSI colours.
+    Ansi16,
+}
+
+impl ColorDepth {
+    /// Name used in `settings.toml`.
+    pub fn name(self) -> &'static str {
+        match self {
+            ColorDepth::TrueColor => "truecolor",
+            ColorDepth::Ansi256 => "256",
+            ColorDepth::Ansi16 => "16",
+        }
+    }
+
+    /// Parse the `color_depth` setting: `auto` (detect), `truecolor`,
+    /// `256` or `16`. `None` for `auto` and unknown values.
+    pub fn from_name(name: &str) -> Option<Self> {
+        [ColorDepth::TrueColor, ColorDepth::Ansi256, ColorDepth::Ansi16]
+            .into_iter()
+            .find(|d| d.name().eq_ignore_ascii_case(name.trim()))
+    }
+
+    /// Guess the depth from `COLORTERM` and `TERM`.
+    pub fn detect() -> Self {
+        Self::from_env(std::env::var("COLORTERM").ok().as_deref(), std::env::var("TERM").ok().as_deref())
+    }
+
+    /// Depth implied by the given `COLORTERM` and `TERM` values. Windows
+    /// consoles set neither and support truecolor.
+    pub fn from_env(colorterm: Option<&str>, term: Option<&str>) -> Self {
+        let colorterm = colorterm.unwrap_or("").to_ascii_lowercase();
+        if colorterm == "truecolor" || colorterm == "24bit" {
+            return ColorDepth::TrueColor;
+        }
+        match term {
+            Some(t) if t.contains("truecolor") || t.contains("direct") => ColorDepth::TrueColor,
+            Some(t) if t.contains("256") => ColorDepth::Ansi256,
+            Some(t) if !t.is_empty() => ColorDepth::Ansi16,
+            _ if cfg!(windows) => ColorDepth::TrueColor,
+            _ => ColorDepth::Ansi16,
+        }
+    }
+
+    /// The depth the `color_depth` setting asks for, detecting it for
+    /// `auto`.
+    pub fn resolve(setting: &str) -> Self {
+        Self::from_name(setting).unwrap_or_else(Self::detect)
+    }
+
+    /// Map `color` to the nearest colour this depth can show. Named
+    /// colours and `Reset` are left alone.
+    pub fn quantize(self, color: Color) -> Color {
+        match (self, color) {
+            (ColorDepth::TrueColor, c) => c,
+            (ColorDepth::Ansi256, Color::Rgb(r, g, b)) => Color::Indexed(nearest_256(r, g, b)),
+            (ColorDepth::Ansi16, Color::Rgb(r, g, b)) => nearest_16(r, g, b),
+            (ColorDepth::Ansi16, Color::Indexed(i)) if i >= 16 => {
+                let (r, g, b) = indexed_rgb(i);
+                nearest_16(r, g, b)
+            }
+            (_, c) => c,
+        }
+    }
+}
+
+/// Channel levels of the 6x6x6 colour cube (indices 16-231).
+const CUBE: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+/// The 16 ANSI colours with their usual xterm RGB values.
+const ANSI16: [(Color, (u8, u8, u8)); 16] = [
+    (Color::Black, (0, 0, 0)),
+    (Color::Red, (205, 0, 0)),
+    (Color::Green, (0, 205, 0)),
+    (Color::Yellow, (205, 205, 0)),
+    (Color::Blue, (0, 0, 238)),
+    (Color::Magenta, (205, 0, 205)),
+    (Color::Cyan, (0, 205, 205)),
+    (Color::Gray, (229, 229, 229)),
+    (Color::DarkGray, (127, 127, 127)),
+    (Color::LightRed, (255, 0, 0)),
+    (Color::LightGreen, (0, 255, 0)),
+    (Color::LightYellow, (255, 255, 0)),
+    (Color::LightBlue, (92, 92, 255)),
+    (Color::LightMagenta, (255, 0, 255)),
+    (Color::LightCyan, (0, 255, 255)),
+    (Color::White, (255, 255, 255)),
+];
+
+fn distance((r1, g1, b1): (u8, u8, u8), (r2, g2, b2): (u8, u8, u8)) -> u32 {
+    let d = |a: u8, b: u8| (a as i32 - b as i32).pow(2) as u32;
+    d(r1, r2) + d(g1, g2) + d(b1, b2)
+}
+
+/// RGB value of entry `i` of the xterm 256-colour palette.
+fn indexed_rgb(i: u8) -> (u8, u8, u8) {
+    match i {
+        0..=15 => ANSI16[i as usize].1,
+        16..=231 => {
+            let i = i - 16;
+            (CUBE[(i / 36) as usize], CUBE[(i / 6 % 6) as usize], CUBE[(i % 6) as usize])
+        }
+        _ => {
+            let v = 8 + (i - 232) * 10;
+            (v, v, v)
+        }
+    }
+}
+
+/// Nearest palette index among the colour cube and the grey ramp (the
+/// first 16 entries vary between terminals, so they are not used).
+fn nearest_256(r: u8, g: u8, b: u8) -> u8 {
+    let level = |c: u8| (0..6).min_by_key(|&i| (CUBE[i] as i32 - c as i32).abs()).unwrap_or(0) as u8;
+    let cube = 16 + 36 * level(r) + 6 * level(g) + level(b);
+    let avg = (r as u32 + g as u32 + b as u32) / 3;
+    let grey = 232 + ((avg.saturating_sub(3)) / 10).min(23) as u8;
+    if distance((r, g, b), indexed_rgb(grey)) < distance((r, g, b), indexed_rgb(cube)) {
+        grey
+    } else {
+        cube
+    }
+}
+
+fn nearest_16(r: u8, g: u8, b: u8) -> Color {
+    ANSI16.iter().min_by_key(|(_, rgb)| distance((r, g, b), *rgb)).map_or(Color::Reset, |(c, _)| *c)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_depth_from_the_environment() {
+        assert_eq!(ColorDepth::from_env(Some("truecolor"), Some("xterm")), ColorDepth::TrueColor);
+        assert_eq!(ColorDepth::from_env(None, Some("xterm-256color")), ColorDepth::Ansi256);
+        assert_eq!(ColorDepth::from_env(None, Some("linux")), ColorDepth::Ansi16);
+        assert_eq!(ColorDepth::from_name(" 256 "), Some(ColorDepth::Ansi256));
+        assert_eq!(ColorDepth::from_name("auto"), None);
+    }
+
+    #[test]
+    fn quantizes_to_the_nearest_palette_entry() {
+        assert_eq!(ColorDepth::Ansi256.quantize(Color::Rgb(0, 95, 135)), Color::Indexed(24));
+        assert_eq!(ColorDepth::Ansi256.quantize(Color::Rgb(11, 12, 13)), Color::Indexed(232));
+        assert_eq!(ColorDepth::Ansi256.quantize(Color::Rgb(255, 255, 255)), Color::Indexed(231));
+        assert_eq!(ColorDepth::Ansi16.quantize(Color::Rgb(0, 188, 212)), Color::Cyan);
+        assert_eq!(ColorDepth::Ansi16.quantize(Color::Indexed(196)), Color::LightRed);
+        assert_eq!(ColorDepth::Ansi16.quantize(Color::Yellow), Color::Yellow);
+        assert_eq!(ColorDepth::TrueColor.quantize(Color::Rgb(1, 2, 3)), Color::Rgb(1, 2, 3));
+    }
+}
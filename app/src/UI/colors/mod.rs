@@ -3,7 +3,10 @@ use crate::ui::Theme;
 use once_cell::sync::Lazy;
 use std::sync::Mutex;
 
-#[derive(Clone, Debug)]
+pub mod depth;
+pub use depth::ColorDepth;
+
+#[derive(Clone, Debug, Default)]
 pub struct Colors {
 	pub panel_block_style: Style,
 	pub panel_selected_style: Style,
@@ -15,27 +18,44 @@ pub struct Colors {
 	pub preview_block_style: Style,
 	pub scrollbar_thumb_style: Style,
 	pub scrollbar_track_style: Style,
+	/// Listing row styles per file kind.
+	pub filetype: crate::ui::filetype::FileTypeColors,
 }
 
-static CURRENT: Lazy<Mutex<Colors>> = Lazy::new(|| Mutex::new(Colors {
-	panel_block_style: Style::default(),
-	panel_selected_style: Style::default(),
-	panel_inactive_selected_style: Style::default(),
-	header_style: Style::default(),
-	footer_style: Style::default(),
-	preview_block_style: Style::default(),
-	menu_style: Style::default(),
-	menu_inactive_style: Style::default(),
-	scrollbar_thumb_style: Style::default(),
-	scrollbar_track_style: Style::default(),
-}));
+static CURRENT: Lazy<Mutex<Colors>> = Lazy::new(|| Mutex::new(Colors::default()));
+static DEPTH: Lazy<Mutex<ColorDepth>> = Lazy::new(|| Mutex::new(ColorDepth::TrueColor));
+
+/// Set the colour depth `current` quantizes theme colours to.
+pub fn set_color_depth(depth: ColorDepth) {
+	*DEPTH.lock().unwrap() = depth;
+}
 
+pub fn color_depth() -> ColorDepth {
+	*DEPTH.lock().unwrap()
+}
+
+/// Apply the theme called `name` (see `themes::named`); unknown or broken
+/// themes are logged and leave the colours unchanged.
 pub fn set_theme(name: &str) {
-	match name {
-		"dark" => set_from_theme(&crate::ui::Theme::dark()),
-		"light" => set_from_theme(&crate::ui::Theme::light()),
-		_ => {}
+	if let Err(e) = apply_theme(name) {
+		tracing::warn!("theme not applied: {:#}", e);
+	}
+}
+
+/// Apply the theme called `name`, reporting unknown names and user theme
+/// files that cannot be loaded. `default` restores the terminal colours.
+pub fn apply_theme(name: &str) -> anyhow::Result<()> {
+	match crate::ui::themes::named(name)? {
+		Some(theme) => set_from_theme(&theme),
+		None if name == "default" => reset(),
+		None => anyhow::bail!("unknown theme `{}`", name),
 	}
+	Ok(())
+}
+
+/// Go back to the terminal's own colours.
+pub fn reset() {
+	*CURRENT.lock().unwrap() = Colors::default();
 }
 
 /// Derive concrete runtime Styles from the provided Theme and store them.
@@ -64,6 +84,16 @@ pub fn set_from_theme(theme: &Theme) {
 	let scrollbar_thumb = panels.scrollbar_thumb_bg.unwrap_or(theme.accent);
 	let scrollbar_track = panels.scrollbar_track_bg.unwrap_or(theme.bg);
 
+	let kinds = crate::ui::filetype::FileTypeColors::default();
+	let kind = |token: Option<Color>, base: Style| token.map_or(base, |c| base.fg(c));
+	let filetype = crate::ui::filetype::FileTypeColors {
+		directory: kind(panels.dir_fg, kinds.directory),
+		symlink: kind(panels.symlink_fg, kinds.symlink),
+		executable: kind(panels.executable_fg, kinds.executable),
+		archive: kind(panels.archive_fg, kinds.archive),
+		image: kind(panels.image_fg, kinds.image),
+	};
+
 	*g = Colors {
 		panel_block_style: Style::default().fg(panel_fg).bg(panel_bg),
 		panel_selected_style: Style::default().fg(selected_fg).bg(selected_bg),
@@ -75,10 +105,29 @@ pub fn set_from_theme(theme: &Theme) {
 		preview_block_style: Style::default().fg(preview_fg).bg(preview_bg),
 		scrollbar_thumb_style: Style::default().bg(scrollbar_thumb),
 		scrollbar_track_style: Style::default().bg(scrollbar_track),
+		filetype,
 	};
 }
 
-pub fn current() -> Colors { CURRENT.lock().unwrap().clone() }
+/// The active styles, with their colours quantized to `color_depth`.
+pub fn current() -> Colors {
+	let depth = color_depth();
+	let q = |s: Style| Style { fg: s.fg.map(|c| depth.quantize(c)), bg: s.bg.map(|c| depth.quantize(c)), ..s };
+	let c = CURRENT.lock().unwrap().clone();
+	Colors {
+		panel_block_style: q(c.panel_block_style),
+		panel_selected_style: q(c.panel_selected_style),
+		panel_inactive_selected_style: q(c.panel_inactive_selected_style),
+		header_style: q(c.header_style),
+		menu_style: q(c.menu_style),
+		menu_inactive_style: q(c.menu_inactive_style),
+		footer_style: q(c.footer_style),
+		preview_block_style: q(c.preview_block_style),
+		scrollbar_thumb_style: q(c.scrollbar_thumb_style),
+		scrollbar_track_style: q(c.scrollbar_track_style),
+		filetype: c.filetype.map(q),
+	}
+}
 
 pub fn toggle() {
 	let cur = current();
@@ -1,21 +1,56 @@
 use crate::app::core::App;
 use crate::input::KeyCode;
+use ratatui::{layout::{Position, Rect}, widgets::Paragraph, Frame};
 
 #[derive(Clone, Debug, Default)]
 pub struct CommandLineState { pub visible: bool, pub buffer: String, pub cursor: usize }
 
+/// Handle a key while the command line is open.
+///
+/// Enter first tries the built-in commands (`runner::commands`); anything
+/// else is run through the shell in the active panel's directory and its
+/// output is shown in `Mode::CommandOutput`. Esc closes the command line.
 pub fn handle_input(app: &mut App, code: KeyCode) -> anyhow::Result<bool> {
     if let Some(cmd) = &mut app.command_line {
         match code {
-            KeyCode::Char(c) => { cmd.buffer.push(c); return Ok(false); }
+            KeyCode::Char(c) => { cmd.buffer.insert(byte_index(&cmd.buffer, cmd.cursor), c); cmd.cursor += 1; }
+            KeyCode::Backspace if cmd.cursor > 0 => {
+                cmd.cursor -= 1;
+                cmd.buffer.remove(byte_index(&cmd.buffer, cmd.cursor));
+            }
+            KeyCode::Left => cmd.cursor = cmd.cursor.saturating_sub(1),
+            KeyCode::Right => cmd.cursor = (cmd.cursor + 1).min(cmd.buffer.chars().count()),
+            KeyCode::Home => cmd.cursor = 0,
+            KeyCode::End => cmd.cursor = cmd.buffer.chars().count(),
+            KeyCode::Esc => app.command_line = None,
             KeyCode::Enter => {
-                let b = cmd.buffer.clone(); cmd.visible = false; cmd.buffer.clear();
+                let b = std::mem::take(&mut cmd.buffer);
+                app.command_line = None;
                 // delegate to runner commands to parse/execute
-                let _ = crate::runner::commands::execute_command(app, &b);
-                return Ok(true);
+                if b.trim().is_empty() || matches!(crate::runner::commands::execute_command(app, &b), Ok(true)) {
+                    return Ok(false);
+                }
+                crate::runner::shell::run_for_app(app, &b)?;
             }
             _ => {}
         }
     }
     Ok(false)
 }
+
+/// Open the command line with an empty buffer.
+pub fn open(app: &mut App) {
+    app.command_line = Some(CommandLineState { visible: true, ..Default::default() });
+}
+
+fn byte_index(s: &str, col: usize) -> usize {
+    s.char_indices().nth(col).map(|(i, _)| i).unwrap_or(s.len())
+}
+
+/// Draw the command line as a `:` prompt in `area` and place the cursor.
+pub fn render(f: &mut Frame, area: Rect, state: &CommandLineState) {
+    let colors = crate::ui::colors::current();
+    f.render_widget(Paragraph::new(format!(":{}", state.buffer)).style(colors.footer_style), area);
+    let x = area.x + (1 + state.cursor as u16).min(area.width.saturating_sub(1));
+    f.set_cursor_position(Position::new(x, area.y));
+}
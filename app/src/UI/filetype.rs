@@ -0,0 +1,314 @@
+//! File-type colours and icons for panel listings.
+//!
+//! Each entry is classified into a `FileKind` (directory, symlink,
+//! executable, archive, image or plain file), by its extension and, for
+//! local files the extension says nothing about, by its content. The kind
+//! picks the row style from the theme (`dir_fg`, `symlink_fg`... in
+//! `[panels]`, see `FileTypeColors`) and, when `icons` is enabled in the
+//! settings, a Nerd Font glyph shown before the name. When `ls_colors` is enabled the
+//! `LS_COLORS` environment variable is imported at startup and its rules
+//! take precedence over the theme.
+
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+use once_cell::sync::Lazy;
+use ratatui::style::{Color, Modifier, Style};
+
+use crate::app::types::Entry;
+
+/// Broad kind of an entry, used to colour it and pick its icon.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum FileKind {
+    Directory,
+    Symlink,
+    Executable,
+    Archive,
+    Image,
+    File,
+}
+
+const ARCHIVE_EXTS: &[&str] = &[
+    "7z", "apk", "bz2", "cab", "deb", "gz", "jar", "lz", "lz4", "lzma", "rar", "rpm", "tar", "tbz2", "tgz", "txz", "xz", "zip",
+    "zst",
+];
+const IMAGE_EXTS: &[&str] = &["avif", "bmp", "gif", "heic", "ico", "jpeg", "jpg", "png", "svg", "tif", "tiff", "webp"];
+#[cfg(windows)]
+const EXECUTABLE_EXTS: &[&str] = &["bat", "cmd", "com", "exe", "ps1"];
+
+/// Lowercased extension of `entry`, if any.
+fn extension(entry: &Entry) -> Option<String> {
+    entry.path.extension().map(|e| e.to_string_lossy().to_lowercase())
+}
+
+/// Classify `entry`. Symlinks win over everything else; the executable bit
+/// wins over the extension, as in `ls`.
+pub fn kind_of(entry: &Entry) -> FileKind {
+    if entry.is_symlink {
+        return FileKind::Symlink;
+    }
+    if entry.is_dir {
+        return FileKind::Directory;
+    }
+    let ext = extension(entry);
+    #[cfg(windows)]
+    let executable = ext.as_deref().is_some_and(|e| EXECUTABLE_EXTS.contains(&e));
+    #[cfg(not(windows))]
+    let executable = entry.unix_mode.is_some_and(|m| m & 0o111 != 0);
+    if executable {
+        return FileKind::Executable;
+    }
+    match ext.as_deref() {
+        Some(e) if ARCHIVE_EXTS.contains(&e) => FileKind::Archive,
+        Some(e) if IMAGE_EXTS.contains(&e) => FileKind::Image,
+        _ => FileKind::File,
+    }
+}
+
+/// Classify `entry` like `kind_of`, and when that finds a plain file, by
+/// its content (see `fs_op::filetype::detect_cached`): an image or archive
+/// without a telling extension is still coloured as one. Only for local
+/// entries.
+pub fn kind_by_content(entry: &Entry) -> FileKind {
+    use crate::fs_op::filetype::{detect_cached, TypeClass};
+    match kind_of(entry) {
+        FileKind::File => match detect_cached(entry).map(|t| t.class) {
+            Some(TypeClass::Archive) => FileKind::Archive,
+            Some(TypeClass::Image) => FileKind::Image,
+            _ => FileKind::File,
+        },
+        kind => kind,
+    }
+}
+
+/// Nerd Font glyph for `kind`.
+pub fn icon(kind: FileKind) -> &'static str {
+    match kind {
+        FileKind::Directory => "\u{f07b}",
+        FileKind::Symlink => "\u{f0c1}",
+        FileKind::Executable => "\u{f489}",
+        FileKind::Archive => "\u{f410}",
+        FileKind::Image => "\u{f1c5}",
+        FileKind::File => "\u{f15b}",
+    }
+}
+
+/// Row styles per kind, derived from the theme by `colors::set_from_theme`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FileTypeColors {
+    pub directory: Style,
+    pub symlink: Style,
+    pub executable: Style,
+    pub archive: Style,
+    pub image: Style,
+}
+
+impl Default for FileTypeColors {
+    fn default() -> Self {
+        FileTypeColors {
+            directory: Style::default().fg(Color::Blue).add_modifier(Modifier::BOLD),
+            symlink: Style::default().fg(Color::Cyan),
+            executable: Style::default().fg(Color::Green).add_modifier(Modifier::BOLD),
+            archive: Style::default().fg(Color::Red),
+            image: Style::default().fg(Color::Magenta),
+        }
+    }
+}
+
+impl FileTypeColors {
+    /// Style of `kind`; plain files keep the panel style.
+    pub fn style(&self, kind: FileKind) -> Style {
+        match kind {
+            FileKind::Directory => self.directory,
+            FileKind::Symlink => self.symlink,
+            FileKind::Executable => self.executable,
+            FileKind::Archive => self.archive,
+            FileKind::Image => self.image,
+            FileKind::File => Style::default(),
+        }
+    }
+
+    /// Apply `f` to every style (used to quantize colours).
+    pub fn map(&self, f: impl Fn(Style) -> Style) -> Self {
+        FileTypeColors {
+            directory: f(self.directory),
+            symlink: f(self.symlink),
+            executable: f(self.executable),
+            archive: f(self.archive),
+            image: f(self.image),
+        }
+    }
+}
+
+/// Rules imported from `LS_COLORS`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LsColors {
+    /// `di`, `ln`, `ex` and `fi` styles.
+    pub kinds: HashMap<FileKind, Style>,
+    /// `*.ext` styles, keyed by the lowercased extension.
+    pub extensions: HashMap<String, Style>,
+}
+
+impl LsColors {
+    /// Parse an `LS_COLORS` value such as `di=01;34:ln=36:*.tar=01;31`.
+    /// Unknown keys and unparseable codes are skipped.
+    pub fn parse(value: &str) -> Self {
+        let mut out = LsColors::default();
+        for rule in value.split(':') {
+            let Some((key, codes)) = rule.split_once('=') else { continue };
+            let Some(style) = parse_sgr(codes) else { continue };
+            if let Some(ext) = key.strip_prefix("*.") {
+                out.extensions.insert(ext.to_lowercase(), style);
+                continue;
+            }
+            let kind = match key {
+                "di" => FileKind::Directory,
+                "ln" => FileKind::Symlink,
+                "ex" => FileKind::Executable,
+                "fi" => FileKind::File,
+                _ => continue,
+            };
+            out.kinds.insert(kind, style);
+        }
+        out
+    }
+
+    /// Style `ls` would give `entry`: its kind's rule, else for plain
+    /// files (and archives and images) the rule for its extension.
+    pub fn style_for(&self, entry: &Entry, kind: FileKind) -> Option<Style> {
+        if matches!(kind, FileKind::Directory | FileKind::Symlink | FileKind::Executable) {
+            return self.kinds.get(&kind).copied();
+        }
+        extension(entry)
+            .and_then(|e| self.extensions.get(&e).copied())
+            .or_else(|| self.kinds.get(&FileKind::File).copied())
+    }
+}
+
+/// Turn SGR codes (`01;38;5;208`) into a style. `None` when a code is not
+/// understood, so values like `ln=target` are ignored.
+fn parse_sgr(codes: &str) -> Option<Style> {
+    let nums: Vec<u8> = codes.split(';').filter(|c| !c.is_empty()).map(|c| c.parse().ok()).collect::<Option<_>>()?;
+    let basic = |n: u8| match n {
+        0 => Color::Black,
+        1 => Color::Red,
+        2 => Color::Green,
+        3 => Color::Yellow,
+        4 => Color::Blue,
+        5 => Color::Magenta,
+        6 => Color::Cyan,
+        _ => Color::Gray,
+    };
+    let bright = |n: u8| match n {
+        0 => Color::DarkGray,
+        1 => Color::LightRed,
+        2 => Color::LightGreen,
+        3 => Color::LightYellow,
+        4 => Color::LightBlue,
+        5 => Color::LightMagenta,
+        6 => Color::LightCyan,
+        _ => Color::White,
+    };
+    let mut style = Style::default();
+    let mut it = nums.into_iter();
+    while let Some(n) = it.next() {
+        style = match n {
+            0 => Style::default(),
+            1 => style.add_modifier(Modifier::BOLD),
+            2 => style.add_modifier(Modifier::DIM),
+            3 => style.add_modifier(Modifier::ITALIC),
+            4 => style.add_modifier(Modifier::UNDERLINED),
+            5 => style.add_modifier(Modifier::SLOW_BLINK),
+            7 => style.add_modifier(Modifier::REVERSED),
+            30..=37 => style.fg(basic(n - 30)),
+            40..=47 => style.bg(basic(n - 40)),
+            90..=97 => style.fg(bright(n - 90)),
+            100..=107 => style.bg(bright(n - 100)),
+            38 | 48 => {
+                let color = match it.next()? {
+                    5 => Color::Indexed(it.next()?),
+                    2 => Color::Rgb(it.next()?, it.next()?, it.next()?),
+                    _ => return None,
+                };
+                if n == 38 { style.fg(color) } else { style.bg(color) }
+            }
+            _ => return None,
+        };
+    }
+    Some(style)
+}
+
+static LS_COLORS: Lazy<RwLock<Option<LsColors>>> = Lazy::new(|| RwLock::new(None));
+
+/// Use `rules` (or the theme only, for `None`) for listings from now on.
+pub fn set_ls_colors(rules: Option<LsColors>) {
+    *LS_COLORS.write().unwrap_or_else(|e| e.into_inner()) = rules;
+}
+
+/// Import the `LS_COLORS` environment variable, if set.
+pub fn import_ls_colors() {
+    set_ls_colors(std::env::var("LS_COLORS").ok().filter(|v| !v.is_empty()).map(|v| LsColors::parse(&v)));
+}
+
+/// Style of a listing row for `entry` of `kind`: the `LS_COLORS` rule if
+/// one applies, else the theme's colour for its kind.
+pub fn style_for(entry: &Entry, kind: FileKind, theme: &FileTypeColors) -> Style {
+    let ls = LS_COLORS.read().unwrap_or_else(|e| e.into_inner());
+    ls.as_ref().and_then(|rules| rules.style_for(entry, kind)).unwrap_or_else(|| theme.style(kind))
+}
+
+/// Colour laid over a listing row for its git status: yellow when
+/// modified, green when staged, cyan when untracked, red when conflicted
+/// and dark grey when ignored.
+#[cfg(feature = "git")]
+pub fn git_style(status: crate::integrations::git::GitFileStatus) -> Style {
+    use crate::integrations::git::GitFileStatus;
+    let color = match status {
+        GitFileStatus::Modified => Color::Yellow,
+        GitFileStatus::Staged => Color::Green,
+        GitFileStatus::Untracked => Color::Cyan,
+        GitFileStatus::Conflicted => Color::Red,
+        GitFileStatus::Ignored => Color::DarkGray,
+    };
+    Style::default().fg(color)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn file(name: &str, mode: u32) -> Entry {
+        let mut e = Entry::file(name, PathBuf::from("/tmp").join(name), 1, None);
+        e.unix_mode = Some(mode);
+        e
+    }
+
+    #[test]
+    fn classifies_entries() {
+        assert_eq!(kind_of(&Entry::directory("src", PathBuf::from("/tmp/src"), None)), FileKind::Directory);
+        let mut link = Entry::directory("link", PathBuf::from("/tmp/link"), None);
+        link.is_symlink = true;
+        assert_eq!(kind_of(&link), FileKind::Symlink);
+        assert_eq!(kind_of(&file("backup.TAR", 0o644)), FileKind::Archive);
+        assert_eq!(kind_of(&file("photo.jpg", 0o644)), FileKind::Image);
+        assert_eq!(kind_of(&file("notes.txt", 0o644)), FileKind::File);
+        #[cfg(unix)]
+        assert_eq!(kind_of(&file("run.zip", 0o755)), FileKind::Executable);
+    }
+
+    #[test]
+    fn parses_ls_colors() {
+        let ls = LsColors::parse("rs=0:di=01;34:ln=target:ex=38;5;208:*.tar=01;31:*.PNG=38;2;1;2;3:bogus");
+        assert_eq!(ls.kinds[&FileKind::Directory], Style::default().fg(Color::Blue).add_modifier(Modifier::BOLD));
+        assert_eq!(ls.kinds[&FileKind::Executable], Style::default().fg(Color::Indexed(208)));
+        assert!(!ls.kinds.contains_key(&FileKind::Symlink));
+        assert_eq!(ls.extensions["tar"], Style::default().fg(Color::Red).add_modifier(Modifier::BOLD));
+        assert_eq!(ls.extensions["png"], Style::default().fg(Color::Rgb(1, 2, 3)));
+
+        let tar = file("a.tar", 0o644);
+        assert_eq!(ls.style_for(&tar, kind_of(&tar)), Some(ls.extensions["tar"]));
+        let txt = file("a.txt", 0o644);
+        assert_eq!(ls.style_for(&txt, kind_of(&txt)), None);
+    }
+}
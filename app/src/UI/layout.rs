@@ -0,0 +1,153 @@
+//! Screen regions of the main view.
+//!
+//! `ScreenLayout::compute` is the one place that splits the terminal into
+//! menu bar, header, panels and footer. `ui::ui` draws into these rects and
+//! `runner::handlers::mouse` hit-tests against the same ones, so clicks
+//! land on what is drawn for every `PanelLayout`.
+
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+
+use crate::app::core::App;
+use crate::app::types::{OtherPanel, PanelLayout, Side};
+
+/// Width of the panel in the preview-dominant layout, in percent.
+const PREVIEW_DOMINANT_PANEL: u16 = 30;
+
+/// Width of the directory tree, in columns; at most half the screen.
+const TREE_WIDTH: u16 = 30;
+
+/// Where each part of the main view goes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ScreenLayout {
+    pub menu: Rect,
+    pub header: Rect,
+    /// Area shared by the panels and the preview, right of the tree.
+    pub main: Rect,
+    pub footer: Rect,
+    /// Left panel; empty when the layout, quick view or info panel hides it.
+    pub left: Rect,
+    /// Right panel; empty when the layout, quick view or info panel hides it.
+    pub right: Rect,
+    /// Preview of the active panel's selection: the preview pane when it is
+    /// toggled on, the other panel's place in quick view, and always in the
+    /// preview-dominant layout.
+    pub preview: Option<Rect>,
+    /// Info panel, in the inactive panel's place.
+    pub info: Option<Rect>,
+    /// Directory tree, left of everything else when it is shown.
+    pub tree: Option<Rect>,
+}
+
+impl ScreenLayout {
+    /// Lay out `area` for the current state of `app`.
+    pub fn compute(app: &App, area: Rect) -> Self {
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(1), Constraint::Length(3), Constraint::Min(0), Constraint::Length(2)])
+            .split(area);
+        let (tree, main) = if app.tree.visible {
+            let r = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([Constraint::Length(TREE_WIDTH.min(rows[2].width / 2)), Constraint::Min(0)])
+                .split(rows[2]);
+            (Some(r[0]), r[1])
+        } else {
+            (None, rows[2])
+        };
+        let hidden = Rect::new(main.x, main.y, 0, 0);
+        // A preview pane toggled with `p` takes the right `preview_width`
+        // percent of the main area; the panels share the rest.
+        let quick_view = app.other_panel_shows(OtherPanel::QuickView);
+        let info_view = app.other_panel_shows(OtherPanel::Info);
+        let (panels, side_preview) = if app.preview_visible && !quick_view && app.layout != PanelLayout::PreviewDominant {
+            let width = app.settings.preview_width.clamp(10, 90);
+            let r = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([Constraint::Percentage(100 - width), Constraint::Percentage(width)])
+                .split(main);
+            (r[0], Some(r[1]))
+        } else {
+            (main, None)
+        };
+        let halves = |direction| {
+            let r = Layout::default()
+                .direction(direction)
+                .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+                .split(panels);
+            (r[0], r[1])
+        };
+        // The active panel gets `rect`; the other one is hidden.
+        let only_active = |rect| match app.active {
+            Side::Left => (rect, hidden),
+            Side::Right => (hidden, rect),
+        };
+        let ((left, right), preview) = match app.layout {
+            PanelLayout::Horizontal => (halves(Direction::Horizontal), side_preview),
+            PanelLayout::Vertical => (halves(Direction::Vertical), side_preview),
+            PanelLayout::Single => (only_active(panels), side_preview),
+            PanelLayout::PreviewDominant => {
+                let r = Layout::default()
+                    .direction(Direction::Horizontal)
+                    .constraints([Constraint::Percentage(PREVIEW_DOMINANT_PANEL), Constraint::Min(0)])
+                    .split(main);
+                (only_active(r[0]), Some(r[1]))
+            }
+        };
+        // Quick view and the info panel take the inactive panel's place.
+        let (left, right, other) = match (quick_view || info_view, app.active) {
+            (true, Side::Left) => (left, hidden, Some(right)),
+            (true, Side::Right) => (hidden, right, Some(left)),
+            (false, _) => (left, right, None),
+        };
+        let (preview, info) = if quick_view { (other, None) } else { (preview, other.filter(|_| info_view)) };
+        ScreenLayout { menu: rows[0], header: rows[1], main, footer: rows[3], left, right, preview, info, tree }
+    }
+
+    /// Rect of the panel on `side`.
+    pub fn panel(&self, side: Side) -> Rect {
+        match side {
+            Side::Left => self.left,
+            Side::Right => self.right,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn layouts_place_the_panels() {
+        let opts = crate::app::StartOptions { start_dir: Some(std::env::temp_dir()), ..Default::default() };
+        let mut app = App::with_options(&opts).unwrap();
+        let area = Rect::new(0, 0, 80, 24);
+
+        let l = ScreenLayout::compute(&app, area);
+        assert_eq!((l.main.y, l.main.height), (4, 18));
+        assert_eq!((l.left.width, l.right.x), (40, 40));
+
+        app.layout = PanelLayout::Vertical;
+        let l = ScreenLayout::compute(&app, area);
+        assert_eq!((l.left.width, l.right.width, l.right.y), (80, 80, 13));
+
+        app.layout = PanelLayout::Single;
+        app.active = Side::Right;
+        let l = ScreenLayout::compute(&app, area);
+        assert_eq!((l.left.area(), l.right), (0, l.main));
+
+        app.layout = PanelLayout::PreviewDominant;
+        let l = ScreenLayout::compute(&app, area);
+        assert_eq!((l.right.width, l.preview.map(|p| p.x)), (24, Some(24)));
+
+        app.layout = PanelLayout::Horizontal;
+        app.preview_visible = true;
+        app.settings.preview_width = 25;
+        let l = ScreenLayout::compute(&app, area);
+        assert_eq!((l.left.width, l.right.width, l.preview.map(|p| (p.x, p.width))), (30, 30, Some((60, 20))));
+
+        app.preview_visible = false;
+        app.tree.visible = true;
+        let l = ScreenLayout::compute(&app, area);
+        assert_eq!((l.tree.map(|t| t.width), l.main.x, l.left.x, l.right.width), (Some(30), 30, 30, 25));
+    }
+}
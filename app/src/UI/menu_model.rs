@@ -11,9 +11,62 @@ pub enum MenuAction {
     Help,
     Quit,
     About,
+    ShowLog,
     Noop,
 }
 
+impl MenuAction {
+    /// Keymap action (see `app::settings::keymap::ACTIONS`) that does the
+    /// same as this menu entry, if any.
+    pub fn key_action(self) -> Option<&'static str> {
+        match self {
+            MenuAction::NewFile => Some("new_file"),
+            MenuAction::NewDir => Some("new_dir"),
+            MenuAction::Copy => Some("f5"),
+            MenuAction::Move => Some("f6"),
+            MenuAction::Sort => Some("sort"),
+            MenuAction::Help => Some("help"),
+            MenuAction::Quit => Some("quit"),
+            MenuAction::Settings | MenuAction::About | MenuAction::ShowLog | MenuAction::Noop => None,
+        }
+    }
+
+    /// First key bound to this entry's action in the active key table,
+    /// shown next to the entry (`F5`, `n`).
+    pub fn shortcut(self) -> Option<String> {
+        let action = self.key_action()?;
+        let keys = crate::app::settings::runtime_keybinds::get();
+        keys.chords(action).first().map(|k| k.to_string())
+    }
+}
+
+/// Mnemonic of each label: the char index of the first letter not already
+/// taken by an earlier label (case-insensitive), or `None` when every
+/// letter is taken.
+pub fn mnemonics<S: AsRef<str>>(labels: &[S]) -> Vec<Option<usize>> {
+    let mut taken: Vec<char> = Vec::new();
+    labels
+        .iter()
+        .map(|label| {
+            let (idx, c) = label
+                .as_ref()
+                .chars()
+                .enumerate()
+                .find(|(_, c)| c.is_alphanumeric() && !taken.contains(&c.to_ascii_lowercase()))?;
+            taken.push(c.to_ascii_lowercase());
+            Some(idx)
+        })
+        .collect()
+}
+
+/// Index of the label whose mnemonic is `c` (case-insensitive).
+pub fn mnemonic_match<S: AsRef<str>>(labels: &[S], c: char) -> Option<usize> {
+    let c = c.to_ascii_lowercase();
+    mnemonics(labels).into_iter().zip(labels).position(|(m, label)| {
+        m.and_then(|i| label.as_ref().chars().nth(i)).is_some_and(|m| m.to_ascii_lowercase() == c)
+    })
+}
+
 #[derive(Clone, Debug)]
 pub struct MenuItem { pub label: String, pub action: Option<MenuAction> }
 
@@ -30,7 +83,7 @@ pub struct MenuModel;
 impl MenuModel {
     pub fn default_model() -> (Vec<MenuTop>, ()) {
         let tops = vec![
-            MenuTop { label: "File".into(), action: None, submenu: Some(vec![MenuItem{label:"Open".into(), action: Some(MenuAction::Noop)}]) },
+            MenuTop { label: "File".into(), action: None, submenu: Some(vec![MenuItem{label:"Open".into(), action: Some(MenuAction::Noop)}, MenuItem{label:"Show log".into(), action: Some(MenuAction::ShowLog)}]) },
             MenuTop { label: "Copy".into(), action: Some(MenuAction::Copy), submenu: None },
             MenuTop { label: "Move".into(), action: Some(MenuAction::Move), submenu: None },
             MenuTop { label: "New".into(), action: None, submenu: Some(vec![MenuItem{label:"New File".into(), action: Some(MenuAction::NewFile)}, MenuItem{label:"New Dir".into(), action: Some(MenuAction::NewDir)}])},
@@ -67,3 +120,22 @@ impl MenuState {
 
     pub fn select_prev(&mut self, total: usize) { if let Some(i) = self.submenu_index { self.submenu_index = Some((i + total - 1) % total); } }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mnemonics_skip_taken_letters() {
+        let labels = crate::ui::menu::menu_labels();
+        let picked: Vec<char> = mnemonics(&labels)
+            .iter()
+            .zip(&labels)
+            .map(|(m, l)| l.chars().nth(m.unwrap()).unwrap())
+            .collect();
+        assert_eq!(picked, vec!['F', 'C', 'M', 'N', 'S', 'e', 'H']);
+        assert_eq!(mnemonic_match(&labels, 'E'), Some(5));
+        assert_eq!(mnemonic_match(&labels, 'x'), None);
+        assert_eq!(mnemonics(&["a", "A"]), vec![Some(0), None]);
+    }
+}
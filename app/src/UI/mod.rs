@@ -1,6 +1,8 @@
 pub mod ui_main;
+pub mod layout;
 pub mod ui_state;
 pub mod themes;
+pub mod filetype;
 pub mod menu;
 pub mod menu_model;
 pub mod colors;
@@ -11,10 +13,23 @@ pub mod panels;
 pub mod widgets {
     pub mod header;
     pub mod footer;
+    pub mod breadcrumbs;
+    pub mod info;
+    pub mod tree;
+    pub mod command_output;
+    pub mod log_view;
+    pub mod diagnostics;
+    pub mod disk_usage;
+    pub mod key_hints;
+    pub mod dialog;
+    pub mod editor;
+    pub mod user_menu;
+    pub mod warning_banner;
     pub mod main_menu;
     pub mod submenu;
     pub mod file_list;
     pub mod preview;
+    pub mod structured_preview;
     pub mod progress_bar;
     pub mod panel;
 }
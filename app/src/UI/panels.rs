@@ -1,22 +1,91 @@
+use std::borrow::Cow;
+
 use ratatui::{layout::Rect, widgets::{Block, Paragraph, Borders}, Frame};
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
 use crate::app::Panel;
 
+/// Columns between tab stops when previews expand tabs.
+pub const TAB_WIDTH: usize = 8;
+
+/// Terminal cells `s` takes: wide glyphs (CJK, most emoji) count two and
+/// combining marks none, matching how the buffer lays them out.
+pub fn display_width(s: &str) -> usize {
+    s.width()
+}
+
+/// The longest prefix of `s` that fits in `width` cells. Whole graphemes
+/// are kept, so a wide glyph or a letter with its combining marks is never
+/// split.
+pub fn take_width(s: &str, width: usize) -> &str {
+    let mut used = 0;
+    for (i, g) in s.grapheme_indices(true) {
+        used += g.width();
+        if used > width {
+            return &s[..i];
+        }
+    }
+    s
+}
+
+/// `s` cut to fit `width` cells, ending in `…` when anything was cut.
+pub fn truncate_to_width(s: &str, width: usize) -> Cow<'_, str> {
+    if s.width() <= width {
+        return Cow::Borrowed(s);
+    }
+    match width {
+        0 => Cow::Borrowed(""),
+        _ => Cow::Owned(format!("{}…", take_width(s, width - 1))),
+    }
+}
+
+/// `s` cut (see `truncate_to_width`) or padded with spaces to exactly
+/// `width` cells.
+pub fn pad_to_width(s: &str, width: usize) -> String {
+    let cut = truncate_to_width(s, width);
+    let pad = width.saturating_sub(cut.width());
+    format!("{}{:pad$}", cut, "", pad = pad)
+}
+
+/// `text` with each tab replaced by spaces up to the next `TAB_WIDTH` stop,
+/// counting cells rather than chars so wide glyphs keep columns aligned.
+/// The terminal buffer drops tabs, which would otherwise glue columns
+/// together.
+pub fn expand_tabs(text: &str) -> Cow<'_, str> {
+    if !text.contains('\t') {
+        return Cow::Borrowed(text);
+    }
+    let mut out = String::with_capacity(text.len() + 16);
+    let mut column = 0;
+    for g in text.graphemes(true) {
+        if g == "\t" {
+            let n = TAB_WIDTH - column % TAB_WIDTH;
+            out.extend(std::iter::repeat_n(' ', n));
+            column += n;
+        } else {
+            out.push_str(g);
+            column = if g.ends_with('\n') { 0 } else { column + g.width() };
+        }
+    }
+    Cow::Owned(out)
+}
+
+/// Synthetic rows shown before a panel's entries. The path itself is not
+/// a row: it is drawn as breadcrumbs in the panel border (see
+/// `widgets::breadcrumbs`).
 #[derive(Clone, Debug)]
 pub enum UiEntry {
-    Header(std::path::PathBuf),
     Parent(std::path::PathBuf),
 }
 
 impl UiEntry {
-    pub fn header(path: std::path::PathBuf) -> Self { UiEntry::Header(path) }
     pub fn parent(path: std::path::PathBuf) -> Self { UiEntry::Parent(path) }
 }
 
-pub fn is_entry_header(e: &UiEntry) -> bool { matches!(e, UiEntry::Header(_)) }
 pub fn is_entry_parent(e: &UiEntry) -> bool { matches!(e, UiEntry::Parent(_)) }
 
 pub fn draw_preview(f: &mut Frame, area: Rect, panel: &Panel) {
-    let txt = if panel.preview.is_empty() { "(no preview)".to_string() } else { panel.preview.clone() };
+    let txt = if panel.preview.is_empty() { "(no preview)".to_string() } else { expand_tabs(&panel.preview).into_owned() };
     let p = Paragraph::new(txt).block(Block::default().borders(Borders::ALL).title("Preview"));
     f.render_widget(p, area);
 }
@@ -35,5 +104,5 @@ use crate::app::Entry;
 pub fn format_entry_line(e: &Entry) -> String {
     let time = e.modified.as_ref().map(|d| d.format("%Y-%m-%d %H:%M").to_string()).unwrap_or_else(|| "-".into());
     let size = if e.is_dir { "<dir>".into() } else { format!("{}", e.size) };
-    format!("{}  {}  {}", e.name, size, time)
+    format!("{}  {}  {}", e.display_name(), size, time)
 }
@@ -1,5 +1,14 @@
+use std::path::PathBuf;
+
+use anyhow::Context;
 use ratatui::style::{Color, Style};
 use serde::Deserialize;
+
+use crate::app::settings::config_dirs::project_config_dir;
+
+/// Themes compiled into the binary. `default` keeps the terminal's own colours.
+pub const BUILTIN_THEMES: [&str; 3] = ["default", "dark", "light"];
+
 #[derive(Clone, Debug)]
 pub struct Theme { pub bg: Color, pub fg: Color, pub accent: Color, pub panels: Option<PanelTokens> }
 
@@ -22,6 +31,12 @@ pub struct PanelTokens {
     pub footer_fg: Option<Color>,
     pub scrollbar_thumb_bg: Option<Color>,
     pub scrollbar_track_bg: Option<Color>,
+    /// Listing colours per file kind (see `ui::filetype`).
+    pub dir_fg: Option<Color>,
+    pub symlink_fg: Option<Color>,
+    pub executable_fg: Option<Color>,
+    pub archive_fg: Option<Color>,
+    pub image_fg: Option<Color>,
 }
 
 #[derive(Deserialize)]
@@ -46,6 +61,11 @@ struct PanelsToml {
     scrollbar_track_bg: Option<String>,
     menu_bg: Option<String>,
     menu_fg: Option<String>,
+    dir_fg: Option<String>,
+    symlink_fg: Option<String>,
+    executable_fg: Option<String>,
+    archive_fg: Option<String>,
+    image_fg: Option<String>,
 }
 
 impl Theme {
@@ -76,6 +96,11 @@ impl Theme {
                     footer_fg: make(&pt.footer_fg),
                     scrollbar_thumb_bg: make(&pt.scrollbar_thumb_bg),
                     scrollbar_track_bg: make(&pt.scrollbar_track_bg),
+                    dir_fg: make(&pt.dir_fg),
+                    symlink_fg: make(&pt.symlink_fg),
+                    executable_fg: make(&pt.executable_fg),
+                    archive_fg: make(&pt.archive_fg),
+                    image_fg: make(&pt.image_fg),
                 });
             }
         }
@@ -90,6 +115,57 @@ impl Theme {
 
 fn parse_hex(s: &str) -> Color {
     let s = s.trim_start_matches('#');
-    if s.len() == 6 { if let (Ok(r),Ok(g),Ok(b)) = (u8::from_str_radix(&s[0..2],16), u8::from_str_radix(&s[2..4],16), u8::from_str_radix(&s[4..6],16)) { return Color::Rgb(r,g,b); } }
+    // Byte slicing below needs single-byte characters.
+    if s.len() == 6 && s.is_ascii() { if let (Ok(r),Ok(g),Ok(b)) = (u8::from_str_radix(&s[0..2],16), u8::from_str_radix(&s[2..4],16), u8::from_str_radix(&s[4..6],16)) { return Color::Rgb(r,g,b); } }
     Color::Reset
 }
+
+/// Directory scanned for user themes (`<config dir>/themes`).
+pub fn user_themes_dir() -> PathBuf {
+    project_config_dir().join("themes")
+}
+
+/// Whether `name` is one of the built-in themes.
+pub fn is_builtin(name: &str) -> bool {
+    BUILTIN_THEMES.contains(&name)
+}
+
+/// Names of the `*.toml` files in the user theme directory, sorted. Files
+/// named after a built-in theme are skipped so they cannot shadow it.
+pub fn user_theme_names() -> Vec<String> {
+    let Ok(rd) = std::fs::read_dir(user_themes_dir()) else { return Vec::new() };
+    let mut names: Vec<String> = rd
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.extension().is_some_and(|ext| ext == "toml"))
+        .filter_map(|p| p.file_stem().and_then(|s| s.to_str()).map(str::to_string))
+        .filter(|n| !is_builtin(n))
+        .collect();
+    names.sort();
+    names
+}
+
+/// Built-in themes followed by the user themes, as offered by the picker.
+pub fn theme_names() -> Vec<String> {
+    BUILTIN_THEMES.iter().map(|s| s.to_string()).chain(user_theme_names()).collect()
+}
+
+/// Look up the theme called `name`: a built-in or `<name>.toml` in the user
+/// theme directory. `Ok(None)` for `default` and unknown names; an error
+/// when the user file cannot be read or parsed.
+pub fn named(name: &str) -> anyhow::Result<Option<Theme>> {
+    match name {
+        "default" => Ok(None),
+        "dark" => Ok(Some(Theme::dark())),
+        "light" => Ok(Some(Theme::light())),
+        _ => {
+            let path = user_themes_dir().join(format!("{}.toml", name));
+            if !path.is_file() {
+                return Ok(None);
+            }
+            let text = std::fs::read_to_string(&path).with_context(|| format!("reading {}", path.display()))?;
+            let theme = Theme::from_toml(&text).with_context(|| format!("parsing {}", path.display()))?;
+            Ok(Some(theme))
+        }
+    }
+}
@@ -3,6 +3,7 @@ use ratatui::backend::Backend;
 use crate::ui::{UIState, Theme};
 use ratatui::Frame;
 use crate::app::core::App as CoreApp;
+use crate::app::Side;
 
 /// Draw one frame using the provided Terminal and view model.
 pub fn draw_frame<B: Backend>(terminal: &mut Terminal<B>, state: &UIState, theme: &Theme) -> std::io::Result<()> {
@@ -27,18 +28,14 @@ pub fn draw_frame<B: Backend>(terminal: &mut Terminal<B>, state: &UIState, theme
 
         crate::ui::widgets::main_menu::render(f, chunks[0], state.menu_selected, state.menu_focused);
         crate::ui::widgets::header::render(f, chunks[1], state, theme);
-        crate::ui::widgets::file_list::render(f, main[0], &state.left_list, state.left_selected, theme);
-        crate::ui::widgets::file_list::render(f, main[1], &state.right_list, state.right_selected, theme);
+        crate::ui::widgets::file_list::render_styled(f, main[0], &state.left_list, &state.left_styles, state.left_selected, theme);
+        crate::ui::widgets::file_list::render_styled(f, main[1], &state.right_list, &state.right_styles, state.right_selected, theme);
         crate::ui::widgets::footer::render(f, chunks[3], state, theme);
     }).map(|_| ())
 }
 
 /// Legacy UI entrypoint used by the runner: draw directly into a Frame
 pub fn ui(f: &mut Frame, app: &CoreApp) {
-    // Build a UIState view-model from the live Core App so the runner
-    // reflects the real runtime state (menu focus, selected index, preview, etc.).
-    let state = UIState::from_core(app);
-
     // Choose a reasonable Theme matching the app settings string so
     // `draw_frame` can render headers/file lists consistently with the
     // configured theme. Default to dark if an unknown value is present.
@@ -48,19 +45,158 @@ pub fn ui(f: &mut Frame, app: &CoreApp) {
     };
 
     let size = f.area();
-    // Make the top menu flexible so tiny terminals still get a content row.
-    let chunks = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([Constraint::Min(1), Constraint::Length(3), Constraint::Min(0), Constraint::Length(2)])
-        .split(size);
-    let main = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([Constraint::Percentage(55), Constraint::Percentage(45)])
-        .split(chunks[2]);
+    let layout = crate::ui::layout::ScreenLayout::compute(app, size);
+    let chunks = [layout.menu, layout.header, layout.main, layout.footer];
+    // Build a UIState view-model from the live Core App so the runner
+    // reflects the real runtime state (menu focus, selected index, preview,
+    // etc.). Only the rows that fit inside the bordered lists are built.
+    let state = UIState::from_core_window(app, layout.panel(app.active).height.saturating_sub(2) as usize);
 
     crate::ui::widgets::main_menu::render(f, chunks[0], state.menu_selected, state.menu_focused);
     crate::ui::widgets::header::render(f, chunks[1], &state, &theme);
-    crate::ui::widgets::file_list::render(f, main[0], &state.left_list, state.left_selected, &theme);
-    crate::ui::widgets::file_list::render(f, main[1], &state.right_list, state.right_selected, &theme);
+    // Each panel's path goes in its top border as breadcrumbs; the one
+    // picked with Left/Right is highlighted while the cursor is on them.
+    let crumbs = |side: Side| {
+        let panel = app.panel(side);
+        let highlight = (side == app.active && panel.selected == 0 && !app.menu_focused).then_some(panel.crumb);
+        let mut line = crate::ui::widgets::breadcrumbs::Breadcrumbs::new(&panel.cwd, layout.panel(side)).line(highlight, crate::ui::colors::current().panel_selected_style);
+        // Marked after the crumbs so clicks on them still line up.
+        if panel.is_flat() {
+            line.spans.push(ratatui::text::Span::raw("[flat] "));
+        }
+        if let Some(search) = panel.search_results() {
+            line.spans.push(ratatui::text::Span::raw(format!("[find: {}] ", search.options.query())));
+        }
+        if app.link.is_some() {
+            line.spans.push(ratatui::text::Span::raw("[linked] "));
+        }
+        line
+    };
+    crate::ui::widgets::file_list::render_titled(f, layout.left, &state.left_list, &state.left_styles, state.left_selected, crumbs(Side::Left), &theme);
+    crate::ui::widgets::file_list::render_titled(f, layout.right, &state.right_list, &state.right_styles, state.right_selected, crumbs(Side::Right), &theme);
+    crate::ui::widgets::file_list::render_marks(f, layout.left, &state.left_marks);
+    crate::ui::widgets::file_list::render_marks(f, layout.right, &state.right_marks);
+    crate::ui::widgets::file_list::render_matches(f, layout.left, &state.left_matches);
+    crate::ui::widgets::file_list::render_matches(f, layout.right, &state.right_matches);
+    crate::ui::widgets::file_list::render_scrollbar(f, layout.left, app.left.entries.len(), state.left_offset);
+    crate::ui::widgets::file_list::render_scrollbar(f, layout.right, app.right.entries.len(), state.right_offset);
+    if let Some(area) = layout.preview {
+        crate::ui::widgets::preview::render_panel(f, area, app.active_panel(), app.preview_focused);
+    }
+    if let Some(area) = layout.tree {
+        crate::ui::widgets::tree::render(f, area, app);
+    }
+    if let Some(area) = layout.info {
+        crate::ui::widgets::info::render(f, area, app);
+    }
     crate::ui::widgets::footer::render(f, chunks[3], &state, &theme);
+
+    // The integrated editor and command output views take over the panel
+    // area while they are open.
+    match &app.mode {
+        crate::app::Mode::Editor { editor } => crate::ui::widgets::editor::render(f, chunks[2], editor),
+        crate::app::Mode::CommandOutput { command, lines, scroll, status, running } => {
+            crate::ui::widgets::command_output::render(f, chunks[2], command, lines, *scroll, (!*running).then_some(*status))
+        }
+        crate::app::Mode::LogView { path, lines, scroll } => {
+            crate::ui::widgets::log_view::render(f, chunks[2], path.as_deref(), lines, *scroll)
+        }
+        crate::app::Mode::DiskUsage { selected, confirm_delete } => {
+            if let Some(usage) = &app.usage {
+                crate::ui::widgets::disk_usage::render(f, chunks[2], usage, *selected, *confirm_delete)
+            }
+        }
+        crate::app::Mode::UserMenu { entries, selected } => {
+            crate::ui::widgets::user_menu::render(f, chunks[2], entries, *selected)
+        }
+        crate::app::Mode::Conflict { path, selected, apply_all, in_use, src, dst, compare } => crate::ui::widgets::dialog::render_conflict(
+            f,
+            size,
+            path,
+            src.as_deref(),
+            dst.as_deref(),
+            in_use.as_deref(),
+            compare.as_deref(),
+            *selected,
+            *apply_all,
+        ),
+        crate::app::Mode::OperationError { path, error, retry, elevated, selected } => {
+            crate::ui::widgets::dialog::render_operation_error(f, size, path.as_deref(), error, retry.is_none(), elevated.is_some(), *selected)
+        }
+        crate::app::Mode::Confirm { msg, on_yes, selected } => {
+            let entry = crate::ui::widgets::dialog::confirm_entry(app, on_yes);
+            crate::ui::widgets::dialog::render_confirm(f, size, msg, entry, *selected)
+        }
+        crate::app::Mode::Message { title, content, buttons, selected, .. } => {
+            crate::ui::widgets::dialog::render_message(f, size, title, content, buttons, *selected)
+        }
+        crate::app::Mode::Input { prompt, buffer, kind } => {
+            use crate::app::core::path_jump::Candidate;
+            use crate::ui::widgets::dialog::{path_preview, render_input, InputExtras};
+            let preview = path_preview(app, *kind, buffer);
+            match kind {
+                crate::app::InputKind::ChangePath => {
+                    let hint = "Tab complete  Up/Down choose  Enter go  Esc cancel";
+                    let extras = InputExtras { candidates: &app.path_jump.candidates, selected: app.path_jump.selected, hint, preview };
+                    render_input(f, size, prompt, buffer, extras)
+                }
+                crate::app::InputKind::ChangeOwner => {
+                    let hint = "Tab complete  Up/Down choose  -R recursive  Enter apply  Esc cancel";
+                    let extras = InputExtras { candidates: &app.owners.candidates, selected: app.owners.selected, hint, preview };
+                    render_input(f, size, prompt, buffer, extras)
+                }
+                crate::app::InputKind::NewFile => {
+                    let templates: Vec<Candidate> = app.templates.names().into_iter().map(|text| Candidate { text, recent: false }).collect();
+                    let hint = "Up/Down template (none: empty file)  Enter create  Esc cancel";
+                    render_input(f, size, prompt, buffer, InputExtras { candidates: &templates, selected: app.templates.selected, hint, preview })
+                }
+                _ => render_input(f, size, prompt, buffer, InputExtras { preview, ..Default::default() }),
+            }
+        }
+        crate::app::Mode::Progress { title, processed, total, message, verify, .. } => {
+            let stats = app.op_meter.as_ref().map(|m| m.stats(std::time::Instant::now()));
+            crate::ui::widgets::dialog::render_progress(f, size, title, *processed, *total, message, *verify, stats.as_ref())
+        }
+        crate::app::Mode::Settings { selected } => {
+            crate::ui::widgets::dialog::render_settings(f, size, &app.settings, *selected)
+        }
+        crate::app::Mode::KeymapEditor { editor } => crate::ui::widgets::dialog::render_keymap_editor(f, size, editor),
+        crate::app::Mode::ThemePicker { themes, selected, error, .. } => {
+            crate::ui::widgets::dialog::render_theme_picker(f, size, themes, *selected, error.as_deref())
+        }
+        crate::app::Mode::MountPicker { mounts, selected } => {
+            crate::ui::widgets::dialog::render_mount_picker(f, size, mounts, *selected)
+        }
+        crate::app::Mode::EncodingPicker { selected } => {
+            crate::ui::widgets::dialog::render_encoding_picker(f, size, *selected, app.active_panel().preview_encoding)
+        }
+        crate::app::Mode::Summary { title, note, summary, scroll } => {
+            crate::ui::widgets::dialog::render_summary(f, size, title, note.as_deref(), summary, *scroll)
+        }
+        crate::app::Mode::SearchPicker { selected } => {
+            crate::ui::widgets::dialog::render_search_picker(f, size, &app.searches, *selected)
+        }
+        crate::app::Mode::Jobs { selected } => {
+            crate::ui::widgets::dialog::render_jobs(f, size, app.scheduler.jobs(), *selected, &app.settings, std::time::Instant::now())
+        }
+        crate::app::Mode::ContextMenu { title, options, selected, at, .. } => {
+            crate::ui::widgets::dialog::render_context_menu(f, size, title, options, *selected, *at)
+        }
+        _ => {}
+    }
+    if let Some(cmd) = app.command_line.as_ref().filter(|c| c.visible) {
+        crate::ui::command_line::render(f, chunks[3], cmd);
+    }
+    if let Some(text) = crate::ui::widgets::warning_banner::refresh_warning(app, std::time::Instant::now()) {
+        crate::ui::widgets::warning_banner::render(f, chunks[2], &text);
+    }
+    if app.diagnostics.visible {
+        let lines = app.diagnostics.lines(app.left.entries.len(), app.right.entries.len());
+        crate::ui::widgets::diagnostics::render(f, chunks[2], &lines);
+    }
+    if !app.pending_keys.is_empty() && matches!(app.mode, crate::app::Mode::Normal) {
+        let next = crate::app::settings::runtime_keybinds::get().continuations(&app.pending_keys.keys);
+        let lines = crate::ui::widgets::key_hints::hint_lines(&next);
+        crate::ui::widgets::key_hints::render(f, chunks[2], &app.pending_keys.keys, &lines);
+    }
 }
@@ -3,10 +3,37 @@ use serde::Serialize;
 /// Thin view model passed to renderers — keeps widget code testable and small.
 #[derive(Clone, Debug, Serialize, Default)]
 pub struct UIState {
+    /// Rows of the left panel. `from_core_window` only materializes the
+    /// rows that fit on screen, starting at entry `left_offset`.
     pub left_list: Vec<String>,
+    /// Index into `left_list` of the highlighted row; out of range when
+    /// the header or `..` row is selected.
     pub left_selected: usize,
+    /// Entry index of `left_list[0]`.
+    pub left_offset: usize,
+    /// Number of entries in the left panel (not just the materialized rows).
+    pub left_total: usize,
     pub right_list: Vec<String>,
+    /// Row styles of `left_list`/`right_list` by file type (see
+    /// `ui::filetype`); empty rows use the panel style.
+    #[serde(skip)]
+    pub left_styles: Vec<ratatui::style::Style>,
+    #[serde(skip)]
+    pub right_styles: Vec<ratatui::style::Style>,
+    /// Tag marks (name and colour) of each row of `left_list`/`right_list`.
+    #[serde(skip)]
+    pub left_marks: Vec<crate::app::core::tags::Marks>,
+    #[serde(skip)]
+    pub right_marks: Vec<crate::app::core::tags::Marks>,
+    /// Columns of the characters a search matched in each row of
+    /// `left_list`/`right_list`, highlighted in search results.
+    #[serde(skip)]
+    pub left_matches: Vec<Vec<u16>>,
+    #[serde(skip)]
+    pub right_matches: Vec<Vec<u16>>,
     pub right_selected: usize,
+    pub right_offset: usize,
+    pub right_total: usize,
     pub menu_selected: usize,
     pub menu_focused: bool,
     /// Whether the top menu is open and showing a submenu
@@ -15,6 +42,14 @@ pub struct UIState {
     pub menu_sub_selected: Option<usize>,
     pub preview_text: Option<String>,
     pub progress: u16,
+    /// Working directory of the active panel, shown in the header.
+    pub active_cwd: std::path::PathBuf,
+    /// Git branch of the active panel's work tree, shown in the header.
+    pub active_branch: Option<String>,
+    /// Recent outside change to a listed directory, shown in the footer.
+    pub change_hint: Option<String>,
+    /// Running-jobs spinner and clock, right-aligned in the footer.
+    pub status: Option<String>,
 }
 
 #[cfg(test)]
@@ -45,28 +80,171 @@ impl UIState {
         Self {
             left_list: vec!["left-a".into(), "left-b".into(), "left-c".into()],
             left_selected: 0,
+            left_offset: 0,
+            left_total: 3,
             right_list: vec!["right-x".into(), "right-y".into(), "right-z".into()],
+            left_styles: Vec::new(),
+            right_styles: Vec::new(),
+            left_marks: Vec::new(),
+            right_marks: Vec::new(),
+            left_matches: Vec::new(),
+            right_matches: Vec::new(),
             right_selected: 1,
+            right_offset: 0,
+            right_total: 3,
             menu_selected: 0,
             menu_focused: true,
             menu_open: false,
             menu_sub_selected: None,
             preview_text: Some("preview".into()),
             progress: 25,
+            active_cwd: std::path::PathBuf::from("/"),
+            active_branch: None,
+            change_hint: None,
+            status: None,
         }
     }
 
     
 
+    /// Entry names (with their icon when `icons` is set) followed by any
+    /// plugin column text for `entries[range]`, their file-type styles, their
+    /// tag marks and the highlighted row within them. With plugin columns the
+    /// names are padded to the widest one, in cells, so the columns line up
+    /// whatever glyphs the names use.
+    fn panel_rows(
+        panel: &crate::app::core::panel::Panel,
+        range: std::ops::Range<usize>,
+        icons: bool,
+        tags: &crate::app::core::tags::Tags,
+    ) -> (Vec<String>, Vec<ratatui::style::Style>, Vec<crate::app::core::tags::Marks>, usize) {
+        if let Some(pending) = &panel.loading {
+            let secs = pending.started.elapsed().as_secs();
+            let row = format!("Loading {}... {}s (Esc cancels)", pending.path.display(), secs);
+            return (vec![row], Vec::new(), Vec::new(), 1);
+        }
+        let start = range.start;
+        let kinds = crate::ui::colors::current().filetype;
+        let entries = &panel.entries[range];
+        let local = panel.vfs.is_local();
+        let file_kinds: Vec<_> = entries
+            .iter()
+            .map(|e| match local {
+                true => crate::ui::filetype::kind_by_content(e),
+                false => crate::ui::filetype::kind_of(e),
+            })
+            .collect();
+        let names: Vec<String> = entries
+            .iter()
+            .zip(&file_kinds)
+            .map(|(e, &kind)| {
+                if icons {
+                    format!("{} {}", crate::ui::filetype::icon(kind), e.display_name())
+                } else {
+                    e.display_name().into_owned()
+                }
+            })
+            .collect();
+        let columns = |i: usize| panel.plugin_columns.get(i).filter(|c| !c.is_empty());
+        let name_width = names
+            .iter()
+            .zip(start..)
+            .filter(|&(_, i)| columns(i).is_some())
+            .map(|(n, _)| crate::ui::panels::display_width(n))
+            .max()
+            .unwrap_or(0);
+        let rows: Vec<String> = names
+            .into_iter()
+            .zip(start..)
+            .map(|(name, i)| match columns(i) {
+                Some(cols) => format!("{}  {}", crate::ui::panels::pad_to_width(&name, name_width), cols),
+                None => name,
+            })
+            .collect();
+        let styles = entries
+            .iter()
+            .zip(file_kinds)
+            .map(|(e, kind)| {
+                let style = crate::ui::filetype::style_for(e, kind, &kinds);
+                #[cfg(feature = "git")]
+                let style = match panel.git.as_ref().and_then(|g| g.status_of(&e.path)) {
+                    Some(status) => style.patch(crate::ui::filetype::git_style(status)),
+                    None => style,
+                };
+                style
+            })
+            .collect();
+        let marks = entries.iter().map(|e| tags.marks(&e.path)).collect();
+        let highlight = panel
+            .selected_entry_index()
+            .and_then(|i| i.checked_sub(start))
+            .unwrap_or(rows.len());
+        (rows, styles, marks, highlight)
+    }
+
+    /// Columns, within each row of `entries[range]`, of the characters of
+    /// the file name the panel's search matched; empty outside search
+    /// results.
+    fn match_columns(panel: &crate::app::core::panel::Panel, range: std::ops::Range<usize>, icons: bool) -> Vec<Vec<u16>> {
+        let Some(search) = panel.search_results().filter(|_| panel.loading.is_none()) else {
+            return Vec::new();
+        };
+        let width = |c: char| crate::ui::panels::display_width(c.encode_utf8(&mut [0; 4]));
+        panel.entries[range]
+            .iter()
+            .map(|e| {
+                let display = e.display_name();
+                let split = display.rfind(std::path::MAIN_SEPARATOR).map_or(0, |i| i + 1);
+                let (dir, name) = display.split_at(split);
+                let Some(matched) = search.matched(name) else {
+                    return Vec::new();
+                };
+                let icon = if icons { crate::ui::panels::display_width(crate::ui::filetype::icon(crate::ui::filetype::kind_by_content(e))) + 1 } else { 0 };
+                let mut column = icon + crate::ui::panels::display_width(dir);
+                let mut columns = Vec::new();
+                for (i, c) in name.chars().enumerate() {
+                    if matched.contains(&i) {
+                        columns.push(column as u16);
+                    }
+                    column += width(c);
+                }
+                columns
+            })
+            .collect()
+    }
+
     /// Build a UIState view-model from the core App so UI rendering shows real data.
     pub fn from_core(app: &crate::app::core::App) -> Self {
-        let left_list = app.left.entries.iter().map(|e| e.name.clone()).collect();
-        let right_list = app.right.entries.iter().map(|e| e.name.clone()).collect();
+        Self::from_core_window(app, usize::MAX)
+    }
+
+    /// Like `from_core` but only materializes the `rows` entries per panel
+    /// that a list of that height can show (see `Panel::visible_range`), so
+    /// drawing a frame costs the same for ten entries or a million.
+    pub fn from_core_window(app: &crate::app::core::App, rows: usize) -> Self {
+        let left_range = app.left.visible_range(rows);
+        let right_range = app.right.visible_range(rows);
+        let (left_offset, right_offset) = (left_range.start, right_range.start);
+        let icons = app.settings.icons;
+        let left_matches = Self::match_columns(&app.left, left_range.clone(), icons);
+        let right_matches = Self::match_columns(&app.right, right_range.clone(), icons);
+        let (left_list, left_styles, left_marks, left_selected) = Self::panel_rows(&app.left, left_range, icons, &app.tags);
+        let (right_list, right_styles, right_marks, right_selected) = Self::panel_rows(&app.right, right_range.clone(), icons, &app.tags);
         Self {
             left_list,
-            left_selected: app.left.selected,
+            left_selected,
+            left_offset,
+            left_total: app.left.entries.len(),
             right_list,
-            right_selected: app.right.selected,
+            left_styles,
+            right_styles,
+            left_marks,
+            right_marks,
+            left_matches,
+            right_matches,
+            right_selected,
+            right_offset,
+            right_total: app.right.entries.len(),
             preview_text: {
                 let lp = app.left.preview.clone();
                 if !lp.is_empty() {
@@ -81,6 +259,16 @@ impl UIState {
             menu_focused: app.menu_focused,
             menu_open: app.menu_state.open,
             menu_sub_selected: app.menu_state.submenu_index,
+            active_cwd: app.active_panel().cwd.clone(),
+            #[cfg(feature = "git")]
+            active_branch: app.active_panel().git.as_ref().and_then(|g| g.branch.clone()),
+            #[cfg(not(feature = "git"))]
+            active_branch: None,
+            change_hint: [(&app.left, "Left"), (&app.right, "Right")]
+                .into_iter()
+                .filter_map(|(p, name)| p.external_change_hint().map(|h| format!("{}: {}", name, h)))
+                .reduce(|a, b| format!("{} | {}", a, b)),
+            status: crate::ui::widgets::footer::status_text(app, std::time::Instant::now()),
         }
     }
 }
@@ -0,0 +1,138 @@
+//! Breadcrumb path bar drawn in the top border of each panel.
+//!
+//! `Breadcrumbs::new` lays the segments out once: `ui::ui` draws the panel
+//! title from it and `runner::handlers::mouse` hit-tests clicks against the
+//! same columns, so a click always lands on the segment drawn there.
+
+use std::path::{Path, PathBuf};
+
+use ratatui::{layout::Rect, style::{Modifier, Style}, text::{Line, Span}};
+
+use crate::ui::panels::{display_width, take_width};
+
+/// Between two segments.
+const SEPARATOR: &str = " › ";
+/// In place of the leading segments left out to fit.
+const ELLIPSIS: &str = "…";
+
+/// One ancestor of the panel directory.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Crumb {
+    pub label: String,
+    pub path: PathBuf,
+    /// Column of the label's first cell.
+    pub x: u16,
+}
+
+impl Crumb {
+    fn width(&self) -> u16 {
+        display_width(&self.label) as u16
+    }
+}
+
+/// Segments of a panel's path as laid out in its top border.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Breadcrumbs {
+    /// From the outermost shown ancestor down to the directory itself.
+    pub crumbs: Vec<Crumb>,
+    /// Whether leading segments were left out to fit.
+    pub elided: bool,
+}
+
+impl Breadcrumbs {
+    /// Lay out `dir` in the top border of the panel at `area`, between the
+    /// corners. Leading segments are left out (shown as `…`) until the rest
+    /// fits; the last one is cut short if it does not fit on its own.
+    pub fn new(dir: &Path, area: Rect) -> Self {
+        let mut labels: Vec<(String, PathBuf)> = dir
+            .ancestors()
+            .map(|a| {
+                let label = a.file_name().map_or_else(|| a.to_string_lossy(), |n| n.to_string_lossy()).into_owned();
+                (label, a.to_path_buf())
+            })
+            .collect();
+        labels.reverse();
+        // Corners and the padding spaces either side of the title.
+        let avail = area.width.saturating_sub(4) as usize;
+        let sep = display_width(SEPARATOR);
+        let total = |labels: &[(String, PathBuf)], elided: bool| {
+            let text: usize = labels.iter().map(|(l, _)| display_width(l)).sum();
+            text + sep * labels.len().saturating_sub(1) + if elided { display_width(ELLIPSIS) + sep } else { 0 }
+        };
+        let mut elided = false;
+        while labels.len() > 1 && total(&labels, elided) > avail {
+            labels.remove(0);
+            elided = true;
+        }
+        if let [(label, _)] = labels.as_mut_slice() {
+            let room = avail.saturating_sub(total(&[], elided));
+            *label = take_width(label, room).to_string();
+        }
+
+        let mut x = area.x + 2;
+        if elided {
+            x += (display_width(ELLIPSIS) + sep) as u16;
+        }
+        let mut crumbs = Vec::with_capacity(labels.len());
+        for (label, path) in labels {
+            let crumb = Crumb { label, path, x };
+            x += crumb.width() + sep as u16;
+            crumbs.push(crumb);
+        }
+        Breadcrumbs { crumbs, elided }
+    }
+
+    /// The segment drawn at `column`, if any.
+    pub fn at(&self, column: u16) -> Option<&Crumb> {
+        self.crumbs.iter().find(|c| column >= c.x && column < c.x + c.width())
+    }
+
+    /// Title line for the panel block. `highlight` is the segment picked
+    /// with the keyboard, in levels above the directory (0 is the directory
+    /// itself).
+    pub fn line(&self, highlight: Option<usize>, selected_style: Style) -> Line<'static> {
+        let mut spans = vec![Span::raw(" ")];
+        if self.elided {
+            spans.push(Span::raw(format!("{}{}", ELLIPSIS, SEPARATOR)));
+        }
+        let last = self.crumbs.len().saturating_sub(1);
+        for (i, crumb) in self.crumbs.iter().enumerate() {
+            if i > 0 {
+                spans.push(Span::raw(SEPARATOR));
+            }
+            let style = if highlight == Some(last - i) {
+                selected_style
+            } else if i == last {
+                Style::default().add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+            spans.push(Span::styled(crumb.label.clone(), style));
+        }
+        spans.push(Span::raw(" "));
+        Line::from(spans)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lays_out_and_elides_segments() {
+        let dir = Path::new("/home/user/projects");
+        let bar = Breadcrumbs::new(dir, Rect::new(10, 0, 40, 5));
+        let labels: Vec<&str> = bar.crumbs.iter().map(|c| c.label.as_str()).collect();
+        assert_eq!((labels, bar.elided), (vec!["/", "home", "user", "projects"], false));
+        assert_eq!(bar.crumbs[1].x, 10 + 2 + 1 + 3);
+        assert_eq!(bar.at(17).map(|c| c.path.as_path()), Some(Path::new("/home")));
+        assert_eq!(bar.at(20), None);
+        let text: String = bar.line(None, Style::default()).spans.iter().map(|s| s.content.as_ref()).collect();
+        assert_eq!(text, " / › home › user › projects ");
+
+        let narrow = Breadcrumbs::new(dir, Rect::new(0, 0, 20, 5));
+        let labels: Vec<&str> = narrow.crumbs.iter().map(|c| c.label.as_str()).collect();
+        assert_eq!((labels, narrow.elided), (vec!["projects"], true));
+        assert_eq!(narrow.crumbs[0].x, 2 + 4);
+    }
+}
@@ -0,0 +1,25 @@
+use ratatui::{layout::Rect, widgets::{Block, Borders, Clear, Paragraph}, Frame};
+use crate::ui::colors::current as current_colors;
+
+/// Render captured command output starting at line `scroll`. `status` is
+/// `None` while the command still runs.
+pub fn render(f: &mut Frame, area: Rect, command: &str, lines: &[String], scroll: usize, status: Option<Option<i32>>) {
+    let colors = current_colors();
+    f.render_widget(Clear, area);
+    let state = match status {
+        Some(Some(code)) => format!("exit {}", code),
+        Some(None) => "killed".to_string(),
+        None => "running, Esc stops it".to_string(),
+    };
+    let title = format!(" $ {} ({}) ", command, state);
+    let height = area.height.saturating_sub(2) as usize;
+    let text = if status.is_none() {
+        "(running...)".to_string()
+    } else if lines.is_empty() {
+        "(no output)".to_string()
+    } else {
+        lines.iter().skip(scroll).take(height).cloned().collect::<Vec<_>>().join("\n")
+    };
+    let p = Paragraph::new(text).block(Block::default().borders(Borders::ALL).title(title).style(colors.preview_block_style));
+    f.render_widget(p, area);
+}
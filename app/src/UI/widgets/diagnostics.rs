@@ -0,0 +1,14 @@
+use ratatui::{layout::Rect, widgets::{Block, Borders, Clear, Paragraph}, Frame};
+use crate::ui::colors::current as current_colors;
+
+/// Render the diagnostics overlay in the top-right corner of `area`.
+pub fn render(f: &mut Frame, area: Rect, lines: &[String]) {
+    let colors = current_colors();
+    let width = (lines.iter().map(|l| l.chars().count()).max().unwrap_or(0) as u16 + 2).min(area.width);
+    let height = (lines.len() as u16 + 2).min(area.height);
+    let rect = Rect::new(area.x + area.width - width, area.y, width, height);
+    f.render_widget(Clear, rect);
+    let p = Paragraph::new(lines.join("\n"))
+        .block(Block::default().borders(Borders::ALL).title(" Diagnostics ").style(colors.preview_block_style));
+    f.render_widget(p, rect);
+}
@@ -0,0 +1,795 @@
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+use std::path::Path;
+
+use crate::app::core::App;
+use crate::app::types::{Action, Entry, InputKind, Mode};
+use crate::ui::colors::current as current_colors;
+use crate::ui::menu_model::mnemonics;
+use crate::ui::modal::centered_rect;
+use crate::ui::widgets::main_menu::mnemonic_spans;
+
+const CONFLICT_BUTTONS: [&str; 3] = ["Overwrite", "Skip", "Cancel"];
+const CONFIRM_BUTTONS: [&str; 2] = ["Yes", "No"];
+const JOB_ERROR_BUTTONS: [&str; 3] = ["Retry", "Skip", "Abort"];
+const ACTION_ERROR_BUTTONS: [&str; 2] = ["Retry", "Abort"];
+const ELEVATED_ERROR_BUTTONS: [&str; 3] = ["Retry", "Retry as root", "Abort"];
+const CONFLICT_WIDTH: u16 = 72;
+const CONFIRM_WIDTH: u16 = 60;
+
+fn label_style() -> Style {
+    Style::default().add_modifier(Modifier::DIM)
+}
+
+/// Style for the side of a comparison that "wins" (newer, larger).
+fn winner_style() -> Style {
+    Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)
+}
+
+fn warning_style() -> Style {
+    Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
+}
+
+/// Button row with the selected button highlighted and each button's
+/// mnemonic underlined.
+fn buttons_line<'a>(buttons: impl IntoIterator<Item = &'a str>, selected: usize) -> Line<'a> {
+    let colors = current_colors();
+    let buttons: Vec<&str> = buttons.into_iter().collect();
+    let mut spans = Vec::new();
+    for (i, (b, m)) in buttons.iter().zip(mnemonics(&buttons)).enumerate() {
+        if i > 0 {
+            spans.push(Span::raw("  "));
+        }
+        if i == selected {
+            let style = colors.panel_selected_style.add_modifier(Modifier::BOLD);
+            spans.push(Span::styled("[", style));
+            spans.extend(mnemonic_spans(b, m, style));
+            spans.push(Span::styled("]", style));
+        } else {
+            spans.push(Span::raw(" "));
+            spans.extend(mnemonic_spans(b, m, Style::default()));
+            spans.push(Span::raw(" "));
+        }
+    }
+    Line::from(spans)
+}
+
+fn size_text(e: &Entry) -> String {
+    if e.is_dir { "<dir>".to_string() } else { format!("{} B", e.size) }
+}
+
+fn modified_text(e: &Entry) -> String {
+    e.modified.map(|m| m.format("%Y-%m-%d %H:%M:%S").to_string()).unwrap_or_else(|| "-".to_string())
+}
+
+fn perms_text(e: &Entry) -> String {
+    match e.unix_mode {
+        Some(_) => crate::fs_op::permissions::format_unix_rwx(e.unix_mode.map(|m| m & 0o777)),
+        None if e.attributes.is_some() => e.attributes.map(|a| a.letters()).unwrap_or_default(),
+        None => match e.can_write {
+            Some(false) => "read-only".to_string(),
+            _ => "-".to_string(),
+        },
+    }
+}
+
+fn owner_text(e: &Entry) -> String {
+    match (&e.owner, &e.group) {
+        (Some(o), Some(g)) => format!("{}:{}", o, g),
+        (Some(o), None) => o.clone(),
+        _ => "-".to_string(),
+    }
+}
+
+/// Attribute lines (size, modified, permissions, owner) for a single entry.
+pub fn entry_detail_lines(e: &Entry) -> Vec<Line<'static>> {
+    [("Size", size_text(e)), ("Modified", modified_text(e)), ("Permissions", perms_text(e)), ("Owner", owner_text(e))]
+        .into_iter()
+        .map(|(label, value)| Line::from(vec![Span::styled(format!("{:<12}", label), label_style()), Span::raw(value)]))
+        .collect()
+}
+
+/// Two-column comparison of the source and existing target. The newer
+/// modification time and the larger size are highlighted, and differing
+/// permissions/owners are shown in the warning style.
+pub fn comparison_lines(src: Option<&Entry>, dst: Option<&Entry>, col: usize) -> Vec<Line<'static>> {
+    let cell = |s: String, style: Style| Span::styled(format!("{:<col$}", s, col = col), style);
+    let mut lines = vec![Line::from(vec![
+        Span::styled(format!("{:<12}", ""), label_style()),
+        cell("Source".to_string(), Style::default().add_modifier(Modifier::UNDERLINED)),
+        cell("Target".to_string(), Style::default().add_modifier(Modifier::UNDERLINED)),
+    ])];
+    let text = |e: Option<&Entry>, f: fn(&Entry) -> String| e.map(f).unwrap_or_else(|| "?".to_string());
+
+    // Which side wins each ordered comparison: Some(true) = source.
+    let newer = match (src.and_then(|e| e.modified), dst.and_then(|e| e.modified)) {
+        (Some(a), Some(b)) if a != b => Some(a > b),
+        _ => None,
+    };
+    let larger = match (src, dst) {
+        (Some(a), Some(b)) if !a.is_dir && !b.is_dir && a.size != b.size => Some(a.size > b.size),
+        _ => None,
+    };
+    let ordered = |label: &str, f: fn(&Entry) -> String, winner: Option<bool>| {
+        let pick = |is_src: bool| if winner == Some(is_src) { winner_style() } else { Style::default() };
+        Line::from(vec![
+            Span::styled(format!("{:<12}", label), label_style()),
+            cell(text(src, f), pick(true)),
+            cell(text(dst, f), pick(false)),
+        ])
+    };
+    let unordered = |label: &str, f: fn(&Entry) -> String| {
+        let (a, b) = (text(src, f), text(dst, f));
+        let style = if a != b { warning_style() } else { Style::default() };
+        Line::from(vec![Span::styled(format!("{:<12}", label), label_style()), cell(a, style), cell(b, style)])
+    };
+    lines.push(ordered("Size", size_text, larger));
+    lines.push(ordered("Modified", modified_text, newer));
+    lines.push(unordered("Permissions", perms_text));
+    lines.push(unordered("Owner", owner_text));
+    lines
+}
+
+/// Where `draw_box` puts a box of `lines` at most `width` wide.
+fn box_rect(area: Rect, lines: &[Line], width: u16) -> Rect {
+    // Account for wrapping so long paths and messages stay visible.
+    let inner = width.min(area.width).saturating_sub(2).max(1) as usize;
+    let rows: usize = lines.iter().map(|l| l.width().div_ceil(inner).max(1)).sum();
+    centered_rect(area, width, rows as u16 + 2)
+}
+
+fn draw_box(f: &mut Frame, area: Rect, title: &str, lines: Vec<Line>, width: u16) {
+    let colors = current_colors();
+    let rect = box_rect(area, &lines, width);
+    f.render_widget(Clear, rect);
+    let p = Paragraph::new(lines)
+        .wrap(Wrap { trim: false })
+        .block(Block::default().borders(Borders::ALL).title(format!(" {} ", title)).style(colors.preview_block_style));
+    f.render_widget(p, rect);
+}
+
+/// Render the overwrite conflict dialog for `path`, with the outcome of
+/// comparing the contents (`compare`) once the user asked for it.
+#[allow(clippy::too_many_arguments)]
+pub fn render_conflict(
+    f: &mut Frame,
+    area: Rect,
+    path: &Path,
+    src: Option<&Entry>,
+    dst: Option<&Entry>,
+    in_use: Option<&str>,
+    compare: Option<&str>,
+    selected: usize,
+    apply_all: bool,
+) {
+    let lines = conflict_lines(path, src, dst, in_use, compare, selected, apply_all);
+    draw_box(f, area, "File exists", lines, CONFLICT_WIDTH);
+}
+
+fn conflict_lines(path: &Path, src: Option<&Entry>, dst: Option<&Entry>, in_use: Option<&str>, compare: Option<&str>, selected: usize, apply_all: bool) -> Vec<Line<'static>> {
+    let mut lines = vec![
+        Line::from(vec![Span::styled("Target exists: ", label_style()), Span::raw(path.display().to_string())]),
+        Line::default(),
+    ];
+    lines.extend(comparison_lines(src, dst, 24));
+    if let Some(compare) = compare {
+        lines.push(Line::default());
+        lines.extend(compare.lines().map(|l| Line::from(l.to_string())));
+    }
+    if let Some(warning) = in_use {
+        lines.push(Line::default());
+        lines.extend(warning.lines().map(|l| Line::styled(l.to_string(), warning_style())));
+    }
+    lines.push(Line::default());
+    let mut toggles = format!("[{}] apply to all (a)", if apply_all { "x" } else { " " });
+    if compare.is_none() {
+        toggles.push_str("   compare contents (d)");
+    }
+    lines.push(Line::from(toggles));
+    lines.push(buttons_line(CONFLICT_BUTTONS, selected));
+    lines
+}
+
+/// Buttons of `Mode::OperationError`: Skip only makes sense for an item
+/// of a running job, "Retry as root" when the helper can run the operation.
+pub fn operation_error_buttons(job: bool, elevated: bool) -> &'static [&'static str] {
+    match (job, elevated) {
+        (true, _) => &JOB_ERROR_BUTTONS,
+        (false, true) => &ELEVATED_ERROR_BUTTONS,
+        (false, false) => &ACTION_ERROR_BUTTONS,
+    }
+}
+
+/// Render the error dialog of a recoverable failure on `path`.
+pub fn render_operation_error(f: &mut Frame, area: Rect, path: Option<&Path>, error: &str, job: bool, elevated: bool, selected: usize) {
+    draw_box(f, area, "Error", operation_error_lines(path, error, job, elevated, selected), CONFLICT_WIDTH);
+}
+
+fn operation_error_lines<'a>(path: Option<&Path>, error: &'a str, job: bool, elevated: bool, selected: usize) -> Vec<Line<'a>> {
+    let mut lines = Vec::new();
+    if let Some(p) = path {
+        lines.push(Line::from(vec![Span::styled("Failed: ", label_style()), Span::raw(p.display().to_string())]));
+        lines.push(Line::default());
+    }
+    lines.extend(error.lines().map(|l| Line::styled(l, warning_style())));
+    lines.push(Line::default());
+    let hint = match (job, elevated) {
+        (true, _) => "Fix the cause and retry, skip this item, or abort the job.",
+        (false, true) => "Fix the cause and retry, retry as root (sudo), or abort.",
+        (false, false) => "Fix the cause and retry, or abort.",
+    };
+    lines.push(Line::styled(hint, label_style()));
+    lines.push(buttons_line(operation_error_buttons(job, elevated).iter().copied(), selected));
+    lines
+}
+
+/// Render a yes/no confirmation. `entry` adds its attributes below the
+/// message (for example the file about to be deleted).
+pub fn render_confirm(f: &mut Frame, area: Rect, msg: &str, entry: Option<&Entry>, selected: usize) {
+    draw_box(f, area, "Confirm", confirm_lines(msg, entry, selected), CONFIRM_WIDTH);
+}
+
+fn confirm_lines<'a>(msg: &'a str, entry: Option<&Entry>, selected: usize) -> Vec<Line<'a>> {
+    let mut lines: Vec<Line> = msg.lines().map(|l| Line::from(l.to_string())).collect();
+    if let Some(e) = entry {
+        lines.push(Line::default());
+        lines.extend(entry_detail_lines(e));
+    }
+    lines.push(Line::default());
+    lines.push(buttons_line(CONFIRM_BUTTONS, selected));
+    lines
+}
+
+/// Entry a confirmation shows the attributes of: the one a delete would
+/// remove.
+pub fn confirm_entry<'a>(app: &'a App, on_yes: &Action) -> Option<&'a Entry> {
+    match on_yes {
+        Action::DeleteSelected => app.active_panel().selected_entry(),
+        _ => None,
+    }
+}
+
+/// Width of the bars in the progress dialog, in cells.
+const PROGRESS_BAR_WIDTH: usize = 30;
+
+/// `label [█████░░░░░] done/total`.
+fn progress_bar_line(label: &str, done: usize, total: usize) -> Line<'static> {
+    bar_line(label, done as u64, total as u64, format!("{}/{}", done, total))
+}
+
+/// A labelled bar filled `done` of `total`, followed by `text`.
+fn bar_line(label: &str, done: u64, total: u64, text: String) -> Line<'static> {
+    let filled = (done.min(total) * PROGRESS_BAR_WIDTH as u64).checked_div(total).unwrap_or(0) as usize;
+    Line::from(vec![
+        Span::styled(format!("{:<10}", label), label_style()),
+        Span::raw(format!("[{}{}] {}", "█".repeat(filled), "░".repeat(PROGRESS_BAR_WIDTH - filled), text)),
+    ])
+}
+
+/// `duration` as `m:ss`, or `h:mm:ss` from an hour on.
+pub fn format_eta(duration: std::time::Duration) -> String {
+    let secs = duration.as_secs();
+    match secs / 3600 {
+        0 => format!("{}:{:02}", secs / 60, secs % 60),
+        h => format!("{}:{:02}:{:02}", h, secs / 60 % 60, secs % 60),
+    }
+}
+
+/// Lines for the byte counts of a copy or move: the current file, the
+/// bytes overall and the speed with the time left.
+fn meter_lines(stats: &crate::fs_op::meter::MeterStats) -> Vec<Line<'static>> {
+    use crate::fs_op::mounts::format_bytes;
+    let eta = |eta: Option<std::time::Duration>| eta.map(|d| format!("  ETA {}", format_eta(d))).unwrap_or_default();
+    let mut lines = Vec::new();
+    if let Some((file, done, total)) = &stats.file {
+        let name = file.file_name().map(Path::new).unwrap_or(file);
+        lines.push(Line::from(vec![Span::styled(format!("{:<10}", "File"), label_style()), Span::raw(crate::fs_op::path::display_path(name, 48))]));
+        lines.push(bar_line("", *done, *total, format!("{}/{}{}", format_bytes(*done), format_bytes(*total), eta(stats.file_eta))));
+    }
+    lines.push(bar_line("Bytes", stats.bytes_done, stats.bytes_total, format!("{}/{}", format_bytes(stats.bytes_done), format_bytes(stats.bytes_total))));
+    let speed = if stats.paused {
+        "Paused".to_string()
+    } else {
+        match stats.bytes_per_sec {
+            Some(rate) => format!("{}/s{}", format_bytes(rate as u64), eta(stats.eta)),
+            None => "-".to_string(),
+        }
+    };
+    lines.push(Line::from(vec![Span::styled(format!("{:<10}", "Speed"), label_style()), Span::raw(speed)]));
+    lines
+}
+
+/// Render the progress of a background copy or move: one bar for the
+/// items and, when the copy is verified, one for the verification pass.
+/// With `stats` from the operation's meter, the current file, the bytes,
+/// the speed and the time left are shown too, and it can be paused.
+#[allow(clippy::too_many_arguments)]
+pub fn render_progress(f: &mut Frame, area: Rect, title: &str, processed: usize, total: usize, message: &str, verify: Option<(usize, usize)>, stats: Option<&crate::fs_op::meter::MeterStats>) {
+    let mut lines = Vec::new();
+    if message != title {
+        lines.push(Line::from(message.to_string()));
+    }
+    lines.push(progress_bar_line("Items", processed, total));
+    // The byte counts belong to the copy, not the verification after it.
+    if let Some(stats) = stats.filter(|_| verify.is_none_or(|(checked, _)| checked == 0)) {
+        lines.extend(meter_lines(stats));
+    }
+    if let Some((checked, files)) = verify {
+        lines.push(progress_bar_line("Verify", checked, files));
+    }
+    lines.push(Line::default());
+    let mut hint = "Esc cancel".to_string();
+    if let Some(stats) = stats {
+        hint.push_str(if stats.paused { "   p resume" } else { "   p pause" });
+    }
+    if verify.is_some() {
+        hint.push_str("   s skip verification");
+    }
+    hint.push_str("   b background");
+    lines.push(Line::styled(hint, label_style()));
+    draw_box(f, area, title, lines, 64);
+}
+
+/// Render the jobs dialog: one line per background or queued job with its
+/// state (queued jobs with their place in the queue), progress and speed,
+/// then what the selected job is doing and how many jobs run at once.
+pub fn render_jobs(f: &mut Frame, area: Rect, jobs: &[crate::runner::jobs::Job], selected: usize, settings: &crate::app::settings::Settings, now: std::time::Instant) {
+    use crate::fs_op::mounts::format_bytes;
+    let colors = current_colors();
+    let selected = selected.min(jobs.len().saturating_sub(1));
+    let mut lines = Vec::new();
+    let mut queued = 0;
+    for (i, job) in jobs.iter().enumerate() {
+        let stats = job.stats(now);
+        let percent = match &stats {
+            Some(s) if s.bytes_total > 0 => s.bytes_done.min(s.bytes_total) * 100 / s.bytes_total,
+            _ => (job.processed.min(job.total) * 100).checked_div(job.total).unwrap_or(0) as u64,
+        };
+        let speed = stats.filter(|s| !s.paused).and_then(|s| s.bytes_per_sec).map(|r| format!("{}/s", format_bytes(r as u64))).unwrap_or_default();
+        let status = if job.is_queued() {
+            queued += 1;
+            format!("Queued #{}", queued)
+        } else {
+            job.status().to_string()
+        };
+        let text = format!("{} {:<10} {:>3}% {:>11}  {}", if i == selected { ">" } else { " " }, status, percent, speed, job.label);
+        if i == selected {
+            lines.push(Line::styled(text, colors.panel_selected_style.add_modifier(Modifier::BOLD)));
+        } else {
+            lines.push(Line::from(text));
+        }
+    }
+    if let Some(job) = jobs.get(selected) {
+        lines.push(Line::default());
+        lines.push(Line::from(job.message.clone()));
+    } else {
+        lines.push(Line::from("No background jobs"));
+    }
+    lines.push(Line::default());
+    lines.push(Line::from(if settings.sequential_jobs {
+        "Jobs run one at a time, in queue order.".to_string()
+    } else {
+        format!("Up to {} jobs run at once.", settings.job_slots())
+    }));
+    lines.push(Line::styled("Enter: show  p: pause/resume  c: cancel  +/-: move  s: one at a time on/off  Esc: close", label_style()));
+    draw_box(f, area, "Jobs", lines, 80);
+}
+
+/// What the input dialog shows below the typed text.
+#[derive(Default)]
+pub struct InputExtras<'a> {
+    /// Completions (change path) or templates (new file).
+    pub candidates: &'a [crate::app::core::path_jump::Candidate],
+    /// Candidate chosen with Up/Down.
+    pub selected: Option<usize>,
+    /// Key help below the candidates.
+    pub hint: &'a str,
+    /// Directories of the typed path that will be created (see
+    /// `path_preview`).
+    pub preview: Option<Line<'a>>,
+}
+
+/// Render a text prompt with the typed `buffer` and its `extras`.
+pub fn render_input(f: &mut Frame, area: Rect, prompt: &str, buffer: &str, extras: InputExtras) {
+    let colors = current_colors();
+    let InputExtras { candidates, selected, hint, preview } = extras;
+    let mut lines = vec![Line::from(prompt.to_string()), Line::from(format!("> {}", buffer))];
+    lines.extend(preview);
+    if !candidates.is_empty() {
+        lines.push(Line::default());
+        for (i, c) in candidates.iter().enumerate() {
+            let mut spans = vec![Span::raw(format!("  {}", c.text))];
+            if c.recent {
+                spans.push(Span::styled("  recent", label_style()));
+            }
+            if selected == Some(i) {
+                spans = vec![Span::styled(format!("> {}", c.text), colors.panel_selected_style.add_modifier(Modifier::BOLD))];
+            }
+            lines.push(Line::from(spans));
+        }
+        lines.push(Line::default());
+        lines.push(Line::styled(hint.to_string(), label_style()));
+    }
+    draw_box(f, area, "Input", lines, 64);
+}
+
+/// For the new-directory, copy and move dialogs: which directories of the
+/// path typed as `input` (relative to the active panel) exist and which
+/// will be created, like `mkdir -p`. `None` when there is nothing to say.
+pub fn path_preview(app: &App, kind: InputKind, input: &str) -> Option<Line<'static>> {
+    let input = input.trim();
+    if input.is_empty() {
+        return None;
+    }
+    let panel = app.active_panel();
+    let dir = match kind {
+        InputKind::NewDir => panel.cwd.join(input),
+        InputKind::Copy | InputKind::Move => {
+            let name = panel.selected_entry()?.name.clone();
+            crate::fs_op::helpers::resolve_target(&panel.cwd.join(input), name).parent()?.to_path_buf()
+        }
+        _ => return None,
+    };
+    let (existing, missing) = crate::fs_op::path::split_existing(&dir);
+    if missing.as_os_str().is_empty() {
+        return (kind == InputKind::NewDir).then(|| Line::styled("Already exists", label_style()));
+    }
+    let verb = match kind {
+        InputKind::NewDir => "Creates ",
+        _ if app.settings.create_dest_dirs => "Also creates ",
+        _ => "Missing ",
+    };
+    let existing = match existing.as_os_str().is_empty() {
+        true => ".".to_string(),
+        false => crate::fs_op::path::display_path(existing, crate::fs_op::path::MAX_DISPLAY_PATH_CHARS / 2),
+    };
+    let missing = format!("{}{}", missing.display(), std::path::MAIN_SEPARATOR);
+    Some(Line::from(vec![
+        Span::styled(verb, label_style()),
+        Span::styled(missing, Style::default().add_modifier(Modifier::BOLD)),
+        Span::styled(format!(" in {}", existing), label_style()),
+    ]))
+}
+
+/// Render an informational message with its buttons.
+pub fn render_message(f: &mut Frame, area: Rect, title: &str, content: &str, buttons: &[String], selected: usize) {
+    let (lines, width) = message_lines(content, buttons, selected);
+    draw_box(f, area, title, lines, width);
+}
+
+fn message_lines<'a>(content: &'a str, buttons: &'a [String], selected: usize) -> (Vec<Line<'a>>, u16) {
+    let mut lines: Vec<Line> = content.lines().map(Line::from).collect();
+    if !buttons.is_empty() {
+        lines.push(Line::default());
+        lines.push(buttons_line(buttons.iter().map(String::as_str), selected));
+    }
+    let width = content.lines().map(|l| crate::ui::panels::display_width(l) as u16 + 4).max().unwrap_or(0).clamp(30, 80);
+    (lines, width)
+}
+
+/// Index of the button of the conflict, confirmation or message dialog of
+/// `app.mode` drawn at (`column`, `row`) on a screen of size `area`.
+pub fn button_at(app: &App, area: Rect, column: u16, row: u16) -> Option<usize> {
+    let (lines, width, buttons): (Vec<Line>, u16, Vec<&str>) = match &app.mode {
+        Mode::Conflict { path, selected, apply_all, in_use, src, dst, compare } => (
+            conflict_lines(path, src.as_deref(), dst.as_deref(), in_use.as_deref(), compare.as_deref(), *selected, *apply_all),
+            CONFLICT_WIDTH,
+            CONFLICT_BUTTONS.to_vec(),
+        ),
+        Mode::Confirm { msg, on_yes, selected } => (confirm_lines(msg, confirm_entry(app, on_yes), *selected), CONFIRM_WIDTH, CONFIRM_BUTTONS.to_vec()),
+        Mode::OperationError { path, error, retry, elevated, selected } => (
+            operation_error_lines(path.as_deref(), error, retry.is_none(), elevated.is_some(), *selected),
+            CONFLICT_WIDTH,
+            operation_error_buttons(retry.is_none(), elevated.is_some()).to_vec(),
+        ),
+        Mode::Message { content, buttons, selected, .. } if !buttons.is_empty() => {
+            let (lines, width) = message_lines(content, buttons, *selected);
+            (lines, width, buttons.iter().map(String::as_str).collect())
+        }
+        _ => return None,
+    };
+    let rect = box_rect(area, &lines, width);
+    // The buttons are the last line; they are hidden when the box is cut off.
+    let inner = rect.width.saturating_sub(2).max(1) as usize;
+    let rows: usize = lines.iter().map(|l| l.width().div_ceil(inner).max(1)).sum();
+    let button_row = rect.y as usize + rows;
+    if row as usize != button_row || button_row + 1 >= rect.bottom() as usize {
+        return None;
+    }
+    // Each button is drawn as ` label ` or `[label]`, two cells apart.
+    let mut x = rect.x + 1;
+    for (i, b) in buttons.iter().enumerate() {
+        let w = crate::ui::panels::display_width(b) as u16 + 2;
+        if column >= x && column < x + w {
+            return Some(i);
+        }
+        x += w + 2;
+    }
+    None
+}
+
+/// Title of the Settings dialog naming its pages, with `page` highlighted.
+fn settings_title(page: usize) -> Line<'static> {
+    let mut spans = vec![Span::raw(" Settings: ")];
+    for (i, name) in ["General", "Keys"].into_iter().enumerate() {
+        if i > 0 {
+            spans.push(Span::raw(" | "));
+        }
+        if i == page {
+            spans.push(Span::styled(name, Style::default().add_modifier(Modifier::BOLD | Modifier::UNDERLINED)));
+        } else {
+            spans.push(Span::styled(name, label_style()));
+        }
+    }
+    spans.push(Span::raw(" (Tab) "));
+    Line::from(spans)
+}
+
+/// Render the Settings dialog. Geometry matches the mouse handler: a 60
+/// column box `SETTINGS_HEIGHT` rows high with fields from the first inner
+/// row and the buttons on the row above the bottom border.
+pub fn render_settings(f: &mut Frame, area: Rect, settings: &crate::app::settings::Settings, selected: usize) {
+    let colors = current_colors();
+    let check = |b: bool| if b { "[x]" } else { "[ ]" };
+    let fields = [
+        ("Mouse enabled", check(settings.mouse_enabled).to_string()),
+        ("Double-click timeout", format!("{} ms  (-/+)", settings.mouse_double_click_ms)),
+        ("CLI-style listing", check(settings.show_cli_listing).to_string()),
+        ("Keybinding preset", format!("<{}>", settings.keybinding_preset)),
+        ("Restore last session", check(settings.restore_session).to_string()),
+        ("Confirm delete", check(settings.confirm_delete).to_string()),
+        ("Confirm overwrite", check(settings.confirm_overwrite).to_string()),
+        ("Confirm cross-fs move", check(settings.confirm_cross_fs_move).to_string()),
+        ("Confirm quit with jobs", check(settings.confirm_quit_with_jobs).to_string()),
+        ("Run jobs one at a time", check(settings.sequential_jobs).to_string()),
+        ("Concurrent jobs", format!("{}  (-/+)", settings.max_concurrent_jobs)),
+        ("Copy buffer", format!("<{}>", crate::runner::handlers::settings::copy_buffer_label(settings.copy_buffer_kib))),
+        ("Colour depth", format!("<{}>", settings.color_depth)),
+    ];
+    let field_count = fields.len();
+    let mut lines: Vec<Line> = fields
+        .into_iter()
+        .enumerate()
+        .map(|(i, (label, value))| {
+            let text = format!("{:<22} {}", label, value);
+            if i == selected {
+                Line::styled(text, colors.panel_selected_style)
+            } else {
+                Line::from(text)
+            }
+        })
+        .collect();
+    let rect = centered_rect(area, 60, crate::runner::handlers::settings::SETTINGS_HEIGHT);
+    let inner_rows = rect.height.saturating_sub(2) as usize;
+    while lines.len() + 1 < inner_rows {
+        lines.push(Line::default());
+    }
+    // Rows past the fields select the buttons.
+    let button = selected.checked_sub(field_count).unwrap_or(usize::MAX);
+    lines.push(buttons_line(crate::runner::handlers::settings::SETTINGS_BUTTONS, button));
+    f.render_widget(Clear, rect);
+    let p = Paragraph::new(lines)
+        .block(Block::default().borders(Borders::ALL).title(settings_title(0)).style(colors.preview_block_style));
+    f.render_widget(p, rect);
+}
+
+/// Render the Keys page of the Settings dialog: a scrolling list of
+/// actions with their chords, a status line and the Save / Reset / Cancel
+/// buttons. Shows `KEYMAP_VISIBLE_ROWS` actions when the terminal is tall
+/// enough.
+pub fn render_keymap_editor(f: &mut Frame, area: Rect, editor: &crate::app::settings::keymap_editor::KeymapEditor) {
+    use crate::app::settings::keymap::ACTIONS;
+    use crate::app::settings::keymap_editor::KEYMAP_BUTTONS;
+    use crate::runner::handlers::keymap_editor::KEYMAP_VISIBLE_ROWS;
+
+    let colors = current_colors();
+    let rect = centered_rect(area, 72, KEYMAP_VISIBLE_ROWS as u16 + 6);
+    // Header, status and button rows take three of the inner rows.
+    let visible = (rect.height.saturating_sub(2) as usize).saturating_sub(3).max(1);
+    let mut scroll = editor.scroll;
+    if let Some(idx) = editor.selected_action() {
+        if idx >= scroll + visible {
+            scroll = idx + 1 - visible;
+        }
+    }
+
+    let mut lines = vec![Line::styled(format!("{:<22} {:<22} {}", "Action", "Keys", "Description"), label_style())];
+    for (i, ((action, chords), (_, desc))) in editor.bindings.iter().zip(ACTIONS).enumerate().skip(scroll).take(visible) {
+        let keys = if editor.capturing && i == editor.selected {
+            "<press a key>".to_string()
+        } else {
+            chords.iter().map(|c| c.to_string()).collect::<Vec<_>>().join(", ")
+        };
+        let text = format!("{:<22} {:<22} {}", action, keys, desc);
+        if i == editor.selected {
+            lines.push(Line::styled(text, colors.panel_selected_style));
+        } else {
+            lines.push(Line::from(text));
+        }
+    }
+    let inner_rows = rect.height.saturating_sub(2) as usize;
+    while lines.len() + 2 < inner_rows {
+        lines.push(Line::default());
+    }
+    let status = editor.status.clone().unwrap_or_else(|| "Enter: rebind  Del: preset keys  Esc: close".to_string());
+    let status_style = if editor.conflict.is_some() { warning_style() } else { label_style() };
+    lines.push(Line::styled(status, status_style));
+    lines.push(buttons_line(KEYMAP_BUTTONS, editor.selected_button().unwrap_or(usize::MAX)));
+
+    f.render_widget(Clear, rect);
+    let p = Paragraph::new(lines)
+        .block(Block::default().borders(Borders::ALL).title(settings_title(1)).style(colors.preview_block_style));
+    f.render_widget(p, rect);
+}
+
+/// Render the theme picker. User themes are marked so they can be told
+/// apart from the built-in ones.
+pub fn render_theme_picker(f: &mut Frame, area: Rect, themes: &[String], selected: usize, error: Option<&str>) {
+    let colors = current_colors();
+    let mut lines = Vec::new();
+    for (i, name) in themes.iter().enumerate() {
+        let kind = if crate::ui::themes::is_builtin(name) { "built-in" } else { "user" };
+        let text = format!("{} {:<28} {}", if i == selected { ">" } else { " " }, name, kind);
+        if i == selected {
+            lines.push(Line::styled(text, colors.panel_selected_style.add_modifier(Modifier::BOLD)));
+        } else {
+            lines.push(Line::from(text));
+        }
+    }
+    lines.push(Line::default());
+    match error {
+        Some(e) => lines.push(Line::styled(e.to_string(), warning_style())),
+        None => lines.push(Line::styled("Enter: use theme  Esc: cancel", label_style())),
+    }
+    draw_box(f, area, "Theme", lines, 48);
+}
+
+/// Render the mount picker: one line per mounted filesystem with its type,
+/// free space and source.
+pub fn render_mount_picker(f: &mut Frame, area: Rect, mounts: &[crate::fs_op::mounts::Mount], selected: usize) {
+    let colors = current_colors();
+    let mut lines = Vec::new();
+    for (i, m) in mounts.iter().enumerate() {
+        let root = crate::fs_op::path::display_path(&m.root, 28);
+        let kind = if m.removable { format!("{} (removable)", m.fs_type) } else { m.fs_type.clone() };
+        let text = format!("{} {:<28} {:<18} {:<26} {}", if i == selected { ">" } else { " " }, root, kind.trim(), m.space_text(), m.source);
+        if i == selected {
+            lines.push(Line::styled(text, colors.panel_selected_style.add_modifier(Modifier::BOLD)));
+        } else {
+            lines.push(Line::from(text));
+        }
+    }
+    if mounts.is_empty() {
+        lines.push(Line::from("No mounted filesystems found"));
+    }
+    lines.push(Line::default());
+    lines.push(Line::styled("Enter: go to mount point  Esc: cancel", label_style()));
+    draw_box(f, area, "Mounts", lines, 100);
+}
+
+/// Render the preview encoding picker: "Auto" and `encoding::CHOICES`,
+/// with the encoding in use (`current`, `None` for Auto) marked `*`.
+pub fn render_encoding_picker(f: &mut Frame, area: Rect, selected: usize, current: Option<&'static encoding_rs::Encoding>) {
+    let colors = current_colors();
+    let names = std::iter::once("Auto (detect)").chain(crate::app::core::encoding::CHOICES.iter().copied());
+    let mut lines: Vec<Line> = names
+        .enumerate()
+        .map(|(i, name)| {
+            let in_use = match current {
+                None => i == 0,
+                Some(e) => i > 0 && e.name() == name,
+            };
+            let text = format!("{} {} {}", if i == selected { ">" } else { " " }, if in_use { "*" } else { " " }, name);
+            if i == selected {
+                Line::styled(text, colors.panel_selected_style.add_modifier(Modifier::BOLD))
+            } else {
+                Line::from(text)
+            }
+        })
+        .collect();
+    lines.push(Line::default());
+    lines.push(Line::styled("Enter: use  Esc: cancel", label_style()));
+    draw_box(f, area, "Preview encoding", lines, 34);
+}
+
+/// Render the saved searches, by name, and the recent ones, most recent
+/// first, each as the query that runs it.
+pub fn render_search_picker(f: &mut Frame, area: Rect, searches: &crate::app::core::searches::Searches, selected: usize) {
+    let colors = current_colors();
+    let mut lines: Vec<Line> = searches
+        .entries()
+        .into_iter()
+        .enumerate()
+        .map(|(i, (name, options))| {
+            let label = name.map_or_else(|| "(recent)".to_string(), str::to_string);
+            let text = format!("{} {:<16} {}", if i == selected { ">" } else { " " }, label, options.query());
+            if i == selected {
+                Line::styled(text, colors.panel_selected_style.add_modifier(Modifier::BOLD))
+            } else {
+                Line::from(text)
+            }
+        })
+        .collect();
+    if lines.is_empty() {
+        lines.push(Line::from("No searches yet. Search with `/` or `:find`."));
+    }
+    lines.push(Line::default());
+    lines.push(Line::styled("Enter: search again  d: forget  Esc: cancel", label_style()));
+    draw_box(f, area, "Searches", lines, 60);
+}
+
+/// Failures the summary dialog lists at once; the rest scroll.
+pub const SUMMARY_FAILURE_ROWS: usize = 10;
+
+/// Render the summary of an operation that ended: its counts, bytes and
+/// time, its closing `note` and its failures from `scroll` on.
+pub fn render_summary(f: &mut Frame, area: Rect, title: &str, note: Option<&str>, summary: &crate::runner::progress::OperationSummary, scroll: usize) {
+    use crate::fs_op::mounts::format_bytes;
+    let row = |label: &str, value: String| Line::from(vec![Span::styled(format!("{:<10}", label), label_style()), Span::raw(value)]);
+    let mut lines = vec![
+        row("Succeeded", summary.succeeded.to_string()),
+        row("Skipped", summary.skipped.to_string()),
+        row("Failed", summary.failed().to_string()),
+        row("Bytes", format_bytes(summary.bytes)),
+        row("Time", format_eta(summary.elapsed)),
+    ];
+    if let Some(note) = note {
+        lines.push(Line::default());
+        lines.extend(note.lines().map(|l| Line::from(l.to_string())));
+    }
+    if !summary.failures.is_empty() {
+        let end = (scroll + SUMMARY_FAILURE_ROWS).min(summary.failed());
+        lines.push(Line::default());
+        lines.push(Line::styled(format!("Failures {}-{} of {}", scroll + 1, end, summary.failed()), label_style()));
+        for (path, error) in &summary.failures[scroll.min(end)..end] {
+            lines.push(Line::styled(format!("{}: {}", crate::fs_op::path::display_path(path, 48), error), warning_style()));
+        }
+    }
+    lines.push(Line::default());
+    let hint = if summary.failed() > SUMMARY_FAILURE_ROWS { "Up/Down: scroll  l: log  Enter: close" } else { "l: log  Enter: close" };
+    lines.push(Line::styled(hint, label_style()));
+    draw_box(f, area, title, lines, 70);
+}
+
+/// Render the context menu of an entry. A menu opened by a right-click is
+/// drawn next to the clicked cell, flipped above or to the left when it
+/// would run off the screen; otherwise it is centred.
+pub fn render_context_menu(f: &mut Frame, area: Rect, title: &str, options: &[String], selected: usize, at: Option<(u16, u16)>) {
+    let colors = current_colors();
+    let rect = context_menu_rect(area, title, options, at);
+    let lines: Vec<Line> = options
+        .iter()
+        .enumerate()
+        .map(|(i, o)| {
+            let text = format!(" {} ", o);
+            if i == selected {
+                Line::styled(text, colors.panel_selected_style.add_modifier(Modifier::BOLD))
+            } else {
+                Line::from(text)
+            }
+        })
+        .collect();
+    f.render_widget(Clear, rect);
+    let p = Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title(format!(" {} ", title)).style(colors.preview_block_style));
+    f.render_widget(p, rect);
+}
+
+/// Where `render_context_menu` draws the menu.
+pub fn context_menu_rect(area: Rect, title: &str, options: &[String], at: Option<(u16, u16)>) -> Rect {
+    let widest = options.iter().map(|o| crate::ui::panels::display_width(o)).max().unwrap_or(0).max(crate::ui::panels::display_width(title) + 2);
+    let width = (widest as u16 + 4).min(area.width);
+    let height = (options.len() as u16 + 2).min(area.height);
+    match at {
+        Some((x, y)) => {
+            let x = if x + width > area.right() { x.saturating_sub(width).max(area.x) } else { x };
+            let y = if y + 1 + height > area.bottom() { y.saturating_sub(height).max(area.y) } else { y + 1 };
+            Rect::new(x, y, width, height)
+        }
+        None => centered_rect(area, width, height),
+    }
+}
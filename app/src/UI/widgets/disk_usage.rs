@@ -0,0 +1,55 @@
+use ratatui::{layout::Rect, style::{Modifier, Style}, text::{Line, Span}, widgets::{Block, Borders, Clear, Paragraph}, Frame};
+
+use crate::app::core::usage::DiskUsage;
+use crate::fs_op::mounts::format_bytes;
+use crate::ui::colors::current as current_colors;
+
+/// Width of the size bars, in cells.
+const BAR_WIDTH: usize = 20;
+
+/// Render the disk usage analyzer over the panels: the entries of
+/// `usage.dir`, largest first, with their share of the total and a bar
+/// relative to the largest one.
+pub fn render(f: &mut Frame, area: Rect, usage: &DiskUsage, selected: usize, confirm_delete: bool) {
+    let colors = current_colors();
+    f.render_widget(Clear, area);
+    let total = usage.total();
+    let state = if usage.done { String::new() } else { ", scanning...".to_string() };
+    let title = format!(" Disk usage: {} ({}{}) ", usage.dir.display(), format_bytes(total), state);
+    // The last row is the key help, a prompt or an error.
+    let height = area.height.saturating_sub(3) as usize;
+    let selected = selected.min(usage.entries.len().saturating_sub(1));
+    let offset = (selected + 1).saturating_sub(height);
+    let largest = usage.entries.first().map_or(0, |e| e.bytes).max(1);
+    let mut lines: Vec<Line> = usage
+        .entries
+        .iter()
+        .enumerate()
+        .skip(offset)
+        .take(height)
+        .map(|(i, e)| {
+            let percent = (e.bytes as f64 * 100.0 / total.max(1) as f64).min(100.0);
+            let filled = (e.bytes.saturating_mul(BAR_WIDTH as u64) / largest) as usize;
+            let bar = format!("[{}{}]", "#".repeat(filled), " ".repeat(BAR_WIDTH - filled.min(BAR_WIDTH)));
+            let name = if e.is_dir { format!("{}/", e.name) } else { e.name.clone() };
+            let text = format!("{:>10} {:>5.1}% {} {}", format_bytes(e.bytes), percent, bar, name);
+            if i == selected {
+                Line::styled(text, colors.panel_selected_style.add_modifier(Modifier::BOLD))
+            } else {
+                Line::from(text)
+            }
+        })
+        .collect();
+    if usage.entries.is_empty() {
+        lines.push(Line::from(if usage.done { "(empty)" } else { "scanning..." }));
+    }
+    lines.resize(height, Line::default());
+    let footer = match (usage.entries.get(selected), &usage.error) {
+        (Some(e), _) if confirm_delete => Line::styled(format!("Delete {} ({})? (y/n)", e.name, format_bytes(e.bytes)), Style::default().add_modifier(Modifier::BOLD)),
+        (_, Some(error)) => Line::from(Span::raw(error.clone())),
+        _ => Line::styled("Enter: open  Backspace: parent  d: delete  Esc: close", Style::default().add_modifier(Modifier::DIM)),
+    };
+    lines.push(footer);
+    let p = Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title(title).style(colors.preview_block_style));
+    f.render_widget(p, area);
+}
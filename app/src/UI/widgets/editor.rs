@@ -0,0 +1,66 @@
+use ratatui::{layout::{Constraint, Direction, Layout, Position, Rect}, widgets::{Block, Borders, Clear, Paragraph}, Frame};
+use crate::app::text_editors::builtin::{EditorPrompt, EditorState};
+use crate::ui::colors::current as current_colors;
+
+/// Render the integrated editor over `area` with a one-line status bar.
+///
+/// Tabs are drawn as a single space so the on-screen cursor column matches
+/// the editor's character column.
+pub fn render(f: &mut Frame, area: Rect, editor: &EditorState) {
+    let colors = current_colors();
+    f.render_widget(Clear, area);
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(1), Constraint::Length(1)])
+        .split(area);
+
+    let title = format!(
+        " {}{} [{}] ",
+        crate::fs_op::path::display_path(&editor.path, (area.width as usize).saturating_sub(12)),
+        if editor.dirty { " [+]" } else { "" },
+        if editor.overwrite { "OVR" } else { "INS" },
+    );
+    let block = Block::default().borders(Borders::ALL).title(title).style(colors.preview_block_style);
+    let inner = block.inner(chunks[0]);
+    let height = inner.height as usize;
+    let width = inner.width as usize;
+
+    // The handler keeps `scroll` in range for the last known page size; clamp
+    // again here in case the terminal was resized since.
+    let mut scroll = editor.scroll;
+    if height > 0 && editor.row >= scroll + height {
+        scroll = editor.row + 1 - height;
+    }
+    scroll = scroll.min(editor.row);
+    let hscroll = if width > 0 && editor.col >= width { editor.col + 1 - width } else { 0 };
+
+    let text: Vec<String> = editor
+        .lines
+        .iter()
+        .skip(scroll)
+        .take(height)
+        .map(|l| l.chars().skip(hscroll).take(width).map(|c| if c == '\t' { ' ' } else { c }).collect())
+        .collect();
+    f.render_widget(Paragraph::new(text.join("\n")).block(block), chunks[0]);
+
+    let status = match &editor.prompt {
+        Some(EditorPrompt::Search(q)) => format!("Search: {}", q),
+        Some(EditorPrompt::GotoLine(n)) => format!("Go to line: {}", n),
+        None if !editor.status.is_empty() => editor.status.clone(),
+        None => format!(
+            "Ln {}, Col {}  F2 save  F7 search  F9 go to line  Ins mode  Esc close",
+            editor.row + 1,
+            editor.col + 1
+        ),
+    };
+    f.render_widget(Paragraph::new(status.clone()).style(colors.header_style), chunks[1]);
+
+    if editor.prompt.is_some() {
+        let x = chunks[1].x + (status.chars().count() as u16).min(chunks[1].width.saturating_sub(1));
+        f.set_cursor_position(Position::new(x, chunks[1].y));
+    } else if height > 0 && width > 0 {
+        let x = inner.x + (editor.col - hscroll) as u16;
+        let y = inner.y + (editor.row - scroll) as u16;
+        f.set_cursor_position(Position::new(x, y));
+    }
+}
@@ -1,17 +1,93 @@
-use ratatui::{layout::Rect, widgets::{List, ListItem, Block, Borders, ListState}};
+use ratatui::{layout::Rect, style::Style, text::Line, widgets::{List, ListItem, Block, Borders, ListState}};
 use ratatui::Frame;
 use crate::ui::Theme;
 
 /// Render a file list for the given items and selected index. This is a
 /// small widget intended to be reused for both left and right panels.
-pub fn render(f: &mut Frame, area: Rect, items_src: &[String], selected: usize, _theme: &Theme) {
+pub fn render(f: &mut Frame, area: Rect, items_src: &[String], selected: usize, theme: &Theme) {
+    render_styled(f, area, items_src, &[], selected, theme)
+}
+
+/// Like `render`, with a style per item (file-type colours); items past
+/// the end of `styles` use the panel style.
+pub fn render_styled(f: &mut Frame, area: Rect, items_src: &[String], styles: &[Style], selected: usize, theme: &Theme) {
+    render_titled(f, area, items_src, styles, selected, Line::from("Files"), theme)
+}
+
+/// Like `render_styled`, with `title` (the panel's breadcrumbs) in the top
+/// border. Rows wider than the list end in `…`, measured in cells so wide
+/// glyphs are never cut in half.
+pub fn render_titled(f: &mut Frame, area: Rect, items_src: &[String], styles: &[Style], selected: usize, title: Line, _theme: &Theme) {
     let colors = crate::ui::colors::current();
-    let items: Vec<ListItem> = items_src.iter().map(|s| ListItem::new(s.clone())).collect();
+    let width = area.width.saturating_sub(2) as usize;
+    let items: Vec<ListItem> = items_src
+        .iter()
+        .enumerate()
+        .map(|(i, s)| {
+            let text = crate::ui::panels::truncate_to_width(s, width).into_owned();
+            ListItem::new(text).style(styles.get(i).copied().unwrap_or_default())
+        })
+        .collect();
     let list = List::new(items)
-        .block(Block::default().borders(Borders::ALL).title("Files").style(colors.panel_block_style))
+        .block(Block::default().borders(Borders::ALL).title(title).style(colors.panel_block_style))
         .highlight_style(colors.panel_selected_style);
     // `selected` may be out of bounds for simple samples; `List` will ignore then.
     let mut state = ListState::default();
     state.select(if selected < items_src.len() { Some(selected) } else { None });
     f.render_stateful_widget(list, area, &mut state);
 }
+
+/// Draw each row's tag marks (`● name` in the tag colour) right-aligned
+/// inside the bordered list at `area`; `marks[i]` belongs to row `i`.
+pub fn render_marks(f: &mut Frame, area: Rect, marks: &[crate::app::core::tags::Marks]) {
+    use ratatui::text::Span;
+    use ratatui::widgets::Paragraph;
+    let inner = area.inner(ratatui::layout::Margin { horizontal: 1, vertical: 1 });
+    for (i, row) in marks.iter().enumerate().take(inner.height as usize) {
+        if row.is_empty() {
+            continue;
+        }
+        let spans: Vec<Span> = row
+            .iter()
+            .flat_map(|(name, color)| [Span::raw(" "), Span::styled(format!("● {}", name), Style::default().fg(*color))])
+            .collect();
+        let line = Line::from(spans);
+        let width = (line.width() as u16).min(inner.width);
+        let cell = Rect { x: inner.right() - width, y: inner.y + i as u16, width, height: 1 };
+        // A wide glyph whose second half is covered by the marks would
+        // still be drawn over them; blank it instead.
+        if cell.x > inner.x {
+            let left = &mut f.buffer_mut()[(cell.x - 1, cell.y)];
+            if crate::ui::panels::display_width(left.symbol()) > 1 {
+                left.set_symbol(" ");
+            }
+        }
+        f.render_widget(Paragraph::new(line).alignment(ratatui::layout::Alignment::Right), cell);
+    }
+}
+
+/// Embolden and underline the cells at `matches[i]` (columns of the
+/// characters a search matched) in row `i` of the bordered list at `area`.
+pub fn render_matches(f: &mut Frame, area: Rect, matches: &[Vec<u16>]) {
+    let inner = area.inner(ratatui::layout::Margin { horizontal: 1, vertical: 1 });
+    let style = Style::default().add_modifier(ratatui::style::Modifier::BOLD | ratatui::style::Modifier::UNDERLINED);
+    for (row, columns) in matches.iter().enumerate().take(inner.height as usize) {
+        for &column in columns.iter().filter(|&&c| c < inner.width) {
+            let cell = &mut f.buffer_mut()[(inner.x + column, inner.y + row as u16)];
+            cell.set_style(cell.style().patch(style));
+        }
+    }
+}
+
+/// Draw a scrollbar on the right border of the bordered list at `area` when
+/// its `total` rows do not fit; `offset` is the first row shown.
+pub fn render_scrollbar(f: &mut Frame, area: Rect, total: usize, offset: usize) {
+    use ratatui::widgets::{Scrollbar, ScrollbarOrientation, ScrollbarState};
+    let rows = area.height.saturating_sub(2) as usize;
+    if total <= rows {
+        return;
+    }
+    let mut state = ScrollbarState::new(total - rows + 1).viewport_content_length(rows).position(offset);
+    let bar = Scrollbar::new(ScrollbarOrientation::VerticalRight).begin_symbol(None).end_symbol(None);
+    f.render_stateful_widget(bar, area.inner(ratatui::layout::Margin { vertical: 1, horizontal: 0 }), &mut state);
+}
@@ -1,10 +1,79 @@
-use ratatui::{layout::Rect, widgets::{Block, Paragraph, Borders}, Frame};
+use std::time::{Duration, Instant};
+
+use ratatui::{layout::{Alignment, Rect}, widgets::{Block, Paragraph, Borders}, Frame};
+use crate::app::core::App;
 use crate::ui::{UIState, Theme};
 use crate::ui::colors::current as current_colors;
 
+/// Frames of the running-jobs spinner, one every `SPINNER_FRAME`.
+const SPINNER: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+pub const SPINNER_FRAME: Duration = Duration::from_millis(100);
+
+/// Spinner and count of the running background jobs, with the label of the
+/// current one: `⠹ 1 job (Copy)`. `None` when nothing runs.
+pub fn job_indicator(running: usize, current: Option<(&str, Instant)>, now: Instant) -> Option<String> {
+    if running == 0 {
+        return None;
+    }
+    let elapsed = current.map(|(_, started)| now.saturating_duration_since(started)).unwrap_or_default();
+    let frame = SPINNER[(elapsed.as_millis() / SPINNER_FRAME.as_millis()) as usize % SPINNER.len()];
+    let count = if running == 1 { "1 job".to_string() } else { format!("{} jobs", running) };
+    Some(match current {
+        Some((label, _)) => format!("{} {} ({})", frame, count, label),
+        None => format!("{} {}", frame, count),
+    })
+}
+
+/// Right-hand side of the status line: what the clipboard holds, the job
+/// indicator and, when the `show_clock` setting is on, the time.
+pub fn status_text(app: &App, now: Instant) -> Option<String> {
+    let clipboard = app.clipboard.as_ref().map(|c| format!("[{}]", c.summary()));
+    let jobs = job_indicator(app.running_jobs(), app.jobs.running(), now);
+    let clock = app.settings.show_clock.then(|| chrono::DateTime::<chrono::Local>::from(crate::app::core::clock::now()).format("%H:%M").to_string());
+    let parts: Vec<String> = [clipboard, jobs, clock].into_iter().flatten().collect();
+    (!parts.is_empty()).then(|| parts.join("  "))
+}
+
+/// Draw the status line. It is drawn with every frame; ratatui only sends
+/// the cells that changed, so the clock and spinner tick without
+/// repainting the screen.
 pub fn render(f: &mut Frame, area: Rect, state: &UIState, _theme: &Theme) {
-    let content = format!("Progress: {}% | {} items", state.progress, state.left_list.len());
+    let mut content = format!("Progress: {}% | {} items", state.progress, state.left_total);
+    if let Some(hint) = &state.change_hint {
+        content.push_str(" | ");
+        content.push_str(hint);
+    }
     let colors = current_colors();
-    let p = Paragraph::new(content).block(Block::default().borders(Borders::ALL).style(colors.footer_style));
+    let block = Block::default().borders(Borders::ALL).style(colors.footer_style);
+    let inner = block.inner(area);
+    let p = Paragraph::new(content).block(block);
     f.render_widget(p, area);
+    if let Some(status) = &state.status {
+        f.render_widget(Paragraph::new(status.as_str()).alignment(Alignment::Right), inner);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn job_indicator_spins_and_counts() {
+        let started = Instant::now();
+        assert_eq!(job_indicator(0, None, started), None);
+        assert_eq!(job_indicator(1, Some(("Copy", started)), started).as_deref(), Some("⠋ 1 job (Copy)"));
+        let later = started + Duration::from_millis(250);
+        assert_eq!(job_indicator(2, Some(("Move", started)), later).as_deref(), Some("⠹ 2 jobs (Move)"));
+    }
+
+    #[test]
+    fn clock_shows_only_when_enabled() {
+        let opts = crate::app::StartOptions { start_dir: Some(std::env::temp_dir()), ..Default::default() };
+        let mut app = App::with_options(&opts).unwrap();
+        app.settings.show_clock = false;
+        assert_eq!(status_text(&app, Instant::now()), None);
+        app.settings.show_clock = true;
+        let clock = status_text(&app, Instant::now()).unwrap();
+        assert_eq!((clock.len(), &clock[2..3]), (5, ":"));
+    }
 }
@@ -5,7 +5,12 @@ use crate::ui::colors::current as current_colors;
 
 pub fn render(f: &mut Frame, area: Rect, state: &UIState, _theme: &Theme) {
     let colors = current_colors();
-    let p = Paragraph::new(Span::raw(" fileZoom — left/right panels | adaptive UI "))
+    // Leave room for the borders and the surrounding padding/title text.
+    let prefix = " fileZoom — ";
+    let branch = state.active_branch.as_ref().map(|b| format!("  [{}]", b)).unwrap_or_default();
+    let avail = (area.width as usize).saturating_sub(prefix.chars().count() + branch.chars().count() + 3);
+    let path = crate::fs_op::path::display_path(&state.active_cwd, avail);
+    let p = Paragraph::new(Span::raw(format!("{}{}{} ", prefix, path, branch)))
         .block(Block::default().borders(Borders::ALL).title(" header ").style(colors.header_style))
         .style(colors.header_style);
     f.render_widget(p, area);
@@ -16,10 +21,7 @@ pub fn render(f: &mut Frame, area: Rect, state: &UIState, _theme: &Theme) {
     if state.menu_open {
         if let Some(model_top) = crate::ui::menu_model::MenuModel::default_model().0.get(state.menu_selected) {
             if let Some(sub) = &model_top.submenu {
-                // Convert submenu items to owned strings for the widget.
-                let labels: Vec<String> = sub.iter().map(|it| it.label.clone()).collect();
-                // Render submenu using the submenu widget.
-                crate::ui::widgets::submenu::render(f, area, &labels, state.menu_sub_selected);
+                crate::ui::widgets::submenu::render(f, area, sub, state.menu_sub_selected);
             }
         }
     }
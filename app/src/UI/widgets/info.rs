@@ -0,0 +1,46 @@
+use ratatui::{layout::Rect, style::{Modifier, Style}, text::{Line, Span}, widgets::{Block, Borders, Paragraph, Wrap}, Frame};
+
+use crate::app::core::App;
+use crate::fs_op::info::FileInfo;
+use crate::ui::colors::current as current_colors;
+
+/// Info panel: extended details of the active panel's selection, and once
+/// computed (see `App::poll_info`) a directory's entry count and total size
+/// or the details of a file's format.
+pub fn render(f: &mut Frame, area: Rect, app: &App) {
+    let colors = current_colors();
+    let label = |l: &str| Span::styled(format!("{:<12}", l), Style::default().add_modifier(Modifier::DIM));
+    let panel = app.active_panel();
+    let mut lines = Vec::new();
+    match panel.selected_entry() {
+        None => lines.push(Line::from("(no selection)")),
+        Some(e) if !panel.vfs.is_local() => {
+            lines.push(Line::from(e.display_name()));
+            lines.push(Line::from(format!("Details are not available on {}", panel.vfs.name())));
+        }
+        Some(e) => {
+            lines.push(Line::styled(e.display_name(), Style::default().add_modifier(Modifier::BOLD)));
+            lines.push(Line::default());
+            match FileInfo::read(&e.path) {
+                Ok(info) => lines.extend(info.rows().into_iter().map(|(l, v)| Line::from(vec![label(l), Span::raw(v)]))),
+                Err(err) => lines.push(Line::from(format!("Cannot read details: {}", err))),
+            }
+            if e.is_dir {
+                let contents = match app.info.totals.filter(|_| app.info.path.as_ref() == Some(&e.path)) {
+                    Some(t) => format!("{} entries, {} B", t.entries, t.bytes),
+                    None => "counting...".to_string(),
+                };
+                lines.push(Line::from(vec![label("Contents"), Span::raw(contents)]));
+            } else if let Some(rows) = app.info.details.as_ref().filter(|_| app.info.path.as_ref() == Some(&e.path)) {
+                if !rows.is_empty() {
+                    lines.push(Line::default());
+                }
+                lines.extend(rows.iter().map(|(l, v)| Line::from(vec![label(l), Span::raw(v.clone())])));
+            }
+        }
+    }
+    let p = Paragraph::new(lines)
+        .wrap(Wrap { trim: false })
+        .block(Block::default().borders(Borders::ALL).title("Info").style(colors.preview_block_style));
+    f.render_widget(p, area);
+}
@@ -0,0 +1,31 @@
+use ratatui::{layout::Rect, widgets::{Block, Borders, Clear, Paragraph}, Frame};
+use crate::app::settings::keymap::{KeyChord, KeySequence, ACTIONS};
+use crate::ui::colors::current as current_colors;
+
+/// Lines of the key-sequence hint popup: each remaining key sequence and
+/// the description of the action it finishes.
+pub fn hint_lines(continuations: &[(KeySequence, String)]) -> Vec<String> {
+    let width = continuations.iter().map(|(rest, _)| rest.to_string().chars().count()).max().unwrap_or(0);
+    continuations
+        .iter()
+        .map(|(rest, action)| {
+            let desc = ACTIONS.iter().find(|(a, _)| a == action).map_or(action.as_str(), |(_, d)| *d);
+            format!("{:<width$}  {}", rest.to_string(), desc, width = width)
+        })
+        .collect()
+}
+
+/// Render the which-key style popup for the keys typed so far in the
+/// bottom-right corner of `area`.
+pub fn render(f: &mut Frame, area: Rect, typed: &[KeyChord], lines: &[String]) {
+    let colors = current_colors();
+    let title = format!(" {} … ", KeySequence(typed.to_vec()));
+    let body_width = lines.iter().map(|l| l.chars().count()).max().unwrap_or(0).max(title.chars().count());
+    let width = (body_width as u16 + 2).min(area.width);
+    let height = (lines.len() as u16 + 2).min(area.height);
+    let rect = Rect::new(area.x + area.width - width, area.y + area.height - height, width, height);
+    f.render_widget(Clear, rect);
+    let p = Paragraph::new(lines.join("\n"))
+        .block(Block::default().borders(Borders::ALL).title(title).style(colors.preview_block_style));
+    f.render_widget(p, rect);
+}
@@ -0,0 +1,39 @@
+use std::path::Path;
+
+use ratatui::{layout::Rect, style::{Color, Style}, text::Line, widgets::{Block, Borders, Clear, Paragraph}, Frame};
+use crate::ui::colors::current as current_colors;
+
+/// Render the log viewer: the page of `lines` ending `scroll` lines above
+/// the last one, with warnings and errors highlighted.
+pub fn render(f: &mut Frame, area: Rect, path: Option<&Path>, lines: &[String], scroll: usize) {
+    let colors = current_colors();
+    f.render_widget(Clear, area);
+    let title = match path {
+        Some(p) => format!(" Log: {} ", p.display()),
+        None => " Log ".to_string(),
+    };
+    let height = area.height.saturating_sub(2) as usize;
+    let end = lines.len().saturating_sub(scroll);
+    let text: Vec<Line> = if lines.is_empty() {
+        vec![Line::from(match path {
+            Some(_) => "(the log is empty)",
+            None => "(nothing logged yet)",
+        })]
+    } else {
+        lines[end.saturating_sub(height)..end]
+            .iter()
+            .map(|l| {
+                let style = if l.contains(" ERROR ") {
+                    Style::default().fg(Color::Red)
+                } else if l.contains(" WARN ") {
+                    Style::default().fg(Color::Yellow)
+                } else {
+                    Style::default()
+                };
+                Line::styled(l.as_str(), style)
+            })
+            .collect()
+    };
+    let p = Paragraph::new(text).block(Block::default().borders(Borders::ALL).title(title).style(colors.preview_block_style));
+    f.render_widget(p, area);
+}
@@ -1,14 +1,41 @@
-use ratatui::{layout::Rect, widgets::{Block, Paragraph, Borders}, Frame};
+use ratatui::{layout::Rect, style::{Modifier, Style}, text::{Line, Span}, widgets::{Block, Paragraph, Borders}, Frame};
+
+use crate::ui::menu_model::{mnemonics, MenuModel};
+
+/// `label` with its mnemonic letter (char index `mnemonic`) underlined.
+pub fn mnemonic_spans(label: &str, mnemonic: Option<usize>, style: Style) -> Vec<Span<'static>> {
+    let Some((start, c)) = mnemonic.and_then(|i| label.char_indices().nth(i)) else {
+        return vec![Span::styled(label.to_string(), style)];
+    };
+    let end = start + c.len_utf8();
+    vec![
+        Span::styled(label[..start].to_string(), style),
+        Span::styled(label[start..end].to_string(), style.add_modifier(Modifier::UNDERLINED | Modifier::BOLD)),
+        Span::styled(label[end..].to_string(), style),
+    ]
+}
 
 /// Render a simple horizontal main menu above the header.
-/// Active item is bracketed for emphasis. Uses header_style when available.
+/// Active item is bracketed for emphasis, each label's mnemonic is
+/// underlined and entries without a submenu show their shortcut key.
 pub fn render(f: &mut Frame, area: Rect, active_index: usize, focused: bool) {
     let labels = crate::ui::menu::menu_labels();
-    let mut parts: Vec<String> = Vec::new();
-    for (i, l) in labels.iter().enumerate() {
-        if i == active_index { parts.push(format!("[{}]", l)); } else { parts.push(l.to_string()); }
+    let tops = MenuModel::default_model().0;
+    let mut spans: Vec<Span> = Vec::new();
+    for (i, (l, m)) in labels.iter().zip(mnemonics(&labels)).enumerate() {
+        if i > 0 {
+            spans.push(Span::raw(" | "));
+        }
+        let active = i == active_index;
+        if active { spans.push(Span::raw("[")); }
+        spans.extend(mnemonic_spans(l, m, Style::default()));
+        if active { spans.push(Span::raw("]")); }
+        let shortcut = tops.get(i).filter(|t| t.submenu.is_none()).and_then(|t| t.action).and_then(|a| a.shortcut());
+        if let Some(key) = shortcut {
+            spans.push(Span::styled(format!(" {}", key), Style::default().add_modifier(Modifier::DIM)));
+        }
     }
-    let content = parts.join(" | ");
+    let content = Line::from(spans);
     let colors = crate::ui::colors::current();
     let style = if focused { colors.menu_style } else { colors.menu_inactive_style };
     // If the allocated vertical height is too small to show the bordered
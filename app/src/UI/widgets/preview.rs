@@ -1,10 +1,44 @@
-use ratatui::{layout::Rect, widgets::{Block, Paragraph, Borders}, Frame};
+use ratatui::{
+    layout::{Margin, Rect},
+    widgets::{Block, BorderType, Borders, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState},
+    Frame,
+};
 use crate::ui::{UIState, Theme};
 use crate::ui::colors::current as current_colors;
 
 pub fn render(f: &mut Frame, area: Rect, state: &UIState, _theme: &Theme) {
-    let text = state.preview_text.clone().unwrap_or_else(|| "(no preview)".into());
+    let text = state.preview_text.as_deref().map_or_else(|| "(no preview)".to_string(), |t| crate::ui::panels::expand_tabs(t).into_owned());
     let colors = current_colors();
     let p = Paragraph::new(text).block(Block::default().borders(Borders::ALL).title("Preview").style(colors.preview_block_style));
     f.render_widget(p, area);
 }
+
+/// Preview of `panel`'s selected entry, scrolled by its `preview_offset`,
+/// with a scrollbar on the right border. A `focused` preview has a double
+/// border. A followed preview pinned to the end shows its last page, and
+/// JSON, CSV and Markdown previews are styled by `structured_preview`.
+pub fn render_panel(f: &mut Frame, area: Rect, panel: &crate::app::Panel, focused: bool) {
+    let colors = current_colors();
+    let text = if panel.preview.is_empty() { "(no preview)".into() } else { crate::ui::panels::expand_tabs(&panel.preview) };
+    let lines = panel.preview.lines().count();
+    let rows = area.height.saturating_sub(2) as usize;
+    let (offset, title) = match &panel.follow {
+        Some(follow) if follow.pinned => (lines.saturating_sub(rows), "Preview [follow]"),
+        Some(_) => (panel.preview_offset, "Preview [follow, paused]"),
+        None => (panel.preview_offset, "Preview"),
+    };
+    let border = if focused { BorderType::Double } else { BorderType::Plain };
+    let text = match super::structured_preview::styled_lines(&text) {
+        Some(styled) => ratatui::text::Text::from(styled),
+        None => text.into_owned().into(),
+    };
+    let p = Paragraph::new(text)
+        .scroll((offset.min(u16::MAX as usize) as u16, 0))
+        .block(Block::default().borders(Borders::ALL).border_type(border).title(title).style(colors.preview_block_style));
+    f.render_widget(p, area);
+    if lines > rows {
+        let mut state = ScrollbarState::new(lines).position(offset);
+        let bar = Scrollbar::new(ScrollbarOrientation::VerticalRight).begin_symbol(None).end_symbol(None);
+        f.render_stateful_widget(bar, area.inner(Margin { vertical: 1, horizontal: 0 }), &mut state);
+    }
+}
@@ -0,0 +1,157 @@
+//! Styling of format-aware previews (see `app::core::preview_format`).
+//!
+//! `styled_lines` turns a preview with a `Format:` line into styled lines:
+//! JSON keys, strings, numbers and literals in their own colours, the CSV
+//! header row in bold under a dimmed rule, and Markdown headings, emphasis,
+//! code and list bullets. Each preview line stays one line, so scrolling
+//! and folding work on the text as built.
+
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+
+use crate::app::core::preview_format::PreviewFormat;
+
+fn dim() -> Style {
+    Style::default().add_modifier(Modifier::DIM)
+}
+
+/// `text` styled for its format, or `None` for a plain preview.
+pub fn styled_lines(text: &str) -> Option<Vec<Line<'static>>> {
+    let (format, header) = PreviewFormat::of(text)?;
+    let mut in_code = false;
+    let lines = text
+        .lines()
+        .enumerate()
+        .map(|(i, line)| {
+            if i <= header {
+                return Line::styled(line.to_string(), dim());
+            }
+            match format {
+                PreviewFormat::Json => json_line(line),
+                PreviewFormat::Csv => csv_line(line, i == header + 1),
+                PreviewFormat::Markdown => markdown_line(line, &mut in_code),
+            }
+        })
+        .collect();
+    Some(lines)
+}
+
+/// A line of re-indented JSON: keys cyan, strings green, numbers magenta,
+/// `true`, `false` and `null` yellow, punctuation and fold summaries dim.
+fn json_line(line: &str) -> Line<'static> {
+    let mut spans = Vec::new();
+    let mut rest = line;
+    while !rest.is_empty() {
+        let token_len = if rest.starts_with('"') {
+            let mut escaped = false;
+            rest.char_indices()
+                .skip(1)
+                .find(|&(_, c)| {
+                    let end = c == '"' && !escaped;
+                    escaped = c == '\\' && !escaped;
+                    end
+                })
+                .map_or(rest.len(), |(i, _)| i + 1)
+        } else {
+            rest.find(|c: char| "\"{}[],: ".contains(c)).map_or(rest.len(), |i| i.max(1))
+        };
+        let (token, tail) = rest.split_at(token_len);
+        let style = match token.chars().next() {
+            Some('"') if tail.starts_with(':') => Style::default().fg(Color::Cyan),
+            Some('"') => Style::default().fg(Color::Green),
+            Some('-' | '0'..='9') => Style::default().fg(Color::Magenta),
+            _ if matches!(token, "true" | "false" | "null") => Style::default().fg(Color::Yellow),
+            Some(' ') => Style::default(),
+            _ => dim(),
+        };
+        spans.push(Span::styled(token.to_string(), style));
+        rest = tail;
+    }
+    Line::from(spans)
+}
+
+/// A row of the CSV table: the header row bold, the rule and column
+/// separators dim.
+fn csv_line(line: &str, header: bool) -> Line<'static> {
+    if line.starts_with('─') {
+        return Line::styled(line.to_string(), dim());
+    }
+    let cell = if header { Style::default().add_modifier(Modifier::BOLD) } else { Style::default() };
+    let mut spans = Vec::new();
+    for (i, part) in line.split(" │ ").enumerate() {
+        if i > 0 {
+            spans.push(Span::styled(" │ ", dim()));
+        }
+        spans.push(Span::styled(part.to_string(), cell));
+    }
+    Line::from(spans)
+}
+
+/// A Markdown line: headings without their `#`s, bold (level 1 also
+/// underlined); fenced code and quotes dimmed; bullets as `•`; inline
+/// `**bold**`, `*emphasis*`/`_emphasis_` and `` `code` ``.
+fn markdown_line(line: &str, in_code: &mut bool) -> Line<'static> {
+    let trimmed = line.trim_start();
+    if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+        *in_code = !*in_code;
+        return Line::styled(line.to_string(), dim());
+    }
+    if *in_code {
+        return Line::styled(line.to_string(), Style::default().fg(Color::Cyan));
+    }
+    let level = trimmed.chars().take_while(|&c| c == '#').count();
+    if (1..=6).contains(&level) && trimmed[level..].starts_with(' ') {
+        let style = Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD);
+        let style = if level == 1 { style.add_modifier(Modifier::UNDERLINED) } else { style };
+        return Line::styled(trimmed[level + 1..].to_string(), style);
+    }
+    if let Some(quote) = trimmed.strip_prefix('>') {
+        return Line::styled(format!("│{}", quote), dim().add_modifier(Modifier::ITALIC));
+    }
+    let indent = &line[..line.len() - trimmed.len()];
+    match trimmed.strip_prefix("- ").or_else(|| trimmed.strip_prefix("* ")).or_else(|| trimmed.strip_prefix("+ ")) {
+        Some(item) => {
+            let mut spans = vec![Span::raw(format!("{}• ", indent))];
+            spans.extend(inline_spans(item));
+            Line::from(spans)
+        }
+        None => Line::from(inline_spans(line)),
+    }
+}
+
+/// Spans of inline Markdown: `**bold**`, `*emphasis*`, `_emphasis_` and
+/// `` `code` `` lose their markers; anything unclosed stays as written.
+fn inline_spans(text: &str) -> Vec<Span<'static>> {
+    const MARKERS: [(&str, Style); 4] = [
+        ("`", Style::new().fg(Color::Cyan)),
+        ("**", Style::new().add_modifier(Modifier::BOLD)),
+        ("*", Style::new().add_modifier(Modifier::ITALIC)),
+        ("_", Style::new().add_modifier(Modifier::ITALIC)),
+    ];
+    let mut spans = Vec::new();
+    let mut plain = String::new();
+    let mut rest = text;
+    'outer: while let Some(c) = rest.chars().next() {
+        for (marker, style) in MARKERS {
+            let Some(after) = rest.strip_prefix(marker) else { continue };
+            // `_` inside words (snake_case) is not emphasis.
+            if marker == "_" && plain.ends_with(|c: char| c.is_alphanumeric()) {
+                continue;
+            }
+            if let Some(end) = after.find(marker).filter(|&end| end > 0) {
+                if !plain.is_empty() {
+                    spans.push(Span::raw(std::mem::take(&mut plain)));
+                }
+                spans.push(Span::styled(after[..end].to_string(), style));
+                rest = &after[end + marker.len()..];
+                continue 'outer;
+            }
+        }
+        plain.push(c);
+        rest = &rest[c.len_utf8()..];
+    }
+    if !plain.is_empty() {
+        spans.push(Span::raw(plain));
+    }
+    spans
+}
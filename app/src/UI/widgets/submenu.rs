@@ -1,27 +1,35 @@
-use ratatui::{Frame, layout::Rect, widgets::{Block, Borders, List, ListItem}};
+use ratatui::{Frame, layout::Rect, style::{Modifier, Style}, text::{Line, Span}, widgets::{Block, Borders, List, ListItem}};
 use crate::ui::colors::current as current_colors;
+use crate::ui::menu_model::{mnemonics, MenuItem};
+use crate::ui::widgets::main_menu::mnemonic_spans;
 
 /// Render a small vertical submenu inside the header area when a top label
 /// is open. The submenu will display items stacked top->down and highlight
-/// the selected submenu index when present.
-pub fn render(f: &mut Frame, area: Rect, labels: &Vec<String>, selected: Option<usize>) {
-    if labels.is_empty() { return; }
+/// the selected submenu index when present. Mnemonics are underlined and
+/// each item's shortcut key follows its label.
+pub fn render(f: &mut Frame, area: Rect, items: &[MenuItem], selected: Option<usize>) {
+    if items.is_empty() { return; }
 
     // Render the submenu as a simple list inside the supplied area. If the
     // area is too small this will naturally truncate.
     let colors = current_colors();
-    let items: Vec<ListItem> = labels
+    let labels: Vec<&str> = items.iter().map(|it| it.label.as_str()).collect();
+    let width = labels.iter().map(|l| l.chars().count()).max().unwrap_or(0);
+    let rows: Vec<ListItem> = items
         .iter()
+        .zip(mnemonics(&labels))
         .enumerate()
-        .map(|(i, s)| {
-            if Some(i) == selected {
-                ListItem::new(format!("> {}", s))
-            } else {
-                ListItem::new(s.clone())
+        .map(|(i, (item, m))| {
+            let mut spans = vec![Span::raw(if Some(i) == selected { "> " } else { "" })];
+            spans.extend(mnemonic_spans(&item.label, m, Style::default()));
+            if let Some(key) = item.action.and_then(|a| a.shortcut()) {
+                let pad = width - item.label.chars().count() + 2;
+                spans.push(Span::styled(format!("{:pad$}{}", "", key, pad = pad), Style::default().add_modifier(Modifier::DIM)));
             }
+            ListItem::new(Line::from(spans))
         })
         .collect();
 
-    let list = List::new(items).block(Block::default().borders(Borders::ALL).title("submenu")).style(colors.menu_inactive_style);
+    let list = List::new(rows).block(Block::default().borders(Borders::ALL).title("submenu")).style(colors.menu_inactive_style);
     f.render_widget(list, area);
 }
@@ -0,0 +1,36 @@
+use ratatui::{layout::Rect, style::{Modifier, Style}, text::{Line, Span}, widgets::{Block, BorderType, Borders, Paragraph}, Frame};
+
+use crate::app::core::App;
+use crate::ui::colors::current as current_colors;
+
+/// Directory tree sidebar (see `app::core::tree`). The active panel's
+/// directory is bold; the selected node is highlighted while the tree has
+/// the focus.
+pub fn render(f: &mut Frame, area: Rect, app: &App) {
+    let colors = current_colors();
+    let tree = &app.tree;
+    let height = area.height.saturating_sub(2) as usize;
+    let offset = tree.view_offset(height);
+    let cwd = &app.active_panel().cwd;
+    let lines: Vec<Line> = tree
+        .nodes
+        .iter()
+        .enumerate()
+        .skip(offset)
+        .take(height)
+        .map(|(i, node)| {
+            let marker = if node.expanded { "▾ " } else { "▸ " };
+            let mut style = Style::default();
+            if &node.path == cwd {
+                style = style.add_modifier(Modifier::BOLD);
+            }
+            if i == tree.selected && tree.focused {
+                style = style.patch(colors.panel_selected_style);
+            }
+            Line::from(vec![Span::raw("  ".repeat(node.depth)), Span::raw(marker), Span::styled(node.name(), style)])
+        })
+        .collect();
+    let border = if tree.focused { BorderType::Double } else { BorderType::Plain };
+    let p = Paragraph::new(lines).block(Block::default().borders(Borders::ALL).border_type(border).title("Tree").style(colors.preview_block_style));
+    f.render_widget(p, area);
+}
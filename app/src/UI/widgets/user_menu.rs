@@ -0,0 +1,27 @@
+use ratatui::{layout::Rect, text::Line, widgets::{Block, Borders, Clear, Paragraph}, Frame};
+use crate::app::settings::user_menu::UserMenuEntry;
+use crate::ui::colors::current as current_colors;
+use crate::ui::modal::centered_rect;
+
+/// Render the user menu as a centered modal with `selected` highlighted.
+pub fn render(f: &mut Frame, area: Rect, entries: &[UserMenuEntry], selected: usize) {
+    let colors = current_colors();
+    let rows: Vec<Line> = entries
+        .iter()
+        .enumerate()
+        .map(|(i, e)| {
+            let key = e.key.as_deref().unwrap_or("");
+            let line = Line::from(format!(" {:>3}  {}", key, e.title));
+            if i == selected { line.style(colors.panel_selected_style) } else { line }
+        })
+        .collect();
+    let width = entries.iter().map(|e| e.title.chars().count() + 10).max().unwrap_or(0).max(30) as u16;
+    let rect = centered_rect(area, width, entries.len() as u16 + 2);
+    // Keep the selection visible when the menu is taller than the area.
+    let skip = selected.saturating_sub(rect.height.saturating_sub(3) as usize) as u16;
+    f.render_widget(Clear, rect);
+    let p = Paragraph::new(rows)
+        .scroll((skip, 0))
+        .block(Block::default().borders(Borders::ALL).title(" User menu ").style(colors.preview_block_style));
+    f.render_widget(p, rect);
+}
@@ -0,0 +1,40 @@
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    widgets::{Clear, Paragraph},
+    Frame,
+};
+
+/// Text of the banner for failing watcher refreshes, one line per panel,
+/// or `None` while every panel refreshes fine.
+pub fn refresh_warning(app: &crate::app::core::App, now: std::time::Instant) -> Option<String> {
+    let key = crate::app::settings::runtime_keybinds::get().chords("refresh").first().map(|k| k.to_string());
+    let retry_now = key.map(|k| format!("; {} retries now", k)).unwrap_or_default();
+    let lines: Vec<String> = [(&app.left, "Left"), (&app.right, "Right")]
+        .into_iter()
+        .filter_map(|(p, side)| {
+            let failure = p.refresh_failure.as_ref()?;
+            let wait = failure.retry_at.saturating_duration_since(now).as_secs_f32().ceil() as u64;
+            let times = if failure.failures == 1 { "once".to_string() } else { format!("{} times", failure.failures) };
+            Some(format!(
+                "⚠ {}: cannot refresh {}: {} (failed {}, retrying in {}s{})",
+                side,
+                p.cwd.display(),
+                failure.error,
+                times,
+                wait,
+                retry_now
+            ))
+        })
+        .collect();
+    (!lines.is_empty()).then(|| lines.join("\n"))
+}
+
+/// Render `text` over the bottom rows of `area`.
+pub fn render(f: &mut Frame, area: Rect, text: &str) {
+    let height = (text.lines().count() as u16).min(area.height);
+    let rect = Rect::new(area.x, area.y + area.height - height, area.width, height);
+    f.render_widget(Clear, rect);
+    let style = Style::default().fg(Color::Black).bg(Color::Yellow).add_modifier(Modifier::BOLD);
+    f.render_widget(Paragraph::new(text.to_string()).style(style), rect);
+}
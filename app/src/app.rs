@@ -16,6 +16,9 @@ pub struct StartOptions {
 	/// Optional directory to start in. When `None`, the current working
 	/// directory is used.
 	pub start_dir: Option<PathBuf>,
+	/// Optional directory for the right panel. When `None` it starts in the
+	/// same directory as the left panel.
+	pub right_dir: Option<PathBuf>,
 	/// Optional initial mouse enabled flag. When `None`, persisted
 	/// settings (or defaults) are used.
 	pub mouse_enabled: Option<bool>,
@@ -31,10 +34,28 @@ pub struct StartOptions {
 	/// Optional verbosity count (mapped from `-v`). When `None` no change
 	/// is applied to logging beyond environment defaults.
 	pub verbosity: Option<u8>,
+
+	/// Optional single-pane override: show only the active panel at full
+	/// width. When `None` the persisted setting is used.
+	pub single_pane: Option<bool>,
+
+	/// Optional settings file to use instead of the default location.
+	pub config_path: Option<PathBuf>,
+
+	/// Log how long each startup phase took (`--profile-startup`).
+	pub profile_startup: bool,
+
+	/// Optional file to select in the left panel once it is listed
+	/// (`--remote reveal` with no instance running).
+	pub reveal: Option<PathBuf>,
+
+	/// Run as a file picker: Enter confirms the files to print on exit
+	/// (`--choose-files`).
+	pub choose_files: bool,
 }
 
-pub use core::panel::Panel;
+pub use core::panel::{DirPosition, Panel};
 pub use core::App;
-pub use types::{Action, Entry, InputKind, Mode, Side, SortKey};
+pub use types::{Action, Entry, InputKind, Mode, OtherPanel, PanelLayout, ScrollTarget, Side, SortKey};
 // Deprecated compatibility shim: keep `crate::app::path` working for older code/tests.
 pub use crate::fs_op::path;
@@ -0,0 +1,40 @@
+//! Internal clipboard for `yank` (Ctrl+C), `cut` (Ctrl+X) and `paste`
+//! (Ctrl+V).
+//!
+//! Yanking or cutting remembers the marked entries of the active panel (or
+//! the one under the cursor). Pasting copies or moves them into the active
+//! panel's directory, typically after switching to the other panel with
+//! Tab. A cut is pasted once; yanked paths can be pasted again. The paths
+//! are also put on the system clipboard as text, one per line.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use crate::fs_op::vfs::VfsProvider;
+
+/// Paths waiting to be pasted.
+#[derive(Clone, Debug)]
+pub struct Clipboard {
+    pub paths: Vec<PathBuf>,
+    /// Pasting moves the paths instead of copying them.
+    pub cut: bool,
+    /// Backend of the panel the paths were taken from.
+    pub vfs: Arc<dyn VfsProvider>,
+}
+
+impl Clipboard {
+    /// The paths as text for the system clipboard, one per line.
+    pub fn text(&self) -> String {
+        self.paths.iter().map(|p| p.display().to_string()).collect::<Vec<_>>().join("\n")
+    }
+
+    /// Short description for the status line, e.g. `2 cut` or
+    /// `a.txt yanked`.
+    pub fn summary(&self) -> String {
+        let verb = if self.cut { "cut" } else { "yanked" };
+        match self.paths.as_slice() {
+            [one] => format!("{} {}", one.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_else(|| one.display().to_string()), verb),
+            many => format!("{} {}", many.len(), verb),
+        }
+    }
+}
@@ -0,0 +1,50 @@
+//! Source of the current time for time-dependent features.
+//!
+//! Code that compares against "now" (job timestamps, relative dates,
+//! overwrite-if-newer checks) calls `clock::now()` instead of
+//! `SystemTime::now()` so tests can substitute a `Clock` with
+//! `set_thread_clock` (see `test_helpers::FakeClock`) rather than sleeping.
+//! The override is per thread, so a test's fake clock never leaks into
+//! tests running in parallel.
+
+use std::cell::RefCell;
+use std::fmt;
+use std::sync::Arc;
+use std::time::SystemTime;
+
+use chrono::{DateTime, Local};
+
+/// Something that can tell the time.
+pub trait Clock: fmt::Debug + Send + Sync {
+    fn now(&self) -> SystemTime;
+}
+
+/// The real wall clock.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+}
+
+thread_local! {
+    static THREAD_CLOCK: RefCell<Option<Arc<dyn Clock>>> = const { RefCell::new(None) };
+}
+
+/// Replace the clock used by `now()` on the current thread (`None`
+/// restores the system clock). Returns the previous override.
+pub fn set_thread_clock(clock: Option<Arc<dyn Clock>>) -> Option<Arc<dyn Clock>> {
+    THREAD_CLOCK.with(|c| c.replace(clock))
+}
+
+/// The current time according to the thread's clock.
+pub fn now() -> SystemTime {
+    THREAD_CLOCK.with(|c| c.borrow().as_ref().map(|clock| clock.now())).unwrap_or_else(SystemTime::now)
+}
+
+/// `now()` in local time.
+pub fn now_local() -> DateTime<Local> {
+    DateTime::from(now())
+}
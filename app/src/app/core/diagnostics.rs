@@ -0,0 +1,132 @@
+//! Performance counters behind the diagnostics overlay (F12).
+//!
+//! The event loop records how long each frame took to draw and
+//! `refresh_panel` records how long each directory took to load and how
+//! many entries the metadata cache (`fs_op::cache`) served, so the overlay
+//! can show a hit rate. It also names the keyboard protocol the terminal
+//! was set up with and the last key chord received, so users can check
+//! whether a binding such as `Ctrl+Shift+k` reaches the app. The overlay gives users concrete numbers to attach to
+//! performance reports.
+
+use std::time::Duration;
+
+use crate::app::settings::keymap::KeyChord;
+use crate::app::types::Side;
+use crate::input::{keyboard_protocol, Key};
+
+/// Counters shown by the diagnostics overlay.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Diagnostics {
+    /// Whether the overlay is drawn.
+    pub visible: bool,
+    /// Number of frames drawn so far.
+    pub frames: u64,
+    /// Render time of the most recent frame.
+    pub last_frame: Duration,
+    /// Slowest frame seen so far.
+    pub max_frame: Duration,
+    /// Most recent directory load: panel side, entry count and duration.
+    pub last_load: Option<(Side, usize, Duration)>,
+    /// Cache lookups that were served from a cache.
+    pub cache_hits: u64,
+    /// Cache lookups that had to go to the filesystem.
+    pub cache_misses: u64,
+    /// Most recent key, as the chord bindings see it.
+    pub last_key: Option<KeyChord>,
+}
+
+impl Diagnostics {
+    /// Record the time taken to draw one frame.
+    pub fn record_frame(&mut self, elapsed: Duration) {
+        self.frames += 1;
+        self.last_frame = elapsed;
+        self.max_frame = self.max_frame.max(elapsed);
+    }
+
+    /// Record a directory load of `entries` entries for `side`.
+    pub fn record_load(&mut self, side: Side, entries: usize, elapsed: Duration) {
+        self.last_load = Some((side, entries, elapsed));
+    }
+
+    /// Record one cache lookup.
+    pub fn record_cache(&mut self, hit: bool) {
+        if hit {
+            self.cache_hits += 1;
+        } else {
+            self.cache_misses += 1;
+        }
+    }
+
+    /// Record `hits` and `misses` lookups at once.
+    pub fn record_cache_counts(&mut self, hits: u64, misses: u64) {
+        self.cache_hits += hits;
+        self.cache_misses += misses;
+    }
+
+    /// Record a key received from the terminal.
+    pub fn record_key(&mut self, key: &Key) {
+        self.last_key = Some(KeyChord::from_key(key));
+    }
+
+    /// Cache hit rate in percent, or `None` before the first lookup.
+    pub fn cache_hit_rate(&self) -> Option<f64> {
+        let total = self.cache_hits + self.cache_misses;
+        (total > 0).then(|| self.cache_hits as f64 * 100.0 / total as f64)
+    }
+
+    /// Overlay text. `left`/`right` are the current panel entry counts.
+    pub fn lines(&self, left: usize, right: usize) -> Vec<String> {
+        let ms = |d: Duration| format!("{:.2} ms", d.as_secs_f64() * 1000.0);
+        let load = match self.last_load {
+            Some((side, n, d)) => format!("{} ({} entries, {})", ms(d), n, side),
+            None => "-".to_string(),
+        };
+        let cache = match self.cache_hit_rate() {
+            Some(rate) => format!("{:.1}% ({} hits, {} misses)", rate, self.cache_hits, self.cache_misses),
+            None => "n/a".to_string(),
+        };
+        vec![
+            format!("frame:   {} (max {}, #{})", ms(self.last_frame), ms(self.max_frame), self.frames),
+            format!("load:    {}", load),
+            format!("entries: {} left, {} right", left, right),
+            format!("cache:   {}", cache),
+            format!(
+                "keys:    {}, last {}",
+                keyboard_protocol().describe(),
+                self.last_key.map_or_else(|| "-".to_string(), |k| k.to_string())
+            ),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tracks_frames_loads_and_cache_rate() {
+        let mut d = Diagnostics::default();
+        assert_eq!(d.cache_hit_rate(), None);
+        d.record_frame(Duration::from_millis(4));
+        d.record_frame(Duration::from_millis(2));
+        assert_eq!((d.frames, d.last_frame, d.max_frame), (2, Duration::from_millis(2), Duration::from_millis(4)));
+        d.record_load(Side::Right, 42, Duration::from_millis(7));
+        d.record_cache(true);
+        d.record_cache(true);
+        d.record_cache(true);
+        d.record_cache(false);
+        assert_eq!(d.cache_hit_rate(), Some(75.0));
+        let lines = d.lines(3, 42);
+        assert!(lines[0].starts_with("frame:   2.00 ms (max 4.00 ms, #2)"));
+        assert_eq!(lines[1], "load:    7.00 ms (42 entries, Right)");
+        assert_eq!(lines[2], "entries: 3 left, 42 right");
+        assert!(lines[3].starts_with("cache:   75.0%"));
+        assert!(lines[4].ends_with(", last -"), "{}", lines[4]);
+        let shift_tab = Key {
+            code: crate::input::KeyCode::Tab,
+            modifiers: crate::input::KeyModifiers { shift: true, ..Default::default() },
+        };
+        d.record_key(&shift_tab);
+        assert!(d.lines(0, 0)[4].ends_with(", last Shift+Tab"));
+    }
+}
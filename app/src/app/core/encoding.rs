@@ -0,0 +1,132 @@
+//! Character set detection for the preview pane and quick view.
+//!
+//! `decode` turns the sampled bytes of a file into text. A byte-order mark
+//! decides the encoding first, then UTF-16 without one (spotted by its NUL
+//! bytes), then UTF-8; anything else is guessed with `chardetng`
+//! (Windows-1252 for Latin-1 text, Shift_JIS, GBK, ...). The encoding
+//! picker (`Mode::EncodingPicker`) stores an override per panel in
+//! `Panel::preview_encoding`.
+
+use encoding_rs::{Encoding, UTF_16BE, UTF_16LE, UTF_8};
+
+/// Encodings offered by the picker, after "Auto".
+pub const CHOICES: &[&str] = &[
+    "UTF-8",
+    "UTF-16LE",
+    "UTF-16BE",
+    "windows-1252",
+    "ISO-8859-2",
+    "windows-1251",
+    "KOI8-R",
+    "Shift_JIS",
+    "EUC-JP",
+    "GBK",
+    "Big5",
+    "EUC-KR",
+];
+
+/// Bytes looked at when guessing.
+const SNIFF_BYTES: usize = 64 * 1024;
+
+/// Text decoded from a file sample.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Decoded {
+    pub text: String,
+    pub encoding: &'static Encoding,
+    /// The encoding was chosen in the picker rather than detected.
+    pub chosen: bool,
+}
+
+/// The encoding named `label` (any WHATWG label, case-insensitive, e.g.
+/// `latin1` or `sjis`).
+pub fn by_name(label: &str) -> Option<&'static Encoding> {
+    Encoding::for_label(label.trim().as_bytes())
+}
+
+/// Guess the encoding of `bytes`, or `None` when they look binary.
+pub fn detect(bytes: &[u8]) -> Option<&'static Encoding> {
+    let bytes = &bytes[..bytes.len().min(SNIFF_BYTES)];
+    if let Some((encoding, _)) = Encoding::for_bom(bytes) {
+        return Some(encoding);
+    }
+    if let Some(encoding) = utf16_without_bom(bytes) {
+        return Some(encoding);
+    }
+    if bytes.contains(&0) {
+        return None;
+    }
+    // A sample can end inside a character; that still counts as UTF-8.
+    match std::str::from_utf8(bytes) {
+        Ok(_) => return Some(UTF_8),
+        Err(e) if e.error_len().is_none() => return Some(UTF_8),
+        Err(_) => {}
+    }
+    let mut detector = chardetng::EncodingDetector::new();
+    detector.feed(bytes, true);
+    Some(detector.guess(None, true))
+}
+
+/// UTF-16 text without a byte-order mark: mostly ASCII, so one byte of
+/// nearly every pair is NUL and the other almost never is.
+fn utf16_without_bom(bytes: &[u8]) -> Option<&'static Encoding> {
+    let pairs = bytes.len() / 2;
+    if pairs < 2 {
+        return None;
+    }
+    let (mut even, mut odd) = (0, 0);
+    for pair in bytes.chunks_exact(2) {
+        even += usize::from(pair[0] == 0);
+        odd += usize::from(pair[1] == 0);
+    }
+    let mostly = |n: usize| n * 10 >= pairs * 4;
+    let rarely = |n: usize| n * 20 <= pairs;
+    if mostly(odd) && rarely(even) {
+        Some(UTF_16LE)
+    } else if mostly(even) && rarely(odd) {
+        Some(UTF_16BE)
+    } else {
+        None
+    }
+}
+
+/// Decode `bytes` as `chosen`, or as the detected encoding. Returns `None`
+/// when nothing was chosen and the bytes look binary: NUL bytes outside
+/// UTF-16, or text that is mostly control characters once decoded.
+pub fn decode(bytes: &[u8], chosen: Option<&'static Encoding>) -> Option<Decoded> {
+    let encoding = match chosen {
+        Some(encoding) => encoding,
+        None => detect(bytes)?,
+    };
+    let (text, _) = encoding.decode_with_bom_removal(bytes);
+    if chosen.is_none() && super::preview::mostly_control(&text) {
+        return None;
+    }
+    Some(Decoded { text: text.into_owned(), encoding, chosen: chosen.is_some() })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_boms_utf16_legacy_text_and_binary() {
+        assert_eq!(detect(b"plain ascii"), Some(UTF_8));
+        assert_eq!(detect(b"\xef\xbb\xbfbom"), Some(UTF_8));
+        assert_eq!(detect(b"h\0e\0l\0l\0o\0\n\0"), Some(UTF_16LE));
+        assert_eq!(detect(b"\0h\0e\0l\0l\0o\0\n"), Some(UTF_16BE));
+        assert_eq!(detect(b"\x7fELF\x02\x01\x01\0\0\0\0\0\0\0\0\0\x03\0>\0"), None);
+        // A sample cut inside `é` is still UTF-8.
+        assert_eq!(detect(b"caf\xc3"), Some(UTF_8));
+
+        let latin1 = decode(b"Caf\xe9 cr\xe8me br\xfbl\xe9e, na\xefve fa\xe7ade", None).unwrap();
+        assert_eq!(latin1.text, "Café crème brûlée, naïve façade");
+        assert!(!latin1.chosen);
+
+        let (sjis, _, _) = encoding_rs::SHIFT_JIS.encode("日本語のテキストファイルです。これは文字コードの判定のテストです。");
+        let decoded = decode(&sjis, None).unwrap();
+        assert_eq!(decoded.encoding, encoding_rs::SHIFT_JIS);
+
+        let forced = decode(b"\x80\x81", Some(by_name("latin1").unwrap())).unwrap();
+        assert_eq!((forced.encoding.name(), forced.chosen), ("windows-1252", true));
+    }
+}
@@ -0,0 +1,119 @@
+//! Tail-follow mode of the preview (`F` in the focused preview, `:follow`).
+//!
+//! A followed preview shows the end of the file instead of its start and is
+//! re-read when the file grows: right away when the watcher reports the
+//! change, otherwise when `App::poll_follow` notices a new size or
+//! modification time (remote backends and builds without `fs-watch`). While
+//! `pinned` the preview stays scrolled to the end; scrolling up unpins it
+//! and scrolling back to the end pins it again.
+
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Local};
+
+use super::preview::{with_encoding_line, PreviewError};
+use super::App;
+use crate::app::types::Side;
+use crate::fs_op::vfs::VfsProvider;
+
+/// How often `poll_follow` checks a followed file for changes.
+pub const FOLLOW_POLL: Duration = Duration::from_millis(500);
+
+/// First line of a followed preview that does not start at the top.
+pub const EARLIER_LINES: &str = "... (earlier lines not shown)";
+
+/// A panel's preview following the end of a file.
+#[derive(Clone, Debug)]
+pub struct Follow {
+    /// File followed; selecting another entry stops following.
+    pub path: PathBuf,
+    /// Keep the preview scrolled to the end of the file.
+    pub pinned: bool,
+    /// Size and modification time when the preview was last read.
+    seen: (u64, Option<DateTime<Local>>),
+    /// When `poll_follow` last looked at the file.
+    polled: Instant,
+}
+
+/// The last `max_bytes` of `path` as preview text, starting at a whole
+/// line, decoded like `build_file_preview_as`.
+pub fn build_tail_preview(
+    vfs: &dyn VfsProvider,
+    path: &Path,
+    max_bytes: usize,
+    chosen: Option<&'static encoding_rs::Encoding>,
+) -> Result<String, PreviewError> {
+    let size = vfs.stat(path).map_err(|_| PreviewError::Unreadable)?.size;
+    let skip = size.saturating_sub(max_bytes as u64);
+    let mut buf = Vec::with_capacity(max_bytes.min(size as usize));
+    let read = if vfs.is_local() {
+        std::fs::File::open(path).and_then(|mut f| {
+            f.seek(SeekFrom::Start(skip))?;
+            f.take(max_bytes as u64).read_to_end(&mut buf)
+        })
+    } else {
+        vfs.open(path).and_then(|mut f| {
+            std::io::copy(&mut (&mut f).take(skip), &mut std::io::sink())?;
+            f.take(max_bytes as u64).read_to_end(&mut buf)
+        })
+    };
+    read.map_err(|_| PreviewError::Unreadable)?;
+    let mut decoded = super::encoding::decode(&buf, chosen).ok_or(PreviewError::Binary)?;
+    if skip > 0 {
+        // The sample starts inside a line; drop what is left of it.
+        let rest = decoded.text.split_once('\n').map_or("", |(_, rest)| rest);
+        decoded.text = format!("{}\n{}", EARLIER_LINES, rest);
+    }
+    Ok(with_encoding_line(decoded))
+}
+
+impl App {
+    /// Start or stop following the file selected in the active panel. A
+    /// new follow shows the end of the file, pinned.
+    pub fn toggle_follow(&mut self) {
+        let side = self.active;
+        let panel = self.active_panel_mut();
+        if panel.follow.take().is_some() {
+            self.update_preview_for(side);
+            return;
+        }
+        let Some(e) = panel.selected_entry().filter(|e| !e.is_dir) else {
+            return;
+        };
+        let seen = (e.size, e.modified);
+        panel.follow = Some(Follow { path: e.path.clone(), pinned: true, seen, polled: Instant::now() });
+        self.reload_preview_for(side);
+    }
+
+    /// Re-read followed previews whose file changed size or modification
+    /// time, at most every `FOLLOW_POLL`. Called from `poll_listings`.
+    pub fn poll_follow(&mut self, now: Instant) {
+        for side in [Side::Left, Side::Right] {
+            let panel = self.panel_mut(side);
+            let Some(follow) = panel.follow.as_mut().filter(|f| now.duration_since(f.polled) >= FOLLOW_POLL) else {
+                continue;
+            };
+            follow.polled = now;
+            let Ok(entry) = panel.vfs.stat(&follow.path) else {
+                continue;
+            };
+            let seen = (entry.size, entry.modified);
+            if seen != follow.seen {
+                follow.seen = seen;
+                self.reload_preview_for(side);
+            }
+        }
+    }
+
+    /// Before scrolling a pinned followed preview up from the end, start
+    /// from the last `page` lines that were shown and unpin it.
+    pub fn unpin_follow(&mut self, page: usize) {
+        let panel = self.active_panel_mut();
+        if let Some(follow) = panel.follow.as_mut().filter(|f| f.pinned) {
+            follow.pinned = false;
+            panel.preview_offset = panel.preview.lines().count().saturating_sub(page);
+        }
+    }
+}
@@ -0,0 +1,267 @@
+//! Frecency of visited directories, for `:jump`.
+//!
+//! Every directory a panel lists gets its rank bumped and its access time
+//! updated (`App::apply_listing`). A directory's score weighs the rank by
+//! how recently it was visited, the same way zoxide does, so `:jump pro`
+//! goes to the project visited most, and most lately, whose path matches
+//! `pro`. The ranks are saved to `frecency.toml` in `user_cache_dir()` when
+//! the event loop exits; like the job history they are only persisted when
+//! `path` is set, which the event loop does at startup.
+//!
+//! An existing zoxide database (`db.zo`) can be merged in with
+//! `:jump-import`.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::app::settings::config_dirs::user_cache_dir;
+use crate::fs_op::path::fuzzy_score;
+
+/// File name of the frecency database inside the cache directory.
+pub const FRECENCY_FILE: &str = "frecency.toml";
+
+/// Once the ranks add up to more than this they are all scaled down and
+/// directories whose rank drops below 1 are forgotten.
+const MAX_TOTAL_RANK: f64 = 10_000.0;
+
+const HOUR: u64 = 60 * 60;
+const DAY: u64 = 24 * HOUR;
+const WEEK: u64 = 7 * DAY;
+
+/// Version tag at the start of a zoxide `db.zo` file.
+const ZOXIDE_DB_VERSION: u32 = 3;
+
+/// One visited directory.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct DirRank {
+    pub path: PathBuf,
+    /// Grows by one per visit.
+    pub rank: f64,
+    /// Last visit as seconds since the Unix epoch.
+    pub last_access: u64,
+}
+
+impl DirRank {
+    /// Rank weighted by how long ago (at `now`) the directory was visited.
+    pub fn score(&self, now: u64) -> f64 {
+        match now.saturating_sub(self.last_access) {
+            age if age < HOUR => self.rank * 4.0,
+            age if age < DAY => self.rank * 2.0,
+            age if age < WEEK => self.rank / 2.0,
+            _ => self.rank / 4.0,
+        }
+    }
+}
+
+/// Ranks of the visited directories.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct Frecency {
+    #[serde(default, rename = "dir")]
+    pub dirs: Vec<DirRank>,
+    /// File the ranks are saved to; `None` keeps them in memory only.
+    #[serde(skip)]
+    pub path: Option<PathBuf>,
+}
+
+/// Default location of the frecency database.
+pub fn frecency_path() -> PathBuf {
+    user_cache_dir().join(FRECENCY_FILE)
+}
+
+/// Current time as seconds since the Unix epoch.
+fn now_secs() -> u64 {
+    super::clock::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0)
+}
+
+impl Frecency {
+    /// Load the ranks from `path` and save future ones there. A missing
+    /// file yields no ranks.
+    pub fn load_from(path: PathBuf) -> Result<Self> {
+        let mut db = if path.exists() {
+            let raw = fs::read_to_string(&path)
+                .with_context(|| format!("failed to read frecency database {}", path.display()))?;
+            toml::from_str::<Frecency>(&raw)
+                .with_context(|| format!("failed to parse frecency TOML in {}", path.display()))?
+        } else {
+            Frecency::default()
+        };
+        db.path = Some(path);
+        Ok(db)
+    }
+
+    /// Write the ranks to `path` (no-op when unset).
+    pub fn save(&self) -> Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("failed to create cache dir {}", parent.display()))?;
+        }
+        let s = toml::to_string_pretty(self).context("failed to serialize frecency database")?;
+        fs::write(path, s).with_context(|| format!("failed to write frecency database {}", path.display()))
+    }
+
+    /// Record a visit of `dir`.
+    pub fn visit(&mut self, dir: &Path) {
+        self.add(dir, 1.0, now_secs());
+    }
+
+    /// Add `rank` to `dir`, keeping the later of the access times.
+    fn add(&mut self, dir: &Path, rank: f64, last_access: u64) {
+        match self.dirs.iter_mut().find(|d| d.path == dir) {
+            Some(d) => {
+                d.rank += rank;
+                d.last_access = d.last_access.max(last_access);
+            }
+            None => self.dirs.push(DirRank { path: dir.to_path_buf(), rank, last_access }),
+        }
+        self.age();
+    }
+
+    /// Scale the ranks down once they add up to more than `MAX_TOTAL_RANK`.
+    fn age(&mut self) {
+        let total: f64 = self.dirs.iter().map(|d| d.rank).sum();
+        if total > MAX_TOTAL_RANK {
+            let factor = 0.9 * MAX_TOTAL_RANK / total;
+            for d in &mut self.dirs {
+                d.rank *= factor;
+            }
+            self.dirs.retain(|d| d.rank >= 1.0);
+        }
+    }
+
+    /// Existing directories other than `exclude` matching `query`, best
+    /// first. Each word of `query` must fuzzy-match the path in order, and
+    /// the last word its final component.
+    pub fn matches(&self, query: &str, exclude: &Path) -> Vec<&DirRank> {
+        let words: Vec<&str> = query.split_whitespace().collect();
+        let now = now_secs();
+        let mut found: Vec<&DirRank> = self
+            .dirs
+            .iter()
+            .filter(|d| d.path != exclude && words_match(&words, &d.path) && d.path.is_dir())
+            .collect();
+        found.sort_by(|a, b| b.score(now).total_cmp(&a.score(now)));
+        found
+    }
+
+    /// Merge the directories of a zoxide database (`db.zo`) into these
+    /// ranks. Returns how many were read.
+    pub fn import_zoxide(&mut self, path: &Path) -> Result<usize> {
+        let bytes = fs::read(path).with_context(|| format!("failed to read zoxide database {}", path.display()))?;
+        let dirs = parse_zoxide_db(&bytes).with_context(|| format!("failed to parse zoxide database {}", path.display()))?;
+        let count = dirs.len();
+        for d in dirs {
+            self.add(&d.path, d.rank, d.last_access);
+        }
+        Ok(count)
+    }
+}
+
+/// Whether every word fuzzy-matches `path` in order, the last one within
+/// its final component.
+fn words_match(words: &[&str], path: &Path) -> bool {
+    let Some((last, rest)) = words.split_last() else {
+        return true;
+    };
+    let name = path.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+    if fuzzy_score(last, &name).is_none() {
+        return false;
+    }
+    let text = path.to_string_lossy();
+    let mut from = 0;
+    for word in rest {
+        // Find the shortest prefix of the remaining path the word matches.
+        let Some(end) = text[from..].char_indices().map(|(i, c)| from + i + c.len_utf8()).find(|&end| fuzzy_score(word, &text[from..end]).is_some()) else {
+            return false;
+        };
+        from = end;
+    }
+    true
+}
+
+/// Default location of the zoxide database: `$_ZO_DATA_DIR/db.zo`, else
+/// `zoxide/db.zo` in the platform's local data directory.
+pub fn zoxide_db_path() -> Option<PathBuf> {
+    if let Some(dir) = std::env::var_os("_ZO_DATA_DIR") {
+        return Some(PathBuf::from(dir).join("db.zo"));
+    }
+    directories_next::BaseDirs::new().map(|b| b.data_local_dir().join("zoxide").join("db.zo"))
+}
+
+/// Decode a zoxide `db.zo` file: a `u32` version, then a `u64` count of
+/// entries, each a `u64`-length UTF-8 path, an `f64` rank and a `u64`
+/// access time, all little-endian.
+pub fn parse_zoxide_db(bytes: &[u8]) -> Result<Vec<DirRank>> {
+    let mut pos = 0;
+    let mut take = |n: usize| -> Result<&[u8]> {
+        let Some(chunk) = bytes.get(pos..pos + n) else {
+            bail!("unexpected end of file");
+        };
+        pos += n;
+        Ok(chunk)
+    };
+    let version = u32::from_le_bytes(take(4)?.try_into()?);
+    if version != ZOXIDE_DB_VERSION {
+        bail!("unsupported zoxide database version {}", version);
+    }
+    let count = u64::from_le_bytes(take(8)?.try_into()?);
+    let mut dirs = Vec::new();
+    for _ in 0..count {
+        let len = u64::from_le_bytes(take(8)?.try_into()?) as usize;
+        let path = std::str::from_utf8(take(len)?)?.to_string();
+        let rank = f64::from_le_bytes(take(8)?.try_into()?);
+        let last_access = u64::from_le_bytes(take(8)?.try_into()?);
+        dirs.push(DirRank { path: PathBuf::from(path), rank, last_access });
+    }
+    Ok(dirs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recent_visits_outscore_old_ones() {
+        let now = 10 * WEEK;
+        let old = DirRank { path: "/a".into(), rank: 10.0, last_access: now - 2 * WEEK };
+        let fresh = DirRank { path: "/b".into(), rank: 2.0, last_access: now - 60 };
+        assert!(fresh.score(now) > old.score(now));
+    }
+
+    #[test]
+    fn words_match_in_order_with_the_last_in_the_name() {
+        let path = Path::new("/home/me/projects/rust_mc");
+        assert!(words_match(&["rmc"], path));
+        assert!(words_match(&["proj", "rust"], path));
+        assert!(!words_match(&["rust", "proj"], path));
+        assert!(!words_match(&["proj"], path));
+    }
+
+    #[test]
+    fn parses_a_zoxide_database() {
+        let mut bytes = ZOXIDE_DB_VERSION.to_le_bytes().to_vec();
+        bytes.extend(1u64.to_le_bytes());
+        bytes.extend(4u64.to_le_bytes());
+        bytes.extend(b"/srv");
+        bytes.extend(12.5f64.to_le_bytes());
+        bytes.extend(1_700_000_000u64.to_le_bytes());
+        let dirs = parse_zoxide_db(&bytes).unwrap();
+        assert_eq!(dirs, [DirRank { path: "/srv".into(), rank: 12.5, last_access: 1_700_000_000 }]);
+        assert!(parse_zoxide_db(&bytes[..bytes.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn ranks_age_once_the_total_is_too_high() {
+        let mut db = Frecency::default();
+        db.add(Path::new("/big"), MAX_TOTAL_RANK, 0);
+        db.add(Path::new("/small"), 1.0, 0);
+        assert_eq!(db.dirs.len(), 1);
+        assert!(db.dirs[0].rank < MAX_TOTAL_RANK);
+    }
+}
@@ -0,0 +1,100 @@
+//! Directory totals and file details for the info panel.
+//!
+//! The info panel (`i`, see `ui::widgets::info`) shows the recursive entry
+//! count and size of a selected directory, and for a file its detected type
+//! (`fs_op::filetype`) and the details of its format (`fs_op::format_info`).
+//! Walking a tree or reading a large archive index can take a while, so
+//! `App::poll_info` starts it on a worker thread when the selection changes
+//! and picks up the result on a later tick; moving on cancels a walk still
+//! in progress.
+
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use super::panel::Pending;
+use super::App;
+use crate::app::types::OtherPanel;
+use crate::fs_op::ignore::IgnoreRules;
+use crate::fs_op::filetype;
+use crate::fs_op::format_info::{self, Rows};
+use crate::fs_op::info::{dir_totals, DirTotals};
+
+/// What the worker found for the selected entry.
+#[derive(Debug)]
+enum Found {
+    Totals(DirTotals),
+    Details(Rows),
+}
+
+/// Totals of the directory, or details of the file, last shown in the
+/// info panel.
+#[derive(Debug, Default)]
+pub struct InfoState {
+    /// Entry the totals or details are for.
+    pub path: Option<PathBuf>,
+    /// `None` while they are being computed.
+    pub totals: Option<DirTotals>,
+    /// Format details of a file; `None` while they are being read.
+    pub details: Option<Rows>,
+    pending: Option<Pending<Found>>,
+    cancel: Arc<AtomicBool>,
+}
+
+impl InfoState {
+    fn store(&mut self, found: Found) {
+        match found {
+            Found::Totals(totals) => self.totals = Some(totals),
+            Found::Details(rows) => self.details = Some(rows),
+        }
+    }
+}
+
+impl App {
+    /// Start computing the totals of the selected directory, or reading
+    /// the details of the selected file, when the info panel shows it, and
+    /// collect a finished result. Called every tick from `poll_listings`.
+    pub fn poll_info(&mut self) {
+        if let Some(pending) = &self.info.pending {
+            if let Ok(found) = pending.try_take() {
+                self.dirty = true;
+                self.info.store(found);
+                self.info.pending = None;
+            }
+        }
+        if !self.other_panel_shows(OtherPanel::Info) {
+            return;
+        }
+        let panel = self.active_panel();
+        let selected = panel.selected_entry().filter(|_| panel.vfs.is_local()).map(|e| (e.path.clone(), e.is_dir));
+        if selected.as_ref().map(|(path, _)| path) == self.info.path.as_ref() {
+            return;
+        }
+        self.info.cancel.store(true, Ordering::Relaxed);
+        self.info.pending = None;
+        self.info.totals = None;
+        self.info.details = None;
+        self.info.path = selected.as_ref().map(|(path, _)| path.clone());
+        if let Some((path, is_dir)) = selected {
+            let cancel = Arc::new(AtomicBool::new(false));
+            self.info.cancel = cancel.clone();
+            let work_path = path.clone();
+            let hide_ignored = self.settings.hide_gitignored;
+            let work = move || match is_dir {
+                true => {
+                    let rules = hide_ignored.then(|| IgnoreRules::for_dir(&work_path)).flatten();
+                    Found::Totals(dir_totals(&work_path, rules.as_ref(), &cancel))
+                }
+                false => {
+                    let mime = filetype::detect(&work_path).map(|t| ("MIME type", format!("{} ({})", t.mime, t.description)));
+                    Found::Details(mime.into_iter().chain(format_info::read(&work_path)).collect())
+                }
+            };
+            match Pending::run(path, Duration::ZERO, work) {
+                Ok(found) => self.info.store(found),
+                Err(pending) => self.info.pending = Some(pending),
+            }
+        }
+    }
+}
@@ -0,0 +1,217 @@
+//! History of finished background jobs (copy/move).
+//!
+//! `handle_operation_start` calls `JobHistory::start` and `poll_progress`
+//! calls `finish` once the worker reports `done`, recording the operation,
+//! item counts, duration and any error. The history is written to
+//! `job_history.toml` in `project_config_dir()` after every job so it
+//! survives restarts; it is only persisted when `path` is set, which the
+//! event loop does at startup (tests keep it in memory). Press `J` to view
+//! it. Jobs sent to the background are recorded by `runner::jobs::poll`.
+
+use std::fs;
+use std::path::PathBuf;
+use std::time::Instant;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::app::settings::config_dirs::project_config_dir;
+
+/// File name of the job history inside the config directory.
+pub const JOB_HISTORY_FILE: &str = "job_history.toml";
+
+/// Number of finished jobs kept; older entries are dropped.
+pub const MAX_JOB_HISTORY: usize = 200;
+
+/// One finished job.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct JobRecord {
+    /// Operation label, e.g. "Copy".
+    pub operation: String,
+    /// Completion time as seconds since the Unix epoch.
+    pub finished: i64,
+    /// Items processed before the job ended.
+    pub processed: usize,
+    /// Items the job was started with.
+    pub total: usize,
+    /// Wall-clock duration in milliseconds.
+    pub duration_ms: u64,
+    /// Error (or cancellation) message; `None` when the job completed cleanly.
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
+impl JobRecord {
+    /// Single-line summary used by the history dialog.
+    pub fn summary(&self) -> String {
+        let when = chrono::DateTime::from_timestamp(self.finished, 0)
+            .map(|t| t.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M").to_string())
+            .unwrap_or_else(|| "-".to_string());
+        let status = match &self.error {
+            Some(e) => format!("FAILED: {}", e),
+            None => "ok".to_string(),
+        };
+        format!(
+            "{}  {:<5} {}/{} items  {:.1}s  {}",
+            when,
+            self.operation,
+            self.processed,
+            self.total,
+            self.duration_ms as f64 / 1000.0,
+            status
+        )
+    }
+}
+
+/// Finished jobs (oldest first) plus the job currently running, if any.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct JobHistory {
+    #[serde(default, rename = "job")]
+    pub records: Vec<JobRecord>,
+    /// File the history is saved to; `None` keeps it in memory only.
+    #[serde(skip)]
+    pub path: Option<PathBuf>,
+    /// Operation label and start time of the running job.
+    #[serde(skip)]
+    active: Option<(String, Instant)>,
+}
+
+/// Default location of the job history file.
+pub fn job_history_path() -> PathBuf {
+    project_config_dir().join(JOB_HISTORY_FILE)
+}
+
+impl JobHistory {
+    /// Load the history from `path` and persist future jobs there. A missing
+    /// file yields an empty history.
+    pub fn load_from(path: PathBuf) -> Result<Self> {
+        let mut history = if path.exists() {
+            let raw = fs::read_to_string(&path)
+                .with_context(|| format!("failed to read job history {}", path.display()))?;
+            toml::from_str::<JobHistory>(&raw)
+                .with_context(|| format!("failed to parse job history TOML in {}", path.display()))?
+        } else {
+            JobHistory::default()
+        };
+        history.path = Some(path);
+        Ok(history)
+    }
+
+    /// Mark the start of a job labelled `operation`.
+    pub fn start(&mut self, operation: &str) {
+        self.active = Some((operation.to_string(), Instant::now()));
+    }
+
+    /// Operation label and start time of the running job, if any.
+    pub fn running(&self) -> Option<(&str, Instant)> {
+        self.active.as_ref().map(|(op, started)| (op.as_str(), *started))
+    }
+
+    /// Stop following the running job, which goes on in the background.
+    /// Returns its operation label and start time.
+    pub fn detach(&mut self) -> Option<(String, Instant)> {
+        self.active.take()
+    }
+
+    /// Follow a job started at `started` as the running one again.
+    pub fn resume(&mut self, operation: String, started: Instant) {
+        self.active = Some((operation, started));
+    }
+
+    /// Record the end of the running job. Does nothing if no job was
+    /// started. Returns the save error, if persisting failed.
+    pub fn finish(&mut self, processed: usize, total: usize, error: Option<String>) -> Result<()> {
+        let Some((operation, started)) = self.active.take() else {
+            return Ok(());
+        };
+        self.record(operation, started, processed, total, error)
+    }
+
+    /// Record the end of a job started at `started` and save the history.
+    pub fn record(&mut self, operation: String, started: Instant, processed: usize, total: usize, error: Option<String>) -> Result<()> {
+        self.push(JobRecord {
+            operation,
+            finished: super::clock::now_local().timestamp(),
+            processed,
+            total,
+            duration_ms: started.elapsed().as_millis() as u64,
+            error,
+        });
+        self.save()
+    }
+
+    /// Append a record, dropping the oldest beyond `MAX_JOB_HISTORY`.
+    pub fn push(&mut self, record: JobRecord) {
+        self.records.push(record);
+        let excess = self.records.len().saturating_sub(MAX_JOB_HISTORY);
+        self.records.drain(..excess);
+    }
+
+    /// Write the history to `path` (no-op when unset).
+    pub fn save(&self) -> Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("failed to create config dir {}", parent.display()))?;
+        }
+        let s = toml::to_string_pretty(self).context("failed to serialize job history")?;
+        fs::write(path, s).with_context(|| format!("failed to write job history {}", path.display()))
+    }
+
+    /// Text for the history dialog, most recent job first.
+    pub fn dialog_text(&self) -> String {
+        if self.records.is_empty() {
+            return "No finished jobs yet.".to_string();
+        }
+        self.records.iter().rev().map(JobRecord::summary).collect::<Vec<_>>().join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finish_records_job_and_round_trips_through_file() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join(JOB_HISTORY_FILE);
+        let mut h = JobHistory::load_from(path.clone()).unwrap();
+        h.finish(1, 1, None).unwrap();
+        assert!(h.records.is_empty(), "finish without start records nothing");
+
+        h.start("Copy");
+        h.finish(3, 3, None).unwrap();
+        h.start("Move");
+        h.finish(1, 4, Some("Cancelled".to_string())).unwrap();
+
+        let loaded = JobHistory::load_from(path).unwrap();
+        assert_eq!(loaded.records, h.records);
+        assert_eq!(loaded.records[0].operation, "Copy");
+        let text = loaded.dialog_text();
+        let first = text.lines().next().unwrap();
+        assert!(first.contains("Move") && first.contains("1/4 items") && first.contains("FAILED: Cancelled"));
+        assert!(text.lines().nth(1).unwrap().ends_with("ok"));
+    }
+
+    #[test]
+    fn finish_stamps_the_clock_time() {
+        let clock = crate::test_helpers::FakeClock::at_unix(1_700_000_000);
+        let _guard = clock.install();
+        let mut h = JobHistory::default();
+        h.start("Copy");
+        h.finish(1, 1, None).unwrap();
+        assert_eq!(h.records[0].finished, 1_700_000_000);
+    }
+
+    #[test]
+    fn push_keeps_only_the_newest_records() {
+        let mut h = JobHistory::default();
+        for i in 0..MAX_JOB_HISTORY + 5 {
+            h.push(JobRecord { operation: "Copy".into(), finished: i as i64, processed: 0, total: 0, duration_ms: 0, error: None });
+        }
+        assert_eq!(h.records.len(), MAX_JOB_HISTORY);
+        assert_eq!(h.records[0].finished, 5);
+    }
+}
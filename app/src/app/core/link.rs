@@ -0,0 +1,73 @@
+//! Linked panels (`K`, `:link`): while on, entering a directory in the
+//! active panel takes the other panel to the same path relative to its own
+//! root, when that directory exists there. Handy for walking two copies of
+//! a tree side by side.
+
+use std::path::{Path, PathBuf};
+
+use super::{App, Side};
+
+/// The directories both panels showed when they were linked; paths below
+/// one root are mirrored below the other.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PanelLink {
+    pub left_root: PathBuf,
+    pub right_root: PathBuf,
+}
+
+impl PanelLink {
+    /// Where the panel opposite `side` should go when `side` lists `dir`:
+    /// the same path below its root, if `dir` is below the root of `side`.
+    pub fn mirror(&self, side: Side, dir: &Path) -> Option<PathBuf> {
+        let (from, to) = match side {
+            Side::Left => (&self.left_root, &self.right_root),
+            Side::Right => (&self.right_root, &self.left_root),
+        };
+        dir.strip_prefix(from).ok().map(|rel| to.join(rel))
+    }
+}
+
+impl App {
+    /// Link the panels at their current directories, or unlink them.
+    pub fn toggle_link(&mut self) {
+        self.link = match self.link {
+            Some(_) => None,
+            None => Some(PanelLink { left_root: self.left.cwd.clone(), right_root: self.right.cwd.clone() }),
+        };
+        self.left.dirty = true;
+        self.right.dirty = true;
+    }
+
+    /// Take the panel opposite `side` after it, if the panels are linked.
+    /// Called when the active panel lists another directory.
+    pub(crate) fn follow_link(&mut self, side: Side) {
+        let Some(target) = self.link.as_ref().and_then(|link| link.mirror(side, &self.panel(side).cwd)) else {
+            return;
+        };
+        let other = match side {
+            Side::Left => Side::Right,
+            Side::Right => Side::Left,
+        };
+        let panel = self.panel_mut(other);
+        if panel.cwd == target || !panel.vfs.stat(&target).is_ok_and(|e| e.is_dir) {
+            return;
+        }
+        panel.cwd = target;
+        if let Err(e) = self.refresh_side(other) {
+            tracing::warn!("linked panel not followed: {}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mirrors_paths_below_the_roots() {
+        let link = PanelLink { left_root: PathBuf::from("/a/src"), right_root: PathBuf::from("/b/src") };
+        assert_eq!(link.mirror(Side::Left, Path::new("/a/src/ui/widgets")), Some(PathBuf::from("/b/src/ui/widgets")));
+        assert_eq!(link.mirror(Side::Right, Path::new("/b/src")), Some(PathBuf::from("/a/src")));
+        assert_eq!(link.mirror(Side::Left, Path::new("/a")), None);
+    }
+}
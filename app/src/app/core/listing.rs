@@ -0,0 +1,112 @@
+//! Sorting and filtering of directory listings before they reach a panel.
+//!
+//! `apply_listing` runs every fresh listing through `filter_hidden` and
+//! `sort_entries`. Both are pure so the property tests in
+//! `tests/proptest_listing.rs` can feed them arbitrary names. `diff`
+//! compares a new listing of the same directory with the one it replaces.
+
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
+
+use crate::app::types::{Entry, SortKey, SortOrder};
+
+/// Whether `entry` is a dotfile or has the Windows hidden attribute, hidden
+/// unless `show_hidden` is set.
+pub fn is_hidden(entry: &Entry) -> bool {
+    entry.name.as_encoded_bytes().starts_with(b".")
+        || entry.attributes.is_some_and(|a| a.contains(crate::fs_op::attributes::FileAttributes::HIDDEN))
+}
+
+/// Drop hidden entries unless `show_hidden` is set, keeping the order of
+/// the rest.
+pub fn filter_hidden(entries: &mut Vec<Entry>, show_hidden: bool) {
+    if !show_hidden {
+        entries.retain(|e| !is_hidden(e));
+    }
+}
+
+/// Sort `entries` by `key` in `order`.
+///
+/// For `Name` directories come first (last when descending) and names
+/// compare case-insensitively, then by their raw bytes so names that only
+/// differ in case or in bytes that are not UTF-8 still sort the same way
+/// every time. The sort is stable in both directions:
+/// entries with equal keys keep their listing order. The lowercased name
+/// is computed once per entry rather than once per comparison, which
+/// dominates on huge listings.
+pub fn sort_entries(entries: &mut [Entry], key: SortKey, order: SortOrder) {
+    let desc = order == SortOrder::Descending;
+    match (key, desc) {
+        (SortKey::Name, false) => entries.sort_by_cached_key(|e| (!e.is_dir, e.display_name().to_lowercase(), e.name.clone())),
+        (SortKey::Name, true) => entries.sort_by_cached_key(|e| Reverse((!e.is_dir, e.display_name().to_lowercase(), e.name.clone()))),
+        (SortKey::Size, false) => entries.sort_by_key(|e| e.size),
+        (SortKey::Size, true) => entries.sort_by_key(|e| Reverse(e.size)),
+        (SortKey::Modified, false) => entries.sort_by_key(|e| e.modified),
+        (SortKey::Modified, true) => entries.sort_by_key(|e| Reverse(e.modified)),
+    }
+}
+
+/// What changed between two listings of the same directory, as returned
+/// by `App::refresh_side`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ListingChanges {
+    pub added: usize,
+    pub removed: usize,
+    /// Entries still present whose metadata (size, times, permissions...)
+    /// differs.
+    pub modified: usize,
+}
+
+impl ListingChanges {
+    /// Whether both listings hold the same entries.
+    pub fn is_empty(&self) -> bool {
+        self.added == 0 && self.removed == 0 && self.modified == 0
+    }
+}
+
+impl fmt::Display for ListingChanges {
+    /// Short summary such as `+3 -1 files, 2 modified`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut counts = Vec::new();
+        if self.added > 0 {
+            counts.push(format!("+{}", self.added));
+        }
+        if self.removed > 0 {
+            counts.push(format!("-{}", self.removed));
+        }
+        if !counts.is_empty() {
+            let noun = if self.added + self.removed == 1 { "file" } else { "files" };
+            write!(f, "{} {}", counts.join(" "), noun)?;
+            if self.modified > 0 {
+                write!(f, ", ")?;
+            }
+        }
+        if self.modified > 0 {
+            write!(f, "{} modified", self.modified)?;
+        }
+        Ok(())
+    }
+}
+
+/// Compare `new` with the `old` listing of the same directory, matching
+/// entries by path.
+pub fn diff(old: &[Entry], new: &[Entry]) -> ListingChanges {
+    let before: HashMap<&Path, &Entry> = old.iter().map(|e| (e.path.as_path(), e)).collect();
+    let mut changes = ListingChanges::default();
+    let mut kept = 0;
+    for e in new {
+        match before.get(e.path.as_path()) {
+            Some(prev) => {
+                kept += 1;
+                if *prev != e {
+                    changes.modified += 1;
+                }
+            }
+            None => changes.added += 1,
+        }
+    }
+    changes.removed = before.len() - kept;
+    changes
+}
@@ -6,12 +6,14 @@
 
 use std::io;
 
+use super::listing::ListingChanges;
+use super::refresh_backoff::RefreshFailure;
 use super::{init, App, Panel, Mode, Side, SortKey};
 
 impl App {
     // Helper: refresh only the active panel
     pub fn refresh_active(&mut self) -> io::Result<()> {
-        self.refresh_panel(self.active)
+        self.refresh_panel(self.active, false, super::panel::LISTING_WAIT).map(|_| ())
     }
 
     pub fn new() -> io::Result<Self> {
@@ -32,14 +34,24 @@ impl App {
     /// `StartOptions` when provided so callers can control initial state
     /// without mutating global process state.
     pub fn with_options(opts: &crate::app::StartOptions) -> io::Result<Self> {
+        let mut app = Self::unlisted(opts)?;
+        app.refresh()?;
+        Ok(app)
+    }
+
+    /// `with_options` without listing the panels: the event loop finishes
+    /// setting up (settings, session) first and then starts both listings
+    /// with `start_listing`, so nothing is listed twice.
+    pub fn unlisted(opts: &crate::app::StartOptions) -> io::Result<Self> {
         let cwd = if let Some(d) = &opts.start_dir {
             d.clone()
         } else {
             std::env::current_dir()?
         };
+        let right_cwd = opts.right_dir.clone().unwrap_or_else(|| cwd.clone());
         let mut app = App {
-            left: Panel::new(cwd.clone()),
-            right: Panel::new(cwd),
+            left: Panel::new(cwd),
+            right: Panel::new(right_cwd),
             active: Side::Left,
             mode: Mode::Normal,
             sort: SortKey::Name,
@@ -53,6 +65,9 @@ impl App {
             settings: crate::app::settings::write_settings::Settings::default(),
             op_progress_rx: None,
             op_cancel_flag: None,
+            op_skip_verify: None,
+            op_meter: None,
+            scheduler: Default::default(),
             op_decision_tx: None,
             last_mouse_click_time: None,
             last_mouse_click_pos: None,
@@ -60,6 +75,30 @@ impl App {
             drag_start: None,
             drag_current: None,
             drag_button: None,
+            scroll_drag: None,
+            dirty: false,
+            plugins: Default::default(),
+            diagnostics: Default::default(),
+            jobs: Default::default(),
+            pending_keys: Default::default(),
+            path_jump: Default::default(),
+            frecency: Default::default(),
+            tags: Default::default(),
+            searches: Default::default(),
+            clipboard: None,
+            other_panel: Default::default(),
+            info: Default::default(),
+            tree: Default::default(),
+            usage: None,
+            shell: None,
+            compare: None,
+            open_files: None,
+            chooser: opts.choose_files.then(Vec::new),
+            link: None,
+            templates: Default::default(),
+            owners: Default::default(),
+            preview_focused: Default::default(),
+            layout: Default::default(),
         };
         // Apply any immediate overrides requested by CLI options. Persisted
         // settings (loaded later) will be applied afterwards; callers that
@@ -71,18 +110,205 @@ impl App {
         if let Some(s) = opts.show_hidden {
             app.settings.show_hidden = s;
         }
+        if let Some(s) = opts.single_pane {
+            app.settings.single_pane = s;
+        }
+        if app.settings.single_pane {
+            app.layout = crate::app::types::PanelLayout::Single;
+        }
         if let Some(ref theme) = opts.theme {
             // Update persisted-in-memory setting and apply theme to UI
             app.settings.theme = theme.clone();
             crate::ui::colors::set_theme(theme.as_str());
         }
-        app.refresh()?;
+        // Nothing is listed yet, so the first listing counts as entering
+        // the directory (restoring a remembered position).
+        app.left.listed_dir = std::path::PathBuf::new();
+        app.right.listed_dir = std::path::PathBuf::new();
         Ok(app)
     }
 
+    /// Start listing both panels in the background without waiting, so the
+    /// first frame can be drawn right away; `poll_listings` applies them.
+    pub fn start_listing(&mut self) -> io::Result<()> {
+        self.refresh_panel(Side::Left, false, std::time::Duration::ZERO)?;
+        self.refresh_panel(Side::Right, false, std::time::Duration::ZERO)?;
+        Ok(())
+    }
+
+    /// Whether a listing of either panel is still running.
+    pub fn listing_pending(&self) -> bool {
+        self.left.loading.is_some() || self.right.loading.is_some()
+    }
+
+    /// Note that the screen needs to be drawn again.
+    pub fn mark_dirty(&mut self) {
+        self.dirty = true;
+    }
+
+    /// Whether the app or a panel changed since the last call, clearing
+    /// the flags. The event loop only draws a frame when this, input or
+    /// a timer asks for one.
+    pub fn take_dirty(&mut self) -> bool {
+        let dirty = self.dirty | self.left.dirty | self.right.dirty;
+        self.dirty = false;
+        self.left.dirty = false;
+        self.right.dirty = false;
+        dirty
+    }
+
+    /// Number of file operations running: the one in the progress dialog
+    /// and those sent to the background.
+    pub fn running_jobs(&self) -> usize {
+        usize::from(self.op_progress_rx.is_some()) + self.scheduler.running()
+    }
+
+    /// Switch to the next panel layout.
+    pub fn cycle_layout(&mut self) {
+        self.layout = self.layout.next();
+    }
+
     /// Toggle the preview pane visibility.
     pub fn toggle_preview(&mut self) {
         self.preview_visible = !self.preview_visible;
+        self.preview_focused &= self.preview_visible;
+    }
+
+    /// Move the keyboard focus between the active panel and the preview
+    /// pane, showing the pane if it is hidden.
+    pub fn toggle_preview_focus(&mut self) {
+        if !self.preview_on_screen() {
+            self.preview_visible = true;
+            self.preview_focused = true;
+        } else {
+            self.preview_focused = !self.preview_focused;
+        }
+    }
+
+    /// Turn quick view on or off. It applies to the side-by-side and
+    /// stacked layouts, where the other panel can make room for it.
+    pub fn toggle_quick_view(&mut self) {
+        self.toggle_other_panel(crate::app::types::OtherPanel::QuickView);
+    }
+
+    /// Turn the info panel on or off; like quick view it takes the
+    /// inactive panel's place.
+    pub fn toggle_info_panel(&mut self) {
+        self.toggle_other_panel(crate::app::types::OtherPanel::Info);
+    }
+
+    /// Show the directory tree with the focus on the active panel's
+    /// directory; when the tree has the focus already, hide it.
+    pub fn toggle_tree(&mut self) {
+        if self.tree.visible && self.tree.focused {
+            self.tree.visible = false;
+            self.tree.focused = false;
+            return;
+        }
+        self.tree.visible = true;
+        self.tree.focused = true;
+        // The tree shows the local filesystem only.
+        if self.active_panel().vfs.is_local() {
+            let cwd = self.active_panel().cwd.clone();
+            self.tree.reveal(&cwd, self.settings.show_hidden);
+        }
+    }
+
+    /// List every file below the active panel's directory in one flat
+    /// listing, or go back to the plain listing.
+    pub fn toggle_flat(&mut self) -> io::Result<()> {
+        let panel = self.active_panel_mut();
+        panel.flat = if panel.is_flat() { None } else { Some(panel.cwd.clone()) };
+        self.refresh_side(self.active).map(|_| ())
+    }
+
+    /// Mark the files of the active panel that meet `spec` (see
+    /// `fs_op::criteria`), replacing the marks. Returns how many.
+    pub fn select_by_criteria(&mut self, spec: &str) -> Result<usize, String> {
+        let criteria = crate::fs_op::criteria::Criteria::parse(spec, super::clock::now())?;
+        let panel = self.active_panel_mut();
+        panel.selections = panel.entries.iter().enumerate().filter(|(_, e)| criteria.matches(e)).map(|(i, _)| i).collect();
+        Ok(panel.selections.len())
+    }
+
+    /// Search below the active panel's directory for `query` (a name,
+    /// glob or regex with the flags of `SearchOptions::parse`), skipping
+    /// git-ignored paths by default when those are hidden.
+    pub fn start_search(&mut self, query: &str) -> io::Result<()> {
+        let options = crate::fs_op::search::SearchOptions::parse(query, self.settings.hide_gitignored);
+        self.run_search(options)
+    }
+
+    /// Search below the active panel's directory with `options` and list
+    /// the matches in the panel; the search goes to the recent ones. From
+    /// a results panel the same directory is searched again, which refines
+    /// the query.
+    pub fn run_search(&mut self, options: crate::fs_op::search::SearchOptions) -> io::Result<()> {
+        use crate::fs_op::search::SearchResultsFs;
+        options.matcher().map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+        let panel = self.active_panel_mut();
+        if panel.search.is_none() && !panel.vfs.is_local() {
+            return Err(io::Error::new(io::ErrorKind::Unsupported, "searching works on local directories only"));
+        }
+        let root = panel.search_results().map_or_else(|| panel.cwd.clone(), |s| s.root.clone());
+        if let Some(previous) = panel.search.take() {
+            previous.cancel();
+        }
+        let search = std::sync::Arc::new(SearchResultsFs::new(root.clone(), options.clone()));
+        panel.vfs = search.clone();
+        panel.search = Some(search);
+        panel.cwd = root;
+        self.searches.remember(options);
+        if let Err(e) = self.searches.save() {
+            tracing::warn!("search history not saved: {:#}", e);
+        }
+        self.refresh_side(self.active).map(|_| ())
+    }
+
+    /// Leave the search results of the active panel for the plain listing
+    /// of the directory that was searched.
+    pub fn close_search(&mut self) -> io::Result<()> {
+        let panel = self.active_panel_mut();
+        let Some(search) = panel.search.take() else {
+            return Ok(());
+        };
+        search.cancel();
+        panel.vfs = crate::fs_op::vfs::local();
+        panel.cwd = search.root.clone();
+        self.refresh_side(self.active).map(|_| ())
+    }
+
+    fn toggle_other_panel(&mut self, view: crate::app::types::OtherPanel) {
+        use crate::app::types::OtherPanel;
+        self.other_panel = if self.other_panel == view { OtherPanel::Listing } else { view };
+        self.preview_focused &= self.preview_on_screen();
+    }
+
+    /// Whether the inactive panel's place shows `view`: only in the layouts
+    /// that draw both panels.
+    pub fn other_panel_shows(&self, view: crate::app::types::OtherPanel) -> bool {
+        use crate::app::types::PanelLayout;
+        self.other_panel == view && matches!(self.layout, PanelLayout::Horizontal | PanelLayout::Vertical)
+    }
+
+    /// Whether some part of the screen shows the active panel's preview.
+    pub fn preview_on_screen(&self) -> bool {
+        use crate::app::types::PanelLayout;
+        self.preview_visible
+            || self.layout == PanelLayout::PreviewDominant
+            || self.other_panel_shows(crate::app::types::OtherPanel::QuickView)
+    }
+
+    /// Scroll the active panel's preview by `delta` lines, stopping at its
+    /// first and last line.
+    pub fn scroll_preview(&mut self, delta: isize) {
+        let panel = self.active_panel_mut();
+        let last = panel.preview.lines().count().saturating_sub(1);
+        panel.preview_offset = panel.preview_offset.saturating_add_signed(delta).min(last);
+        // Scrolling a followed preview back to the end pins it there again.
+        if let Some(follow) = panel.follow.as_mut() {
+            follow.pinned |= delta > 0 && panel.preview_offset == last;
+        }
     }
 
     /// Toggle the dedicated file-stats column visibility.
@@ -90,6 +316,19 @@ impl App {
         self.file_stats_visible = !self.file_stats_visible;
     }
 
+    /// Show what an operation came to in the summary dialog
+    /// (`Mode::Summary`), writing its failures to the log. `error` ended the
+    /// operation early; otherwise `note` is its closing message.
+    pub fn show_summary(&mut self, summary: crate::runner::progress::OperationSummary, error: Option<String>, note: Option<String>) {
+        summary.log_failures();
+        let title = match (&error, summary.failed()) {
+            (Some(_), _) => "Error",
+            (None, 0) => "Done",
+            (None, _) => "Done with errors",
+        };
+        self.mode = Mode::Summary { title: title.to_string(), note: error.or(note), summary: Box::new(summary), scroll: 0 };
+    }
+
     /// Poll an active progress receiver and update the `Mode::Progress` state
     /// accordingly. This should be called periodically from the event loop so
     /// the UI can reflect progress updates and completion.
@@ -97,42 +336,67 @@ impl App {
         // Poll and consume available progress updates, keeping only the
         // most-recent one. If the channel closes we clear the receiver.
         if let Some(rx) = self.op_progress_rx.as_ref() {
+            use crate::runner::progress::ProgressPhase;
             let mut last: Option<crate::runner::progress::ProgressUpdate> = None;
+            // Item count of the copy, for its (full) bar while verifying.
+            let mut copy_total = None;
             while let Ok(update) = rx.try_recv() {
+                if update.phase == ProgressPhase::Copying {
+                    copy_total = Some(update.total);
+                }
                 last = Some(update);
             }
 
-            // If channel is closed, ensure receiver is cleared and return.
-            if let Err(std::sync::mpsc::TryRecvError::Disconnected) = rx.try_recv() {
+            // If channel is closed with nothing left to handle, clear the
+            // receiver and return. A final update that arrived before the
+            // worker exited is still handled below.
+            if last.is_none() && matches!(rx.try_recv(), Err(std::sync::mpsc::TryRecvError::Disconnected)) {
                 self.op_progress_rx = None;
+                self.op_meter = None;
+                self.dirty = true;
                 return;
             }
 
             if let Some(update) = last {
-                if let Some(conflict_path) = update.conflict {
-                    self.mode = Mode::Conflict {
-                        path: conflict_path,
-                        selected: 0,
-                        apply_all: false,
-                    };
+                self.dirty = true;
+                if self.ask_worker_question(&update) {
                     return;
                 }
 
                 if update.done {
                     self.op_progress_rx = None;
                     self.op_cancel_flag = None;
+                    self.op_skip_verify = None;
+                    self.op_meter = None;
                     self.op_decision_tx = None;
+                    let label = self.scheduler.foreground.take().or_else(|| self.jobs.running().map(|(op, _)| op.to_string())).unwrap_or_default();
+                    if let Some((_, started)) = self.jobs.running() {
+                        crate::runner::notify::job_finished(&self.settings, &label, started.elapsed(), update.error.as_deref());
+                    }
+                    match &update.error {
+                        Some(error) => tracing::warn!(processed = update.processed, total = update.total, %error, "operation failed"),
+                        None => tracing::info!(processed = update.processed, total = update.total, "operation finished"),
+                    }
+                    if let Err(e) = self.jobs.finish(update.processed, update.total, update.error.clone()) {
+                        tracing::warn!("failed to save job history: {:#}", e);
+                    }
 
-                    if let Some(err_msg) = update.error {
+                    if let Some(summary) = update.summary {
+                        let note = (update.phase == ProgressPhase::Verifying).then(|| update.message.unwrap_or_default());
+                        self.show_summary(summary, update.error, note);
+                    } else if let Some(err_msg) = update.error {
                         self.mode = Mode::Message {
                             title: "Error".to_string(),
                             content: err_msg,
-                            buttons: vec!["OK".to_string()],
-                            selected: 0,
-                            actions: None,
+                            buttons: vec!["Show log".to_string(), "OK".to_string()],
+                            selected: 1,
+                            actions: Some(vec![crate::app::Action::ShowLog]),
                         };
                     } else {
-                        let content = format!("{} items processed", update.processed);
+                        let mut content = format!("{} items processed", update.processed);
+                        if update.phase == ProgressPhase::Verifying {
+                            content = format!("{}\n{}", content, update.message.unwrap_or_default());
+                        }
                         self.mode = Mode::Message {
                             title: "Done".to_string(),
                             content,
@@ -147,29 +411,100 @@ impl App {
                     let _ = self.refresh();
                 } else {
                     let message = update.message.unwrap_or_default();
+                    // While verifying, the copy bar stays full and the
+                    // counters go to the verification bar.
+                    let (processed, total, verify) = match update.phase {
+                        ProgressPhase::Copying => (update.processed, update.total, self.op_skip_verify.as_ref().map(|_| (0, 0))),
+                        ProgressPhase::Verifying => {
+                            let copied = copy_total.unwrap_or(match &self.mode {
+                                Mode::Progress { total, .. } => *total,
+                                _ => 0,
+                            });
+                            (copied, copied, Some((update.processed, update.total)))
+                        }
+                    };
                     self.mode = Mode::Progress {
                         title: if message.is_empty() { "Progress".to_string() } else { message.clone() },
-                        processed: update.processed,
-                        total: update.total,
+                        processed,
+                        total,
                         message,
                         cancelled: false,
+                        verify,
                     };
                 }
             }
         }
     }
 
+    /// Ask the user about a conflict or recoverable error the worker of the
+    /// foreground job reported in `update` and waits on. Returns whether
+    /// there was one.
+    pub(crate) fn ask_worker_question(&mut self, update: &crate::runner::progress::ProgressUpdate) -> bool {
+        if let Some(conflict_path) = update.conflict.clone() {
+            let in_use = crate::runner::open_files::conflict_warning(self, conflict_path.clone());
+            let stat = |p: &std::path::Path| crate::app::core::panel::stat_entry(p).ok().map(Box::new);
+            let src = update.conflict_src.as_deref().and_then(stat);
+            let dst = stat(&conflict_path);
+            self.mode = Mode::Conflict {
+                path: conflict_path,
+                selected: 0,
+                apply_all: false,
+                in_use,
+                src,
+                dst,
+                compare: None,
+            };
+            return true;
+        }
+
+        if let Some(path) = &update.failed {
+            self.mode = Mode::OperationError { path: Some(path.clone()), error: update.error.clone().unwrap_or_default(), retry: None, elevated: None, selected: 0 };
+            return true;
+        }
+        false
+    }
+
     pub fn refresh(&mut self) -> io::Result<()> {
-        self.refresh_panel(Side::Left)?;
-        self.refresh_panel(Side::Right)?;
+        self.refresh_panel(Side::Left, false, super::panel::LISTING_WAIT)?;
+        self.refresh_panel(Side::Right, false, super::panel::LISTING_WAIT)?;
         Ok(())
     }
 
     /// Refresh only the specified panel side. This allows callers (for
     /// example filesystem watchers) to update just the affected panel
     /// instead of forcing a full two-panel refresh.
-    pub fn refresh_side(&mut self, side: Side) -> io::Result<()> {
-        self.refresh_panel(side)
+    ///
+    /// The cursor and multi-selection follow their entries by path, so
+    /// files appearing or disappearing around them do not shift the
+    /// selection onto a different entry.
+    ///
+    /// Returns what changed since the previous listing of the directory,
+    /// or `None` while the listing is still loading in the background.
+    /// When nothing changed the panel (and its preview) is left untouched.
+    pub fn refresh_side(&mut self, side: Side) -> io::Result<Option<ListingChanges>> {
+        let panel = self.panel_mut(side);
+        let cursor = panel.selected_entry().map(|e| e.path.clone());
+        let marked: std::collections::HashSet<std::path::PathBuf> =
+            panel.selections.iter().filter_map(|&i| panel.entries.get(i).map(|e| e.path.clone())).collect();
+        let changes = self.refresh_panel(side, true, super::panel::LISTING_WAIT)?;
+        if changes.is_some_and(|c| c.is_empty()) {
+            return Ok(changes);
+        }
+        let panel = self.panel_mut(side);
+        panel.selections = panel
+            .entries
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| marked.contains(&e.path))
+            .map(|(i, _)| i)
+            .collect();
+        if let Some(path) = cursor {
+            if let Some(idx) = panel.entries.iter().position(|e| e.path == path) {
+                panel.selected = super::utils::entry_to_ui_index(idx, panel);
+                self.update_preview_for(side);
+            }
+        }
+        Ok(changes)
     }
 
     /// Switches the menu selection to the next tab (wraps around).
@@ -201,13 +536,14 @@ impl App {
             if let Some(action) = self.menu_state.selected_action(&MenuModel::default_model()) {
                 match action {
                     MenuAction::Settings => { self.mode = Mode::Settings { selected: 0 }; }
-                    MenuAction::NewFile => { self.mode = Mode::Input { prompt: "New file name:".to_string(), buffer: String::new(), kind: crate::app::InputKind::NewFile }; }
+                    MenuAction::NewFile => self.open_new_file(),
                     MenuAction::NewDir => { self.mode = Mode::Input { prompt: "New dir name:".to_string(), buffer: String::new(), kind: crate::app::InputKind::NewDir }; }
                     MenuAction::Copy => { let _ = crate::runner::handlers::handle_key(self, crate::input::KeyCode::F(5), 10); }
                     MenuAction::Move => { let _ = crate::runner::handlers::handle_key(self, crate::input::KeyCode::F(6), 10); }
                     MenuAction::Sort => { self.sort = self.sort.next(); let _ = self.refresh(); }
                     MenuAction::Help => { let content = "See help ( ? )".to_string(); self.mode = Mode::Message { title: "Help".to_string(), content, buttons: vec!["OK".to_string()], selected: 0, actions: None }; }
                     MenuAction::Quit => { let content = "Quit the app with 'q'".to_string(); self.mode = Mode::Message { title: "Quit".to_string(), content, buttons: vec!["OK".to_string()], selected: 0, actions: None }; }
+                    MenuAction::ShowLog => crate::runner::handlers::log_view::open_log_view(self),
                     MenuAction::About | MenuAction::Noop => { /* fallthrough to label-based message below */ }
                 }
                 // Close submenu after activation
@@ -277,6 +613,35 @@ impl App {
         self.menu_state.toggle_top(idx);
     }
 
+    /// Open or run the menu entry whose mnemonic is `c`: an item of the
+    /// open submenu, otherwise a top label. Returns whether one matched.
+    pub fn menu_mnemonic(&mut self, c: char) -> bool {
+        use crate::ui::menu_model::{mnemonic_match, MenuModel};
+        let model = MenuModel::default_model();
+        if self.menu_state.open {
+            let items = model.0.get(self.menu_index).and_then(|top| top.submenu.as_ref());
+            let labels: Vec<&str> = items.map(|s| s.iter().map(|it| it.label.as_str()).collect()).unwrap_or_default();
+            if let Some(i) = mnemonic_match(&labels, c) {
+                self.menu_state.submenu_index = Some(i);
+                self.menu_activate();
+                self.menu_focused = false;
+                return true;
+            }
+        }
+        let Some(idx) = mnemonic_match(&crate::ui::menu::menu_labels(), c) else {
+            return false;
+        };
+        if model.0.get(idx).is_some_and(|top| top.submenu.is_some()) {
+            self.open_menu(idx);
+        } else {
+            self.close_menu();
+            self.menu_index = idx;
+            self.menu_activate();
+            self.menu_focused = false;
+        }
+        true
+    }
+
     pub fn open_menu(&mut self, idx: usize) {
         self.menu_index = idx;
         self.menu_state.open_top(idx);
@@ -306,34 +671,224 @@ impl App {
         }
     }
 
-    fn refresh_panel(&mut self, side: Side) -> io::Result<()> {
+    /// Apply finished background listings and previews (see `Pending`).
+    /// Call periodically from the event loop, like `poll_progress`. A failed
+    /// listing returns the panel to the directory it showed before and
+    /// reports the error.
+    pub fn poll_listings(&mut self) {
+        use std::sync::mpsc::TryRecvError;
+        for side in [Side::Left, Side::Right] {
+            let panel = self.panel_mut(side);
+            if let Some(pending) = &panel.plugin_columns_loading {
+                match pending.try_take() {
+                    Ok(columns) => {
+                        panel.plugin_columns_loading = None;
+                        panel.plugin_columns = columns;
+                        panel.dirty = true;
+                    }
+                    Err(TryRecvError::Disconnected) => panel.plugin_columns_loading = None,
+                    Err(TryRecvError::Empty) => {}
+                }
+            }
+            if let Some(pending) = &panel.preview_loading {
+                match pending.try_take() {
+                    Ok(text) => {
+                        panel.preview_loading = None;
+                        panel.set_preview(text);
+                        if panel.follow.as_ref().is_some_and(|f| f.pinned) {
+                            panel.preview_offset = panel.preview.lines().count().saturating_sub(1);
+                        }
+                    }
+                    Err(TryRecvError::Disconnected) => {
+                        panel.preview_loading = None;
+                        panel.dirty = true;
+                    }
+                    Err(TryRecvError::Empty) => {}
+                }
+            }
+
+            let res = match panel.loading.as_ref().map(|l| l.try_take()) {
+                None | Some(Err(TryRecvError::Empty)) => continue,
+                Some(Ok(res)) => res,
+                Some(Err(TryRecvError::Disconnected)) => Err(io::Error::other("listing worker exited")),
+            };
+            let started = panel.loading.take().map(|l| l.started).unwrap_or_else(std::time::Instant::now);
+            let quiet = std::mem::take(&mut panel.quiet_listing);
+            // The "listing…" indicator goes away whatever the result.
+            panel.dirty = true;
+            match res {
+                // The backend is slow, so the preview does not wait either.
+                Ok(entries) => {
+                    self.apply_listing(side, entries, started, None, std::time::Duration::ZERO, false);
+                }
+                Err(e) if quiet => {
+                    let panel = self.panel_mut(side);
+                    let failure = RefreshFailure::record(panel.refresh_failure.as_ref(), e.to_string(), std::time::Instant::now());
+                    panel.refresh_failure = Some(failure);
+                }
+                Err(e) => {
+                    let panel = self.panel_mut(side);
+                    // A first listing that fails has nowhere to go back to.
+                    let dir = if panel.listed_dir.as_os_str().is_empty() {
+                        panel.cwd.clone()
+                    } else {
+                        std::mem::replace(&mut panel.cwd, panel.listed_dir.clone())
+                    };
+                    self.mode = Mode::Message {
+                        title: "Error".to_string(),
+                        content: format!("Failed to list {}: {}", dir.display(), e),
+                        buttons: vec!["OK".to_string()],
+                        selected: 0,
+                        actions: None,
+                    };
+                }
+            }
+        }
+        self.poll_info();
+        self.poll_usage();
+        self.poll_follow(std::time::Instant::now());
+    }
+
+    /// Refresh `side` after the watcher saw its directory change. Changes
+    /// are noted for the footer hint; a failure does not open a dialog but
+    /// sets `refresh_failure`, retried by `retry_failed_refreshes` with a
+    /// growing delay.
+    pub fn refresh_watched(&mut self, side: Side, now: std::time::Instant) {
+        self.panel_mut(side).dirty = true;
+        match self.refresh_side(side) {
+            Ok(Some(changes)) if !changes.is_empty() => self.panel_mut(side).external_change = Some((changes, now)),
+            Ok(Some(_)) => {}
+            Ok(None) => self.panel_mut(side).quiet_listing = true,
+            Err(e) => {
+                let panel = self.panel_mut(side);
+                panel.refresh_failure = Some(RefreshFailure::record(panel.refresh_failure.as_ref(), e.to_string(), now));
+            }
+        }
+    }
+
+    /// Retry the failed watcher refreshes whose delay has passed by `now`.
+    pub fn retry_failed_refreshes(&mut self, now: std::time::Instant) {
+        for side in [Side::Left, Side::Right] {
+            let panel = self.panel_mut(side);
+            if panel.loading.is_none() && panel.refresh_failure.as_ref().is_some_and(|f| f.due(now)) {
+                self.refresh_watched(side, now);
+            }
+        }
+    }
+
+    /// Stop waiting for the listing of `side` and return the panel to the
+    /// directory it showed before.
+    pub fn cancel_listing(&mut self, side: Side) {
+        let panel = self.panel_mut(side);
+        if panel.loading.take().is_some() && !panel.listed_dir.as_os_str().is_empty() {
+            panel.cwd = panel.listed_dir.clone();
+        }
+    }
+
+    /// List the directory of `side` again, waiting up to `wait`. Returns the
+    /// changes, or `None` when the listing continues in the background.
+    /// With `keep_unchanged` a listing identical to the current one is not
+    /// applied.
+    fn refresh_panel(&mut self, side: Side, keep_unchanged: bool, wait: std::time::Duration) -> io::Result<Option<ListingChanges>> {
+        let started = std::time::Instant::now();
+        let cache_before = crate::fs_op::cache::global().stats();
+        let (hide_ignored, show_hidden) = (self.settings.hide_gitignored, self.settings.show_hidden);
+        let panel = self.panel_mut(side);
+        // List on a worker thread; a listing that is not done within
+        // `wait` finishes in the background and the panel shows it as
+        // loading until `poll_listings` picks it up.
+        match panel.read_entries_within(wait, hide_ignored, show_hidden) {
+            Ok(entries) => {
+                panel.loading = None;
+                Ok(Some(self.apply_listing(side, entries?, started, Some(cache_before), wait, keep_unchanged)))
+            }
+            Err(pending) => {
+                panel.loading = Some(pending);
+                Ok(None)
+            }
+        }
+    }
+
+    /// Sort `entries` into the panel on `side` and update its selection,
+    /// diagnostics and preview (waiting up to `preview_wait` for it).
+    /// Returns the changes from the previous listing; a listing of another
+    /// directory counts every entry as added. With `keep_unchanged` an
+    /// unchanged listing of the same directory is dropped.
+    fn apply_listing(
+        &mut self,
+        side: Side,
+        mut entries: Vec<crate::app::types::Entry>,
+        started: std::time::Instant,
+        cache_before: Option<(u64, u64)>,
+        preview_wait: std::time::Duration,
+        keep_unchanged: bool,
+    ) -> ListingChanges {
         let panel = match side {
             Side::Left => &mut self.left,
             Side::Right => &mut self.right,
         };
-        // Read directory entries once via a helper so the iteration and
-        // filesystem interaction can be easily unit-tested or refactored.
-        let mut entries = panel.read_entries()?;
 
-        // Single sort pass. For `Name` sort, keep directories first (so dirs
-        // appear before files) then compare by name. For other sorts compare
-        // by the selected key. Apply `sort_desc` by reversing once to avoid
-        // multiple reversals.
-        match self.sort {
-            SortKey::Name => entries.sort_by_key(|entry| (!entry.is_dir, entry.name.to_lowercase())),
-            SortKey::Size => entries.sort_by_key(|entry| entry.size),
-            SortKey::Modified => entries.sort_by_key(|entry| entry.modified),
-        }
+        super::listing::filter_hidden(&mut entries, self.settings.show_hidden);
+        super::listing::sort_entries(&mut entries, self.sort, self.sort_order);
 
-        if self.sort_order == crate::app::types::SortOrder::Descending {
-            entries.reverse();
+        let same_dir = panel.listed_dir == panel.cwd;
+        let changes = if same_dir {
+            super::listing::diff(&panel.entries, &entries)
+        } else {
+            ListingChanges { added: entries.len(), ..Default::default() }
+        };
+        if panel.refresh_failure.take().is_some() {
+            panel.dirty = true;
         }
-
+        // Read before the unchanged check: staging or committing changes
+        // the status but not the listing.
+        #[cfg(feature = "git")]
+        {
+            panel.git = if panel.vfs.is_local() { crate::integrations::git::status(&panel.cwd) } else { None };
+        }
+        if keep_unchanged && same_dir && changes.is_empty() {
+            self.diagnostics.record_load(side, entries.len(), started.elapsed());
+            return changes;
+        }
+        if !same_dir {
+            panel.remember_position();
+            panel.crumb = 0;
+            panel.flat = None;
+            self.path_jump.visited(&panel.cwd);
+            self.frecency.visit(&panel.cwd);
+        }
+        panel.listed_dir = panel.cwd.clone();
+
+        // Plugins are asked for their columns on a worker thread and
+        // `poll_listings` fills them in. Until then entries still listed
+        // keep the text they had.
+        panel.plugin_columns_loading = None;
+        panel.plugin_columns = if self.plugins.has_columns() {
+            let paths: Vec<std::path::PathBuf> = entries.iter().map(|e| e.path.clone()).collect();
+            let known: std::collections::HashMap<_, _> = if same_dir {
+                panel.entries.iter().map(|e| &e.path).zip(&panel.plugin_columns).collect()
+            } else {
+                Default::default()
+            };
+            let shown = paths.iter().map(|p| known.get(p).map_or_else(String::new, |c| c.to_string())).collect();
+            let plugins = self.plugins.clone();
+            let work = move || plugins.column_text(&paths);
+            match super::panel::Pending::run(panel.cwd.clone(), std::time::Duration::ZERO, work) {
+                Ok(columns) => columns,
+                Err(pending) => {
+                    panel.plugin_columns_loading = Some(pending);
+                    shown
+                }
+            }
+        } else {
+            Vec::new()
+        };
         // Keep `panel.entries` as a pure domain list: only filesystem
         // entries (no synthetic header/parent). Store the read entries
         // directly and clamp UI selection/offset against the UI row
         // count (header + parent + entries).
         panel.entries = entries;
+        panel.dirty = true;
         let visible_rows = super::utils::ui_row_count(panel);
         let last_index = visible_rows.saturating_sub(1);
         if panel.selected > last_index {
@@ -342,8 +897,20 @@ impl App {
         if panel.offset > last_index {
             panel.offset = last_index;
         }
-        self.update_preview_for(side);
-        Ok(())
+        if !same_dir {
+            panel.restore_position();
+        }
+        let loaded = panel.entries.len();
+        self.diagnostics.record_load(side, loaded, started.elapsed());
+        if let Some((hits_before, misses_before)) = cache_before {
+            let (hits, misses) = crate::fs_op::cache::global().stats();
+            self.diagnostics.record_cache_counts(hits - hits_before, misses - misses_before);
+        }
+        self.update_preview_within(side, preview_wait);
+        if !same_dir && side == self.active {
+            self.follow_link(side);
+        }
+        changes
     }
 }
 
@@ -40,6 +40,34 @@ pub struct App {
     pub menu_state: crate::ui::menu_model::MenuState,
     /// Whether the preview pane is visible in the UI.
     pub preview_visible: bool,
+    /// Whether scrolling keys go to the preview pane instead of the
+    /// active panel's list.
+    pub preview_focused: bool,
+    /// What the inactive panel shows: its listing, quick view or info.
+    pub other_panel: crate::app::types::OtherPanel,
+    /// Directory totals for the info panel.
+    pub info: info::InfoState,
+    /// Directory tree sidebar (see `tree`).
+    pub tree: tree::DirTree,
+    /// Disk usage analyzer, while it is open.
+    pub usage: Option<usage::DiskUsage>,
+    /// Shell command from the command line or user menu still running.
+    pub shell: Option<crate::runner::shell::ShellRun>,
+    /// Content comparison started from the conflict dialog, while it runs.
+    pub compare: Option<crate::runner::handlers::conflict::ContentCompare>,
+    /// Scan for other processes holding the files of an open delete or
+    /// overwrite dialog, while it runs.
+    pub open_files: Option<crate::runner::open_files::OpenFilesCheck>,
+    /// In file picker mode (`--choose-files`), the paths confirmed with
+    /// Enter, printed on exit; `None` otherwise.
+    pub chooser: Option<Vec<std::path::PathBuf>>,
+    /// Linked panels: the other panel follows the active one into
+    /// subdirectories (see `link`).
+    pub link: Option<link::PanelLink>,
+    /// Templates offered by the open new-file dialog.
+    pub templates: templates::TemplatePicker,
+    /// User and group completion of the open change-owner dialog.
+    pub owners: owners::OwnerCompletion,
     /// Whether the dedicated file-stats column is visible in the UI.
     pub file_stats_visible: bool,
     /// Optional command-line state when user opens the command input.
@@ -50,9 +78,18 @@ pub struct App {
     pub op_progress_rx: Option<OpProgressReceiver>,
     /// Cancel flag shared with background operation thread (if any).
     pub op_cancel_flag: Option<OpCancelFlag>,
+    /// Set to skip the rest of a copy's verification pass; present while a
+    /// copy with `verify_after_copy` runs.
+    pub op_skip_verify: Option<OpCancelFlag>,
+    /// Bytes, speed and pausing of the running copy or move (see
+    /// `fs_op::meter`).
+    pub op_meter: Option<std::sync::Arc<crate::fs_op::meter::Meter>>,
     /// Sender for communicating user's decision back to the background worker
     /// when a file-exists conflict is presented.
     pub op_decision_tx: Option<OpDecisionSender>,
+    /// Copies and moves running in the background or waiting for a slot
+    /// (see `runner::jobs`).
+    pub scheduler: crate::runner::jobs::Scheduler,
     /// Last mouse click timestamp (used for double-click detection).
     pub last_mouse_click_time: Option<std::time::Instant>,
     /// Last mouse click position (column, row).
@@ -65,16 +102,63 @@ pub struct App {
     pub drag_current: Option<(u16, u16)>,
     /// Which mouse button started the drag.
     pub drag_button: Option<crate::input::mouse::MouseButton>,
+    /// Scrollbar held down with the left button, which drags follow.
+    pub scroll_drag: Option<crate::app::types::ScrollTarget>,
+    /// Something on screen changed outside the key and mouse handlers
+    /// (job progress, a finished listing) since the last frame; see
+    /// `take_dirty`.
+    pub dirty: bool,
+    /// External plugins providing context-menu actions and panel columns.
+    pub plugins: crate::plugins::PluginRegistry,
+    /// Frame/load timings and counters for the diagnostics overlay.
+    pub diagnostics: diagnostics::Diagnostics,
+    /// Running job and history of finished jobs.
+    pub jobs: jobs::JobHistory,
+    /// Keys typed so far of an unfinished key sequence.
+    pub pending_keys: pending_keys::PendingKeys,
+    /// Recently visited directories and the change-path dialog's
+    /// completions.
+    pub path_jump: path_jump::PathJump,
+    /// Visit ranks of directories, for `:jump`.
+    pub frecency: frecency::Frecency,
+    /// Named, coloured file tags.
+    pub tags: tags::Tags,
+    /// Recent and saved searches.
+    pub searches: searches::Searches,
+    /// Entries yanked or cut for pasting.
+    pub clipboard: Option<clipboard::Clipboard>,
+    /// Arrangement of the panels (saved in the session).
+    pub layout: crate::app::types::PanelLayout,
 }
 
 // submodules live in `app/src/app/core/`
 pub mod panel;
+pub mod diagnostics;
+pub mod jobs;
+pub mod clipboard;
+pub mod clock;
+pub mod encoding;
+pub mod follow;
+pub mod frecency;
+pub mod info;
+pub mod link;
+pub mod listing;
+pub mod owners;
+pub mod path_jump;
+pub mod pending_keys;
+pub mod refresh_backoff;
+pub mod searches;
+pub mod tags;
+pub mod templates;
+pub mod tree;
+pub mod usage;
 // Re-export the canonical path helpers into the `app::core` namespace so
 // code referencing `crate::app::core::path` continues to work without using
 // the deprecated `app::path` shim.
 pub use crate::fs_op::path;
 mod navigation;
 mod preview;
+pub mod preview_format;
 pub mod preview_helpers;
 
 mod init;
@@ -109,6 +193,14 @@ impl App {
         }
     }
 
+    /// Return a reference to the panel identified by `side`.
+    pub fn panel(&self, side: Side) -> &Panel {
+        match side {
+            Side::Left => &self.left,
+            Side::Right => &self.right,
+        }
+    }
+
     /// Return a mutable reference to the panel identified by `side`.
     pub fn panel_mut(&mut self, side: Side) -> &mut Panel {
         match side {
@@ -33,11 +33,24 @@ impl App {
         if !panel.entries.is_empty() {
             op(panel);
         }
+        // Leaving the breadcrumb row drops the breadcrumb picked there.
+        if panel.selected != 0 {
+            panel.crumb = 0;
+        }
 
         self.ensure_selection_visible(viewport_height);
         self.update_preview_for(self.active);
     }
 
+    /// Put the cursor on the breadcrumb row with the parent directory
+    /// picked, so Left/Right choose an ancestor and Enter jumps to it.
+    pub fn focus_breadcrumbs(&mut self) {
+        let panel = self.active_panel_mut();
+        panel.selected = 0;
+        panel.crumb = 0;
+        panel.move_crumb(1);
+    }
+
     /// Move active selection down by one UI row.
     pub fn select_next(&mut self, viewport_height: usize) {
         self.apply_navigation(viewport_height, |panel| panel.select_next());
@@ -0,0 +1,118 @@
+//! Completion state of the change-owner dialog (`InputKind::ChangeOwner`).
+//!
+//! The dialog takes `[-R ]user[:group]`. While it is open `candidates`
+//! holds the system users (or, after a `:`, groups) starting with the name
+//! being typed; Tab accepts the highlighted one, Up/Down choose one. Names
+//! come from `/etc/passwd` and `/etc/group`; accounts only known to other
+//! name services can still be typed in full.
+
+use std::path::Path;
+
+use super::path_jump::{Candidate, MAX_CANDIDATES};
+
+/// Prefix of the dialog input asking for the change to recurse.
+pub const RECURSIVE_FLAG: &str = "-R ";
+
+/// System users and groups, and the open dialog's suggestions.
+#[derive(Clone, Debug, Default)]
+pub struct OwnerCompletion {
+    users: Vec<String>,
+    groups: Vec<String>,
+    pub candidates: Vec<Candidate>,
+    /// Candidate chosen with Up/Down, if any.
+    pub selected: Option<usize>,
+}
+
+impl OwnerCompletion {
+    /// Load the user and group names when the dialog opens.
+    pub fn open(&mut self) {
+        self.users = account_names(Path::new("/etc/passwd"));
+        self.groups = account_names(Path::new("/etc/group"));
+        self.candidates.clear();
+        self.selected = None;
+    }
+
+    /// Recompute the candidates for `input`: the users completing the
+    /// name before the `:`, or the groups completing the one after it.
+    pub fn update(&mut self, input: &str) {
+        self.selected = None;
+        let (head, word, names) = match input.rfind(':') {
+            Some(i) => (&input[..=i], &input[i + 1..], &self.groups),
+            None => {
+                let start = if input.starts_with(RECURSIVE_FLAG) { RECURSIVE_FLAG.len() } else { 0 };
+                (&input[..start], &input[start..], &self.users)
+            }
+        };
+        self.candidates = names
+            .iter()
+            .filter(|n| n.starts_with(word) && n.as_str() != word)
+            .take(MAX_CANDIDATES)
+            .map(|n| Candidate { text: format!("{}{}", head, n), recent: false })
+            .collect();
+    }
+
+    /// Move the highlight `delta` candidates down (negative: up), wrapping.
+    pub fn move_selection(&mut self, delta: isize) {
+        let n = self.candidates.len() as isize;
+        if n > 0 {
+            let from = self.selected.map_or(if delta < 0 { 0 } else { -1 }, |i| i as isize);
+            self.selected = Some((from + delta).rem_euclid(n) as usize);
+        }
+    }
+
+    /// Text Tab puts in the input: the highlighted candidate, or the first.
+    pub fn completion(&self) -> Option<&str> {
+        self.candidates.get(self.selected.unwrap_or(0)).map(|c| c.text.as_str())
+    }
+
+    /// Forget the names and suggestions when the dialog closes.
+    pub fn close(&mut self) {
+        *self = Self::default();
+    }
+}
+
+/// Split dialog input into the owner spec and whether to recurse.
+pub fn split_recursive(input: &str) -> (&str, bool) {
+    match input.trim_start().strip_prefix(RECURSIVE_FLAG.trim_end()) {
+        Some(rest) if rest.starts_with(char::is_whitespace) => (rest.trim(), true),
+        _ => (input.trim(), false),
+    }
+}
+
+/// The sorted first fields of a passwd- or group-style file.
+fn account_names(file: &Path) -> Vec<String> {
+    let mut names: Vec<String> = std::fs::read_to_string(file)
+        .map(|text| {
+            text.lines()
+                .filter(|l| !l.starts_with('#'))
+                .filter_map(|l| l.split(':').next())
+                .filter(|n| !n.is_empty())
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default();
+    names.sort();
+    names.dedup();
+    names
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn completes_users_then_groups() {
+        let mut owners = OwnerCompletion {
+            users: vec!["alex".into(), "alice".into(), "bob".into()],
+            groups: vec!["staff".into(), "wheel".into()],
+            ..Default::default()
+        };
+        owners.update("-R al");
+        let texts: Vec<&str> = owners.candidates.iter().map(|c| c.text.as_str()).collect();
+        assert_eq!(texts, ["-R alex", "-R alice"]);
+        owners.update("alice:w");
+        assert_eq!(owners.completion(), Some("alice:wheel"));
+        assert_eq!(split_recursive("-R alice:wheel"), ("alice:wheel", true));
+        assert_eq!(split_recursive("-Ralice"), ("-Ralice", false));
+    }
+}
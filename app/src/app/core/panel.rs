@@ -1,9 +1,70 @@
 use crate::app::types::Entry;
 use chrono::{DateTime, Local};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+use std::ffi::{OsStr, OsString};
 use std::io;
-use std::path::PathBuf;
-use walkdir::WalkDir;
+use std::path::{Path, PathBuf};
+use std::sync::{mpsc, Arc};
+use std::time::{Duration, Instant};
+
+use crate::fs_op::vfs::VfsProvider;
+
+/// How long a refresh or preview waits for the filesystem before leaving
+/// the work to finish in the background (see `Pending`).
+pub const LISTING_WAIT: Duration = Duration::from_millis(200);
+
+/// How long the "directory changed externally" hint stays in the footer.
+pub const EXTERNAL_CHANGE_HINT: Duration = Duration::from_secs(5);
+
+/// Work for a panel (a listing or a preview) still running on a worker
+/// thread. Slow or remote backends finish here so the UI keeps drawing and
+/// can cancel the load; `App::poll_listings` applies the result once it
+/// arrives.
+#[derive(Debug)]
+pub struct Pending<T> {
+    /// Directory being listed or entry being previewed.
+    pub path: PathBuf,
+    pub started: Instant,
+    rx: mpsc::Receiver<T>,
+}
+
+/// A directory listing still in progress.
+pub type PendingListing = Pending<io::Result<Vec<Entry>>>;
+
+impl<T: Send + 'static> Pending<T> {
+    /// Run `work` on a worker thread and wait up to `wait` for its result;
+    /// `Err` carries the still-running work.
+    pub(crate) fn run(path: PathBuf, wait: Duration, work: impl FnOnce() -> T + Send + 'static) -> Result<T, Pending<T>> {
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let _ = tx.send(work());
+            crate::runner::wake::wake();
+        });
+        let started = Instant::now();
+        match rx.recv_timeout(wait) {
+            Ok(res) => Ok(res),
+            Err(_) => Err(Pending { path, started, rx }),
+        }
+    }
+
+    /// The result if the worker has finished. `Disconnected` means the
+    /// worker died without one.
+    pub(crate) fn try_take(&self) -> Result<T, mpsc::TryRecvError> {
+        self.rx.try_recv()
+    }
+}
+
+/// Where the cursor was in a directory the panel left, so coming back
+/// puts it on the same entry.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DirPosition {
+    /// Name of the selected entry; `None` on the header or `..` row.
+    pub name: Option<OsString>,
+    /// UI row of the selection, used when the entry is gone.
+    pub selected: usize,
+    /// UI scroll offset.
+    pub offset: usize,
+}
 
 /// Panel holds the minimal, UI-independent state for one side of the
 /// dual-pane file manager. It intentionally keeps presentation details
@@ -26,24 +87,106 @@ pub struct Panel {
     pub preview: String,
     /// Scroll offset for the preview text.
     pub preview_offset: usize,
+    /// Encoding picked for the preview with the encoding picker; `None`
+    /// detects it per file.
+    pub preview_encoding: Option<&'static encoding_rs::Encoding>,
+    /// The preview follows the end of the selected file (`F`, see
+    /// `core::follow`).
+    pub follow: Option<crate::app::core::follow::Follow>,
+    /// Folded blocks of a JSON preview, once one was folded.
+    pub preview_folds: Option<crate::app::core::preview_format::JsonFolds>,
     /// Selected entry indices for multi-selection (domain indexes into `entries`).
     pub selections: HashSet<usize>,
+    /// Plugin column text per entry (aligned with `entries`); empty when no
+    /// plugin registers columns.
+    pub plugin_columns: Vec<String>,
+    /// Plugin column text for the current `entries` still being asked
+    /// from the plugins.
+    pub plugin_columns_loading: Option<Pending<Vec<String>>>,
+    /// Filesystem backend the panel lists and previews through.
+    pub vfs: Arc<dyn VfsProvider>,
+    /// Listing of `cwd` still in progress, if it took longer than
+    /// `LISTING_WAIT`. `entries` still hold the previous listing.
+    pub loading: Option<PendingListing>,
+    /// Preview of the selected entry still in progress.
+    pub preview_loading: Option<Pending<String>>,
+    /// Directory `entries` were read from; cancelling a pending listing
+    /// returns the panel here. Empty before the first listing of an app
+    /// built with `App::unlisted`.
+    pub listed_dir: PathBuf,
+    /// Changes found by the last watcher-driven refresh and when, shown as
+    /// a hint for `EXTERNAL_CHANGE_HINT`.
+    pub external_change: Option<(crate::app::core::listing::ListingChanges, Instant)>,
+    /// Set while watcher-driven refreshes of `cwd` keep failing; cleared by
+    /// the next successful listing.
+    pub refresh_failure: Option<crate::app::core::refresh_backoff::RefreshFailure>,
+    /// `loading` was started by the watcher, so its failure goes to
+    /// `refresh_failure` instead of an error dialog.
+    pub quiet_listing: bool,
+    /// The listing or preview changed since the last frame.
+    pub dirty: bool,
+    /// Cursor positions in directories visited during this run, restored
+    /// when the panel lists them again.
+    pub positions: HashMap<PathBuf, DirPosition>,
+    /// Breadcrumb picked with Left/Right while the cursor is on the
+    /// breadcrumb row, in levels above `cwd` (0 is `cwd` itself).
+    pub crumb: usize,
+    /// Directory listed flat, with every file below it (see `list_flat`);
+    /// listing another directory ends the flat view.
+    pub flat: Option<PathBuf>,
+    /// Search whose results the panel lists at their root (see
+    /// `App::start_search`); `vfs` is the same provider.
+    pub search: Option<Arc<crate::fs_op::search::SearchResultsFs>>,
+    /// Git status of the work tree `cwd` is in, read with each listing.
+    #[cfg(feature = "git")]
+    pub git: Option<crate::integrations::git::GitStatus>,
 }
 
 impl Panel {
-    /// Create a new panel rooted at `cwd` with sensible defaults.
+    /// Create a new panel rooted at `cwd` on the local filesystem.
     pub fn new(cwd: PathBuf) -> Self {
+        Self::with_vfs(cwd, crate::fs_op::vfs::local())
+    }
+
+    /// Create a new panel rooted at `cwd` backed by `vfs`.
+    pub fn with_vfs(cwd: PathBuf, vfs: Arc<dyn VfsProvider>) -> Self {
         Panel {
+            listed_dir: cwd.clone(),
             cwd,
             entries: Vec::new(),
             selected: 0,
             offset: 0,
             preview: String::new(),
             preview_offset: 0,
+            preview_encoding: None,
+            follow: None,
+            preview_folds: None,
             selections: HashSet::new(),
+            plugin_columns: Vec::new(),
+            plugin_columns_loading: None,
+            vfs,
+            loading: None,
+            preview_loading: None,
+            external_change: None,
+            refresh_failure: None,
+            quiet_listing: false,
+            dirty: false,
+            positions: HashMap::new(),
+            crumb: 0,
+            flat: None,
+            search: None,
+            #[cfg(feature = "git")]
+            git: None,
         }
     }
 
+    /// Footer hint for a recent change made outside the app, such as
+    /// `directory changed externally (+3 files)`.
+    pub fn external_change_hint(&self) -> Option<String> {
+        let (changes, at) = self.external_change.as_ref()?;
+        (at.elapsed() < EXTERNAL_CHANGE_HINT).then(|| format!("directory changed externally ({})", changes))
+    }
+
     /// Toggle selection of the currently selected entry (if any).
     pub fn toggle_selection(&mut self) {
         if let Some(idx) = super::utils::ui_to_entry_index(self.selected, self) {
@@ -69,6 +212,69 @@ impl Panel {
             .and_then(|idx| self.entries.get(idx))
     }
 
+    /// Index into `entries` of the selected row, if it is an entry.
+    pub fn selected_entry_index(&self) -> Option<usize> {
+        super::utils::ui_to_entry_index(self.selected, self)
+    }
+
+    /// Remember the cursor in `listed_dir` before the panel lists another
+    /// directory. `entries` must still hold the listing of `listed_dir`.
+    pub(crate) fn remember_position(&mut self) {
+        if self.entries.is_empty() {
+            return;
+        }
+        let first_entry = super::utils::HEADER_ROWS + self.listed_dir.parent().is_some() as usize;
+        let name = self.selected.checked_sub(first_entry).and_then(|i| self.entries.get(i)).map(|e| e.name.clone());
+        let position = DirPosition { name, selected: self.selected, offset: self.offset };
+        self.positions.insert(self.listed_dir.clone(), position);
+    }
+
+    /// Put the cursor back where it was when `cwd` was last left, if it
+    /// was visited before. Call after `entries` hold the new listing.
+    pub(crate) fn restore_position(&mut self) {
+        let Some(position) = self.positions.get(&self.cwd).cloned() else {
+            return;
+        };
+        if !position.name.is_some_and(|name| self.select_named(name)) {
+            self.selected = position.selected;
+            self.clamp_selected();
+        }
+        self.offset = position.offset.min(self.selected);
+    }
+
+    /// Select the file `path` when its directory is next listed, as if the
+    /// cursor had been left on it.
+    pub fn select_after_listing(&mut self, path: &Path) {
+        if let (Some(dir), Some(name)) = (path.parent(), path.file_name()) {
+            let position = DirPosition { name: Some(name.to_owned()), ..Default::default() };
+            self.positions.insert(dir.to_path_buf(), position);
+        }
+    }
+
+    /// Pick the breadcrumb `delta` levels further up (negative: down),
+    /// staying between the root and `cwd`.
+    pub fn move_crumb(&mut self, delta: isize) {
+        let depth = self.cwd.ancestors().count().saturating_sub(1);
+        self.crumb = self.crumb.saturating_add_signed(delta).min(depth);
+    }
+
+    /// Directory of the picked breadcrumb.
+    pub fn crumb_dir(&self) -> &Path {
+        self.cwd.ancestors().nth(self.crumb).unwrap_or(&self.cwd)
+    }
+
+    /// Select the entry called `name`; returns whether it was found.
+    pub fn select_named(&mut self, name: impl AsRef<OsStr>) -> bool {
+        let name = name.as_ref();
+        match self.entries.iter().position(|e| e.name == name) {
+            Some(idx) => {
+                self.selected = super::utils::entry_to_ui_index(idx, self);
+                true
+            }
+            None => false,
+        }
+    }
+
     /// Move selection down by one, clamping at the last UI row.
     pub fn select_next(&mut self) {
         let max_rows = super::utils::ui_row_count(self);
@@ -125,79 +331,174 @@ impl Panel {
         }
     }
 
+    /// Scroll a list `rows` tall so it starts at entry `start` (clamped to
+    /// the last full page), moving the selection into view when it leaves.
+    pub fn scroll_to(&mut self, start: usize, rows: usize) {
+        let rows = rows.max(1);
+        let start = start.min(self.entries.len().saturating_sub(rows));
+        self.offset = super::utils::entry_to_ui_index(start, self);
+        let end = start.saturating_add(rows).min(self.entries.len());
+        if start >= end {
+            return;
+        }
+        let sel = super::utils::ui_to_entry_index(self.selected, self).unwrap_or(start).clamp(start, end - 1);
+        self.selected = super::utils::entry_to_ui_index(sel, self);
+    }
+
+    /// Range of `entries` to materialize for a list `rows` tall: starts at
+    /// the scroll `offset` and moves just enough to keep the selected entry
+    /// in view, so renderers never touch rows that are off screen.
+    pub fn visible_range(&self, rows: usize) -> std::ops::Range<usize> {
+        let len = self.entries.len();
+        let rows = rows.max(1);
+        let mut start = self.offset.saturating_sub(super::utils::entry_to_ui_index(0, self));
+        if let Some(sel) = super::utils::ui_to_entry_index(self.selected, self) {
+            if sel < start {
+                start = sel;
+            } else if sel >= start.saturating_add(rows) {
+                start = sel + 1 - rows;
+            }
+        }
+        start = start.min(len.saturating_sub(rows));
+        start..start.saturating_add(rows).min(len)
+    }
+
     /// Replace the preview text and reset the preview scroll offset.
     pub fn set_preview(&mut self, text: String) {
         self.preview = text;
         self.preview_offset = 0;
+        self.preview_folds = None;
+        self.dirty = true;
     }
 
-    /// Read directory entries and return a Vec<Entry>.
-    /// This centralises the filesystem access and metadata reading used by
-    /// `App::refresh_panel` and keeps the Panel's path-related concerns in one place.
-    /// Read the immediate children of the panel's `cwd` and return them as
-    /// a `Vec<Entry>`. This is intentionally a thin wrapper around
-    /// filesystem access so callers can handle errors appropriately.
-    pub(crate) fn read_entries(&self) -> io::Result<Vec<Entry>> {
-        let mut entries_vec = Vec::new();
+    /// Read the immediate children of the panel's `cwd` through the
+    /// panel's `vfs` and return them as a `Vec<Entry>`. This is intentionally
+    /// a thin wrapper so callers can handle errors appropriately. Refreshes
+    /// use `read_entries_within` so a slow backend cannot block the UI.
+    /// With `hide_ignored`, entries a local git work tree ignores are left
+    /// out (see `fs_op::ignore`).
+    #[cfg(test)]
+    pub(crate) fn read_entries(&self, hide_ignored: bool) -> io::Result<Vec<Entry>> {
+        list_dir(&*self.vfs, &self.cwd, hide_ignored)
+    }
 
-        for dir_entry_result in WalkDir::new(&self.cwd)
-            .min_depth(1)
-            .max_depth(1)
-            .follow_links(false)
-        {
-            let dir_entry = dir_entry_result
-                .map_err(io::Error::other)?;
-
-            let metadata = dir_entry.metadata()?;
-            let modified_time = metadata.modified().ok().map(DateTime::<Local>::from);
-            let name = dir_entry.file_name().to_string_lossy().into_owned();
-            let path_buf = dir_entry.path().to_path_buf();
-
-            let mut file_entry = if metadata.is_dir() {
-                Entry::directory(name, path_buf.clone(), modified_time)
-            } else {
-                Entry::file(name, path_buf.clone(), metadata.len(), modified_time)
-            };
-
-            // Best-effort: populate permission/ownership flags using the
-            // existing helpers. Failure to inspect is tolerated.
-            if let Ok(perms) = crate::fs_op::permissions::inspect_permissions(&path_buf, false)
-            {
-                file_entry.unix_mode = perms.unix_mode;
-                file_entry.can_read = Some(perms.can_read);
-                file_entry.can_write = Some(perms.can_write);
-                file_entry.can_execute = Some(perms.can_execute);
-            }
+    /// Like `read_entries`, but on a worker thread: waits up to `wait` and
+    /// returns `Err(pending)` when the listing is still running. In the
+    /// flat view the whole tree below `cwd` is listed instead, without
+    /// hidden entries unless `show_hidden` is set.
+    pub(crate) fn read_entries_within(&self, wait: Duration, hide_ignored: bool, show_hidden: bool) -> Result<io::Result<Vec<Entry>>, PendingListing> {
+        let (vfs, dir) = (self.vfs.clone(), self.cwd.clone());
+        if self.is_flat() {
+            return Pending::run(self.cwd.clone(), wait, move || list_flat(&*vfs, &dir, hide_ignored, show_hidden));
+        }
+        Pending::run(self.cwd.clone(), wait, move || list_dir(&*vfs, &dir, hide_ignored))
+    }
+
+    /// Whether the panel shows its directory flat.
+    pub fn is_flat(&self) -> bool {
+        self.flat.as_ref() == Some(&self.cwd)
+    }
 
-            // Best-effort: uid/gid when available on unix platforms.
-            #[cfg(unix)]
-            {
-                use std::os::unix::fs::MetadataExt;
-                file_entry.uid = Some(metadata.uid());
-                file_entry.gid = Some(metadata.gid());
-
-                // Best-effort: resolve uid/gid to names for display
-                // Use the `users` crate which works cross-platform.
-                if let Some(u) = users::get_user_by_uid(metadata.uid()) {
-                    file_entry.owner = Some(u.name().to_string_lossy().into_owned());
-                }
-                if let Some(g) = users::get_group_by_gid(metadata.gid()) {
-                    file_entry.group = Some(g.name().to_string_lossy().into_owned());
-                }
+    /// The search whose results the panel shows, while it lists their root.
+    pub fn search_results(&self) -> Option<&Arc<crate::fs_op::search::SearchResultsFs>> {
+        self.search.as_ref().filter(|s| s.root == self.cwd)
+    }
+}
+
+/// List `dir` through `vfs`, without the git-ignored entries when
+/// `hide_ignored` is set and the backend is local.
+fn list_dir(vfs: &dyn VfsProvider, dir: &Path, hide_ignored: bool) -> io::Result<Vec<Entry>> {
+    let mut entries = vfs.list(dir)?;
+    if hide_ignored && vfs.is_local() {
+        crate::fs_op::ignore::filter_ignored(dir, &mut entries);
+    }
+    Ok(entries)
+}
+
+/// Every file below `dir`, named by its path relative to `dir`, for the
+/// flat view. Symbolic links are listed but not followed, and unreadable
+/// subdirectories are skipped.
+fn list_flat(vfs: &dyn VfsProvider, dir: &Path, hide_ignored: bool, show_hidden: bool) -> io::Result<Vec<Entry>> {
+    let mut files = Vec::new();
+    let mut dirs = vec![dir.to_path_buf()];
+    while let Some(current) = dirs.pop() {
+        let entries = match list_dir(vfs, &current, hide_ignored) {
+            Ok(entries) => entries,
+            Err(e) if current == dir => return Err(e),
+            Err(_) => continue,
+        };
+        for mut entry in entries {
+            if !show_hidden && crate::app::core::listing::is_hidden(&entry) {
+                continue;
             }
-            #[cfg(not(unix))]
-            {
-                // populate the uid/gid fields where possible via metadata but
-                // avoid making platform assumptions about user/group resolution
-                file_entry.uid = None;
-                file_entry.gid = None;
+            if entry.is_dir {
+                dirs.push(entry.path);
+                continue;
             }
-
-            entries_vec.push(file_entry);
+            if let Ok(relative) = entry.path.strip_prefix(dir) {
+                entry.name = relative.as_os_str().to_owned();
+            }
+            files.push(entry);
         }
+    }
+    Ok(files)
+}
+
+/// Build an `Entry` for `path` from already-read `metadata`, filling in the
+/// best-effort permission and ownership fields.
+pub(crate) fn entry_from_metadata(name: OsString, path_buf: PathBuf, metadata: &std::fs::Metadata) -> Entry {
+    let modified_time = metadata.modified().ok().map(DateTime::<Local>::from);
+
+    let mut file_entry = if metadata.is_dir() {
+        Entry::directory(name, path_buf.clone(), modified_time)
+    } else {
+        Entry::file(name, path_buf.clone(), metadata.len(), modified_time)
+    };
+    file_entry.is_symlink = metadata.file_type().is_symlink();
+    file_entry.attributes = crate::fs_op::attributes::read(metadata);
 
-        Ok(entries_vec)
+    // Best-effort: populate permission/ownership flags using the
+    // existing helpers. Failure to inspect is tolerated.
+    if let Ok(perms) = crate::fs_op::permissions::inspect_permissions(&path_buf, false)
+    {
+        file_entry.unix_mode = perms.unix_mode;
+        file_entry.can_read = Some(perms.can_read);
+        file_entry.can_write = Some(perms.can_write);
+        file_entry.can_execute = Some(perms.can_execute);
     }
+
+    // Best-effort: uid/gid when available on unix platforms.
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        file_entry.uid = Some(metadata.uid());
+        file_entry.gid = Some(metadata.gid());
+
+        // Best-effort: resolve uid/gid to names for display
+        // Use the `users` crate which works cross-platform.
+        if let Some(u) = users::get_user_by_uid(metadata.uid()) {
+            file_entry.owner = Some(u.name().to_string_lossy().into_owned());
+        }
+        if let Some(g) = users::get_group_by_gid(metadata.gid()) {
+            file_entry.group = Some(g.name().to_string_lossy().into_owned());
+        }
+    }
+    #[cfg(not(unix))]
+    {
+        // populate the uid/gid fields where possible via metadata but
+        // avoid making platform assumptions about user/group resolution
+        file_entry.uid = None;
+        file_entry.gid = None;
+    }
+
+    file_entry
+}
+
+/// Stat `path` (without following symlinks) into an `Entry`.
+pub fn stat_entry(path: &Path) -> io::Result<Entry> {
+    let metadata = std::fs::symlink_metadata(path)?;
+    let name = path.file_name().map(|n| n.to_os_string()).unwrap_or_else(|| path.as_os_str().to_os_string());
+    Ok(entry_from_metadata(name, path.to_path_buf(), &metadata))
 }
 
 #[cfg(test)]
@@ -212,9 +513,9 @@ mod tests {
         temp.child("subdir").create_dir_all().unwrap();
 
         let p = Panel::new(temp.path().to_path_buf());
-        let entries = p.read_entries().unwrap();
+        let entries = p.read_entries(false).unwrap();
         // Expect at least the file and the directory
-        let mut names: Vec<String> = entries.into_iter().map(|e| e.name).collect();
+        let mut names: Vec<String> = entries.iter().map(|e| e.display_name().into_owned()).collect();
         names.sort();
         assert!(names.contains(&"a.txt".to_string()));
         assert!(names.contains(&"subdir".to_string()));
@@ -226,7 +527,7 @@ mod tests {
         // no children created
 
         let p = Panel::new(temp.path().to_path_buf());
-        let entries = p.read_entries().unwrap();
+        let entries = p.read_entries(false).unwrap();
         assert!(entries.is_empty(), "expected no entries in empty temp dir");
     }
 
@@ -237,7 +538,7 @@ mod tests {
         file.write_str("hello").unwrap();
 
         let p = Panel::new(temp.path().to_path_buf());
-        let entries = p.read_entries().unwrap();
+        let entries = p.read_entries(false).unwrap();
         assert!(!entries.is_empty());
         let e = &entries[0];
         // Best-effort checks: permission flags should be set at least
@@ -0,0 +1,105 @@
+//! Completion state of the change-path dialog (`InputKind::ChangePath`).
+//!
+//! While the dialog is open `candidates` holds the directory completions
+//! of the typed path (`fs_op::path::complete_dir`) followed by recently
+//! visited directories that fuzzy-match it. Tab accepts the highlighted
+//! candidate into the input; Up/Down choose one.
+
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+
+use crate::fs_op::path::{complete_dir, fuzzy_score};
+
+/// Directories remembered as suggestions.
+const MAX_RECENT: usize = 50;
+/// Candidates offered at once.
+pub const MAX_CANDIDATES: usize = 8;
+
+/// One suggestion of the change-path dialog.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Candidate {
+    /// Input text the candidate completes to.
+    pub text: String,
+    /// Whether it comes from the recently visited directories.
+    pub recent: bool,
+}
+
+/// Recently visited directories and the open dialog's suggestions.
+#[derive(Clone, Debug, Default)]
+pub struct PathJump {
+    /// Most recently visited first.
+    recent: VecDeque<PathBuf>,
+    pub candidates: Vec<Candidate>,
+    /// Candidate chosen with Up/Down, if any.
+    pub selected: Option<usize>,
+}
+
+impl PathJump {
+    /// Record that a panel listed `dir`.
+    pub fn visited(&mut self, dir: &Path) {
+        self.recent.retain(|d| d != dir);
+        self.recent.push_front(dir.to_path_buf());
+        self.recent.truncate(MAX_RECENT);
+    }
+
+    /// Recently visited directories, most recent first.
+    pub fn recent(&self) -> impl Iterator<Item = &Path> {
+        self.recent.iter().map(PathBuf::as_path)
+    }
+
+    /// Recompute the candidates for `input` typed in a panel showing `cwd`.
+    /// Empty input offers the recent directories other than `cwd`.
+    pub fn update(&mut self, input: &str, cwd: &Path) {
+        self.selected = None;
+        let mut candidates: Vec<Candidate> = if input.is_empty() {
+            Vec::new()
+        } else {
+            complete_dir(input, cwd).into_iter().map(|text| Candidate { text, recent: false }).collect()
+        };
+        let mut recent: Vec<(u32, usize, String)> = self
+            .recent
+            .iter()
+            .enumerate()
+            .filter(|(_, d)| d.as_path() != cwd)
+            .filter_map(|(i, d)| {
+                let text = d.display().to_string();
+                fuzzy_score(input, &text).map(|score| (score, i, text))
+            })
+            .collect();
+        recent.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+        for (_, _, text) in recent {
+            let text = format!("{}{}", text.trim_end_matches(std::path::MAIN_SEPARATOR), std::path::MAIN_SEPARATOR);
+            if !candidates.iter().any(|c| c.text == text) {
+                candidates.push(Candidate { text, recent: true });
+            }
+        }
+        candidates.truncate(MAX_CANDIDATES);
+        self.candidates = candidates;
+    }
+
+    /// Move the highlight `delta` candidates down (negative: up), wrapping.
+    pub fn move_selection(&mut self, delta: isize) {
+        let n = self.candidates.len() as isize;
+        if n == 0 {
+            return;
+        }
+        let from = match (self.selected, delta < 0) {
+            (Some(i), _) => i as isize,
+            (None, true) => 0,
+            (None, false) => -1,
+        };
+        self.selected = Some((from + delta).rem_euclid(n) as usize);
+    }
+
+    /// Text Tab puts in the input: the highlighted candidate, or the best
+    /// one when none is highlighted.
+    pub fn completion(&self) -> Option<&str> {
+        self.candidates.get(self.selected.unwrap_or(0)).map(|c| c.text.as_str())
+    }
+
+    /// Clear the suggestions when the dialog closes.
+    pub fn close(&mut self) {
+        self.candidates.clear();
+        self.selected = None;
+    }
+}
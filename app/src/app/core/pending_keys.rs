@@ -0,0 +1,45 @@
+//! Keys typed so far of a multi-key sequence (`g g`, `Space r`).
+//!
+//! Normal mode pushes the first key of a sequence here instead of acting
+//! on it; `runner::handlers::sequence` finishes, cancels or times the
+//! sequence out and the hint popup lists the possible continuations.
+
+use std::time::{Duration, Instant};
+
+use crate::app::settings::keymap::KeyChord;
+
+/// Keys of an unfinished key sequence.
+#[derive(Clone, Debug, Default)]
+pub struct PendingKeys {
+    pub keys: Vec<KeyChord>,
+    /// When the last key was typed.
+    pub since: Option<Instant>,
+}
+
+impl PendingKeys {
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Add a key and restart the timeout.
+    pub fn push(&mut self, key: KeyChord) {
+        self.keys.push(key);
+        self.since = Some(Instant::now());
+    }
+
+    /// Drop the keys, returning them.
+    pub fn take(&mut self) -> Vec<KeyChord> {
+        self.since = None;
+        std::mem::take(&mut self.keys)
+    }
+
+    /// Whether more than `timeout` passed since the last key.
+    pub fn expired(&self, now: Instant, timeout: Duration) -> bool {
+        self.since.is_some_and(|t| now.saturating_duration_since(t) >= timeout)
+    }
+
+    /// When the keys expire with `timeout`, if any are pending.
+    pub fn deadline(&self, timeout: Duration) -> Option<Instant> {
+        self.since.map(|t| t + timeout)
+    }
+}
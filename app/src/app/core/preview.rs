@@ -1,9 +1,9 @@
-use std::fs;
-use std::fs::File;
 use std::io::Read;
 use std::path::Path;
 
+use super::panel::Pending;
 use super::App;
+use crate::fs_op::vfs::{LocalFs, VfsProvider};
 use crate::app::types::Side;
 
 /// Maximum number of directory entries to include in a directory preview.
@@ -13,8 +13,8 @@ pub const MAX_DIR_PREVIEW_ENTRIES: usize = 50;
 /// Errors that may occur when attempting to build a preview for a path.
 #[derive(Debug, PartialEq, Eq)]
 pub enum PreviewError {
-    /// The file appears to be binary (contains NUL or invalid UTF-8 or
-    /// a high proportion of non-printable characters).
+    /// The file appears to be binary: NUL bytes outside UTF-16 text, or
+    /// mostly non-printable characters in the detected encoding.
     Binary,
     /// The file could not be opened or read.
     Unreadable,
@@ -46,11 +46,15 @@ pub fn is_binary(buffer: &[u8]) -> bool {
     }
 
     // If the bytes are not valid UTF-8 then treat as binary.
-    let text = match std::str::from_utf8(buffer) {
-        Ok(s) => s,
-        Err(_) => return true,
-    };
+    match std::str::from_utf8(buffer) {
+        Ok(text) => mostly_control(text),
+        Err(_) => true,
+    }
+}
 
+/// Whether `text` is mostly control characters (tabs, newlines and spaces
+/// aside), as binary data decoded as text is.
+pub(crate) fn mostly_control(text: &str) -> bool {
     // Count characters that are control characters (excluding common
     // whitespace). If a significant proportion of characters are
     // non-printable, we consider the buffer binary.
@@ -76,16 +80,15 @@ pub fn is_binary(buffer: &[u8]) -> bool {
 /// header line containing the directory path, followed by up to
 /// `MAX_DIR_PREVIEW_ENTRIES` file/directory names (sorted, one per line).
 pub fn build_directory_preview(path: &Path) -> String {
+    build_directory_preview_with(&LocalFs, path)
+}
+
+/// `build_directory_preview` reading the listing through `vfs`.
+pub fn build_directory_preview_with(vfs: &dyn VfsProvider, path: &Path) -> String {
     let mut out = format!("Directory: {}\n", path.display());
-    if !path.is_dir() {
-        return out;
-    }
 
-    let mut names: Vec<String> = match fs::read_dir(path) {
-        Ok(rd) => rd
-            .filter_map(Result::ok)
-            .map(|entry| entry.file_name().to_string_lossy().into_owned())
-            .collect(),
+    let mut names: Vec<String> = match vfs.list(path) {
+        Ok(entries) => entries.iter().map(|e| e.display_name().into_owned()).collect(),
         Err(_) => Vec::new(),
     };
 
@@ -100,10 +103,31 @@ pub fn build_directory_preview(path: &Path) -> String {
 }
 
 /// Read up to `max_bytes` from `path` and produce a string preview. Returns
-/// `Err(PreviewError::Binary)` if the sample looks like a binary file, and
+/// `Err(PreviewError::Binary)` if the sample looks like a binary file or
+/// starts like a binary format (see `fs_op::filetype`), and
 /// `Err(PreviewError::Unreadable)` if the file could not be read.
+///
+/// Text in another encoding than UTF-8 is converted (see `encoding`) and
+/// the preview starts with an `Encoding:` line naming it. JSON, CSV and
+/// Markdown are laid out by `preview_format` after a `Format:` line.
 pub fn build_file_preview(path: &Path, max_bytes: usize) -> Result<String, PreviewError> {
-    let file = File::open(path).map_err(|_| PreviewError::Unreadable)?;
+    build_file_preview_with(&LocalFs, path, max_bytes)
+}
+
+/// `build_file_preview` reading the file through `vfs`.
+pub fn build_file_preview_with(vfs: &dyn VfsProvider, path: &Path, max_bytes: usize) -> Result<String, PreviewError> {
+    build_file_preview_as(vfs, path, max_bytes, None)
+}
+
+/// `build_file_preview_with` decoding the file as `chosen` instead of the
+/// detected encoding, when given.
+pub fn build_file_preview_as(
+    vfs: &dyn VfsProvider,
+    path: &Path,
+    max_bytes: usize,
+    chosen: Option<&'static encoding_rs::Encoding>,
+) -> Result<String, PreviewError> {
+    let file = vfs.open(path).map_err(|_| PreviewError::Unreadable)?;
 
     let mut reader = file.take(max_bytes as u64);
     let mut buf = Vec::with_capacity(max_bytes.min(8 * 1024));
@@ -111,19 +135,21 @@ pub fn build_file_preview(path: &Path, max_bytes: usize) -> Result<String, Previ
         .read_to_end(&mut buf)
         .map_err(|_| PreviewError::Unreadable)?;
 
-    if is_binary(&buf) {
+    // Content that is known to be binary stays so even when it decodes.
+    if chosen.is_none() && !crate::fs_op::filetype::sniff(&buf).text {
         return Err(PreviewError::Binary);
     }
 
-    // Convert to UTF-8 string without panicking; strip BOM if present.
-    let mut preview = String::from_utf8_lossy(&buf).into_owned();
-    if preview.starts_with('\u{FEFF}') {
-        preview = preview.trim_start_matches('\u{FEFF}').to_string();
+    // Decoding strips a byte-order mark.
+    let mut decoded = super::encoding::decode(&buf, chosen).ok_or(PreviewError::Binary)?;
+    if let Some(formatted) = super::preview_format::format_preview(path, &decoded.text) {
+        decoded.text = formatted;
     }
+    let preview = with_encoding_line(decoded);
 
     // If the file is longer than the bytes we sampled, indicate truncation.
-    let truncated = match fs::metadata(path) {
-        Ok(md) => (md.len() as usize) > buf.len(),
+    let truncated = match vfs.stat(path) {
+        Ok(e) => (e.size as usize) > buf.len(),
         Err(_) => false,
     };
 
@@ -134,40 +160,87 @@ pub fn build_file_preview(path: &Path, max_bytes: usize) -> Result<String, Previ
     }
 }
 
+/// The decoded text, after an `Encoding:` line unless it is detected UTF-8.
+pub(super) fn with_encoding_line(decoded: super::encoding::Decoded) -> String {
+    if decoded.chosen || decoded.encoding != encoding_rs::UTF_8 {
+        let how = if decoded.chosen { "chosen" } else { "detected" };
+        format!("Encoding: {} ({})\n{}", decoded.encoding.name(), how, decoded.text)
+    } else {
+        decoded.text
+    }
+}
+
+/// Preview text for `path` read through `vfs`: a listing for directories,
+/// the first `MAX_PREVIEW_BYTES` for files (the last ones when `tail`, see
+/// `follow`), decoded as `encoding` if given.
+fn preview_text(vfs: &dyn VfsProvider, path: &Path, is_dir: bool, tail: bool, encoding: Option<&'static encoding_rs::Encoding>) -> String {
+    if is_dir {
+        return build_directory_preview_with(vfs, path);
+    }
+    let preview = if tail {
+        super::follow::build_tail_preview(vfs, path, super::MAX_PREVIEW_BYTES, encoding)
+    } else {
+        build_file_preview_as(vfs, path, super::MAX_PREVIEW_BYTES, encoding)
+    };
+    match preview {
+        Ok(s) => s,
+        Err(PreviewError::Binary) => match crate::fs_op::filetype::detect_with(vfs, path) {
+            Ok(t) => format!("Binary file: {} ({}, {}; preview not available)", path.display(), t.description, t.mime),
+            Err(_) => format!("Binary file: {} (preview not available)", path.display()),
+        },
+        Err(_) => format!("Cannot preview file: {} (unreadable)", path.display()),
+    }
+}
+
 impl App {
     pub fn update_preview_for(&mut self, side: Side) {
+        self.update_preview_within(side, super::panel::LISTING_WAIT);
+    }
+
+    /// Update the panel's `preview` text for the currently selected entry,
+    /// waiting up to `wait` for the filesystem. A preview that takes longer
+    /// shows a placeholder and is filled in by `poll_listings`.
+    ///
+    /// For directories this is a small list of contained entries. For files
+    /// this reads up to `App::MAX_PREVIEW_BYTES` bytes to avoid large
+    /// memory usage. Preview updates also reset `preview_offset` (through
+    /// `set_preview`) so the preview scroll position is consistent.
+    pub(crate) fn update_preview_within(&mut self, side: Side, wait: std::time::Duration) {
         let panel = self.panel_mut(side);
-        // Update the panel's `preview` text for the currently selected entry.
-        //
-        // For directories this is a small list of contained entries. For files
-        // this reads up to `App::MAX_PREVIEW_BYTES` bytes to avoid large
-        // memory usage. Preview updates must also reset `preview_offset` so
-        // the preview scroll position is consistent.
-        // Use the Panel API so preview/preview_offset semantics are centralized
-        // - `selected_entry` encapsulates bounds-safe access
-        // - `set_preview` resets `preview_offset` to zero
-        if let Some(e) = panel.selected_entry() {
-            if e.is_dir {
-                let s = build_directory_preview(&e.path);
-                panel.set_preview(s);
-            } else {
-                // Read up to the module-level `MAX_PREVIEW_BYTES` for previews.
-                match build_file_preview(&e.path, super::MAX_PREVIEW_BYTES) {
-                    Ok(s) => panel.set_preview(s),
-                    Err(PreviewError::Binary) => panel.set_preview(format!(
-                        "Binary file: {} (preview not available)",
-                        e.path.display()
-                    )),
-                    Err(_) => panel.set_preview(format!(
-                        "Cannot preview file: {} (unreadable)",
-                        e.path.display()
-                    )),
-                }
-            }
-        } else {
+        panel.preview_loading = None;
+        let vfs = panel.vfs.clone();
+        let encoding = panel.preview_encoding;
+        let Some(e) = panel.selected_entry() else {
             panel.set_preview(String::new());
+            return;
+        };
+        let (path, is_dir) = (e.path.clone(), e.is_dir);
+        // Moving to another entry stops following the old one.
+        if panel.follow.as_ref().is_some_and(|f| f.path != path) {
+            panel.follow = None;
+        }
+        let tail = panel.follow.is_some();
+        let work_path = path.clone();
+        match Pending::run(path.clone(), wait, move || preview_text(vfs.as_ref(), &work_path, is_dir, tail, encoding)) {
+            Ok(text) => panel.set_preview(text),
+            Err(pending) => {
+                panel.set_preview(format!("Loading preview of {}...", path.display()));
+                panel.preview_loading = Some(pending);
+            }
         }
     }
+
+    /// Re-read the preview of the selected entry after its file changed,
+    /// keeping the preview scroll position (clamped to the new text). A
+    /// followed preview pinned to its end stays at the end.
+    pub fn reload_preview_for(&mut self, side: Side) {
+        let offset = self.panel_mut(side).preview_offset;
+        self.update_preview_for(side);
+        let panel = self.panel_mut(side);
+        let last = panel.preview.lines().count().saturating_sub(1);
+        let pinned = panel.follow.as_ref().is_some_and(|f| f.pinned);
+        panel.preview_offset = if pinned { last } else { offset.min(last) };
+    }
 }
 
 // Unit tests for the preview helpers.
@@ -175,6 +248,7 @@ impl App {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::fs::File;
     use std::io::Write;
     use tempfile::tempdir;
 
@@ -0,0 +1,340 @@
+//! Format-aware previews of JSON, CSV and Markdown files.
+//!
+//! `build_file_preview` passes the decoded text through `format_preview`,
+//! which picks a format by extension (or, for JSON, by content) and
+//! rewrites the text: JSON is re-indented, CSV and TSV become an aligned
+//! table under their header row, and Markdown is kept as written. The
+//! result starts with a `Format:` line; `ui::widgets::structured_preview`
+//! reads it back to style the preview. JSON blocks fold in the focused
+//! preview (see `JsonFolds`).
+
+use std::collections::BTreeSet;
+use std::path::Path;
+
+use unicode_width::UnicodeWidthStr;
+
+use super::App;
+
+/// Widest a CSV column gets before its cells are cut with `…`.
+pub const MAX_CSV_COLUMN: usize = 40;
+/// Indentation of re-indented JSON.
+const JSON_INDENT: &str = "  ";
+
+/// How a preview is laid out and styled.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PreviewFormat {
+    Json,
+    Csv,
+    Markdown,
+}
+
+impl PreviewFormat {
+    fn name(self) -> &'static str {
+        match self {
+            PreviewFormat::Json => "JSON",
+            PreviewFormat::Csv => "CSV",
+            PreviewFormat::Markdown => "Markdown",
+        }
+    }
+
+    /// The format named by the `Format:` line among the first lines of a
+    /// preview (after an `Encoding:` line), with the line's index.
+    pub fn of(preview: &str) -> Option<(Self, usize)> {
+        preview.lines().take(2).enumerate().find_map(|(i, line)| {
+            let format = match line.strip_prefix("Format: ")? {
+                "JSON" => PreviewFormat::Json,
+                "CSV" => PreviewFormat::Csv,
+                "Markdown" => PreviewFormat::Markdown,
+                _ => return None,
+            };
+            Some((format, i))
+        })
+    }
+}
+
+/// `text` laid out for `path`'s format, after a `Format:` line, or `None`
+/// when it is plain text (or does not parse as its format).
+pub fn format_preview(path: &Path, text: &str) -> Option<String> {
+    let ext = path.extension().map(|e| e.to_string_lossy().to_ascii_lowercase()).unwrap_or_default();
+    let (format, body) = match ext.as_str() {
+        "json" | "geojson" | "ipynb" => (PreviewFormat::Json, reindent_json(text)?),
+        "csv" => (PreviewFormat::Csv, csv_table(text, ',')),
+        "tsv" => (PreviewFormat::Csv, csv_table(text, '\t')),
+        "md" | "markdown" | "mdown" => (PreviewFormat::Markdown, text.to_string()),
+        _ if text.trim_start().starts_with(['{', '[']) => (PreviewFormat::Json, reindent_json(text)?),
+        _ => return None,
+    };
+    Some(format!("Format: {}\n{}", format.name(), body))
+}
+
+/// Valid JSON `text` with one value per line, indented by depth. Object
+/// keys stay in their order and numbers as written.
+pub fn reindent_json(text: &str) -> Option<String> {
+    serde_json::from_str::<serde::de::IgnoredAny>(text).ok()?;
+    let mut out = String::with_capacity(text.len() * 2);
+    let mut depth = 0usize;
+    let mut chars = text.chars().peekable();
+    let newline = |out: &mut String, depth: usize| {
+        out.push('\n');
+        out.push_str(&JSON_INDENT.repeat(depth));
+    };
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => {
+                out.push('"');
+                while let Some(c) = chars.next() {
+                    out.push(c);
+                    match c {
+                        '\\' => out.extend(chars.next()),
+                        '"' => break,
+                        _ => {}
+                    }
+                }
+            }
+            '{' | '[' => {
+                while chars.next_if(|c| c.is_whitespace()).is_some() {}
+                let close = if c == '{' { '}' } else { ']' };
+                if chars.next_if_eq(&close).is_some() {
+                    out.push(c);
+                    out.push(close);
+                } else {
+                    out.push(c);
+                    depth += 1;
+                    newline(&mut out, depth);
+                }
+            }
+            '}' | ']' => {
+                depth = depth.saturating_sub(1);
+                newline(&mut out, depth);
+                out.push(c);
+            }
+            ',' => {
+                out.push(',');
+                newline(&mut out, depth);
+            }
+            ':' => out.push_str(": "),
+            c if c.is_whitespace() => {}
+            c => out.push(c),
+        }
+    }
+    out.push('\n');
+    Some(out)
+}
+
+/// Split CSV `text` into records of fields. Quoted fields may hold the
+/// separator, doubled quotes and line breaks (shown as spaces).
+pub fn parse_csv(text: &str, sep: char) -> Vec<Vec<String>> {
+    let mut records = Vec::new();
+    let (mut record, mut field) = (Vec::new(), String::new());
+    let mut quoted = false;
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.next_if_eq(&'"').is_some() => field.push('"'),
+            '"' if quoted => quoted = false,
+            '"' if field.is_empty() => quoted = true,
+            '\r' | '\n' if quoted => field.push(' '),
+            '\r' => {}
+            '\n' => {
+                record.push(std::mem::take(&mut field));
+                records.push(std::mem::take(&mut record));
+            }
+            c if c == sep && !quoted => record.push(std::mem::take(&mut field)),
+            c => field.push(c),
+        }
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+    records
+}
+
+/// CSV `text` as a table: columns padded to their widest cell (at most
+/// `MAX_CSV_COLUMN` cells) and split by `│`, with a rule under the header.
+pub fn csv_table(text: &str, sep: char) -> String {
+    let records = parse_csv(text, sep);
+    let columns = records.iter().map(Vec::len).max().unwrap_or(0);
+    let widths: Vec<usize> = (0..columns)
+        .map(|c| records.iter().filter_map(|r| r.get(c)).map(|f| f.width()).max().unwrap_or(0).min(MAX_CSV_COLUMN))
+        .collect();
+    let mut out = String::new();
+    for (i, record) in records.iter().enumerate() {
+        let cells: Vec<String> = widths.iter().enumerate().map(|(c, &w)| fit(record.get(c).map_or("", String::as_str), w)).collect();
+        out.push_str(cells.join(" │ ").trim_end());
+        out.push('\n');
+        if i == 0 && records.len() > 1 {
+            let rule: Vec<String> = widths.iter().map(|&w| "─".repeat(w)).collect();
+            out.push_str(&rule.join("─┼─"));
+            out.push('\n');
+        }
+    }
+    out
+}
+
+/// `s` padded with spaces, or cut with `…`, to `width` cells.
+fn fit(s: &str, width: usize) -> String {
+    if s.width() <= width {
+        return format!("{}{}", s, " ".repeat(width - s.width()));
+    }
+    let (mut out, mut used) = (String::new(), 0);
+    for c in s.chars() {
+        let w = unicode_width::UnicodeWidthChar::width(c).unwrap_or(0);
+        if used + w + 1 > width {
+            break;
+        }
+        out.push(c);
+        used += w;
+    }
+    out.push('…');
+    let pad = width.saturating_sub(out.width());
+    out + " ".repeat(pad).as_str()
+}
+
+/// Folded blocks of a JSON preview. `full` is the preview as built and
+/// `folded` the lines of it whose `{`/`[` block is shown on one line.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct JsonFolds {
+    pub full: String,
+    pub folded: BTreeSet<usize>,
+}
+
+impl JsonFolds {
+    pub fn new(full: String) -> Self {
+        JsonFolds { full, folded: BTreeSet::new() }
+    }
+
+    /// The preview with the folded blocks collapsed to `{ … }`, and for
+    /// each shown line its line in `full`.
+    pub fn render(&self) -> (String, Vec<usize>) {
+        let lines: Vec<&str> = self.full.lines().collect();
+        let (mut out, mut map) = (String::new(), Vec::new());
+        let mut i = 0;
+        while i < lines.len() {
+            map.push(i);
+            match self.folded.contains(&i).then(|| block_end(&lines, i)).flatten() {
+                Some(end) => {
+                    let close = lines[end].trim_start();
+                    let inner = end - i - 1;
+                    let noun = if inner == 1 { "line" } else { "lines" };
+                    out.push_str(&format!("{} … {}  ({} {})\n", lines[i], close, inner, noun));
+                    i = end + 1;
+                }
+                None => {
+                    out.push_str(lines[i]);
+                    out.push('\n');
+                    i += 1;
+                }
+            }
+        }
+        (out, map)
+    }
+
+    /// Fold or unfold the block that line `line` of `full` opens, or else
+    /// the innermost block holding it. Returns the line of `full` toggled.
+    pub fn toggle(&mut self, line: usize) -> Option<usize> {
+        let lines: Vec<&str> = self.full.lines().collect();
+        let opener = (0..=line.min(lines.len().checked_sub(1)?))
+            .rev()
+            .find(|&i| block_end(&lines, i).is_some_and(|end| end >= line))?;
+        if !self.folded.remove(&opener) {
+            self.folded.insert(opener);
+        }
+        Some(opener)
+    }
+
+    /// Fold every block but the outermost (`fold`), or none.
+    pub fn set_all(&mut self, fold: bool) {
+        let lines: Vec<&str> = self.full.lines().collect();
+        self.folded = match fold {
+            true => (0..lines.len()).filter(|&i| lines[i].starts_with(' ') && block_end(&lines, i).is_some()).collect(),
+            false => BTreeSet::new(),
+        };
+    }
+}
+
+impl App {
+    /// Fold or unfold the JSON block at the top of the active panel's
+    /// preview (Enter in the focused preview). Returns `false` when the
+    /// preview is not JSON.
+    pub fn toggle_preview_fold(&mut self) -> bool {
+        self.refold_preview(|folds, line| folds.toggle(line))
+    }
+
+    /// Fold every block of the active panel's JSON preview but the
+    /// outermost (`fold`), or unfold them all (`-` and `+`).
+    pub fn fold_preview_all(&mut self, fold: bool) -> bool {
+        self.refold_preview(|folds, line| {
+            folds.set_all(fold);
+            Some(line)
+        })
+    }
+
+    /// Apply `change` to the folds of a JSON preview, given the line of
+    /// `full` at the top of the view, and show the result scrolled to the
+    /// line `change` returns.
+    fn refold_preview(&mut self, change: impl FnOnce(&mut JsonFolds, usize) -> Option<usize>) -> bool {
+        let panel = self.active_panel_mut();
+        if !matches!(PreviewFormat::of(&panel.preview), Some((PreviewFormat::Json, _))) {
+            return false;
+        }
+        let preview = &panel.preview;
+        let folds = panel.preview_folds.get_or_insert_with(|| JsonFolds::new(preview.clone()));
+        let top = folds.render().1.get(panel.preview_offset).copied().unwrap_or(0);
+        let target = change(folds, top).unwrap_or(top);
+        let (text, map) = folds.render();
+        // The shown line at or above `target`, which may now be folded away.
+        panel.preview_offset = map.iter().rposition(|&l| l <= target).unwrap_or(0);
+        panel.preview = text;
+        panel.dirty = true;
+        true
+    }
+}
+
+/// The line closing the block line `start` opens, if it opens one.
+fn block_end(lines: &[&str], start: usize) -> Option<usize> {
+    let line = lines.get(start)?;
+    if !line.ends_with(['{', '[']) {
+        return None;
+    }
+    let indent = line.len() - line.trim_start().len();
+    lines
+        .iter()
+        .enumerate()
+        .skip(start + 1)
+        .find(|(_, l)| l.len() - l.trim_start().len() == indent && l.trim_start().starts_with(['}', ']']))
+        .map(|(i, _)| i)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reindents_json_keeping_order() {
+        let text = reindent_json(r#"{"b":1,"a":[true,{},"x,\"y\""],"c":{"d":null}}"#).unwrap();
+        let expected = "{\n  \"b\": 1,\n  \"a\": [\n    true,\n    {},\n    \"x,\\\"y\\\"\"\n  ],\n  \"c\": {\n    \"d\": null\n  }\n}\n";
+        assert_eq!(text, expected);
+        assert_eq!(reindent_json("{oops"), None);
+    }
+
+    #[test]
+    fn csv_quotes_and_alignment() {
+        let records = parse_csv("a,\"b,c\",\"say \"\"hi\"\"\"\r\n1,2\n", ',');
+        assert_eq!(records, [vec!["a", "b,c", "say \"hi\""], vec!["1", "2"]]);
+        assert_eq!(csv_table("name,n\nbob,10\n", ','), "name │ n\n─────┼───\nbob  │ 10\n");
+    }
+
+    #[test]
+    fn json_blocks_fold_and_unfold() {
+        let mut folds = JsonFolds::new("Format: JSON\n{\n  \"a\": [\n    1\n  ],\n  \"b\": 2\n}\n".to_string());
+        assert_eq!(folds.toggle(3), Some(2));
+        let (text, map) = folds.render();
+        assert_eq!(text, "Format: JSON\n{\n  \"a\": [ … ],  (1 line)\n  \"b\": 2\n}\n");
+        assert_eq!(map, [0, 1, 2, 5, 6]);
+        folds.set_all(true);
+        assert_eq!(folds.folded, BTreeSet::from([2]));
+        folds.set_all(false);
+        assert_eq!(folds.render().0, folds.full);
+    }
+}
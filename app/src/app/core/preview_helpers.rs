@@ -2,6 +2,7 @@
 // `preview` module so code that referenced
 // `crate::app::core::preview_helpers::...` continues to work.
 pub use crate::app::core::preview::{
-    build_directory_preview, build_file_preview, is_binary, PreviewError,
+    build_directory_preview, build_directory_preview_with, build_file_preview, build_file_preview_as, build_file_preview_with,
+    is_binary, PreviewError,
     MAX_DIR_PREVIEW_ENTRIES,
 };
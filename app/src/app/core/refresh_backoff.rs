@@ -0,0 +1,63 @@
+//! Back-off for watcher-driven refreshes that keep failing.
+//!
+//! A directory on a flapping network mount can fail to list every time
+//! the watcher fires. Instead of a dialog per failure the panel keeps one
+//! `RefreshFailure`, shown as a warning banner, and retries on its own
+//! with a doubling delay until a listing succeeds or the user retries.
+
+use std::time::{Duration, Instant};
+
+/// Delay before the first automatic retry.
+pub const FIRST_RETRY: Duration = Duration::from_secs(1);
+/// Longest delay between automatic retries.
+pub const MAX_RETRY: Duration = Duration::from_secs(60);
+
+/// The latest failed refresh of a panel.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RefreshFailure {
+    /// Error of the most recent attempt.
+    pub error: String,
+    /// Failed attempts in a row.
+    pub failures: u32,
+    /// When the next automatic retry is due.
+    pub retry_at: Instant,
+}
+
+impl RefreshFailure {
+    /// Delay after `failures` failed attempts in a row: `FIRST_RETRY`,
+    /// doubling each time, at most `MAX_RETRY`.
+    pub fn delay(failures: u32) -> Duration {
+        let factor = 1u32 << failures.saturating_sub(1).min(16);
+        FIRST_RETRY.saturating_mul(factor).min(MAX_RETRY)
+    }
+
+    /// Record another failure at `now` after `prev`.
+    pub fn record(prev: Option<&RefreshFailure>, error: String, now: Instant) -> Self {
+        let failures = prev.map_or(0, |p| p.failures) + 1;
+        RefreshFailure { error, failures, retry_at: now + Self::delay(failures) }
+    }
+
+    /// Whether the automatic retry is due at `now`.
+    pub fn due(&self, now: Instant) -> bool {
+        now >= self.retry_at
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn delay_doubles_up_to_the_cap() {
+        let delays: Vec<u64> = (1..=8).map(|n| RefreshFailure::delay(n).as_secs()).collect();
+        assert_eq!(delays, vec![1, 2, 4, 8, 16, 32, 60, 60]);
+        assert_eq!(RefreshFailure::delay(u32::MAX), MAX_RETRY);
+
+        let now = Instant::now();
+        let first = RefreshFailure::record(None, "gone".into(), now);
+        let second = RefreshFailure::record(Some(&first), "gone".into(), now);
+        assert_eq!(second.failures, 2);
+        assert!(!second.due(now + Duration::from_secs(1)));
+        assert!(second.due(now + Duration::from_secs(2)));
+    }
+}
@@ -0,0 +1,147 @@
+//! Recent and saved searches.
+//!
+//! `App::run_search` adds every search to the recent ones, `:save-search
+//! NAME` keeps the search of the active results panel under a name, and
+//! `:searches` lists both to run one again (`Mode::SearchPicker`). Each
+//! keeps its options (case, regex, ignore rules) along with the pattern.
+//! They are written to `searches.toml` in `project_config_dir()` after
+//! every change; only when `path` is set, which the event loop does at
+//! startup (tests keep them in memory).
+
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::app::settings::config_dirs::project_config_dir;
+use crate::fs_op::search::SearchOptions;
+
+/// File name of the searches inside the config directory.
+pub const SEARCHES_FILE: &str = "searches.toml";
+
+/// Number of recent searches kept; older ones are dropped.
+pub const MAX_RECENT_SEARCHES: usize = 30;
+
+/// A search kept under a name.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct SavedSearch {
+    pub name: String,
+    #[serde(flatten)]
+    pub options: SearchOptions,
+}
+
+/// Saved searches and the recent ones (oldest first).
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct Searches {
+    #[serde(default)]
+    pub saved: Vec<SavedSearch>,
+    #[serde(default)]
+    pub recent: Vec<SearchOptions>,
+    /// File the searches are saved to; `None` keeps them in memory only.
+    #[serde(skip)]
+    pub path: Option<PathBuf>,
+}
+
+/// Default location of the searches file.
+pub fn searches_path() -> PathBuf {
+    project_config_dir().join(SEARCHES_FILE)
+}
+
+impl Searches {
+    /// Load the searches from `path` and persist changes there. A missing
+    /// file yields none.
+    pub fn load_from(path: PathBuf) -> Result<Self> {
+        let mut searches = if path.exists() {
+            let raw = fs::read_to_string(&path)
+                .with_context(|| format!("failed to read searches {}", path.display()))?;
+            toml::from_str::<Searches>(&raw)
+                .with_context(|| format!("failed to parse searches TOML in {}", path.display()))?
+        } else {
+            Searches::default()
+        };
+        searches.path = Some(path);
+        Ok(searches)
+    }
+
+    /// Write the searches to `path` (no-op when unset).
+    pub fn save(&self) -> Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("failed to create config dir {}", parent.display()))?;
+        }
+        let s = toml::to_string_pretty(self).context("failed to serialize searches")?;
+        fs::write(path, s).with_context(|| format!("failed to write searches {}", path.display()))
+    }
+
+    /// Make `options` the most recent search, dropping an earlier copy and
+    /// the oldest beyond `MAX_RECENT_SEARCHES`.
+    pub fn remember(&mut self, options: SearchOptions) {
+        self.recent.retain(|o| *o != options);
+        self.recent.push(options);
+        let excess = self.recent.len().saturating_sub(MAX_RECENT_SEARCHES);
+        self.recent.drain(..excess);
+    }
+
+    /// Keep `options` as `name`, replacing a saved search of that name
+    /// (ignoring case).
+    pub fn save_as(&mut self, name: &str, options: SearchOptions) {
+        let search = SavedSearch { name: name.trim().to_string(), options };
+        match self.saved.iter_mut().find(|s| s.name.eq_ignore_ascii_case(&search.name)) {
+            Some(existing) => *existing = search,
+            None => self.saved.push(search),
+        }
+    }
+
+    /// The picker's list: saved searches by name, then the recent ones,
+    /// most recent first.
+    pub fn entries(&self) -> Vec<(Option<&str>, &SearchOptions)> {
+        let saved = self.saved.iter().map(|s| (Some(s.name.as_str()), &s.options));
+        saved.chain(self.recent.iter().rev().map(|o| (None, o))).collect()
+    }
+
+    /// Forget the search at `index` of `entries`.
+    pub fn remove_entry(&mut self, index: usize) {
+        let Some(back) = index.checked_sub(self.saved.len()) else {
+            self.saved.remove(index);
+            return;
+        };
+        if back < self.recent.len() {
+            self.recent.remove(self.recent.len() - 1 - back);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn searches_round_trip_through_file() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join(SEARCHES_FILE);
+        let mut searches = Searches::load_from(path.clone()).unwrap();
+        searches.remember(SearchOptions::parse("*.log", false));
+        searches.remember(SearchOptions::parse("-r -c ^Make", true));
+        searches.remember(SearchOptions::parse("*.log", false));
+        searches.save_as("Logs", SearchOptions::parse("-g *.log", false));
+        searches.save_as("logs", SearchOptions::parse("*.txt", false));
+        searches.save().unwrap();
+
+        let loaded = Searches::load_from(path).unwrap();
+        assert_eq!(loaded.saved, searches.saved);
+        assert_eq!(loaded.recent, searches.recent);
+        let queries: Vec<(Option<&str>, String)> = loaded.entries().into_iter().map(|(name, o)| (name, o.query())).collect();
+        assert_eq!(queries, [(Some("logs"), "*.txt".to_string()), (None, "*.log".to_string()), (None, "-r -c -g ^Make".to_string())]);
+
+        let mut loaded = loaded;
+        loaded.remove_entry(2);
+        loaded.remove_entry(0);
+        assert!(loaded.saved.is_empty());
+        assert_eq!(loaded.recent.len(), 1);
+        assert_eq!(loaded.recent[0].pattern, "*.log");
+    }
+}
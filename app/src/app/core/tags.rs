@@ -0,0 +1,220 @@
+//! Named, coloured file tags (`:tag`, `:untag`, `:select-tag`).
+//!
+//! Tags live in a sidecar database, `tags.toml` in `user_cache_dir()`, so
+//! files are never touched. Each tag has a colour (a name such as `red` or
+//! a `#rrggbb` value); tags created without one take the next colour of
+//! `PALETTE`. Listings draw a coloured mark per tag at the end of the row.
+//! Tagged paths follow renames made in the app or reported by the watcher,
+//! including renames of a directory above them. Like the job history the
+//! database is only persisted when `path` is set, which the event loop does
+//! at startup; it is saved after every change.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use anyhow::{bail, Context, Result};
+use ratatui::style::Color;
+use serde::{Deserialize, Serialize};
+
+use crate::app::settings::config_dirs::user_cache_dir;
+
+/// File name of the tag database inside the cache directory.
+pub const TAGS_FILE: &str = "tags.toml";
+
+/// Colours given, in turn, to tags created without one.
+pub const PALETTE: [&str; 6] = ["red", "yellow", "green", "cyan", "blue", "magenta"];
+
+/// Tag names and colours of one path, as drawn after its row.
+pub type Marks = Vec<(String, Color)>;
+
+/// A tag name and its colour.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct TagDef {
+    pub name: String,
+    pub color: String,
+}
+
+/// Tags of one path.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct TaggedFile {
+    pub path: PathBuf,
+    pub tags: Vec<String>,
+}
+
+/// Tag definitions and the tagged paths.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct Tags {
+    #[serde(default, rename = "tag")]
+    pub defs: Vec<TagDef>,
+    #[serde(default, rename = "file")]
+    pub files: Vec<TaggedFile>,
+    /// File the tags are saved to; `None` keeps them in memory only.
+    #[serde(skip)]
+    pub path: Option<PathBuf>,
+}
+
+/// Default location of the tag database.
+pub fn tags_path() -> PathBuf {
+    user_cache_dir().join(TAGS_FILE)
+}
+
+/// Parse a tag colour: a colour name, an index or `#rrggbb`.
+pub fn parse_color(s: &str) -> Option<Color> {
+    Color::from_str(s).ok()
+}
+
+impl Tags {
+    /// Load the tags from `path` and save future changes there. A missing
+    /// file yields no tags.
+    pub fn load_from(path: PathBuf) -> Result<Self> {
+        let mut tags = if path.exists() {
+            let raw = fs::read_to_string(&path)
+                .with_context(|| format!("failed to read tag database {}", path.display()))?;
+            toml::from_str::<Tags>(&raw).with_context(|| format!("failed to parse tag TOML in {}", path.display()))?
+        } else {
+            Tags::default()
+        };
+        tags.path = Some(path);
+        Ok(tags)
+    }
+
+    /// Write the tags to `path` (no-op when unset).
+    pub fn save(&self) -> Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("failed to create cache dir {}", parent.display()))?;
+        }
+        let s = toml::to_string_pretty(self).context("failed to serialize tag database")?;
+        fs::write(path, s).with_context(|| format!("failed to write tag database {}", path.display()))
+    }
+
+    /// Create tag `name`, or recolour it when `color` is given. Without a
+    /// colour a new tag takes the next one of the palette.
+    pub fn define(&mut self, name: &str, color: Option<&str>) -> Result<()> {
+        if name.is_empty() || name.chars().any(char::is_whitespace) {
+            bail!("invalid tag name '{}'", name);
+        }
+        if let Some(c) = color {
+            if parse_color(c).is_none() {
+                bail!("unknown colour '{}'", c);
+            }
+        }
+        match self.defs.iter_mut().find(|d| d.name == name) {
+            Some(def) => {
+                if let Some(c) = color {
+                    def.color = c.to_string();
+                }
+            }
+            None => {
+                let color = color.unwrap_or(PALETTE[self.defs.len() % PALETTE.len()]).to_string();
+                self.defs.push(TagDef { name: name.to_string(), color });
+            }
+        }
+        Ok(())
+    }
+
+    /// Colour of tag `name` (the default colour for unknown tags).
+    pub fn color_of(&self, name: &str) -> Color {
+        self.defs.iter().find(|d| d.name == name).and_then(|d| parse_color(&d.color)).unwrap_or_default()
+    }
+
+    /// Tags of `path`, in the order they were added.
+    pub fn tags_of(&self, path: &Path) -> &[String] {
+        self.files.iter().find(|f| f.path == path).map(|f| f.tags.as_slice()).unwrap_or_default()
+    }
+
+    /// Tags of `path` with their colours, for drawing marks.
+    pub fn marks(&self, path: &Path) -> Marks {
+        self.tags_of(path).iter().map(|t| (t.clone(), self.color_of(t))).collect()
+    }
+
+    /// Paths tagged `name`.
+    pub fn tagged_with<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Path> + 'a {
+        self.files.iter().filter(move |f| f.tags.iter().any(|t| t == name)).map(|f| f.path.as_path())
+    }
+
+    /// Add tag `name` to `path` (defining it when new).
+    pub fn add(&mut self, path: &Path, name: &str) -> Result<()> {
+        self.define(name, None)?;
+        match self.files.iter_mut().find(|f| f.path == path) {
+            Some(f) if !f.tags.iter().any(|t| t == name) => f.tags.push(name.to_string()),
+            Some(_) => {}
+            None => self.files.push(TaggedFile { path: path.to_path_buf(), tags: vec![name.to_string()] }),
+        }
+        Ok(())
+    }
+
+    /// Remove tag `name`, or every tag when `None`, from `path`.
+    pub fn remove(&mut self, path: &Path, name: Option<&str>) {
+        if let Some(f) = self.files.iter_mut().find(|f| f.path == path) {
+            f.tags.retain(|t| name.is_some_and(|n| n != t));
+        }
+        self.files.retain(|f| !f.tags.is_empty());
+    }
+
+    /// Tag all of `paths` with `name`, or untag them when they all have it
+    /// already. Returns whether the tag was added.
+    pub fn toggle(&mut self, paths: &[PathBuf], name: &str) -> Result<bool> {
+        let all_tagged = paths.iter().all(|p| self.tags_of(p).iter().any(|t| t == name));
+        for p in paths {
+            if all_tagged {
+                self.remove(p, Some(name));
+            } else {
+                self.add(p, name)?;
+            }
+        }
+        Ok(!all_tagged)
+    }
+
+    /// Move the tags of `from`, and of everything below it, to `to`.
+    /// Returns whether any tag moved.
+    pub fn rename(&mut self, from: &Path, to: &Path) -> bool {
+        let mut moved = false;
+        for f in &mut self.files {
+            if let Ok(rest) = f.path.strip_prefix(from) {
+                f.path = if rest.as_os_str().is_empty() { to.to_path_buf() } else { to.join(rest) };
+                moved = true;
+            }
+        }
+        moved
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn toggle_defines_tags_with_palette_colours() {
+        let mut tags = Tags::default();
+        let a = PathBuf::from("/d/a");
+        let b = PathBuf::from("/d/b");
+        assert!(tags.toggle(std::slice::from_ref(&a), "work").unwrap());
+        assert!(tags.toggle(&[a.clone(), b.clone()], "work").unwrap());
+        assert_eq!(tags.tags_of(&b), ["work"]);
+        assert!(!tags.toggle(&[a.clone(), b.clone()], "work").unwrap());
+        assert!(tags.files.is_empty());
+        tags.add(&a, "todo").unwrap();
+        assert_eq!(tags.color_of("work"), Color::Red);
+        assert_eq!(tags.color_of("todo"), Color::Yellow);
+        assert!(tags.define("bad name", None).is_err());
+        assert!(tags.define("todo", Some("nonsense")).is_err());
+        tags.define("todo", Some("#00ff00")).unwrap();
+        assert_eq!(tags.color_of("todo"), Color::Rgb(0, 255, 0));
+    }
+
+    #[test]
+    fn rename_moves_paths_below_a_directory() {
+        let mut tags = Tags::default();
+        tags.add(Path::new("/d/sub/x"), "a").unwrap();
+        tags.add(Path::new("/d/subway"), "a").unwrap();
+        assert!(tags.rename(Path::new("/d/sub"), Path::new("/e/new")));
+        assert_eq!(tags.tags_of(Path::new("/e/new/x")), ["a"]);
+        assert_eq!(tags.tags_of(Path::new("/d/subway")), ["a"]);
+        assert!(!tags.rename(Path::new("/nowhere"), Path::new("/x")));
+    }
+}
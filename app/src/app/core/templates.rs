@@ -0,0 +1,94 @@
+//! Templates offered by the new-file dialog (`InputKind::NewFile`).
+//!
+//! Every file or directory in `<config dir>/templates` is a template. While
+//! the dialog is open Up/Down choose one and Enter creates the new entry
+//! from it with `fs_op::create::create_from_template`; with none chosen an
+//! empty file is created as before.
+
+use std::path::{Path, PathBuf};
+
+use super::App;
+use crate::app::settings::config_dirs::project_config_dir;
+use crate::app::{InputKind, Mode};
+
+/// Directory scanned for templates (`<config dir>/templates`).
+pub fn templates_dir() -> PathBuf {
+    project_config_dir().join("templates")
+}
+
+/// The templates of the open new-file dialog.
+#[derive(Clone, Debug, Default)]
+pub struct TemplatePicker {
+    /// Template paths, sorted by name.
+    pub templates: Vec<PathBuf>,
+    /// Template chosen with Up/Down, if any.
+    pub selected: Option<usize>,
+}
+
+impl TemplatePicker {
+    /// Offer the templates in `dir`; hidden files are skipped.
+    pub fn open(&mut self, dir: &Path) {
+        let mut templates: Vec<PathBuf> = std::fs::read_dir(dir)
+            .map(|rd| rd.flatten().filter(|e| !e.file_name().to_string_lossy().starts_with('.')).map(|e| e.path()).collect())
+            .unwrap_or_default();
+        templates.sort();
+        self.templates = templates;
+        self.selected = None;
+    }
+
+    /// Move the choice `delta` templates down (negative: up). Going past
+    /// either end leaves no template chosen.
+    pub fn move_selection(&mut self, delta: isize) {
+        let n = self.templates.len() as isize;
+        let from = self.selected.map_or(if delta < 0 { n } else { -1 }, |i| i as isize);
+        let to = from + delta;
+        self.selected = (0..n).contains(&to).then_some(to as usize);
+    }
+
+    /// The chosen template.
+    pub fn chosen(&self) -> Option<&Path> {
+        self.templates.get(self.selected?).map(PathBuf::as_path)
+    }
+
+    /// Names to list in the dialog; directories end with a separator.
+    pub fn names(&self) -> Vec<String> {
+        self.templates
+            .iter()
+            .map(|p| {
+                let name = p.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+                if p.is_dir() { format!("{}{}", name, std::path::MAIN_SEPARATOR) } else { name }
+            })
+            .collect()
+    }
+
+    /// Forget the templates when the dialog closes.
+    pub fn close(&mut self) {
+        self.templates.clear();
+        self.selected = None;
+    }
+}
+
+impl App {
+    /// Open the new-file dialog with the templates of `templates_dir`.
+    pub fn open_new_file(&mut self) {
+        self.templates.open(&templates_dir());
+        self.mode = Mode::Input { prompt: "New file name:".to_string(), buffer: String::new(), kind: InputKind::NewFile };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn choice_runs_off_both_ends() {
+        let mut picker = TemplatePicker { templates: vec![PathBuf::from("a"), PathBuf::from("b")], selected: None };
+        picker.move_selection(1);
+        assert_eq!(picker.chosen(), Some(Path::new("a")));
+        picker.move_selection(1);
+        picker.move_selection(1);
+        assert_eq!(picker.chosen(), None);
+        picker.move_selection(-1);
+        assert_eq!(picker.chosen(), Some(Path::new("b")));
+    }
+}
@@ -0,0 +1,228 @@
+//! Directory tree sidebar.
+//!
+//! The tree (`e`, see `ui::widgets::tree`) shows the directories from the
+//! root of the active panel's directory down, as a flat list of rows that
+//! grows and shrinks as nodes are expanded and collapsed. A directory's
+//! children are only read when it is expanded, so large trees cost nothing
+//! until they are opened. Enter on a node sends the active panel there.
+
+use std::path::{Path, PathBuf};
+
+/// One row of the tree.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TreeNode {
+    pub path: PathBuf,
+    /// Nesting below the root, which is at depth 0.
+    pub depth: usize,
+    pub expanded: bool,
+}
+
+impl TreeNode {
+    fn new(path: PathBuf, depth: usize) -> Self {
+        TreeNode { path, depth, expanded: false }
+    }
+
+    /// Last component of the path, or the whole path for the root.
+    pub fn name(&self) -> String {
+        match self.path.file_name() {
+            Some(name) => name.to_string_lossy().into_owned(),
+            None => self.path.display().to_string(),
+        }
+    }
+}
+
+/// State of the tree sidebar.
+#[derive(Debug, Default)]
+pub struct DirTree {
+    pub visible: bool,
+    /// Whether the navigation keys go to the tree instead of the panel.
+    pub focused: bool,
+    /// Expanded rows in display order; children follow their parent.
+    pub nodes: Vec<TreeNode>,
+    pub selected: usize,
+    /// First row shown.
+    pub offset: usize,
+}
+
+impl DirTree {
+    /// Root the tree at the root of `path` and expand it down to `path`,
+    /// which is selected. Expanded nodes stay expanded while the root is
+    /// the same.
+    pub fn reveal(&mut self, path: &Path, show_hidden: bool) {
+        let Some(root) = path.ancestors().last() else {
+            return;
+        };
+        if self.nodes.first().map(|n| n.path.as_path()) != Some(root) {
+            self.nodes = vec![TreeNode::new(root.to_path_buf(), 0)];
+            self.selected = 0;
+            self.offset = 0;
+        }
+        let mut ancestors: Vec<&Path> = path.ancestors().collect();
+        ancestors.reverse();
+        let mut index = 0;
+        for dir in ancestors.iter().skip(1) {
+            if !self.nodes[index].expanded {
+                self.expand(index, show_hidden);
+            }
+            let found = self.children(index).find(|&i| self.nodes[i].path == *dir);
+            index = match found {
+                Some(child) => child,
+                // A hidden directory on the way is shown anyway.
+                None => {
+                    let key = sort_key(dir);
+                    let at = self.children(index).find(|&i| sort_key(&self.nodes[i].path) > key).unwrap_or_else(|| self.subtree_end(index));
+                    let depth = self.nodes[index].depth + 1;
+                    self.nodes.insert(at, TreeNode::new(dir.to_path_buf(), depth));
+                    at
+                }
+            };
+        }
+        self.selected = index;
+    }
+
+    /// Indices of the direct children of row `index`.
+    fn children(&self, index: usize) -> impl Iterator<Item = usize> + '_ {
+        let depth = self.nodes[index].depth;
+        (index + 1..self.subtree_end(index)).filter(move |&i| self.nodes[i].depth == depth + 1)
+    }
+
+    /// Index just past the rows below row `index`.
+    fn subtree_end(&self, index: usize) -> usize {
+        let depth = self.nodes[index].depth;
+        self.nodes[index + 1..].iter().position(|n| n.depth <= depth).map_or(self.nodes.len(), |p| index + 1 + p)
+    }
+
+    /// Read the subdirectories of row `index` and show them below it.
+    pub fn expand(&mut self, index: usize, show_hidden: bool) {
+        let Some(node) = self.nodes.get_mut(index) else {
+            return;
+        };
+        if node.expanded {
+            return;
+        }
+        node.expanded = true;
+        let depth = node.depth + 1;
+        let children = subdirectories(&node.path, show_hidden).into_iter().map(|p| TreeNode::new(p, depth));
+        self.nodes.splice(index + 1..index + 1, children);
+    }
+
+    /// Hide the rows below row `index`.
+    pub fn collapse(&mut self, index: usize) {
+        if self.nodes.get(index).is_none_or(|n| !n.expanded) {
+            return;
+        }
+        let end = self.subtree_end(index);
+        self.nodes.drain(index + 1..end);
+        self.nodes[index].expanded = false;
+        if self.selected > index {
+            self.selected = if self.selected < end { index } else { self.selected - (end - index - 1) };
+        }
+    }
+
+    /// Right: expand the selected node, or step into its first child when
+    /// it already is.
+    pub fn expand_selected(&mut self, show_hidden: bool) {
+        if self.nodes.get(self.selected).is_some_and(|n| n.expanded) {
+            let first = self.children(self.selected).next();
+            if let Some(child) = first {
+                self.selected = child;
+            }
+        } else {
+            self.expand(self.selected, show_hidden);
+        }
+    }
+
+    /// Left: collapse the selected node, or step to its parent when it is
+    /// collapsed already.
+    pub fn collapse_selected(&mut self) {
+        let Some(node) = self.nodes.get(self.selected) else {
+            return;
+        };
+        if node.expanded {
+            self.collapse(self.selected);
+        } else if let Some(parent) = self.nodes[..self.selected].iter().rposition(|n| n.depth + 1 == node.depth) {
+            self.selected = parent;
+        }
+    }
+
+    /// Move the selection by `delta` rows.
+    pub fn move_selection(&mut self, delta: isize) {
+        let last = self.nodes.len().saturating_sub(1);
+        self.selected = self.selected.saturating_add_signed(delta).min(last);
+    }
+
+    /// Path of the selected node.
+    pub fn selected_path(&self) -> Option<&Path> {
+        self.nodes.get(self.selected).map(|n| n.path.as_path())
+    }
+
+    /// First row shown in `height` rows: the scroll position, moved as
+    /// little as needed to show the selection.
+    pub fn view_offset(&self, height: usize) -> usize {
+        self.offset.min(self.selected).max((self.selected + 1).saturating_sub(height))
+    }
+
+    /// Scroll so the selection shows in `height` rows.
+    pub fn scroll_to_selection(&mut self, height: usize) {
+        let height = height.max(1);
+        if self.selected < self.offset {
+            self.offset = self.selected;
+        } else if self.selected >= self.offset + height {
+            self.offset = self.selected + 1 - height;
+        }
+    }
+}
+
+/// Subdirectories of `dir`, sorted by name; unreadable directories have
+/// none.
+fn subdirectories(dir: &Path, show_hidden: bool) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut dirs: Vec<PathBuf> = entries
+        .flatten()
+        .filter(|e| show_hidden || !e.file_name().to_string_lossy().starts_with('.'))
+        .filter(|e| e.path().is_dir())
+        .map(|e| e.path())
+        .collect();
+    dirs.sort_by_key(|p| sort_key(p));
+    dirs
+}
+
+fn sort_key(path: &Path) -> Option<String> {
+    path.file_name().map(|n| n.to_string_lossy().to_lowercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expands_lazily_and_collapses() {
+        let tmp = tempfile::tempdir().unwrap();
+        for dir in ["a/x", "a/y", "b", ".hidden"] {
+            std::fs::create_dir_all(tmp.path().join(dir)).unwrap();
+        }
+        std::fs::write(tmp.path().join("file"), "").unwrap();
+
+        let mut tree = DirTree::default();
+        tree.reveal(&tmp.path().join("a"), false);
+        let a = tree.selected;
+        assert_eq!(tree.selected_path(), Some(tmp.path().join("a").as_path()));
+        // `a` itself is not read until it is expanded.
+        assert!(!tree.nodes[a].expanded);
+        assert_eq!(tree.nodes[a + 1].path, tmp.path().join("b"));
+
+        tree.expand_selected(false);
+        let names: Vec<_> = tree.nodes[a..].iter().take(4).map(|n| (n.name(), n.depth)).collect();
+        let depth = tree.nodes[a].depth;
+        assert_eq!(names, [("a".into(), depth), ("x".into(), depth + 1), ("y".into(), depth + 1), ("b".into(), depth)]);
+
+        tree.expand_selected(false);
+        assert_eq!(tree.selected, a + 1);
+        tree.collapse_selected();
+        assert_eq!(tree.selected, a);
+        tree.collapse_selected();
+        assert_eq!(tree.nodes[a + 1].path, tmp.path().join("b"));
+    }
+}
@@ -0,0 +1,92 @@
+//! State of the disk usage analyzer (`U`, `Mode::DiskUsage`).
+//!
+//! `DiskUsage::start` sizes the entries of a directory on a worker thread
+//! (see `fs_op::usage`); `App::poll_usage` takes in the sizes found so far
+//! on every tick and keeps them sorted largest first. Leaving the
+//! directory or closing the analyzer drops the state, which stops the
+//! walk.
+
+use std::io;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{Receiver, TryRecvError};
+use std::sync::Arc;
+
+use super::App;
+use crate::fs_op::usage::{scan, UsageEntry};
+
+/// Sizes of the entries of `dir`, largest first.
+#[derive(Debug)]
+pub struct DiskUsage {
+    pub dir: PathBuf,
+    pub entries: Vec<UsageEntry>,
+    /// Whether every entry has been sized.
+    pub done: bool,
+    /// Why the directory could not be read, or the last delete failed.
+    pub error: Option<String>,
+    rx: Option<Receiver<io::Result<UsageEntry>>>,
+    cancel: Arc<AtomicBool>,
+}
+
+impl DiskUsage {
+    /// Start sizing the entries of `dir` in the background.
+    pub fn start(dir: PathBuf) -> Self {
+        let (tx, rx) = std::sync::mpsc::channel();
+        let cancel = Arc::new(AtomicBool::new(false));
+        let (work_dir, work_cancel) = (dir.clone(), cancel.clone());
+        std::thread::spawn(move || {
+            if let Err(e) = scan(&work_dir, &work_cancel, |entry| tx.send(Ok(entry)).is_ok()) {
+                let _ = tx.send(Err(e));
+            }
+        });
+        DiskUsage { dir, entries: Vec::new(), done: false, error: None, rx: Some(crate::runner::wake::forward(rx)), cancel }
+    }
+
+    /// Take in the sizes found since the last call. Returns whether any
+    /// arrived or the scan ended.
+    pub fn poll(&mut self) -> bool {
+        let Some(rx) = &self.rx else {
+            return false;
+        };
+        let mut changed = false;
+        loop {
+            match rx.try_recv() {
+                Ok(Ok(entry)) => self.entries.push(entry),
+                Ok(Err(e)) => self.error = Some(format!("Cannot read {}: {}", self.dir.display(), e)),
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    self.done = true;
+                    self.rx = None;
+                    changed = true;
+                    break;
+                }
+            }
+            changed = true;
+        }
+        if changed {
+            self.entries.sort_by(|a, b| b.bytes.cmp(&a.bytes).then_with(|| a.name.cmp(&b.name)));
+        }
+        changed
+    }
+
+    /// Bytes of all entries sized so far.
+    pub fn total(&self) -> u64 {
+        self.entries.iter().map(|e| e.bytes).sum()
+    }
+}
+
+impl Drop for DiskUsage {
+    fn drop(&mut self) {
+        self.cancel.store(true, Ordering::Relaxed);
+    }
+}
+
+impl App {
+    /// Take in the progress of the disk usage analyzer. Called every tick
+    /// from `poll_listings`.
+    pub fn poll_usage(&mut self) {
+        if self.usage.as_mut().is_some_and(DiskUsage::poll) {
+            self.dirty = true;
+        }
+    }
+}
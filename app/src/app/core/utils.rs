@@ -1,11 +1,12 @@
 use super::panel::Panel;
 
-/// Number of always-present UI header rows.
-const HEADER_ROWS: usize = 1;
+/// Number of always-present UI rows before the parent row: the breadcrumb
+/// row, drawn in the panel's top border (see `ui::widgets::breadcrumbs`).
+pub(super) const HEADER_ROWS: usize = 1;
 
 /// Return the total number of UI rows that will be rendered for a panel.
 ///
-/// The UI contains a synthetic header row (path/title) and may include a
+/// The UI contains the breadcrumb row (the path) and may include a
 /// synthetic parent row when the panel's `cwd` has a parent directory.
 /// The remainder of the rows correspond to the domain `entries` stored in
 /// the panel. This helper is intentionally tiny and pure to make unit
@@ -31,6 +32,11 @@ pub(super) fn ui_to_entry_index(selected_row: usize, panel: &Panel) -> Option<us
 }
 
 
+/// Inverse of `ui_to_entry_index`: the UI row showing `entries[idx]`.
+pub(super) fn entry_to_ui_index(idx: usize, panel: &Panel) -> usize {
+    HEADER_ROWS + (panel.cwd.parent().is_some() as usize) + idx
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
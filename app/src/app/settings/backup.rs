@@ -0,0 +1,102 @@
+//! Timestamped backups of config files.
+//!
+//! Before a config file is overwritten, `backup_file` copies the current
+//! version to `backups/<name>.<timestamp>` next to it and keeps the newest
+//! `MAX_BACKUPS`. `restore_latest` moves the newest backup back over the
+//! file, so repeated restores step further back in time. Settings use this
+//! through `save_settings` and the Settings dialog's "Restore" button.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+
+/// Backups kept per config file; older ones are deleted.
+pub const MAX_BACKUPS: usize = 5;
+
+/// Name of the backup directory inside the config file's directory.
+pub const BACKUP_DIR: &str = "backups";
+
+fn backup_dir(path: &Path) -> PathBuf {
+    path.parent().unwrap_or_else(|| Path::new(".")).join(BACKUP_DIR)
+}
+
+fn file_name(path: &Path) -> Result<String> {
+    path.file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .with_context(|| format!("config path {} has no file name", path.display()))
+}
+
+/// Backups of `path`, newest first.
+pub fn list_backups(path: &Path) -> Result<Vec<PathBuf>> {
+    let dir = backup_dir(path);
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+    let prefix = format!("{}.", file_name(path)?);
+    let mut backups: Vec<PathBuf> = fs::read_dir(&dir)
+        .with_context(|| format!("failed to read backup dir {}", dir.display()))?
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|p| p.file_name().is_some_and(|n| n.to_string_lossy().starts_with(&prefix)))
+        .collect();
+    // Timestamps sort lexically, so the newest name is the largest.
+    backups.sort_unstable_by(|a, b| b.cmp(a));
+    Ok(backups)
+}
+
+/// Copy the current `path` into the backup directory and prune old
+/// backups down to `keep`. Returns the new backup, or `None` when `path`
+/// does not exist yet.
+pub fn backup_file(path: &Path, keep: usize) -> Result<Option<PathBuf>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let dir = backup_dir(path);
+    fs::create_dir_all(&dir).with_context(|| format!("failed to create backup dir {}", dir.display()))?;
+    let stamp = crate::app::core::clock::now_local().format("%Y%m%d-%H%M%S%.3f");
+    let target = dir.join(format!("{}.{}", file_name(path)?, stamp));
+    fs::copy(path, &target).with_context(|| format!("failed to back up {} to {}", path.display(), target.display()))?;
+    for old in list_backups(path)?.into_iter().skip(keep) {
+        fs::remove_file(&old).with_context(|| format!("failed to remove old backup {}", old.display()))?;
+    }
+    Ok(Some(target))
+}
+
+/// Replace `path` with its newest backup and delete that backup. Returns
+/// the backup that was restored, or `None` when there is none.
+pub fn restore_latest(path: &Path) -> Result<Option<PathBuf>> {
+    let Some(latest) = list_backups(path)?.into_iter().next() else {
+        return Ok(None);
+    };
+    fs::copy(&latest, path).with_context(|| format!("failed to restore {} from {}", path.display(), latest.display()))?;
+    fs::remove_file(&latest).with_context(|| format!("failed to remove restored backup {}", latest.display()))?;
+    Ok(Some(latest))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backups_are_pruned_and_restored_newest_first() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("settings.toml");
+        let clock = crate::test_helpers::FakeClock::at_unix(1_700_000_000);
+        let _guard = clock.install();
+        assert_eq!(backup_file(&path, 2).unwrap(), None, "nothing to back up yet");
+
+        for v in 1..=3 {
+            fs::write(&path, format!("v{}", v)).unwrap();
+            backup_file(&path, 2).unwrap().unwrap();
+            clock.advance(std::time::Duration::from_secs(1));
+        }
+        assert_eq!(list_backups(&path).unwrap().len(), 2);
+
+        fs::write(&path, "broken").unwrap();
+        restore_latest(&path).unwrap().unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "v3");
+        restore_latest(&path).unwrap().unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "v2");
+        assert_eq!(restore_latest(&path).unwrap(), None);
+    }
+}
@@ -6,8 +6,8 @@
 // wrappers today so they can later be wired to user-configurable
 // settings (XML/JSON) without touching the handlers.
 
-use crate::input::KeyCode;
-use crate::app::settings::runtime_keybinds;
+use crate::input::{Key, KeyCode};
+use crate::app::settings::{keymap, runtime_keybinds};
 
 fn is_bound(action: &str, code: &KeyCode) -> bool {
 	runtime_keybinds::get().is_bound(action, code)
@@ -105,6 +105,84 @@ pub fn is_esc(code: &KeyCode) -> bool {
 	is_bound("esc", code)
 }
 
+/// Unbound by default (`g h` is its key sequence).
+pub fn is_go_home(code: &KeyCode) -> bool {
+	is_bound("go_home", code)
+}
+
+/// Map `code` through the active preset to the default key of its action
+/// (see `Keybinds::canonical_key`).
+pub fn canonical_key(code: &KeyCode) -> KeyCode {
+	runtime_keybinds::get().canonical_key(code)
+}
+
+/// Map a key event with Ctrl/Alt modifiers to the default key of the
+/// action it is bound to; unbound chords yield their bare key.
+pub fn chord_key(key: &Key) -> KeyCode {
+	runtime_keybinds::get().chord_key(key).unwrap_or(key.code)
+}
+
+/// Ctrl+O by default: drop to a shell (handled by the event loop).
+pub fn is_shell(key: &Key) -> bool {
+	runtime_keybinds::get().is_chord_bound("shell", key)
+}
+
+/// Ctrl+Q by default: toggle quick view (handled by the event loop).
+pub fn is_quick_view(key: &Key) -> bool {
+	runtime_keybinds::get().is_chord_bound("quick_view", key)
+}
+
+/// Ctrl+C by default: yank the selection (handled by the event loop).
+pub fn is_yank(key: &Key) -> bool {
+	runtime_keybinds::get().is_chord_bound("yank", key)
+}
+
+/// Ctrl+X by default: cut the selection (handled by the event loop).
+pub fn is_cut(key: &Key) -> bool {
+	runtime_keybinds::get().is_chord_bound("cut", key)
+}
+
+/// Ctrl+V by default: paste into the active panel (handled by the event
+/// loop).
+pub fn is_paste(key: &Key) -> bool {
+	runtime_keybinds::get().is_chord_bound("paste", key)
+}
+
+/// Ctrl+U by default: swap the panels (handled by the event loop).
+pub fn is_swap_panels(key: &Key) -> bool {
+	runtime_keybinds::get().is_chord_bound("swap_panels", key)
+}
+
+/// Alt+I by default: show the active panel's directory in the other one
+/// (handled by the event loop).
+pub fn is_other_panel_here(key: &Key) -> bool {
+	runtime_keybinds::get().is_chord_bound("other_panel_here", key)
+}
+
+/// Alt+Shift+I by default: show the other panel's directory in the
+/// active one (handled by the event loop).
+pub fn is_this_panel_there(key: &Key) -> bool {
+	runtime_keybinds::get().is_chord_bound("this_panel_there", key)
+}
+
+/// Help screen text generated from the active bindings.
+pub fn help_text() -> String {
+	keymap::help_text(&runtime_keybinds::get())
+}
+
+/// Why `keymap.toml` was rejected on the last load, if it was.
+pub fn keymap_error() -> Option<String> {
+	runtime_keybinds::get().keymap_error().map(str::to_string)
+}
+
+/// Apply the preset named `name`; unknown names fall back to the default
+/// preset. Returns the preset that was applied.
+pub fn apply_preset(name: &str) -> runtime_keybinds::KeybindPreset {
+	let preset = runtime_keybinds::KeybindPreset::from_name(name).unwrap_or_default();
+	runtime_keybinds::set_preset(preset);
+	preset
+}
+
 pub fn is_char(code: &KeyCode, want: char) -> bool {
 	matches!(code, &KeyCode::Char(c) if c == want)
 }
@@ -0,0 +1,507 @@
+//! User keymap: `keymap.toml` in the project config dir.
+//!
+//! The file binds actions to one or more key chords. A chord is a key with
+//! optional `Ctrl+`/`Alt+`/`Shift+`/`Super+` modifiers:
+//!
+//! ```toml
+//! [bindings]
+//! quit = ["q", "F10"]
+//! copy = ["c", "Ctrl+c"]
+//! shell = "Alt+s"
+//! diagnostics = "Ctrl+Shift+d"
+//!
+//! [sequences]
+//! home = "g g"
+//! refresh = "Space r"
+//! ```
+//!
+//! `Ctrl+Shift+` and `Super+` chords only arrive from terminals speaking the
+//! kitty keyboard protocol, which the terminal backend negotiates at startup.
+//!
+//! `[sequences]` binds actions to keys typed one after the other. The first
+//! key of a sequence waits for the next one (a hint popup lists the
+//! continuations); if nothing follows within `key_sequence_timeout_ms` the
+//! first key does what it is bound to on its own, if anything.
+//!
+//! Each listed action replaces that action's keys (or sequences) from the
+//! preset and `keybinds.xml`. The file is validated as a whole: unknown
+//! actions, unparsable keys and a chord or sequence bound to two unrelated
+//! actions reject it, and only the preset and `keybinds.xml` apply.
+//! `:reload-keys` re-reads it.
+//! The Keys page of the Settings dialog writes it with `save_keymap`.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::PathBuf;
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::app::settings::config_dirs::project_config_dir;
+use crate::app::settings::runtime_keybinds::Keybinds;
+use crate::input::{Key, KeyCode};
+
+/// File name of the keymap inside the project config dir.
+pub const KEYMAP_FILE: &str = "keymap.toml";
+
+/// Every bindable action with the description shown on the help screen,
+/// in help order.
+pub const ACTIONS: &[(&str, &str)] = &[
+    ("quit", "quit"),
+    ("help", "show this help"),
+    ("menu", "toggle menu focus"),
+    ("up", "move up"),
+    ("down", "move down"),
+    ("page_up", "page up"),
+    ("page_down", "page down"),
+    ("home", "first entry"),
+    ("end", "last entry"),
+    ("go_home", "home directory"),
+    ("open", "open / activate"),
+    ("go_up", "parent directory"),
+    ("breadcrumbs", "pick an ancestor in the path bar (Left/Right, Enter)"),
+    ("tab", "switch panels"),
+    ("toggle_selection", "select entry"),
+    ("refresh", "refresh panels"),
+    ("copy", "copy"),
+    ("mv", "move"),
+    ("f5", "copy to other panel"),
+    ("f6", "move to other panel"),
+    ("delete", "delete"),
+    ("rename", "rename"),
+    ("new_file", "new file"),
+    ("new_dir", "new directory"),
+    ("sort", "cycle sort key"),
+    ("toggle_sort_direction", "toggle sort direction"),
+    ("view", "context actions"),
+    ("edit", "edit file"),
+    ("user_menu", "user menu"),
+    ("toggle_preview", "toggle preview"),
+    ("focus_preview", "focus preview (PgUp/PgDn scroll it)"),
+    ("info_panel", "info panel in the other panel"),
+    ("tree", "directory tree (Right/Left expand, Enter go, Tab back)"),
+    ("flat_view", "list all files below this directory"),
+    ("find", "search below this directory; results list as a panel"),
+    ("select_by_criteria", "mark files by age and size (older 30d, >10M)"),
+    ("disk_usage", "disk usage analyzer"),
+    ("link_panels", "link panels: the other one follows into subdirectories"),
+    ("preview_scroll_down", "scroll preview down"),
+    ("preview_scroll_up", "scroll preview up"),
+    ("toggle_theme", "toggle theme"),
+    ("theme_picker", "choose theme"),
+    ("mounts", "mounted filesystems and drives"),
+    ("cycle_layout", "cycle panel layout"),
+    ("job_history", "job history"),
+    ("jobs", "background jobs"),
+    ("diagnostics", "diagnostics overlay"),
+    ("command_line", "command line (%f = selection, %d = cwd)"),
+    ("shell", "suspend to shell"),
+    ("quick_view", "quick view in the other panel"),
+    ("yank", "yank selection to the clipboard"),
+    ("cut", "cut selection to the clipboard"),
+    ("paste", "paste clipboard into this panel"),
+    ("swap_panels", "swap the panels"),
+    ("other_panel_here", "show this directory in the other panel"),
+    ("this_panel_there", "show the other panel's directory here"),
+    ("enter", "confirm in dialogs"),
+    ("esc", "cancel / close"),
+    ("backspace", "delete character in prompts"),
+    ("left", "previous button / menu, or ancestor breadcrumb"),
+    ("right", "next button / menu, or inner breadcrumb"),
+];
+
+/// Whether `action` is a known action name.
+pub fn is_action(action: &str) -> bool {
+    ACTIONS.iter().any(|(a, _)| *a == action)
+}
+
+/// A key with optional Ctrl/Alt/Shift/Super modifiers. Shift is part of a
+/// character typed on its own (`N`, not `Shift+n`); it is only written out
+/// with other modifiers (`Ctrl+Shift+n`) or keys without a character
+/// (`Shift+Tab`). Terminals without the kitty keyboard protocol report
+/// neither Shift with Ctrl nor Super, so such chords need one that has it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct KeyChord {
+    pub code: KeyCode,
+    pub ctrl: bool,
+    pub alt: bool,
+    pub shift: bool,
+    /// The Super (Windows, Command) key.
+    pub logo: bool,
+}
+
+impl KeyChord {
+    /// A chord without modifiers.
+    pub const fn plain(code: KeyCode) -> Self {
+        KeyChord { code, ctrl: false, alt: false, shift: false, logo: false }
+    }
+
+    /// A Ctrl chord.
+    pub const fn ctrl(code: KeyCode) -> Self {
+        KeyChord { code, ctrl: true, alt: false, shift: false, logo: false }
+    }
+
+    /// An Alt chord.
+    pub const fn alt(code: KeyCode) -> Self {
+        KeyChord { code, ctrl: false, alt: true, shift: false, logo: false }
+    }
+
+    pub fn is_plain(&self) -> bool {
+        !self.ctrl && !self.alt && !self.shift && !self.logo
+    }
+
+    /// The chord of key event `key`, normalized like `parse` does.
+    pub fn from_key(key: &Key) -> Self {
+        let m = key.modifiers;
+        Self::normalized(key.code, m.ctrl, m.alt, m.shift, m.logo)
+    }
+
+    fn normalized(code: KeyCode, ctrl: bool, alt: bool, shift: bool, logo: bool) -> Self {
+        match code {
+            // A character alone carries its own Shift. With other modifiers
+            // letters are kept lower case and Shift is a flag; terminals
+            // report Ctrl+letter in either case, so only a reported Shift
+            // (or an upper-case letter without Ctrl) sets it.
+            KeyCode::Char(c) if ctrl || alt || logo => KeyChord {
+                code: KeyCode::Char(c.to_ascii_lowercase()),
+                ctrl,
+                alt,
+                shift: shift || (!ctrl && c.is_ascii_uppercase()),
+                logo,
+            },
+            KeyCode::Char(_) => KeyChord::plain(code),
+            _ => KeyChord { code, ctrl, alt, shift, logo },
+        }
+    }
+
+    /// Whether the key event `key` is this chord.
+    pub fn matches(&self, key: &Key) -> bool {
+        *self == KeyChord::from_key(key)
+    }
+
+    /// Parse `Ctrl+Alt+x`, `Ctrl+Shift+k`, `Super+e`, `Shift+Tab`, `F5`,
+    /// `PageDown`, `Space`, `+`, ...
+    pub fn parse(s: &str) -> Result<Self> {
+        let mut rest = s.trim();
+        let (mut ctrl, mut alt, mut shift, mut logo) = (false, false, false, false);
+        while let Some((m, tail)) = rest.split_once('+') {
+            if tail.is_empty() {
+                break;
+            }
+            match m.trim().to_ascii_lowercase().as_str() {
+                "ctrl" | "control" => ctrl = true,
+                "alt" | "meta" => alt = true,
+                "shift" => shift = true,
+                "super" | "cmd" | "command" | "win" => logo = true,
+                _ => break,
+            }
+            rest = tail;
+        }
+        let code = Keybinds::parse_keycode(rest).with_context(|| format!("unknown key `{}`", s))?;
+        if shift && matches!(code, KeyCode::Char(_)) && !(ctrl || alt || logo) {
+            bail!("`{}`: write the shifted character instead of Shift+", s);
+        }
+        Ok(Self::normalized(code, ctrl, alt, shift, logo))
+    }
+}
+
+impl fmt::Display for KeyChord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (on, name) in [(self.ctrl, "Ctrl+"), (self.alt, "Alt+"), (self.shift, "Shift+"), (self.logo, "Super+")] {
+            if on {
+                write!(f, "{}", name)?;
+            }
+        }
+        match self.code {
+            KeyCode::Char(' ') => write!(f, "Space"),
+            code => write!(f, "{}", code),
+        }
+    }
+}
+
+/// Keys typed one after the other (`g g`, `Space c`). Every step is a
+/// plain key; sequences have at least two.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct KeySequence(pub Vec<KeyChord>);
+
+impl KeySequence {
+    /// Parse whitespace-separated keys: `g g`, `Space r`, `F2 x`.
+    pub fn parse(s: &str) -> Result<Self> {
+        let keys = s.split_whitespace().map(KeyChord::parse).collect::<Result<Vec<_>>>()?;
+        if keys.len() < 2 {
+            bail!("`{}`: a sequence needs at least two keys", s);
+        }
+        if keys.iter().any(|k| !k.is_plain()) {
+            bail!("`{}`: sequences take keys without Ctrl/Alt", s);
+        }
+        Ok(KeySequence(keys))
+    }
+
+    /// Whether the sequence begins with `keys` (and is longer).
+    pub fn continues(&self, keys: &[KeyChord]) -> bool {
+        self.0.len() > keys.len() && self.0.starts_with(keys)
+    }
+}
+
+impl fmt::Display for KeySequence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, key) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, " ")?;
+            }
+            write!(f, "{}", key)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum OneOrMany {
+    One(String),
+    Many(Vec<String>),
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct KeymapFile {
+    #[serde(default)]
+    bindings: BTreeMap<String, OneOrMany>,
+    #[serde(default)]
+    sequences: BTreeMap<String, OneOrMany>,
+}
+
+impl OneOrMany {
+    fn into_vec(self) -> Vec<String> {
+        match self {
+            OneOrMany::One(k) => vec![k],
+            OneOrMany::Many(ks) => ks,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct KeymapOut {
+    bindings: BTreeMap<String, Vec<String>>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    sequences: BTreeMap<String, Vec<String>>,
+}
+
+/// Path of the keymap file.
+pub fn keymap_file_path() -> PathBuf {
+    project_config_dir().join(KEYMAP_FILE)
+}
+
+/// Parse and validate keymap TOML into `(action, chords)` pairs.
+pub fn parse_keymap(text: &str) -> Result<Vec<(String, Vec<KeyChord>)>> {
+    let file: KeymapFile = toml::from_str(text).context("invalid keymap TOML")?;
+    let defaults = Keybinds::with_preset(Default::default());
+    let mut out: Vec<(String, Vec<KeyChord>)> = Vec::new();
+    for (action, keys) in file.bindings {
+        if !is_action(&action) {
+            bail!("unknown action `{}`", action);
+        }
+        let keys = keys.into_vec();
+        if keys.is_empty() {
+            bail!("action `{}` has no keys", action);
+        }
+        let chords = keys
+            .iter()
+            .map(|k| KeyChord::parse(k).with_context(|| format!("action `{}`", action)))
+            .collect::<Result<Vec<_>>>()?;
+        for chord in &chords {
+            // Some actions share a key by default (Enter opens entries and
+            // confirms dialogs); only reject pairs that never share one.
+            if let Some((other, _)) = out
+                .iter()
+                .find(|(other, cs)| cs.contains(chord) && !defaults.share_key(other, &action))
+            {
+                bail!("`{}` is bound to both `{}` and `{}`", chord, other, action);
+            }
+        }
+        out.push((action, chords));
+    }
+    Ok(out)
+}
+
+/// Parse and validate the `[sequences]` table of keymap TOML into
+/// `(action, sequences)` pairs. A sequence may not be bound twice or be
+/// the start of another sequence.
+pub fn parse_sequences(text: &str) -> Result<Vec<(String, Vec<KeySequence>)>> {
+    let file: KeymapFile = toml::from_str(text).context("invalid keymap TOML")?;
+    let mut out: Vec<(String, Vec<KeySequence>)> = Vec::new();
+    for (action, keys) in file.sequences {
+        if !is_action(&action) {
+            bail!("unknown action `{}`", action);
+        }
+        let keys = keys.into_vec();
+        if keys.is_empty() {
+            bail!("action `{}` has no sequences", action);
+        }
+        let seqs = keys
+            .iter()
+            .map(|k| KeySequence::parse(k).with_context(|| format!("action `{}`", action)))
+            .collect::<Result<Vec<_>>>()?;
+        for (i, seq) in seqs.iter().enumerate() {
+            let earlier = out.iter().flat_map(|(a, ss)| ss.iter().map(move |s| (a.as_str(), s)));
+            let same = seqs[..i].iter().map(|s| (action.as_str(), s));
+            for (other, prev) in earlier.chain(same) {
+                if prev == seq {
+                    bail!("`{}` is bound to both `{}` and `{}`", seq, other, action);
+                }
+                if prev.continues(&seq.0) || seq.continues(&prev.0) {
+                    bail!("`{}` and `{}` start the same way; one cannot finish before the other", prev, seq);
+                }
+            }
+        }
+        out.push((action, seqs));
+    }
+    Ok(out)
+}
+
+/// Keymap TOML binding each action in `bindings` to its chords and each
+/// action in `sequences` to its key sequences.
+pub fn keymap_toml(bindings: &[(String, Vec<KeyChord>)], sequences: &[(String, Vec<KeySequence>)]) -> String {
+    let out = KeymapOut {
+        bindings: bindings
+            .iter()
+            .map(|(action, chords)| (action.clone(), chords.iter().map(|c| c.to_string()).collect()))
+            .collect(),
+        sequences: sequences
+            .iter()
+            .map(|(action, seqs)| (action.clone(), seqs.iter().map(|s| s.to_string()).collect()))
+            .collect(),
+    };
+    let body = toml::to_string(&out).unwrap_or_default();
+    format!("# Written by the fileZoom Settings dialog (Keys page).\n{}", body)
+}
+
+/// Write `bindings` and `sequences` to the keymap file, replacing it
+/// (creates the config dir if needed).
+pub fn save_keymap(bindings: &[(String, Vec<KeyChord>)], sequences: &[(String, Vec<KeySequence>)]) -> Result<()> {
+    let path = keymap_file_path();
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).with_context(|| format!("failed to create config dir {}", parent.display()))?;
+    }
+    std::fs::write(&path, keymap_toml(bindings, sequences)).with_context(|| format!("failed to write {}", path.display()))
+}
+
+/// Help text listing the keys of every bound action in `kb`, with the
+/// action names to use in `keymap.toml`.
+pub fn help_text(kb: &Keybinds) -> String {
+    let mut s = String::from("Keys:\n\n");
+    for (action, desc) in ACTIONS {
+        let keys: Vec<String> = kb
+            .chords(action)
+            .iter()
+            .map(|k| k.to_string())
+            .chain(kb.sequences(action).iter().map(|s| s.to_string()))
+            .collect();
+        if keys.is_empty() {
+            continue;
+        }
+        s.push_str(&format!("{}: {} [{}]\n", keys.join(", "), desc, action));
+    }
+    s.push_str(&format!("\nKeys can be changed in {}\n", keymap_file_path().display()));
+    s
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::KeyModifiers;
+
+    #[test]
+    fn chords_parse_and_display() {
+        let c = KeyChord::parse("Ctrl+Alt+X").unwrap();
+        assert_eq!(c, KeyChord { code: KeyCode::Char('x'), ctrl: true, alt: true, shift: false, logo: false });
+        assert_eq!(c.to_string(), "Ctrl+Alt+x");
+        assert_eq!(KeyChord::parse("ctrl++").unwrap(), KeyChord::ctrl(KeyCode::Char('+')));
+        assert_eq!(KeyChord::parse("PageDown").unwrap(), KeyChord::plain(KeyCode::PageDown));
+        assert_eq!(KeyChord::parse("Space").unwrap().to_string(), "Space");
+        assert!(KeyChord::parse("Shift+n").is_err());
+        assert!(KeyChord::parse("Hyper+x").is_err());
+
+        let key = Key { code: KeyCode::Char('X'), modifiers: KeyModifiers { ctrl: true, alt: true, ..Default::default() } };
+        assert!(c.matches(&key));
+        assert!(!KeyChord::plain(KeyCode::Char('x')).matches(&key));
+    }
+
+    #[test]
+    fn shift_and_super_chords() {
+        let with = |code, ctrl, alt, shift, logo| Key { code, modifiers: KeyModifiers { ctrl, alt, shift, logo } };
+        let ctrl_shift_k = KeyChord::parse("Ctrl+Shift+K").unwrap();
+        assert_eq!(ctrl_shift_k.to_string(), "Ctrl+Shift+k");
+        // The kitty protocol reports the shifted or the base letter.
+        assert!(ctrl_shift_k.matches(&with(KeyCode::Char('K'), true, false, true, false)));
+        assert!(ctrl_shift_k.matches(&with(KeyCode::Char('k'), true, false, true, false)));
+        // Ctrl+K without a reported Shift (caps lock, legacy codes) is Ctrl+k.
+        assert!(!ctrl_shift_k.matches(&with(KeyCode::Char('K'), true, false, false, false)));
+        assert!(KeyChord::parse("Ctrl+k").unwrap().matches(&with(KeyCode::Char('K'), true, false, false, false)));
+
+        let super_e = KeyChord::parse("Super+e").unwrap();
+        assert_eq!(super_e.to_string(), "Super+e");
+        assert!(super_e.matches(&with(KeyCode::Char('e'), false, false, false, true)));
+        assert!(!super_e.matches(&with(KeyCode::Char('e'), false, false, false, false)));
+
+        // Alt+N is Alt+Shift+n, however it is written or reported.
+        assert_eq!(KeyChord::parse("Alt+N").unwrap(), KeyChord::parse("Alt+Shift+n").unwrap());
+        assert!(KeyChord::parse("Alt+N").unwrap().matches(&with(KeyCode::Char('N'), false, true, true, false)));
+
+        let back_tab = KeyChord::parse("Shift+Tab").unwrap();
+        assert!(back_tab.matches(&with(KeyCode::Tab, false, false, true, false)));
+        assert!(!KeyChord::plain(KeyCode::Tab).matches(&with(KeyCode::Tab, false, false, true, false)));
+        // A shifted character alone stays the character.
+        assert!(KeyChord::plain(KeyCode::Char('N')).matches(&with(KeyCode::Char('N'), false, false, true, false)));
+        assert_eq!(KeyChord::parse("Cmd+Shift+F5").unwrap().to_string(), "Shift+Super+F5");
+    }
+
+    #[test]
+    fn keymap_is_validated() {
+        let ok = parse_keymap("[bindings]\nquit = [\"q\", \"Ctrl+q\"]\nshell = \"Alt+s\"\n").unwrap();
+        assert_eq!(ok.len(), 2);
+        assert!(parse_keymap("[bindings]\nfly = \"f\"\n").unwrap_err().to_string().contains("unknown action"));
+        assert!(format!("{:#}", parse_keymap("[bindings]\nquit = \"Ctrl+\"\n").unwrap_err()).contains("unknown key"));
+        assert!(parse_keymap("[bindings]\nquit = []\n").is_err());
+        assert!(parse_keymap("[bindings]\ncopy = \"x\"\nquit = \"x\"\n").unwrap_err().to_string().contains("bound to both"));
+        // Enter is shared by `open` and `enter` in the default table.
+        assert!(parse_keymap("[bindings]\nenter = \"Enter\"\nopen = \"Enter\"\n").is_ok());
+        assert!(parse_keymap("[keys]\n").is_err());
+    }
+
+    #[test]
+    fn written_keymaps_parse_back() {
+        let kb = Keybinds::with_preset(Default::default());
+        let all: Vec<(String, Vec<KeyChord>)> =
+            ACTIONS.iter().map(|(a, _)| (a.to_string(), kb.chords(a).to_vec())).filter(|(_, c)| !c.is_empty()).collect();
+        let mut parsed = parse_keymap(&keymap_toml(&all, &[])).unwrap();
+        parsed.sort_by(|a, b| a.0.cmp(&b.0));
+        let mut all = all;
+        all.sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(parsed, all);
+
+        let seqs = vec![("go_home".to_string(), vec![KeySequence::parse("Space h").unwrap()])];
+        let text = keymap_toml(&[], &seqs);
+        assert!(text.contains("go_home = [\"Space h\"]"), "{}", text);
+        assert_eq!(parse_sequences(&text).unwrap(), seqs);
+    }
+
+    #[test]
+    fn sequences_are_validated() {
+        let seq = KeySequence::parse(" g  Space ").unwrap();
+        assert_eq!(seq.0, vec![KeyChord::plain(KeyCode::Char('g')), KeyChord::plain(KeyCode::Char(' '))]);
+        assert_eq!(seq.to_string(), "g Space");
+        assert!(seq.continues(&[KeyChord::plain(KeyCode::Char('g'))]));
+        assert!(!seq.continues(&seq.0));
+        assert!(KeySequence::parse("g").is_err());
+        assert!(KeySequence::parse("g Ctrl+g").is_err());
+
+        let ok = parse_sequences("[sequences]\nhome = [\"g g\", \"Space g\"]\nend = \"g e\"\n").unwrap();
+        assert_eq!(ok.len(), 2);
+        assert!(parse_sequences("[sequences]\nfly = \"g f\"\n").unwrap_err().to_string().contains("unknown action"));
+        assert!(parse_sequences("[sequences]\nhome = \"g g\"\nend = \"g g\"\n").unwrap_err().to_string().contains("bound to both"));
+        assert!(parse_sequences("[sequences]\nhome = \"g g\"\nend = \"g g e\"\n").unwrap_err().to_string().contains("start the same way"));
+        // Sequences do not disturb the single-key table.
+        assert!(parse_keymap("[sequences]\nhome = \"g g\"\n").unwrap().is_empty());
+    }
+}
@@ -0,0 +1,278 @@
+//! State of the "Keys" page of the Settings dialog.
+//!
+//! The page lists every action from `keymap::ACTIONS` with its chords. The
+//! user picks an action, presses Enter and then the new chord; the chord
+//! replaces the action's keys. A chord already bound to another action is
+//! not applied straight away: the page asks whether to take it from that
+//! action first. Saving writes the actions that differ from the preset to
+//! `keymap.toml` and reloads the runtime table; key sequences from the
+//! keymap are not edited here and are written back unchanged.
+
+use anyhow::Result;
+
+use crate::app::settings::keymap::{self, KeyChord, KeySequence, ACTIONS};
+use crate::app::settings::runtime_keybinds::{self, Keybinds};
+use crate::input::{Key, KeyCode};
+
+/// Buttons below the action list.
+pub const KEYMAP_BUTTONS: [&str; 3] = ["Save", "Reset", "Cancel"];
+
+/// A captured chord waiting for the user to resolve a conflict.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PendingConflict {
+    pub chord: KeyChord,
+    /// Action that currently owns `chord`.
+    pub owner: String,
+}
+
+/// Editable copy of the key table.
+#[derive(Clone, Debug)]
+pub struct KeymapEditor {
+    /// Chords of every action in `ACTIONS` order.
+    pub bindings: Vec<(String, Vec<KeyChord>)>,
+    /// Selected row: an action, then the buttons.
+    pub selected: usize,
+    /// First visible action row.
+    pub scroll: usize,
+    /// Waiting for the next key to bind to the selected action.
+    pub capturing: bool,
+    pub conflict: Option<PendingConflict>,
+    /// One-line feedback shown above the buttons.
+    pub status: Option<String>,
+    /// Table the keys are compared with when saving and resetting.
+    preset: Keybinds,
+    /// Key sequences that differ from the preset, kept for saving.
+    sequences: Vec<(String, Vec<KeySequence>)>,
+}
+
+impl KeymapEditor {
+    /// Start editing the keys of `kb`.
+    pub fn new(kb: &Keybinds) -> Self {
+        let bindings = ACTIONS.iter().map(|(a, _)| (a.to_string(), kb.chords(a).to_vec())).collect();
+        let preset = Keybinds::with_preset(kb.preset());
+        let sequences = ACTIONS
+            .iter()
+            .filter(|(a, _)| !kb.sequences(a).is_empty() && kb.sequences(a) != preset.sequences(a))
+            .map(|(a, _)| (a.to_string(), kb.sequences(a).to_vec()))
+            .collect();
+        KeymapEditor {
+            bindings,
+            selected: 0,
+            scroll: 0,
+            capturing: false,
+            conflict: None,
+            status: None,
+            preset,
+            sequences,
+        }
+    }
+
+    /// Start editing the global key table.
+    pub fn from_runtime() -> Self {
+        KeymapEditor::new(&runtime_keybinds::get())
+    }
+
+    /// Number of selectable rows (actions plus buttons).
+    pub fn rows(&self) -> usize {
+        self.bindings.len() + KEYMAP_BUTTONS.len()
+    }
+
+    /// Index of the selected action, if an action (not a button) is selected.
+    pub fn selected_action(&self) -> Option<usize> {
+        (self.selected < self.bindings.len()).then_some(self.selected)
+    }
+
+    /// Index of the selected button, if any.
+    pub fn selected_button(&self) -> Option<usize> {
+        self.selected.checked_sub(self.bindings.len())
+    }
+
+    /// Move the selection by `delta` rows, wrapping around.
+    pub fn move_selection(&mut self, delta: isize) {
+        let rows = self.rows() as isize;
+        self.selected = (self.selected as isize + delta).rem_euclid(rows) as usize;
+    }
+
+    /// Keep the selected action inside a window of `height` rows.
+    pub fn scroll_into_view(&mut self, height: usize) {
+        let Some(idx) = self.selected_action() else { return };
+        if idx < self.scroll {
+            self.scroll = idx;
+        } else if height > 0 && idx >= self.scroll + height {
+            self.scroll = idx + 1 - height;
+        }
+    }
+
+    /// Wait for the next key to bind to the selected action.
+    pub fn start_capture(&mut self) {
+        if let Some(idx) = self.selected_action() {
+            self.capturing = true;
+            self.conflict = None;
+            self.status = Some(format!("Press the new key for `{}` (Esc cancels)", self.bindings[idx].0));
+        }
+    }
+
+    /// Stop capturing or drop a pending conflict without changing anything.
+    pub fn cancel(&mut self) {
+        self.capturing = false;
+        self.conflict = None;
+        self.status = None;
+    }
+
+    /// Handle the key pressed while capturing. Esc cancels; other keys are
+    /// bound to the selected action unless another action already owns
+    /// them, in which case the conflict is left for `take_conflicting`.
+    pub fn capture(&mut self, key: &Key) {
+        if !self.capturing {
+            return;
+        }
+        self.capturing = false;
+        if key.code == KeyCode::Esc && !key.modifiers.ctrl && !key.modifiers.alt {
+            self.status = None;
+            return;
+        }
+        if matches!(key.code, KeyCode::Null | KeyCode::Other) {
+            self.status = Some("That key cannot be bound".to_string());
+            return;
+        }
+        let chord = KeyChord::from_key(key);
+        let Some(idx) = self.selected_action() else { return };
+        match self.owner_of(&chord, idx) {
+            Some(owner) => {
+                self.status = Some(format!("{} is bound to `{}`: Enter takes it, Esc cancels", chord, owner));
+                self.conflict = Some(PendingConflict { chord, owner });
+            }
+            None => self.bind(idx, chord),
+        }
+    }
+
+    /// Resolve the pending conflict by removing the chord from its owner
+    /// and binding it to the selected action. Refused when the owner
+    /// would be left without keys.
+    pub fn take_conflicting(&mut self) {
+        let Some(PendingConflict { chord, owner }) = self.conflict.take() else { return };
+        let Some(idx) = self.selected_action() else { return };
+        let Some(keys) = self.bindings.iter_mut().find(|(a, _)| *a == owner).map(|(_, k)| k) else { return };
+        if keys.len() == 1 {
+            self.status = Some(format!("`{}` has no other key; rebind it first", owner));
+            return;
+        }
+        keys.retain(|k| *k != chord);
+        self.bind(idx, chord);
+    }
+
+    /// Restore the preset keys of the selected action.
+    pub fn reset_selected(&mut self) {
+        let Some(idx) = self.selected_action() else { return };
+        let action = self.bindings[idx].0.clone();
+        let keys = self.preset.chords(&action).to_vec();
+        if let Some((chord, owner)) = keys.iter().find_map(|k| self.owner_of(k, idx).map(|o| (*k, o))) {
+            self.status = Some(format!("Cannot reset `{}`: {} is bound to `{}`", action, chord, owner));
+            return;
+        }
+        self.bindings[idx].1 = keys;
+        self.status = Some(format!("`{}` reset to the preset keys", action));
+    }
+
+    /// Restore the preset keys of every action.
+    pub fn reset_all(&mut self) {
+        for (action, keys) in &mut self.bindings {
+            *keys = self.preset.chords(action).to_vec();
+        }
+        self.cancel();
+        self.status = Some("All keys reset to the preset".to_string());
+    }
+
+    /// Actions whose keys differ from the preset, as written to the keymap.
+    pub fn changes(&self) -> Vec<(String, Vec<KeyChord>)> {
+        self.bindings
+            .iter()
+            .filter(|(action, keys)| !keys.is_empty() && keys.as_slice() != self.preset.chords(action))
+            .cloned()
+            .collect()
+    }
+
+    /// Write the changes to `keymap.toml` and reload the runtime table.
+    pub fn save(&self) -> Result<()> {
+        keymap::save_keymap(&self.changes(), &self.sequences)?;
+        runtime_keybinds::reload()
+    }
+
+    fn bind(&mut self, idx: usize, chord: KeyChord) {
+        self.bindings[idx].1 = vec![chord];
+        self.status = Some(format!("`{}` bound to {}", self.bindings[idx].0, chord));
+    }
+
+    /// Action other than the one at `idx` that owns `chord`. Actions that
+    /// share a key in the preset (Enter: `open` and `enter`) may keep sharing it.
+    fn owner_of(&self, chord: &KeyChord, idx: usize) -> Option<String> {
+        let action = &self.bindings[idx].0;
+        self.bindings
+            .iter()
+            .enumerate()
+            .find(|(i, (other, keys))| *i != idx && keys.contains(chord) && !self.preset.share_key(other, action))
+            .map(|(_, (other, _))| other.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::KeyModifiers;
+
+    fn editor_at(action: &str) -> KeymapEditor {
+        let mut ed = KeymapEditor::new(&Keybinds::with_preset(Default::default()));
+        ed.selected = ed.bindings.iter().position(|(a, _)| a == action).unwrap();
+        ed
+    }
+
+    fn ctrl(c: char) -> Key {
+        Key { code: KeyCode::Char(c), modifiers: KeyModifiers { ctrl: true, ..Default::default() } }
+    }
+
+    #[test]
+    fn captured_chord_replaces_the_keys() {
+        let mut ed = editor_at("refresh");
+        ed.start_capture();
+        ed.capture(&ctrl('R'));
+        assert_eq!(ed.bindings[ed.selected].1, vec![KeyChord::ctrl(KeyCode::Char('r'))]);
+        assert_eq!(ed.changes(), vec![("refresh".to_string(), vec![KeyChord::ctrl(KeyCode::Char('r'))])]);
+
+        ed.start_capture();
+        ed.capture(&Key::simple(KeyCode::Esc));
+        assert!(!ed.capturing);
+        assert_eq!(ed.changes().len(), 1);
+
+        ed.reset_selected();
+        assert!(ed.changes().is_empty());
+    }
+
+    #[test]
+    fn conflicts_wait_for_confirmation() {
+        let mut ed = editor_at("refresh");
+        ed.start_capture();
+        ed.capture(&Key::simple(KeyCode::Char('c')));
+        assert_eq!(ed.conflict.as_ref().map(|c| c.owner.as_str()), Some("copy"));
+        assert!(ed.changes().is_empty());
+        // `copy` has no other key, so it cannot give `c` away.
+        ed.take_conflicting();
+        assert!(ed.changes().is_empty());
+
+        let copy = ed.bindings.iter().position(|(a, _)| a == "copy").unwrap();
+        ed.bindings[copy].1.push(KeyChord::ctrl(KeyCode::Char('c')));
+        ed.start_capture();
+        ed.capture(&Key::simple(KeyCode::Char('c')));
+        ed.take_conflicting();
+        assert_eq!(ed.bindings[copy].1, vec![KeyChord::ctrl(KeyCode::Char('c'))]);
+        assert_eq!(ed.bindings[ed.selected].1, vec![KeyChord::plain(KeyCode::Char('c'))]);
+        assert!(keymap::parse_keymap(&keymap::keymap_toml(&ed.changes(), &[])).is_ok());
+    }
+
+    #[test]
+    fn keys_shared_by_the_preset_are_not_conflicts() {
+        let mut ed = editor_at("open");
+        ed.start_capture();
+        ed.capture(&Key::simple(KeyCode::Enter));
+        assert!(ed.conflict.is_none());
+    }
+}
@@ -1,12 +1,19 @@
 pub mod read_settings;
 pub mod write_settings;
+pub mod backup;
+pub mod session;
 pub mod config_dirs;
 pub mod keybinds;
+pub mod keymap;
+pub mod keymap_editor;
 pub mod runtime_keybinds;
+pub mod profiles;
+pub mod user_menu;
 
 // Re-export commonly used types/functions for convenience
 pub use read_settings::load_settings;
-pub use write_settings::save_settings;
+pub use write_settings::{restore_previous_settings, save_settings};
 pub use write_settings::Settings;
 pub use config_dirs::{project_config_dir, user_cache_dir, ensure_dirs_exist};
 pub use keybinds::*;
+pub use runtime_keybinds::KeybindPreset;
@@ -0,0 +1,143 @@
+//! Named operation profiles ("copy profiles").
+//!
+//! Profiles are read from `profiles.toml` in `project_config_dir()` and run
+//! against the active panel's selection with `:profile NAME` on the command
+//! line (`:profiles` lists them). Each `[[profile]]` table names a
+//! destination and how to copy there:
+//!
+//! ```toml
+//! [[profile]]
+//! name = "Backup to NAS"
+//! destination = "/mnt/nas/backup"
+//! operation = "copy"          # or "move"
+//! excludes = ["*.tmp", "node_modules"]
+//! preserve = true             # permissions and timestamps
+//! verify = true
+//! throttle_mb_s = 20
+//! on_conflict = "overwrite"   # fail (default), skip or overwrite
+//! ```
+
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+use crate::app::settings::config_dirs::project_config_dir;
+use crate::runner::batch::ConflictPolicy;
+
+/// File name of the profiles inside the config directory.
+pub const PROFILES_FILE: &str = "profiles.toml";
+
+/// Whether a profile copies or moves the selection.
+#[derive(Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ProfileOperation {
+    #[default]
+    Copy,
+    Move,
+}
+
+/// One saved operation.
+#[derive(Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct OperationProfile {
+    /// Name used with `:profile`.
+    pub name: String,
+    #[serde(default)]
+    pub operation: ProfileOperation,
+    /// Directory the selection is copied or moved into.
+    pub destination: PathBuf,
+    /// Glob patterns (`*`, `?`) matched against file and directory names;
+    /// matching entries and everything below them are left out.
+    #[serde(default)]
+    pub excludes: Vec<String>,
+    /// Keep permissions and timestamps of the sources.
+    #[serde(default = "default_preserve")]
+    pub preserve: bool,
+    /// Compare checksums of the copies before finishing (and, for a move,
+    /// before removing the sources).
+    #[serde(default)]
+    pub verify: bool,
+    /// Limit on the copy rate in MB/s.
+    #[serde(default)]
+    pub throttle_mb_s: Option<u32>,
+    #[serde(default)]
+    pub on_conflict: ConflictPolicy,
+}
+
+fn default_preserve() -> bool {
+    true
+}
+
+/// Top-level structure of `profiles.toml`.
+#[derive(Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Profiles {
+    #[serde(default, rename = "profile")]
+    pub profiles: Vec<OperationProfile>,
+}
+
+impl Profiles {
+    /// The profile called `name`, ignoring case.
+    pub fn find(&self, name: &str) -> Option<&OperationProfile> {
+        self.profiles.iter().find(|p| p.name.eq_ignore_ascii_case(name.trim()))
+    }
+}
+
+/// Path of the profiles file.
+pub fn profiles_path() -> PathBuf {
+    project_config_dir().join(PROFILES_FILE)
+}
+
+/// Parse profiles TOML.
+pub fn parse_profiles(raw: &str) -> Result<Profiles> {
+    Ok(toml::from_str(raw)?)
+}
+
+/// Load the profiles. A missing file yields no profiles.
+pub fn load_profiles() -> Result<Profiles> {
+    let path = profiles_path();
+    if !path.exists() {
+        return Ok(Profiles::default());
+    }
+    let raw = fs::read_to_string(&path)
+        .with_context(|| format!("failed to read profiles {}", path.display()))?;
+    parse_profiles(&raw).with_context(|| format!("failed to parse profiles TOML in {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_profiles_with_defaults() {
+        let profiles = parse_profiles(
+            r#"
+            [[profile]]
+            name = "Backup to NAS"
+            destination = "/mnt/nas"
+            excludes = ["*.tmp"]
+            verify = true
+            throttle_mb_s = 20
+            on_conflict = "overwrite"
+
+            [[profile]]
+            name = "Archive"
+            operation = "move"
+            destination = "/srv/archive"
+            preserve = false
+            "#,
+        )
+        .unwrap();
+        let nas = profiles.find("backup to nas").unwrap();
+        assert_eq!((nas.operation, nas.preserve, nas.verify, nas.throttle_mb_s), (ProfileOperation::Copy, true, true, Some(20)));
+        assert_eq!(nas.on_conflict, ConflictPolicy::Overwrite);
+        let archive = profiles.find("Archive").unwrap();
+        assert_eq!((archive.operation, archive.preserve, archive.on_conflict), (ProfileOperation::Move, false, ConflictPolicy::Fail));
+        assert!(profiles.find("missing").is_none());
+    }
+
+    #[test]
+    fn unknown_operation_is_an_error() {
+        assert!(parse_profiles("[[profile]]\nname = \"x\"\ndestination = \"/\"\noperation = \"link\"\n").is_err());
+    }
+}
@@ -1,37 +1,193 @@
-use std::{collections::HashMap, path::PathBuf};
+use std::{collections::HashMap, path::PathBuf, sync::{RwLock, RwLockReadGuard}};
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use once_cell::sync::Lazy;
 use crate::app::settings::config_dirs::project_config_dir;
-use crate::input::KeyCode;
+use crate::app::settings::keymap::{self, KeyChord, KeySequence};
+use crate::input::{Key, KeyCode};
 use std::fs;
 
-/// Runtime-configured keybindings. Loads `keybinds.xml` from the
-/// project config dir or current working directory if present. Always
-/// provides a sensible default mapping so callers can simply consult
-/// bindings via `KEYBINDS.is_bound("action", &code)`.
+/// Named keybinding layouts that remap the action table at once.
+///
+/// A preset is applied on top of the default table: each of its keys is
+/// taken away from whatever action had it and bound to the preset's action.
+/// `keybinds.xml` and then `keymap.toml` overrides are applied last.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum KeybindPreset {
+    /// The built-in fileZoom keys.
+    #[default]
+    Default,
+    /// Midnight Commander function keys.
+    Mc,
+    /// vim-style `hjkl` navigation.
+    Vim,
+    /// Norton Commander function keys.
+    Norton,
+}
+
+impl KeybindPreset {
+    /// All presets in display order.
+    pub const ALL: [KeybindPreset; 4] = [KeybindPreset::Default, KeybindPreset::Mc, KeybindPreset::Vim, KeybindPreset::Norton];
+
+    /// Name used in `settings.toml`.
+    pub fn name(self) -> &'static str {
+        match self {
+            KeybindPreset::Default => "default",
+            KeybindPreset::Mc => "mc",
+            KeybindPreset::Vim => "vim",
+            KeybindPreset::Norton => "norton",
+        }
+    }
+
+    /// Parse a preset name (case-insensitive).
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|p| p.name().eq_ignore_ascii_case(name.trim()))
+    }
+
+    /// The next preset, wrapping around (used by the Settings dialog).
+    pub fn next(self) -> Self {
+        let i = Self::ALL.iter().position(|p| *p == self).unwrap_or(0);
+        Self::ALL[(i + 1) % Self::ALL.len()]
+    }
+
+    /// `(action, key)` pairs the preset binds.
+    fn bindings(self) -> &'static [(&'static str, KeyCode)] {
+        use KeyCode::*;
+        // MC kept Norton Commander's function-key layout, so both presets
+        // share it.
+        const FUNCTION_KEYS: &[(&str, KeyCode)] = &[
+            ("help", F(1)),
+            ("user_menu", F(2)),
+            ("view", F(3)),
+            ("edit", F(4)),
+            ("f5", F(5)),
+            ("f6", F(6)),
+            ("new_dir", F(7)),
+            ("delete", F(8)),
+            ("menu", F(9)),
+            ("quit", F(10)),
+            ("toggle_selection", Insert),
+        ];
+        const VIM: &[(&str, KeyCode)] = &[
+            ("down", Char('j')),
+            ("up", Char('k')),
+            ("go_up", Char('h')),
+            ("open", Char('l')),
+            ("home", Char('g')),
+            ("end", Char('G')),
+            ("delete", Char('x')),
+            ("f5", Char('y')),
+            ("toggle_selection", Char('v')),
+        ];
+        match self {
+            KeybindPreset::Default => &[],
+            KeybindPreset::Mc | KeybindPreset::Norton => FUNCTION_KEYS,
+            KeybindPreset::Vim => VIM,
+        }
+    }
+}
+
+/// Runtime-configured keybindings. Loads `keybinds.xml` and `keymap.toml`
+/// from the project config dir (`keybinds.xml` also from the current
+/// working directory) if present. Always provides a sensible default
+/// mapping so callers can simply consult bindings via
+/// `KEYBINDS.is_bound("action", &code)`.
+#[derive(Clone, Debug)]
 pub struct Keybinds {
-    map: HashMap<String, Vec<KeyCode>>,
+    map: HashMap<String, Vec<KeyChord>>,
+    /// Multi-key sequences per action (`g g` -> `home`).
+    sequences: HashMap<String, Vec<KeySequence>>,
+    preset: KeybindPreset,
+    /// Why `keymap.toml` was rejected on the last load, if it was.
+    keymap_error: Option<String>,
 }
 
 impl Keybinds {
+    /// Whether the unmodified key `code` is bound to `action`.
     pub fn is_bound(&self, action: &str, code: &KeyCode) -> bool {
-        self.map
-            .get(action)
-            .map(|v| v.iter().any(|k| k == code))
-            .unwrap_or(false)
+        self.chords(action).iter().any(|k| k.is_plain() && k.code == *code)
+    }
+
+    /// Whether the key event `key` (including Ctrl/Alt) is bound to `action`.
+    pub fn is_chord_bound(&self, action: &str, key: &Key) -> bool {
+        self.chords(action).iter().any(|k| k.matches(key))
+    }
+
+    /// Chords bound to `action`.
+    pub fn chords(&self, action: &str) -> &[KeyChord] {
+        self.map.get(action).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// Key sequences bound to `action`.
+    pub fn sequences(&self, action: &str) -> &[KeySequence] {
+        self.sequences.get(action).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// What the keys typed so far (`keys`) add up to.
+    pub fn sequence_match(&self, keys: &[KeyChord]) -> SequenceMatch {
+        let mut prefix = false;
+        for (action, seqs) in &self.sequences {
+            for seq in seqs {
+                if seq.0 == keys {
+                    return SequenceMatch::Complete(action.clone());
+                }
+                prefix |= seq.continues(keys);
+            }
+        }
+        if prefix {
+            SequenceMatch::Prefix
+        } else {
+            SequenceMatch::None
+        }
+    }
+
+    /// Sequences that continue `keys`, as `(remaining keys, action)`
+    /// sorted by the remaining keys, for the hint popup.
+    pub fn continuations(&self, keys: &[KeyChord]) -> Vec<(KeySequence, String)> {
+        let mut out: Vec<(KeySequence, String)> = self
+            .sequences
+            .iter()
+            .flat_map(|(action, seqs)| {
+                seqs.iter()
+                    .filter(|s| s.continues(keys))
+                    .map(move |s| (KeySequence(s.0[keys.len()..].to_vec()), action.clone()))
+            })
+            .collect();
+        out.sort_by_key(|(rest, _)| rest.to_string());
+        out
+    }
+
+    /// The default plain key of `action`, which normal-mode dispatch
+    /// matches on; `None` for actions without one (`shell`, `go_home`).
+    pub fn default_key(action: &str) -> Option<KeyCode> {
+        Keybinds::default().map.get(action).and_then(|keys| keys.first()).filter(|k| k.is_plain()).map(|k| k.code)
+    }
+
+    /// The preset the table was built from.
+    pub fn preset(&self) -> KeybindPreset {
+        self.preset
+    }
+
+    /// Why `keymap.toml` was rejected on the last load, if it was.
+    pub fn keymap_error(&self) -> Option<&str> {
+        self.keymap_error.as_deref()
+    }
+
+    /// Whether `a` and `b` have a key in common.
+    pub fn share_key(&self, a: &str, b: &str) -> bool {
+        self.chords(a).iter().any(|k| self.chords(b).contains(k))
     }
 
     fn insert(&mut self, action: &str, kc: KeyCode) {
         self.map
             .entry(action.to_string())
             .or_default()
-            .push(kc);
+            .push(KeyChord::plain(kc));
     }
 
     fn default() -> Self {
         use KeyCode::*;
-        let mut m = HashMap::new();
+        let mut m: HashMap<String, Vec<KeyCode>> = HashMap::new();
         m.insert("quit".to_string(), vec![Char('q')]);
         m.insert("down".to_string(), vec![Down]);
         m.insert("up".to_string(), vec![Up]);
@@ -55,11 +211,100 @@ impl Keybinds {
         m.insert("left".to_string(), vec![Left]);
         m.insert("right".to_string(), vec![Right]);
         m.insert("esc".to_string(), vec![Esc]);
+        // Normal-mode actions that presets may rebind.
+        m.insert("open".to_string(), vec![Enter]);
+        m.insert("go_up".to_string(), vec![Backspace]);
+        m.insert("home".to_string(), vec![Home]);
+        m.insert("end".to_string(), vec![End]);
+        m.insert("menu".to_string(), vec![F(1)]);
+        m.insert("user_menu".to_string(), vec![F(2)]);
+        m.insert("view".to_string(), vec![F(3)]);
+        m.insert("edit".to_string(), vec![F(4)]);
+        m.insert("help".to_string(), vec![Char('?')]);
+        m.insert("toggle_preview".to_string(), vec![Char('p')]);
+        m.insert("focus_preview".to_string(), vec![Char('P')]);
+        m.insert("info_panel".to_string(), vec![Char('i')]);
+        m.insert("tree".to_string(), vec![Char('e')]);
+        m.insert("flat_view".to_string(), vec![Char('F')]);
+        m.insert("find".to_string(), vec![Char('/')]);
+        m.insert("select_by_criteria".to_string(), vec![Char('+')]);
+        m.insert("disk_usage".to_string(), vec![Char('U')]);
+        m.insert("link_panels".to_string(), vec![Char('K')]);
+        m.insert("breadcrumbs".to_string(), vec![Char('b')]);
+        m.insert("preview_scroll_down".to_string(), vec![Char('>')]);
+        m.insert("preview_scroll_up".to_string(), vec![Char('<')]);
+        m.insert("toggle_theme".to_string(), vec![Char('t')]);
+        m.insert("theme_picker".to_string(), vec![Char('T')]);
+        m.insert("mounts".to_string(), vec![Char('M')]);
+        m.insert("cycle_layout".to_string(), vec![Char('L')]);
+        m.insert("job_history".to_string(), vec![Char('J')]);
+        m.insert("jobs".to_string(), vec![Char('B')]);
+        m.insert("diagnostics".to_string(), vec![F(12)]);
+        m.insert("command_line".to_string(), vec![Char(':')]);
+
+        let mut map: HashMap<String, Vec<KeyChord>> =
+            m.into_iter().map(|(a, keys)| (a, keys.into_iter().map(KeyChord::plain).collect())).collect();
+        map.insert("shell".to_string(), vec![KeyChord::ctrl(Char('o'))]);
+        map.insert("quick_view".to_string(), vec![KeyChord::ctrl(Char('q'))]);
+        map.insert("yank".to_string(), vec![KeyChord::ctrl(Char('c'))]);
+        map.insert("cut".to_string(), vec![KeyChord::ctrl(Char('x'))]);
+        map.insert("paste".to_string(), vec![KeyChord::ctrl(Char('v'))]);
+        map.insert("swap_panels".to_string(), vec![KeyChord::ctrl(Char('u'))]);
+        map.insert("other_panel_here".to_string(), vec![KeyChord::alt(Char('i'))]);
+        map.insert("this_panel_there".to_string(), vec![KeyChord { shift: true, ..KeyChord::alt(Char('i')) }]);
+
+        let seq = |keys: &[KeyCode]| KeySequence(keys.iter().copied().map(KeyChord::plain).collect());
+        let mut sequences = HashMap::new();
+        sequences.insert("home".to_string(), vec![seq(&[Char('g'), Char('g')])]);
+        sequences.insert("end".to_string(), vec![seq(&[Char('g'), Char('e')])]);
+        sequences.insert("go_home".to_string(), vec![seq(&[Char('g'), Char('h')])]);
+        Keybinds { map, sequences, preset: KeybindPreset::Default, keymap_error: None }
+    }
+
+    /// The default table with `preset` applied.
+    pub fn with_preset(preset: KeybindPreset) -> Self {
+        let mut kb = Keybinds::default();
+        kb.preset = preset;
+        for (action, kc) in preset.bindings() {
+            for keys in kb.map.values_mut() {
+                keys.retain(|k| *k != KeyChord::plain(*kc));
+            }
+            kb.insert(action, *kc);
+        }
+        kb
+    }
+
+    /// Translate `code` into the default key of the action it is bound to,
+    /// so normal-mode dispatch (written against the default keys) follows
+    /// the active preset. Keys bound to no action are returned unchanged.
+    pub fn canonical_key(&self, code: &KeyCode) -> KeyCode {
+        self.canonical_chord(&KeyChord::plain(*code)).unwrap_or(*code)
+    }
 
-        Keybinds { map: m }
+    /// Like `canonical_key` for a key event with modifiers: the default
+    /// key of the action `key` is bound to, or `None` when it is unbound.
+    pub fn chord_key(&self, key: &Key) -> Option<KeyCode> {
+        let chord = self.map.values().flatten().find(|c| c.matches(key))?;
+        self.canonical_chord(chord)
     }
 
-    fn parse_keycode(s: &str) -> Option<KeyCode> {
+    fn canonical_chord(&self, chord: &KeyChord) -> Option<KeyCode> {
+        let defaults = Keybinds::default();
+        let mut actions: Vec<&String> = self.map.iter().filter(|(_, keys)| keys.contains(chord)).map(|(a, _)| a).collect();
+        // Prefer an action whose default key is `code` itself so keys
+        // shared by several actions (Enter: "enter" and "open") stay put.
+        actions.sort();
+        // Actions whose default is a modifier chord (`shell`) are handled
+        // by the event loop, not by default-key dispatch.
+        let canonical =
+            |a: &String| defaults.map.get(a).and_then(|keys| keys.first()).filter(|k| k.is_plain()).map(|k| k.code);
+        if chord.is_plain() && actions.iter().any(|a| canonical(a) == Some(chord.code)) {
+            return Some(chord.code);
+        }
+        actions.into_iter().find_map(canonical)
+    }
+
+    pub(crate) fn parse_keycode(s: &str) -> Option<KeyCode> {
         // Accept patterns like "Enter", "Backspace", "Esc", "Left",
         // "Char x" (or single char strings), "F5".
         use KeyCode::*;
@@ -94,6 +339,21 @@ impl Keybinds {
         if t.eq_ignore_ascii_case("pagedown") || t.eq_ignore_ascii_case("page_down") {
             return Some(PageDown);
         }
+        if t.eq_ignore_ascii_case("home") {
+            return Some(Home);
+        }
+        if t.eq_ignore_ascii_case("end") {
+            return Some(End);
+        }
+        if t.eq_ignore_ascii_case("delete") || t.eq_ignore_ascii_case("del") {
+            return Some(Delete);
+        }
+        if t.eq_ignore_ascii_case("insert") || t.eq_ignore_ascii_case("ins") {
+            return Some(Insert);
+        }
+        if t.eq_ignore_ascii_case("space") {
+            return Some(Char(' '));
+        }
         if let Some(rest) = t.strip_prefix('F') {
             if let Ok(n) = rest.parse::<u8>() {
                 return Some(F(n));
@@ -104,16 +364,17 @@ impl Keybinds {
             let ch = rest.chars().next()?;
             return Some(Char(ch));
         }
-        if t.len() == 1 {
-            return Some(Char(t.chars().next().unwrap()));
+        let mut chars = t.chars();
+        if let (Some(c), None) = (chars.next(), chars.next()) {
+            return Some(Char(c));
         }
         None
     }
 
-    fn load_from_path(path: PathBuf) -> Result<Self> {
+    fn load_from_path(path: PathBuf, preset: KeybindPreset) -> Result<Self> {
         // Simple, tolerant XML-ish parser: look for `<bind action="...">VALUE</bind>`
         let raw = fs::read_to_string(path)?;
-        let mut kb = Keybinds { map: HashMap::new() };
+        let mut kb = Keybinds { map: HashMap::new(), sequences: HashMap::new(), preset, keymap_error: None };
 
         let mut rest = raw.as_str();
         while let Some(start) = rest.find("<bind") {
@@ -150,9 +411,9 @@ impl Keybinds {
         }
 
         if kb.map.is_empty() {
-            Ok(Keybinds::default())
+            Ok(Keybinds::with_preset(preset))
         } else {
-            let mut def = Keybinds::default();
+            let mut def = Keybinds::with_preset(preset);
             for (k, v) in kb.map.into_iter() {
                 def.map.insert(k, v);
             }
@@ -161,28 +422,192 @@ impl Keybinds {
     }
 }
 
-static KEYBINDS: Lazy<Keybinds> = Lazy::new(|| {
-    // Look for `keybinds.xml` first in the project config dir, then the cwd
-    let mut candidates = Vec::new();
-    let mut pc = project_config_dir();
-    pc.push("keybinds.xml");
-    candidates.push(pc);
-    let mut cwd = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
-    cwd.push("keybinds.xml");
-    candidates.push(cwd);
-
-    for p in candidates {
-        if p.exists() {
-            if let Ok(k) = Keybinds::load_from_path(p) {
-                return k;
+impl Keybinds {
+    /// Apply validated `keymap.toml` text on top of the table.
+    pub fn apply_keymap(&mut self, text: &str) -> Result<()> {
+        let bindings = keymap::parse_keymap(text)?;
+        let sequences = keymap::parse_sequences(text)?;
+        // Chords from the keymap are taken from other actions they were
+        // bound to (`copy = "Ctrl+c"` replaces `yank`), except between
+        // actions that share a key by default.
+        let defaults = Keybinds::with_preset(Default::default());
+        for (action, keys) in self.map.iter_mut() {
+            if !bindings.iter().any(|(a, _)| a == action) {
+                keys.retain(|k| !bindings.iter().any(|(a, cs)| cs.contains(k) && !defaults.share_key(a, action)));
+            }
+        }
+        for (action, chords) in bindings {
+            self.map.insert(action, chords);
+        }
+        // Sequences from the keymap win over preset ones they clash with.
+        let clashes = |s: &KeySequence| {
+            sequences.iter().flat_map(|(_, ss)| ss).any(|n| n == s || n.continues(&s.0) || s.continues(&n.0))
+        };
+        for (action, seqs) in self.sequences.iter_mut() {
+            if !sequences.iter().any(|(a, _)| a == action) {
+                seqs.retain(|s| !clashes(s));
+            }
+        }
+        for (action, seqs) in sequences {
+            self.sequences.insert(action, seqs);
+        }
+        Ok(())
+    }
+
+    /// Build the table for `preset`, applying `keybinds.xml` from the
+    /// project config dir or, failing that, the cwd, and then
+    /// `keymap.toml`. A rejected keymap leaves the rest in place and is
+    /// reported through `keymap_error`.
+    fn load(preset: KeybindPreset) -> Self {
+        let mut kb = Keybinds::load_xml(preset);
+        let path = keymap::keymap_file_path();
+        if path.exists() {
+            let res = fs::read_to_string(&path)
+                .with_context(|| format!("failed to read {}", path.display()))
+                .and_then(|text| kb.apply_keymap(&text).with_context(|| format!("in {}", path.display())));
+            if let Err(e) = res {
+                kb.keymap_error = Some(format!("{:#}", e));
             }
         }
+        kb
     }
 
-    Keybinds::default()
-});
+    fn load_xml(preset: KeybindPreset) -> Self {
+        let mut candidates = Vec::new();
+        let mut pc = project_config_dir();
+        pc.push("keybinds.xml");
+        candidates.push(pc);
+        let mut cwd = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+        cwd.push("keybinds.xml");
+        candidates.push(cwd);
+
+        for p in candidates {
+            if p.exists() {
+                if let Ok(k) = Keybinds::load_from_path(p, preset) {
+                    return k;
+                }
+            }
+        }
+
+        Keybinds::with_preset(preset)
+    }
+}
+
+/// Result of matching the keys typed so far against the key sequences.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SequenceMatch {
+    /// No sequence starts with these keys.
+    None,
+    /// Some sequence starts with these keys; wait for the next one.
+    Prefix,
+    /// The keys form the sequence bound to this action.
+    Complete(String),
+}
+
+static KEYBINDS: Lazy<RwLock<Keybinds>> = Lazy::new(|| RwLock::new(Keybinds::load(KeybindPreset::Default)));
 
 /// Expose a reference to the global keybinds.
-pub fn get() -> &'static Keybinds {
-    &KEYBINDS
+pub fn get() -> RwLockReadGuard<'static, Keybinds> {
+    KEYBINDS.read().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Switch the global keybinds to `preset` (re-reading `keybinds.xml`
+/// and `keymap.toml`).
+pub fn set_preset(preset: KeybindPreset) {
+    *KEYBINDS.write().unwrap_or_else(|e| e.into_inner()) = Keybinds::load(preset);
+}
+
+/// Re-read the keybinding files for the current preset. Returns the
+/// keymap validation error, if any; the other bindings are applied anyway.
+pub fn reload() -> Result<()> {
+    let preset = get().preset();
+    let kb = Keybinds::load(preset);
+    let err = kb.keymap_error.clone();
+    *KEYBINDS.write().unwrap_or_else(|e| e.into_inner()) = kb;
+    match err {
+        Some(e) => Err(anyhow::anyhow!(e)),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use KeyCode::*;
+
+    #[test]
+    fn presets_round_trip_names_and_cycle() {
+        for p in KeybindPreset::ALL {
+            assert_eq!(KeybindPreset::from_name(p.name()), Some(p));
+        }
+        assert_eq!(KeybindPreset::from_name("VIM"), Some(KeybindPreset::Vim));
+        assert_eq!(KeybindPreset::from_name("emacs"), None);
+        assert_eq!(KeybindPreset::Norton.next(), KeybindPreset::Default);
+    }
+
+    #[test]
+    fn preset_keys_translate_to_default_keys() {
+        let vim = Keybinds::with_preset(KeybindPreset::Vim);
+        assert!(vim.is_bound("down", &Char('j')) && vim.is_bound("down", &Down));
+        assert_eq!(vim.canonical_key(&Char('j')), Down);
+        assert_eq!(vim.canonical_key(&Char('h')), Backspace);
+        assert_eq!(vim.canonical_key(&Char('l')), Enter);
+        assert_eq!(vim.canonical_key(&Enter), Enter);
+        assert_eq!(vim.canonical_key(&Char('z')), Char('z'));
+
+        // F1 moves from the menu to help; F9 takes over the menu.
+        let mc = Keybinds::with_preset(KeybindPreset::Mc);
+        assert!(!mc.is_bound("menu", &F(1)));
+        assert_eq!(mc.canonical_key(&F(1)), Char('?'));
+        assert_eq!(mc.canonical_key(&F(9)), F(1));
+        assert_eq!(mc.canonical_key(&F(8)), Char('d'));
+        assert_eq!(mc.canonical_key(&F(7)), Char('N'));
+        assert_eq!(mc.canonical_key(&F(10)), Char('q'));
+
+        let default = Keybinds::with_preset(KeybindPreset::Default);
+        assert_eq!(default.canonical_key(&F(1)), F(1));
+    }
+
+    #[test]
+    fn keymap_chords_reach_their_actions() {
+        use crate::input::KeyModifiers;
+        let mut kb = Keybinds::with_preset(KeybindPreset::Default);
+        kb.apply_keymap("[bindings]\nquit = [\"F10\", \"Ctrl+q\"]\nshell = \"Alt+s\"\n").unwrap();
+        assert!(!kb.is_bound("quit", &Char('q')));
+        assert_eq!(kb.canonical_key(&F(10)), Char('q'));
+        let ctrl_q = Key { code: Char('q'), modifiers: KeyModifiers { ctrl: true, ..Default::default() } };
+        assert!(kb.is_chord_bound("quit", &ctrl_q));
+        assert!(!kb.is_bound("quit", &Char('q')));
+        assert_eq!(kb.chord_key(&ctrl_q), Some(Char('q')));
+        // The keymap takes Ctrl+q away from its default action.
+        assert!(!kb.is_chord_bound("quick_view", &ctrl_q));
+        let alt_s = Key { code: Char('s'), modifiers: KeyModifiers { alt: true, ..Default::default() } };
+        assert!(kb.is_chord_bound("shell", &alt_s));
+        // `shell` has no default plain key to translate to.
+        assert_eq!(kb.chord_key(&alt_s), None);
+
+        assert!(kb.apply_keymap("[bindings]\nnope = \"x\"\n").is_err());
+        assert!(kb.is_chord_bound("quit", &ctrl_q), "a rejected keymap changes nothing");
+    }
+
+    #[test]
+    fn sequences_match_and_list_continuations() {
+        let g = KeyChord::plain(Char('g'));
+        let mut kb = Keybinds::with_preset(KeybindPreset::Default);
+        assert_eq!(kb.sequence_match(&[g]), SequenceMatch::Prefix);
+        assert_eq!(kb.sequence_match(&[g, g]), SequenceMatch::Complete("home".to_string()));
+        assert_eq!(kb.sequence_match(&[g, KeyChord::plain(Char('z'))]), SequenceMatch::None);
+        let next: Vec<String> = kb.continuations(&[g]).into_iter().map(|(rest, a)| format!("{} {}", rest, a)).collect();
+        assert_eq!(next, vec!["e end", "g home", "h go_home"]);
+
+        // A keymap replaces an action's sequences and may add a leader.
+        kb.apply_keymap("[sequences]\nhome = \"Space g\"\nrefresh = \"Space r\"\n").unwrap();
+        assert_eq!(kb.sequence_match(&[g, g]), SequenceMatch::None);
+        assert_eq!(kb.continuations(&[KeyChord::plain(Char(' '))]).len(), 2);
+        kb.apply_keymap("[sequences]\nrefresh = \"g e\"\n").unwrap();
+        assert_eq!(kb.sequence_match(&[g, KeyChord::plain(Char('e'))]), SequenceMatch::Complete("refresh".to_string()));
+        assert!(kb.sequences("end").is_empty());
+        assert_eq!(Keybinds::default_key("refresh"), Some(Char('r')));
+        assert_eq!(Keybinds::default_key("go_home"), None);
+    }
 }
@@ -0,0 +1,155 @@
+//! Session file: where the user left off.
+//!
+//! On exit the event loop saves each panel's directory and selected entry,
+//! the sort key and order, the active side and the panel layout to
+//! `session.toml` next to `settings.toml`. On the next start, when
+//! `Settings::restore_session` is enabled and no panel directory was given
+//! on the command line, the session is applied again. Directories that no
+//! longer exist are skipped.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use super::write_settings::config_file_path;
+use crate::app::core::App;
+use crate::app::DirPosition;
+use crate::app::types::{PanelLayout, Side, SortKey, SortOrder};
+
+/// File name of the session inside the settings directory.
+pub const SESSION_FILE: &str = "session.toml";
+
+/// Saved state of one panel.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+pub struct PanelSession {
+    pub cwd: PathBuf,
+    /// Name of the selected entry, if an entry (not the header or `..`) was selected.
+    #[serde(default)]
+    pub selected: Option<String>,
+}
+
+/// Saved state of both panels.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+pub struct Session {
+    pub left: PanelSession,
+    pub right: PanelSession,
+    /// `"left"` or `"right"`.
+    pub active: String,
+    /// `"name"`, `"size"` or `"modified"`.
+    pub sort: String,
+    #[serde(default)]
+    pub descending: bool,
+    /// Panel layout name (see `PanelLayout::name`); empty in older files.
+    #[serde(default)]
+    pub layout: String,
+}
+
+/// Path of the session file.
+pub fn session_file_path() -> Result<PathBuf> {
+    Ok(config_file_path()?.with_file_name(SESSION_FILE))
+}
+
+/// Load the saved session, or `None` if there is none.
+pub fn load_session() -> Result<Option<Session>> {
+    let path = session_file_path()?;
+    if !path.exists() {
+        return Ok(None);
+    }
+    let raw = fs::read_to_string(&path).with_context(|| format!("failed to read session file {}", path.display()))?;
+    let session = toml::from_str(&raw).with_context(|| format!("failed to parse session TOML in {}", path.display()))?;
+    Ok(Some(session))
+}
+
+/// Write `session` to the session file (creates parent directory if needed).
+pub fn save_session(session: &Session) -> Result<()> {
+    let path = session_file_path()?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).with_context(|| format!("failed to create config dir {}", parent.display()))?;
+    }
+    let s = toml::to_string_pretty(session).context("failed to serialize session to TOML")?;
+    fs::write(&path, s).with_context(|| format!("failed to write session file {}", path.display()))
+}
+
+/// `dir`, or its nearest ancestor whose path is valid UTF-8, since the
+/// session file cannot store other paths.
+fn utf8_ancestor(dir: &Path) -> PathBuf {
+    dir.ancestors().find(|a| a.to_str().is_some()).unwrap_or(dir).to_path_buf()
+}
+
+impl Session {
+    /// Capture the current state of `app`.
+    pub fn capture(app: &App) -> Self {
+        let panel = |p: &crate::app::Panel| PanelSession {
+            cwd: utf8_ancestor(&p.cwd),
+            // A lossy name could restore the wrong entry, so names that are
+            // not valid UTF-8 are not saved (TOML cannot hold them as-is).
+            selected: p.selected_entry().filter(|_| p.cwd.to_str().is_some()).and_then(|e| e.name.to_str()).map(str::to_string),
+        };
+        Session {
+            left: panel(&app.left),
+            right: panel(&app.right),
+            active: match app.active {
+                Side::Left => "left",
+                Side::Right => "right",
+            }
+            .to_string(),
+            sort: match app.sort {
+                SortKey::Name => "name",
+                SortKey::Size => "size",
+                SortKey::Modified => "modified",
+            }
+            .to_string(),
+            descending: app.sort_order == SortOrder::Descending,
+            layout: app.layout.name().to_string(),
+        }
+    }
+
+    /// Apply the session to `app` and reload both panels. Unknown values
+    /// keep the current state.
+    pub fn apply(&self, app: &mut App) -> std::io::Result<()> {
+        self.restore(app);
+        app.refresh()?;
+        if let Some(name) = &self.left.selected {
+            app.left.select_named(name);
+        }
+        if let Some(name) = &self.right.selected {
+            app.right.select_named(name);
+        }
+        Ok(())
+    }
+
+    /// Apply the session to `app` without listing the panels. The selected
+    /// entries are remembered as positions in the panels' directories, so
+    /// the first listing of an `App::unlisted` puts the cursor back on them.
+    pub fn restore(&self, app: &mut App) {
+        if self.left.cwd.is_dir() {
+            app.left.cwd = self.left.cwd.clone();
+        }
+        if self.right.cwd.is_dir() {
+            app.right.cwd = self.right.cwd.clone();
+        }
+        match self.active.as_str() {
+            "left" => app.active = Side::Left,
+            "right" => app.active = Side::Right,
+            _ => {}
+        }
+        match self.sort.as_str() {
+            "name" => app.sort = SortKey::Name,
+            "size" => app.sort = SortKey::Size,
+            "modified" => app.sort = SortKey::Modified,
+            _ => {}
+        }
+        app.sort_order = if self.descending { SortOrder::Descending } else { SortOrder::Ascending };
+        if let Some(layout) = PanelLayout::from_name(&self.layout) {
+            app.layout = layout;
+        }
+        for (panel, saved) in [(&mut app.left, &self.left), (&mut app.right, &self.right)] {
+            if let Some(name) = &saved.selected {
+                let position = DirPosition { name: Some(name.into()), selected: 0, offset: 0 };
+                panel.positions.insert(panel.cwd.clone(), position);
+            }
+        }
+    }
+}
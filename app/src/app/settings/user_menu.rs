@@ -0,0 +1,115 @@
+//! User-defined command menu (F2).
+//!
+//! The menu is read from `user_menu.toml` in `project_config_dir()`. Each
+//! `[[entry]]` table has a `title`, an optional single `key` (same syntax as
+//! `keybinds.xml`, e.g. `"e"` or `"F8"`) and a `command` template that is run
+//! through `runner::shell::run_for_app`, so the command-line placeholders
+//! (`%f`, `%d`, `%%`) are available:
+//!
+//! ```toml
+//! [[entry]]
+//! title = "Compress selection"
+//! key = "z"
+//! command = "tar czf archive.tar.gz %f"
+//! ```
+
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::app::settings::config_dirs::project_config_dir;
+use crate::app::settings::runtime_keybinds::Keybinds;
+use crate::input::KeyCode;
+
+/// File name of the user menu inside the config directory.
+pub const USER_MENU_FILE: &str = "user_menu.toml";
+
+/// A single user menu entry.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct UserMenuEntry {
+    /// Label shown in the menu.
+    pub title: String,
+    /// Optional hotkey that runs the entry directly while the menu is open.
+    #[serde(default)]
+    pub key: Option<String>,
+    /// Shell command template.
+    pub command: String,
+}
+
+impl UserMenuEntry {
+    /// Parsed hotkey, if one is set and valid.
+    pub fn key_code(&self) -> Option<KeyCode> {
+        self.key.as_deref().and_then(Keybinds::parse_keycode)
+    }
+}
+
+/// Top-level structure of `user_menu.toml`.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct UserMenu {
+    #[serde(default, rename = "entry")]
+    pub entries: Vec<UserMenuEntry>,
+}
+
+/// Path of the user menu file.
+pub fn user_menu_path() -> PathBuf {
+    project_config_dir().join(USER_MENU_FILE)
+}
+
+/// Parse user menu TOML.
+pub fn parse_user_menu(raw: &str) -> Result<UserMenu> {
+    Ok(toml::from_str(raw)?)
+}
+
+/// Load the user menu. A missing file yields an empty menu.
+pub fn load_user_menu() -> Result<UserMenu> {
+    let path = user_menu_path();
+    if !path.exists() {
+        return Ok(UserMenu::default());
+    }
+    let raw = fs::read_to_string(&path)
+        .with_context(|| format!("failed to read user menu {}", path.display()))?;
+    parse_user_menu(&raw).with_context(|| format!("failed to parse user menu TOML in {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_entries_and_keys() {
+        let menu = parse_user_menu(
+            r#"
+            [[entry]]
+            title = "List"
+            key = "l"
+            command = "ls -l %f"
+
+            [[entry]]
+            title = "Disk usage"
+            key = "F8"
+            command = "du -sh %d"
+
+            [[entry]]
+            title = "No key"
+            command = "true"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(menu.entries.len(), 3);
+        assert_eq!(menu.entries[0].key_code(), Some(KeyCode::Char('l')));
+        assert_eq!(menu.entries[1].key_code(), Some(KeyCode::F(8)));
+        assert_eq!(menu.entries[2].key_code(), None);
+    }
+
+    #[test]
+    fn empty_file_is_empty_menu() {
+        assert!(parse_user_menu("").unwrap().entries.is_empty());
+    }
+
+    #[test]
+    fn missing_command_is_an_error() {
+        assert!(parse_user_menu("[[entry]]\ntitle = \"x\"\n").is_err());
+    }
+}
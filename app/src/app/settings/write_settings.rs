@@ -4,6 +4,9 @@ use std::env;
 use std::fs;
 use std::io::Write;
 use std::path::PathBuf;
+use std::sync::RwLock;
+
+use once_cell::sync::Lazy;
 
 /// User-editable settings persisted to a TOML file.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -29,6 +32,126 @@ pub struct Settings {
     /// user's `EDITOR` command; integrated launcher is still used when
     /// the editor is `vim` or `vi`.
     pub prefer_integrated_vim: bool,
+    /// Warn before deleting or overwriting files that other processes
+    /// currently hold open (see `fs_op::open_files`).
+    #[serde(default = "default_true")]
+    pub warn_open_files: bool,
+    /// Copy buffer size in KiB for F5/F6 operations. `0` picks a size per
+    /// file automatically (see `fs_op::options::auto_buffer_size`).
+    #[serde(default)]
+    pub copy_buffer_kib: u32,
+    /// Keybinding preset name (`default`, `mc`, `vim`, `norton`); see
+    /// `runtime_keybinds::KeybindPreset`.
+    #[serde(default = "default_keybinding_preset")]
+    pub keybinding_preset: String,
+    /// Reopen the directories, selection and sort of the last session on
+    /// startup (see `session`).
+    #[serde(default = "default_true")]
+    pub restore_session: bool,
+    /// Show only the active panel, at full width.
+    #[serde(default)]
+    pub single_pane: bool,
+    /// How long the first key of a key sequence (`g g`) waits for the
+    /// next one, in milliseconds.
+    #[serde(default = "default_key_sequence_timeout_ms")]
+    pub key_sequence_timeout_ms: u64,
+    /// Colours the terminal can show: `auto` (detect from `COLORTERM` and
+    /// `TERM`), `truecolor`, `256` or `16`; see `ui::colors::ColorDepth`.
+    #[serde(default = "default_color_depth")]
+    pub color_depth: String,
+    /// Show Nerd Font icons before entry names (needs a patched font).
+    #[serde(default)]
+    pub icons: bool,
+    /// Colour listings with the `LS_COLORS` environment variable, when
+    /// set, instead of the theme's file-type colours.
+    #[serde(default = "default_true")]
+    pub ls_colors: bool,
+    /// Show the time at the right of the status line.
+    #[serde(default)]
+    pub show_clock: bool,
+    /// Width of the preview pane shown with `p`, in percent of the screen.
+    #[serde(default = "default_preview_width")]
+    pub preview_width: u16,
+    /// Compare checksums of every copied file with its source after an F5
+    /// copy (see `fs_op::hash`).
+    #[serde(default)]
+    pub verify_after_copy: bool,
+    /// Hide entries ignored by `.gitignore` in listings and leave them out
+    /// of searches and directory totals (see `fs_op::ignore`).
+    #[serde(default)]
+    pub hide_gitignored: bool,
+    /// Most frames drawn per second; changes coming faster are drawn
+    /// together. `0` draws every change at once.
+    #[serde(default = "default_max_fps")]
+    pub max_fps: u32,
+    /// Ask before deleting. Deleting entries other processes hold open
+    /// (`warn_open_files`) always asks.
+    #[serde(default = "default_true")]
+    pub confirm_delete: bool,
+    /// Ask what to do when a copy or move finds its target existing; off
+    /// overwrites without asking.
+    #[serde(default = "default_true")]
+    pub confirm_overwrite: bool,
+    /// Ask before moving to another filesystem, where a move copies and
+    /// then deletes instead of renaming.
+    #[serde(default)]
+    pub confirm_cross_fs_move: bool,
+    /// Ask before quitting while a copy or move is still running.
+    #[serde(default = "default_true")]
+    pub confirm_quit_with_jobs: bool,
+    /// Copies and moves run at the same time; more wait in the job queue
+    /// (see `runner::jobs`).
+    #[serde(default = "default_max_concurrent_jobs")]
+    pub max_concurrent_jobs: usize,
+    /// Run copies and moves one at a time, in queue order, instead of up
+    /// to `max_concurrent_jobs` at once.
+    #[serde(default)]
+    pub sequential_jobs: bool,
+    /// Notify (bell, OSC 9 and, with the `desktop-notify` feature, a
+    /// desktop notification) when a job that ran at least this many
+    /// seconds finishes while the terminal is unfocused. `0` never does.
+    #[serde(default = "default_notify_after_secs")]
+    pub notify_after_secs: u64,
+    /// Listen for commands from other programs (`--remote`, see
+    /// `runner::ipc`).
+    #[serde(default = "default_true")]
+    pub ipc_server: bool,
+    /// Create the missing directories of a destination typed in the copy
+    /// or move dialog, like `mkdir -p`.
+    #[serde(default = "default_true")]
+    pub create_dest_dirs: bool,
+}
+
+fn default_keybinding_preset() -> String {
+    "default".to_string()
+}
+
+fn default_key_sequence_timeout_ms() -> u64 {
+    1000
+}
+
+fn default_color_depth() -> String {
+    "auto".to_string()
+}
+
+fn default_preview_width() -> u16 {
+    40
+}
+
+fn default_max_fps() -> u32 {
+    30
+}
+
+fn default_max_concurrent_jobs() -> usize {
+    2
+}
+
+fn default_notify_after_secs() -> u64 {
+    30
+}
+
+fn default_true() -> bool {
+    true
 }
 
 impl Default for Settings {
@@ -45,6 +168,8 @@ impl Default for Settings {
                 "View".to_string(),
                 "Edit".to_string(),
                 "Permissions".to_string(),
+                "Copy path".to_string(),
+                "Copy name".to_string(),
                 "Cancel".to_string(),
             ],
             mouse_enabled: true,
@@ -52,12 +177,60 @@ impl Default for Settings {
             prefer_integrated_vim: false,
             // Default to CLI-style listing to match the expected TUI look
             show_cli_listing: true,
+            warn_open_files: true,
+            copy_buffer_kib: 0,
+            keybinding_preset: default_keybinding_preset(),
+            restore_session: true,
+            single_pane: false,
+            key_sequence_timeout_ms: default_key_sequence_timeout_ms(),
+            color_depth: default_color_depth(),
+            icons: false,
+            ls_colors: true,
+            show_clock: false,
+            preview_width: default_preview_width(),
+            verify_after_copy: false,
+            hide_gitignored: false,
+            max_fps: default_max_fps(),
+            confirm_delete: true,
+            confirm_overwrite: true,
+            confirm_cross_fs_move: false,
+            confirm_quit_with_jobs: true,
+            max_concurrent_jobs: default_max_concurrent_jobs(),
+            sequential_jobs: false,
+            notify_after_secs: default_notify_after_secs(),
+            ipc_server: true,
+            create_dest_dirs: true,
         }
     }
 }
 
-/// Compute the config file path using XDG_CONFIG_HOME or fallback to $HOME/.config/fileZoom/settings.toml
+impl Settings {
+    /// How many copies and moves may run at once: one with
+    /// `sequential_jobs`, else `max_concurrent_jobs` (at least one).
+    pub fn job_slots(&self) -> usize {
+        if self.sequential_jobs {
+            1
+        } else {
+            self.max_concurrent_jobs.max(1)
+        }
+    }
+}
+
+static CONFIG_FILE_OVERRIDE: Lazy<RwLock<Option<PathBuf>>> = Lazy::new(|| RwLock::new(None));
+
+/// Use `path` as the settings file instead of the default location (the
+/// `--config` flag); `None` restores the default. Files kept next to the
+/// settings (backups, session) follow it.
+pub fn set_config_file_override(path: Option<PathBuf>) {
+    *CONFIG_FILE_OVERRIDE.write().unwrap_or_else(|e| e.into_inner()) = path;
+}
+
+/// Compute the config file path: the `--config` override if set, else
+/// XDG_CONFIG_HOME or fallback to $HOME/.config/fileZoom/settings.toml
 pub fn config_file_path() -> Result<PathBuf> {
+    if let Some(p) = CONFIG_FILE_OVERRIDE.read().unwrap_or_else(|e| e.into_inner()).as_ref() {
+        return Ok(p.clone());
+    }
     if let Some(xdg) = env::var_os("XDG_CONFIG_HOME") {
         let mut p = PathBuf::from(xdg);
         p.push("fileZoom");
@@ -74,13 +247,15 @@ pub fn config_file_path() -> Result<PathBuf> {
     Ok(p)
 }
 
-/// Save settings to disk (creates parent directory if needed).
+/// Save settings to disk (creates parent directory if needed). The
+/// previous file is kept as a timestamped backup (see `backup`).
 pub fn save_settings(settings: &Settings) -> Result<()> {
     let path = config_file_path()?;
     if let Some(parent) = path.parent() {
         fs::create_dir_all(parent)
             .with_context(|| format!("failed to create config dir {}", parent.display()))?;
     }
+    super::backup::backup_file(&path, super::backup::MAX_BACKUPS)?;
     let s = toml::to_string_pretty(settings).context("failed to serialize settings to TOML")?;
     let mut file = fs::File::create(&path)
         .with_context(|| format!("failed to create settings file {}", path.display()))?;
@@ -88,3 +263,13 @@ pub fn save_settings(settings: &Settings) -> Result<()> {
         .with_context(|| format!("failed to write settings to {}", path.display()))?;
     Ok(())
 }
+
+/// Restore the settings file from its newest backup and return the
+/// restored settings, or `None` when no backup exists.
+pub fn restore_previous_settings() -> Result<Option<Settings>> {
+    let path = config_file_path()?;
+    if super::backup::restore_latest(&path)?.is_none() {
+        return Ok(None);
+    }
+    super::read_settings::load_settings().map(Some)
+}
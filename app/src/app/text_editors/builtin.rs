@@ -0,0 +1,411 @@
+//! Minimal integrated text editor used by `Mode::Editor`.
+//!
+//! The editor keeps the whole file as a vector of lines and supports the
+//! small set of operations needed for quick config edits: insert/overwrite
+//! typing, line splitting/joining, forward search, go-to-line and an atomic
+//! save through `fs_op::helpers::atomic_rewrite`. It holds no terminal state
+//! so it can be unit-tested without a TTY.
+
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Largest file (in bytes) the integrated editor will open.
+pub const MAX_EDITOR_FILE_BYTES: u64 = 1024 * 1024;
+
+/// A single-line prompt shown at the bottom of the editor.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum EditorPrompt {
+    /// Search for the buffered text starting after the cursor.
+    Search(String),
+    /// Jump to the buffered (1-based) line number.
+    GotoLine(String),
+}
+
+/// Buffer, cursor and prompt state for the integrated editor.
+#[derive(Clone, Debug)]
+pub struct EditorState {
+    /// File being edited.
+    pub path: PathBuf,
+    /// File contents split into lines (without line terminators).
+    pub lines: Vec<String>,
+    /// Whether lines end in CRLF, judged by the first line ending.
+    pub crlf: bool,
+    /// Whether the last line ends in a line terminator.
+    pub trailing_newline: bool,
+    /// Cursor row (index into `lines`).
+    pub row: usize,
+    /// Cursor column in characters.
+    pub col: usize,
+    /// Index of the first visible line.
+    pub scroll: usize,
+    /// When true typed characters replace the character under the cursor.
+    pub overwrite: bool,
+    /// Whether the buffer has unsaved changes.
+    pub dirty: bool,
+    /// Active prompt, if any.
+    pub prompt: Option<EditorPrompt>,
+    /// Last search query so it can be repeated.
+    pub last_search: Option<String>,
+    /// Short status text shown in the editor footer.
+    pub status: String,
+    /// Set after the first close request on a dirty buffer; a second
+    /// request discards the changes.
+    pub confirm_discard: bool,
+    /// Set once saving was refused for lack of permission and the file can
+    /// be saved as root instead (see `fs_op::elevated`); F2 then does that.
+    pub save_as_root: bool,
+}
+
+impl EditorState {
+    /// Create an editor for `path` holding `text`.
+    pub fn from_text(path: PathBuf, text: &str) -> Self {
+        let mut lines: Vec<String> = text
+            .split('\n')
+            .map(|l| l.trim_end_matches('\r').to_string())
+            .collect();
+        // A trailing newline produces an empty final element; keep the file
+        // shape stable on save by dropping it and re-adding the newline.
+        let trailing_newline = text.ends_with('\n');
+        if trailing_newline {
+            lines.pop();
+        }
+        let crlf = text.find('\n').is_some_and(|i| text[..i].ends_with('\r'));
+        if lines.is_empty() {
+            lines.push(String::new());
+        }
+        EditorState {
+            path,
+            lines,
+            crlf,
+            trailing_newline,
+            row: 0,
+            col: 0,
+            scroll: 0,
+            overwrite: false,
+            dirty: false,
+            prompt: None,
+            last_search: None,
+            status: String::new(),
+            confirm_discard: false,
+            save_as_root: false,
+        }
+    }
+
+    /// Open `path` for editing. Files larger than `MAX_EDITOR_FILE_BYTES`
+    /// or that are not valid UTF-8 are rejected with `InvalidData`.
+    pub fn open(path: &Path) -> io::Result<Self> {
+        let md = std::fs::metadata(path)?;
+        if md.len() > MAX_EDITOR_FILE_BYTES {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "file too large for the integrated editor",
+            ));
+        }
+        let bytes = std::fs::read(path)?;
+        if crate::app::core::preview_helpers::is_binary(&bytes) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "file appears to be binary",
+            ));
+        }
+        let text =
+            String::from_utf8(bytes).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        Ok(Self::from_text(path.to_path_buf(), &text))
+    }
+
+    /// Return the buffer joined back into file contents, with the line
+    /// endings and final newline the file had.
+    pub fn text(&self) -> String {
+        let eol = if self.crlf { "\r\n" } else { "\n" };
+        let mut s = self.lines.join(eol);
+        if self.trailing_newline {
+            s.push_str(eol);
+        }
+        s
+    }
+
+    /// Atomically write the buffer back to `path`, or to the file it links
+    /// to, keeping the permissions and owner of the file.
+    pub fn save(&mut self) -> io::Result<()> {
+        let target = std::fs::canonicalize(&self.path).unwrap_or_else(|_| self.path.clone());
+        crate::fs_op::helpers::atomic_rewrite(&target, self.text().as_bytes())?;
+        self.mark_saved();
+        Ok(())
+    }
+
+    /// Note that the buffer was written to `path` by other means.
+    pub fn mark_saved(&mut self) {
+        self.dirty = false;
+        self.confirm_discard = false;
+        self.status = format!("Saved {} lines", self.lines.len());
+    }
+
+    fn line_len(&self, row: usize) -> usize {
+        self.lines.get(row).map(|l| l.chars().count()).unwrap_or(0)
+    }
+
+    /// Convert a character column into a byte offset within `line`.
+    fn byte_index(line: &str, col: usize) -> usize {
+        line.char_indices()
+            .nth(col)
+            .map(|(i, _)| i)
+            .unwrap_or(line.len())
+    }
+
+    fn touch(&mut self) {
+        self.dirty = true;
+        self.confirm_discard = false;
+    }
+
+    /// Insert (or overwrite, depending on mode) `c` at the cursor.
+    pub fn insert_char(&mut self, c: char) {
+        let col = self.col;
+        let line = &mut self.lines[self.row];
+        let at = Self::byte_index(line, col);
+        if self.overwrite && at < line.len() {
+            let end = Self::byte_index(line, col + 1);
+            line.replace_range(at..end, c.encode_utf8(&mut [0; 4]));
+        } else {
+            line.insert(at, c);
+        }
+        self.col += 1;
+        self.touch();
+    }
+
+    /// Split the current line at the cursor.
+    pub fn newline(&mut self) {
+        let at = Self::byte_index(&self.lines[self.row], self.col);
+        let rest = self.lines[self.row].split_off(at);
+        self.lines.insert(self.row + 1, rest);
+        self.row += 1;
+        self.col = 0;
+        self.touch();
+    }
+
+    /// Delete the character before the cursor, joining lines at column 0.
+    pub fn backspace(&mut self) {
+        if self.col > 0 {
+            let line = &mut self.lines[self.row];
+            let start = Self::byte_index(line, self.col - 1);
+            let end = Self::byte_index(line, self.col);
+            line.replace_range(start..end, "");
+            self.col -= 1;
+            self.touch();
+        } else if self.row > 0 {
+            let cur = self.lines.remove(self.row);
+            self.row -= 1;
+            self.col = self.line_len(self.row);
+            self.lines[self.row].push_str(&cur);
+            self.touch();
+        }
+    }
+
+    /// Delete the character under the cursor, joining the next line at EOL.
+    pub fn delete(&mut self) {
+        if self.col < self.line_len(self.row) {
+            let line = &mut self.lines[self.row];
+            let start = Self::byte_index(line, self.col);
+            let end = Self::byte_index(line, self.col + 1);
+            line.replace_range(start..end, "");
+            self.touch();
+        } else if self.row + 1 < self.lines.len() {
+            let next = self.lines.remove(self.row + 1);
+            self.lines[self.row].push_str(&next);
+            self.touch();
+        }
+    }
+
+    pub fn move_left(&mut self) {
+        if self.col > 0 {
+            self.col -= 1;
+        } else if self.row > 0 {
+            self.row -= 1;
+            self.col = self.line_len(self.row);
+        }
+    }
+
+    pub fn move_right(&mut self) {
+        if self.col < self.line_len(self.row) {
+            self.col += 1;
+        } else if self.row + 1 < self.lines.len() {
+            self.row += 1;
+            self.col = 0;
+        }
+    }
+
+    pub fn move_up(&mut self, n: usize) {
+        self.row = self.row.saturating_sub(n);
+        self.col = self.col.min(self.line_len(self.row));
+    }
+
+    pub fn move_down(&mut self, n: usize) {
+        self.row = (self.row + n).min(self.lines.len().saturating_sub(1));
+        self.col = self.col.min(self.line_len(self.row));
+    }
+
+    pub fn home(&mut self) {
+        self.col = 0;
+    }
+
+    pub fn end(&mut self) {
+        self.col = self.line_len(self.row);
+    }
+
+    /// Move the cursor to 1-based `line`, clamped to the buffer.
+    pub fn goto_line(&mut self, line: usize) {
+        self.row = line
+            .saturating_sub(1)
+            .min(self.lines.len().saturating_sub(1));
+        self.col = 0;
+    }
+
+    /// Search forward for `query` starting just after the cursor, wrapping
+    /// around at the end of the buffer. Returns `true` when found.
+    pub fn search(&mut self, query: &str) -> bool {
+        self.last_search = Some(query.to_string());
+        if query.is_empty() {
+            return false;
+        }
+        let n = self.lines.len();
+        for step in 0..=n {
+            let row = (self.row + step) % n;
+            let line = &self.lines[row];
+            // On the cursor row only look past the cursor on the first pass;
+            // the wrap-around pass (`step == n`) checks the part before it.
+            let from = if step == 0 {
+                Self::byte_index(line, self.col + 1)
+            } else {
+                0
+            };
+            if let Some(pos) = line.get(from..).and_then(|s| s.find(query)) {
+                let byte = from + pos;
+                self.row = row;
+                self.col = line[..byte].chars().count();
+                self.status = format!("Found \"{}\"", query);
+                return true;
+            }
+        }
+        self.status = format!("\"{}\" not found", query);
+        false
+    }
+
+    /// Adjust `scroll` so the cursor row is inside a viewport of `height`.
+    pub fn ensure_cursor_visible(&mut self, height: usize) {
+        if height == 0 {
+            return;
+        }
+        if self.row < self.scroll {
+            self.scroll = self.row;
+        } else if self.row >= self.scroll + height {
+            self.scroll = self.row + 1 - height;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ed(text: &str) -> EditorState {
+        EditorState::from_text(PathBuf::from("/tmp/x.txt"), text)
+    }
+
+    #[test]
+    fn insert_overwrite_and_newline() {
+        let mut e = ed("abc\n");
+        e.col = 1;
+        e.insert_char('X');
+        assert_eq!(e.lines[0], "aXbc");
+        e.overwrite = true;
+        e.insert_char('Y');
+        assert_eq!(e.lines[0], "aXYc");
+        e.newline();
+        assert_eq!(e.lines, vec!["aXY".to_string(), "c".to_string()]);
+        assert!(e.dirty);
+        assert_eq!(e.text(), "aXY\nc\n");
+    }
+
+    #[test]
+    fn backspace_and_delete_join_lines() {
+        let mut e = ed("ab\ncd");
+        e.row = 1;
+        e.backspace();
+        assert_eq!(e.lines, vec!["abcd".to_string()]);
+        assert_eq!(e.col, 2);
+        e.end();
+        e.delete();
+        assert_eq!(e.lines, vec!["abcd".to_string()]);
+        e.home();
+        e.delete();
+        assert_eq!(e.lines[0], "bcd");
+    }
+
+    #[test]
+    fn search_wraps_and_goto_line_clamps() {
+        let mut e = ed("foo\nbar\nfoo bar\n");
+        assert!(e.search("bar"));
+        assert_eq!((e.row, e.col), (1, 0));
+        assert!(e.search("bar"));
+        assert_eq!((e.row, e.col), (2, 4));
+        assert!(e.search("foo"));
+        assert_eq!((e.row, e.col), (0, 0));
+        assert!(!e.search("missing"));
+        e.goto_line(99);
+        assert_eq!(e.row, 2);
+        e.goto_line(0);
+        assert_eq!(e.row, 0);
+    }
+
+    #[test]
+    fn save_writes_atomically() {
+        let dir = tempfile::tempdir().unwrap();
+        let p = dir.path().join("cfg.toml");
+        std::fs::write(&p, "a = 1\n").unwrap();
+        let mut e = EditorState::open(&p).unwrap();
+        e.end();
+        e.insert_char('0');
+        e.save().unwrap();
+        assert_eq!(std::fs::read_to_string(&p).unwrap(), "a = 10\n");
+        assert!(!e.dirty);
+    }
+
+    #[test]
+    fn keeps_line_endings_and_the_final_newline() {
+        let mut e = ed("a\r\nb\r\n");
+        assert_eq!(e.lines, vec!["a".to_string(), "b".to_string()]);
+        e.end();
+        e.newline();
+        e.insert_char('c');
+        assert_eq!(e.text(), "a\r\nc\r\nb\r\n");
+        assert_eq!(ed("a\nb").text(), "a\nb");
+        assert_eq!(ed("a\r\nb").text(), "a\r\nb");
+        assert_eq!(ed("").text(), "");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn save_keeps_the_mode_and_writes_through_symlinks() {
+        use std::os::unix::fs::PermissionsExt;
+        let dir = tempfile::tempdir().unwrap();
+        let p = dir.path().join("run.sh");
+        std::fs::write(&p, "echo hi\n").unwrap();
+        std::fs::set_permissions(&p, std::fs::Permissions::from_mode(0o750)).unwrap();
+        let link = dir.path().join("link.sh");
+        std::os::unix::fs::symlink("run.sh", &link).unwrap();
+
+        let mut e = EditorState::open(&link).unwrap();
+        e.insert_char('#');
+        e.save().unwrap();
+        assert!(std::fs::symlink_metadata(&link).unwrap().file_type().is_symlink());
+        assert_eq!(std::fs::read_to_string(&p).unwrap(), "#echo hi\n");
+        assert_eq!(std::fs::metadata(&p).unwrap().permissions().mode() & 0o777, 0o750);
+    }
+
+    #[test]
+    fn open_rejects_binary() {
+        let dir = tempfile::tempdir().unwrap();
+        let p = dir.path().join("bin");
+        std::fs::write(&p, [0u8, 1, 2]).unwrap();
+        assert!(EditorState::open(&p).is_err());
+    }
+}
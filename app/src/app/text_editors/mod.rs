@@ -1,4 +1,6 @@
+pub mod builtin;
 pub mod vim_support;
 
 // Add more editors here (e.g. nano_support) and re-export helpers as needed.
+pub use builtin::EditorState;
 pub use vim_support::spawn_vim;
@@ -1,39 +1,16 @@
-use std::io::{self, stdout};
+use std::io;
 use std::path::Path;
 use std::process::Command;
 
-use crossterm::cursor::{Show, Hide};
-use crossterm::execute;
-use crossterm::event::{DisableMouseCapture, EnableMouseCapture};
-use crossterm::terminal::{disable_raw_mode, enable_raw_mode, LeaveAlternateScreen, EnterAlternateScreen};
-
 /// Spawn `vim` on the given `path`, suspending the TUI (restoring the terminal)
 /// and re-entering TUI mode after the editor exits.
 ///
 /// This function is conservative: it attempts to restore the terminal state
 /// even if launching the editor fails.
 pub fn spawn_vim<P: AsRef<Path>>(path: P) -> io::Result<()> {
-	// Disable raw mode and leave the alternate screen so the spawned editor
-	// can take full control of the terminal with normal line buffering.
-	// Best-effort restore of terminal state; propagate errors directly.
-	disable_raw_mode()?;
-
-	let mut stdout = stdout();
-	// Leave alternate screen, disable mouse capture and show cursor
-	let _ = execute!(stdout, LeaveAlternateScreen, DisableMouseCapture, Show);
-
 	// Run the editor synchronously. If `vim` isn't available, the error will
-	// be returned to the caller after we try to restore terminal state.
-	let status = Command::new("vim").arg(path.as_ref()).status();
-
-	// After the editor exits (or fails to spawn), try to re-enter the TUI
-	// environment: hide cursor, enable mouse capture and enter alternate
-	// screen, then enable raw mode.
-	let _ = execute!(stdout, Hide, EnableMouseCapture, EnterAlternateScreen);
-	if let Err(e) = enable_raw_mode() {
-	// Return original spawn error if present, otherwise this one.
-	return status.and(Err(e));
-	}
+	// be returned to the caller after the terminal state is restored.
+	let status = crate::runner::terminal::outside_tui(|| Command::new("vim").arg(path.as_ref()).status());
 
 	// Propagate the editor process status (map to io::Error when appropriate).
 	match status {
@@ -1,4 +1,6 @@
 use chrono::{DateTime, Local};
+use std::borrow::Cow;
+use std::ffi::OsString;
 use std::fmt;
 use std::path::PathBuf;
 
@@ -6,19 +8,23 @@ use std::path::PathBuf;
 ///
 /// This is a lightweight representation used by the UI layer; it intentionally
 /// stores a `PathBuf` and a precomputed `name` to avoid repeated allocations
-/// while rendering.
+/// while rendering. Both keep the exact bytes from the filesystem, so names
+/// that are not valid UTF-8 can still be copied, renamed and deleted; they
+/// are only converted (lossily) for display, via `display_name`.
 ///
 /// This is a domain-only type. Presentation concerns (headers, parent
 /// rows, and synthetic UI rows) are owned by the UI module.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Entry {
-    /// Display name for the entry (filename or `..` or the full path in the
-    /// case of the header row).
-    pub name: String,
+    /// File name of the entry, exactly as stored on disk.
+    pub name: OsString,
     /// Full path to the entry.
     pub path: PathBuf,
     /// Whether the entry is a directory. Header rows are not directories.
     pub is_dir: bool,
+    /// Whether the entry is a symbolic link (its own metadata, not the
+    /// target's, fills the other fields).
+    pub is_symlink: bool,
     /// File size in bytes. Directories typically have `0` here.
     pub size: u64,
     /// Optional last-modified timestamp.
@@ -39,12 +45,14 @@ pub struct Entry {
     pub owner: Option<String>,
     /// Optional human-readable group name (best-effort lookup from GID).
     pub group: Option<String>,
+    /// Windows file attributes (read-only, hidden, ...); `None` elsewhere.
+    pub attributes: Option<crate::fs_op::attributes::FileAttributes>,
 }
 
 impl Entry {
     /// Construct a regular file entry.
     pub fn file(
-        name: impl Into<String>,
+        name: impl Into<OsString>,
         path: PathBuf,
         size: u64,
         modified: Option<DateTime<Local>>,
@@ -53,6 +61,7 @@ impl Entry {
             name: name.into(),
             path,
             is_dir: false,
+            is_symlink: false,
             size,
             modified,
             unix_mode: None,
@@ -63,12 +72,13 @@ impl Entry {
             can_execute: None,
             owner: None,
             group: None,
+            attributes: None,
         }
     }
 
     /// Construct a regular directory entry.
     pub fn directory(
-        name: impl Into<String>,
+        name: impl Into<OsString>,
         path: PathBuf,
         modified: Option<DateTime<Local>>,
     ) -> Self {
@@ -76,6 +86,7 @@ impl Entry {
             name: name.into(),
             path,
             is_dir: true,
+            is_symlink: false,
             size: 0,
             modified,
             unix_mode: None,
@@ -86,10 +97,17 @@ impl Entry {
             can_execute: None,
             owner: None,
             group: None,
+            attributes: None,
         }
     }
 
-    // Header/parent are UI concerns implemented in `ui::panels::UiEntry`.
+    /// The name for display; bytes that are not valid UTF-8 show as `�`.
+    pub fn display_name(&self) -> Cow<'_, str> {
+        self.name.to_string_lossy()
+    }
+
+    // The parent row is a UI concern implemented in `ui::panels::UiEntry`;
+    // the path is drawn as breadcrumbs (`ui::widgets::breadcrumbs`).
 
     // NOTE: UI-only helpers like `is_header` and `is_parent` were intentionally
     // moved into the UI layer. This keeps `Entry` as a domain struct and
@@ -126,12 +144,87 @@ impl SortKey {
 
 // Default derived via `#[default]` on the `Name` variant.
 
+/// Arrangement of the two panels in the main area.
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default)]
+pub enum PanelLayout {
+    /// Side by side (the default).
+    #[default]
+    Horizontal,
+    /// Left panel above the right one.
+    Vertical,
+    /// Only the active panel, at full size.
+    Single,
+    /// The active panel narrow, next to a large preview of its selection.
+    PreviewDominant,
+}
+
+impl PanelLayout {
+    /// Cycle Horizontal -> Vertical -> Single -> PreviewDominant -> Horizontal.
+    pub fn next(self) -> Self {
+        match self {
+            PanelLayout::Horizontal => PanelLayout::Vertical,
+            PanelLayout::Vertical => PanelLayout::Single,
+            PanelLayout::Single => PanelLayout::PreviewDominant,
+            PanelLayout::PreviewDominant => PanelLayout::Horizontal,
+        }
+    }
+
+    /// Name used in the session file and the status message.
+    pub fn name(self) -> &'static str {
+        match self {
+            PanelLayout::Horizontal => "horizontal",
+            PanelLayout::Vertical => "vertical",
+            PanelLayout::Single => "single",
+            PanelLayout::PreviewDominant => "preview",
+        }
+    }
+
+    /// Inverse of `name`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        [PanelLayout::Horizontal, PanelLayout::Vertical, PanelLayout::Single, PanelLayout::PreviewDominant]
+            .into_iter()
+            .find(|l| l.name() == name)
+    }
+}
+
+/// What the inactive panel shows in the two-panel layouts.
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default)]
+pub enum OtherPanel {
+    /// Its own directory listing (the default).
+    #[default]
+    Listing,
+    /// A preview of the active panel's selection (Ctrl+Q).
+    QuickView,
+    /// Extended details of the active panel's selection (`i`).
+    Info,
+}
+
+/// Scrollbar being dragged with the mouse.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum ScrollTarget {
+    /// The listing of a panel.
+    Panel(Side),
+    /// The preview of the active panel's selection.
+    Preview,
+}
+
 /// Mode represents the global UI mode/state the application may be in.
 ///
 /// - `Normal` is the default browsing mode.
 /// - `Confirm` is used for yes/no prompts (for example, delete).
 /// - `Message` displays an information dialog with buttons.
 /// - `Input` requests textual input from the user.
+/// - `CommandOutput` shows the captured output of a command-line command.
+/// - `Editor` shows the integrated text editor over the panels.
+/// - `UserMenu` lists the user-defined commands from `user_menu.toml`.
+/// - `MountPicker` lists the mounted filesystems to switch to.
+/// - `EncodingPicker` chooses the encoding the preview is decoded with.
+/// - `SearchPicker` lists the saved and recent searches to run again.
+/// - `LogView` shows the end of the log file.
+/// - `OperationError` offers Retry, Skip and Abort after a recoverable error.
+/// - `Jobs` lists the background and queued copies and moves.
+/// - `Summary` sums up a copy, move or delete that ended.
+/// - `DiskUsage` is the disk usage analyzer.
 #[derive(Clone, Debug, Default)]
 pub enum Mode {
     #[default]
@@ -153,31 +246,132 @@ pub enum Mode {
     },
     /// Settings dialog allowing toggling mouse and editing numeric timeout.
     Settings { selected: usize },
+    /// "Keys" page of the Settings dialog (Tab switches between the pages).
+    /// Boxed to keep `Mode` small since it holds a copy of the key table.
+    KeymapEditor {
+        editor: Box<crate::app::settings::keymap_editor::KeymapEditor>,
+    },
     Progress {
         title: String,
         processed: usize,
         total: usize,
         message: String,
         cancelled: bool,
+        /// Files checked and to check by the verification pass after a
+        /// copy; `None` when the operation is not verified.
+        verify: Option<(usize, usize)>,
     },
     Conflict {
         path: std::path::PathBuf,
         selected: usize,
         apply_all: bool,
+        /// Warning text when the conflicting target is open in another
+        /// process (see `fs_op::open_files`).
+        in_use: Option<String>,
+        /// Metadata of the item being copied/moved, when known.
+        src: Option<Box<Entry>>,
+        /// Metadata of the existing target at `path`.
+        dst: Option<Box<Entry>>,
+        /// Outcome of comparing the contents of both files, once asked for
+        /// with `d` (see `fs_op::compare`).
+        compare: Option<String>,
     },
     /// Context menu shown for a selected entry. `options` are the action
     /// labels (e.g. View, Edit, Permissions). `path` is the target entry.
+    /// `at` is the screen cell of the right-click that opened the menu, which
+    /// it is drawn next to; menus opened from the keyboard are centred.
     ContextMenu {
         title: String,
         options: Vec<String>,
         selected: usize,
         path: std::path::PathBuf,
+        at: Option<(u16, u16)>,
     },
     Input {
         prompt: String,
         buffer: String,
         kind: InputKind,
     },
+    /// Scrollable results view for a shell command run from the command
+    /// line. `status` is the exit code (`None` when killed by a signal);
+    /// while `running` the command is `App::shell`.
+    CommandOutput {
+        command: String,
+        lines: Vec<String>,
+        scroll: usize,
+        status: Option<i32>,
+        running: bool,
+    },
+    /// Integrated text editor for small files (opened with F4). Boxed to
+    /// keep `Mode` small since the editor owns the whole buffer.
+    Editor {
+        editor: Box<crate::app::text_editors::builtin::EditorState>,
+    },
+    /// User-defined command menu (opened with F2).
+    UserMenu {
+        entries: Vec<crate::app::settings::user_menu::UserMenuEntry>,
+        selected: usize,
+    },
+    /// Theme picker listing the built-in and user themes. The selected
+    /// theme is previewed live; `original` is restored on Esc.
+    ThemePicker {
+        themes: Vec<String>,
+        selected: usize,
+        original: String,
+        /// Error from loading the selected theme, shown below the list.
+        error: Option<String>,
+    },
+    /// Mounted filesystems (drives on Windows) with their free space;
+    /// Enter switches the active panel to the selected one's root.
+    MountPicker {
+        mounts: Vec<crate::fs_op::mounts::Mount>,
+        selected: usize,
+    },
+    /// "Auto" and `encoding::CHOICES`; Enter decodes the active panel's
+    /// preview with the selected one (`selected` 0 is "Auto").
+    EncodingPicker {
+        selected: usize,
+    },
+    /// `App::searches` as listed by `Searches::entries`; Enter runs the
+    /// selected one.
+    SearchPicker {
+        selected: usize,
+    },
+    /// A file operation failed with a recoverable error (see
+    /// `fs_op::error::is_recoverable`) on `path`, when known. With `retry`
+    /// the failed action is run again on Retry; without, the running
+    /// background job waits for Retry, Skip or Abort. `elevated` is the
+    /// failed operation as the privileged helper runs it, offered as
+    /// "Retry as root" after permission was denied.
+    OperationError {
+        path: Option<std::path::PathBuf>,
+        error: String,
+        retry: Option<Action>,
+        elevated: Option<crate::fs_op::elevated::ElevatedOp>,
+        selected: usize,
+    },
+    /// The last lines of the newest log file (`path`, `None` when nothing
+    /// was logged yet). `scroll` counts the lines scrolled up from the end.
+    LogView {
+        path: Option<std::path::PathBuf>,
+        lines: Vec<String>,
+        scroll: usize,
+    },
+    /// Copies and moves running in the background or queued (see
+    /// `runner::jobs`); `selected` indexes `Scheduler::jobs`.
+    Jobs { selected: usize },
+    /// What a copy, move or delete came to: its counts, bytes, time and
+    /// failures. `note` is its closing message or error, `scroll` the first
+    /// failure listed.
+    Summary {
+        title: String,
+        note: Option<String>,
+        summary: Box<crate::runner::progress::OperationSummary>,
+        scroll: usize,
+    },
+    /// The disk usage analyzer over `App::usage`; `selected` indexes its
+    /// entries. `confirm_delete` asks whether to delete the selected one.
+    DiskUsage { selected: usize, confirm_delete: bool },
 }
 
 // Default for Mode is derived via `#[default]` on the `Normal` variant.
@@ -192,6 +386,14 @@ pub enum InputKind {
     NewFile,
     NewDir,
     ChangePath,
+    /// New access and modification times for the selection.
+    ChangeTimes,
+    /// New owner and group (`[-R ]user[:group]`) for the selection.
+    ChangeOwner,
+    /// Name or glob to search for below the active panel's directory.
+    Search,
+    /// Ages and sizes of the files to mark (see `fs_op::criteria`).
+    SelectCriteria,
 }
 
 /// Actions represent high-level user requests executed by the runner.
@@ -201,22 +403,39 @@ pub enum InputKind {
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum Action {
     DeleteSelected,
+    /// Delete the marked entries of the active panel and show what it came
+    /// to (`Mode::Summary`).
+    DeleteMarked,
+    /// Delete the selected symlink and the directory it points to.
+    DeleteLinkTarget,
     CopyTo(PathBuf),
     MoveTo(PathBuf),
     RenameTo(String),
     NewFile(String),
     NewDir(String),
+    /// Open the log viewer, e.g. from the error dialog of a failed job.
+    ShowLog,
+    /// Move the marked entries, or the selected one, into a directory in
+    /// the background, like F6.
+    MoveMarked(PathBuf),
+    /// Quit fileZoom; confirmed when jobs are still running.
+    Quit,
 }
 
 impl fmt::Display for Action {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Action::DeleteSelected => write!(f, "DeleteSelected"),
+            Action::DeleteMarked => write!(f, "DeleteMarked"),
+            Action::DeleteLinkTarget => write!(f, "DeleteLinkTarget"),
             Action::CopyTo(p) => write!(f, "CopyTo({})", p.display()),
             Action::MoveTo(p) => write!(f, "MoveTo({})", p.display()),
             Action::RenameTo(name) => write!(f, "RenameTo({})", name),
             Action::NewFile(name) => write!(f, "NewFile({})", name),
             Action::NewDir(name) => write!(f, "NewDir({})", name),
+            Action::ShowLog => write!(f, "ShowLog"),
+            Action::MoveMarked(p) => write!(f, "MoveMarked({})", p.display()),
+            Action::Quit => write!(f, "Quit"),
         }
     }
 }
@@ -0,0 +1,63 @@
+//! Run commands in the fakefs image against a tiny tmpfs.
+//!
+//! Docker's `--tmpfs` mount takes a size limit, which gives a real
+//! filesystem that fills up after a few KiB. `run_with_small_tmpfs` mounts
+//! one at `SMALL_MOUNT`, bind-mounts a host directory read-only at
+//! `SOURCE_MOUNT` and runs a shell script, so out-of-space handling can be
+//! tested end-to-end (for example with `fileZoom --batch`). Locally the
+//! `test-helpers` feature simulates the same error instead.
+
+use std::path::Path;
+use std::process::{Command, Output};
+
+use anyhow::{Context, Result};
+
+/// Image built by `make_fakefs build`.
+pub const IMAGE: &str = "filezoom-fakefs";
+
+/// Where the size-limited tmpfs is mounted in the container.
+pub const SMALL_MOUNT: &str = "/small";
+
+/// Where the host source directory is mounted in the container.
+pub const SOURCE_MOUNT: &str = "/src";
+
+/// Arguments for `docker run` running `script` with a `size_kib` tmpfs.
+pub fn docker_run_args(size_kib: u64, source: &Path, script: &str) -> Vec<String> {
+    vec![
+        "run".into(),
+        "--rm".into(),
+        "--tmpfs".into(),
+        format!("{}:rw,size={}k", SMALL_MOUNT, size_kib),
+        "-v".into(),
+        format!("{}:{}:ro", source.display(), SOURCE_MOUNT),
+        IMAGE.into(),
+        "sh".into(),
+        "-c".into(),
+        script.into(),
+    ]
+}
+
+/// Run `script` in the fakefs image with a `size_kib` tmpfs at
+/// `SMALL_MOUNT` and `source` at `SOURCE_MOUNT`, returning its output.
+pub fn run_with_small_tmpfs(size_kib: u64, source: &Path, script: &str) -> Result<Output> {
+    let source = source
+        .canonicalize()
+        .with_context(|| format!("source dir {} not found", source.display()))?;
+    Command::new("docker")
+        .args(docker_run_args(size_kib, &source, script))
+        .output()
+        .context("failed to run docker")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tmpfs_is_size_limited_and_source_read_only() {
+        let args = docker_run_args(512, Path::new("/tmp/src"), "true");
+        assert!(args.windows(2).any(|w| w == ["--tmpfs", "/small:rw,size=512k"]));
+        assert!(args.windows(2).any(|w| w == ["-v", "/tmp/src:/src:ro"]));
+        assert_eq!(args[args.len() - 4..], ["filezoom-fakefs", "sh", "-c", "true"]);
+    }
+}
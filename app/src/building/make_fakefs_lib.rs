@@ -192,6 +192,7 @@ RUN apt-get update \
 WORKDIR /work
 COPY --from=builder /work /work
 RUN if [ -x /work/app/scripts/apply_permissions_in_image.sh ]; then /bin/bash /work/app/scripts/apply_permissions_in_image.sh; fi
+RUN if [ -x /work/target/release/make_fakefs ] && [ -d /work/tests/fixtures ]; then /work/target/release/make_fakefs permission-scenarios /work/tests/fixtures; fi
 WORKDIR /work/app
 RUN chmod +x scripts/*.sh || true
 CMD ["/work/target/release/fileZoom"]
@@ -330,6 +331,7 @@ RUN apt-get update \
 WORKDIR /work
 COPY --from=builder /work /work
 RUN if [ -x /work/app/scripts/apply_permissions_in_image.sh ]; then /bin/bash /work/app/scripts/apply_permissions_in_image.sh; fi
+RUN if [ -x /work/target/release/make_fakefs ] && [ -d /work/tests/fixtures ]; then /work/target/release/make_fakefs permission-scenarios /work/tests/fixtures; fi
 WORKDIR /work/app
 RUN chmod +x scripts/*.sh || true
 CMD ["/work/target/release/fileZoom"]
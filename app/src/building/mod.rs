@@ -4,3 +4,5 @@
 //! previously embedded in the binary source.
 
 pub mod make_fakefs_lib;
+pub mod remote_servers;
+pub mod disk_full;
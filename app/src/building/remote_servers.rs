@@ -0,0 +1,227 @@
+//! Throwaway SFTP/FTP/WebDAV servers for remote-backend integration tests.
+//!
+//! `start_server` runs a small off-the-shelf server image in Docker with a
+//! fixtures directory mounted read-only as the test user's home, so remote
+//! backends can be exercised against the same generated tree as the local
+//! tests. The container is removed when the returned `RemoteServer` is
+//! dropped (or kept running with `detach`, as `make_fakefs serve` does).
+//! All servers accept the `TEST_USER`/`TEST_PASSWORD` login.
+
+use std::net::{TcpStream, ToSocketAddrs};
+use std::path::Path;
+use std::process::Command;
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result};
+
+/// Login accepted by every test server.
+pub const TEST_USER: &str = "fz";
+pub const TEST_PASSWORD: &str = "fz";
+
+/// Passive-mode data ports published by the FTP server.
+const FTP_PASSIVE_PORTS: (u16, u16) = (21000, 21010);
+
+/// Remote protocols a test server can be started for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RemoteProtocol {
+    Sftp,
+    Ftp,
+    WebDav,
+}
+
+impl RemoteProtocol {
+    pub const ALL: [RemoteProtocol; 3] = [RemoteProtocol::Sftp, RemoteProtocol::Ftp, RemoteProtocol::WebDav];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            RemoteProtocol::Sftp => "sftp",
+            RemoteProtocol::Ftp => "ftp",
+            RemoteProtocol::WebDav => "webdav",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|p| p.name().eq_ignore_ascii_case(name))
+    }
+
+    /// Host port used when the caller does not pick one.
+    pub fn default_port(self) -> u16 {
+        match self {
+            RemoteProtocol::Sftp => 2222,
+            RemoteProtocol::Ftp => 2121,
+            RemoteProtocol::WebDav => 8080,
+        }
+    }
+
+    fn image(self) -> &'static str {
+        match self {
+            RemoteProtocol::Sftp => "atmoz/sftp",
+            RemoteProtocol::Ftp => "delfer/alpine-ftp-server",
+            RemoteProtocol::WebDav => "bytemark/webdav",
+        }
+    }
+
+    fn container_port(self) -> u16 {
+        match self {
+            RemoteProtocol::Sftp => 22,
+            RemoteProtocol::Ftp => 21,
+            RemoteProtocol::WebDav => 80,
+        }
+    }
+
+    /// Where the fixtures appear inside the container.
+    fn mount_point(self) -> String {
+        match self {
+            RemoteProtocol::Sftp => format!("/home/{}/fixtures", TEST_USER),
+            RemoteProtocol::Ftp => format!("/ftp/{}", TEST_USER),
+            RemoteProtocol::WebDav => "/var/lib/dav/data".to_string(),
+        }
+    }
+
+    /// URL a client uses to reach a server published on `port`.
+    pub fn url(self, port: u16) -> String {
+        let path = match self {
+            RemoteProtocol::Sftp => "/fixtures",
+            RemoteProtocol::Ftp | RemoteProtocol::WebDav => "/",
+        };
+        let scheme = match self {
+            RemoteProtocol::WebDav => "http",
+            other => other.name(),
+        };
+        format!("{}://{}:{}@localhost:{}{}", scheme, TEST_USER, TEST_PASSWORD, port, path)
+    }
+
+    /// Arguments for `docker run` serving `fixtures` on host `port`.
+    pub fn docker_run_args(self, container: &str, fixtures: &Path, port: u16) -> Vec<String> {
+        let mut args: Vec<String> = vec![
+            "run".into(),
+            "-d".into(),
+            "--rm".into(),
+            "--name".into(),
+            container.into(),
+            "-p".into(),
+            format!("{}:{}", port, self.container_port()),
+            "-v".into(),
+            format!("{}:{}:ro", fixtures.display(), self.mount_point()),
+        ];
+        match self {
+            RemoteProtocol::Sftp => {
+                args.push(self.image().into());
+                args.push(format!("{}:{}:::upload", TEST_USER, TEST_PASSWORD));
+            }
+            RemoteProtocol::Ftp => {
+                let (lo, hi) = FTP_PASSIVE_PORTS;
+                args.extend([
+                    "-p".into(),
+                    format!("{lo}-{hi}:{lo}-{hi}"),
+                    "-e".into(),
+                    format!("USERS={}|{}|/ftp/{}", TEST_USER, TEST_PASSWORD, TEST_USER),
+                    "-e".into(),
+                    "ADDRESS=localhost".into(),
+                    "-e".into(),
+                    format!("MIN_PORT={lo}"),
+                    "-e".into(),
+                    format!("MAX_PORT={hi}"),
+                ]);
+                args.push(self.image().into());
+            }
+            RemoteProtocol::WebDav => {
+                args.extend([
+                    "-e".into(),
+                    "AUTH_TYPE=Basic".into(),
+                    "-e".into(),
+                    format!("USERNAME={}", TEST_USER),
+                    "-e".into(),
+                    format!("PASSWORD={}", TEST_PASSWORD),
+                ]);
+                args.push(self.image().into());
+            }
+        }
+        args
+    }
+}
+
+/// A running test server. Dropping it removes the container.
+#[derive(Debug)]
+pub struct RemoteServer {
+    pub protocol: RemoteProtocol,
+    /// Docker container name.
+    pub container: String,
+    /// Host port the server is published on.
+    pub port: u16,
+    detached: bool,
+}
+
+/// Start a `protocol` server in Docker serving `fixtures` on host `port`.
+pub fn start_server(protocol: RemoteProtocol, fixtures: &Path, port: u16) -> Result<RemoteServer> {
+    let fixtures = fixtures
+        .canonicalize()
+        .with_context(|| format!("fixtures dir {} not found", fixtures.display()))?;
+    let container = format!("filezoom-{}-{}-{}", protocol.name(), std::process::id(), port);
+    let status = Command::new("docker")
+        .args(protocol.docker_run_args(&container, &fixtures, port))
+        .status()
+        .context("failed to run docker")?;
+    if !status.success() {
+        return Err(anyhow::anyhow!("docker run for the {} server failed", protocol.name()));
+    }
+    Ok(RemoteServer { protocol, container, port, detached: false })
+}
+
+impl RemoteServer {
+    /// URL including the test login.
+    pub fn url(&self) -> String {
+        self.protocol.url(self.port)
+    }
+
+    /// Wait until the server accepts TCP connections.
+    pub fn wait_ready(&self, timeout: Duration) -> Result<()> {
+        let addr = ("localhost", self.port)
+            .to_socket_addrs()?
+            .next()
+            .context("localhost did not resolve")?;
+        let deadline = Instant::now() + timeout;
+        loop {
+            if TcpStream::connect_timeout(&addr, Duration::from_millis(200)).is_ok() {
+                return Ok(());
+            }
+            if Instant::now() >= deadline {
+                return Err(anyhow::anyhow!("{} server on port {} did not start", self.protocol.name(), self.port));
+            }
+            std::thread::sleep(Duration::from_millis(200));
+        }
+    }
+
+    /// Keep the container running after this handle is dropped and return
+    /// its name (remove it with `docker rm -f NAME`).
+    pub fn detach(mut self) -> String {
+        self.detached = true;
+        self.container.clone()
+    }
+}
+
+impl Drop for RemoteServer {
+    fn drop(&mut self) {
+        if !self.detached {
+            let _ = Command::new("docker").args(["rm", "-f", &self.container]).status();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn docker_args_mount_fixtures_and_publish_the_port() {
+        let args = RemoteProtocol::Sftp.docker_run_args("c", Path::new("/tmp/fx"), 2222);
+        assert!(args.windows(2).any(|w| w == ["-p", "2222:22"]));
+        assert!(args.windows(2).any(|w| w == ["-v", "/tmp/fx:/home/fz/fixtures:ro"]));
+        assert_eq!(args.last().unwrap(), "fz:fz:::upload");
+
+        let args = RemoteProtocol::WebDav.docker_run_args("c", Path::new("/tmp/fx"), 8080);
+        assert_eq!(args.last().unwrap(), "bytemark/webdav");
+        assert_eq!(RemoteProtocol::from_name("WebDAV"), Some(RemoteProtocol::WebDav));
+        assert_eq!(RemoteProtocol::Ftp.url(2121), "ftp://fz:fz@localhost:2121/");
+    }
+}
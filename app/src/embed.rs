@@ -0,0 +1,288 @@
+//! Embedding API: the file-browsing engine behind a small, stable facade.
+//!
+//! `FileBrowser` owns an `App` and exposes what another ratatui
+//! application needs to host fileZoom's two panels: navigation, listing,
+//! selection, file operations, key input and drawing. State changes are
+//! published as `BrowserEvent`s to every receiver returned by `subscribe`,
+//! so the host can react (update a status line, open a file) without
+//! polling the panels. The fileZoom runner is one such host; it drives the
+//! same `App` through `runner::handlers` and `ui::ui`.
+//!
+//! ```no_run
+//! use fileZoom::embed::{BrowserEvent, FileBrowser};
+//! use fileZoom::Side;
+//!
+//! let mut browser = FileBrowser::open("/tmp")?;
+//! let events = browser.subscribe();
+//! browser.navigate(Side::Left, "/var/log")?;
+//! for event in events.try_iter() {
+//!     if let BrowserEvent::DirectoryChanged { side, dir } = event {
+//!         println!("{:?} panel now shows {}", side, dir.display());
+//!     }
+//! }
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+//!
+//! Items not reachable from this module (`App` fields, handlers, widgets)
+//! are internal and may change between releases; `app` and `app_mut`
+//! give access to them when the facade is not enough.
+
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+
+use ratatui::Frame;
+
+use crate::app::core::listing::{self, ListingChanges};
+use crate::app::{App, Entry, Side, StartOptions};
+use crate::fs_op::error::FsOpError;
+use crate::input::KeyCode;
+
+/// A change reported to subscribers.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum BrowserEvent {
+    /// The panel on `side` now lists `dir`.
+    DirectoryChanged { side: Side, dir: PathBuf },
+    /// The listing of the same directory changed (after an operation or
+    /// a refresh).
+    ListingChanged { side: Side, changes: ListingChanges },
+    /// The cursor of the panel on `side` moved; `None` when it is on the
+    /// header or `..` row.
+    SelectionChanged { side: Side, path: Option<PathBuf> },
+    /// The active panel switched to `side`.
+    ActivePanelChanged { side: Side },
+}
+
+/// What is compared before and after each call to find the events.
+struct Snapshot {
+    active: Side,
+    panels: [(PathBuf, Option<PathBuf>, Vec<Entry>); 2],
+}
+
+/// fileZoom's two-panel file browser for embedding in other applications.
+pub struct FileBrowser {
+    app: App,
+    subscribers: Vec<mpsc::Sender<BrowserEvent>>,
+}
+
+impl FileBrowser {
+    /// Open both panels on `dir`.
+    pub fn open(dir: impl Into<PathBuf>) -> io::Result<Self> {
+        Self::with_options(&StartOptions { start_dir: Some(dir.into()), ..Default::default() })
+    }
+
+    /// Start with the same options as the command line (directories,
+    /// hidden files, single pane...).
+    pub fn with_options(opts: &StartOptions) -> io::Result<Self> {
+        Ok(FileBrowser { app: App::with_options(opts)?, subscribers: Vec::new() })
+    }
+
+    /// Receive every event from now on. Dropping the receiver unsubscribes.
+    pub fn subscribe(&mut self) -> mpsc::Receiver<BrowserEvent> {
+        let (tx, rx) = mpsc::channel();
+        self.subscribers.push(tx);
+        rx
+    }
+
+    /// The underlying application state (unstable API).
+    pub fn app(&self) -> &App {
+        &self.app
+    }
+
+    /// Mutable access to the underlying application state (unstable API).
+    /// Changes made here are not reported to subscribers.
+    pub fn app_mut(&mut self) -> &mut App {
+        &mut self.app
+    }
+
+    // ----- Listing -----
+
+    /// Directory shown by the panel on `side`.
+    pub fn cwd(&self, side: Side) -> &Path {
+        &self.panel(side).cwd
+    }
+
+    /// Entries of the panel on `side`, sorted and filtered as displayed.
+    pub fn entries(&self, side: Side) -> &[Entry] {
+        &self.panel(side).entries
+    }
+
+    /// The panel keys and operations act on.
+    pub fn active(&self) -> Side {
+        self.app.active
+    }
+
+    /// Make `side` the active panel.
+    pub fn set_active(&mut self, side: Side) {
+        self.track(|app| {
+            app.active = side;
+            Ok::<(), FsOpError>(())
+        })
+        .unwrap_or(())
+    }
+
+    /// List both panels again.
+    pub fn refresh(&mut self) -> io::Result<()> {
+        self.track(|app| app.refresh())
+    }
+
+    // ----- Navigation -----
+
+    /// Show `dir` in the panel on `side`.
+    pub fn navigate(&mut self, side: Side, dir: impl Into<PathBuf>) -> io::Result<()> {
+        let dir = dir.into();
+        self.track(|app| {
+            let panel = app.panel_mut(side);
+            let previous = std::mem::replace(&mut panel.cwd, dir);
+            app.refresh_side(side).map(|_| ()).inspect_err(|_| app.panel_mut(side).cwd = previous)
+        })
+    }
+
+    /// Enter the selected directory of the active panel.
+    pub fn enter_selected(&mut self) -> Result<(), FsOpError> {
+        self.track(|app| app.enter())
+    }
+
+    /// Go to the parent directory of the active panel.
+    pub fn go_up(&mut self) -> Result<(), FsOpError> {
+        self.track(|app| app.go_up())
+    }
+
+    /// Go to the home directory in the active panel.
+    pub fn go_home(&mut self) -> Result<(), FsOpError> {
+        self.track(|app| app.go_home())
+    }
+
+    // ----- Selection -----
+
+    /// Entry under the cursor of the active panel.
+    pub fn selected(&self) -> Option<&Entry> {
+        self.app.active_panel().selected_entry()
+    }
+
+    /// Put the cursor of the active panel on the entry called `name`.
+    /// Returns `false` when there is no such entry.
+    pub fn select(&mut self, name: &str) -> bool {
+        self.track(|app| Ok::<bool, FsOpError>(app.active_panel_mut().select_named(name))).unwrap_or(false)
+    }
+
+    /// Mark or unmark the entry under the cursor of the active panel.
+    pub fn toggle_mark(&mut self) {
+        self.app.active_panel_mut().toggle_selection();
+    }
+
+    /// Marked entries of the panel on `side`, in listing order.
+    pub fn marked(&self, side: Side) -> Vec<&Entry> {
+        let panel = self.panel(side);
+        let mut idx: Vec<usize> = panel.selections.iter().copied().collect();
+        idx.sort_unstable();
+        idx.into_iter().filter_map(|i| panel.entries.get(i)).collect()
+    }
+
+    // ----- Operations on the entry under the cursor -----
+
+    /// Copy it into (or to) `dst`.
+    pub fn copy_selected_to(&mut self, dst: impl Into<PathBuf>) -> Result<(), FsOpError> {
+        let dst = dst.into();
+        self.track(|app| app.copy_selected_to(dst))
+    }
+
+    /// Move it into (or to) `dst`.
+    pub fn move_selected_to(&mut self, dst: impl Into<PathBuf>) -> Result<(), FsOpError> {
+        let dst = dst.into();
+        self.track(|app| app.move_selected_to(dst))
+    }
+
+    /// Rename it within its directory.
+    pub fn rename_selected_to(&mut self, name: impl Into<String>) -> Result<(), FsOpError> {
+        let name = name.into();
+        self.track(|app| app.rename_selected_to(name))
+    }
+
+    /// Delete it (directories recursively).
+    pub fn delete_selected(&mut self) -> Result<(), FsOpError> {
+        self.track(|app| app.delete_selected())
+    }
+
+    /// Create an empty file in the active panel's directory.
+    pub fn new_file(&mut self, name: impl Into<String>) -> Result<(), FsOpError> {
+        let name = name.into();
+        self.track(|app| app.new_file(name))
+    }
+
+    /// Create a directory in the active panel's directory.
+    pub fn new_dir(&mut self, name: impl Into<String>) -> Result<(), FsOpError> {
+        let name = name.into();
+        self.track(|app| app.new_dir(name))
+    }
+
+    // ----- Hosting the UI -----
+
+    /// Feed a key to fileZoom's own key handling (dialogs, menus and the
+    /// configured key bindings). `page_size` is the number of rows
+    /// PageUp/PageDown move. Returns `true` when the user asked to quit.
+    pub fn handle_key(&mut self, code: KeyCode, page_size: usize) -> anyhow::Result<bool> {
+        self.track(|app| crate::runner::handlers::handle_key(app, code, page_size))
+    }
+
+    /// Pick up background work (copy progress, slow listings, previews
+    /// and content comparisons). Call it once per frame.
+    pub fn tick(&mut self) {
+        self.track(|app| {
+            app.poll_progress();
+            crate::runner::jobs::poll(app);
+            crate::runner::handlers::conflict::poll(app);
+            crate::runner::open_files::poll(app);
+            app.poll_listings();
+            Ok::<(), FsOpError>(())
+        })
+        .unwrap_or(())
+    }
+
+    /// Draw the panels, menus and dialogs over the whole frame.
+    pub fn draw(&self, f: &mut Frame) {
+        crate::ui::ui(f, &self.app)
+    }
+
+    fn panel(&self, side: Side) -> &crate::app::Panel {
+        match side {
+            Side::Left => &self.app.left,
+            Side::Right => &self.app.right,
+        }
+    }
+
+    fn snapshot(&self) -> Snapshot {
+        let panel = |p: &crate::app::Panel| (p.cwd.clone(), p.selected_entry().map(|e| e.path.clone()), p.entries.clone());
+        Snapshot { active: self.app.active, panels: [panel(&self.app.left), panel(&self.app.right)] }
+    }
+
+    /// Run `f` and publish the events for what it changed.
+    fn track<T, E>(&mut self, f: impl FnOnce(&mut App) -> Result<T, E>) -> Result<T, E> {
+        if self.subscribers.is_empty() {
+            return f(&mut self.app);
+        }
+        let before = self.snapshot();
+        let result = f(&mut self.app);
+        let after = self.snapshot();
+        let mut events = Vec::new();
+        if before.active != after.active {
+            events.push(BrowserEvent::ActivePanelChanged { side: after.active });
+        }
+        for (side, (old, new)) in [Side::Left, Side::Right].into_iter().zip(before.panels.iter().zip(&after.panels)) {
+            if old.0 != new.0 {
+                events.push(BrowserEvent::DirectoryChanged { side, dir: new.0.clone() });
+            } else {
+                let changes = listing::diff(&old.2, &new.2);
+                if !changes.is_empty() {
+                    events.push(BrowserEvent::ListingChanged { side, changes });
+                }
+            }
+            if old.1 != new.1 {
+                events.push(BrowserEvent::SelectionChanged { side, path: new.1.clone() });
+            }
+        }
+        self.subscribers.retain(|tx| events.iter().all(|e| tx.send(e.clone()).is_ok()));
+        result
+    }
+}
@@ -2,7 +2,6 @@ use handlebars::Handlebars;
 use serde_json::Value as JsonValue;
 use std::collections::HashMap;
 use std::sync::OnceLock;
-use toml::Value;
 
 /// Parse a TOML string and extract the `[errors]` table into a String map.
 ///
@@ -13,7 +12,8 @@ use toml::Value;
 fn parse_templates_from_str(raw: &str) -> HashMap<String, String> {
     let mut map = HashMap::new();
 
-    match raw.parse::<Value>() {
+    // Parse as a document (table); `Value` would only accept a single value.
+    match raw.parse::<toml::Table>() {
         Ok(val) => {
             if let Some(errors) = val.get("errors") {
                 if let Some(table) = errors.as_table() {
@@ -119,6 +119,11 @@ pub fn render_io_error(
                 &[("path", path.or(src).or(dst).unwrap_or("<unknown>"))],
             )
         }
+        ErrorKind::StorageFull | ErrorKind::QuotaExceeded => {
+            let tmpl = template_or_default("no_space", "Not enough space to write {path}: {err}");
+            let err_s = format!("{}", err);
+            format_template(&tmpl, &[("path", path.or(dst).unwrap_or("<unknown>")), ("err", &err_s)])
+        }
         ErrorKind::InvalidInput => {
             let tmpl = template_or_default("invalid_input", "Invalid input: {details}");
             let details = format!("{}", err);
@@ -8,9 +8,113 @@
 
 use std::fs;
 use std::path::{Path, PathBuf};
+use crate::app::{Action, Mode, Side};
+use crate::fs_op::elevated::ElevatedOp;
 use crate::fs_op::error::FsOpError;
 
+/// Entries whose deletion or move reaches further than they appear to, and
+/// so get a clarifying confirmation first (see `App::precheck_special`).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SpecialTarget {
+    /// A directory with another filesystem mounted on it.
+    MountPoint,
+    /// A symlink to the directory `target`.
+    DirSymlink(PathBuf),
+}
+
+impl SpecialTarget {
+    /// Classify `path`; `None` for ordinary entries. Mount points are
+    /// directories on a different device than their parent, so bind
+    /// mounts of the same filesystem are not detected.
+    pub fn of(path: &Path) -> Option<Self> {
+        let meta = fs::symlink_metadata(path).ok()?;
+        if meta.file_type().is_symlink() {
+            return fs::metadata(path).ok().filter(|m| m.is_dir()).map(|_| {
+                let target = fs::read_link(path).unwrap_or_default();
+                SpecialTarget::DirSymlink(path.parent().map(|p| p.join(&target)).unwrap_or(target))
+            });
+        }
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::MetadataExt;
+            let parent = path.parent().and_then(|p| fs::metadata(p).ok())?;
+            if meta.is_dir() && parent.dev() != meta.dev() {
+                return Some(SpecialTarget::MountPoint);
+            }
+        }
+        None
+    }
+}
+
 impl crate::app::core::App {
+    /// Pre-check for `action` (`DeleteSelected` or `MoveTo`) on the selected
+    /// entry. When the entry is a mount point, or a symlink to a directory
+    /// being deleted, returns the dialog asking how to go on; `None` means
+    /// the usual flow applies.
+    pub fn precheck_special(&self, action: &Action) -> Option<Mode> {
+        let entry = self.active_panel().selected_entry()?;
+        if !self.active_panel().vfs.is_local() {
+            return None;
+        }
+        let name = entry.display_name();
+        let (content, buttons, actions) = match (SpecialTarget::of(&entry.path)?, action) {
+            (SpecialTarget::MountPoint, Action::DeleteSelected) => (
+                format!("{} is a mount point.\nDeleting it removes the files of the filesystem mounted there.", name),
+                vec!["Delete contents"],
+                vec![Action::DeleteSelected],
+            ),
+            (SpecialTarget::MountPoint, Action::MoveTo(_)) => (
+                format!("{} is a mount point.\nMoving it copies the mounted filesystem and then deletes it.", name),
+                vec!["Move anyway"],
+                vec![action.clone()],
+            ),
+            (SpecialTarget::DirSymlink(target), Action::DeleteSelected) => (
+                format!("{} is a symlink to {}.\nDelete the link only, or the directory it points to as well?", name, target.display()),
+                vec!["Delete link only", "Delete link and target"],
+                vec![Action::DeleteSelected, Action::DeleteLinkTarget],
+            ),
+            _ => return None,
+        };
+        let buttons = buttons.into_iter().chain(["Cancel"]).map(String::from).collect();
+        Some(Mode::Message { title: "Confirm".to_string(), content, buttons, selected: 0, actions: Some(actions) })
+    }
+
+    /// `action` on the selected entry as an operation the privileged helper
+    /// can run again as root (see `fs_op::elevated`). `None` for actions it
+    /// does not cover and for non-local panels.
+    pub fn elevated_op(&self, action: &Action) -> Option<ElevatedOp> {
+        let panel = self.active_panel();
+        if !panel.vfs.is_local() {
+            return None;
+        }
+        let selected = || panel.selected_entry().map(|e| e.path.clone());
+        let target = |dst: &Path| selected().map(|src| (crate::fs_op::helpers::resolve_target(dst, src.file_name().unwrap_or_default()), src));
+        Some(match action {
+            Action::DeleteSelected => ElevatedOp::Remove(selected()?),
+            Action::CopyTo(dst) => target(dst).map(|(to, from)| ElevatedOp::Copy { from, to })?,
+            Action::MoveTo(dst) => target(dst).map(|(to, from)| ElevatedOp::Move { from, to })?,
+            Action::RenameTo(name) => ElevatedOp::Rename { from: selected()?, to: panel.cwd.join(name) },
+            Action::NewFile(name) => ElevatedOp::CreateFile(panel.cwd.join(name)),
+            Action::NewDir(name) => ElevatedOp::CreateDir(panel.cwd.join(name)),
+            Action::DeleteMarked | Action::DeleteLinkTarget | Action::ShowLog | Action::MoveMarked(_) | Action::Quit => return None,
+        })
+    }
+
+    /// Delete the selected symlink and the directory tree it points to.
+    pub fn delete_link_target(&mut self) -> Result<(), FsOpError> {
+        if let Some(sel) = self.selected_index() {
+            let panel = self.active_panel_mut();
+            if let Some(entry) = panel.entries.get(sel) {
+                if let Some(SpecialTarget::DirSymlink(target)) = SpecialTarget::of(&entry.path) {
+                    panel.vfs.remove(&target)?;
+                }
+                panel.vfs.remove(&entry.path)?;
+                self.refresh_active()?;
+            }
+        }
+        Ok(())
+    }
+
     /// Enter the selected directory (if any) by updating the active
     /// panel's `cwd` and refreshing the panel listing.
     pub fn enter(&mut self) -> Result<(), FsOpError> {
@@ -20,6 +124,8 @@ impl crate::app::core::App {
                 if entry.is_dir {
                     panel.cwd = entry.path.clone();
                     self.refresh_active()?;
+                    #[cfg(feature = "scripting")]
+                    crate::scripting::fire(self, crate::scripting::Hook::EnterDirectory);
                 }
             }
         }
@@ -27,52 +133,138 @@ impl crate::app::core::App {
     }
 
     /// Move the active panel up to its parent directory (if any) and
-    /// refresh the listing.
+    /// refresh the listing. Above a Windows drive or share root the drives
+    /// are offered in the mount picker.
     pub fn go_up(&mut self) -> Result<(), FsOpError> {
+        // Going up from search results goes back to the searched directory.
+        if self.active_panel().search_results().is_some() {
+            return Ok(self.close_search()?);
+        }
         let panel = self.active_panel_mut();
+        if cfg!(windows) && panel.cwd.parent().is_none() && panel.vfs.is_local() {
+            crate::runner::handlers::mount_picker::open_mount_picker(self);
+            return Ok(());
+        }
         if let Some(parent) = panel.cwd.parent() {
             panel.cwd = parent.to_path_buf();
             self.refresh_active()?;
+            #[cfg(feature = "scripting")]
+            crate::scripting::fire(self, crate::scripting::Hook::EnterDirectory);
         }
         Ok(())
     }
 
+    /// Switch the active panel to `dir`, such as one of its breadcrumbs.
+    /// The panel stays where it was when `dir` cannot be listed.
+    pub fn go_to(&mut self, dir: PathBuf) -> Result<(), FsOpError> {
+        let previous = std::mem::replace(&mut self.active_panel_mut().cwd, dir);
+        if let Err(e) = self.refresh_active() {
+            self.active_panel_mut().cwd = previous;
+            return Err(e.into());
+        }
+        #[cfg(feature = "scripting")]
+        crate::scripting::fire(self, crate::scripting::Hook::EnterDirectory);
+        Ok(())
+    }
+
+    /// Switch the active panel to the user's home directory.
+    pub fn go_home(&mut self) -> Result<(), FsOpError> {
+        let home = crate::fs_op::path::resolve_path("~", &self.active_panel().cwd).map_err(|e| e.to_string())?;
+        self.active_panel_mut().cwd = home;
+        self.refresh_active()?;
+        #[cfg(feature = "scripting")]
+        crate::scripting::fire(self, crate::scripting::Hook::EnterDirectory);
+        Ok(())
+    }
+
+    /// Swap the two panels (Ctrl+U): their directories move along with
+    /// their cursors, selections and filesystems. The active side stays.
+    pub fn swap_panels(&mut self) {
+        std::mem::swap(&mut self.left, &mut self.right);
+        self.left.dirty = true;
+        self.right.dirty = true;
+        self.update_preview_for(self.active);
+    }
+
+    /// Switch the panel on side `to` to the directory (and filesystem) of
+    /// the other one: Alt+I for the inactive panel, Alt+Shift+I for the
+    /// active one.
+    pub fn show_other_dir_in(&mut self, to: Side) -> Result<(), FsOpError> {
+        let from = match to {
+            Side::Left => Side::Right,
+            Side::Right => Side::Left,
+        };
+        let (cwd, vfs) = (self.panel(from).cwd.clone(), self.panel(from).vfs.clone());
+        let active = std::mem::replace(&mut self.active, to);
+        self.active_panel_mut().vfs = vfs;
+        let result = self.go_to(cwd);
+        self.active = active;
+        result
+    }
+
     /// Delete the currently selected entry (file or directory).
     pub fn delete_selected(&mut self) -> Result<(), FsOpError> {
         if let Some(sel) = self.selected_index() {
             let panel = self.active_panel_mut();
             if let Some(entry) = panel.entries.get(sel) {
-                if entry.is_dir {
-                    fs::remove_dir_all(&entry.path)?;
-                } else {
-                    fs::remove_file(&entry.path)?;
-                }
+                panel.vfs.remove(&entry.path)?;
                 self.refresh_active()?;
             }
         }
         Ok(())
     }
 
+    /// Delete the marked entries of the active panel, going on past the
+    /// ones that fail, and return what it came to.
+    pub fn delete_marked(&mut self) -> crate::runner::progress::OperationSummary {
+        let started = std::time::Instant::now();
+        let mut summary = crate::runner::progress::OperationSummary::default();
+        let panel = self.active_panel();
+        let local = panel.vfs.is_local();
+        let mut indices: Vec<usize> = panel.selections.iter().copied().collect();
+        indices.sort_unstable();
+        let items: Vec<(PathBuf, u64)> = indices
+            .into_iter()
+            .filter_map(|i| panel.entries.get(i))
+            .map(|e| {
+                let bytes = if local { crate::fs_op::meter::total_bytes(std::slice::from_ref(&e.path), &std::sync::atomic::AtomicBool::new(false)) } else { e.size };
+                (e.path.clone(), bytes)
+            })
+            .collect();
+        let vfs = panel.vfs.clone();
+        for (path, bytes) in items {
+            let res = crate::logging::log_fs_op("delete", &path, None, vfs.remove(&path));
+            match res {
+                Ok(()) => {
+                    summary.succeeded += 1;
+                    summary.bytes += bytes;
+                }
+                Err(e) => summary.fail(path, e.to_string()),
+            }
+        }
+        self.active_panel_mut().clear_selections();
+        let _ = self.refresh_active();
+        summary.ended(started)
+    }
+
     /// Copy the selected entry to `dst`.
     ///
     /// If the selection is a directory, performs a recursive copy. For
     /// regular files an atomic file-copy helper is used.
     pub fn copy_selected_to(&mut self, dst: PathBuf) -> Result<(), FsOpError> {
+        let create_dirs = self.settings.create_dest_dirs;
         if let Some(sel) = self.selected_index() {
             let panel = self.active_panel_mut();
             if let Some(src_entry) = panel.entries.get(sel) {
                 let src_path: &Path = &src_entry.path;
-                let src_name: &str = &src_entry.name;
+                let src_name = src_entry.name.as_os_str();
                 let target = crate::fs_op::helpers::resolve_target(&dst, src_name);
-                if src_entry.is_dir {
-                    // Call into the shared copy implementation directly so we
-                    // avoid borrowing `self` immutably while a mutable borrow
-                    // to the panel is still active.
-                    crate::fs_op::copy::copy_recursive(src_path, &target)?;
-                } else {
+                if create_dirs {
                     crate::fs_op::helpers::ensure_parent_exists(&target)?;
-                    crate::fs_op::helpers::atomic_copy_file(src_path, &target)?;
+                } else {
+                    crate::fs_op::helpers::require_parent_exists(&target)?;
                 }
+                panel.vfs.copy(src_path, &target)?;
                 self.refresh_active()?;
             }
         }
@@ -84,28 +276,43 @@ impl crate::app::core::App {
     /// Attempts an atomic rename and falls back to copy+remove when
     /// required (e.g. cross-filesystem moves).
     pub fn move_selected_to(&mut self, dst: PathBuf) -> Result<(), FsOpError> {
+        let create_dirs = self.settings.create_dest_dirs;
         if let Some(sel) = self.selected_index() {
             let panel = self.active_panel_mut();
             if let Some(src_entry) = panel.entries.get(sel) {
-                let src_path: &Path = &src_entry.path;
-                let src_name: &str = &src_entry.name;
-                let target = crate::fs_op::helpers::resolve_target(&dst, src_name);
-                crate::fs_op::helpers::ensure_parent_exists(&target)?;
-                crate::fs_op::helpers::atomic_rename_or_copy(src_path, &target)?;
+                let src_path = src_entry.path.clone();
+                let target = crate::fs_op::helpers::resolve_target(&dst, src_entry.name.as_os_str());
+                if create_dirs {
+                    crate::fs_op::helpers::ensure_parent_exists(&target)?;
+                } else {
+                    crate::fs_op::helpers::require_parent_exists(&target)?;
+                }
+                crate::fs_op::helpers::atomic_rename_or_copy(&src_path, &target)?;
+                self.move_tags(&src_path, &target);
                 self.refresh_active()?;
             }
         }
         Ok(())
     }
 
+    /// Carry the tags of a moved or renamed path over to its new location.
+    fn move_tags(&mut self, from: &Path, to: &Path) {
+        if self.tags.rename(from, to) {
+            if let Err(e) = self.tags.save() {
+                tracing::warn!("failed to save tag database: {:#}", e);
+            }
+        }
+    }
+
     /// Rename the selected entry to `name` within the same directory.
     pub fn rename_selected_to(&mut self, name: String) -> Result<(), FsOpError> {
         if let Some(sel) = self.selected_index() {
             let panel = self.active_panel_mut();
             if let Some(src_entry) = panel.entries.get(sel) {
-                let src_path: &Path = &src_entry.path;
+                let src_path = src_entry.path.clone();
                 let target = panel.cwd.join(name);
-                crate::fs_op::helpers::atomic_rename_or_copy(src_path, &target)?;
+                crate::fs_op::helpers::atomic_rename_or_copy(&src_path, &target)?;
+                self.move_tags(&src_path, &target);
                 self.refresh_active()?;
             }
         }
@@ -127,6 +334,65 @@ impl crate::app::core::App {
         Ok(())
     }
 
+    /// Create `name` inside the active panel's cwd from `template`, a file
+    /// or directory (see `fs_op::create::create_from_template`).
+    pub fn new_from_template(&mut self, name: String, template: &Path) -> Result<(), FsOpError> {
+        let path = self.active_panel().cwd.join(name);
+        crate::fs_op::create::create_from_template(template, &path).map_err(|e| match e {
+            crate::fs_op::create::CreateError::Io(e) => FsOpError::Io(e),
+            e => FsOpError::Message(e.to_string()),
+        })?;
+        self.refresh_active()?;
+        Ok(())
+    }
+
+    /// Set the access and modification times of the selection (the marked
+    /// entries, or the one under the cursor) to `when`, as accepted by
+    /// `fs_op::metadata::parse_time`. Directories are changed recursively.
+    pub fn set_selection_times(&mut self, when: &str) -> Result<(), FsOpError> {
+        let time = crate::fs_op::metadata::parse_time(when)?;
+        let paths = crate::runner::shell::selected_paths(self);
+        let mut first_err = None;
+        for path in &paths {
+            if let Err(e) = crate::fs_op::metadata::set_times(path, time, time, true) {
+                first_err.get_or_insert_with(|| FsOpError::Message(format!("{}: {}", path.display(), e)));
+            }
+        }
+        self.refresh_active()?;
+        first_err.map_or(Ok(()), Err)
+    }
+
+    /// Give the selection the owner and group in `spec` (see
+    /// `fs_op::chown::parse_owner`), with `recursive` everything below
+    /// selected directories too. Returns the entries that could not be
+    /// changed.
+    #[cfg(unix)]
+    pub fn chown_selection(&mut self, spec: &str, recursive: bool) -> Result<Vec<(PathBuf, std::io::Error)>, FsOpError> {
+        let owner = crate::fs_op::chown::parse_owner(spec)?;
+        let paths = crate::runner::shell::selected_paths(self);
+        let failures = crate::fs_op::chown::chown_paths(&paths, owner, recursive);
+        self.refresh_active()?;
+        Ok(failures)
+    }
+
+    /// Change the Windows attributes of the selection as described by
+    /// `spec` (see `fs_op::attributes::parse_changes`). Every entry is
+    /// attempted; the first failure is returned.
+    pub fn change_selection_attributes(&mut self, spec: &str) -> Result<(), FsOpError> {
+        let changes = crate::fs_op::attributes::parse_changes(spec)?;
+        let mut first_err = None;
+        for path in crate::runner::shell::selected_paths(self) {
+            for &(flag, set) in &changes {
+                if let Err(e) = crate::fs_op::attributes::toggle(&path, flag, set) {
+                    first_err.get_or_insert_with(|| FsOpError::Message(format!("{}: {}", path.display(), e)));
+                    break;
+                }
+            }
+        }
+        self.refresh_active()?;
+        first_err.map_or(Ok(()), Err)
+    }
+
     /// Create a new directory named `name` inside the active panel's cwd.
     pub fn new_dir(&mut self, name: String) -> Result<(), FsOpError> {
         let panel = self.active_panel_mut();
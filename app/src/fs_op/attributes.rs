@@ -0,0 +1,145 @@
+//! Windows file attributes: read-only, hidden, system and archive.
+//!
+//! `Entry::attributes` and the info panel carry the attribute bits read
+//! from `std::os::windows::fs::MetadataExt::file_attributes`; `toggle`
+//! flips one of them with `SetFileAttributesW`. Other platforms have no
+//! such bits, so `read` returns `None` there and `toggle` fails with
+//! `Unsupported`.
+
+use std::fs::Metadata;
+use std::io;
+use std::path::Path;
+
+/// A `FILE_ATTRIBUTE_*` bitfield.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct FileAttributes(pub u32);
+
+impl FileAttributes {
+    pub const READONLY: u32 = 0x1;
+    pub const HIDDEN: u32 = 0x2;
+    pub const SYSTEM: u32 = 0x4;
+    pub const ARCHIVE: u32 = 0x20;
+
+    /// The attributes shown and toggled, with their `attrib` letters.
+    pub const SHOWN: [(u32, char); 4] = [(Self::READONLY, 'R'), (Self::HIDDEN, 'H'), (Self::SYSTEM, 'S'), (Self::ARCHIVE, 'A')];
+
+    pub fn contains(self, flag: u32) -> bool {
+        self.0 & flag != 0
+    }
+
+    /// The set attributes as `attrib` letters, `-` for unset ones (`RH-A`).
+    pub fn letters(self) -> String {
+        Self::SHOWN.iter().map(|&(flag, c)| if self.contains(flag) { c } else { '-' }).collect()
+    }
+
+    /// The flag with `attrib` letter `c`, in either case.
+    pub fn flag_of(c: char) -> Option<u32> {
+        Self::SHOWN.iter().find(|&&(_, l)| l == c.to_ascii_uppercase()).map(|&(flag, _)| flag)
+    }
+}
+
+/// Parse the `:attrib` argument: `attrib`-style letters, each prefixed
+/// with `+` (set), `-` (clear) or nothing (flip), e.g. `+h -r`.
+pub fn parse_changes(spec: &str) -> Result<Vec<(u32, Option<bool>)>, String> {
+    let mut changes = Vec::new();
+    for word in spec.split_whitespace() {
+        let (set, letters) = match word.split_at(usize::from(word.starts_with(['+', '-']))) {
+            ("+", rest) => (Some(true), rest),
+            ("-", rest) => (Some(false), rest),
+            (_, rest) => (None, rest),
+        };
+        if letters.is_empty() {
+            return Err(format!("`{}` names no attribute", word));
+        }
+        for c in letters.chars() {
+            let flag = FileAttributes::flag_of(c).ok_or_else(|| format!("Unknown attribute `{}` (use R, H, S or A)", c))?;
+            changes.push((flag, set));
+        }
+    }
+    if changes.is_empty() {
+        return Err("Name the attributes to change, e.g. `+h -r`".to_string());
+    }
+    Ok(changes)
+}
+
+/// The attributes in `meta`, on Windows.
+pub fn read(meta: &Metadata) -> Option<FileAttributes> {
+    #[cfg(windows)]
+    {
+        use std::os::windows::fs::MetadataExt;
+        Some(FileAttributes(meta.file_attributes()))
+    }
+    #[cfg(not(windows))]
+    {
+        let _ = meta;
+        None
+    }
+}
+
+/// Set (`Some(true)`), clear (`Some(false)`) or flip (`None`) `flag` on
+/// `path`, without following a final symlink. Returns the new attributes.
+pub fn toggle(path: &Path, flag: u32, set: Option<bool>) -> io::Result<FileAttributes> {
+    #[cfg(windows)]
+    {
+        /// What `SetFileAttributesW` takes for "no attributes".
+        const FILE_ATTRIBUTE_NORMAL: u32 = 0x80;
+        let current = read(&std::fs::symlink_metadata(path)?).unwrap_or_default();
+        let on = set.unwrap_or(!current.contains(flag));
+        let new = FileAttributes(if on { current.0 | flag } else { current.0 & !flag });
+        let bits = if new.0 == 0 { FILE_ATTRIBUTE_NORMAL } else { new.0 };
+        // SAFETY: the path is NUL-terminated UTF-16.
+        if unsafe { win::SetFileAttributesW(win::wide(path).as_ptr(), bits) } == 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(new)
+    }
+    #[cfg(not(windows))]
+    {
+        let _ = (path, flag, set);
+        Err(io::Error::new(io::ErrorKind::Unsupported, "file attributes are only supported on Windows"))
+    }
+}
+
+#[cfg(windows)]
+mod win {
+    #[link(name = "kernel32")]
+    extern "system" {
+        pub fn SetFileAttributesW(path: *const u16, attributes: u32) -> i32;
+    }
+
+    /// `path` as a NUL-terminated UTF-16 string.
+    pub fn wide(path: &std::path::Path) -> Vec<u16> {
+        use std::os::windows::ffi::OsStrExt;
+        path.as_os_str().encode_wide().chain(Some(0)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn letters_follow_attrib() {
+        let attrs = FileAttributes(FileAttributes::READONLY | FileAttributes::ARCHIVE | 0x10);
+        assert_eq!(attrs.letters(), "R--A");
+        assert_eq!(FileAttributes::flag_of('h'), Some(FileAttributes::HIDDEN));
+        assert_eq!(FileAttributes::flag_of('x'), None);
+    }
+
+    #[test]
+    fn parses_set_clear_and_flip() {
+        let changes = parse_changes("+h -rs a").unwrap();
+        assert_eq!(
+            changes,
+            [
+                (FileAttributes::HIDDEN, Some(true)),
+                (FileAttributes::READONLY, Some(false)),
+                (FileAttributes::SYSTEM, Some(false)),
+                (FileAttributes::ARCHIVE, None),
+            ]
+        );
+        assert!(parse_changes("+").is_err());
+        assert!(parse_changes("+x").is_err());
+        assert!(parse_changes("").is_err());
+    }
+}
@@ -0,0 +1,144 @@
+//! Per-path entry metadata cache.
+//!
+//! Building an `Entry` costs more than the `stat` that produced its
+//! metadata: permissions are probed by opening the file and uid/gid are
+//! resolved to names through the user database. `MetadataCache` memoizes
+//! finished entries keyed by path and validated by a `Stamp` (size, mtime
+//! and, on Unix, ctime and inode), so re-entering a large directory or
+//! re-sorting only pays for the directory read. A chmod/chown bumps the
+//! ctime and a replaced file changes the inode, so stale entries are never
+//! served; watcher events additionally drop entries through `invalidate`.
+
+use std::collections::HashMap;
+use std::fs::Metadata;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+use once_cell::sync::Lazy;
+
+use crate::app::types::Entry;
+
+/// Entries kept before the cache is emptied and starts over.
+pub const MAX_CACHED_ENTRIES: usize = 100_000;
+
+/// The parts of a path's metadata that decide whether a cached entry is
+/// still valid.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Stamp {
+    len: u64,
+    modified: Option<SystemTime>,
+    #[cfg(unix)]
+    ctime: (i64, i64),
+    #[cfg(unix)]
+    ino: u64,
+}
+
+impl Stamp {
+    pub fn of(md: &Metadata) -> Self {
+        #[cfg(unix)]
+        use std::os::unix::fs::MetadataExt;
+        Stamp {
+            len: md.len(),
+            modified: md.modified().ok(),
+            #[cfg(unix)]
+            ctime: (md.ctime(), md.ctime_nsec()),
+            #[cfg(unix)]
+            ino: md.ino(),
+        }
+    }
+}
+
+/// Thread-safe path -> `Entry` cache with hit/miss counters.
+#[derive(Debug, Default)]
+pub struct MetadataCache {
+    map: Mutex<HashMap<PathBuf, (Stamp, Entry)>>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl MetadataCache {
+    /// The cached entry for `path` if `md` still matches it, else the
+    /// result of `build` (which is cached for next time).
+    pub fn entry(&self, path: &Path, md: &Metadata, build: impl FnOnce() -> Entry) -> Entry {
+        let stamp = Stamp::of(md);
+        if let Some((cached_stamp, entry)) = self.lock().get(path) {
+            if *cached_stamp == stamp {
+                self.hits.fetch_add(1, Ordering::Relaxed);
+                return entry.clone();
+            }
+        }
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        let entry = build();
+        let mut map = self.lock();
+        if map.len() >= MAX_CACHED_ENTRIES {
+            map.clear();
+        }
+        map.insert(path.to_path_buf(), (stamp, entry.clone()));
+        entry
+    }
+
+    /// Drop `path` and anything cached below it.
+    pub fn invalidate(&self, path: &Path) {
+        self.lock().retain(|p, _| !p.starts_with(path));
+    }
+
+    /// Drop everything.
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    /// Number of cached entries.
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Lookups served from the cache and lookups that built a new entry.
+    pub fn stats(&self) -> (u64, u64) {
+        (self.hits.load(Ordering::Relaxed), self.misses.load(Ordering::Relaxed))
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<PathBuf, (Stamp, Entry)>> {
+        self.map.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+static CACHE: Lazy<MetadataCache> = Lazy::new(MetadataCache::default);
+
+/// The process-wide cache used by `vfs::LocalFs`.
+pub fn global() -> &'static MetadataCache {
+    &CACHE
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn entries_are_reused_until_metadata_changes() {
+        let tmp = tempfile::tempdir().unwrap();
+        let p = tmp.path().join("a.txt");
+        std::fs::write(&p, "one").unwrap();
+        let cache = MetadataCache::default();
+        let build = |size| move || Entry::file("a.txt", PathBuf::from("a.txt"), size, None);
+
+        let md = std::fs::metadata(&p).unwrap();
+        assert_eq!(cache.entry(&p, &md, build(1)).size, 1);
+        assert_eq!(cache.entry(&p, &md, build(2)).size, 1, "same stamp is a hit");
+        assert_eq!(cache.stats(), (1, 1));
+
+        std::fs::write(&p, "longer").unwrap();
+        let md = std::fs::metadata(&p).unwrap();
+        assert_eq!(cache.entry(&p, &md, build(3)).size, 3, "changed size is a miss");
+
+        cache.invalidate(tmp.path());
+        assert!(cache.is_empty());
+        assert_eq!(cache.entry(&p, &md, build(4)).size, 4);
+        assert_eq!(cache.stats(), (1, 3));
+    }
+}
@@ -0,0 +1,94 @@
+//! Change the owner and group of files (Unix only).
+//!
+//! `parse_owner` reads the `user[:group]` spec typed into the change-owner
+//! dialog and `chown_paths` applies it, optionally to everything below
+//! directories. Unlike the best-effort ownership copy in `metadata`, every
+//! failure is collected so the dialog can report which files kept their
+//! owner.
+
+use std::io;
+use std::path::{Path, PathBuf};
+
+use nix::unistd::{chown, Gid, Group, Uid, User};
+use walkdir::WalkDir;
+
+/// The owner and group to set; `None` leaves that one unchanged.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Ownership {
+    pub uid: Option<Uid>,
+    pub gid: Option<Gid>,
+}
+
+/// Parse `user`, `user:group`, `user:` or `:group` (names or numeric ids),
+/// like chown(1). An empty user or group leaves it unchanged.
+pub fn parse_owner(spec: &str) -> Result<Ownership, String> {
+    let spec = spec.trim();
+    let (user, group) = match spec.split_once(':') {
+        Some((user, group)) => (user, group),
+        None => (spec, ""),
+    };
+    if user.is_empty() && group.is_empty() {
+        return Err("Type a user, user:group or :group".to_string());
+    }
+    let uid = match user {
+        "" => None,
+        name => Some(lookup(name, |n| User::from_name(n).map(|u| u.map(|u| u.uid)), Uid::from_raw, "user")?),
+    };
+    let gid = match group {
+        "" => None,
+        name => Some(lookup(name, |n| Group::from_name(n).map(|g| g.map(|g| g.gid)), Gid::from_raw, "group")?),
+    };
+    Ok(Ownership { uid, gid })
+}
+
+/// Resolve a user or group `name`, falling back to a numeric id.
+fn lookup<T>(name: &str, by_name: impl Fn(&str) -> nix::Result<Option<T>>, from_raw: fn(u32) -> T, what: &str) -> Result<T, String> {
+    match by_name(name) {
+        Ok(Some(id)) => Ok(id),
+        _ => name.parse().map(from_raw).map_err(|_| format!("No {} named `{}`", what, name)),
+    }
+}
+
+/// Give every path in `paths` (and, with `recursive`, everything below the
+/// directories among them) `owner`. Symlinks themselves are changed, never
+/// their targets. Returns the paths that could not be changed and why.
+pub fn chown_paths(paths: &[PathBuf], owner: Ownership, recursive: bool) -> Vec<(PathBuf, io::Error)> {
+    let depth = if recursive { usize::MAX } else { 0 };
+    let mut failures = Vec::new();
+    for root in paths {
+        for entry in WalkDir::new(root).follow_root_links(false).max_depth(depth) {
+            match entry {
+                Ok(e) => {
+                    if let Err(err) = chown_one(e.path(), e.path_is_symlink(), owner) {
+                        failures.push((e.into_path(), err));
+                    }
+                }
+                Err(e) => failures.push((e.path().unwrap_or(root).to_path_buf(), io::Error::from(e))),
+            }
+        }
+    }
+    failures
+}
+
+fn chown_one(path: &Path, is_symlink: bool, owner: Ownership) -> io::Result<()> {
+    if is_symlink {
+        std::os::unix::fs::lchown(path, owner.uid.map(Uid::as_raw), owner.gid.map(Gid::as_raw))
+    } else {
+        chown(path, owner.uid, owner.gid).map_err(io::Error::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_names_ids_and_partial_specs() {
+        let root = Ownership { uid: Some(Uid::from_raw(0)), gid: None };
+        assert_eq!(parse_owner("root").unwrap(), root);
+        assert_eq!(parse_owner("0:").unwrap(), root);
+        assert_eq!(parse_owner(":0").unwrap(), Ownership { uid: None, gid: Some(Gid::from_raw(0)) });
+        assert!(parse_owner("no-such-user-here").is_err());
+        assert!(parse_owner(":").is_err());
+    }
+}
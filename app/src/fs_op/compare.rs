@@ -0,0 +1,167 @@
+//! Quick comparison of the contents of two files, offered by the overwrite
+//! conflict dialog (`d`).
+//!
+//! Files of different sizes differ without being read. Otherwise both are
+//! read side by side until the first differing byte, so differing files
+//! are usually told apart after the first chunk; identical files are read
+//! to the end. For text, the differing line of each file is shown.
+
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Read};
+use std::path::Path;
+
+/// Bytes compared at a time.
+const CHUNK: usize = 64 * 1024;
+
+/// Longest line excerpt shown, in characters.
+const MAX_EXCERPT_CHARS: usize = 60;
+
+/// How two files compare.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Comparison {
+    Identical,
+    /// The sizes differ; the contents were not read.
+    SizeDiffers,
+    /// The first difference is at byte `offset`, on (1-based) `line`. The
+    /// excerpts are that line of each file, when both are text.
+    DiffersAt { offset: u64, line: usize, excerpts: Option<(String, String)> },
+}
+
+impl std::fmt::Display for Comparison {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Comparison::Identical => write!(f, "Contents are identical"),
+            Comparison::SizeDiffers => write!(f, "Contents differ (the sizes differ)"),
+            Comparison::DiffersAt { offset, line, excerpts } => {
+                write!(f, "Contents differ from byte {} (line {})", offset, line)?;
+                if let Some((src, dst)) = excerpts {
+                    write!(f, "\nSource: {}\nTarget: {}", src, dst)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Compare the contents of files `a` and `b`.
+pub fn compare_files(a: &Path, b: &Path) -> io::Result<Comparison> {
+    compare_files_until(a, b, &|| false)
+}
+
+/// `compare_files`, giving up with `ErrorKind::Interrupted` once `stop`
+/// returns true. It is checked before each chunk.
+pub fn compare_files_until(a: &Path, b: &Path, stop: &dyn Fn() -> bool) -> io::Result<Comparison> {
+    if a.metadata()?.len() != b.metadata()?.len() {
+        return Ok(Comparison::SizeDiffers);
+    }
+    let (mut ra, mut rb) = (File::open(a)?, File::open(b)?);
+    let (mut ba, mut bb) = (vec![0u8; CHUNK], vec![0u8; CHUNK]);
+    let mut offset = 0u64;
+    let mut line = 1usize;
+    loop {
+        if stop() {
+            return Err(io::Error::new(io::ErrorKind::Interrupted, "comparison cancelled"));
+        }
+        let n = read_full(&mut ra, &mut ba)?;
+        let m = read_full(&mut rb, &mut bb)?;
+        let same = n.min(m);
+        if let Some(i) = (0..same).find(|&i| ba[i] != bb[i]) {
+            line += ba[..i].iter().filter(|&&c| c == b'\n').count();
+            let excerpts = line_excerpt(a, line)?.zip(line_excerpt(b, line)?);
+            return Ok(Comparison::DiffersAt { offset: offset + i as u64, line, excerpts });
+        }
+        if n != m {
+            // One file grew or shrank while being read.
+            line += ba[..same].iter().filter(|&&c| c == b'\n').count();
+            return Ok(Comparison::DiffersAt { offset: offset + same as u64, line, excerpts: None });
+        }
+        if n == 0 {
+            return Ok(Comparison::Identical);
+        }
+        line += ba[..n].iter().filter(|&&c| c == b'\n').count();
+        offset += n as u64;
+    }
+}
+
+/// Fill `buf` from `r` unless the end comes first; returns the bytes read.
+fn read_full(r: &mut impl Read, buf: &mut [u8]) -> io::Result<usize> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match r.read(&mut buf[filled..])? {
+            0 => break,
+            n => filled += n,
+        }
+    }
+    Ok(filled)
+}
+
+/// Line `line` (1-based) of `path`, shortened, or `None` when it is not
+/// text.
+fn line_excerpt(path: &Path, line: usize) -> io::Result<Option<String>> {
+    let mut reader = BufReader::new(File::open(path)?);
+    let mut buf = Vec::new();
+    for _ in 0..line {
+        buf.clear();
+        if reader.read_until(b'\n', &mut buf)? == 0 {
+            break;
+        }
+    }
+    let Ok(text) = std::str::from_utf8(&buf) else {
+        return Ok(None);
+    };
+    let text = text.trim_end_matches(['\n', '\r']);
+    if text.chars().any(|c| c.is_control() && c != '\t') {
+        return Ok(None);
+    }
+    let mut excerpt: String = text.chars().take(MAX_EXCERPT_CHARS).collect();
+    if text.chars().count() > MAX_EXCERPT_CHARS {
+        excerpt.push('…');
+    }
+    Ok(Some(excerpt))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_the_first_differing_line() {
+        let tmp = tempfile::tempdir().unwrap();
+        let (a, b, c) = (tmp.path().join("a"), tmp.path().join("b"), tmp.path().join("c"));
+        std::fs::write(&a, "one\ntwo\nthree\n").unwrap();
+        std::fs::write(&b, "one\ntwo\nthreE\n").unwrap();
+        std::fs::write(&c, "one\n").unwrap();
+
+        assert_eq!(compare_files(&a, &a).unwrap(), Comparison::Identical);
+        assert_eq!(compare_files(&a, &c).unwrap(), Comparison::SizeDiffers);
+        let cmp = compare_files(&a, &b).unwrap();
+        assert_eq!(cmp, Comparison::DiffersAt { offset: 12, line: 3, excerpts: Some(("three".into(), "threE".into())) });
+        assert_eq!(cmp.to_string(), "Contents differ from byte 12 (line 3)\nSource: three\nTarget: threE");
+    }
+
+    #[test]
+    fn binary_files_get_no_excerpt() {
+        let tmp = tempfile::tempdir().unwrap();
+        let (a, b) = (tmp.path().join("a"), tmp.path().join("b"));
+        let mut data = vec![0u8; CHUNK + 10];
+        std::fs::write(&a, &data).unwrap();
+        data[CHUNK + 3] = 1;
+        std::fs::write(&b, &data).unwrap();
+        assert_eq!(compare_files(&a, &b).unwrap(), Comparison::DiffersAt { offset: CHUNK as u64 + 3, line: 1, excerpts: None });
+    }
+
+    #[test]
+    fn stops_when_asked() {
+        let tmp = tempfile::tempdir().unwrap();
+        let a = tmp.path().join("a");
+        std::fs::write(&a, vec![7u8; CHUNK * 3]).unwrap();
+        let chunks = std::cell::Cell::new(0);
+        let stop = || {
+            chunks.set(chunks.get() + 1);
+            chunks.get() > 2
+        };
+        let err = compare_files_until(&a, &a, &stop).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::Interrupted);
+        assert_eq!(chunks.get(), 3);
+    }
+}
@@ -1,14 +1,12 @@
 use std::fs;
 use std::io;
 use std::path::Path;
-use fs_extra::file::copy as file_copy;
-use fs_extra::dir::{copy as dir_copy, CopyOptions};
+use super::meter::{dir_copy, file_copy};
+use super::options::CopyOptions;
 #[cfg(unix)]
-use std::os::unix::fs::{PermissionsExt, symlink as unix_symlink};
+use std::os::unix::fs::PermissionsExt;
 #[cfg(unix)]
 use std::os::unix::ffi::OsStrExt;
-#[cfg(windows)]
-use std::os::windows::fs::{symlink_dir as windows_symlink_dir, symlink_file as windows_symlink_file};
 
 /// Copy the contents of a directory recursively from `src` into `dst`.
 ///
@@ -30,8 +28,19 @@ use std::os::windows::fs::{symlink_dir as windows_symlink_dir, symlink_file as w
 ///
 /// # Errors
 /// Returns an `io::Error` for any underlying filesystem or copy errors.
-/// Errors coming from `fs_extra` are mapped into `io::ErrorKind::Other`.
+/// I/O errors coming from `fs_extra` keep their kind; its other errors are
+/// mapped into `io::ErrorKind::Other`.
 pub(crate) fn copy_recursive(src: impl AsRef<Path>, dst: impl AsRef<Path>) -> io::Result<()> {
+    copy_recursive_with(src, dst, &CopyOptions { follow_symlinks: false, ..Default::default() })
+}
+
+/// Recursive directory copy honouring `opts`.
+///
+/// Unlike `copy_recursive`, existing files in `dst` are replaced when
+/// `opts.overwrite` is set, top-level symlinks are copied as their targets
+/// when `opts.follow_symlinks` is set, and metadata is only preserved when
+/// `opts.preserve_metadata` is set.
+pub(crate) fn copy_recursive_with(src: impl AsRef<Path>, dst: impl AsRef<Path>, opts: &CopyOptions) -> io::Result<()> {
     let src = src.as_ref();
     let dst = dst.as_ref();
 
@@ -51,26 +60,28 @@ pub(crate) fn copy_recursive(src: impl AsRef<Path>, dst: impl AsRef<Path>) -> io
         };
 
         // Use symlink_metadata so we can detect symlinks and special file types
-        // without following the link.
-        let meta = fs::symlink_metadata(&path).map_err(io::Error::other)?;
+        // without following the link (unless asked to follow them).
+        let meta = if opts.follow_symlinks {
+            fs::metadata(&path).map_err(io::Error::other)?
+        } else {
+            fs::symlink_metadata(&path).map_err(io::Error::other)?
+        };
 
         if meta.file_type().is_dir() {
             // If the destination directory already exists, copy the contents
             // of `path` into it (preserving existing files). Otherwise copy
             // the directory itself into `dst`.
             let dest_dir = dst.join(&file_name);
-            let mut dir_opts = CopyOptions::new();
-            dir_opts.overwrite = false;
-            dir_opts.buffer_size = 64 * 1024;
+            let mut dir_opts = opts.to_fs_extra_dir();
 
             if dest_dir.exists() {
                 // copy contents into existing dest_dir
                 dir_opts.copy_inside = true;
-                dir_copy(&path, &dest_dir, &dir_opts).map_err(|e| io::Error::other(e.to_string()))?;
+                dir_copy(&path, &dest_dir, &dir_opts)?;
             } else {
                 // copy directory as a child of dst
                 dir_opts.copy_inside = false;
-                dir_copy(&path, dst, &dir_opts).map_err(|e| io::Error::other(e.to_string()))?;
+                dir_copy(&path, dst, &dir_opts)?;
             }
             continue;
         }
@@ -78,38 +89,40 @@ pub(crate) fn copy_recursive(src: impl AsRef<Path>, dst: impl AsRef<Path>) -> io
         if meta.file_type().is_file() {
             // Copy the file into `dst/<file_name>` using fs_extra file copy.
             let dest_file = dst.join(&file_name);
-            if dest_file.exists() {
+            if dest_file.exists() && !opts.overwrite {
                 // Respect non-overwrite semantics: skip existing files.
                 continue;
             }
-            let mut file_opts = fs_extra::file::CopyOptions::new();
-            file_opts.overwrite = false;
-            file_opts.buffer_size = 64 * 1024;
-            file_copy(&path, &dest_file, &file_opts).map_err(|e| io::Error::other(e.to_string()))?;
+            let file_opts = opts.to_fs_extra_file();
+            let existed = dest_file.exists();
+            let copied = file_copy(&path, &dest_file, &file_opts)
+                .and_then(super::test_helpers::consume_simulated_space);
+            if let Err(e) = copied {
+                // Don't leave a truncated new file behind (e.g. out of space).
+                if !existed {
+                    let _ = fs::remove_file(&dest_file);
+                }
+                return Err(e);
+            }
             continue;
         }
 
         // Handle symlinks and some special file types.
         if meta.file_type().is_symlink() {
             // Recreate the symlink at the destination with the same target.
-            let target = fs::read_link(&path).map_err(io::Error::other)?;
             let dest_link = dst.join(&file_name);
-            // If destination exists, do not overwrite.
-            if dest_link.exists() {
-                continue;
-            }
-            #[cfg(unix)]
-            {
-                unix_symlink(&target, &dest_link).map_err(io::Error::other)?;
-            }
-            #[cfg(windows)]
-            {
-                if meta.file_type().is_dir() {
-                    windows_symlink_dir(&target, &dest_link).map_err(io::Error::other)?;
+            // If destination exists, only replace it when overwriting.
+            if let Ok(existing) = fs::symlink_metadata(&dest_link) {
+                if !opts.overwrite {
+                    continue;
+                }
+                if existing.file_type().is_symlink() {
+                    super::symlink::remove_symlink(&dest_link)?;
                 } else {
-                    windows_symlink_file(&target, &dest_link).map_err(io::Error::other)?;
+                    fs::remove_file(&dest_link)?;
                 }
             }
+            super::symlink::copy_symlink(&path, &dest_link)?;
             continue;
         }
 
@@ -154,7 +167,9 @@ pub(crate) fn copy_recursive(src: impl AsRef<Path>, dst: impl AsRef<Path>) -> io
     }
 
     // Attempt to preserve metadata for the whole tree (best-effort).
-    crate::fs_op::metadata::preserve_all_metadata(src, dst)?;
+    if opts.preserve_metadata {
+        crate::fs_op::metadata::preserve_all_metadata(src, dst)?;
+    }
 
     Ok(())
 }
@@ -28,19 +28,66 @@ pub fn create_file<P: AsRef<Path>>(path: P) -> Result<(), CreateError> {
     // Ensure parent exists (no-op if there is no parent).
     crate::fs_op::helpers::ensure_parent_exists(p)?;
 
-    crate::fs_op::helpers::atomic_write(p, &[]).map_err(|e| {
+    let res = crate::fs_op::helpers::atomic_write(p, &[]).map_err(|e| {
         if e.kind() == io::ErrorKind::AlreadyExists {
             CreateError::AlreadyExists(p.to_path_buf())
         } else {
             CreateError::Io(e)
         }
-    })
+    });
+    crate::logging::log_fs_op("create file", p, None, res)
 }
 
 /// Create a directory and all parent components, returning an I/O-style
 /// crate-local error type on failure.
 pub fn create_dir_all<P: AsRef<Path>>(path: P) -> Result<(), CreateError> {
-    std::fs::create_dir_all(path.as_ref()).map_err(CreateError::Io)
+    let p = path.as_ref();
+    crate::logging::log_fs_op("create dir", p, None, std::fs::create_dir_all(p).map_err(CreateError::Io))
+}
+
+/// Replace the placeholders of a template: `{{name}}` (file name of
+/// `target`), `{{stem}}` (without its extension), `{{date}}`, `{{time}}`
+/// and `{{year}}` (local time now).
+pub fn expand_placeholders(text: &str, target: &Path) -> String {
+    let name = target.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+    let stem = target.file_stem().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+    let now = crate::app::core::clock::now_local();
+    text.replace("{{name}}", &name)
+        .replace("{{stem}}", &stem)
+        .replace("{{date}}", &now.format("%Y-%m-%d").to_string())
+        .replace("{{time}}", &now.format("%H:%M").to_string())
+        .replace("{{year}}", &now.format("%Y").to_string())
+}
+
+/// Create `target` as a copy of `template`, a file or a directory tree,
+/// with the placeholders of `expand_placeholders` replaced in text files
+/// and in the names inside a directory template (all for `target`'s
+/// name). Binary files are copied as they are. An existing `target` is
+/// not overwritten.
+pub fn create_from_template(template: &Path, target: &Path) -> Result<(), CreateError> {
+    if target.symlink_metadata().is_ok() {
+        return Err(CreateError::AlreadyExists(target.to_path_buf()));
+    }
+    crate::fs_op::helpers::ensure_parent_exists(target)?;
+    let res = instantiate(template, target, target).map_err(CreateError::Io);
+    crate::logging::log_fs_op("create from template", target, Some(template), res)
+}
+
+fn instantiate(template: &Path, target: &Path, named: &Path) -> io::Result<()> {
+    if template.is_dir() {
+        std::fs::create_dir(target)?;
+        for entry in std::fs::read_dir(template)? {
+            let entry = entry?;
+            let name = expand_placeholders(&entry.file_name().to_string_lossy(), named);
+            instantiate(&entry.path(), &target.join(name), named)?;
+        }
+        return Ok(());
+    }
+    let data = std::fs::read(template)?;
+    match String::from_utf8(data) {
+        Ok(text) => crate::fs_op::helpers::atomic_write(target, expand_placeholders(&text, named).as_bytes()),
+        Err(e) => crate::fs_op::helpers::atomic_write(target, e.as_bytes()),
+    }
 }
 
 #[cfg(test)]
@@ -72,6 +119,26 @@ mod tests {
         let _ = fs::remove_dir_all(&base);
     }
 
+    #[test]
+    fn templates_are_copied_with_placeholders() {
+        let tmp = tempfile::tempdir().unwrap();
+        let templates = tmp.path().join("templates");
+        fs::create_dir_all(templates.join("crate/src")).unwrap();
+        fs::write(templates.join("script.sh"), "#!/bin/sh\n# {{name}} ({{stem}})\n").unwrap();
+        fs::write(templates.join("crate/src/{{name}}.rs"), "// {{name}}\n").unwrap();
+        fs::write(templates.join("crate/blob"), [0xff, 0xfe, b'{']).unwrap();
+
+        let script = tmp.path().join("build.sh");
+        create_from_template(&templates.join("script.sh"), &script).unwrap();
+        assert_eq!(fs::read_to_string(&script).unwrap(), "#!/bin/sh\n# build.sh (build)\n");
+        assert!(matches!(create_from_template(&templates.join("script.sh"), &script), Err(CreateError::AlreadyExists(_))));
+
+        let dir = tmp.path().join("tool");
+        create_from_template(&templates.join("crate"), &dir).unwrap();
+        assert_eq!(fs::read_to_string(dir.join("src/tool.rs")).unwrap(), "// tool\n");
+        assert_eq!(fs::read(dir.join("blob")).unwrap(), [0xff, 0xfe, b'{']);
+    }
+
     // Note: creating a file currently overwrites existing targets because
     // `atomic_write` writes a temp file then renames into place. Overwriting
     // behavior is intentional in some flows; do not assert an "already
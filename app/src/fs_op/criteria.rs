@@ -0,0 +1,167 @@
+//! Criteria for marking files by age and size ("Select by criteria").
+//!
+//! `+` or `:select` reads them as words, all of which must hold:
+//!
+//! - `older AGE` and `newer AGE`: modified before or after AGE ago, where
+//!   AGE is a number with `h`, `d`, `w` or `y` (`30d`), or a local date
+//!   `YYYY-MM-DD [HH:MM[:SS]]` as in the change-times dialog. Both
+//!   together give a range.
+//! - `>SIZE`, `>=SIZE`, `<SIZE`, `<=SIZE` and `=SIZE`, optionally after
+//!   `size`: SIZE in bytes or with `k`, `M`, `G` or `T` (powers of 1024,
+//!   `B` optional).
+//!
+//! Only files are marked; directories never match.
+
+use std::time::{Duration, SystemTime};
+
+use crate::app::types::Entry;
+
+/// How a file's size compares to a limit.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SizeCmp {
+    Less,
+    AtMost,
+    Equal,
+    AtLeast,
+    Greater,
+}
+
+/// Parsed criteria; an entry matches when it meets all of them.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Criteria {
+    /// Modified after this time (`newer`).
+    pub newer: Option<SystemTime>,
+    /// Modified before this time (`older`).
+    pub older: Option<SystemTime>,
+    pub sizes: Vec<(SizeCmp, u64)>,
+}
+
+impl Criteria {
+    /// Read `spec` with ages counted back from `now`.
+    pub fn parse(spec: &str, now: SystemTime) -> Result<Self, String> {
+        let mut criteria = Criteria::default();
+        let mut words = spec.split_whitespace().peekable();
+        while let Some(word) = words.next() {
+            match word.to_ascii_lowercase().as_str() {
+                which @ ("older" | "newer") => {
+                    let age = words.next().ok_or_else(|| format!("`{}` needs an age such as 30d or a date", which))?;
+                    // A date may be followed by its time of day.
+                    let time = words.next_if(|w| w.contains(':'));
+                    let at = point_in_time(age, time, now)?;
+                    *if which == "older" { &mut criteria.older } else { &mut criteria.newer } = Some(at);
+                }
+                "size" => {}
+                _ => criteria.sizes.push(parse_size_cmp(word)?),
+            }
+        }
+        if criteria == Criteria::default() {
+            return Err("Give an age (`older 30d`, `newer 2024-01-01`) or a size (`>10M`)".to_string());
+        }
+        Ok(criteria)
+    }
+
+    /// Whether `entry` is a file meeting every criterion. Files without a
+    /// modification time fail age criteria.
+    pub fn matches(&self, entry: &Entry) -> bool {
+        if entry.is_dir {
+            return false;
+        }
+        let modified = entry.modified.map(SystemTime::from);
+        let time_ok = |limit: Option<SystemTime>, newer: bool| match (limit, modified) {
+            (None, _) => true,
+            (Some(_), None) => false,
+            (Some(limit), Some(m)) => if newer { m > limit } else { m < limit },
+        };
+        time_ok(self.newer, true)
+            && time_ok(self.older, false)
+            && self.sizes.iter().all(|&(cmp, limit)| match cmp {
+                SizeCmp::Less => entry.size < limit,
+                SizeCmp::AtMost => entry.size <= limit,
+                SizeCmp::Equal => entry.size == limit,
+                SizeCmp::AtLeast => entry.size >= limit,
+                SizeCmp::Greater => entry.size > limit,
+            })
+    }
+}
+
+/// `age` ago from `now` (`30d`), or the date `age` with the optional
+/// `time` of day.
+fn point_in_time(age: &str, time: Option<&str>, now: SystemTime) -> Result<SystemTime, String> {
+    let split = age.find(|c: char| !c.is_ascii_digit()).unwrap_or(age.len());
+    let (count, unit) = age.split_at(split);
+    let hours = match unit {
+        "h" => Some(1),
+        "d" => Some(24),
+        "w" => Some(24 * 7),
+        "y" => Some(24 * 365),
+        _ => None,
+    };
+    if let (Ok(count), Some(hours), None) = (count.parse::<u64>(), hours, time) {
+        let ago = Duration::from_secs(count.saturating_mul(hours * 3600));
+        return Ok(now.checked_sub(ago).unwrap_or(SystemTime::UNIX_EPOCH));
+    }
+    let date = match time {
+        Some(time) => format!("{} {}", age, time),
+        None => age.to_string(),
+    };
+    super::metadata::parse_time(&date)
+        .map(|t| SystemTime::UNIX_EPOCH + Duration::from_secs(t.unix_seconds().max(0) as u64))
+        .map_err(|_| format!("`{}` is not an age such as 30d or a date YYYY-MM-DD [HH:MM[:SS]]", date))
+}
+
+/// A size comparison such as `>10M`.
+fn parse_size_cmp(word: &str) -> Result<(SizeCmp, u64), String> {
+    let invalid = || format!("`{}` is not a criterion: use older/newer AGE or a size such as >10M", word);
+    let (cmp, size) = [(">=", SizeCmp::AtLeast), ("<=", SizeCmp::AtMost), (">", SizeCmp::Greater), ("<", SizeCmp::Less), ("=", SizeCmp::Equal)]
+        .into_iter()
+        .find_map(|(op, cmp)| word.strip_prefix(op).map(|rest| (cmp, rest)))
+        .ok_or_else(invalid)?;
+    let size = size.strip_suffix(['b', 'B']).unwrap_or(size);
+    let split = size.find(|c: char| !c.is_ascii_digit() && c != '.').unwrap_or(size.len());
+    let (number, unit) = size.split_at(split);
+    let power = match unit.to_ascii_lowercase().as_str() {
+        "" => 0,
+        "k" => 1,
+        "m" => 2,
+        "g" => 3,
+        "t" => 4,
+        _ => return Err(invalid()),
+    };
+    let number: f64 = number.parse().map_err(|_| invalid())?;
+    Ok((cmp, (number * 1024f64.powi(power)) as u64))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(size: u64, age_days: u64, now: SystemTime) -> Entry {
+        let modified = now - Duration::from_secs(age_days * 86_400);
+        Entry::file("f", std::path::PathBuf::from("/f"), size, Some(modified.into()))
+    }
+
+    #[test]
+    fn parses_ages_ranges_and_sizes() {
+        let now = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let old_big = file(20 << 20, 40, now);
+        let new_small = file(100, 2, now);
+
+        let older = Criteria::parse("older 30d", now).unwrap();
+        assert!(older.matches(&old_big) && !older.matches(&new_small));
+        let range = Criteria::parse("newer 1w older 1d", now).unwrap();
+        assert!(range.matches(&new_small) && !range.matches(&old_big));
+        let sizes = Criteria::parse("size >=10M <1.5G", now).unwrap();
+        assert_eq!(sizes.sizes, [(SizeCmp::AtLeast, 10 << 20), (SizeCmp::Less, 1536 << 20)]);
+        assert!(sizes.matches(&old_big) && !sizes.matches(&new_small));
+        assert!(Criteria::parse("=100b", now).unwrap().matches(&new_small));
+        assert!(Criteria::parse("newer 2000-01-01 00:00", now).unwrap().matches(&old_big));
+
+        let mut dir = old_big.clone();
+        dir.is_dir = true;
+        assert!(!older.matches(&dir));
+        assert!(Criteria::parse("", now).is_err());
+        assert!(Criteria::parse("older", now).is_err());
+        assert!(Criteria::parse("big", now).is_err());
+        assert!(Criteria::parse(">10Q", now).is_err());
+    }
+}
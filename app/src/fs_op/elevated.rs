@@ -0,0 +1,211 @@
+//! Running a single file operation with root privileges.
+//!
+//! When an operation fails with permission denied, the error dialog and the
+//! editor's save can run it again through a privileged helper: this binary,
+//! started under `sudo` (or `pkexec` without sudo) with `--elevated-op` and
+//! one `ElevatedOp` as arguments. The helper performs that operation with
+//! the usual `fs_op` functions and exits. It reads no settings, takes no
+//! other input and runs no shell, so what runs as root is exactly the
+//! operation the user asked for. The terminal is handed over while the
+//! helper runs so sudo can ask for a password.
+
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+
+/// Flag starting the privileged helper.
+pub const HELPER_FLAG: &str = "--elevated-op";
+
+/// An operation the privileged helper can run.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ElevatedOp {
+    Remove(PathBuf),
+    Rename { from: PathBuf, to: PathBuf },
+    Copy { from: PathBuf, to: PathBuf },
+    Move { from: PathBuf, to: PathBuf },
+    CreateFile(PathBuf),
+    CreateDir(PathBuf),
+    /// Replace the contents of `to` with those of `from`, keeping the owner
+    /// and mode of `to` (how the editor saves a file it may not write).
+    WriteFrom { from: PathBuf, to: PathBuf },
+}
+
+impl ElevatedOp {
+    /// The helper's arguments after `HELPER_FLAG`: the operation's name,
+    /// then its paths.
+    pub fn to_args(&self) -> Vec<OsString> {
+        let (name, paths): (&str, Vec<&Path>) = match self {
+            ElevatedOp::Remove(p) => ("remove", vec![p]),
+            ElevatedOp::Rename { from, to } => ("rename", vec![from, to]),
+            ElevatedOp::Copy { from, to } => ("copy", vec![from, to]),
+            ElevatedOp::Move { from, to } => ("move", vec![from, to]),
+            ElevatedOp::CreateFile(p) => ("create-file", vec![p]),
+            ElevatedOp::CreateDir(p) => ("create-dir", vec![p]),
+            ElevatedOp::WriteFrom { from, to } => ("write-from", vec![from, to]),
+        };
+        std::iter::once(OsString::from(name)).chain(paths.into_iter().map(OsString::from)).collect()
+    }
+
+    /// Parse the helper's arguments (see `to_args`).
+    pub fn from_args(args: &[OsString]) -> Result<Self, String> {
+        let (name, paths) = args.split_first().ok_or("no operation given")?;
+        let paths: Vec<PathBuf> = paths.iter().map(PathBuf::from).collect();
+        let name = name.to_string_lossy();
+        let op = match (name.as_ref(), paths.as_slice()) {
+            ("remove", [p]) => ElevatedOp::Remove(p.clone()),
+            ("create-file", [p]) => ElevatedOp::CreateFile(p.clone()),
+            ("create-dir", [p]) => ElevatedOp::CreateDir(p.clone()),
+            ("rename", [from, to]) => ElevatedOp::Rename { from: from.clone(), to: to.clone() },
+            ("copy", [from, to]) => ElevatedOp::Copy { from: from.clone(), to: to.clone() },
+            ("move", [from, to]) => ElevatedOp::Move { from: from.clone(), to: to.clone() },
+            ("write-from", [from, to]) => ElevatedOp::WriteFrom { from: from.clone(), to: to.clone() },
+            _ => return Err(format!("unknown operation `{}` with {} paths", name, paths.len())),
+        };
+        Ok(op)
+    }
+
+    /// Run the operation in this process.
+    fn run_here(&self) -> Result<(), String> {
+        use crate::fs_op::{create, mv, remove};
+        let res = match self {
+            ElevatedOp::Remove(p) => remove::remove_path(p).map_err(|e| e.to_string()),
+            ElevatedOp::Rename { from, to } => crate::logging::log_fs_op("rename", from, Some(to), std::fs::rename(from, to)).map_err(|e| e.to_string()),
+            ElevatedOp::Copy { from, to } => mv::copy_path(from, to).map_err(|e| e.to_string()),
+            ElevatedOp::Move { from, to } => mv::move_path(from, to).map_err(|e| e.to_string()),
+            ElevatedOp::CreateFile(p) => create::create_file(p).map_err(|e| e.to_string()),
+            ElevatedOp::CreateDir(p) => create::create_dir_all(p).map_err(|e| e.to_string()),
+            ElevatedOp::WriteFrom { from, to } => crate::logging::log_fs_op("write", to, None, write_into(from, to)).map_err(|e| e.to_string()),
+        };
+        res.map_err(|e| format!("{}: {}", self, e))
+    }
+}
+
+/// Replace the contents of the existing file `to` with those of `from`.
+/// `to` is truncated and written in place rather than replaced, so the file
+/// keeps its inode and with it its owner, mode and ACLs (`fs::copy` would
+/// give it the permissions of `from`).
+fn write_into(from: &Path, to: &Path) -> std::io::Result<()> {
+    let mut src = std::fs::File::open(from)?;
+    let mut dst = std::fs::OpenOptions::new().write(true).truncate(true).open(to)?;
+    std::io::copy(&mut src, &mut dst)?;
+    dst.sync_all()
+}
+
+impl std::fmt::Display for ElevatedOp {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ElevatedOp::Remove(p) => write!(f, "delete {}", p.display()),
+            ElevatedOp::Rename { from, to } => write!(f, "rename {} to {}", from.display(), to.display()),
+            ElevatedOp::Copy { from, to } => write!(f, "copy {} to {}", from.display(), to.display()),
+            ElevatedOp::Move { from, to } => write!(f, "move {} to {}", from.display(), to.display()),
+            ElevatedOp::CreateFile(p) => write!(f, "create {}", p.display()),
+            ElevatedOp::CreateDir(p) => write!(f, "create directory {}", p.display()),
+            ElevatedOp::WriteFrom { to, .. } => write!(f, "save {}", to.display()),
+        }
+    }
+}
+
+/// Entry point of the privileged helper: run the operation `args` describe
+/// and return the process exit code. Errors go to stderr, where `run`
+/// picks them up.
+pub fn run_helper(args: &[OsString]) -> i32 {
+    match ElevatedOp::from_args(args).and_then(|op| op.run_here()) {
+        Ok(()) => 0,
+        Err(e) => {
+            eprintln!("{}", e);
+            1
+        }
+    }
+}
+
+/// `sudo`, or else `pkexec`, when one is on `PATH`.
+fn elevation_tool() -> Option<PathBuf> {
+    let path = std::env::var_os("PATH")?;
+    ["sudo", "pkexec"].iter().find_map(|tool| std::env::split_paths(&path).map(|dir| dir.join(tool)).find(|p| p.is_file()))
+}
+
+/// Whether operations can be retried as root: on Unix, when not root
+/// already and `sudo` or `pkexec` is installed.
+pub fn available() -> bool {
+    #[cfg(unix)]
+    {
+        users::get_effective_uid() != 0 && elevation_tool().is_some()
+    }
+    #[cfg(not(unix))]
+    {
+        false
+    }
+}
+
+/// Run `op` as root through the privileged helper, handing the terminal to
+/// `sudo`/`pkexec` meanwhile. The error says why the helper failed, or that
+/// it could not be started.
+pub fn run(op: &ElevatedOp) -> Result<(), String> {
+    let tool = elevation_tool().ok_or("neither sudo nor pkexec is installed")?;
+    let exe = std::env::current_exe().map_err(|e| format!("cannot find the fileZoom binary: {}", e))?;
+    let mut cmd = std::process::Command::new(&tool);
+    if tool.ends_with("sudo") {
+        cmd.arg("--");
+    }
+    cmd.arg(exe).arg(HELPER_FLAG).args(op.to_args()).stderr(std::process::Stdio::piped());
+
+    tracing::info!(tool = %tool.display(), %op, "running as root");
+    let output = crate::runner::terminal::outside_tui(|| cmd.output()).map_err(|e| format!("cannot run {}: {}", tool.display(), e))?;
+    if output.status.success() {
+        return Ok(());
+    }
+    let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+    let err = if stderr.is_empty() { format!("{} as root failed ({})", op, output.status) } else { stderr };
+    tracing::warn!(%op, error = %err, "running as root failed");
+    Err(err)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ops_survive_the_helper_arguments() {
+        let ops = [
+            ElevatedOp::Remove("/etc/-odd name".into()),
+            ElevatedOp::Rename { from: "/a".into(), to: "/b".into() },
+            ElevatedOp::WriteFrom { from: "/tmp/x".into(), to: "/etc/hosts".into() },
+        ];
+        for op in ops {
+            assert_eq!(ElevatedOp::from_args(&op.to_args()), Ok(op));
+        }
+        assert!(ElevatedOp::from_args(&["remove".into()]).is_err());
+        assert!(ElevatedOp::from_args(&["chmod".into(), "/a".into()]).is_err());
+    }
+
+    #[test]
+    fn helper_runs_the_operation() {
+        let tmp = tempfile::tempdir().unwrap();
+        let src = tmp.path().join("new.txt");
+        let dst = tmp.path().join("target.txt");
+        std::fs::write(&src, "new").unwrap();
+        std::fs::write(&dst, "old").unwrap();
+
+        assert_eq!(run_helper(&ElevatedOp::WriteFrom { from: src.clone(), to: dst.clone() }.to_args()), 0);
+        assert_eq!(std::fs::read_to_string(&dst).unwrap(), "new");
+        assert_eq!(run_helper(&ElevatedOp::Remove(src.clone()).to_args()), 0);
+        assert!(!src.exists());
+        assert_eq!(run_helper(&["bogus".into()]), 1);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn write_from_keeps_the_mode_of_the_target() {
+        use std::os::unix::fs::PermissionsExt;
+        let tmp = tempfile::tempdir().unwrap();
+        let src = tmp.path().join("edited");
+        let dst = tmp.path().join("hosts");
+        std::fs::write(&src, "127.0.0.1 new").unwrap();
+        std::fs::set_permissions(&src, std::fs::Permissions::from_mode(0o600)).unwrap();
+        std::fs::write(&dst, "127.0.0.1 old").unwrap();
+        std::fs::set_permissions(&dst, std::fs::Permissions::from_mode(0o644)).unwrap();
+
+        assert_eq!(run_helper(&ElevatedOp::WriteFrom { from: src, to: dst.clone() }.to_args()), 0);
+        assert_eq!(std::fs::read_to_string(&dst).unwrap(), "127.0.0.1 new");
+        assert_eq!(std::fs::metadata(&dst).unwrap().permissions().mode() & 0o777, 0o644);
+    }
+}
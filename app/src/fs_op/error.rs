@@ -2,6 +2,20 @@ use std::path::PathBuf;
 
 use thiserror::Error;
 
+/// Whether trying again after `e` can succeed once the user intervened:
+/// permission denied (fix the permissions), or a file in use by another
+/// process (close it). Error dialogs offer Retry for these.
+pub fn is_recoverable(e: &std::io::Error) -> bool {
+    use std::io::ErrorKind;
+    // ERROR_SHARING_VIOLATION and ERROR_LOCK_VIOLATION: the file is open
+    // elsewhere.
+    #[cfg(windows)]
+    if matches!(e.raw_os_error(), Some(32 | 33)) {
+        return true;
+    }
+    matches!(e.kind(), ErrorKind::PermissionDenied | ErrorKind::ResourceBusy | ErrorKind::ExecutableFileBusy)
+}
+
 /// Crate-wide error type for filesystem operations.
 ///
 /// This error centralises common filesystem-related failure modes so
@@ -43,6 +57,18 @@ impl From<&str> for FsOpError {
 }
 
 impl FsOpError {
+    /// Whether the error may go away when the operation is tried again
+    /// (see `is_recoverable`).
+    pub fn is_recoverable(&self) -> bool {
+        matches!(self, FsOpError::Io(e) if is_recoverable(e))
+    }
+
+    /// Whether permission was denied (EACCES/EPERM), so running the
+    /// operation as root may succeed.
+    pub fn is_permission_denied(&self) -> bool {
+        matches!(self, FsOpError::Io(e) if e.kind() == std::io::ErrorKind::PermissionDenied)
+    }
+
     /// Convenience constructor for `PathContext` errors.
     pub fn path_context<S: Into<String>, P: Into<PathBuf>>(src: P, dst: P, msg: S) -> Self {
         FsOpError::PathContext {
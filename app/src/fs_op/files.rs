@@ -20,7 +20,7 @@
 pub use crate::fs_op::create::{create_dir_all, create_file};
 
 /// Move/copy/rename helpers.
-pub use crate::fs_op::mv::{copy_path, move_path, rename_path};
+pub use crate::fs_op::mv::{copy_path, copy_path_with, move_path, rename_path};
 
 /// Permission helpers and related types.
 pub use crate::fs_op::permissions::{
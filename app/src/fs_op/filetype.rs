@@ -0,0 +1,308 @@
+//! File type detection from content ("magic bytes").
+//!
+//! `sniff` matches the first `SNIFF_BYTES` of a file against a table of
+//! signatures in the spirit of libmagic's: images, archives and compressed
+//! files, audio and video, documents, executables and fonts, with a few
+//! containers (RIFF, ISO media, ZIP) told apart by what follows their
+//! magic. Data that no signature matches is text when it decodes (see
+//! `app::core::encoding`), with scripts, HTML, XML, SVG and JSON spotted by
+//! how they start, and `application/octet-stream` otherwise.
+//!
+//! The preview refuses binary types even when their bytes happen to decode,
+//! listings colour files by their detected type when the extension says
+//! nothing (`detect_cached`), and the info panel shows the MIME type.
+
+use std::collections::HashMap;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use chrono::{DateTime, Local};
+use once_cell::sync::Lazy;
+
+use super::vfs::{LocalFs, VfsProvider};
+use crate::app::types::Entry;
+
+/// Bytes looked at to detect a type.
+pub const SNIFF_BYTES: usize = 4096;
+
+/// Detected types kept by `detect_cached` before it starts over.
+const CACHE_LIMIT: usize = 8192;
+
+/// Broad class of a detected type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TypeClass {
+    Text,
+    Image,
+    Archive,
+    Audio,
+    Video,
+    Document,
+    Executable,
+    Font,
+    Data,
+}
+
+/// A detected file type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct FileType {
+    pub mime: &'static str,
+    /// Short human-readable name, e.g. `"PNG image"`.
+    pub description: &'static str,
+    pub class: TypeClass,
+    /// The content is text (SVG images and scripts included).
+    pub text: bool,
+}
+
+const fn binary(mime: &'static str, description: &'static str, class: TypeClass) -> FileType {
+    FileType { mime, description, class, text: false }
+}
+
+const fn text(mime: &'static str, description: &'static str) -> FileType {
+    FileType { mime, description, class: TypeClass::Text, text: true }
+}
+
+/// `application/octet-stream`: not text and no signature matched.
+pub const DATA: FileType = binary("application/octet-stream", "data", TypeClass::Data);
+
+/// An empty file.
+pub const EMPTY: FileType = text("inode/x-empty", "empty");
+
+/// Plain text.
+pub const PLAIN_TEXT: FileType = text("text/plain", "text");
+
+/// Signatures found at a fixed offset: `(offset, magic, type)`.
+const SIGNATURES: &[(usize, &[u8], FileType)] = &[
+    (0, b"\x89PNG\r\n\x1a\n", binary("image/png", "PNG image", TypeClass::Image)),
+    (0, b"\xff\xd8\xff", binary("image/jpeg", "JPEG image", TypeClass::Image)),
+    (0, b"GIF87a", binary("image/gif", "GIF image", TypeClass::Image)),
+    (0, b"GIF89a", binary("image/gif", "GIF image", TypeClass::Image)),
+    (0, b"II*\0", binary("image/tiff", "TIFF image", TypeClass::Image)),
+    (0, b"MM\0*", binary("image/tiff", "TIFF image", TypeClass::Image)),
+    (0, b"\0\0\x01\0", binary("image/vnd.microsoft.icon", "Windows icon", TypeClass::Image)),
+    (0, b"8BPS", binary("image/vnd.adobe.photoshop", "Photoshop image", TypeClass::Image)),
+    (0, b"\x1f\x8b", binary("application/gzip", "gzip compressed data", TypeClass::Archive)),
+    (0, b"BZh", binary("application/x-bzip2", "bzip2 compressed data", TypeClass::Archive)),
+    (0, b"\xfd7zXZ\0", binary("application/x-xz", "XZ compressed data", TypeClass::Archive)),
+    (0, b"\x28\xb5\x2f\xfd", binary("application/zstd", "Zstandard compressed data", TypeClass::Archive)),
+    (0, b"\x04\x22\x4d\x18", binary("application/x-lz4", "LZ4 compressed data", TypeClass::Archive)),
+    (0, b"7z\xbc\xaf\x27\x1c", binary("application/x-7z-compressed", "7-Zip archive", TypeClass::Archive)),
+    (0, b"Rar!\x1a\x07", binary("application/vnd.rar", "RAR archive", TypeClass::Archive)),
+    (0, b"MSCF\0\0\0\0", binary("application/vnd.ms-cab-compressed", "Cabinet archive", TypeClass::Archive)),
+    (0, b"\xed\xab\xee\xdb", binary("application/x-rpm", "RPM package", TypeClass::Archive)),
+    (0, b"!<arch>\ndebian-binary", binary("application/vnd.debian.binary-package", "Debian package", TypeClass::Archive)),
+    (0, b"!<arch>\n", binary("application/x-archive", "ar archive", TypeClass::Archive)),
+    (257, b"ustar", binary("application/x-tar", "tar archive", TypeClass::Archive)),
+    (0, b"%PDF-", binary("application/pdf", "PDF document", TypeClass::Document)),
+    (0, b"%!PS", binary("application/postscript", "PostScript document", TypeClass::Document)),
+    (0, b"\xd0\xcf\x11\xe0\xa1\xb1\x1a\xe1", binary("application/x-ole-storage", "Microsoft Office document", TypeClass::Document)),
+    (0, b"SQLite format 3\0", binary("application/vnd.sqlite3", "SQLite database", TypeClass::Data)),
+    (0, b"MZ", binary("application/vnd.microsoft.portable-executable", "PE executable", TypeClass::Executable)),
+    (0, b"\xcf\xfa\xed\xfe", binary("application/x-mach-binary", "Mach-O binary", TypeClass::Executable)),
+    (0, b"\xce\xfa\xed\xfe", binary("application/x-mach-binary", "Mach-O binary", TypeClass::Executable)),
+    (0, b"\xfe\xed\xfa\xcf", binary("application/x-mach-binary", "Mach-O binary", TypeClass::Executable)),
+    (0, b"\0asm", binary("application/wasm", "WebAssembly module", TypeClass::Executable)),
+    (0, b"fLaC", binary("audio/flac", "FLAC audio", TypeClass::Audio)),
+    (0, b"OggS", binary("audio/ogg", "Ogg media", TypeClass::Audio)),
+    (0, b"ID3", binary("audio/mpeg", "MP3 audio", TypeClass::Audio)),
+    (0, b"\xff\xfb", binary("audio/mpeg", "MP3 audio", TypeClass::Audio)),
+    (0, b"\xff\xf3", binary("audio/mpeg", "MP3 audio", TypeClass::Audio)),
+    (0, b"MThd", binary("audio/midi", "MIDI audio", TypeClass::Audio)),
+    (0, b"\x1a\x45\xdf\xa3", binary("video/x-matroska", "Matroska video", TypeClass::Video)),
+    (0, b"FLV\x01", binary("video/x-flv", "Flash video", TypeClass::Video)),
+    (0, b"wOFF", binary("font/woff", "WOFF font", TypeClass::Font)),
+    (0, b"wOF2", binary("font/woff2", "WOFF2 font", TypeClass::Font)),
+    (0, b"OTTO", binary("font/otf", "OpenType font", TypeClass::Font)),
+    (0, b"\0\x01\0\0\0", binary("font/ttf", "TrueType font", TypeClass::Font)),
+];
+
+/// Detect the type of a file from its first bytes (up to `SNIFF_BYTES`).
+pub fn sniff(head: &[u8]) -> FileType {
+    let head = &head[..head.len().min(SNIFF_BYTES)];
+    if head.is_empty() {
+        return EMPTY;
+    }
+    if let Some(found) = container(head) {
+        return found;
+    }
+    if let Some(&(_, _, found)) = SIGNATURES.iter().find(|(at, magic, _)| head.get(*at..).is_some_and(|h| h.starts_with(magic))) {
+        return found;
+    }
+    match crate::app::core::encoding::decode(head, None) {
+        Some(decoded) => text_type(&decoded.text, head.len() == SNIFF_BYTES),
+        None => DATA,
+    }
+}
+
+/// Types whose magic is shared by several formats: RIFF, ISO media, ZIP,
+/// ELF, `0xCAFEBABE` (Java class or Mach-O universal binary) and BMP.
+fn container(head: &[u8]) -> Option<FileType> {
+    let at = |start: usize, magic: &[u8]| head.get(start..).is_some_and(|h| h.starts_with(magic));
+    if at(0, b"RIFF") {
+        return match head.get(8..12)? {
+            b"WEBP" => Some(binary("image/webp", "WebP image", TypeClass::Image)),
+            b"WAVE" => Some(binary("audio/wav", "WAV audio", TypeClass::Audio)),
+            b"AVI " => Some(binary("video/x-msvideo", "AVI video", TypeClass::Video)),
+            _ => None,
+        };
+    }
+    if at(4, b"ftyp") {
+        return Some(match head.get(8..12)? {
+            b"M4A " | b"M4B " => binary("audio/mp4", "MPEG-4 audio", TypeClass::Audio),
+            b"qt  " => binary("video/quicktime", "QuickTime video", TypeClass::Video),
+            b"heic" | b"heix" | b"mif1" => binary("image/heic", "HEIF image", TypeClass::Image),
+            b"avif" => binary("image/avif", "AVIF image", TypeClass::Image),
+            brand if brand.starts_with(b"3g") => binary("video/3gpp", "3GPP video", TypeClass::Video),
+            _ => binary("video/mp4", "MPEG-4 video", TypeClass::Video),
+        });
+    }
+    if at(0, b"PK\x03\x04") || at(0, b"PK\x05\x06") {
+        let contains = |needle: &[u8]| head.windows(needle.len()).any(|w| w == needle);
+        return Some(if at(30, b"mimetypeapplication/epub+zip") {
+            binary("application/epub+zip", "EPUB document", TypeClass::Document)
+        } else if at(30, b"mimetypeapplication/vnd.oasis.opendocument.") {
+            binary("application/vnd.oasis.opendocument", "OpenDocument file", TypeClass::Document)
+        } else if contains(b"word/") {
+            binary("application/vnd.openxmlformats-officedocument.wordprocessingml.document", "Word document", TypeClass::Document)
+        } else if contains(b"xl/") {
+            binary("application/vnd.openxmlformats-officedocument.spreadsheetml.sheet", "Excel spreadsheet", TypeClass::Document)
+        } else if contains(b"ppt/") {
+            binary("application/vnd.openxmlformats-officedocument.presentationml.presentation", "PowerPoint presentation", TypeClass::Document)
+        } else if contains(b"META-INF/MANIFEST.MF") {
+            binary("application/java-archive", "Java archive", TypeClass::Archive)
+        } else {
+            binary("application/zip", "ZIP archive", TypeClass::Archive)
+        });
+    }
+    if at(0, b"\x7fELF") {
+        return Some(match head.get(16..18)? {
+            [1, 0] | [0, 1] => binary("application/x-object", "ELF relocatable object", TypeClass::Executable),
+            [3, 0] | [0, 3] => binary("application/x-sharedlib", "ELF shared object", TypeClass::Executable),
+            [4, 0] | [0, 4] => binary("application/x-coredump", "ELF core dump", TypeClass::Data),
+            _ => binary("application/x-executable", "ELF executable", TypeClass::Executable),
+        });
+    }
+    if at(0, b"\xca\xfe\xba\xbe") {
+        // A universal binary counts its architectures where a class file
+        // has its version, which starts at 45.
+        let major = u16::from_be_bytes(head.get(6..8)?.try_into().ok()?);
+        return Some(match major >= 45 {
+            true => binary("application/java-vm", "Java class", TypeClass::Executable),
+            false => binary("application/x-mach-binary", "Mach-O universal binary", TypeClass::Executable),
+        });
+    }
+    if at(0, b"BM") {
+        let header = u32::from_le_bytes(head.get(14..18)?.try_into().ok()?);
+        return matches!(header, 12 | 40 | 52 | 56 | 108 | 124).then_some(binary("image/bmp", "BMP image", TypeClass::Image));
+    }
+    None
+}
+
+/// The kind of text `content` is; `truncated` when it is only the start
+/// of the file.
+fn text_type(content: &str, truncated: bool) -> FileType {
+    let start = content.trim_start_matches('\u{feff}').trim_start();
+    if let Some(shebang) = start.strip_prefix("#!") {
+        let interpreter = shebang.lines().next().unwrap_or("");
+        return if interpreter.contains("python") {
+            text("text/x-python", "Python script")
+        } else if interpreter.contains("perl") {
+            text("text/x-perl", "Perl script")
+        } else if interpreter.contains("node") {
+            text("text/javascript", "JavaScript script")
+        } else if ["/sh", "bash", "zsh", "dash", "ksh", "env sh"].iter().any(|s| interpreter.contains(s)) {
+            text("text/x-shellscript", "shell script")
+        } else {
+            text("text/x-script", "script")
+        };
+    }
+    let lower: String = start.chars().take(256).collect::<String>().to_ascii_lowercase();
+    if lower.starts_with("<!doctype html") || lower.starts_with("<html") {
+        return text("text/html", "HTML document");
+    }
+    if lower.starts_with("<?xml") || lower.starts_with("<svg") {
+        return match lower.contains("<svg") || content.contains("<svg") {
+            true => FileType { class: TypeClass::Image, ..text("image/svg+xml", "SVG image") },
+            false => text("application/xml", "XML document"),
+        };
+    }
+    if start.starts_with(['{', '[']) {
+        match serde_json::from_str::<serde::de::IgnoredAny>(content) {
+            Ok(_) => return text("application/json", "JSON data"),
+            Err(e) if truncated && e.is_eof() => return text("application/json", "JSON data"),
+            Err(_) => {}
+        }
+    }
+    PLAIN_TEXT
+}
+
+/// Detect the type of `path`, read through `vfs`.
+pub fn detect_with(vfs: &dyn VfsProvider, path: &Path) -> io::Result<FileType> {
+    let mut head = Vec::with_capacity(SNIFF_BYTES);
+    vfs.open(path)?.take(SNIFF_BYTES as u64).read_to_end(&mut head)?;
+    Ok(sniff(&head))
+}
+
+/// Detect the type of the local file `path`.
+pub fn detect(path: &Path) -> io::Result<FileType> {
+    detect_with(&LocalFs, path)
+}
+
+/// Detected types of local files, with the size and modification time
+/// they were detected at.
+type Cache = HashMap<PathBuf, (u64, Option<DateTime<Local>>, Option<FileType>)>;
+
+static CACHE: Lazy<Mutex<Cache>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// The detected type of the local file `entry`, remembered until its size
+/// or modification time changes. `None` for directories, symlinks and
+/// files that cannot be read.
+pub fn detect_cached(entry: &Entry) -> Option<FileType> {
+    if entry.is_dir || entry.is_symlink {
+        return None;
+    }
+    let key = (entry.size, entry.modified);
+    let mut cache = CACHE.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some((size, modified, found)) = cache.get(&entry.path) {
+        if (*size, *modified) == key {
+            return *found;
+        }
+    }
+    let found = detect(&entry.path).ok();
+    if cache.len() >= CACHE_LIMIT {
+        cache.clear();
+    }
+    cache.insert(entry.path.clone(), (key.0, key.1, found));
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sniffs_signatures_containers_and_text() {
+        assert_eq!(sniff(b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR").mime, "image/png");
+        assert_eq!(sniff(b"%PDF-1.7\n%\xe2\xe3").mime, "application/pdf");
+        assert_eq!(sniff(b"RIFF\0\0\0\0WAVEfmt ").mime, "audio/wav");
+        assert_eq!(sniff(b"\0\0\0\x18ftypqt  \0\0\0\0").mime, "video/quicktime");
+        assert_eq!(sniff(b"\x7fELF\x02\x01\x01\0\0\0\0\0\0\0\0\0\x02\0>\0").description, "ELF executable");
+        assert_eq!(sniff(b"\xca\xfe\xba\xbe\0\0\0\x34").mime, "application/java-vm");
+        assert_eq!(sniff(b"PK\x03\x04\x14\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0word/document.xml").class, TypeClass::Document);
+
+        let mut tar = vec![0u8; 512];
+        tar[257..262].copy_from_slice(b"ustar");
+        assert_eq!(sniff(&tar).mime, "application/x-tar");
+
+        assert_eq!(sniff(b"#!/usr/bin/env python3\nprint(1)\n").mime, "text/x-python");
+        assert_eq!(sniff(b"#!/bin/sh\necho hi\n").mime, "text/x-shellscript");
+        assert_eq!(sniff(b"<!DOCTYPE html><html></html>").mime, "text/html");
+        let svg = sniff(b"<?xml version=\"1.0\"?>\n<svg xmlns=\"http://www.w3.org/2000/svg\"/>");
+        assert_eq!((svg.mime, svg.class, svg.text), ("image/svg+xml", TypeClass::Image, true));
+        assert_eq!(sniff(b"{\"a\": [1, 2]}").mime, "application/json");
+        assert_eq!(sniff(b"[section]\nkey = value\n").mime, "text/plain");
+        assert_eq!(sniff(b"Caf\xe9 cr\xe8me").mime, "text/plain");
+        assert_eq!(sniff(b""), EMPTY);
+        assert_eq!(sniff(b"\x00\x01\x02\x03\xff\xfe\x00\x80"), DATA);
+    }
+}
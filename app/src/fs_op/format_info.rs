@@ -0,0 +1,639 @@
+//! Type-specific details of a file for the info panel: entry count and
+//! compression ratio of archives, image dimensions, duration and codec of
+//! audio and video, and the target of executables.
+//!
+//! Everything comes from the file's headers (and for ZIP its central
+//! directory, for MP4 its `moov` box) read by small parsers here, so no
+//! decoder crates are needed. Formats that are not recognised, or whose
+//! headers do not make sense, get no rows. `App::poll_info` calls `read`
+//! on its worker thread.
+
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
+
+/// Bytes read from the start of a file to recognise it.
+const HEAD_BYTES: u64 = 64 * 1024;
+
+/// Largest ZIP central directory or MP4 `moov` box read.
+const MAX_INDEX_BYTES: u64 = 16 * 1024 * 1024;
+
+/// Tar headers walked before the count is given up.
+const MAX_TAR_ENTRIES: u64 = 100_000;
+
+/// `(label, value)` rows, like `FileInfo::rows`.
+pub type Rows = Vec<(&'static str, String)>;
+
+/// Details of the file at `path`, or no rows for an unknown format.
+pub fn read(path: &Path) -> Rows {
+    let Ok(mut file) = File::open(path) else {
+        return Vec::new();
+    };
+    let size = file.metadata().map_or(0, |m| m.len());
+    let mut head = Vec::new();
+    if (&mut file).take(HEAD_BYTES).read_to_end(&mut head).is_err() {
+        return Vec::new();
+    }
+    detect(&mut file, &head, size).unwrap_or_default()
+}
+
+fn detect<R: Read + Seek>(r: &mut R, head: &[u8], size: u64) -> Option<Rows> {
+    if head.starts_with(b"PK\x03\x04") || head.starts_with(b"PK\x05\x06") {
+        zip(r, size)
+    } else if head.starts_with(b"\x1f\x8b") {
+        gzip(r, size)
+    } else if head.get(257..262) == Some(b"ustar") {
+        tar(r, size)
+    } else if head.starts_with(b"\x7fELF") {
+        elf(head)
+    } else if head.starts_with(b"MZ") {
+        pe(head)
+    } else if head.starts_with(b"fLaC") {
+        flac(head)
+    } else if head.get(4..8) == Some(b"ftyp") {
+        mp4(r, head, size)
+    } else if head.starts_with(b"RIFF") && head.get(8..12) == Some(b"WAVE") {
+        wav(head)
+    } else {
+        image(head)
+    }
+}
+
+fn le16(b: &[u8], at: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(b.get(at..at + 2)?.try_into().ok()?))
+}
+
+fn le32(b: &[u8], at: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(b.get(at..at + 4)?.try_into().ok()?))
+}
+
+fn le64(b: &[u8], at: usize) -> Option<u64> {
+    Some(u64::from_le_bytes(b.get(at..at + 8)?.try_into().ok()?))
+}
+
+fn be16(b: &[u8], at: usize) -> Option<u16> {
+    Some(u16::from_be_bytes(b.get(at..at + 2)?.try_into().ok()?))
+}
+
+fn be32(b: &[u8], at: usize) -> Option<u32> {
+    Some(u32::from_be_bytes(b.get(at..at + 4)?.try_into().ok()?))
+}
+
+fn be64(b: &[u8], at: usize) -> Option<u64> {
+    Some(u64::from_be_bytes(b.get(at..at + 8)?.try_into().ok()?))
+}
+
+/// `len` bytes of `r` at `offset`.
+fn read_at<R: Read + Seek>(r: &mut R, offset: u64, len: u64) -> Option<Vec<u8>> {
+    r.seek(SeekFrom::Start(offset)).ok()?;
+    let mut buf = Vec::new();
+    r.take(len).read_to_end(&mut buf).ok()?;
+    (buf.len() as u64 == len).then_some(buf)
+}
+
+/// `unpacked` bytes and how much of it `packed` takes.
+fn unpacked(unpacked: u64, packed: u64) -> String {
+    match unpacked {
+        0 => "0 B".to_string(),
+        n => format!("{} B, compressed to {}%", n, (packed.saturating_mul(100) / n).min(999)),
+    }
+}
+
+/// `seconds` as `m:ss`, or `h:mm:ss` from an hour on.
+pub fn format_duration(seconds: f64) -> String {
+    let total = seconds.round() as u64;
+    let (h, m, s) = (total / 3600, total / 60 % 60, total % 60);
+    match h {
+        0 => format!("{}:{:02}", m, s),
+        h => format!("{}:{:02}:{:02}", h, m, s),
+    }
+}
+
+/// ZIP: entries and sizes from the central directory, found through the
+/// end-of-central-directory record (or its ZIP64 version).
+fn zip<R: Read + Seek>(r: &mut R, size: u64) -> Option<Rows> {
+    // The record is 22 bytes plus a comment of up to 64 KiB.
+    let tail_len = size.min(22 + 0xffff);
+    let tail = read_at(r, size - tail_len, tail_len)?;
+    let eocd = (0..tail.len().saturating_sub(21)).rev().find(|&i| tail[i..].starts_with(b"PK\x05\x06"))?;
+    let mut entries = u64::from(le16(&tail, eocd + 10)?);
+    let mut cd_size = u64::from(le32(&tail, eocd + 12)?);
+    let mut cd_offset = u64::from(le32(&tail, eocd + 16)?);
+    if entries == 0xffff || cd_offset == 0xffff_ffff {
+        let locator = eocd.checked_sub(20).filter(|&l| tail[l..].starts_with(b"PK\x06\x07"))?;
+        let record = read_at(r, le64(&tail, locator + 8)?, 56)?;
+        if !record.starts_with(b"PK\x06\x06") {
+            return None;
+        }
+        entries = le64(&record, 32)?;
+        cd_size = le64(&record, 40)?;
+        cd_offset = le64(&record, 48)?;
+    }
+    let mut rows = vec![("Format", "ZIP archive".to_string()), ("Entries", entries.to_string())];
+    if cd_size > MAX_INDEX_BYTES {
+        return Some(rows);
+    }
+    let cd = read_at(r, cd_offset, cd_size)?;
+    let (mut packed, mut total, mut at) = (0u64, 0u64, 0);
+    while cd.get(at..).is_some_and(|rest| rest.starts_with(b"PK\x01\x02")) {
+        let mut comp = u64::from(le32(&cd, at + 20)?);
+        let mut uncomp = u64::from(le32(&cd, at + 24)?);
+        let name_len = usize::from(le16(&cd, at + 28)?);
+        let extra_len = usize::from(le16(&cd, at + 30)?);
+        let comment_len = usize::from(le16(&cd, at + 32)?);
+        if comp == 0xffff_ffff || uncomp == 0xffff_ffff {
+            // The ZIP64 extra field holds the sizes that did not fit.
+            let start = at.checked_add(46 + name_len)?;
+            let extra = cd.get(start..start.checked_add(extra_len)?)?;
+            let mut e = 0;
+            while let (Some(id), Some(len)) = (le16(extra, e), le16(extra, e + 2)) {
+                if id == 1 {
+                    let mut field = e + 4;
+                    for value in [&mut uncomp, &mut comp] {
+                        if *value == 0xffff_ffff {
+                            *value = le64(extra, field)?;
+                            field += 8;
+                        }
+                    }
+                    break;
+                }
+                e = e.checked_add(4 + usize::from(len))?;
+            }
+        }
+        packed = packed.saturating_add(comp);
+        total = total.saturating_add(uncomp);
+        at = at.checked_add(46 + name_len + extra_len + comment_len)?;
+    }
+    rows.push(("Unpacked", unpacked(total, packed)));
+    Some(rows)
+}
+
+/// gzip: the unpacked size modulo 4 GiB is stored in the last four bytes.
+fn gzip<R: Read + Seek>(r: &mut R, size: u64) -> Option<Rows> {
+    let mut rows = vec![("Format", "gzip archive".to_string())];
+    if (18..1 << 32).contains(&size) {
+        let isize = le32(&read_at(r, size - 4, 4)?, 0)?;
+        rows.push(("Unpacked", unpacked(u64::from(isize), size)));
+    }
+    Some(rows)
+}
+
+/// tar: walk the 512-byte headers, skipping over the file data.
+fn tar<R: Read + Seek>(r: &mut R, size: u64) -> Option<Rows> {
+    let (mut entries, mut total, mut offset) = (0u64, 0u64, 0u64);
+    while offset.checked_add(512).is_some_and(|end| end <= size) && entries < MAX_TAR_ENTRIES {
+        let header = read_at(r, offset, 512)?;
+        if header.iter().all(|&b| b == 0) {
+            break;
+        }
+        let field = &header[124..136];
+        let len = if field[0] & 0x80 != 0 {
+            // GNU base-256 for sizes of 8 GiB and more.
+            field[1..].iter().try_fold(0u64, |n, &b| n.checked_mul(256).map(|n| n | u64::from(b)))?
+        } else {
+            let digits = std::str::from_utf8(field).ok()?.trim_matches(|c: char| c == '\0' || c == ' ');
+            u64::from_str_radix(digits, 8).ok()?
+        };
+        // Extended headers and long names describe the next entry.
+        if !matches!(header[156], b'x' | b'g' | b'L' | b'K') {
+            entries += 1;
+            if matches!(header[156], b'0' | b'\0' | b'7') {
+                total = total.saturating_add(len);
+            }
+        }
+        offset = len.div_ceil(512).checked_mul(512).and_then(|data| offset.checked_add(512)?.checked_add(data))?;
+    }
+    let count = if entries >= MAX_TAR_ENTRIES { format!("{}+", entries) } else { entries.to_string() };
+    Some(vec![("Format", "tar archive".to_string()), ("Entries", count), ("Unpacked", format!("{} B", total))])
+}
+
+
+/// PNG, GIF, BMP, WebP and JPEG dimensions.
+fn image(head: &[u8]) -> Option<Rows> {
+    let (format, width, height) = if head.starts_with(b"\x89PNG\r\n\x1a\n") {
+        ("PNG", be32(head, 16)?, be32(head, 20)?)
+    } else if head.starts_with(b"GIF87a") || head.starts_with(b"GIF89a") {
+        ("GIF", u32::from(le16(head, 6)?), u32::from(le16(head, 8)?))
+    } else if head.starts_with(b"BM") && le32(head, 14)? >= 40 {
+        // A negative height means the rows are stored top-down.
+        ("BMP", le32(head, 18)?, (le32(head, 22)? as i32).unsigned_abs())
+    } else if head.starts_with(b"RIFF") && head.get(8..12) == Some(b"WEBP") {
+        let (width, height) = match head.get(12..16)? {
+            b"VP8 " => (u32::from(le16(head, 26)? & 0x3fff), u32::from(le16(head, 28)? & 0x3fff)),
+            b"VP8L" => {
+                let bits = le32(head, 21)?;
+                ((bits & 0x3fff) + 1, (bits >> 14 & 0x3fff) + 1)
+            }
+            b"VP8X" => ((le32(head, 24)? & 0xff_ffff) + 1, (le32(head, 27)? & 0xff_ffff) + 1),
+            _ => return None,
+        };
+        ("WebP", width, height)
+    } else if head.starts_with(b"\xff\xd8\xff") {
+        let (width, height) = jpeg_size(head)?;
+        ("JPEG", width, height)
+    } else {
+        return None;
+    };
+    Some(vec![("Format", format!("{} image", format)), ("Dimensions", format!("{} × {}", width, height))])
+}
+
+/// Width and height from the first start-of-frame segment of a JPEG.
+fn jpeg_size(head: &[u8]) -> Option<(u32, u32)> {
+    let mut at = 2;
+    loop {
+        if *head.get(at)? != 0xff {
+            return None;
+        }
+        let marker = *head.get(at + 1)?;
+        match marker {
+            0xff => at += 1,
+            0x01 | 0xd0..=0xd7 => at += 2,
+            0xc0..=0xcf if !matches!(marker, 0xc4 | 0xc8 | 0xcc) => {
+                return Some((u32::from(be16(head, at + 7)?), u32::from(be16(head, at + 5)?)));
+            }
+            _ => at += 2 + usize::from(be16(head, at + 2)?),
+        }
+    }
+}
+
+/// Channels and sample rate, as shown after the codec.
+fn audio_layout(channels: u32, rate: u32) -> String {
+    let channels = match channels {
+        1 => "mono".to_string(),
+        2 => "stereo".to_string(),
+        n => format!("{} channels", n),
+    };
+    format!("{}, {} Hz", channels, rate)
+}
+
+/// WAV: codec and layout from the `fmt ` chunk, duration from the size of
+/// the `data` chunk.
+fn wav(head: &[u8]) -> Option<Rows> {
+    let (mut fmt, mut data) = (None, None);
+    let mut at = 12;
+    while let (Some(id), Some(len)) = (head.get(at..at + 4), le32(head, at + 4)) {
+        match id {
+            b"fmt " => fmt = Some(at + 8),
+            b"data" => data = Some(u64::from(len)),
+            _ => {}
+        }
+        if data.is_some() {
+            break;
+        }
+        at = at.checked_add(8)?.checked_add(len as usize)?.checked_add(len as usize % 2)?;
+    }
+    let fmt = fmt?;
+    let codec = match le16(head, fmt)? {
+        1 => "PCM".to_string(),
+        3 => "IEEE float".to_string(),
+        6 => "A-law".to_string(),
+        7 => "µ-law".to_string(),
+        0x55 => "MP3".to_string(),
+        0xfffe => "PCM (extensible)".to_string(),
+        n => format!("format {:#06x}", n),
+    };
+    let layout = audio_layout(u32::from(le16(head, fmt + 2)?), le32(head, fmt + 4)?);
+    let mut rows = vec![("Format", "WAV audio".to_string()), ("Codec", format!("{}, {}", codec, layout))];
+    let byte_rate = le32(head, fmt + 8)?;
+    if let Some(data) = data.filter(|_| byte_rate > 0) {
+        rows.insert(1, ("Duration", format_duration(data as f64 / f64::from(byte_rate))));
+    }
+    Some(rows)
+}
+
+/// FLAC: sample rate, channels and total samples from `STREAMINFO`.
+fn flac(head: &[u8]) -> Option<Rows> {
+    if head.get(4)? & 0x7f != 0 {
+        return None;
+    }
+    let info = head.get(8..26)?;
+    let rate = u32::from(info[10]) << 12 | u32::from(info[11]) << 4 | u32::from(info[12]) >> 4;
+    let channels = u32::from(info[12] >> 1 & 7) + 1;
+    let samples = u64::from(info[13] & 0x0f) << 32 | u64::from(be32(info, 14)?);
+    let mut rows = vec![("Format", "FLAC audio".to_string())];
+    if rate > 0 && samples > 0 {
+        rows.push(("Duration", format_duration(samples as f64 / f64::from(rate))));
+    }
+    rows.push(("Codec", format!("FLAC, {}", audio_layout(channels, rate))));
+    Some(rows)
+}
+
+/// The boxes (atoms) of an MP4 byte range: type and contents.
+fn mp4_boxes(mut data: &[u8]) -> Vec<(&[u8], &[u8])> {
+    let mut boxes = Vec::new();
+    while data.len() >= 8 {
+        let (mut len, mut header) = (be32(data, 0).unwrap_or(0) as usize, 8);
+        if len == 1 {
+            len = be64(data, 8).unwrap_or(0) as usize;
+            header = 16;
+        } else if len == 0 {
+            len = data.len();
+        }
+        if len < header || len > data.len() {
+            break;
+        }
+        boxes.push((&data[4..8], &data[header..len]));
+        data = &data[len..];
+    }
+    boxes
+}
+
+/// The first box of type `kind` in `data`.
+fn mp4_child<'a>(data: &'a [u8], kind: &[u8]) -> Option<&'a [u8]> {
+    mp4_boxes(data).into_iter().find(|(k, _)| *k == kind).map(|(_, body)| body)
+}
+
+/// MP4 and QuickTime: duration from `mvhd`, and the codec of each track
+/// from its sample description.
+fn mp4<R: Read + Seek>(r: &mut R, head: &[u8], size: u64) -> Option<Rows> {
+    let container = if head.get(8..12) == Some(b"qt  ") { "QuickTime" } else { "MP4" };
+    // Find `moov` among the top-level boxes; it may follow the media data.
+    let mut offset = 0;
+    let moov = loop {
+        let header = read_at(r, offset, 16.min(size - offset))?;
+        let (mut len, mut skip) = (u64::from(be32(&header, 0)?), 8);
+        if len == 1 {
+            len = be64(&header, 8)?;
+            skip = 16;
+        } else if len == 0 {
+            len = size - offset;
+        }
+        if len < skip || offset.checked_add(len).is_none_or(|end| end > size) {
+            return None;
+        }
+        if &header[4..8] == b"moov" {
+            if len > MAX_INDEX_BYTES {
+                return Some(vec![("Format", format!("{} media", container))]);
+            }
+            break read_at(r, offset + skip, len - skip)?;
+        }
+        offset += len;
+        if offset.checked_add(8).is_none_or(|end| end > size) {
+            return None;
+        }
+    };
+    let mut rows = Vec::new();
+    if let Some(mvhd) = mp4_child(&moov, b"mvhd") {
+        let (scale, duration) = match mvhd.first()? {
+            1 => (be32(mvhd, 20)?, be64(mvhd, 24)?),
+            _ => (be32(mvhd, 12)?, u64::from(be32(mvhd, 16)?)),
+        };
+        if scale > 0 {
+            rows.push(("Duration", format_duration(duration as f64 / f64::from(scale))));
+        }
+    }
+    let (mut video, mut codecs) = (false, Vec::new());
+    for (_, trak) in mp4_boxes(&moov).into_iter().filter(|(k, _)| *k == b"trak") {
+        let Some(mdia) = mp4_child(trak, b"mdia") else { continue };
+        let handler = mp4_child(mdia, b"hdlr").and_then(|h| h.get(8..12));
+        video |= handler == Some(b"vide");
+        let stsd = mp4_child(mdia, b"minf").and_then(|m| mp4_child(m, b"stbl")).and_then(|s| mp4_child(s, b"stsd"));
+        if let Some(codec) = stsd.and_then(|s| s.get(12..16)) {
+            let codec = String::from_utf8_lossy(codec).trim().to_string();
+            if matches!(handler, Some(b"vide" | b"soun")) && !codecs.contains(&codec) {
+                codecs.push(codec);
+            }
+        }
+    }
+    let kind = if video { "video" } else { "audio" };
+    rows.insert(0, ("Format", format!("{} {}", container, kind)));
+    if !codecs.is_empty() {
+        rows.push(("Codec", codecs.join(", ")));
+    }
+    Some(rows)
+}
+
+/// ELF: object type, machine, word size and byte order.
+fn elf(head: &[u8]) -> Option<Rows> {
+    let big = match head.get(5)? {
+        1 => false,
+        2 => true,
+        _ => return None,
+    };
+    let u16_at = |at| if big { be16(head, at) } else { le16(head, at) };
+    let bits = match head.get(4)? {
+        1 => "32-bit",
+        2 => "64-bit",
+        _ => return None,
+    };
+    let kind = match u16_at(16)? {
+        1 => "relocatable object",
+        2 => "executable",
+        3 => "shared object",
+        4 => "core dump",
+        _ => "file",
+    };
+    let machine = match u16_at(18)? {
+        2 => "SPARC".to_string(),
+        3 => "x86".to_string(),
+        8 => "MIPS".to_string(),
+        0x14 => "PowerPC".to_string(),
+        0x15 => "PowerPC64".to_string(),
+        0x16 => "s390".to_string(),
+        0x28 => "ARM".to_string(),
+        0x2b => "SPARC V9".to_string(),
+        0x3e => "x86-64".to_string(),
+        0xb7 => "AArch64".to_string(),
+        0xf3 => "RISC-V".to_string(),
+        0x102 => "LoongArch".to_string(),
+        n => format!("machine {:#x}", n),
+    };
+    let order = if big { "big-endian" } else { "little-endian" };
+    Some(vec![("Format", format!("ELF {}", kind)), ("Target", format!("{}, {}, {}", machine, bits, order))])
+}
+
+/// PE (Windows executables and DLLs): machine, PE32 or PE32+ and
+/// subsystem from the COFF and optional headers.
+fn pe(head: &[u8]) -> Option<Rows> {
+    let pe = le32(head, 0x3c)? as usize;
+    if head.get(pe..pe + 4)? != b"PE\0\0" {
+        return None;
+    }
+    let machine = match le16(head, pe + 4)? {
+        0x14c => "x86".to_string(),
+        0x8664 => "x86-64".to_string(),
+        0x1c0 | 0x1c4 => "ARM".to_string(),
+        0xaa64 => "ARM64".to_string(),
+        0x200 => "Itanium".to_string(),
+        n => format!("machine {:#x}", n),
+    };
+    let kind = if le16(head, pe + 22)? & 0x2000 != 0 { "DLL" } else { "executable" };
+    let mut target = vec![machine];
+    let optional = pe + 24;
+    match le16(head, optional) {
+        Some(0x10b) => target.push("PE32".to_string()),
+        Some(0x20b) => target.push("PE32+".to_string()),
+        _ => {}
+    }
+    match le16(head, optional + 68) {
+        Some(1) => target.push("native".to_string()),
+        Some(2) => target.push("Windows GUI".to_string()),
+        Some(3) => target.push("console".to_string()),
+        Some(10..=13) => target.push("EFI".to_string()),
+        _ => {}
+    }
+    Some(vec![("Format", format!("PE {}", kind)), ("Target", target.join(", "))])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    fn rows_of(bytes: &[u8]) -> Rows {
+        let head = &bytes[..bytes.len().min(HEAD_BYTES as usize)];
+        detect(&mut Cursor::new(bytes), head, bytes.len() as u64).unwrap_or_default()
+    }
+
+    fn value<'a>(rows: &'a Rows, label: &str) -> Option<&'a str> {
+        rows.iter().find(|(l, _)| *l == label).map(|(_, v)| v.as_str())
+    }
+
+    /// A stored (uncompressed) ZIP of `files`.
+    fn zip_of(files: &[(&str, &[u8])]) -> Vec<u8> {
+        let (mut out, mut cd) = (Vec::new(), Vec::new());
+        for (name, data) in files {
+            let offset = out.len() as u32;
+            let sizes = [data.len() as u32, data.len() as u32];
+            out.extend_from_slice(b"PK\x03\x04\x14\0\0\0\0\0\0\0\0\0\0\0\0\0");
+            sizes.iter().for_each(|s| out.extend_from_slice(&s.to_le_bytes()));
+            out.extend_from_slice(&(name.len() as u16).to_le_bytes());
+            out.extend_from_slice(&[0, 0]);
+            out.extend_from_slice(name.as_bytes());
+            out.extend_from_slice(data);
+            cd.extend_from_slice(b"PK\x01\x02\x14\0\x14\0\0\0\0\0\0\0\0\0\0\0\0\0");
+            sizes.iter().for_each(|s| cd.extend_from_slice(&s.to_le_bytes()));
+            cd.extend_from_slice(&(name.len() as u16).to_le_bytes());
+            cd.extend_from_slice(&[0; 12]);
+            cd.extend_from_slice(&offset.to_le_bytes());
+            cd.extend_from_slice(name.as_bytes());
+        }
+        let cd_offset = out.len() as u32;
+        out.extend_from_slice(&cd);
+        out.extend_from_slice(b"PK\x05\x06\0\0\0\0");
+        out.extend_from_slice(&(files.len() as u16).to_le_bytes().repeat(2));
+        out.extend_from_slice(&(cd.len() as u32).to_le_bytes());
+        out.extend_from_slice(&cd_offset.to_le_bytes());
+        out.extend_from_slice(&[0, 0]);
+        out
+    }
+
+    #[test]
+    fn reads_archive_counts_and_sizes() {
+        let rows = rows_of(&zip_of(&[("a.txt", b"hello"), ("b.txt", b"world!")]));
+        assert_eq!(value(&rows, "Format"), Some("ZIP archive"));
+        assert_eq!(value(&rows, "Entries"), Some("2"));
+        assert_eq!(value(&rows, "Unpacked"), Some("11 B, compressed to 100%"));
+
+        let mut tar = vec![0u8; 512 * 4];
+        tar[..5].copy_from_slice(b"a.txt");
+        tar[124..135].copy_from_slice(b"00000001750");
+        tar[156] = b'0';
+        tar[257..262].copy_from_slice(b"ustar");
+        let rows = rows_of(&tar);
+        assert_eq!(value(&rows, "Entries"), Some("1"));
+        assert_eq!(value(&rows, "Unpacked"), Some("1000 B"));
+
+        let mut gz = b"\x1f\x8b\x08\0\0\0\0\0\0\x03".to_vec();
+        gz.extend_from_slice(&[0; 10]);
+        gz.extend_from_slice(&400u32.to_le_bytes());
+        assert_eq!(value(&rows_of(&gz), "Unpacked"), Some("400 B, compressed to 6%"));
+    }
+
+    #[test]
+    fn malformed_headers_do_not_panic() {
+        // A central directory entry whose comment runs past the directory.
+        let mut zip = zip_of(&[("a.txt", b"hello")]);
+        let cd = zip.windows(4).position(|w| w == b"PK\x01\x02").unwrap();
+        zip[cd + 32..cd + 34].copy_from_slice(&0xffffu16.to_le_bytes());
+        assert_eq!(value(&rows_of(&zip), "Entries"), Some("1"));
+        // Cut short inside the central directory.
+        let mut truncated = zip_of(&[("a.txt", b"hello"), ("b.txt", b"world")]);
+        let cd = truncated.windows(4).position(|w| w == b"PK\x01\x02").unwrap();
+        truncated.truncate(cd + 10);
+        assert!(rows_of(&truncated).is_empty());
+
+        // A GNU base-256 size of 2^88 - 1 bytes.
+        let mut tar = vec![0u8; 512 * 2];
+        tar[124] = 0x80;
+        tar[125..136].fill(0xff);
+        tar[156] = b'0';
+        tar[257..262].copy_from_slice(b"ustar");
+        assert!(rows_of(&tar).is_empty());
+        // 2^64 - 1 bytes: fits, but not rounded up to whole blocks.
+        tar[125..128].fill(0);
+        assert!(rows_of(&tar).is_empty());
+
+        // A 64-bit box length of 2^64 - 1.
+        let mut mp4 = b"\0\0\0\x10ftypisom\0\0\0\0\0\0\0\x01mdat".to_vec();
+        mp4.extend_from_slice(&u64::MAX.to_be_bytes());
+        mp4.extend_from_slice(&[0; 16]);
+        assert!(rows_of(&mp4).is_empty());
+    }
+
+    #[test]
+    fn reads_image_dimensions() {
+        let mut png = b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR".to_vec();
+        png.extend_from_slice(&640u32.to_be_bytes());
+        png.extend_from_slice(&480u32.to_be_bytes());
+        assert_eq!(value(&rows_of(&png), "Dimensions"), Some("640 × 480"));
+
+        let gif = b"GIF89a\x40\x01\xc8\x00";
+        assert_eq!(value(&rows_of(gif), "Dimensions"), Some("320 × 200"));
+
+        // SOI, an APP0 segment, then SOF0 with height 100 and width 200.
+        let jpeg = b"\xff\xd8\xff\xe0\0\x04ab\xff\xc0\0\x11\x08\0\x64\0\xc8\x03";
+        let rows = rows_of(jpeg);
+        assert_eq!(value(&rows, "Format"), Some("JPEG image"));
+        assert_eq!(value(&rows, "Dimensions"), Some("200 × 100"));
+    }
+
+    #[test]
+    fn reads_audio_video_and_executables() {
+        // One second of 8 kHz mono 16-bit PCM.
+        let mut wav = b"RIFF\0\0\0\0WAVEfmt \x10\0\0\0\x01\0\x01\0".to_vec();
+        wav.extend_from_slice(&8000u32.to_le_bytes());
+        wav.extend_from_slice(&16000u32.to_le_bytes());
+        wav.extend_from_slice(b"\x02\0\x10\0data");
+        wav.extend_from_slice(&16000u32.to_le_bytes());
+        let rows = rows_of(&wav);
+        assert_eq!(value(&rows, "Duration"), Some("0:01"));
+        assert_eq!(value(&rows, "Codec"), Some("PCM, mono, 8000 Hz"));
+
+        let boxed = |kind: &[u8], body: &[u8]| [&(body.len() as u32 + 8).to_be_bytes()[..], kind, body].concat();
+        let mut mvhd = vec![0u8; 100];
+        mvhd[12..16].copy_from_slice(&1000u32.to_be_bytes());
+        mvhd[16..20].copy_from_slice(&125_000u32.to_be_bytes());
+        let hdlr = [&[0u8; 8][..], b"vide", &[0; 12]].concat();
+        let stsd = [&[0u8; 4][..], &1u32.to_be_bytes(), &86u32.to_be_bytes(), b"avc1"].concat();
+        let stbl = boxed(b"stbl", &boxed(b"stsd", &stsd));
+        let mdia = [boxed(b"hdlr", &hdlr), boxed(b"minf", &stbl)].concat();
+        let moov = boxed(b"moov", &[boxed(b"mvhd", &mvhd), boxed(b"trak", &boxed(b"mdia", &mdia))].concat());
+        let mp4 = [boxed(b"ftyp", b"isom\0\0\0\0"), boxed(b"mdat", &[0; 32]), moov].concat();
+        let rows = rows_of(&mp4);
+        assert_eq!(value(&rows, "Format"), Some("MP4 video"));
+        assert_eq!(value(&rows, "Duration"), Some("2:05"));
+        assert_eq!(value(&rows, "Codec"), Some("avc1"));
+
+        let mut elf = b"\x7fELF\x02\x01\x01\0".to_vec();
+        elf.resize(16, 0);
+        elf.extend_from_slice(&[3, 0, 0x3e, 0]);
+        let rows = rows_of(&elf);
+        assert_eq!(value(&rows, "Format"), Some("ELF shared object"));
+        assert_eq!(value(&rows, "Target"), Some("x86-64, 64-bit, little-endian"));
+
+        let mut pe = vec![0u8; 0x200];
+        pe[..2].copy_from_slice(b"MZ");
+        pe[0x3c] = 0x80;
+        pe[0x80..0x84].copy_from_slice(b"PE\0\0");
+        pe[0x84..0x86].copy_from_slice(&0x8664u16.to_le_bytes());
+        pe[0x98..0x9a].copy_from_slice(&0x20bu16.to_le_bytes());
+        pe[0x98 + 68] = 3;
+        let rows = rows_of(&pe);
+        assert_eq!(value(&rows, "Format"), Some("PE executable"));
+        assert_eq!(value(&rows, "Target"), Some("x86-64, PE32+, console"));
+
+        assert!(rows_of(b"just some text").is_empty());
+        assert_eq!(format_duration(3725.0), "1:02:05");
+    }
+}
@@ -0,0 +1,273 @@
+//! Shared file hashing pipeline.
+//!
+//! Checksum, verify, dedup and compare features all need digests of many
+//! files. Hashing them one after another on a single thread leaves fast
+//! (NVMe) storage idle while the CPU hashes, so `hash_files` splits the work:
+//! a dedicated IO thread streams every file in `chunk_size` blocks, and each
+//! file is pinned to one of `workers` hashing threads that consume its blocks
+//! in order. Bounded queues (`queue_depth` blocks per worker) keep memory use
+//! flat and let reading run ahead of hashing.
+//!
+//! Digests are SHA-256, rendered as lowercase hex.
+
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, SyncSender};
+use std::thread;
+
+use sha2::{Digest, Sha256};
+
+/// Tuning knobs for `hash_files`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct HashOptions {
+    /// Number of hashing threads. `0` uses the available parallelism.
+    pub workers: usize,
+    /// Size of each block read by the IO thread.
+    pub chunk_size: usize,
+    /// Blocks queued per worker before the IO thread waits.
+    pub queue_depth: usize,
+}
+
+impl Default for HashOptions {
+    fn default() -> Self {
+        HashOptions { workers: 0, chunk_size: 1024 * 1024, queue_depth: 4 }
+    }
+}
+
+/// Digest (or error) for one input path.
+#[derive(Debug)]
+pub struct HashResult {
+    pub path: PathBuf,
+    pub digest: io::Result<String>,
+}
+
+/// Messages from the IO thread to a hashing worker.
+enum Block {
+    Data(Vec<u8>),
+    /// End of file `idx`; the worker finishes its digest.
+    Done(usize),
+    /// Reading file `idx` failed; the worker drops its partial state.
+    Failed(usize, io::Error),
+}
+
+/// Hash a single file on the calling thread.
+pub fn hash_file(path: &Path) -> io::Result<String> {
+    let mut f = File::open(path)?;
+    let mut hasher = Sha256::new();
+    let mut buf = vec![0u8; 64 * 1024];
+    loop {
+        let n = f.read(&mut buf)?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buf[..n]);
+    }
+    Ok(to_hex(&hasher.finalize()))
+}
+
+/// Hash `paths` with the IO/worker pipeline. Results are returned in input
+/// order. Setting `cancel` stops reading; files not yet hashed then report
+/// `io::ErrorKind::Interrupted`.
+pub fn hash_files(paths: &[PathBuf], opts: &HashOptions, cancel: Option<&AtomicBool>) -> Vec<HashResult> {
+    let stop = || cancel.is_some_and(|c| c.load(Ordering::Relaxed));
+    hash_files_until(paths, opts, &stop, &mut |_| {})
+}
+
+/// `hash_files` that stops reading once `stop` returns true and calls
+/// `done` with the number of files finished after each one.
+pub fn hash_files_until(paths: &[PathBuf], opts: &HashOptions, stop: &(dyn Fn() -> bool + Sync), done: &mut dyn FnMut(usize)) -> Vec<HashResult> {
+    let workers = match opts.workers {
+        0 => thread::available_parallelism().map(|n| n.get()).unwrap_or(1),
+        n => n,
+    }
+    .min(paths.len())
+    .max(1);
+    let chunk_size = opts.chunk_size.max(4096);
+    let mut digests: Vec<Option<io::Result<String>>> = paths.iter().map(|_| None).collect();
+
+    thread::scope(|s| {
+        let (res_tx, res_rx) = mpsc::channel::<(usize, io::Result<String>)>();
+        let mut senders = Vec::with_capacity(workers);
+        for _ in 0..workers {
+            let (tx, rx) = mpsc::sync_channel::<Block>(opts.queue_depth.max(1));
+            senders.push(tx);
+            let res_tx = res_tx.clone();
+            s.spawn(move || hash_worker(rx, res_tx));
+        }
+        drop(res_tx);
+        s.spawn(move || read_files(paths, &senders, chunk_size, stop));
+        for (n, (idx, digest)) in res_rx.into_iter().enumerate() {
+            digests[idx] = Some(digest);
+            done(n + 1);
+        }
+    });
+
+    paths
+        .iter()
+        .zip(digests)
+        .map(|(p, d)| HashResult {
+            path: p.clone(),
+            digest: d.unwrap_or_else(|| Err(io::Error::new(io::ErrorKind::Interrupted, "hashing cancelled"))),
+        })
+        .collect()
+}
+
+/// IO thread: stream each file to the worker it is pinned to (`idx % workers`).
+fn read_files(paths: &[PathBuf], senders: &[SyncSender<Block>], chunk_size: usize, cancelled: &(dyn Fn() -> bool + Sync)) {
+    for (idx, path) in paths.iter().enumerate() {
+        if cancelled() {
+            return;
+        }
+        let tx = &senders[idx % senders.len()];
+        let mut f = match File::open(path) {
+            Ok(f) => f,
+            Err(e) => {
+                let _ = tx.send(Block::Failed(idx, e));
+                continue;
+            }
+        };
+        loop {
+            if cancelled() {
+                return;
+            }
+            let mut buf = vec![0u8; chunk_size];
+            match read_full(&mut f, &mut buf) {
+                Ok(0) => {
+                    let _ = tx.send(Block::Done(idx));
+                    break;
+                }
+                Ok(n) => {
+                    buf.truncate(n);
+                    if tx.send(Block::Data(buf)).is_err() {
+                        return;
+                    }
+                }
+                Err(e) => {
+                    let _ = tx.send(Block::Failed(idx, e));
+                    break;
+                }
+            }
+        }
+    }
+}
+
+/// Fill `buf` as far as possible so blocks stay `chunk_size` even when the
+/// OS returns short reads.
+fn read_full(f: &mut File, buf: &mut [u8]) -> io::Result<usize> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match f.read(&mut buf[filled..]) {
+            Ok(0) => break,
+            Ok(n) => filled += n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(filled)
+}
+
+/// Hashing thread. Files pinned to this worker arrive one after another, so
+/// at most one digest is in progress at a time.
+fn hash_worker(rx: Receiver<Block>, res_tx: mpsc::Sender<(usize, io::Result<String>)>) {
+    let mut hasher = Sha256::new();
+    for block in rx {
+        match block {
+            Block::Data(data) => hasher.update(&data),
+            Block::Done(idx) => {
+                let digest = std::mem::take(&mut hasher).finalize();
+                let _ = res_tx.send((idx, Ok(to_hex(&digest))));
+            }
+            Block::Failed(idx, e) => {
+                hasher = Sha256::new();
+                let _ = res_tx.send((idx, Err(e)));
+            }
+        }
+    }
+}
+
+/// Regular files to verify after copying each `(source, target)` pair:
+/// the pair itself for a file, every file below a copied directory paired
+/// with its copy. Symlinks are not followed.
+pub fn copied_file_pairs(copied: &[(PathBuf, PathBuf)]) -> Vec<(PathBuf, PathBuf)> {
+    let mut pairs = Vec::new();
+    for (src, dst) in copied {
+        for entry in walkdir::WalkDir::new(src).into_iter().filter_map(Result::ok) {
+            if entry.file_type().is_file() {
+                let rel = entry.path().strip_prefix(src).unwrap_or(Path::new(""));
+                pairs.push((entry.path().to_path_buf(), if rel.as_os_str().is_empty() { dst.clone() } else { dst.join(rel) }));
+            }
+        }
+    }
+    pairs
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EMPTY_SHA256: &str = "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
+
+    #[test]
+    fn single_file_digest_matches_known_value() {
+        let tmp = tempfile::tempdir().unwrap();
+        let p = tmp.path().join("abc");
+        std::fs::write(&p, "abc").unwrap();
+        assert_eq!(hash_file(&p).unwrap(), "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad");
+        std::fs::write(&p, "").unwrap();
+        assert_eq!(hash_file(&p).unwrap(), EMPTY_SHA256);
+    }
+
+    #[test]
+    fn pipeline_matches_single_threaded_digests_in_order() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut paths = Vec::new();
+        for i in 0..9usize {
+            let p = tmp.path().join(format!("f{}", i));
+            // Sizes straddle the chunk size to exercise multi-block files.
+            std::fs::write(&p, vec![i as u8; i * 3000]).unwrap();
+            paths.push(p);
+        }
+        paths.push(tmp.path().join("missing"));
+        let opts = HashOptions { workers: 3, chunk_size: 4096, queue_depth: 2 };
+        let results = hash_files(&paths, &opts, None);
+        assert_eq!(results.len(), paths.len());
+        for (r, p) in results.iter().zip(&paths).take(9) {
+            assert_eq!(&r.path, p);
+            assert_eq!(r.digest.as_ref().unwrap(), &hash_file(p).unwrap());
+        }
+        assert_eq!(results[0].digest.as_ref().unwrap(), EMPTY_SHA256);
+        assert_eq!(results[9].digest.as_ref().unwrap_err().kind(), io::ErrorKind::NotFound);
+    }
+
+    #[test]
+    fn copied_pairs_cover_files_below_directories() {
+        let tmp = tempfile::tempdir().unwrap();
+        let (src, dst) = (tmp.path().join("src"), tmp.path().join("dst"));
+        std::fs::create_dir_all(src.join("sub")).unwrap();
+        std::fs::write(src.join("sub/a"), "a").unwrap();
+        std::fs::write(tmp.path().join("f"), "f").unwrap();
+        let pairs = copied_file_pairs(&[(src.clone(), dst.clone()), (tmp.path().join("f"), tmp.path().join("g"))]);
+        assert_eq!(pairs, vec![(src.join("sub/a"), dst.join("sub/a")), (tmp.path().join("f"), tmp.path().join("g"))]);
+    }
+
+    #[test]
+    fn cancelled_pipeline_reports_interrupted() {
+        let tmp = tempfile::tempdir().unwrap();
+        let p = tmp.path().join("a");
+        std::fs::write(&p, "a").unwrap();
+        let cancel = AtomicBool::new(true);
+        let results = hash_files(std::slice::from_ref(&p), &HashOptions::default(), Some(&cancel));
+        assert_eq!(results[0].digest.as_ref().unwrap_err().kind(), io::ErrorKind::Interrupted);
+
+        let mut finished = Vec::new();
+        let results = hash_files_until(&[p.clone(), p], &HashOptions::default(), &|| false, &mut |n| finished.push(n));
+        assert!(results.iter().all(|r| r.digest.is_ok()));
+        assert_eq!(finished, [1, 2]);
+    }
+}
@@ -1,4 +1,5 @@
 use std::collections::hash_map::DefaultHasher;
+use std::ffi::OsStr;
 use std::fs;
 use std::hash::{Hash, Hasher};
 use std::io;
@@ -7,7 +8,8 @@ use std::process;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::{SystemTime, UNIX_EPOCH};
 
-use fs_extra::file::{copy as fs_extra_copy, CopyOptions};
+use super::meter::file_copy;
+use super::options::CopyOptions;
 use super::test_helpers as tests;
 
 /// Resolve destination path for an operation: if `dst` looks like a directory
@@ -20,14 +22,23 @@ use super::test_helpers as tests;
 /// If `dst` is a directory (exists as directory) or syntactically ends
 /// with a trailing `/`, the returned path will be `dst.join(src_name)`.
 /// Otherwise `dst` is returned as-is.
-pub fn resolve_target(dst: &Path, src_name: &str) -> PathBuf {
+pub fn resolve_target(dst: &Path, src_name: impl AsRef<OsStr>) -> PathBuf {
     if dst.is_dir() || dst.to_string_lossy().ends_with('/') {
-        dst.join(src_name)
+        dst.join(src_name.as_ref())
     } else {
         dst.to_path_buf()
     }
 }
 
+/// Convert an `fs_extra` error into an `io::Error`, keeping the original
+/// `io::Error` (and so its kind, e.g. "no space left") when there is one.
+pub(crate) fn fs_extra_io_error(e: fs_extra::error::Error) -> io::Error {
+    match e.kind {
+        fs_extra::error::ErrorKind::Io(err) => err,
+        _ => io::Error::other(e),
+    }
+}
+
 /// Ensure parent directory exists for a path.
 /// Ensure the parent directory of `p` exists.
 pub fn ensure_parent_exists(p: &Path) -> io::Result<()> {
@@ -37,6 +48,38 @@ pub fn ensure_parent_exists(p: &Path) -> io::Result<()> {
     Ok(())
 }
 
+/// Fail with `NotFound` unless the parent directory of `p` exists, for
+/// operations told not to create it.
+pub fn require_parent_exists(p: &Path) -> io::Result<()> {
+    match p.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() && !parent.is_dir() => {
+            Err(io::Error::new(io::ErrorKind::NotFound, format!("directory {} does not exist", parent.display())))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Whether moving `src` into `dst` crosses filesystems, so the move copies
+/// and deletes instead of renaming. A `dst` that does not exist yet counts
+/// as being on the filesystem of its nearest existing ancestor. Always
+/// `false` off Unix.
+pub fn crosses_filesystems(src: &Path, dst: &Path) -> bool {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        let Ok(src) = fs::symlink_metadata(src) else { return false };
+        match dst.ancestors().find_map(|p| fs::metadata(p).ok()) {
+            Some(dst) => src.dev() != dst.dev(),
+            None => false,
+        }
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = (src, dst);
+        false
+    }
+}
+
 /// Write `data` to `target` atomically by writing to a temporary file in the
 /// same directory and then renaming into place. This avoids partial writes
 /// being observed by other processes.
@@ -44,6 +87,26 @@ pub fn ensure_parent_exists(p: &Path) -> io::Result<()> {
 /// renaming into place. Temp files are created in the same directory as
 /// `target` to ensure the rename is atomic on the same filesystem.
 pub fn atomic_write(target: &Path, data: &[u8]) -> io::Result<()> {
+    write_then_rename(target, data, |_| Ok(()))
+}
+
+/// `atomic_write` for a file edited in place: before the rename the temp
+/// file gets the permissions of the existing `target` and, best-effort on
+/// Unix, its owner, extended attributes and ACLs, which a new inode would
+/// otherwise lose. Symlinks must be resolved by the caller.
+pub fn atomic_rewrite(target: &Path, data: &[u8]) -> io::Result<()> {
+    write_then_rename(target, data, |tmp| {
+        if !target.is_file() {
+            return Ok(());
+        }
+        super::metadata::copy_permissions(target, tmp)?;
+        #[cfg(unix)]
+        super::metadata::copy_unix_extras(target, tmp);
+        Ok(())
+    })
+}
+
+fn write_then_rename(target: &Path, data: &[u8], prepare: impl FnOnce(&Path) -> io::Result<()>) -> io::Result<()> {
     if let Some(dir) = target.parent() {
         fs::create_dir_all(dir)?;
         let mut tmp = dir.join(".tmp_atomic_write");
@@ -58,7 +121,7 @@ pub fn atomic_write(target: &Path, data: &[u8]) -> io::Result<()> {
         tmp.set_file_name(format!(".tmp_atomic_write.{}", suffix));
 
         // Ensure the temp file is removed on any early return.
-        if let Err(e) = fs::write(&tmp, data) {
+        if let Err(e) = fs::write(&tmp, data).and_then(|()| prepare(&tmp)) {
             let _ = fs::remove_file(&tmp);
             return Err(e);
         }
@@ -83,11 +146,42 @@ pub fn atomic_write(target: &Path, data: &[u8]) -> io::Result<()> {
 /// Atomically copy a single file by copying into a temp file in the
 /// destination directory and renaming into place. Returns number of bytes
 /// copied on success.
+///
+/// An existing `dst` is replaced (rename semantics); use
+/// `atomic_copy_file_with` to refuse overwriting.
 pub fn atomic_copy_file(src: &Path, dst: &Path) -> io::Result<u64> {
-    // Prepare copy options used in both branches.
-    let mut options = CopyOptions::new();
-    options.overwrite = false;
-    options.buffer_size = 64 * 1024;
+    atomic_copy_file_with(src, dst, &CopyOptions { overwrite: true, ..Default::default() })
+}
+
+/// Atomically copy a single file honouring `opts`.
+///
+/// - When `opts.overwrite` is false and `dst` exists, fails with
+///   `io::ErrorKind::AlreadyExists` without touching either file.
+/// - When `opts.follow_symlinks` is false and `src` is a symlink, the link
+///   itself is recreated at `dst` (Unix only; elsewhere the target is
+///   copied).
+/// - Metadata is copied only when `opts.preserve_metadata` is set.
+pub fn atomic_copy_file_with(src: &Path, dst: &Path, opts: &CopyOptions) -> io::Result<u64> {
+    if !opts.overwrite && fs::symlink_metadata(dst).is_ok() {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("destination exists: {}", dst.display()),
+        ));
+    }
+    // The temp file is always fresh, so fs_extra itself never overwrites.
+    let options = CopyOptions { overwrite: false, ..*opts }.to_fs_extra_file();
+
+    if !opts.follow_symlinks && fs::symlink_metadata(src)?.file_type().is_symlink() {
+        if let Some(existing) = fs::symlink_metadata(dst).ok().filter(|_| opts.overwrite) {
+            if existing.file_type().is_symlink() {
+                crate::fs_op::symlink::remove_symlink(dst)?;
+            } else {
+                fs::remove_file(dst)?;
+            }
+        }
+        crate::fs_op::symlink::copy_symlink(src, dst)?;
+        return Ok(0);
+    }
 
     if let Some(dir) = dst.parent() {
         fs::create_dir_all(dir)?;
@@ -110,7 +204,17 @@ pub fn atomic_copy_file(src: &Path, dst: &Path) -> io::Result<u64> {
         let suffix = raw.chars().rev().take(12).collect::<String>().chars().rev().collect::<String>();
         tmp.set_file_name(format!(".tmp_atomic_copy.{}", suffix));
 
-        let n = fs_extra_copy(src, &tmp, &options).map_err(io::Error::other)?;
+        // A failed copy (for example out of space) leaves a partial temp
+        // file behind; remove it before reporting the error.
+        let n = match file_copy(src, &tmp, &options)
+            .and_then(|n| tests::consume_simulated_space(n).map(|_| n))
+        {
+            Ok(n) => n,
+            Err(e) => {
+                let _ = fs::remove_file(&tmp);
+                return Err(e);
+            }
+        };
 
         // test hook may force a failure to exercise cleanup
         if tests::should_force_rename_fail_in_copy() {
@@ -122,11 +226,15 @@ pub fn atomic_copy_file(src: &Path, dst: &Path) -> io::Result<u64> {
             let _ = fs::remove_file(&tmp);
         })?;
 
-        let _ = crate::fs_op::metadata::preserve_all_metadata(src, dst);
+        if opts.preserve_metadata {
+            let _ = crate::fs_op::metadata::preserve_all_metadata(src, dst);
+        }
         Ok(n)
     } else {
-        let res = fs_extra_copy(src, dst, &options).map_err(io::Error::other)?;
-        let _ = crate::fs_op::metadata::preserve_all_metadata(src, dst);
+        let res = file_copy(src, dst, &options)?;
+        if opts.preserve_metadata {
+            let _ = crate::fs_op::metadata::preserve_all_metadata(src, dst);
+        }
         Ok(res)
     }
 }
@@ -137,9 +245,23 @@ pub fn atomic_copy_file(src: &Path, dst: &Path) -> io::Result<u64> {
 /// example cross-filesystem moves). Directories are delegated to the
 /// `mv::move_path` helper which handles recursive semantics.
 pub fn atomic_rename_or_copy(src: &Path, dst: &Path) -> io::Result<()> {
+    atomic_rename_or_copy_with(src, dst, &CopyOptions { overwrite: true, ..Default::default() })
+}
+
+/// Rename `src` to `dst` honouring `opts`; the copy fallback uses
+/// `atomic_copy_file_with`. When `opts.overwrite` is false and `dst`
+/// exists, fails with `io::ErrorKind::AlreadyExists` before moving anything.
+pub fn atomic_rename_or_copy_with(src: &Path, dst: &Path, opts: &CopyOptions) -> io::Result<()> {
+    if !opts.overwrite && fs::symlink_metadata(dst).is_ok() {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("destination exists: {}", dst.display()),
+        ));
+    }
+
     // test hook: force fallback path
     if tests::should_force_rename_fail_in_rename_or_copy() {
-        atomic_copy_file(src, dst)?;
+        atomic_copy_file_with(src, dst, opts)?;
         fs::remove_file(src)?;
         return Ok(());
     }
@@ -155,7 +277,7 @@ pub fn atomic_rename_or_copy(src: &Path, dst: &Path) -> io::Result<()> {
     if fs::rename(src, dst).is_ok() {
         Ok(())
     } else {
-        atomic_copy_file(src, dst)?;
+        atomic_copy_file_with(src, dst, opts)?;
         fs::remove_file(src)?;
         Ok(())
     }
@@ -0,0 +1,305 @@
+//! `.gitignore` rules, shared by listings, `search` and directory totals.
+//!
+//! `IgnoreRules::for_dir` finds the git work tree (the nearest ancestor with
+//! a `.git` entry) and reads its `.git/info/exclude`. The `.gitignore` of
+//! each directory is read the first time a path below it is checked and
+//! kept for later checks. Matching follows git: the last matching pattern
+//! wins, `!` re-includes, a trailing `/` only matches directories, a
+//! pattern with a `/` elsewhere is relative to its `.gitignore`'s directory
+//! and `**` spans directories. Nothing below an ignored directory can be
+//! re-included. Outside a work tree there are no rules.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use crate::app::types::Entry;
+
+/// One line of an ignore file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Rule {
+    /// Directory of the file the rule comes from.
+    base: PathBuf,
+    pattern: Vec<char>,
+    negate: bool,
+    dir_only: bool,
+    /// Matched against the path relative to `base` rather than the name.
+    anchored: bool,
+}
+
+impl Rule {
+    fn parse(line: &str, base: &Path) -> Option<Rule> {
+        let line = line.trim_end_matches(['\r', '\n']);
+        // Trailing spaces are ignored unless escaped.
+        let line = match line.trim_end_matches(' ') {
+            l if l.ends_with('\\') && line.len() > l.len() => &line[..l.len() + 1],
+            l => l,
+        };
+        if line.is_empty() || line.starts_with('#') {
+            return None;
+        }
+        let (negate, line) = match line.strip_prefix('!') {
+            Some(rest) => (true, rest),
+            None => (false, line.strip_prefix('\\').filter(|r| r.starts_with(['#', '!'])).unwrap_or(line)),
+        };
+        let (dir_only, line) = match line.strip_suffix('/') {
+            Some(rest) => (true, rest),
+            None => (false, line),
+        };
+        let anchored = line.contains('/');
+        let line = line.strip_prefix('/').unwrap_or(line);
+        if line.is_empty() {
+            return None;
+        }
+        Some(Rule { base: base.to_path_buf(), pattern: line.chars().collect(), negate, dir_only, anchored })
+    }
+
+    fn matches(&self, path: &Path, is_dir: bool) -> bool {
+        if self.dir_only && !is_dir {
+            return false;
+        }
+        let Ok(rel) = path.strip_prefix(&self.base) else {
+            return false;
+        };
+        let text: Vec<char> = if self.anchored {
+            let parts: Vec<String> = rel.components().map(|c| c.as_os_str().to_string_lossy().into_owned()).collect();
+            parts.join("/").chars().collect()
+        } else {
+            match rel.file_name() {
+                Some(name) => name.to_string_lossy().chars().collect(),
+                None => return false,
+            }
+        };
+        glob(&self.pattern, &text)
+    }
+}
+
+/// Whether the file name `name` matches the glob `pattern`, with the
+/// gitignore syntax: `*`, `?`, `[...]` classes and `\` escapes.
+pub fn glob_match(pattern: &str, name: &str) -> bool {
+    let p: Vec<char> = pattern.chars().collect();
+    let t: Vec<char> = name.chars().collect();
+    glob(&p, &t)
+}
+
+/// Whether `text` matches the gitignore glob `p`: `*` and `?` stay within
+/// one path component, `**/` spans any number of them and a trailing `**`
+/// matches everything below.
+fn glob(p: &[char], t: &[char]) -> bool {
+    match p.first() {
+        None => t.is_empty(),
+        Some('*') if p.get(1) == Some(&'*') => {
+            let rest = &p[2..];
+            match rest.first() {
+                None => true,
+                Some('/') => {
+                    let rest = &rest[1..];
+                    glob(rest, t) || (0..t.len()).any(|i| t[i] == '/' && glob(rest, &t[i + 1..]))
+                }
+                // Other runs of asterisks are plain ones.
+                Some(_) => glob(&p[1..], t),
+            }
+        }
+        Some('*') => {
+            let rest = &p[1..];
+            for i in 0..=t.len() {
+                if glob(rest, &t[i..]) {
+                    return true;
+                }
+                if t.get(i) == Some(&'/') {
+                    break;
+                }
+            }
+            false
+        }
+        Some('?') => t.first().is_some_and(|c| *c != '/') && glob(&p[1..], &t[1..]),
+        Some('[') => match (class_match(&p[1..], t.first().copied()), t.first()) {
+            (Some((true, len)), Some(_)) => glob(&p[1 + len..], &t[1..]),
+            (Some((false, _)), _) | (_, None) => false,
+            // No closing bracket: a literal `[`.
+            (None, Some(c)) => *c == '[' && glob(&p[1..], &t[1..]),
+        },
+        Some('\\') if p.len() > 1 => t.first() == Some(&p[1]) && glob(&p[2..], &t[1..]),
+        Some(c) => t.first() == Some(c) && glob(&p[1..], &t[1..]),
+    }
+}
+
+/// Match `c` against the bracket expression starting after `[` in `p`.
+/// Returns whether it matched and the expression's length including `]`,
+/// or `None` when there is no closing bracket.
+fn class_match(p: &[char], c: Option<char>) -> Option<(bool, usize)> {
+    let (negate, start) = match p.first() {
+        Some('!') | Some('^') => (true, 1),
+        _ => (false, 0),
+    };
+    let mut i = start;
+    let mut matched = false;
+    while i < p.len() {
+        // A `]` right after the opening bracket is literal.
+        if p[i] == ']' && i > start {
+            let hit = c.is_some_and(|c| c != '/') && matched != negate;
+            return Some((hit, i + 1));
+        }
+        if i + 2 < p.len() && p[i + 1] == '-' && p[i + 2] != ']' {
+            matched |= c.is_some_and(|c| (p[i]..=p[i + 2]).contains(&c));
+            i += 3;
+        } else {
+            matched |= c == Some(p[i]);
+            i += 1;
+        }
+    }
+    None
+}
+
+/// Ignore rules of one git work tree.
+#[derive(Debug)]
+pub struct IgnoreRules {
+    root: PathBuf,
+    /// `.git/info/exclude`, applied before every `.gitignore`.
+    exclude: Vec<Rule>,
+    /// Rules of each directory's `.gitignore`, read on first use.
+    per_dir: Mutex<HashMap<PathBuf, Arc<Vec<Rule>>>>,
+}
+
+impl IgnoreRules {
+    /// Rules of the work tree containing `dir`, or `None` outside one.
+    pub fn for_dir(dir: &Path) -> Option<Self> {
+        let root = dir.ancestors().find(|a| a.join(".git").exists())?.to_path_buf();
+        let exclude = read_rules(&root.join(".git").join("info").join("exclude"), &root);
+        Some(IgnoreRules { root, exclude, per_dir: Mutex::new(HashMap::new()) })
+    }
+
+    /// Top-level directory of the work tree.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    fn dir_rules(&self, dir: &Path) -> Arc<Vec<Rule>> {
+        let mut cache = self.per_dir.lock().unwrap_or_else(|e| e.into_inner());
+        cache.entry(dir.to_path_buf()).or_insert_with(|| Arc::new(read_rules(&dir.join(".gitignore"), dir))).clone()
+    }
+
+    /// Whether the patterns ignore `path` itself, assuming its parent
+    /// directories are not ignored. Walks that skip ignored directories
+    /// use this for each entry.
+    pub fn matches(&self, path: &Path, is_dir: bool) -> bool {
+        let Some(parent) = path.parent().filter(|p| p.starts_with(&self.root)) else {
+            return false;
+        };
+        if path.file_name().is_some_and(|n| n == ".git") {
+            return false;
+        }
+        let dirs: Vec<&Path> = parent.ancestors().take_while(|a| a.starts_with(&self.root)).collect();
+        let mut ignored = false;
+        let mut apply = |rules: &[Rule]| {
+            for rule in rules {
+                if rule.negate == ignored && rule.matches(path, is_dir) {
+                    ignored = !rule.negate;
+                }
+            }
+        };
+        apply(&self.exclude);
+        for dir in dirs.into_iter().rev() {
+            apply(&self.dir_rules(dir));
+        }
+        ignored
+    }
+
+    /// Whether `path` is ignored, itself or through an ignored directory
+    /// above it.
+    pub fn is_ignored(&self, path: &Path, is_dir: bool) -> bool {
+        let Ok(rel) = path.strip_prefix(&self.root) else {
+            return false;
+        };
+        let mut dir = self.root.clone();
+        let mut parts = rel.components().peekable();
+        while let Some(part) = parts.next() {
+            dir.push(part);
+            let last = parts.peek().is_none();
+            if self.matches(&dir, if last { is_dir } else { true }) {
+                return true;
+            }
+        }
+        false
+    }
+}
+
+fn read_rules(file: &Path, base: &Path) -> Vec<Rule> {
+    std::fs::read_to_string(file).map(|raw| raw.lines().filter_map(|l| Rule::parse(l, base)).collect()).unwrap_or_default()
+}
+
+/// Drop the entries of `dir` that the work tree's ignore rules exclude.
+pub fn filter_ignored(dir: &Path, entries: &mut Vec<Entry>) {
+    let Some(rules) = IgnoreRules::for_dir(dir) else { return };
+    if rules.is_ignored(dir, true) {
+        return entries.clear();
+    }
+    entries.retain(|e| !rules.matches(&e.path, e.is_dir));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules(lines: &str) -> Vec<Rule> {
+        lines.lines().filter_map(|l| Rule::parse(l, Path::new("/repo"))).collect()
+    }
+
+    fn ignored(rules: &[Rule], path: &str, is_dir: bool) -> bool {
+        let mut ignored = false;
+        for rule in rules {
+            if rule.matches(Path::new(path), is_dir) {
+                ignored = !rule.negate;
+            }
+        }
+        ignored
+    }
+
+    #[test]
+    fn globs_match_names() {
+        assert!(glob_match("*.tmp", "a.tmp"));
+        assert!(glob_match("node_modules", "node_modules"));
+        assert!(glob_match("data?.csv", "data1.csv"));
+        assert!(glob_match("*a*b", "xaybab"));
+        assert!(glob_match("[Tt]emp", "temp"));
+        assert!(!glob_match("*.tmp", "a.tmp.txt"));
+        assert!(!glob_match("data?.csv", "data10.csv"));
+    }
+
+    #[test]
+    fn patterns_follow_gitignore_rules() {
+        let r = rules("# comment\n*.log\n!keep.log\nbuild/\n/top.txt\ndocs/**/*.tmp\na?c\n[Tt]emp\n");
+        assert!(ignored(&r, "/repo/x.log", false));
+        assert!(ignored(&r, "/repo/deep/y.log", false));
+        assert!(!ignored(&r, "/repo/keep.log", false));
+        assert!(ignored(&r, "/repo/sub/build", true));
+        assert!(!ignored(&r, "/repo/build", false));
+        assert!(ignored(&r, "/repo/top.txt", false));
+        assert!(!ignored(&r, "/repo/sub/top.txt", false));
+        assert!(ignored(&r, "/repo/docs/a/b/c.tmp", false));
+        assert!(ignored(&r, "/repo/docs/c.tmp", false));
+        assert!(!ignored(&r, "/repo/other/c.tmp", false));
+        assert!(ignored(&r, "/repo/abc", false));
+        assert!(!ignored(&r, "/repo/a/c", false));
+        assert!(ignored(&r, "/repo/Temp", true));
+    }
+
+    #[test]
+    fn nested_gitignores_and_ignored_parents() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        std::fs::create_dir_all(root.join(".git/info")).unwrap();
+        std::fs::create_dir_all(root.join("sub/out")).unwrap();
+        std::fs::write(root.join(".git/info/exclude"), "*.bak\n").unwrap();
+        std::fs::write(root.join(".gitignore"), "*.o\nout/\n").unwrap();
+        std::fs::write(root.join("sub/.gitignore"), "!main.o\n").unwrap();
+
+        let rules = IgnoreRules::for_dir(&root.join("sub")).unwrap();
+        assert_eq!(rules.root(), root);
+        assert!(rules.matches(&root.join("a.o"), false));
+        assert!(!rules.matches(&root.join("sub/main.o"), false));
+        assert!(rules.matches(&root.join("x.bak"), false));
+        assert!(rules.is_ignored(&root.join("sub/out/file.rs"), false));
+        assert!(!rules.is_ignored(&root.join(".git"), true));
+    }
+}
@@ -0,0 +1,173 @@
+//! Extended details of one entry for the info panel (see
+//! `ui::widgets::info`): file type, ownership, permissions, inode, link
+//! count, device and extended attributes, plus the recursive entry count
+//! and size of a directory.
+//!
+//! `FileInfo::read` is a couple of system calls and is cheap enough to run
+//! per frame; `dir_totals` walks a whole tree and runs on a worker thread
+//! (see `App::poll_info`).
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use chrono::{DateTime, Local};
+use walkdir::WalkDir;
+
+use super::ignore::IgnoreRules;
+
+/// Details of a filesystem entry, read without following a final symlink.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FileInfo {
+    /// `file`, `directory`, `symlink`, `fifo`, `socket`, `block device`
+    /// or `char device`.
+    pub kind: &'static str,
+    pub size: u64,
+    /// Permission bits (`0o7777`), on Unix.
+    pub mode: Option<u32>,
+    pub owner: Option<String>,
+    pub group: Option<String>,
+    pub inode: Option<u64>,
+    pub links: Option<u64>,
+    /// Device the entry lives on.
+    pub device: Option<u64>,
+    pub modified: Option<DateTime<Local>>,
+    pub accessed: Option<DateTime<Local>>,
+    /// Where a symlink points.
+    pub link_target: Option<PathBuf>,
+    /// Names of the extended attributes.
+    pub xattrs: Vec<String>,
+    /// Windows file attributes.
+    pub attributes: Option<super::attributes::FileAttributes>,
+}
+
+impl FileInfo {
+    /// Read the details of `path`.
+    pub fn read(path: &Path) -> io::Result<Self> {
+        let meta = fs::symlink_metadata(path)?;
+        let ft = meta.file_type();
+        let mut info = FileInfo {
+            kind: if ft.is_symlink() {
+                "symlink"
+            } else if ft.is_dir() {
+                "directory"
+            } else {
+                "file"
+            },
+            size: meta.len(),
+            modified: meta.modified().ok().map(DateTime::from),
+            accessed: meta.accessed().ok().map(DateTime::from),
+            link_target: ft.is_symlink().then(|| fs::read_link(path).ok()).flatten(),
+            attributes: super::attributes::read(&meta),
+            ..Default::default()
+        };
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::{FileTypeExt, MetadataExt};
+            if ft.is_fifo() {
+                info.kind = "fifo";
+            } else if ft.is_socket() {
+                info.kind = "socket";
+            } else if ft.is_block_device() {
+                info.kind = "block device";
+            } else if ft.is_char_device() {
+                info.kind = "char device";
+            }
+            info.mode = Some(meta.mode() & 0o7777);
+            info.owner = Some(users::get_user_by_uid(meta.uid()).map_or_else(|| meta.uid().to_string(), |u| u.name().to_string_lossy().into_owned()));
+            info.group = Some(users::get_group_by_gid(meta.gid()).map_or_else(|| meta.gid().to_string(), |g| g.name().to_string_lossy().into_owned()));
+            info.inode = Some(meta.ino());
+            info.links = Some(meta.nlink());
+            info.device = Some(meta.dev());
+            if let Ok(names) = xattr::list(path) {
+                info.xattrs = names.map(|n| n.to_string_lossy().into_owned()).collect();
+                info.xattrs.sort();
+            }
+        }
+        Ok(info)
+    }
+
+    /// `(label, value)` rows for display; fields the platform does not
+    /// provide are left out.
+    pub fn rows(&self) -> Vec<(&'static str, String)> {
+        let time = |t: &Option<DateTime<Local>>| t.map(|t| t.format("%Y-%m-%d %H:%M:%S").to_string());
+        let mut rows = vec![("Type", self.kind.to_string()), ("Size", format!("{} B", self.size))];
+        if let Some(target) = &self.link_target {
+            rows.push(("Target", target.display().to_string()));
+        }
+        let optional = [
+            ("Permissions", self.mode.map(|m| format!("{} ({:04o})", super::permissions::format_unix_rwx(Some(m & 0o777)), m))),
+            ("Owner", self.owner.clone()),
+            ("Group", self.group.clone()),
+            ("Inode", self.inode.map(|i| i.to_string())),
+            ("Links", self.links.map(|l| l.to_string())),
+            ("Device", self.device.map(|d| format!("{:#x}", d))),
+            ("Modified", time(&self.modified)),
+            ("Accessed", time(&self.accessed)),
+            ("Attributes", self.attributes.map(|a| a.letters())),
+        ];
+        rows.extend(optional.into_iter().filter_map(|(label, value)| value.map(|v| (label, v))));
+        if self.mode.is_some() {
+            let xattrs = if self.xattrs.is_empty() { "none".to_string() } else { self.xattrs.join(", ") };
+            rows.push(("Xattrs", xattrs));
+        }
+        rows
+    }
+}
+
+/// Entries below a directory and their total size.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DirTotals {
+    pub entries: u64,
+    pub bytes: u64,
+}
+
+/// Count the entries below `dir` and add up the sizes of its files,
+/// without following symlinks. Unreadable parts and, with `ignore`, the
+/// entries its rules exclude are skipped. Stops early (with partial
+/// totals) once `cancel` is set.
+pub fn dir_totals(dir: &Path, ignore: Option<&IgnoreRules>, cancel: &AtomicBool) -> DirTotals {
+    let mut totals = DirTotals::default();
+    let walk = WalkDir::new(dir).min_depth(1).into_iter();
+    for entry in walk.filter_entry(|e| !ignore.is_some_and(|r| r.matches(e.path(), e.file_type().is_dir()))).filter_map(Result::ok) {
+        if cancel.load(Ordering::Relaxed) {
+            break;
+        }
+        totals.entries += 1;
+        if entry.file_type().is_file() {
+            totals.bytes += entry.metadata().map(|m| m.len()).unwrap_or(0);
+        }
+    }
+    totals
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_details_and_totals() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::create_dir(tmp.path().join("sub")).unwrap();
+        std::fs::write(tmp.path().join("a"), "12345").unwrap();
+        std::fs::write(tmp.path().join("sub/b"), "123").unwrap();
+
+        let info = FileInfo::read(&tmp.path().join("a")).unwrap();
+        assert_eq!((info.kind, info.size), ("file", 5));
+        let labels: Vec<&str> = info.rows().iter().map(|(l, _)| *l).collect();
+        assert!(labels.starts_with(&["Type", "Size"]));
+        #[cfg(unix)]
+        assert!(["Permissions", "Inode", "Links", "Device", "Xattrs"].iter().all(|l| labels.contains(l)));
+
+        let totals = dir_totals(tmp.path(), None, &AtomicBool::new(false));
+        assert_eq!(totals, DirTotals { entries: 3, bytes: 8 });
+        assert_eq!(dir_totals(tmp.path(), None, &AtomicBool::new(true)).entries, 0);
+
+        std::fs::create_dir(tmp.path().join(".git")).unwrap();
+        std::fs::write(tmp.path().join(".gitignore"), "sub/\n").unwrap();
+        let rules = IgnoreRules::for_dir(tmp.path()).unwrap();
+        let totals = dir_totals(tmp.path(), Some(&rules), &AtomicBool::new(false));
+        assert_eq!(totals, DirTotals { entries: 3, bytes: 5 + 5 });
+    }
+}
@@ -51,6 +51,9 @@ mod unix_extra {
     }
 }
 
+#[cfg(unix)]
+pub(crate) use unix_extra::copy_unix_extras;
+
 /// Copy permission bits from `src` to `dst`.
 ///
 /// This propagates IO errors (it's considered a core operation).
@@ -107,6 +110,46 @@ pub(crate) fn preserve_all_metadata(src: &Path, dst: &Path) -> io::Result<()> {
     Ok(())
 }
 
+/// Parse the time typed into the change-times dialog: `now`, or a local
+/// `YYYY-MM-DD`, `YYYY-MM-DD HH:MM` or `YYYY-MM-DD HH:MM:SS`.
+pub fn parse_time(input: &str) -> Result<FileTime, String> {
+    use chrono::{Local, NaiveDate, NaiveDateTime, TimeZone};
+
+    let input = input.trim();
+    if input.eq_ignore_ascii_case("now") {
+        return Ok(FileTime::from_system_time(crate::app::core::clock::now()));
+    }
+    let naive = ["%Y-%m-%d %H:%M:%S", "%Y-%m-%d %H:%M"]
+        .iter()
+        .find_map(|fmt| NaiveDateTime::parse_from_str(input, fmt).ok())
+        .or_else(|| NaiveDate::parse_from_str(input, "%Y-%m-%d").ok().and_then(|d| d.and_hms_opt(0, 0, 0)))
+        .ok_or_else(|| format!("`{}` is not `now` or YYYY-MM-DD [HH:MM[:SS]]", input))?;
+    // A time skipped by a DST change has no local instant.
+    let local = Local.from_local_datetime(&naive).earliest().ok_or_else(|| format!("`{}` does not exist in local time", input))?;
+    Ok(FileTime::from_unix_time(local.timestamp(), 0))
+}
+
+/// Set the access and modification times of `path` and, when it is a
+/// directory and `recursive` is set, of everything below it. Symlinks are
+/// not followed. Every entry is attempted; the first failure is returned.
+pub fn set_times(path: &Path, atime: FileTime, mtime: FileTime, recursive: bool) -> io::Result<()> {
+    let depth = if recursive { usize::MAX } else { 0 };
+    let mut first_err = None;
+    for entry in WalkDir::new(path).follow_root_links(false).max_depth(depth) {
+        let result = entry.map_err(io::Error::from).and_then(|e| {
+            if e.path_is_symlink() {
+                filetime::set_symlink_file_times(e.path(), atime, mtime)
+            } else {
+                set_file_times(e.path(), atime, mtime)
+            }
+        });
+        if let Err(e) = result {
+            first_err.get_or_insert(e);
+        }
+    }
+    first_err.map_or(Ok(()), Err)
+}
+
 fn apply_metadata_to_target(path: &Path, src_root: &Path, dst_root: &Path) {
     let rel = match path.strip_prefix(src_root) {
         Ok(r) => r,
@@ -193,4 +236,13 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn parse_time_accepts_dates_with_optional_time() {
+        let day = parse_time("2020-05-06").unwrap();
+        assert_eq!(parse_time(" 2020-05-06 00:00 ").unwrap(), day);
+        assert_eq!(parse_time("2020-05-06 00:00:30").unwrap().unix_seconds(), day.unix_seconds() + 30);
+        assert!(parse_time("NOW").is_ok());
+        assert!(parse_time("06/05/2020").is_err());
+    }
 }
@@ -0,0 +1,319 @@
+//! Byte counts, speed and pausing of a running copy or move.
+//!
+//! A background worker creates a `Meter`, shares it with the UI and
+//! installs it on its thread (`Meter::install`). While one is installed,
+//! the copy helpers (`helpers::atomic_copy_file_with`,
+//! `copy::copy_recursive_with` and `copy_items`) copy through the progress
+//! variants of `fs_extra` and add every chunk to it; the worker settles the
+//! count between items (`settle`), which also covers renames and
+//! skipped items. Pausing holds the copy between two chunks, so even a
+//! single large file stops promptly.
+
+use std::cell::RefCell;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// How often a paused copy checks whether it may go on.
+const PAUSE_POLL: Duration = Duration::from_millis(50);
+
+/// Weight of the newest sample in the smoothed speed.
+const SPEED_SMOOTHING: f64 = 0.3;
+
+/// Speed samples closer together than this are merged, so a burst of
+/// redraws does not make the speed jump around.
+const MIN_SAMPLE_INTERVAL: Duration = Duration::from_millis(250);
+
+thread_local! {
+    static CURRENT: RefCell<Option<Arc<Meter>>> = const { RefCell::new(None) };
+}
+
+/// Progress of one copy or move, in bytes.
+#[derive(Debug)]
+pub struct Meter {
+    bytes_done: AtomicU64,
+    bytes_total: AtomicU64,
+    file: Mutex<Option<PathBuf>>,
+    file_done: AtomicU64,
+    file_total: AtomicU64,
+    paused: AtomicBool,
+    /// The operation's cancel flag: a paused copy goes on once it is set.
+    cancel: Arc<AtomicBool>,
+    speed: Mutex<Speed>,
+}
+
+#[derive(Debug, Default)]
+struct Speed {
+    last: Option<(Instant, u64)>,
+    bytes_per_sec: Option<f64>,
+}
+
+/// What the progress dialog shows of a `Meter`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MeterStats {
+    pub bytes_done: u64,
+    pub bytes_total: u64,
+    /// The file being copied (a bare name inside directory copies), with
+    /// its bytes copied and size.
+    pub file: Option<(PathBuf, u64, u64)>,
+    /// Smoothed copy speed; `None` until it can be told.
+    pub bytes_per_sec: Option<f64>,
+    /// Time left for the whole operation and for the current file.
+    pub eta: Option<Duration>,
+    pub file_eta: Option<Duration>,
+    pub paused: bool,
+}
+
+impl Meter {
+    /// A meter for an operation cancelled through `cancel`.
+    pub fn new(cancel: Arc<AtomicBool>) -> Arc<Self> {
+        Arc::new(Meter {
+            bytes_done: AtomicU64::new(0),
+            bytes_total: AtomicU64::new(0),
+            file: Mutex::new(None),
+            file_done: AtomicU64::new(0),
+            file_total: AtomicU64::new(0),
+            paused: AtomicBool::new(false),
+            cancel,
+            speed: Mutex::new(Speed::default()),
+        })
+    }
+
+    /// Make `meter` the meter of the copy helpers on this thread.
+    pub fn install(meter: &Arc<Meter>) {
+        CURRENT.with(|c| *c.borrow_mut() = Some(meter.clone()));
+    }
+
+    /// The meter installed on this thread, if any.
+    pub fn current() -> Option<Arc<Meter>> {
+        CURRENT.with(|c| c.borrow().clone())
+    }
+
+    /// Set the size of everything the operation copies.
+    pub fn set_total(&self, bytes: u64) {
+        self.bytes_total.store(bytes, Ordering::Relaxed);
+    }
+
+    /// Between two items: `bytes` are done in total, whatever the chunks
+    /// reported (a rename reports none, a skipped item nothing).
+    pub fn settle(&self, bytes: u64) {
+        self.bytes_done.store(bytes, Ordering::Relaxed);
+        *self.file.lock().unwrap_or_else(|e| e.into_inner()) = None;
+    }
+
+    /// Note `delta` more bytes of `file` (of `file_total` bytes, `file_done`
+    /// of them copied) and wait while paused.
+    fn chunk(&self, file: &Path, file_done: u64, file_total: u64, delta: u64) {
+        self.bytes_done.fetch_add(delta, Ordering::Relaxed);
+        self.file_done.store(file_done, Ordering::Relaxed);
+        self.file_total.store(file_total, Ordering::Relaxed);
+        {
+            let mut current = self.file.lock().unwrap_or_else(|e| e.into_inner());
+            if current.as_deref() != Some(file) {
+                *current = Some(file.to_path_buf());
+            }
+        }
+        self.wait_while_paused();
+    }
+
+    /// Block while the operation is paused and not cancelled.
+    pub fn wait_while_paused(&self) {
+        while self.paused.load(Ordering::Relaxed) && !self.cancelled() {
+            std::thread::sleep(PAUSE_POLL);
+        }
+    }
+
+    fn cancelled(&self) -> bool {
+        self.cancel.load(Ordering::Relaxed)
+    }
+
+    pub fn paused(&self) -> bool {
+        self.paused.load(Ordering::Relaxed)
+    }
+
+    /// Pause or resume the operation.
+    pub fn set_paused(&self, paused: bool) {
+        self.paused.store(paused, Ordering::Relaxed);
+        // The time spent paused does not count towards the speed.
+        self.speed.lock().unwrap_or_else(|e| e.into_inner()).last = None;
+    }
+
+    /// The meter's state at `now`, updating the smoothed speed.
+    pub fn stats(&self, now: Instant) -> MeterStats {
+        let bytes_done = self.bytes_done.load(Ordering::Relaxed);
+        let bytes_total = self.bytes_total.load(Ordering::Relaxed);
+        let paused = self.paused();
+        let bytes_per_sec = {
+            let mut speed = self.speed.lock().unwrap_or_else(|e| e.into_inner());
+            if !paused {
+                speed.sample(now, bytes_done);
+            }
+            speed.bytes_per_sec
+        };
+        let file = self.file.lock().unwrap_or_else(|e| e.into_inner()).clone().map(|f| {
+            (f, self.file_done.load(Ordering::Relaxed), self.file_total.load(Ordering::Relaxed))
+        });
+        let eta = |left: u64| bytes_per_sec.filter(|s| *s > 0.0).map(|s| Duration::from_secs_f64(left as f64 / s));
+        MeterStats {
+            bytes_done,
+            bytes_total,
+            eta: eta(bytes_total.saturating_sub(bytes_done)),
+            file_eta: file.as_ref().and_then(|(_, done, total)| eta(total.saturating_sub(*done))),
+            file,
+            bytes_per_sec,
+            paused,
+        }
+    }
+}
+
+impl Speed {
+    fn sample(&mut self, now: Instant, bytes: u64) {
+        let Some((at, before)) = self.last else {
+            self.last = Some((now, bytes));
+            return;
+        };
+        let elapsed = now.saturating_duration_since(at);
+        if elapsed < MIN_SAMPLE_INTERVAL {
+            return;
+        }
+        let rate = bytes.saturating_sub(before) as f64 / elapsed.as_secs_f64();
+        self.bytes_per_sec = Some(match self.bytes_per_sec {
+            Some(old) => old + SPEED_SMOOTHING * (rate - old),
+            None => rate,
+        });
+        self.last = Some((now, bytes));
+    }
+}
+
+/// Bytes `paths` hold, directories counted with their contents; stops
+/// early once `cancel` is set.
+pub fn total_bytes(paths: &[PathBuf], cancel: &AtomicBool) -> u64 {
+    paths
+        .iter()
+        .map(|p| match std::fs::symlink_metadata(p) {
+            Ok(m) if m.is_dir() => crate::fs_op::info::dir_totals(p, None, cancel).bytes,
+            Ok(m) if m.is_file() => m.len(),
+            _ => 0,
+        })
+        .sum()
+}
+
+/// `fs_extra::file::copy`, reporting to the meter installed on this thread.
+pub(crate) fn file_copy(src: &Path, dst: &Path, options: &fs_extra::file::CopyOptions) -> io::Result<u64> {
+    let res = match Meter::current() {
+        Some(meter) => {
+            let mut last = 0;
+            fs_extra::file::copy_with_progress(src, dst, options, |p| {
+                meter.chunk(src, p.copied_bytes, p.total_bytes, p.copied_bytes.saturating_sub(last));
+                last = p.copied_bytes;
+            })
+        }
+        None => fs_extra::file::copy(src, dst, options),
+    };
+    res.map_err(crate::fs_op::helpers::fs_extra_io_error)
+}
+
+/// `fs_extra::dir::copy`, reporting to the meter installed on this thread.
+pub(crate) fn dir_copy(src: &Path, dst: &Path, options: &fs_extra::dir::CopyOptions) -> io::Result<u64> {
+    let res = match Meter::current() {
+        Some(meter) => {
+            let mut last = 0;
+            fs_extra::dir::copy_with_progress(src, dst, options, |p| {
+                dir_chunk(&meter, &mut last, &p.file_name, p.copied_bytes, p.file_bytes_copied, p.file_total_bytes)
+            })
+        }
+        None => fs_extra::dir::copy(src, dst, options),
+    };
+    res.map_err(crate::fs_op::helpers::fs_extra_io_error)
+}
+
+/// `fs_extra::copy_items`, reporting to the meter installed on this thread.
+pub(crate) fn copy_items(paths: &[PathBuf], dst: &Path, options: &fs_extra::dir::CopyOptions) -> io::Result<u64> {
+    let res = match Meter::current() {
+        Some(meter) => {
+            let mut last = 0;
+            fs_extra::copy_items_with_progress(paths, dst, options, |p| {
+                dir_chunk(&meter, &mut last, &p.file_name, p.copied_bytes, p.file_bytes_copied, p.file_total_bytes)
+            })
+        }
+        None => fs_extra::copy_items(paths, dst, options),
+    };
+    res.map_err(crate::fs_op::helpers::fs_extra_io_error)
+}
+
+/// Progress of the directory copies, which only know the names of their
+/// files and count `copied` bytes from the start (`last` is the count of
+/// the previous call). A cancelled operation stops the copy.
+fn dir_chunk(meter: &Meter, last: &mut u64, name: &str, copied: u64, file_done: u64, file_total: u64) -> fs_extra::dir::TransitProcessResult {
+    meter.chunk(Path::new(name), file_done, file_total, copied.saturating_sub(*last));
+    *last = copied;
+    if meter.cancelled() {
+        fs_extra::dir::TransitProcessResult::Abort
+    } else {
+        fs_extra::dir::TransitProcessResult::ContinueOrAbort
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_installed_copies_and_estimates_time_left() {
+        let tmp = tempfile::tempdir().unwrap();
+        let src = tmp.path().join("src");
+        std::fs::create_dir(&src).unwrap();
+        std::fs::write(src.join("a"), vec![0u8; 3000]).unwrap();
+        std::fs::write(tmp.path().join("b"), vec![0u8; 1000]).unwrap();
+        let cancel = Arc::new(AtomicBool::new(false));
+        let paths = [src.clone(), tmp.path().join("b")];
+        assert_eq!(total_bytes(&paths, &cancel), 4000);
+
+        let meter = Meter::new(cancel.clone());
+        Meter::install(&meter);
+        std::fs::create_dir(tmp.path().join("out")).unwrap();
+        copy_items(&paths, &tmp.path().join("out"), &fs_extra::dir::CopyOptions::new()).unwrap();
+        assert_eq!(meter.stats(Instant::now()).bytes_done, 4000);
+
+        let meter = Meter::new(cancel);
+        meter.set_total(4000);
+        let t0 = Instant::now();
+        meter.settle(1000);
+        meter.stats(t0);
+        meter.settle(2000);
+        let stats = meter.stats(t0 + Duration::from_secs(1));
+        assert_eq!(stats.bytes_per_sec, Some(1000.0));
+        assert_eq!(stats.eta, Some(Duration::from_secs(2)));
+
+        meter.set_paused(true);
+        assert!(meter.stats(t0 + Duration::from_secs(5)).paused);
+    }
+
+    #[test]
+    fn pausing_holds_a_copy_between_chunks() {
+        let tmp = tempfile::tempdir().unwrap();
+        let (src, dst) = (tmp.path().join("src"), tmp.path().join("dst"));
+        std::fs::write(&src, vec![0u8; 64 * 1024]).unwrap();
+        let meter = Meter::new(Arc::new(AtomicBool::new(false)));
+        meter.set_paused(true);
+
+        let copier = {
+            let meter = meter.clone();
+            std::thread::spawn(move || {
+                Meter::install(&meter);
+                let mut options = fs_extra::file::CopyOptions::new();
+                options.buffer_size = 4096;
+                file_copy(&src, &dst, &options)
+            })
+        };
+        std::thread::sleep(Duration::from_millis(200));
+        let held = meter.stats(Instant::now());
+        assert!(held.bytes_done < 64 * 1024 && !copier.is_finished());
+        assert_eq!(held.file.map(|(_, _, total)| total), Some(64 * 1024));
+
+        meter.set_paused(false);
+        assert_eq!(copier.join().unwrap().unwrap(), 64 * 1024);
+    }
+}
@@ -1,18 +1,38 @@
 pub mod app_ops;
+pub mod attributes;
+pub mod cache;
+#[cfg(unix)]
+pub mod chown;
+pub mod compare;
 pub mod copy;
 pub mod create;
+pub mod criteria;
+pub mod elevated;
 pub mod files;
+pub mod filetype;
+pub mod hash;
+pub mod ignore;
+pub mod info;
+pub mod format_info;
 pub mod helpers;
 pub mod test_helpers;
 pub mod error;
 pub mod metadata;
+pub mod meter;
+pub mod mounts;
 pub mod posix_acl;
 pub mod mv;
+pub mod options;
+pub mod open_files;
 pub mod path;
 pub mod permissions;
 pub mod remove;
+pub mod search;
 pub mod stat;
 pub mod symlink;
+pub mod transfer;
+pub mod usage;
+pub mod vfs;
 #[cfg(feature = "fs-watch")]
 pub mod watcher;
 
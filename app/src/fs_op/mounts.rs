@@ -0,0 +1,304 @@
+//! Mounted filesystems for the mount picker (`Mode::MountPicker`).
+//!
+//! On Linux the mounts are read from `/proc/mounts`, leaving out virtual
+//! filesystems (`proc`, `sysfs`, cgroups, ...) and anything mounted under
+//! `/proc`, `/sys`, `/dev` or `/run`. macOS and FreeBSD use `getmntinfo`,
+//! Windows lists the drive letters. Sizes come from `statvfs` (or
+//! `GetDiskFreeSpaceExW`) and are left out when the filesystem does not
+//! answer. Like `open_files`, everything here is best-effort.
+
+use std::path::{Path, PathBuf};
+
+/// Filesystem types that never hold user files.
+const VIRTUAL_FS_TYPES: &[&str] = &[
+    "autofs", "binfmt_misc", "bpf", "cgroup", "cgroup2", "configfs", "debugfs", "devpts", "devtmpfs", "efivarfs",
+    "fusectl", "hugetlbfs", "mqueue", "nsfs", "proc", "pstore", "rpc_pipefs", "securityfs", "selinuxfs", "sysfs",
+    "tracefs",
+];
+
+/// System directories whose mounts are left out, except removable media
+/// under `/run/media`.
+const SYSTEM_DIRS: &[&str] = &["/proc", "/sys", "/dev", "/run"];
+
+/// One mounted filesystem (or Windows drive).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Mount {
+    /// Directory the filesystem is mounted on (`C:\` for a drive).
+    pub root: PathBuf,
+    /// Device or remote source, e.g. `/dev/sda1` or `server:/export`.
+    pub source: String,
+    /// Filesystem type, e.g. `ext4`; empty when unknown.
+    pub fs_type: String,
+    /// Size in bytes, when known.
+    pub total: Option<u64>,
+    /// Bytes available to unprivileged users, when known.
+    pub free: Option<u64>,
+    /// USB sticks, SD cards, optical discs and the like.
+    pub removable: bool,
+}
+
+impl Mount {
+    fn new(root: PathBuf, source: String, fs_type: String) -> Self {
+        Mount { root, source, fs_type, total: None, free: None, removable: false }
+    }
+
+    /// `12.3 GiB free of 100.0 GiB`, or an empty string when the sizes are
+    /// unknown.
+    pub fn space_text(&self) -> String {
+        match (self.free, self.total) {
+            (Some(free), Some(total)) => format!("{} free of {}", format_bytes(free), format_bytes(total)),
+            (Some(free), None) => format!("{} free", format_bytes(free)),
+            _ => String::new(),
+        }
+    }
+}
+
+/// `bytes` in the largest binary unit that keeps the number at least 1,
+/// e.g. `1.5 GiB`.
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: &[&str] = &["B", "KiB", "MiB", "GiB", "TiB", "PiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit + 1 < UNITS.len() {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
+
+/// The mounted filesystems with their sizes, sorted by mount point.
+pub fn list_mounts() -> Vec<Mount> {
+    let mut mounts = platform_mounts();
+    for m in &mut mounts {
+        if m.total.is_none() {
+            (m.total, m.free) = space(&m.root);
+        }
+    }
+    mounts.sort_by(|a, b| a.root.cmp(&b.root));
+    mounts
+}
+
+/// Parse the contents of `/proc/mounts` (or `/etc/mtab`), keeping only
+/// filesystems that hold user files. A later mount on the same directory
+/// hides the earlier one, so only the last is kept.
+pub fn parse_proc_mounts(raw: &str) -> Vec<Mount> {
+    let mut mounts: Vec<Mount> = Vec::new();
+    for line in raw.lines() {
+        let mut fields = line.split_whitespace();
+        let (Some(source), Some(root), Some(fs_type)) = (fields.next(), fields.next(), fields.next()) else {
+            continue;
+        };
+        let root = PathBuf::from(unescape_mount_field(root));
+        if VIRTUAL_FS_TYPES.contains(&fs_type) || is_system_dir(&root) {
+            continue;
+        }
+        mounts.retain(|m| m.root != root);
+        mounts.push(Mount::new(root, unescape_mount_field(source), fs_type.to_string()));
+    }
+    mounts
+}
+
+fn is_system_dir(root: &Path) -> bool {
+    !root.starts_with("/run/media") && SYSTEM_DIRS.iter().any(|d| root.starts_with(d))
+}
+
+/// Undo the octal escapes (`\040` for a space) the kernel writes for
+/// whitespace and backslashes in mount fields.
+fn unescape_mount_field(field: &str) -> String {
+    let bytes = field.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let octal = bytes.get(i + 1..i + 4).filter(|d| bytes[i] == b'\\' && d.iter().all(|b| (b'0'..=b'7').contains(b)));
+        match octal.and_then(|d| u8::from_str_radix(std::str::from_utf8(d).ok()?, 8).ok()) {
+            Some(b) => {
+                out.push(b);
+                i += 4;
+            }
+            None => {
+                out.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+/// Whether the block device `source` (e.g. `/dev/sdb1`) is flagged as
+/// removable in sysfs, or the filesystem is mounted where desktops put
+/// removable media.
+#[cfg(target_os = "linux")]
+fn is_removable(source: &str, root: &Path) -> bool {
+    if root.starts_with("/media") || root.starts_with("/run/media") {
+        return true;
+    }
+    // `/dev/disk/by-uuid/...` and friends are symlinks to the device node.
+    let Some(device) = source.starts_with("/dev/").then(|| std::fs::canonicalize(source).ok()).flatten() else {
+        return false;
+    };
+    let Some(name) = device.file_name() else { return false };
+    let Ok(mut dev) = std::fs::canonicalize(Path::new("/sys/class/block").join(name)) else {
+        return false;
+    };
+    // A partition's flag lives on its disk.
+    if dev.join("partition").exists() {
+        dev.pop();
+    }
+    std::fs::read_to_string(dev.join("removable")).is_ok_and(|s| s.trim() == "1")
+}
+
+#[cfg(target_os = "linux")]
+fn platform_mounts() -> Vec<Mount> {
+    let raw = std::fs::read_to_string("/proc/mounts").or_else(|_| std::fs::read_to_string("/etc/mtab")).unwrap_or_default();
+    let mut mounts = parse_proc_mounts(&raw);
+    for m in &mut mounts {
+        m.removable = is_removable(&m.source, &m.root);
+    }
+    if mounts.is_empty() {
+        mounts.push(Mount::new(PathBuf::from("/"), String::new(), String::new()));
+    }
+    mounts
+}
+
+#[cfg(any(target_os = "macos", target_os = "freebsd"))]
+fn platform_mounts() -> Vec<Mount> {
+    use std::ffi::CStr;
+
+    let mut buf: *mut libc::statfs = std::ptr::null_mut();
+    // SAFETY: getmntinfo points `buf` at an array of `count` entries that
+    // stays valid until the next call; it is only read within this block.
+    let stats = unsafe {
+        let count = libc::getmntinfo(&mut buf, libc::MNT_NOWAIT);
+        if count <= 0 || buf.is_null() {
+            return vec![Mount::new(PathBuf::from("/"), String::new(), String::new())];
+        }
+        std::slice::from_raw_parts(buf, count as usize)
+    };
+    let text = |s: &[libc::c_char]| {
+        // SAFETY: the statfs name fields are NUL-terminated C strings.
+        unsafe { CStr::from_ptr(s.as_ptr()) }.to_string_lossy().into_owned()
+    };
+    stats
+        .iter()
+        .filter(|s| !matches!(text(&s.f_fstypename).as_str(), "devfs" | "autofs" | "procfs" | "fdescfs" | "nullfs"))
+        .map(|s| {
+            let root = PathBuf::from(text(&s.f_mntonname));
+            let mut m = Mount::new(root, text(&s.f_mntfromname), text(&s.f_fstypename));
+            m.removable = m.root.starts_with("/Volumes") || m.root.starts_with("/media");
+            m
+        })
+        .collect()
+}
+
+#[cfg(all(unix, not(any(target_os = "linux", target_os = "macos", target_os = "freebsd"))))]
+fn platform_mounts() -> Vec<Mount> {
+    vec![Mount::new(PathBuf::from("/"), String::new(), String::new())]
+}
+
+#[cfg(unix)]
+fn space(root: &Path) -> (Option<u64>, Option<u64>) {
+    match nix::sys::statvfs::statvfs(root) {
+        // The field types are narrower on some platforms.
+        #[allow(clippy::unnecessary_cast)]
+        Ok(st) => {
+            let unit = st.fragment_size() as u64;
+            (Some(st.blocks() as u64 * unit), Some(st.blocks_available() as u64 * unit))
+        }
+        Err(_) => (None, None),
+    }
+}
+
+#[cfg(windows)]
+mod win {
+    #[link(name = "kernel32")]
+    extern "system" {
+        pub fn GetDiskFreeSpaceExW(dir: *const u16, available: *mut u64, total: *mut u64, free: *mut u64) -> i32;
+        pub fn GetDriveTypeW(root: *const u16) -> u32;
+    }
+
+    pub const DRIVE_REMOVABLE: u32 = 2;
+    pub const DRIVE_REMOTE: u32 = 4;
+    pub const DRIVE_CDROM: u32 = 5;
+
+    /// `root` as a NUL-terminated UTF-16 string.
+    pub fn wide(root: &std::path::Path) -> Vec<u16> {
+        use std::os::windows::ffi::OsStrExt;
+        root.as_os_str().encode_wide().chain(Some(0)).collect()
+    }
+}
+
+#[cfg(windows)]
+fn platform_mounts() -> Vec<Mount> {
+    (b'A'..=b'Z')
+        .map(|letter| PathBuf::from(format!("{}:\\", letter as char)))
+        .filter(|root| root.exists())
+        .map(|root| {
+            // SAFETY: the argument is a NUL-terminated UTF-16 path.
+            let kind = unsafe { win::GetDriveTypeW(win::wide(&root).as_ptr()) };
+            let fs_type = match kind {
+                win::DRIVE_REMOVABLE => "removable",
+                win::DRIVE_REMOTE => "network",
+                win::DRIVE_CDROM => "cdrom",
+                _ => "",
+            };
+            let mut m = Mount::new(root, String::new(), fs_type.to_string());
+            m.removable = matches!(kind, win::DRIVE_REMOVABLE | win::DRIVE_CDROM);
+            m
+        })
+        .collect()
+}
+
+#[cfg(windows)]
+fn space(root: &Path) -> (Option<u64>, Option<u64>) {
+    let (mut available, mut total, mut free) = (0u64, 0u64, 0u64);
+    // SAFETY: the path is NUL-terminated and the out-pointers are valid.
+    let ok = unsafe { win::GetDiskFreeSpaceExW(win::wide(root).as_ptr(), &mut available, &mut total, &mut free) };
+    if ok != 0 {
+        (Some(total), Some(available))
+    } else {
+        (None, None)
+    }
+}
+
+#[cfg(not(any(unix, windows)))]
+fn platform_mounts() -> Vec<Mount> {
+    Vec::new()
+}
+
+#[cfg(not(any(unix, windows)))]
+fn space(_root: &Path) -> (Option<u64>, Option<u64>) {
+    (None, None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn proc_mounts_skip_virtual_filesystems() {
+        let raw = "\
+/dev/sda1 / ext4 rw,relatime 0 0
+proc /proc proc rw 0 0
+tmpfs /run tmpfs rw 0 0
+/dev/sdb1 /run/media/me/USB\\040STICK vfat rw 0 0
+server:/export /mnt/nas nfs4 rw 0 0
+/dev/sdc1 /mnt/nas ext4 rw 0 0
+";
+        let mounts = parse_proc_mounts(raw);
+        let roots: Vec<&Path> = mounts.iter().map(|m| m.root.as_path()).collect();
+        assert_eq!(roots, [Path::new("/"), Path::new("/run/media/me/USB STICK"), Path::new("/mnt/nas")]);
+        assert_eq!(mounts[2].source, "/dev/sdc1");
+        assert_eq!(mounts[1].fs_type, "vfat");
+    }
+
+    #[test]
+    fn bytes_use_binary_units() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024 * 1024), "3.0 GiB");
+    }
+}
@@ -4,6 +4,8 @@ use std::path::{Path, PathBuf};
 use walkdir::WalkDir;
 use rayon::prelude::*;
 
+use super::options::CopyOptions;
+
 /// Errors returned by move/copy helpers in this module.
 #[derive(Debug, thiserror::Error)]
 pub enum MvError {
@@ -38,8 +40,7 @@ pub fn rename_path<P: AsRef<Path>>(path: P, new_name: &str) -> Result<(), MvErro
     let p = path.as_ref();
     let parent = p.parent().ok_or(MvError::MissingFilename)?;
     let dest = parent.join(new_name);
-    fs::rename(p, &dest)?;
-    Ok(())
+    crate::logging::log_fs_op("rename", p, Some(&dest), fs::rename(p, &dest).map_err(MvError::from))
 }
 
 /// Copy path to `dest`. If `src` is a directory, copy recursively into `dest`.
@@ -48,18 +49,32 @@ pub fn rename_path<P: AsRef<Path>>(path: P, new_name: &str) -> Result<(), MvErro
 /// Symlinks that point to directories are resolved so the directory target
 /// is copied (this matches historical behaviour expected by tests).
 pub fn copy_path<P: AsRef<Path>, Q: AsRef<Path>>(src: P, dest: Q) -> Result<(), MvError> {
-    let s_orig = src.as_ref();
-    let d = dest.as_ref();
+    copy_path_with(src, dest, &CopyOptions { overwrite: true, ..Default::default() })
+}
+
+/// Copy `src` to `dest` honouring `opts`.
+///
+/// Files are copied with `helpers::atomic_copy_file_with`, so without
+/// `opts.overwrite` an existing destination file is an error. A symlinked
+/// `src` is only resolved when `opts.follow_symlinks` is set.
+pub fn copy_path_with<P: AsRef<Path>, Q: AsRef<Path>>(src: P, dest: Q, opts: &CopyOptions) -> Result<(), MvError> {
+    let (src, dest) = (src.as_ref(), dest.as_ref());
+    crate::logging::log_fs_op("copy", src, Some(dest), copy_path_unlogged(src, dest, opts))
+}
+
+fn copy_path_unlogged(s_orig: &Path, d: &Path, opts: &CopyOptions) -> Result<(), MvError> {
 
     // Resolve symlink-to-dir to its canonical target when possible.
     let s_path = match fs::symlink_metadata(s_orig) {
-        Ok(md) if md.file_type().is_symlink() => fs::canonicalize(s_orig).unwrap_or_else(|_| s_orig.to_path_buf()),
+        Ok(md) if md.file_type().is_symlink() && opts.follow_symlinks => fs::canonicalize(s_orig).map(|p| crate::fs_op::path::strip_verbatim(&p)).unwrap_or_else(|_| s_orig.to_path_buf()),
         _ => s_orig.to_path_buf(),
     };
 
     let s = s_path.as_path();
+    // An unresolved symlink is copied as a link by `atomic_copy_file_with`.
+    let is_link = fs::symlink_metadata(s).map(|m| m.file_type().is_symlink()).unwrap_or(false);
 
-    if s.is_dir() {
+    if s.is_dir() && !is_link {
         fs::create_dir_all(d)?;
 
         // Collect directory and file entries deterministically, then create
@@ -94,7 +109,7 @@ pub fn copy_path<P: AsRef<Path>, Q: AsRef<Path>>(src: P, dest: Q) -> Result<(),
                         return Some(MvError::Io { source: e, src: Some(from.clone()), dest: Some(dest_path.clone()), context: format!("creating parent for {:?}", dest_path) });
                     }
                 }
-                match crate::fs_op::helpers::atomic_copy_file(&from, &dest_path) {
+                match crate::fs_op::helpers::atomic_copy_file_with(&from, &dest_path, opts) {
                     Ok(_) => None,
                     Err(e) => Some(MvError::Io { source: e, src: Some(from), dest: Some(dest_path), context: String::new() }),
                 }
@@ -116,7 +131,7 @@ pub fn copy_path<P: AsRef<Path>, Q: AsRef<Path>>(src: P, dest: Q) -> Result<(),
             fs::create_dir_all(parent)?;
         }
 
-        crate::fs_op::helpers::atomic_copy_file(s, &final_dest).map(|_| ())?;
+        crate::fs_op::helpers::atomic_copy_file_with(s, &final_dest, opts).map(|_| ())?;
     }
 
     Ok(())
@@ -125,8 +140,11 @@ pub fn copy_path<P: AsRef<Path>, Q: AsRef<Path>>(src: P, dest: Q) -> Result<(),
 /// Move (rename) path to `dest`. If `rename` fails (cross-device), fallback to copy+remove.
 /// Move (rename) `src` to `dest`. Falls back to copy+remove on cross-device errors.
 pub fn move_path<P: AsRef<Path>, Q: AsRef<Path>>(src: P, dest: Q) -> Result<(), MvError> {
-    let s = src.as_ref();
-    let d = dest.as_ref();
+    let (src, dest) = (src.as_ref(), dest.as_ref());
+    crate::logging::log_fs_op("move", src, Some(dest), move_path_unlogged(src, dest))
+}
+
+fn move_path_unlogged(s: &Path, d: &Path) -> Result<(), MvError> {
 
     // If destination is an existing directory, move into it
     let final_dest: PathBuf = if d.exists() && d.is_dir() {
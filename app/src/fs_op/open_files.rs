@@ -0,0 +1,178 @@
+//! Detection of files that are currently open in other processes.
+//!
+//! Deleting or overwriting a log file or database that another process is
+//! actively writing can corrupt it. These helpers report such files so the
+//! UI can warn before a destructive operation. On Linux the `/proc/<pid>/fd`
+//! links are scanned directly; on other Unix platforms `lsof` is used when
+//! it is installed. Elsewhere (or when nothing can be inspected) no uses are
+//! reported, so callers must treat the result as best-effort.
+
+use std::path::{Path, PathBuf};
+
+/// A process holding an open handle on a path.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OpenFileUse {
+    /// The open file (a target itself or a file beneath a target directory).
+    pub path: PathBuf,
+    /// Process id holding the file open.
+    pub pid: u32,
+    /// Short process name (e.g. `sqlite3`), empty when unknown.
+    pub process: String,
+}
+
+/// Return the open handles on `targets` (or on anything beneath a target
+/// directory) held by processes other than this one.
+pub fn find_open_files(targets: &[PathBuf]) -> Vec<OpenFileUse> {
+    if targets.is_empty() {
+        return Vec::new();
+    }
+    // Canonicalise so fd links (which are always absolute and resolved)
+    // compare equal to user-supplied paths.
+    let targets: Vec<PathBuf> =
+        targets.iter().map(|t| std::fs::canonicalize(t).unwrap_or_else(|_| t.clone())).collect();
+    let mut uses = scan(&targets);
+    uses.sort_by(|a, b| (a.pid, &a.path).cmp(&(b.pid, &b.path)));
+    uses.dedup();
+    uses
+}
+
+fn is_target(path: &Path, targets: &[PathBuf]) -> bool {
+    targets.iter().any(|t| path.starts_with(t))
+}
+
+#[cfg(target_os = "linux")]
+fn scan(targets: &[PathBuf]) -> Vec<OpenFileUse> {
+    let me = std::process::id();
+    let mut out = Vec::new();
+    let Ok(procs) = std::fs::read_dir("/proc") else { return out };
+    for proc_entry in procs.flatten() {
+        let Some(pid) = proc_entry.file_name().to_str().and_then(|s| s.parse::<u32>().ok()) else {
+            continue;
+        };
+        if pid == me {
+            continue;
+        }
+        // Processes owned by other users are unreadable without privileges;
+        // silently skip them.
+        let Ok(fds) = std::fs::read_dir(proc_entry.path().join("fd")) else { continue };
+        let mut process: Option<String> = None;
+        for fd in fds.flatten() {
+            let Ok(link) = std::fs::read_link(fd.path()) else { continue };
+            if is_target(&link, targets) {
+                let name = process.get_or_insert_with(|| {
+                    std::fs::read_to_string(proc_entry.path().join("comm"))
+                        .map(|s| s.trim().to_string())
+                        .unwrap_or_default()
+                });
+                out.push(OpenFileUse { path: link, pid, process: name.clone() });
+            }
+        }
+    }
+    out
+}
+
+#[cfg(all(unix, not(target_os = "linux")))]
+fn scan(targets: &[PathBuf]) -> Vec<OpenFileUse> {
+    // `-F pcn` prints one field per line: `p<pid>`, `c<command>`, `n<name>`.
+    let me = std::process::id();
+    let Ok(output) = std::process::Command::new("lsof")
+        .arg("-F")
+        .arg("pcn")
+        .arg("--")
+        .args(targets)
+        .stderr(std::process::Stdio::null())
+        .output()
+    else {
+        return Vec::new();
+    };
+    let mut out = Vec::new();
+    let (mut pid, mut process) = (0u32, String::new());
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        let (tag, rest) = line.split_at(line.len().min(1));
+        match tag {
+            "p" => pid = rest.parse().unwrap_or(0),
+            "c" => process = rest.to_string(),
+            "n" if pid != 0 && pid != me => {
+                let path = PathBuf::from(rest);
+                if is_target(&path, targets) {
+                    out.push(OpenFileUse { path, pid, process: process.clone() });
+                }
+            }
+            _ => {}
+        }
+    }
+    out
+}
+
+#[cfg(not(unix))]
+fn scan(_targets: &[PathBuf]) -> Vec<OpenFileUse> {
+    Vec::new()
+}
+
+/// Build a short, human-readable warning for `uses`, or `None` when empty.
+///
+/// At most three entries are listed; the remainder is summarised.
+pub fn describe_open_files(uses: &[OpenFileUse]) -> Option<String> {
+    const SHOWN: usize = 3;
+    if uses.is_empty() {
+        return None;
+    }
+    let mut s = String::from("Warning: in use by another process:");
+    for u in uses.iter().take(SHOWN) {
+        let name = crate::fs_op::path::display_path(&u.path, crate::fs_op::path::MAX_DISPLAY_PATH_CHARS);
+        if u.process.is_empty() {
+            s.push_str(&format!("\n  {} (pid {})", name, u.pid));
+        } else {
+            s.push_str(&format!("\n  {} ({}, pid {})", name, u.process, u.pid));
+        }
+    }
+    if uses.len() > SHOWN {
+        s.push_str(&format!("\n  … and {} more", uses.len() - SHOWN));
+    }
+    Some(s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn describe_summarises_extra_entries() {
+        assert_eq!(describe_open_files(&[]), None);
+        let uses: Vec<OpenFileUse> = (0..5)
+            .map(|i| OpenFileUse { path: PathBuf::from(format!("/tmp/f{}", i)), pid: 100 + i, process: "sh".into() })
+            .collect();
+        let s = describe_open_files(&uses).unwrap();
+        assert!(s.contains("/tmp/f0 (sh, pid 100)"));
+        assert!(!s.contains("/tmp/f3"));
+        assert!(s.ends_with("… and 2 more"));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn detects_file_held_open_by_child_process() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("held.log");
+        std::fs::write(&file, "").unwrap();
+        // Keep the file open in a child process for the duration of the scan.
+        let mut child = std::process::Command::new("sh")
+            .arg("-c")
+            .arg("exec 3>>\"$0\"; exec sleep 5")
+            .arg(&file)
+            .spawn()
+            .unwrap();
+        let mut found = Vec::new();
+        for _ in 0..50 {
+            found = find_open_files(&[dir.path().to_path_buf()]);
+            if !found.is_empty() {
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(20));
+        }
+        let _ = child.kill();
+        let _ = child.wait();
+        assert_eq!(found.len(), 1, "expected the child's handle to be reported");
+        assert_eq!(found[0].pid, child.id());
+        assert_eq!(found[0].path, std::fs::canonicalize(&file).unwrap());
+    }
+}
@@ -0,0 +1,177 @@
+//! Options controlling low-level copy behaviour.
+//!
+//! `CopyOptions` is threaded through `helpers`, `copy` and `mv` so callers
+//! (for example the F5/F6 workers acting on a conflict decision) decide
+//! whether existing targets are replaced instead of each helper hard-coding
+//! its own policy. The plain helpers (`atomic_copy_file`, `copy_recursive`,
+//! `copy_path`, ...) keep their historical behaviour by delegating to the
+//! `*_with` variants with fixed options.
+//!
+//! The buffer size comes from the `copy_buffer_kib` setting; its auto mode
+//! (`resolve_buffer_size` with `0`) sizes the buffer per file.
+
+use std::path::Path;
+
+/// Default buffer size used for file copies (64 KiB).
+pub const DEFAULT_COPY_BUFFER_SIZE: usize = 64 * 1024;
+
+/// Smallest buffer accepted from the `copy_buffer_kib` setting.
+pub const MIN_COPY_BUFFER_SIZE: usize = 4 * 1024;
+
+/// Largest buffer accepted from the `copy_buffer_kib` setting.
+pub const MAX_COPY_BUFFER_SIZE: usize = 64 * 1024 * 1024;
+
+/// Pick a buffer size for copying a file of `file_len` bytes into `dst`.
+///
+/// Small files keep the 64 KiB default; larger files get bigger buffers to
+/// cut syscall overhead, and destinations on network filesystems (NFS,
+/// SMB/CIFS, ...) always get at least 1 MiB because every round-trip is
+/// expensive there.
+pub fn auto_buffer_size(file_len: u64, dst: &Path) -> usize {
+    const MIB: u64 = 1024 * 1024;
+    let by_size = if file_len < MIB {
+        DEFAULT_COPY_BUFFER_SIZE
+    } else if file_len < 64 * MIB {
+        256 * 1024
+    } else {
+        1024 * 1024
+    };
+    if is_network_fs(dst) {
+        by_size.max(1024 * 1024)
+    } else {
+        by_size
+    }
+}
+
+/// Resolve the `copy_buffer_kib` setting: `0` selects `auto_buffer_size`,
+/// any other value is a fixed size in KiB clamped to
+/// [`MIN_COPY_BUFFER_SIZE`, `MAX_COPY_BUFFER_SIZE`].
+pub fn resolve_buffer_size(setting_kib: u32, file_len: u64, dst: &Path) -> usize {
+    if setting_kib == 0 {
+        auto_buffer_size(file_len, dst)
+    } else {
+        (setting_kib as usize).saturating_mul(1024).clamp(MIN_COPY_BUFFER_SIZE, MAX_COPY_BUFFER_SIZE)
+    }
+}
+
+/// Best-effort check whether `path` (or its nearest existing ancestor)
+/// lives on a network filesystem. Always false off Linux.
+#[cfg(target_os = "linux")]
+pub fn is_network_fs(path: &Path) -> bool {
+    use std::os::unix::ffi::OsStrExt;
+    // Filesystem magic numbers from linux/magic.h.
+    const NETWORK_MAGICS: &[i64] = &[
+        0x6969, // NFS
+        0x517B, // SMB
+        0xFF53_4D42, // CIFS
+        0xFE53_4D42, // SMB2
+        0x5346_414F, // AFS
+        0x7375_7245, // CODA
+        0x0102_1997, // 9P
+        0x00C3_6400, // CEPH
+    ];
+    let Some(existing) = path.ancestors().find(|p| p.exists()) else { return false };
+    let Ok(c) = std::ffi::CString::new(existing.as_os_str().as_bytes()) else { return false };
+    let mut st: libc::statfs = unsafe { std::mem::zeroed() };
+    if unsafe { libc::statfs(c.as_ptr(), &mut st) } != 0 {
+        return false;
+    }
+    #[allow(clippy::unnecessary_cast)]
+    let magic = st.f_type as i64;
+    NETWORK_MAGICS.contains(&magic)
+}
+
+/// Best-effort check whether `path` lives on a network filesystem.
+#[cfg(not(target_os = "linux"))]
+pub fn is_network_fs(_path: &Path) -> bool {
+    false
+}
+
+/// Options for copy operations.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CopyOptions {
+    /// Replace existing destination files. When false, copying onto an
+    /// existing file fails with `io::ErrorKind::AlreadyExists` (single-file
+    /// helpers) or skips it (recursive directory copies).
+    pub overwrite: bool,
+    /// Buffer size in bytes used when streaming file contents.
+    pub buffer_size: usize,
+    /// Copy permissions and timestamps from the source after copying.
+    pub preserve_metadata: bool,
+    /// Copy the target of symlinks instead of recreating the link itself.
+    pub follow_symlinks: bool,
+}
+
+impl Default for CopyOptions {
+    fn default() -> Self {
+        CopyOptions {
+            overwrite: false,
+            buffer_size: DEFAULT_COPY_BUFFER_SIZE,
+            preserve_metadata: true,
+            follow_symlinks: true,
+        }
+    }
+}
+
+impl CopyOptions {
+    /// Equivalent `fs_extra` file copy options.
+    pub(crate) fn to_fs_extra_file(self) -> fs_extra::file::CopyOptions {
+        let mut o = fs_extra::file::CopyOptions::new();
+        o.overwrite = self.overwrite;
+        o.buffer_size = self.buffer_size;
+        o
+    }
+
+    /// Equivalent `fs_extra` directory copy options.
+    pub(crate) fn to_fs_extra_dir(self) -> fs_extra::dir::CopyOptions {
+        let mut o = fs_extra::dir::CopyOptions::new();
+        o.overwrite = self.overwrite;
+        o.skip_exist = !self.overwrite;
+        o.buffer_size = self.buffer_size;
+        o
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn defaults_do_not_overwrite() {
+        let o = CopyOptions::default();
+        assert!(!o.overwrite);
+        assert_eq!(o.buffer_size, DEFAULT_COPY_BUFFER_SIZE);
+        assert!(o.preserve_metadata);
+        assert!(o.follow_symlinks);
+    }
+
+    #[test]
+    fn auto_buffer_grows_with_file_size() {
+        let tmp = tempfile::tempdir().unwrap();
+        let small = auto_buffer_size(10, tmp.path());
+        let medium = auto_buffer_size(8 * 1024 * 1024, tmp.path());
+        let large = auto_buffer_size(512 * 1024 * 1024, tmp.path());
+        assert!(small <= medium && medium <= large);
+        assert_eq!(large, 1024 * 1024);
+    }
+
+    #[test]
+    fn fixed_setting_is_clamped() {
+        let p = Path::new("/");
+        assert_eq!(resolve_buffer_size(128, 0, p), 128 * 1024);
+        assert_eq!(resolve_buffer_size(1, 0, p), MIN_COPY_BUFFER_SIZE);
+        assert_eq!(resolve_buffer_size(u32::MAX, 0, p), MAX_COPY_BUFFER_SIZE);
+        assert_eq!(resolve_buffer_size(0, 0, p), auto_buffer_size(0, p));
+    }
+
+    #[test]
+    fn converts_to_fs_extra_options() {
+        let o = CopyOptions { overwrite: true, buffer_size: 4096, ..Default::default() };
+        let f = o.to_fs_extra_file();
+        assert!(f.overwrite);
+        assert_eq!(f.buffer_size, 4096);
+        let d = o.to_fs_extra_dir();
+        assert!(d.overwrite && !d.skip_exist);
+        assert_eq!(d.buffer_size, 4096);
+    }
+}
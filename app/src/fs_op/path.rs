@@ -1,5 +1,6 @@
 use directories_next::UserDirs;
-use std::path::{Path, PathBuf};
+use std::collections::HashSet;
+use std::path::{Component, Path, PathBuf, Prefix};
 
 /// Errors that can occur when resolving a user-supplied path.
 #[derive(thiserror::Error, Debug, PartialEq, Eq)]
@@ -29,6 +30,8 @@ pub enum PathError {
 ///   `USERPROFILE` environment variables).
 /// - Absolute paths are returned as-is.
 /// - Relative paths are resolved relative to `base`.
+/// - On Windows a bare drive (`D:`) means its root, and verbatim paths
+///   (`\\?\C:\x`) lose their prefix (see `strip_verbatim`).
 /// - The returned path must exist and be a directory; otherwise a `PathError`
 ///   describing the problem is returned.
 pub fn resolve_path(input: &str, base: &Path) -> Result<PathBuf, PathError> {
@@ -48,6 +51,13 @@ pub fn resolve_path(input: &str, base: &Path) -> Result<PathBuf, PathError> {
         }
     };
 
+    // `D:` alone is the drive's current directory to Windows; take it as
+    // the root like other file managers.
+    let candidate = match candidate.components().collect::<Vec<_>>().as_slice() {
+        [Component::Prefix(_)] => candidate.join(std::path::MAIN_SEPARATOR_STR),
+        _ => strip_verbatim(&candidate),
+    };
+
     if !candidate.exists() {
         return Err(PathError::NotFound(candidate));
     }
@@ -83,5 +93,226 @@ fn expand_tilde(input: &str) -> Option<PathBuf> {
     Some(p)
 }
 
+/// Score how well `pattern` matches `candidate`, ignoring case. Every
+/// character of `pattern` must appear in `candidate` in order; matches at
+/// the start, after a separator (`-`, `_`, `.`, space) and right after the
+/// previous match score higher. Returns `None` when `pattern` does not
+/// match; an empty pattern matches everything with score 0.
+pub fn fuzzy_score(pattern: &str, candidate: &str) -> Option<u32> {
+    let candidate: Vec<char> = candidate.chars().flat_map(char::to_lowercase).collect();
+    let mut score = 0;
+    let mut from = 0;
+    let mut prev: Option<usize> = None;
+    for pc in pattern.chars().flat_map(char::to_lowercase) {
+        let at = from + candidate[from..].iter().position(|&c| c == pc)?;
+        score += 1;
+        if at == 0 || matches!(candidate[at - 1], '-' | '_' | '.' | ' ') {
+            score += 8;
+        }
+        if prev.is_some_and(|p| p + 1 == at) {
+            score += 4;
+        }
+        prev = Some(at);
+        from = at + 1;
+    }
+    Some(score)
+}
+
+/// Skim-style fuzzy match of `pattern` against `candidate`: every
+/// character of `pattern` in order, placed where the score is highest.
+/// Each match scores, more at the start, after a separator (`/`, `-`,
+/// `_`, `.`, space) or a lower-to-upper case change and right after the
+/// previous match; each skipped character between matches costs a little.
+/// Returns the score and the char indices matched, or `None`.
+pub fn fuzzy_match(pattern: &str, candidate: &str, case_sensitive: bool) -> Option<(i64, Vec<usize>)> {
+    const MATCH: i64 = 16;
+    const BOUNDARY: i64 = 8;
+    const CAMEL: i64 = 7;
+    const CONSECUTIVE: i64 = 8;
+    const GAP: i64 = 1;
+    let fold = |c: char| if case_sensitive { c } else { c.to_lowercase().next().unwrap_or(c) };
+    let original: Vec<char> = candidate.chars().collect();
+    let name: Vec<char> = original.iter().map(|&c| fold(c)).collect();
+    let pattern: Vec<char> = pattern.chars().map(fold).collect();
+    let (m, n) = (pattern.len(), name.len());
+    if m == 0 {
+        return Some((0, Vec::new()));
+    }
+    if m > n {
+        return None;
+    }
+    let bonus = |j: usize| match j.checked_sub(1).map(|k| original[k]) {
+        None | Some('/' | '-' | '_' | '.' | ' ') => BOUNDARY,
+        Some(prev) if prev.is_lowercase() && original[j].is_uppercase() => CAMEL,
+        _ => 0,
+    };
+    // `best[i * n + j]`: the highest score with `pattern[i]` at `name[j]`
+    // and the position of `pattern[i - 1]` it follows.
+    let mut best: Vec<Option<(i64, usize)>> = vec![None; m * n];
+    for i in 0..m {
+        // Best earlier match not adjacent to `j`, less its gap.
+        let mut far: Option<(i64, usize)> = None;
+        for j in 0..n {
+            if i > 0 && j >= 2 {
+                let decayed = far.map(|(s, k)| (s - GAP, k));
+                let newest = best[(i - 1) * n + j - 2].map(|(s, _)| (s - GAP, j - 2));
+                far = match (decayed, newest) {
+                    (Some(a), Some(b)) => Some(if b.0 >= a.0 { b } else { a }),
+                    (a, b) => a.or(b),
+                };
+            }
+            if name[j] != pattern[i] {
+                continue;
+            }
+            let here = MATCH + bonus(j);
+            best[i * n + j] = if i == 0 {
+                Some((here, 0))
+            } else {
+                let adjacent = j.checked_sub(1).and_then(|k| best[(i - 1) * n + k]).map(|(s, _)| (s + here + CONSECUTIVE, j - 1));
+                let apart = far.map(|(s, k)| (s + here, k));
+                match (adjacent, apart) {
+                    (Some(a), Some(b)) if b.0 > a.0 => Some(b),
+                    (None, b) => b,
+                    (a, _) => a,
+                }
+            };
+        }
+    }
+    let (mut j, score) = (0..n).filter_map(|j| best[(m - 1) * n + j].map(|(s, _)| (j, s))).max_by_key(|&(j, s)| (s, std::cmp::Reverse(j)))?;
+    let mut positions = vec![0; m];
+    for i in (0..m).rev() {
+        positions[i] = j;
+        j = best[i * n + j].map_or(0, |(_, k)| k);
+    }
+    Some((score, positions))
+}
+
+/// Directory completions for a path being typed: the directories in the
+/// part of `input` before its last separator (resolved with `resolve_path`
+/// against `base`) whose names fuzzy-match the rest, best match first.
+/// Each completion is `input` with the last segment replaced by the name
+/// and a trailing separator. Hidden directories are only offered when the
+/// typed name starts with `.`.
+pub fn complete_dir(input: &str, base: &Path) -> Vec<String> {
+    let (head, name) = match input.rfind(['/', std::path::MAIN_SEPARATOR]) {
+        Some(i) => input.split_at(i + 1),
+        None => ("", input),
+    };
+    let dir = if head.is_empty() { Ok(base.to_path_buf()) } else { resolve_path(head, base) };
+    let Ok(read) = dir.and_then(|d| std::fs::read_dir(&d).map_err(|_| PathError::NotFound(d))) else {
+        return Vec::new();
+    };
+    let mut matches: Vec<(u32, String)> = read
+        .flatten()
+        .filter(|e| e.path().is_dir())
+        .filter_map(|e| {
+            let dir_name = e.file_name().to_string_lossy().into_owned();
+            if dir_name.starts_with('.') && !name.starts_with('.') {
+                return None;
+            }
+            fuzzy_score(name, &dir_name).map(|score| (score, dir_name))
+        })
+        .collect();
+    matches.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.cmp(&b.1)));
+    matches.into_iter().map(|(_, dir_name)| format!("{}{}{}", head, dir_name, std::path::MAIN_SEPARATOR)).collect()
+}
+
+/// `path` without a Windows verbatim prefix: `\\?\C:\x` becomes `C:\x`
+/// and `\\?\UNC\server\share\x` becomes `\\server\share\x`, the forms
+/// users type and other programs accept. `fs::canonicalize` returns
+/// verbatim paths on Windows. Other paths are returned unchanged.
+pub fn strip_verbatim(path: &Path) -> PathBuf {
+    let mut components = path.components();
+    let head = match components.next() {
+        Some(Component::Prefix(p)) => match p.kind() {
+            Prefix::VerbatimDisk(drive) => format!("{}:", drive as char),
+            Prefix::VerbatimUNC(server, share) => format!(r"\\{}\{}", server.to_string_lossy(), share.to_string_lossy()),
+            _ => return path.to_path_buf(),
+        },
+        _ => return path.to_path_buf(),
+    };
+    let mut out = PathBuf::from(head);
+    out.extend(components);
+    out
+}
+
+/// Whether two of `paths` have file names that are equal ignoring case.
+/// Copied into one directory on a case-insensitive filesystem (Windows,
+/// macOS) they land on the same file, so one would silently replace the
+/// other.
+pub fn names_collide(paths: &[PathBuf]) -> bool {
+    let mut seen = HashSet::new();
+    paths.iter().filter_map(|p| p.file_name()).any(|n| !seen.insert(n.to_string_lossy().to_lowercase()))
+}
+
+/// Split `path` into its longest leading part that exists and the rest,
+/// which `mkdir -p` would create: `/tmp/a/b` with only `/tmp` existing
+/// gives (`/tmp`, `a/b`).
+pub fn split_existing(path: &Path) -> (&Path, PathBuf) {
+    let existing = path.ancestors().find(|a| a.as_os_str().is_empty() || a.symlink_metadata().is_ok()).unwrap_or(Path::new(""));
+    let rest = path.strip_prefix(existing).map(Path::to_path_buf).unwrap_or_default();
+    (existing, rest)
+}
+
+/// Default maximum number of characters used when a path is shown inside
+/// dialogs, prompts and progress messages where the final width is not known.
+pub const MAX_DISPLAY_PATH_CHARS: usize = 60;
+
+/// Ellipsis inserted by `display_path` where the middle of a path was removed.
+const ELLIPSIS: char = '…';
+
+/// Return `path` as a display string with a leading home directory replaced
+/// by `~` (e.g. `/home/user/src` -> `~/src`). Paths outside the home
+/// directory are returned unchanged.
+pub fn abbreviate_home(path: &Path) -> String {
+    let home = UserDirs::new()
+        .map(|ud| ud.home_dir().to_path_buf())
+        .or_else(|| std::env::var_os("HOME").map(PathBuf::from));
+
+    if let Some(home) = home.filter(|h| h.parent().is_some()) {
+        if let Ok(rest) = path.strip_prefix(&home) {
+            if rest.as_os_str().is_empty() {
+                return "~".to_string();
+            }
+            return Path::new("~").join(rest).display().to_string();
+        }
+    }
+    path.display().to_string()
+}
+
+/// Format `path` for display within `max_chars` characters.
+///
+/// The home directory is abbreviated via `abbreviate_home`. When the result
+/// is still too long the middle of the path is replaced with `…` so the leaf
+/// (file or directory name) stays visible, e.g. `~/projects/…/src/main.rs`.
+/// If even the leaf does not fit, its trailing characters are kept.
+pub fn display_path(path: &Path, max_chars: usize) -> String {
+    let full = abbreviate_home(path);
+    let len = full.chars().count();
+    if len <= max_chars {
+        return full;
+    }
+    if max_chars == 0 {
+        return String::new();
+    }
+
+    // Keep the separator in front of the leaf so the truncated prefix reads
+    // as a directory (`/usr/sh…/leaf`).
+    let leaf = path
+        .file_name()
+        .map(|n| format!("{}{}", std::path::MAIN_SEPARATOR, n.to_string_lossy()))
+        .unwrap_or_default();
+    let leaf_len = leaf.chars().count();
+
+    if leaf_len + 1 >= max_chars {
+        // Not even the leaf fits: keep its tail.
+        let tail: String = full.chars().skip(len - (max_chars - 1)).collect();
+        return format!("{}{}", ELLIPSIS, tail);
+    }
+
+    let head: String = full.chars().take(max_chars - 1 - leaf_len).collect();
+    format!("{}{}{}", head, ELLIPSIS, leaf)
+}
+
 /* Unit tests moved to integration tests under `app/tests/` to centralize
    fs-op path behaviour checks. */
@@ -142,8 +142,8 @@ pub fn change_permissions<P: AsRef<Path>>(path: P, mode: u32) -> Result<(), Perm
 
     let path = path.as_ref();
     let perm = fs::Permissions::from_mode(mode);
-    fs::set_permissions(path, perm)?;
-    Ok(())
+    let res = fs::set_permissions(path, perm).map_err(PermissionError::from);
+    crate::logging::log_fs_op(&format!("chmod {:o}", mode), path, None, res)
 }
 
 #[cfg(not(unix))]
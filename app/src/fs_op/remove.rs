@@ -27,18 +27,21 @@ pub struct RemoveError(#[from] pub io::Error);
 pub fn remove_path(path: impl AsRef<Path>) -> Result<(), RemoveError> {
     let p = path.as_ref();
 
-    if !p.exists() {
+    // The entry itself, not what a symlink points to: dangling links are
+    // removed too, and a link to a directory never takes its contents.
+    let Ok(meta) = fs::symlink_metadata(p) else {
         // Nothing to do; removal of a non-existent path is a no-op.
         return Ok(());
-    }
+    };
 
-    if p.is_dir() {
-        fs::remove_dir_all(p)?;
+    let res = if meta.file_type().is_symlink() {
+        crate::fs_op::symlink::remove_symlink(p)
+    } else if meta.is_dir() {
+        fs::remove_dir_all(p)
     } else {
-        fs::remove_file(p)?;
-    }
-
-    Ok(())
+        fs::remove_file(p)
+    };
+    crate::logging::log_fs_op("remove", p, None, res.map_err(RemoveError::from))
 }
 
 
@@ -47,6 +50,23 @@ mod tests {
     use super::*;
     use tempfile::tempdir;
 
+    #[cfg(unix)]
+    #[test]
+    fn removes_links_not_their_targets() {
+        let td = tempdir().expect("create temp dir");
+        let dir = td.path().join("dir");
+        std::fs::create_dir_all(&dir).expect("create dir");
+        std::fs::write(dir.join("keep.txt"), b"x").expect("write file");
+        let (link, dangling) = (td.path().join("link"), td.path().join("dangling"));
+        std::os::unix::fs::symlink(&dir, &link).expect("link dir");
+        std::os::unix::fs::symlink(td.path().join("gone"), &dangling).expect("link nothing");
+
+        remove_path(&link).expect("remove link");
+        remove_path(&dangling).expect("remove dangling link");
+        assert!(std::fs::symlink_metadata(&link).is_err() && std::fs::symlink_metadata(&dangling).is_err());
+        assert!(dir.join("keep.txt").exists(), "target must survive");
+    }
+
     #[test]
     fn remove_file_and_dir_ok() {
         let td = tempdir().expect("create temp dir");
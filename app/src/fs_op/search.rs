@@ -0,0 +1,443 @@
+//! Recursive search for entries by name.
+//!
+//! `search` walks a tree without following symlinks and returns the paths
+//! whose name matches the pattern: a regular expression or a fuzzy
+//! pattern when asked for, a glob when it has `*` or `?` (see
+//! `glob_positions`), else a substring, ignoring case unless asked
+//! not to. With
+//! `respect_gitignore` the walk skips what the work tree's `.gitignore`
+//! files exclude, and the `.git` directory itself, using the same rules as
+//! the listings (`fs_op::ignore`).
+//!
+//! `SearchResultsFs` presents the results as a panel (`/`, `:find`, see
+//! `App::start_search`): listing the search root gives the matches, named
+//! by their path below the root, and everything else goes to the local
+//! filesystem, so copies, moves and deletes act on the real files.
+
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+use walkdir::WalkDir;
+
+use super::ignore::IgnoreRules;
+use super::path::fuzzy_match;
+use super::vfs::{LocalFs, VfsProvider};
+use crate::app::types::Entry;
+
+/// How a search pattern is matched against names.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MatchMode {
+    /// A substring, or a glob when the pattern has `*` or `?`.
+    #[default]
+    Substring,
+    /// A regular expression found anywhere in the name.
+    Regex,
+    /// The pattern's characters in order, scored like skim (see
+    /// `path::fuzzy_match`).
+    Fuzzy,
+}
+
+impl MatchMode {
+    /// The mode after this one, as Tab cycles them in the search prompt.
+    pub fn next(self) -> Self {
+        match self {
+            MatchMode::Substring => MatchMode::Regex,
+            MatchMode::Regex => MatchMode::Fuzzy,
+            MatchMode::Fuzzy => MatchMode::Substring,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            MatchMode::Substring => "substring",
+            MatchMode::Regex => "regex",
+            MatchMode::Fuzzy => "fuzzy",
+        }
+    }
+}
+
+/// What to look for and where not to look.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SearchOptions {
+    /// Name pattern.
+    pub pattern: String,
+    /// Match case exactly; otherwise case is ignored.
+    #[serde(default)]
+    pub case_sensitive: bool,
+    #[serde(default)]
+    pub mode: MatchMode,
+    /// Skip entries ignored by `.gitignore` and the `.git` directory.
+    #[serde(default)]
+    pub respect_gitignore: bool,
+}
+
+/// A compiled `SearchOptions` pattern. Patterns are kept case-folded
+/// unless matching is case-sensitive.
+#[derive(Debug)]
+pub enum NameMatcher {
+    Glob { pattern: Vec<char>, case_sensitive: bool },
+    Substring { pattern: Vec<char>, case_sensitive: bool },
+    Regex(regex::Regex),
+    Fuzzy { pattern: String, case_sensitive: bool },
+}
+
+/// `c` in lower case unless `case_sensitive`, one char for one char so
+/// indices into the name stay valid.
+fn fold(c: char, case_sensitive: bool) -> char {
+    if case_sensitive {
+        c
+    } else {
+        c.to_lowercase().next().unwrap_or(c)
+    }
+}
+
+impl NameMatcher {
+    /// Whether an entry called `name` matches.
+    pub fn matches(&self, name: &str) -> bool {
+        match self {
+            NameMatcher::Regex(re) => re.is_match(name),
+            _ => self.find(name).is_some(),
+        }
+    }
+
+    /// The char indices of `name` the pattern matched, for highlighting,
+    /// or `None` when it does not match. The wildcards of a glob match
+    /// no highlighted characters.
+    pub fn find(&self, name: &str) -> Option<Vec<usize>> {
+        match self {
+            NameMatcher::Glob { pattern, case_sensitive } => {
+                let name: Vec<char> = name.chars().map(|c| fold(c, *case_sensitive)).collect();
+                glob_positions(pattern, &name)
+            }
+            NameMatcher::Substring { pattern, case_sensitive } => {
+                let name: Vec<char> = name.chars().map(|c| fold(c, *case_sensitive)).collect();
+                if pattern.is_empty() {
+                    return Some(Vec::new());
+                }
+                let at = name.windows(pattern.len()).position(|w| w == pattern.as_slice())?;
+                Some((at..at + pattern.len()).collect())
+            }
+            NameMatcher::Regex(re) => {
+                let found = re.find(name)?;
+                let chars = name.char_indices().enumerate();
+                Some(chars.filter(|(_, (byte, _))| found.range().contains(byte)).map(|(i, _)| i).collect())
+            }
+            NameMatcher::Fuzzy { pattern, case_sensitive } => fuzzy_match(pattern, name, *case_sensitive).map(|(_, at)| at),
+        }
+    }
+
+    /// How well `name` matches a fuzzy pattern, higher is better; the
+    /// other modes score every match the same.
+    pub fn score(&self, name: &str) -> Option<i64> {
+        match self {
+            NameMatcher::Fuzzy { pattern, case_sensitive } => fuzzy_match(pattern, name, *case_sensitive).map(|(score, _)| score),
+            _ => self.matches(name).then_some(0),
+        }
+    }
+}
+
+/// Match `name` against the glob `pattern`, where `*` matches any run of
+/// characters and `?` any single one. Returns the indices of `name`
+/// matched by the literal characters of `pattern`.
+fn glob_positions(pattern: &[char], name: &[char]) -> Option<Vec<usize>> {
+    let (mut pi, mut ni) = (0, 0);
+    let mut positions = Vec::new();
+    // Position after the last `*`, the name position it is matched up to
+    // and how many positions were matched before it.
+    let mut star = None;
+    while ni < name.len() {
+        if pi < pattern.len() && (pattern[pi] == '?' || pattern[pi] == name[ni]) {
+            if pattern[pi] != '?' {
+                positions.push(ni);
+            }
+            pi += 1;
+            ni += 1;
+        } else if pi < pattern.len() && pattern[pi] == '*' {
+            star = Some((pi + 1, ni, positions.len()));
+            pi += 1;
+        } else if let Some((after, matched, before)) = star {
+            pi = after;
+            ni = matched + 1;
+            positions.truncate(before);
+            star = Some((after, matched + 1, before));
+        } else {
+            return None;
+        }
+    }
+    pattern[pi..].iter().all(|&c| c == '*').then_some(positions)
+}
+
+impl SearchOptions {
+    /// Options from a query as typed in the search prompt: leading flags,
+    /// then the pattern. `-r` makes it a regex, `-f` fuzzy, `-c`
+    /// case-sensitive, `-g` skips git-ignored paths and `-a` does not;
+    /// without either the ignore rules apply when `respect_gitignore` is
+    /// set. `--` ends the flags.
+    pub fn parse(query: &str, respect_gitignore: bool) -> Self {
+        let mut options = SearchOptions { respect_gitignore, ..Default::default() };
+        let mut rest = query.trim_start();
+        loop {
+            let (word, tail) = rest.split_once(' ').unwrap_or((rest, ""));
+            match word {
+                "-r" => options.mode = MatchMode::Regex,
+                "-f" => options.mode = MatchMode::Fuzzy,
+                "-c" => options.case_sensitive = true,
+                "-g" => options.respect_gitignore = true,
+                "-a" => options.respect_gitignore = false,
+                "--" => {
+                    rest = tail;
+                    break;
+                }
+                _ => break,
+            }
+            rest = tail.trim_start();
+        }
+        options.pattern = rest.to_string();
+        options
+    }
+
+    /// The options as a query `parse` reads back, such as `-r -c ^main`.
+    pub fn query(&self) -> String {
+        let mode = match self.mode {
+            MatchMode::Substring => "",
+            MatchMode::Regex => "-r ",
+            MatchMode::Fuzzy => "-f ",
+        };
+        let mut query = mode.to_string();
+        for (set, flag) in [(self.case_sensitive, "-c "), (self.respect_gitignore, "-g ")] {
+            if set {
+                query.push_str(flag);
+            }
+        }
+        if self.pattern.starts_with('-') {
+            query.push_str("-- ");
+        }
+        query.push_str(&self.pattern);
+        query
+    }
+
+    /// The pattern compiled for matching names: per `mode`, where a
+    /// substring pattern with `*` or `?` is a glob. Fails on an invalid
+    /// regex.
+    pub fn matcher(&self) -> Result<NameMatcher, regex::Error> {
+        let case_sensitive = self.case_sensitive;
+        let folded = || self.pattern.chars().map(|c| fold(c, case_sensitive)).collect();
+        Ok(match self.mode {
+            MatchMode::Regex => regex::RegexBuilder::new(&self.pattern).case_insensitive(!case_sensitive).build().map(NameMatcher::Regex)?,
+            MatchMode::Fuzzy => NameMatcher::Fuzzy { pattern: self.pattern.clone(), case_sensitive },
+            MatchMode::Substring if self.pattern.contains(['*', '?']) => NameMatcher::Glob { pattern: folded(), case_sensitive },
+            MatchMode::Substring => NameMatcher::Substring { pattern: folded(), case_sensitive },
+        })
+    }
+}
+
+/// `query` with its match mode flag (`-r`, `-f` or none) switched to the
+/// next mode, its other flags and the pattern kept as typed.
+pub fn with_next_mode(query: &str) -> String {
+    let mode = SearchOptions::parse(query, false).mode.next();
+    let mut flags: Vec<&str> = match mode {
+        MatchMode::Substring => Vec::new(),
+        MatchMode::Regex => vec!["-r"],
+        MatchMode::Fuzzy => vec!["-f"],
+    };
+    let mut rest = query.trim_start();
+    while let Some((word, tail)) = rest.split_once(' ') {
+        match word {
+            "-r" | "-f" => {}
+            "-c" | "-g" | "-a" => flags.push(word),
+            _ => break,
+        }
+        rest = tail.trim_start();
+    }
+    flags.push(rest);
+    flags.join(" ")
+}
+
+/// Paths below `root` whose name matches, in walk order (parents before
+/// their contents, names sorted), or best first for a fuzzy pattern.
+/// Stops early once `cancel` is set; finds nothing for an invalid regex.
+pub fn search(root: &Path, opts: &SearchOptions, cancel: &AtomicBool) -> Vec<PathBuf> {
+    let Ok(matcher) = opts.matcher() else {
+        return Vec::new();
+    };
+    let rules = if opts.respect_gitignore { IgnoreRules::for_dir(root) } else { None };
+    let skip = |path: &Path, is_dir: bool| {
+        opts.respect_gitignore && (path.file_name().is_some_and(|n| n == ".git") || rules.as_ref().is_some_and(|r| r.matches(path, is_dir)))
+    };
+    let mut found = Vec::new();
+    let walk = WalkDir::new(root).min_depth(1).sort_by_file_name().into_iter();
+    for entry in walk.filter_entry(|e| !skip(e.path(), e.file_type().is_dir())).filter_map(Result::ok) {
+        if cancel.load(Ordering::Relaxed) {
+            break;
+        }
+        if let Some(score) = matcher.score(&entry.file_name().to_string_lossy()) {
+            found.push((score, entry.into_path()));
+        }
+    }
+    found.sort_by_key(|&(score, _)| std::cmp::Reverse(score));
+    found.into_iter().map(|(_, path)| path).collect()
+}
+
+/// Search results listed as the contents of the search root.
+#[derive(Debug)]
+pub struct SearchResultsFs {
+    /// Directory searched; listing it lists the results.
+    pub root: PathBuf,
+    /// What was searched for.
+    pub options: SearchOptions,
+    /// `options` compiled, for highlighting what matched.
+    matcher: Option<NameMatcher>,
+    /// Paths found by the last walk; `None` until the next listing walks.
+    found: Mutex<Option<Vec<PathBuf>>>,
+    /// Set to stop a walk still running when the results are closed.
+    cancel: AtomicBool,
+}
+
+impl SearchResultsFs {
+    /// Results of searching `root` for `options`, found on first listing.
+    pub fn new(root: PathBuf, options: SearchOptions) -> Self {
+        let matcher = options.matcher().ok();
+        SearchResultsFs { root, options, matcher, found: Mutex::new(None), cancel: AtomicBool::new(false) }
+    }
+
+    /// The char indices of the file name `name` the search matched.
+    pub fn matched(&self, name: &str) -> Option<Vec<usize>> {
+        self.matcher.as_ref()?.find(name)
+    }
+
+    /// Forget the results so the next listing of the root searches again.
+    pub fn rerun(&self) {
+        *self.found.lock().unwrap_or_else(|e| e.into_inner()) = None;
+    }
+
+    /// Stop a search still running.
+    pub fn cancel(&self) {
+        self.cancel.store(true, Ordering::Relaxed);
+    }
+
+    /// The results, searching first when there are none yet. The lock is
+    /// not held while walking, so the UI can still look at the provider.
+    fn results(&self) -> Vec<PathBuf> {
+        if let Some(found) = self.found.lock().unwrap_or_else(|e| e.into_inner()).clone() {
+            return found;
+        }
+        let found = search(&self.root, &self.options, &self.cancel);
+        *self.found.lock().unwrap_or_else(|e| e.into_inner()) = Some(found.clone());
+        found
+    }
+}
+
+impl VfsProvider for SearchResultsFs {
+    fn name(&self) -> &str {
+        "search"
+    }
+
+    /// The results are local paths.
+    fn is_local(&self) -> bool {
+        true
+    }
+
+    /// The results still there for the root, each named by its path below
+    /// the root; the local listing for any other directory.
+    fn list(&self, dir: &Path) -> io::Result<Vec<Entry>> {
+        if dir != self.root {
+            return LocalFs.list(dir);
+        }
+        let entries = self.results().into_iter().filter_map(|path| {
+            let mut entry = LocalFs.stat(&path).ok()?;
+            if let Ok(relative) = path.strip_prefix(&self.root) {
+                entry.name = relative.as_os_str().to_owned();
+            }
+            Some(entry)
+        });
+        Ok(entries.collect())
+    }
+
+    fn stat(&self, path: &Path) -> io::Result<Entry> {
+        LocalFs.stat(path)
+    }
+
+    fn open(&self, path: &Path) -> io::Result<Box<dyn Read + Send>> {
+        LocalFs.open(path)
+    }
+
+    fn copy(&self, from: &Path, to: &Path) -> io::Result<()> {
+        LocalFs.copy(from, to)
+    }
+
+    fn remove(&self, path: &Path) -> io::Result<()> {
+        LocalFs.remove(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_names_and_skips_ignored_entries() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        for dir in [".git", "src", "target/debug"] {
+            std::fs::create_dir_all(root.join(dir)).unwrap();
+        }
+        for file in [".git/config", "src/main.rs", "src/Main.txt", "target/debug/main.d", ".gitignore"] {
+            std::fs::write(root.join(file), "").unwrap();
+        }
+        std::fs::write(root.join(".gitignore"), "target/\n").unwrap();
+        let cancel = AtomicBool::new(false);
+
+        let mut opts = SearchOptions { pattern: "main".into(), ..Default::default() };
+        assert_eq!(search(root, &opts, &cancel).len(), 3);
+        opts.respect_gitignore = true;
+        assert_eq!(search(root, &opts, &cancel), [root.join("src/Main.txt"), root.join("src/main.rs")]);
+        opts.pattern = "*.rs".into();
+        assert_eq!(search(root, &opts, &cancel), [root.join("src/main.rs")]);
+        opts.pattern = "config".into();
+        assert!(search(root, &opts, &cancel).is_empty());
+        opts = SearchOptions::parse("-r -c ^M", true);
+        assert_eq!(search(root, &opts, &cancel), [root.join("src/Main.txt")]);
+        opts.mode = MatchMode::Substring;
+        opts.pattern = "*.RS".into();
+        assert!(search(root, &opts, &cancel).is_empty());
+        opts.case_sensitive = false;
+        assert_eq!(search(root, &opts, &cancel), [root.join("src/main.rs")]);
+    }
+
+    #[test]
+    fn queries_round_trip_through_parse() {
+        let opts = SearchOptions::parse("-c  -r -- -x.*", false);
+        assert_eq!(opts, SearchOptions { pattern: "-x.*".into(), case_sensitive: true, mode: MatchMode::Regex, respect_gitignore: false });
+        assert_eq!(opts.query(), "-r -c -- -x.*");
+        assert_eq!(SearchOptions::parse(&opts.query(), false), opts);
+        assert!(!SearchOptions::parse("-a log", true).respect_gitignore);
+        assert_eq!(SearchOptions::parse("two words", false).pattern, "two words");
+        assert!(SearchOptions::parse("-r (", false).matcher().is_err());
+    }
+
+    #[test]
+    fn modes_report_the_matched_characters() {
+        let find = |query: &str, name: &str| SearchOptions::parse(query, false).matcher().unwrap().find(name);
+        assert_eq!(find("RE", "core.rs"), Some(vec![2, 3]));
+        assert_eq!(find("*.r?", "core.rs"), Some(vec![4, 5]));
+        assert_eq!(find("-r o.e", "core.rs"), Some(vec![1, 2, 3]));
+        assert_eq!(find("-f crs", "core.rs"), Some(vec![0, 5, 6]));
+        assert_eq!(find("-f -c CR", "core.rs"), None);
+        assert_eq!(with_next_mode("-c -r a b"), "-f -c a b");
+        assert_eq!(with_next_mode("-f x"), "x");
+        assert_eq!(with_next_mode(""), "-r ");
+
+        // Fuzzy results come best first.
+        let tmp = tempfile::tempdir().unwrap();
+        for file in ["src_main.rs", "sum.rs", "some.rs"] {
+            std::fs::write(tmp.path().join(file), "").unwrap();
+        }
+        let opts = SearchOptions::parse("-f sm", false);
+        let found = search(tmp.path(), &opts, &AtomicBool::new(false));
+        assert_eq!(found, [tmp.path().join("src_main.rs"), tmp.path().join("some.rs"), tmp.path().join("sum.rs")]);
+    }
+}
@@ -16,29 +16,65 @@ use std::path::{Path, PathBuf};
 /// - On Windows this chooses `symlink_dir` or `symlink_file` depending on
 ///   whether `src` looks like a directory (uses `metadata().is_dir()`).
 ///
-/// This function does not attempt to elevate privileges on Windows; callers
-/// must handle permission errors if needed.
+/// This function does not attempt to elevate privileges on Windows; see
+/// `create_symlink_as` for the error returned without them.
 #[allow(dead_code)]
 pub(crate) fn create_symlink(src: &Path, dst: &Path) -> io::Result<()> {
+    // If `src` doesn't exist, default to a file symlink to avoid
+    // accidentally creating a directory link.
+    let dir = src.metadata().map(|m| m.is_dir()).unwrap_or(false);
+    create_symlink_as(src, dst, dir)
+}
+
+/// Create a symbolic link at `dst` pointing to `target`, as a directory
+/// link when `dir` is set (only Windows tells the two apart).
+///
+/// Windows only lets administrators, or anyone with Developer Mode on,
+/// create symlinks; without that privilege the error says so and has
+/// `ErrorKind::PermissionDenied`, so the error dialog offers a retry.
+pub(crate) fn create_symlink_as(target: &Path, dst: &Path, dir: bool) -> io::Result<()> {
     #[cfg(unix)]
     {
-        use std::os::unix::fs::symlink as unix_symlink;
-        unix_symlink(src, dst)
+        let _ = dir;
+        std::os::unix::fs::symlink(target, dst)
     }
 
     #[cfg(windows)]
     {
         use std::os::windows::fs::{symlink_dir, symlink_file};
 
-        // If `src` exists we can choose the right symlink kind. If it doesn't
-        // exist, default to file symlink to avoid accidentally creating a
-        // directory junction.
-        let use_dir = src.metadata().map(|m| m.is_dir()).unwrap_or(false);
-        if use_dir {
-            symlink_dir(src, dst)
-        } else {
-            symlink_file(src, dst)
-        }
+        /// ERROR_PRIVILEGE_NOT_HELD
+        const PRIVILEGE_NOT_HELD: i32 = 1314;
+        let made = if dir { symlink_dir(target, dst) } else { symlink_file(target, dst) };
+        made.map_err(|e| match e.raw_os_error() {
+            Some(PRIVILEGE_NOT_HELD) => io::Error::new(
+                io::ErrorKind::PermissionDenied,
+                format!("creating symlink {}: needs Developer Mode or administrator rights", dst.display()),
+            ),
+            _ => e,
+        })
+    }
+}
+
+/// Recreate the symlink `src` at `dst` with the same target and, on
+/// Windows, the same kind (file or directory link).
+pub(crate) fn copy_symlink(src: &Path, dst: &Path) -> io::Result<()> {
+    let target = fs::read_link(src)?;
+    create_symlink_as(&target, dst, is_dir_link(&fs::symlink_metadata(src)?))
+}
+
+/// Whether the symlink described by `meta` (from `symlink_metadata`) is a
+/// Windows directory link. Always `false` elsewhere.
+pub(crate) fn is_dir_link(meta: &fs::Metadata) -> bool {
+    #[cfg(windows)]
+    {
+        use std::os::windows::fs::FileTypeExt;
+        meta.file_type().is_symlink_dir()
+    }
+    #[cfg(not(windows))]
+    {
+        let _ = meta;
+        false
     }
 }
 
@@ -59,29 +95,17 @@ pub(crate) fn read_symlink(path: &Path) -> io::Result<PathBuf> {
     path.read_link()
 }
 
-/// Remove a symbolic link at `path`.
+/// Remove a symbolic link at `path`, never what it points to.
 ///
-/// On Unix `remove_file` is used. On Windows we prefer `remove_dir` when the
-/// symlink target is a directory (determined via `metadata().is_dir()`) and
-/// `remove_file` otherwise.
-#[allow(dead_code)]
+/// On Unix `remove_file` is used. On Windows a directory link needs
+/// `remove_dir`; the link's own kind decides, so links whose target is
+/// gone are removed too.
 pub(crate) fn remove_symlink(path: &Path) -> io::Result<()> {
-    #[cfg(unix)]
-    {
+    if is_dir_link(&fs::symlink_metadata(path)?) {
+        fs::remove_dir(path)
+    } else {
         fs::remove_file(path)
     }
-
-    #[cfg(windows)]
-    {
-        // `metadata()` follows the symlink, so it tells us whether the target
-        // is a directory or not; use the appropriate remove call.
-        let meta = path.metadata()?;
-        if meta.is_dir() {
-            fs::remove_dir(path)
-        } else {
-            fs::remove_file(path)
-        }
-    }
 }
 
 #[cfg(test)]
@@ -111,6 +135,40 @@ mod tests {
         Ok(())
     }
 
+    #[cfg(unix)]
+    #[test]
+    fn dangling_symlink_is_removed() -> io::Result<()> {
+        let tmp = tempdir()?;
+        let link = tmp.path().join("gone.link");
+        create_symlink(&tmp.path().join("gone"), &link)?;
+        remove_symlink(&link)?;
+        assert!(fs::symlink_metadata(&link).is_err());
+        Ok(())
+    }
+
+    // Needs Developer Mode or administrator rights; skipped without them.
+    #[cfg(windows)]
+    #[test]
+    fn directory_links_keep_their_kind() -> io::Result<()> {
+        let tmp = tempdir()?;
+        let dir = tmp.path().join("somedir");
+        fs::create_dir(&dir)?;
+        let link = tmp.path().join("dir.link");
+        match create_symlink(&dir, &link) {
+            Err(e) if e.kind() == io::ErrorKind::PermissionDenied => return Ok(()),
+            other => other?,
+        }
+        let copy = tmp.path().join("copy.link");
+        copy_symlink(&link, &copy)?;
+        assert!(is_dir_link(&fs::symlink_metadata(&copy)?));
+        // Removing the directory a link points to leaves a dangling link.
+        fs::remove_dir(&dir)?;
+        remove_symlink(&copy)?;
+        remove_symlink(&link)?;
+        assert!(fs::symlink_metadata(&link).is_err());
+        Ok(())
+    }
+
     #[cfg(unix)]
     #[test]
     fn create_and_remove_dir_symlink() -> io::Result<()> {
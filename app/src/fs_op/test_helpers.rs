@@ -13,7 +13,8 @@
 
 #[cfg(feature = "test-helpers")]
 mod inner {
-    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::io;
+    use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
     use std::sync::{Mutex, MutexGuard, OnceLock};
 
     // Three distinct, globally-visible flags used to force failure
@@ -23,6 +24,10 @@ mod inner {
     static FORCE_RENAME_FAIL_IN_WRITE: AtomicBool = AtomicBool::new(false);
     static FORCE_RENAME_FAIL_IN_RENAME_OR_COPY: AtomicBool = AtomicBool::new(false);
 
+    // Bytes copies may still write before failing with ENOSPC;
+    // `u64::MAX` means no limit.
+    static SIMULATED_FREE_SPACE: AtomicU64 = AtomicU64::new(u64::MAX);
+
     // A singleton mutex used to serialize test actions that would
     // otherwise race (for example temporary file cleanup checks).
     static TEST_HOOK_MUTEX: OnceLock<Mutex<()>> = OnceLock::new();
@@ -58,6 +63,39 @@ mod inner {
         FORCE_RENAME_FAIL_IN_RENAME_OR_COPY.load(Ordering::SeqCst)
     }
 
+    /// Limit how many more bytes copies may write (`None` removes the
+    /// limit) to simulate a filesystem running out of space.
+    pub(crate) fn set_simulated_free_space(bytes: Option<u64>) {
+        SIMULATED_FREE_SPACE.store(bytes.unwrap_or(u64::MAX), Ordering::SeqCst);
+    }
+
+    /// Account for `bytes` just written by a copy. Fails with the platform's
+    /// "no space left" error once the simulated free space is used up.
+    pub(crate) fn consume_simulated_space(bytes: u64) -> io::Result<()> {
+        let res = SIMULATED_FREE_SPACE.fetch_update(Ordering::SeqCst, Ordering::SeqCst, |left| {
+            if left == u64::MAX {
+                Some(left)
+            } else {
+                left.checked_sub(bytes)
+            }
+        });
+        match res {
+            Ok(_) => Ok(()),
+            Err(_) => Err(no_space_error()),
+        }
+    }
+
+    fn no_space_error() -> io::Error {
+        #[cfg(unix)]
+        {
+            io::Error::from_raw_os_error(libc::ENOSPC)
+        }
+        #[cfg(not(unix))]
+        {
+            io::Error::from(io::ErrorKind::StorageFull)
+        }
+    }
+
     /// Acquire the global test lock. This function returns a
     /// `MutexGuard<'static, ()>` which releases the lock when dropped.
     ///
@@ -75,6 +113,7 @@ mod inner {
 #[cfg(not(feature = "test-helpers"))]
 #[allow(dead_code)]
 mod inner {
+    use std::io;
     use std::sync::{Mutex, MutexGuard, OnceLock};
 
     /// No-op setter when `test-helpers` feature is disabled.
@@ -94,6 +133,11 @@ mod inner {
         false
     }
 
+    pub(crate) fn set_simulated_free_space(_bytes: Option<u64>) {}
+    pub(crate) fn consume_simulated_space(_bytes: u64) -> io::Result<()> {
+        Ok(())
+    }
+
     /// Provide a dummy mutex guard when feature is disabled so callers
     /// can hold a lock without branching on the feature.
     pub(crate) fn acquire_test_lock() -> MutexGuard<'static, ()> {
@@ -127,6 +171,10 @@ pub(crate) use inner::should_force_rename_fail_in_copy;
 pub(crate) use inner::should_force_rename_fail_in_rename_or_copy;
 #[allow(unused_imports)]
 pub(crate) use inner::should_force_rename_fail_in_write;
+#[allow(unused_imports)]
+pub(crate) use inner::set_simulated_free_space;
+#[allow(unused_imports)]
+pub(crate) use inner::consume_simulated_space;
 
 #[cfg(test)]
 mod tests {
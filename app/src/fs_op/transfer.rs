@@ -0,0 +1,151 @@
+//! Building blocks for operation profiles: exclude patterns, planning the
+//! items of a copy, and a rate-limited file copy.
+
+use std::fs::{self, File};
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+use crate::fs_op::ignore::glob_match;
+
+/// Size of the chunks a throttled copy reads and writes.
+const THROTTLE_CHUNK: usize = 64 * 1024;
+
+/// Whether the file name of `path` matches one of `excludes` (globs, see
+/// `ignore::glob_match`).
+pub fn is_excluded(path: &Path, excludes: &[String]) -> bool {
+    let name = path.file_name().map(|n| n.to_string_lossy()).unwrap_or_default();
+    excludes.iter().any(|pattern| glob_match(pattern, &name))
+}
+
+/// One item of a planned copy.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PlannedItem {
+    pub src: PathBuf,
+    pub target: PathBuf,
+    pub is_dir: bool,
+}
+
+/// The items of a planned copy and the entries that could not be read
+/// while planning it.
+#[derive(Debug, Default)]
+pub struct Plan {
+    pub items: Vec<PlannedItem>,
+    /// Unreadable entries (and directories that could not be listed) with
+    /// why; nothing below them is planned.
+    pub errors: Vec<(PathBuf, io::Error)>,
+}
+
+/// Every directory and file to copy `src_paths` into `dst_dir`, parents
+/// before their contents. Entries matching `excludes` are left out along
+/// with everything below them. Symlinks are not followed.
+pub fn plan(src_paths: &[PathBuf], dst_dir: &Path, excludes: &[String]) -> Plan {
+    let mut plan = Plan::default();
+    for src in src_paths {
+        let Some(name) = src.file_name() else { continue };
+        let root = dst_dir.join(name);
+        let walk = walkdir::WalkDir::new(src).sort_by_file_name().into_iter().filter_entry(|e| !is_excluded(e.path(), excludes));
+        for entry in walk {
+            let entry = match entry {
+                Ok(entry) => entry,
+                Err(e) => {
+                    let path = e.path().unwrap_or(src).to_path_buf();
+                    plan.errors.push((path, e.into()));
+                    continue;
+                }
+            };
+            let rel = entry.path().strip_prefix(src).unwrap_or(Path::new(""));
+            let target = if rel.as_os_str().is_empty() { root.clone() } else { root.join(rel) };
+            plan.items.push(PlannedItem { src: entry.path().to_path_buf(), target, is_dir: entry.file_type().is_dir() });
+        }
+    }
+    plan
+}
+
+/// Keeps a copy at or below a byte rate by sleeping once it gets ahead.
+#[derive(Debug)]
+pub struct Throttle {
+    bytes_per_sec: u64,
+    started: Instant,
+    sent: u64,
+}
+
+impl Throttle {
+    /// A throttle allowing `mb_per_sec` megabytes per second.
+    pub fn new(mb_per_sec: u32) -> Self {
+        Throttle { bytes_per_sec: u64::from(mb_per_sec.max(1)) * 1024 * 1024, started: Instant::now(), sent: 0 }
+    }
+
+    /// Account for `bytes` written and wait until the rate allows more.
+    pub fn consume(&mut self, bytes: u64) {
+        self.sent += bytes;
+        let due = Duration::from_secs_f64(self.sent as f64 / self.bytes_per_sec as f64);
+        if let Some(wait) = due.checked_sub(self.started.elapsed()) {
+            std::thread::sleep(wait);
+        }
+    }
+}
+
+/// Copy the file `src` to `dst` no faster than `throttle` allows. The data
+/// goes to a `.part` file next to `dst` that is renamed into place once
+/// complete, and removed when the copy fails or `cancel` is set.
+pub fn copy_file_throttled(src: &Path, dst: &Path, throttle: &mut Throttle, cancel: &AtomicBool) -> io::Result<u64> {
+    let name = dst.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+    let part = dst.with_file_name(format!(".{}.part", name));
+    let result = (|| {
+        let mut input = File::open(src)?;
+        let mut output = File::create(&part)?;
+        let mut buf = vec![0u8; THROTTLE_CHUNK];
+        let mut copied = 0;
+        loop {
+            if cancel.load(Ordering::SeqCst) {
+                return Err(io::Error::new(io::ErrorKind::Interrupted, "Cancelled"));
+            }
+            let n = input.read(&mut buf)?;
+            if n == 0 {
+                break;
+            }
+            output.write_all(&buf[..n])?;
+            copied += n as u64;
+            throttle.consume(n as u64);
+        }
+        output.sync_all()?;
+        fs::rename(&part, dst)?;
+        Ok(copied)
+    })();
+    if result.is_err() {
+        let _ = fs::remove_file(&part);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plan_skips_excluded_subtrees() {
+        let tmp = tempfile::tempdir().unwrap();
+        let src = tmp.path().join("proj");
+        fs::create_dir_all(src.join("node_modules/pkg")).unwrap();
+        fs::write(src.join("node_modules/pkg/index.js"), "x").unwrap();
+        fs::write(src.join("main.rs"), "x").unwrap();
+        fs::write(src.join("scratch.tmp"), "x").unwrap();
+        let items = plan(std::slice::from_ref(&src), Path::new("/dst"), &["node_modules".to_string(), "*.tmp".to_string()]).items;
+        let targets: Vec<&Path> = items.iter().map(|i| i.target.as_path()).collect();
+        assert_eq!(targets, vec![Path::new("/dst/proj"), Path::new("/dst/proj/main.rs")]);
+        assert!(items[0].is_dir && !items[1].is_dir);
+    }
+
+    #[test]
+    fn plan_reports_what_it_cannot_read() {
+        let tmp = tempfile::tempdir().unwrap();
+        let missing = tmp.path().join("gone");
+        let plan = plan(std::slice::from_ref(&missing), Path::new("/dst"), &[]);
+        assert!(plan.items.is_empty());
+        assert_eq!(plan.errors.len(), 1);
+        assert_eq!(plan.errors[0].0, missing);
+        assert_eq!(plan.errors[0].1.kind(), io::ErrorKind::NotFound);
+    }
+}
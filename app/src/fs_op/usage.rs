@@ -0,0 +1,50 @@
+//! Disk usage of the entries of one directory, for the analyzer (`U`, see
+//! `app::core::usage`).
+//!
+//! Each entry is sized in turn, a directory with `info::dir_totals`, and
+//! sent as soon as it is known, so the analyzer fills in while the slow
+//! subtrees are still being walked.
+
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use super::info::dir_totals;
+
+/// Size of one entry of the analyzed directory.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UsageEntry {
+    pub name: String,
+    pub path: PathBuf,
+    pub is_dir: bool,
+    /// Bytes of the file, or of all files below the directory.
+    pub bytes: u64,
+    /// Entries below a directory; 0 for a file.
+    pub items: u64,
+}
+
+/// Size every entry of `dir`, without following symlinks, handing each to
+/// `found` once it is known. Stops once `cancel` is set or `found` returns
+/// `false`.
+pub fn scan(dir: &Path, cancel: &AtomicBool, mut found: impl FnMut(UsageEntry) -> bool) -> io::Result<()> {
+    for entry in std::fs::read_dir(dir)?.flatten() {
+        if cancel.load(Ordering::Relaxed) {
+            break;
+        }
+        let Ok(meta) = entry.path().symlink_metadata() else {
+            continue;
+        };
+        let path = entry.path();
+        let (bytes, items) = if meta.is_dir() {
+            let totals = dir_totals(&path, None, cancel);
+            (totals.bytes, totals.entries)
+        } else {
+            (meta.len(), 0)
+        };
+        let usage = UsageEntry { name: entry.file_name().to_string_lossy().into_owned(), path, is_dir: meta.is_dir(), bytes, items };
+        if !found(usage) {
+            break;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,131 @@
+//! Virtual filesystem abstraction for panels.
+//!
+//! A `Panel` reads its listing, previews and deletions through a
+//! `VfsProvider` instead of calling `std::fs` directly, so views that are
+//! not plain directories (archives, remote hosts, search results, trash)
+//! can be presented as panels by implementing the trait. `LocalFs` is the
+//! provider for the local filesystem and the default for every panel; its
+//! listings go through the `fs_op::cache` metadata cache.
+//!
+//! Paths passed to a provider are the `Entry::path` values it produced
+//! itself (or the panel `cwd`); their meaning is up to the provider.
+
+use std::fmt;
+use std::io::{self, Read};
+use std::path::Path;
+use std::sync::Arc;
+
+use walkdir::WalkDir;
+
+use crate::app::types::Entry;
+
+/// Backend a panel lists and operates on.
+pub trait VfsProvider: fmt::Debug + Send + Sync {
+    /// Short name shown to users, e.g. `"local"`.
+    fn name(&self) -> &str;
+    /// Whether paths are plain local paths that `std::fs` can operate on
+    /// directly. Copies from other providers go through `copy`.
+    fn is_local(&self) -> bool {
+        false
+    }
+    /// Immediate children of `dir` (unsorted).
+    fn list(&self, dir: &Path) -> io::Result<Vec<Entry>>;
+    /// Metadata of a single path, without following symlinks.
+    fn stat(&self, path: &Path) -> io::Result<Entry>;
+    /// Open a file for reading.
+    fn open(&self, path: &Path) -> io::Result<Box<dyn Read + Send>>;
+    /// Copy `from` to `to` within this provider; directories are copied
+    /// recursively and existing files are overwritten.
+    fn copy(&self, from: &Path, to: &Path) -> io::Result<()>;
+    /// Remove a file, or a directory recursively.
+    fn remove(&self, path: &Path) -> io::Result<()>;
+}
+
+/// The local filesystem.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct LocalFs;
+
+/// Shared handle to the local filesystem provider.
+pub fn local() -> Arc<dyn VfsProvider> {
+    Arc::new(LocalFs)
+}
+
+impl VfsProvider for LocalFs {
+    fn name(&self) -> &str {
+        "local"
+    }
+
+    fn is_local(&self) -> bool {
+        true
+    }
+
+    fn list(&self, dir: &Path) -> io::Result<Vec<Entry>> {
+        let mut entries = Vec::new();
+        for dir_entry in WalkDir::new(dir).min_depth(1).max_depth(1).follow_links(false) {
+            let dir_entry = dir_entry.map_err(io::Error::other)?;
+            let metadata = dir_entry.metadata()?;
+            let entry = super::cache::global().entry(dir_entry.path(), &metadata, || {
+                let name = dir_entry.file_name().to_os_string();
+                crate::app::core::panel::entry_from_metadata(name, dir_entry.path().to_path_buf(), &metadata)
+            });
+            entries.push(entry);
+        }
+        Ok(entries)
+    }
+
+    fn stat(&self, path: &Path) -> io::Result<Entry> {
+        crate::app::core::panel::stat_entry(path)
+    }
+
+    fn open(&self, path: &Path) -> io::Result<Box<dyn Read + Send>> {
+        Ok(Box::new(std::fs::File::open(path)?))
+    }
+
+    fn copy(&self, from: &Path, to: &Path) -> io::Result<()> {
+        if std::fs::symlink_metadata(from)?.is_dir() {
+            super::copy::copy_recursive(from, to)
+        } else {
+            super::helpers::ensure_parent_exists(to)?;
+            super::helpers::atomic_copy_file(from, to).map(|_| ())
+        }
+    }
+
+    fn remove(&self, path: &Path) -> io::Result<()> {
+        let result = if std::fs::symlink_metadata(path)?.is_dir() {
+            std::fs::remove_dir_all(path)
+        } else {
+            std::fs::remove_file(path)
+        };
+        super::cache::global().invalidate(path);
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn local_fs_lists_reads_copies_and_removes() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path().join("d");
+        std::fs::create_dir(&dir).unwrap();
+        std::fs::write(dir.join("a.txt"), "hello").unwrap();
+        let fs = LocalFs;
+
+        let names: Vec<String> = fs.list(tmp.path()).unwrap().iter().map(|e| e.display_name().into_owned()).collect();
+        assert_eq!(names, vec!["d"]);
+        assert!(fs.stat(&dir).unwrap().is_dir);
+
+        let copy = tmp.path().join("copy");
+        fs.copy(&dir, &copy).unwrap();
+        let mut text = String::new();
+        fs.open(&copy.join("a.txt")).unwrap().read_to_string(&mut text).unwrap();
+        assert_eq!(text, "hello");
+
+        fs.remove(&copy).unwrap();
+        fs.remove(&dir.join("a.txt")).unwrap();
+        assert!(!copy.exists() && !dir.join("a.txt").exists());
+        assert_eq!(fs.remove(&copy).unwrap_err().kind(), io::ErrorKind::NotFound);
+    }
+}
@@ -1,8 +1,105 @@
+//! Async terminal input.
+//!
+//! `TerminalEvents` is what the event loop awaits in its `tokio::select!`:
+//! with crossterm it is an `EventStream`, with the `termion-backend`
+//! feature it waits for stdin to become readable (or for `SIGWINCH`) and
+//! then reads through the `DefaultEventSource`. Neither busy-polls.
+//! `event_listener` is a callback wrapper for other async programs.
+
 use anyhow::Result;
 use crossterm::event::{Event, EventStream};
-use futures_util::stream::StreamExt;
+use futures::stream::StreamExt;
 use tracing::warn;
 
+use super::{InputError, InputEvent};
+
+/// Terminal events for an async event loop. Create it inside a tokio
+/// runtime.
+///
+/// `next` is cancel-safe: when another branch of a `select!` wins, no
+/// event is lost. With crossterm, a `next` that was cancelled leaves a
+/// reader thread waiting for the next event; it is passed on by the
+/// following `next`, but may be read early, so hand the terminal to
+/// another program (a shell, an editor) only right after `next` returned.
+pub struct TerminalEvents {
+    #[cfg(not(all(unix, feature = "termion-backend")))]
+    stream: EventStream,
+    /// stdin readiness; `None` when stdin cannot be polled (then the size
+    /// check below is all that wakes `next`).
+    #[cfg(all(unix, feature = "termion-backend"))]
+    stdin: Option<tokio::io::unix::AsyncFd<std::io::Stdin>>,
+    #[cfg(all(unix, feature = "termion-backend"))]
+    resize: Option<tokio::signal::unix::Signal>,
+}
+
+#[cfg(not(all(unix, feature = "termion-backend")))]
+impl TerminalEvents {
+    pub fn new() -> Self {
+        TerminalEvents { stream: EventStream::new() }
+    }
+
+    /// Wait for the next terminal event.
+    pub async fn next(&mut self) -> Result<InputEvent, InputError> {
+        match self.stream.next().await {
+            Some(Ok(event)) => Ok(super::map_crossterm_event(event)),
+            Some(Err(e)) => Err(e.into()),
+            None => Err(InputError::AsyncReceiverDisconnected),
+        }
+    }
+}
+
+#[cfg(all(unix, feature = "termion-backend"))]
+impl TerminalEvents {
+    pub fn new() -> Self {
+        use tokio::io::{unix::AsyncFd, Interest};
+        use tokio::signal::unix::{signal, SignalKind};
+        let stdin = AsyncFd::with_interest(std::io::stdin(), Interest::READABLE)
+            .map_err(|e| warn!("stdin cannot be polled, checking for input periodically: {}", e))
+            .ok();
+        let resize = signal(SignalKind::window_change()).ok();
+        TerminalEvents { stdin, resize }
+    }
+
+    /// Wait for the next terminal event.
+    pub async fn next(&mut self) -> Result<InputEvent, InputError> {
+        /// How often input is checked when stdin cannot be polled.
+        const FALLBACK_CHECK: std::time::Duration = std::time::Duration::from_millis(50);
+        use super::EventSource;
+        loop {
+            // Also picks up a size change and events parsed earlier.
+            if super::default_source().poll(std::time::Duration::ZERO)? {
+                return super::read_event_typed();
+            }
+            let TerminalEvents { stdin, resize } = self;
+            let readable = async {
+                match stdin {
+                    Some(fd) => fd.readable().await.map(|mut guard| guard.clear_ready()),
+                    None => {
+                        tokio::time::sleep(FALLBACK_CHECK).await;
+                        Ok(())
+                    }
+                }
+            };
+            let resized = async {
+                match resize {
+                    Some(signal) => signal.recv().await,
+                    None => std::future::pending().await,
+                }
+            };
+            tokio::select! {
+                ready = readable => ready?,
+                _ = resized => {}
+            }
+        }
+    }
+}
+
+impl Default for TerminalEvents {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// Asynchronously listens for terminal events and invokes `on_event` for each one.
 ///
 /// This is a thin wrapper around `crossterm::event::EventStream` that forwards
@@ -3,7 +3,14 @@
 //! This module provides a small, testable abstraction over terminal key
 //! events. It intentionally avoids exposing `crossterm` types in the public
 //! surface so application logic can be tested without a terminal.
+//!
+//! Where the terminal speaks the kitty keyboard protocol the crossterm
+//! backend turns it on (see `runner::terminal`), so Ctrl+Shift and Super
+//! chords arrive with their modifiers; other terminals keep the legacy
+//! codes, in which those chords lose the Shift and Super keys.
+//! `keyboard_protocol` reports which one is in use.
 use core::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 /// Logical key code (application-level).
 ///
@@ -97,6 +104,43 @@ pub fn is_printable_key(k: &KeyCode) -> bool {
     matches!(k, KeyCode::Char(_))
 }
 
+/// How key events are encoded by the terminal.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum KeyboardProtocol {
+    /// Legacy escape codes.
+    #[default]
+    Legacy,
+    /// The kitty progressive enhancement protocol (escape codes
+    /// disambiguated, shifted keys reported).
+    Kitty,
+}
+
+impl KeyboardProtocol {
+    /// Short description for the diagnostics overlay.
+    pub fn describe(self) -> &'static str {
+        match self {
+            KeyboardProtocol::Legacy => "legacy (no Ctrl+Shift/Super)",
+            KeyboardProtocol::Kitty => "kitty protocol",
+        }
+    }
+}
+
+static KITTY_KEYBOARD: AtomicBool = AtomicBool::new(false);
+
+/// Keyboard protocol negotiated when the terminal was set up.
+pub fn keyboard_protocol() -> KeyboardProtocol {
+    if KITTY_KEYBOARD.load(Ordering::Relaxed) {
+        KeyboardProtocol::Kitty
+    } else {
+        KeyboardProtocol::Legacy
+    }
+}
+
+/// Record the keyboard protocol the terminal was set up with.
+pub fn set_keyboard_protocol(protocol: KeyboardProtocol) {
+    KITTY_KEYBOARD.store(protocol == KeyboardProtocol::Kitty, Ordering::Relaxed);
+}
+
 // Conversion from crossterm types is implemented here; crossterm is a direct
 // dependency of this crate so these conversions are always available.
 impl From<crossterm::event::KeyCode> for KeyCode {
@@ -138,7 +182,16 @@ impl From<crossterm::event::KeyModifiers> for KeyModifiers {
 
 impl From<crossterm::event::KeyEvent> for Key {
     fn from(ev: crossterm::event::KeyEvent) -> Self {
-        Key { code: KeyCode::from(ev.code), modifiers: KeyModifiers::from(ev.modifiers) }
+        let mut modifiers = KeyModifiers::from(ev.modifiers);
+        let code = match ev.code {
+            // Shift+Tab has its own code.
+            crossterm::event::KeyCode::BackTab => {
+                modifiers.shift = true;
+                KeyCode::Tab
+            }
+            code => KeyCode::from(code),
+        };
+        Key { code, modifiers }
     }
 }
 
@@ -166,6 +219,16 @@ mod tests {
         assert!(!k.is_printable());
     }
 
+    #[test]
+    fn crossterm_back_tab_is_shift_tab() {
+        use crossterm::event::{KeyCode as CtKC, KeyEvent, KeyModifiers as CtMods};
+        let key = Key::from(KeyEvent::new(CtKC::BackTab, CtMods::SHIFT));
+        assert_eq!(key.code, KeyCode::Tab);
+        assert!(key.modifiers.shift);
+        let key = Key::from(KeyEvent::new(CtKC::Char('k'), CtMods::CONTROL | CtMods::SHIFT | CtMods::SUPER));
+        assert!(key.modifiers.ctrl && key.modifiers.shift && key.modifiers.logo);
+    }
+
     #[test]
     fn modifier_is_any() {
         let m = KeyModifiers { ctrl: false, alt: true, shift: false, logo: false };
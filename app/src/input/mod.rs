@@ -2,10 +2,10 @@
 //!
 //! This module provides a small abstraction layer over terminal input events
 //! (keyboard, mouse, and resize) to make handling input easier and platform
-//! independent inside the application. It currently uses `crossterm` for
-//! low-level event polling and reading, but exposes crate-local types for
-//! mouse events so the rest of the codebase does not depend directly on
-//! `crossterm` internals.
+//! independent inside the application. Events come from an `EventSource`:
+//! `crossterm` by default, or termion with the `termion-backend` feature on
+//! Unix (`DefaultEventSource`). Crate-local key and mouse types keep the
+//! rest of the codebase independent of either library.
 //!
 //! Examples
 //!
@@ -28,15 +28,21 @@ pub mod mouse;
 // Re-export a small, stable public surface for input types. Avoid a
 // blanket `pub use *` so downstream modules only rely on the necessary
 // symbols and refactors remain smaller.
-pub use keyboard::{Key, KeyCode, KeyModifiers};
+pub use keyboard::{keyboard_protocol, Key, KeyCode, KeyModifiers, KeyboardProtocol};
 pub use mouse::{is_left_down, MouseButton, MouseEvent, MouseEventKind};
 
+#[cfg(all(unix, feature = "termion-backend"))]
+pub mod termion_events;
+
+use std::sync::{Mutex, MutexGuard};
 use std::time::Duration;
 
+use once_cell::sync::Lazy;
+
 #[cfg(feature = "async-input")]
 use crossterm::event::Event;
 #[cfg(feature = "async-input")]
-use std::sync::{mpsc::{self, Receiver as MpscReceiver}, OnceLock, Mutex};
+use std::sync::{mpsc::{self, Receiver as MpscReceiver}, OnceLock};
 
 use thiserror::Error;
 
@@ -68,12 +74,17 @@ pub fn install_async_event_receiver(rx: MpscReceiver<Event>) {
     let _ = ASYNC_EVENT_RX.set(Mutex::new(rx));
 }
 
-/// Map a `crossterm::event::Event` into the crate-local `InputEvent`.
+/// Map a `crossterm::event::Event` into the crate-local `InputEvent`. Key
+/// releases (which terminals report with some keyboard protocols) are
+/// dropped.
 fn map_crossterm_event(ev: crossterm::event::Event) -> InputEvent {
     match ev {
-        crossterm::event::Event::Key(k) => InputEvent::Key(k.into()),
+        crossterm::event::Event::Key(k) if k.kind == crossterm::event::KeyEventKind::Release => InputEvent::Other,
+        crossterm::event::Event::Key(k) => InputEvent::Key(Key::from(k)),
         crossterm::event::Event::Mouse(m) => InputEvent::Mouse(m.into()),
         crossterm::event::Event::Resize(w, h) => InputEvent::Resize(w, h),
+        crossterm::event::Event::FocusGained => InputEvent::Focus(true),
+        crossterm::event::Event::FocusLost => InputEvent::Focus(false),
         _ => InputEvent::Other,
     }
 }
@@ -81,13 +92,15 @@ fn map_crossterm_event(ev: crossterm::event::Event) -> InputEvent {
 /// Unified, cross-platform input event for the app.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum InputEvent {
-    /// Keyboard key event (crate-local `KeyCode`).
-    Key(KeyCode),
+    /// Keyboard key event (crate-local `Key`, including modifiers).
+    Key(Key),
     /// Mouse event (crate-local `MouseEvent`).
     Mouse(MouseEvent),
     /// Terminal resize: (width, height).
     Resize(u16, u16),
-    /// Any other event (focus changes, unsupported kinds, ...).
+    /// The terminal gained (`true`) or lost the focus.
+    Focus(bool),
+    /// Any other event (unsupported kinds, ...).
     Other,
 }
 
@@ -120,36 +133,79 @@ pub enum InputError {
     AsyncReceiverDisconnected,
 }
 
-/// Read the next input event but return a typed `InputError` on failure.
+/// Where the event loop gets its input from.
 ///
-/// This helper gives callers a concrete error type to match on while the
-/// existing `read_event()` function keeps the legacy `anyhow::Result`
-/// signature for compatibility.
-pub fn read_event_typed() -> Result<InputEvent, InputError> {
-    #[cfg(feature = "async-input")]
-    {
-        if let Some(rx_mutex) = ASYNC_EVENT_RX.get() {
-            use mpsc::TryRecvError;
-            match rx_mutex.lock().unwrap().try_recv() {
-                Ok(ev) => return Ok(map_crossterm_event(ev)),
-                Err(TryRecvError::Disconnected) => {
-                    // Preserve historical behaviour: fall through to the
-                    // synchronous `read()` path rather than returning an
-                    // error so callers keep working when async producer dies.
+/// Implemented by `CrosstermEvents` and, with the `termion-backend`
+/// feature, `termion_events::TermionEvents`. The free functions `poll` and
+/// `read_event` use the `DefaultEventSource`.
+pub trait EventSource {
+    /// Wait up to `timeout` for an event; `true` when `read` will not block.
+    fn poll(&mut self, timeout: Duration) -> Result<bool, InputError>;
+
+    /// Next event, blocking until one arrives.
+    fn read(&mut self) -> Result<InputEvent, InputError>;
+}
+
+/// Events read with `crossterm::event` (and the async receiver installed
+/// with `install_async_event_receiver`, when that feature is enabled).
+#[derive(Debug, Default)]
+pub struct CrosstermEvents;
+
+impl EventSource for CrosstermEvents {
+    fn poll(&mut self, timeout: Duration) -> Result<bool, InputError> {
+        Ok(crossterm::event::poll(timeout)?)
+    }
+
+    fn read(&mut self) -> Result<InputEvent, InputError> {
+        #[cfg(feature = "async-input")]
+        {
+            if let Some(rx_mutex) = ASYNC_EVENT_RX.get() {
+                use mpsc::TryRecvError;
+                match rx_mutex.lock().unwrap().try_recv() {
+                    Ok(ev) => return Ok(map_crossterm_event(ev)),
+                    Err(TryRecvError::Disconnected) => {
+                        // Preserve historical behaviour: fall through to the
+                        // synchronous `read()` path rather than returning an
+                        // error so callers keep working when async producer dies.
+                    }
+                    Err(TryRecvError::Empty) => {}
                 }
-                Err(TryRecvError::Empty) => {}
             }
         }
+
+        crossterm::event::read()
+            .map_err(|e| InputError::Crossterm(format!("{e:?}")))
+            .map(map_crossterm_event)
     }
+}
+
+/// Event source of the fileZoom binary: crossterm unless the
+/// `termion-backend` feature is enabled on a Unix target.
+#[cfg(not(all(unix, feature = "termion-backend")))]
+pub type DefaultEventSource = CrosstermEvents;
+/// Event source of the fileZoom binary: crossterm unless the
+/// `termion-backend` feature is enabled on a Unix target.
+#[cfg(all(unix, feature = "termion-backend"))]
+pub type DefaultEventSource = termion_events::TermionEvents;
 
-    crossterm::event::read()
-        .map_err(|e| InputError::Crossterm(format!("{e:?}")))
-        .map(map_crossterm_event)
+static DEFAULT_SOURCE: Lazy<Mutex<DefaultEventSource>> = Lazy::new(|| Mutex::new(DefaultEventSource::default()));
+
+fn default_source() -> MutexGuard<'static, DefaultEventSource> {
+    DEFAULT_SOURCE.lock().unwrap_or_else(|e| e.into_inner())
 }
 
-/// Poll for an input event with a timeout. This delegates to `crossterm::event::poll`.
+/// Read the next input event but return a typed `InputError` on failure.
+///
+/// This helper gives callers a concrete error type to match on while the
+/// existing `read_event()` function keeps the legacy `anyhow::Result`
+/// signature for compatibility.
+pub fn read_event_typed() -> Result<InputEvent, InputError> {
+    default_source().read()
+}
+
+/// Poll the `DefaultEventSource` for an input event with a timeout.
 pub fn poll(timeout: Duration) -> anyhow::Result<bool> {
-    Ok(crossterm::event::poll(timeout)?)
+    Ok(default_source().poll(timeout)?)
 }
 
 /// Read the next input event and map it to `InputEvent`. Mouse events are converted
@@ -171,6 +227,15 @@ mod tests {
         assert!(s.contains("crossterm error"));
     }
 
+    #[test]
+    fn key_releases_are_dropped() {
+        use crossterm::event::{Event, KeyCode as CtKC, KeyEvent, KeyEventKind, KeyModifiers as CtMods};
+        let press = KeyEvent::new(CtKC::Char('a'), CtMods::NONE);
+        let release = KeyEvent::new_with_kind(CtKC::Char('a'), CtMods::NONE, KeyEventKind::Release);
+        assert_eq!(map_crossterm_event(Event::Key(press)), InputEvent::Key(Key::simple(KeyCode::Char('a'))));
+        assert_eq!(map_crossterm_event(Event::Key(release)), InputEvent::Other);
+    }
+
     #[cfg(feature = "async-input")]
     #[test]
     fn install_async_receiver_sets_once() {
@@ -183,18 +248,14 @@ mod tests {
     }
 }
 
-// Async input helpers (feature-gated). When the `async-input` feature is
-// enabled the crate exposes an `async_input` module which provides an
-// `EventStream`-based helper compatible with async runtimes.
-#[cfg(feature = "async-input")]
+// Async input for the event loop (`TerminalEvents`) and an
+// `EventStream`-based callback helper for other async programs.
 pub mod async_input;
 
 // Notes for async vs sync usage and diagnostics:
-// - For synchronous applications we prefer `crossterm::event::poll(timeout)`
-//   (used throughout the runner) to avoid blocking indefinitely and to
-//   allow batching/aggregation of bursts of events.
-// - For async applications consider `crossterm`'s `EventStream` feature which
-//   integrates with `tokio`/`async-std` and provides a Stream of events.
+// - The runner awaits `async_input::TerminalEvents` and, once an event
+//   arrived, uses the synchronous `poll(timeout)` to collect a burst of
+//   follow-up events (e.g. mouse moves) for coalescing.
 // - `read_event` will return an error if the underlying `crossterm::event::read`
 //   fails; callers may log and continue on transient IO errors to keep the
 //   application resilient (the runner already coalesces and logs input errors).
@@ -0,0 +1,189 @@
+//! termion implementation of `EventSource` (feature `termion-backend`,
+//! Unix only).
+//!
+//! stdin is only read after `poll(2)` reports input, so nothing is taken
+//! from the terminal while a shell or editor runs in it. termion has no
+//! resize event; a change of `terminal_size` is reported as
+//! `InputEvent::Resize` instead.
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use termion::event::{Event, Key as TKey, MouseButton as TButton, MouseEvent as TMouse};
+use termion::input::TermRead;
+
+use super::{EventSource, InputError, InputEvent, Key, KeyCode, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+
+/// How often the terminal size is checked while waiting for input.
+const RESIZE_CHECK: Duration = Duration::from_millis(50);
+
+/// Events parsed by termion from stdin.
+#[derive(Debug, Default)]
+pub struct TermionEvents {
+    pending: VecDeque<InputEvent>,
+    size: Option<(u16, u16)>,
+}
+
+impl TermionEvents {
+    /// Queue a `Resize` when the terminal size changed since the last check.
+    fn check_size(&mut self) {
+        let size = termion::terminal_size().ok();
+        if let (Some(old), Some((w, h))) = (self.size, size) {
+            if old != (w, h) {
+                self.pending.push_back(InputEvent::Resize(w, h));
+            }
+        }
+        self.size = size.or(self.size);
+    }
+
+    /// Wait up to `timeout` for stdin to become readable, then parse what
+    /// is there.
+    fn read_stdin(&mut self, timeout: Duration) -> Result<(), InputError> {
+        let mut fd = libc::pollfd { fd: libc::STDIN_FILENO, events: libc::POLLIN, revents: 0 };
+        let ms = timeout.as_millis().min(i32::MAX as u128) as i32;
+        let ready = unsafe { libc::poll(&mut fd, 1, ms) };
+        if ready < 0 {
+            let err = std::io::Error::last_os_error();
+            // A signal (SIGWINCH on resize) interrupted the wait.
+            return if err.kind() == std::io::ErrorKind::Interrupted { Ok(()) } else { Err(err.into()) };
+        }
+        if ready == 0 {
+            return Ok(());
+        }
+        let mut buf = [0u8; 1024];
+        let n = unsafe { libc::read(libc::STDIN_FILENO, buf.as_mut_ptr().cast(), buf.len()) };
+        if n < 0 {
+            return Err(std::io::Error::last_os_error().into());
+        }
+        for event in (&buf[..n as usize]).events().flatten() {
+            self.pending.push_back(map_termion_event(event));
+        }
+        Ok(())
+    }
+}
+
+impl EventSource for TermionEvents {
+    fn poll(&mut self, timeout: Duration) -> Result<bool, InputError> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            self.check_size();
+            if !self.pending.is_empty() {
+                return Ok(true);
+            }
+            let left = deadline.saturating_duration_since(Instant::now());
+            self.read_stdin(left.min(RESIZE_CHECK))?;
+            if self.pending.is_empty() && left.is_zero() {
+                return Ok(false);
+            }
+        }
+    }
+
+    fn read(&mut self) -> Result<InputEvent, InputError> {
+        loop {
+            if let Some(event) = self.pending.pop_front() {
+                return Ok(event);
+            }
+            self.poll(Duration::from_secs(60))?;
+        }
+    }
+}
+
+fn map_termion_event(event: Event) -> InputEvent {
+    match event {
+        Event::Key(k) => InputEvent::Key(key_from_termion(k)),
+        Event::Mouse(m) => InputEvent::Mouse(mouse_from_termion(m)),
+        Event::Unsupported(_) => InputEvent::Other,
+    }
+}
+
+/// Map a termion key; modifiers are set the way crossterm reports them
+/// (Shift for upper-case letters, Ctrl+c as `Char('c')` with Ctrl).
+fn key_from_termion(k: TKey) -> Key {
+    let with = |code, ctrl, alt, shift| Key { code, modifiers: KeyModifiers { ctrl, alt, shift, logo: false } };
+    let plain = |code| Key::simple(code);
+    match k {
+        TKey::Char('\n') | TKey::Char('\r') => plain(KeyCode::Enter),
+        TKey::Char('\t') => plain(KeyCode::Tab),
+        TKey::Char(c) => with(KeyCode::Char(c), false, false, c.is_uppercase()),
+        TKey::Alt(c) => with(KeyCode::Char(c), false, true, c.is_uppercase()),
+        TKey::Ctrl(c) => with(KeyCode::Char(c), true, false, false),
+        TKey::Backspace => plain(KeyCode::Backspace),
+        TKey::Esc => plain(KeyCode::Esc),
+        TKey::Left => plain(KeyCode::Left),
+        TKey::Right => plain(KeyCode::Right),
+        TKey::Up => plain(KeyCode::Up),
+        TKey::Down => plain(KeyCode::Down),
+        TKey::ShiftLeft => with(KeyCode::Left, false, false, true),
+        TKey::ShiftRight => with(KeyCode::Right, false, false, true),
+        TKey::ShiftUp => with(KeyCode::Up, false, false, true),
+        TKey::ShiftDown => with(KeyCode::Down, false, false, true),
+        TKey::AltLeft => with(KeyCode::Left, false, true, false),
+        TKey::AltRight => with(KeyCode::Right, false, true, false),
+        TKey::AltUp => with(KeyCode::Up, false, true, false),
+        TKey::AltDown => with(KeyCode::Down, false, true, false),
+        TKey::CtrlLeft => with(KeyCode::Left, true, false, false),
+        TKey::CtrlRight => with(KeyCode::Right, true, false, false),
+        TKey::CtrlUp => with(KeyCode::Up, true, false, false),
+        TKey::CtrlDown => with(KeyCode::Down, true, false, false),
+        TKey::Home => plain(KeyCode::Home),
+        TKey::CtrlHome => with(KeyCode::Home, true, false, false),
+        TKey::End => plain(KeyCode::End),
+        TKey::CtrlEnd => with(KeyCode::End, true, false, false),
+        TKey::PageUp => plain(KeyCode::PageUp),
+        TKey::PageDown => plain(KeyCode::PageDown),
+        TKey::BackTab => with(KeyCode::Tab, false, false, true),
+        TKey::Delete => plain(KeyCode::Delete),
+        TKey::Insert => plain(KeyCode::Insert),
+        TKey::F(n) => plain(KeyCode::F(n)),
+        TKey::Null => plain(KeyCode::Null),
+        _ => plain(KeyCode::Other),
+    }
+}
+
+/// Map a termion mouse event. termion coordinates are one-based and its
+/// release and hold events do not name the button; they are reported as
+/// the left button, which is the one the UI acts on.
+fn mouse_from_termion(m: TMouse) -> MouseEvent {
+    let at = |x: u16, y: u16, kind| MouseEvent { column: x.saturating_sub(1), row: y.saturating_sub(1), kind };
+    match m {
+        TMouse::Press(button, x, y) => {
+            let kind = match button {
+                TButton::Left => MouseEventKind::Down(MouseButton::Left),
+                TButton::Right => MouseEventKind::Down(MouseButton::Right),
+                TButton::Middle => MouseEventKind::Down(MouseButton::Middle),
+                TButton::WheelUp => MouseEventKind::ScrollUp,
+                TButton::WheelDown => MouseEventKind::ScrollDown,
+                _ => MouseEventKind::Other,
+            };
+            at(x, y, kind)
+        }
+        TMouse::Release(x, y) => at(x, y, MouseEventKind::Up(MouseButton::Left)),
+        TMouse::Hold(x, y) => at(x, y, MouseEventKind::Drag(MouseButton::Left)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(bytes: &[u8]) -> Vec<InputEvent> {
+        bytes.events().flatten().map(map_termion_event).collect()
+    }
+
+    #[test]
+    fn maps_keys_like_crossterm() {
+        assert_eq!(parse(b"\r"), vec![InputEvent::Key(Key::simple(KeyCode::Enter))]);
+        let ctrl_r = Key { code: KeyCode::Char('r'), modifiers: KeyModifiers { ctrl: true, ..Default::default() } };
+        assert_eq!(parse(b"\x12"), vec![InputEvent::Key(ctrl_r)]);
+        assert_eq!(parse(b"\x1b[15~"), vec![InputEvent::Key(Key::simple(KeyCode::F(5)))]);
+    }
+
+    #[test]
+    fn mouse_coordinates_are_zero_based() {
+        let events = parse(b"\x1b[<0;5;3M");
+        assert_eq!(
+            events,
+            vec![InputEvent::Mouse(MouseEvent { column: 4, row: 2, kind: MouseEventKind::Down(MouseButton::Left) })]
+        );
+    }
+}
@@ -0,0 +1,146 @@
+//! System clipboard through the platform's command-line tools: `pbcopy` /
+//! `pbpaste` on macOS, `clip` and PowerShell's `Get-Clipboard` on Windows
+//! and `wl-copy`, `xclip` or `xsel` on other systems, depending on the
+//! display server.
+//!
+//! In an SSH session, or when no tool is found, copying falls back to an
+//! OSC 52 escape sequence that asks the terminal emulator to set its
+//! clipboard when stdout is one; this also works through tmux. Reading
+//! needs one of the tools.
+
+use std::io::{self, IsTerminal, Write};
+use std::process::{Command, Stdio};
+
+type Tool = (&'static str, &'static [&'static str]);
+
+/// Commands that read clipboard text on stdin, most preferred first.
+fn copy_commands() -> Vec<Tool> {
+    if cfg!(target_os = "macos") {
+        return vec![("pbcopy", &[])];
+    }
+    if cfg!(windows) {
+        return vec![("clip", &[])];
+    }
+    let mut cmds: Vec<Tool> = Vec::new();
+    if std::env::var_os("WAYLAND_DISPLAY").is_some() {
+        cmds.push(("wl-copy", &[]));
+    }
+    if std::env::var_os("DISPLAY").is_some() {
+        cmds.push(("xclip", &["-selection", "clipboard"]));
+        cmds.push(("xsel", &["--clipboard", "--input"]));
+    }
+    cmds
+}
+
+/// Commands that print the clipboard text, most preferred first.
+fn paste_commands() -> Vec<Tool> {
+    if cfg!(target_os = "macos") {
+        return vec![("pbpaste", &[])];
+    }
+    if cfg!(windows) {
+        return vec![("powershell", &["-NoProfile", "-Command", "Get-Clipboard"])];
+    }
+    let mut cmds: Vec<Tool> = Vec::new();
+    if std::env::var_os("WAYLAND_DISPLAY").is_some() {
+        cmds.push(("wl-paste", &["--no-newline"]));
+    }
+    if std::env::var_os("DISPLAY").is_some() {
+        cmds.push(("xclip", &["-selection", "clipboard", "-o"]));
+        cmds.push(("xsel", &["--clipboard", "--output"]));
+    }
+    cmds
+}
+
+/// Whether we run in an SSH session, where local tools would set the
+/// remote machine's clipboard rather than the user's.
+fn over_ssh() -> bool {
+    std::env::var_os("SSH_TTY").is_some() || std::env::var_os("SSH_CONNECTION").is_some()
+}
+
+/// Put `text` on the system clipboard with the first available tool, or
+/// through the terminal with OSC 52.
+pub fn set_text(text: &str) -> io::Result<()> {
+    if !over_ssh() {
+        for (program, args) in copy_commands() {
+            let mut child = match Command::new(program).args(args).stdin(Stdio::piped()).stdout(Stdio::null()).stderr(Stdio::null()).spawn() {
+                Ok(child) => child,
+                Err(e) if e.kind() == io::ErrorKind::NotFound => continue,
+                Err(e) => return Err(e),
+            };
+            if let Some(mut stdin) = child.stdin.take() {
+                stdin.write_all(text.as_bytes())?;
+            }
+            let status = child.wait()?;
+            if !status.success() {
+                return Err(io::Error::other(format!("{} exited with {}", program, status)));
+            }
+            return Ok(());
+        }
+    }
+    let mut out = io::stdout();
+    if !out.is_terminal() {
+        return Err(io::Error::new(io::ErrorKind::NotFound, "no clipboard tool or terminal available"));
+    }
+    out.write_all(osc52(text, std::env::var_os("TMUX").is_some()).as_bytes())?;
+    out.flush()
+}
+
+/// Text on the system clipboard, read with the first available tool.
+pub fn get_text() -> io::Result<String> {
+    for (program, args) in paste_commands() {
+        let output = match Command::new(program).args(args).stdin(Stdio::null()).stderr(Stdio::null()).output() {
+            Ok(output) => output,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => continue,
+            Err(e) => return Err(e),
+        };
+        if !output.status.success() {
+            return Err(io::Error::other(format!("{} exited with {}", program, output.status)));
+        }
+        return Ok(String::from_utf8_lossy(&output.stdout).into_owned());
+    }
+    Err(io::Error::new(io::ErrorKind::NotFound, "no clipboard tool available"))
+}
+
+/// OSC 52 sequence setting the clipboard to `text`, wrapped in a tmux
+/// passthrough when `tmux` is set.
+pub fn osc52(text: &str, tmux: bool) -> String {
+    let seq = format!("\x1b]52;c;{}\x07", base64(text.as_bytes()));
+    if tmux {
+        format!("\x1bPtmux;{}\x1b\\", seq.replace('\x1b', "\x1b\x1b"))
+    } else {
+        seq
+    }
+}
+
+/// Standard base64 with padding.
+fn base64(data: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let b = [chunk[0], chunk.get(1).copied().unwrap_or(0), chunk.get(2).copied().unwrap_or(0)];
+        let n = (u32::from(b[0]) << 16) | (u32::from(b[1]) << 8) | u32::from(b[2]);
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(ALPHABET[(n >> (18 - 6 * i)) as usize & 63] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn osc52_encodes_text_in_base64() {
+        assert_eq!(base64(b""), "");
+        assert_eq!(base64(b"f"), "Zg==");
+        assert_eq!(base64(b"fo"), "Zm8=");
+        assert_eq!(base64(b"foo"), "Zm9v");
+        assert_eq!(osc52("/tmp/a b", false), "\x1b]52;c;L3RtcC9hIGI=\x07");
+        assert_eq!(osc52("x", true), "\x1bPtmux;\x1b\x1b]52;c;eA==\x07\x1b\\");
+    }
+}
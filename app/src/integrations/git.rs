@@ -0,0 +1,161 @@
+//! Git status of panel listings (feature `git`).
+//!
+//! When a panel lists a directory inside a git work tree, `status` runs
+//! `git status` once for the directory and the listing colours entries by
+//! their state: modified, staged, untracked, ignored or conflicted. A
+//! directory takes the state of the files beneath it. The branch (or the
+//! short commit id when detached) is shown in the header. The status is
+//! read again whenever the panel is listed, so changing directory and
+//! watcher refreshes keep it current.
+//!
+//! The `git` binary is used rather than a library so nothing is linked in;
+//! without it, or outside a work tree, listings are simply not annotated.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+/// State of one path in the work tree.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum GitFileStatus {
+    Ignored,
+    Untracked,
+    /// Changed in the index only (`git add`ed).
+    Staged,
+    /// Changed in the work tree.
+    Modified,
+    /// Unmerged after a conflicting merge or rebase.
+    Conflicted,
+}
+
+/// Status of the work tree containing a panel's directory.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct GitStatus {
+    /// Top-level directory of the work tree.
+    pub root: PathBuf,
+    /// Current branch, or the short commit id on a detached HEAD.
+    pub branch: Option<String>,
+    /// Paths relative to `root` with a status other than unmodified.
+    /// Untracked and ignored directories are listed as a whole.
+    pub files: HashMap<PathBuf, GitFileStatus>,
+}
+
+impl GitStatus {
+    /// Status of `path`: its own, or for a directory the most important
+    /// one of the paths beneath it.
+    pub fn status_of(&self, path: &Path) -> Option<GitFileStatus> {
+        let rel = path.strip_prefix(&self.root).ok()?;
+        if let Some(s) = self.files.get(rel) {
+            return Some(*s);
+        }
+        // Inside an untracked or ignored directory.
+        if let Some(s) = rel.ancestors().skip(1).find_map(|a| self.files.get(a)) {
+            return Some(*s);
+        }
+        self.files.iter().filter(|(p, _)| p.starts_with(rel) && *p != rel).map(|(_, s)| *s).filter(|s| *s != GitFileStatus::Ignored).max()
+    }
+}
+
+/// Status of the work tree containing `dir`, or `None` outside one (or
+/// when `git` is not installed).
+pub fn status(dir: &Path) -> Option<GitStatus> {
+    // The prefix is `dir` relative to the top level; taking as many
+    // components off `dir` keeps the panel's spelling of the path (which
+    // may go through symlinks) so entry paths match.
+    let prefix = git(dir, &["rev-parse", "--show-prefix"])?;
+    let depth = Path::new(String::from_utf8_lossy(&prefix).trim_end()).components().count();
+    let root = dir.ancestors().nth(depth)?.to_path_buf();
+    let out = git(dir, &["status", "--porcelain=v1", "-z", "--branch", "--ignored", "--untracked-files=normal", "--", "."])?;
+    let mut status = parse_porcelain(&out);
+    status.root = root;
+    if status.branch.as_deref() == Some("HEAD (no branch)") {
+        status.branch = git(dir, &["rev-parse", "--short", "HEAD"]).map(|id| String::from_utf8_lossy(&id).trim_end().to_string());
+    }
+    Some(status)
+}
+
+/// Run `git ARGS` in `dir`, returning stdout when it succeeds.
+fn git(dir: &Path, args: &[&str]) -> Option<Vec<u8>> {
+    let out = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .env("GIT_OPTIONAL_LOCKS", "0")
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+    out.status.success().then_some(out.stdout)
+}
+
+/// Parse `git status --porcelain=v1 -z --branch` output. `root` is left
+/// empty for the caller to fill in.
+pub fn parse_porcelain(raw: &[u8]) -> GitStatus {
+    let mut status = GitStatus::default();
+    let mut records = raw.split(|b| *b == 0).map(|r| String::from_utf8_lossy(r).into_owned());
+    while let Some(record) = records.next() {
+        if let Some(branch) = record.strip_prefix("## ") {
+            let name = branch.split("...").next().unwrap_or(branch);
+            let name = name.strip_prefix("No commits yet on ").unwrap_or(name);
+            status.branch = Some(name.to_string());
+            continue;
+        }
+        if record.len() < 4 {
+            continue;
+        }
+        let (xy, path) = record.split_at(3);
+        let (x, y) = (xy.as_bytes()[0], xy.as_bytes()[1]);
+        // Renames and copies are followed by their source path.
+        if matches!(x, b'R' | b'C') {
+            records.next();
+        }
+        let file_status = match (x, y) {
+            (b'!', b'!') => GitFileStatus::Ignored,
+            (b'?', b'?') => GitFileStatus::Untracked,
+            (b'U', _) | (_, b'U') | (b'A', b'A') | (b'D', b'D') => GitFileStatus::Conflicted,
+            (_, b' ') => GitFileStatus::Staged,
+            _ => GitFileStatus::Modified,
+        };
+        status.files.insert(PathBuf::from(path.trim_end_matches('/')), file_status);
+    }
+    status
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_porcelain_status() {
+        let raw = b"## main...origin/main [ahead 1]\0 M src/lib.rs\0M  README.md\0R  new.rs\0old.rs\0?? notes/\0!! target/\0UU merge.rs\0";
+        let mut status = parse_porcelain(raw);
+        assert_eq!(status.branch.as_deref(), Some("main"));
+        let files: Vec<(&str, GitFileStatus)> = {
+            let mut f: Vec<_> = status.files.iter().map(|(p, s)| (p.to_str().unwrap(), *s)).collect();
+            f.sort();
+            f
+        };
+        assert_eq!(
+            files,
+            [
+                ("README.md", GitFileStatus::Staged),
+                ("merge.rs", GitFileStatus::Conflicted),
+                ("new.rs", GitFileStatus::Staged),
+                ("notes", GitFileStatus::Untracked),
+                ("src/lib.rs", GitFileStatus::Modified),
+                ("target", GitFileStatus::Ignored),
+            ]
+        );
+
+        status.root = PathBuf::from("/repo");
+        assert_eq!(status.status_of(Path::new("/repo/src")), Some(GitFileStatus::Modified));
+        assert_eq!(status.status_of(Path::new("/repo/target/debug")), Some(GitFileStatus::Ignored));
+        assert_eq!(status.status_of(Path::new("/repo/Cargo.toml")), None);
+        assert_eq!(status.status_of(Path::new("/elsewhere")), None);
+    }
+
+    #[test]
+    fn new_repository_branch_has_no_commits() {
+        assert_eq!(parse_porcelain(b"## No commits yet on main\0").branch.as_deref(), Some("main"));
+    }
+}
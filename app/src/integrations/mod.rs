@@ -0,0 +1,6 @@
+//! Integrations with external tools. Those that need extra dependencies
+//! sit behind their own cargo feature.
+
+pub mod clipboard;
+#[cfg(feature = "git")]
+pub mod git;
@@ -1,9 +1,15 @@
 #![allow(non_snake_case)]
 pub mod app;
+pub mod embed;
 pub mod errors;
 pub mod fs_op;
 pub mod input;
+pub mod integrations;
+pub mod logging;
 pub mod parallel;
+pub mod plugins;
+#[cfg(feature = "scripting")]
+pub mod scripting;
 #[path = "runner/mod.rs"]
 pub mod runner;
 // ui module moved to a new `UI` folder (uppercase). Keep module name `ui` here
@@ -0,0 +1,108 @@
+//! Logging to a rolling file in the cache directory.
+//!
+//! Every run appends to `filezoom.YYYY-MM-DD.log` in `log_dir()`, a new file
+//! each day, keeping the last `KEEP_LOG_FILES`. `log` records from
+//! dependencies end up there too. File operations log what they did and
+//! why they failed, so the log viewer (`:log`) can show what happened to a
+//! batch that stopped halfway.
+
+use std::io;
+use std::path::{Path, PathBuf};
+
+use tracing_appender::non_blocking::WorkerGuard;
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
+use tracing_subscriber::{fmt, prelude::*, EnvFilter};
+
+const LOG_FILE_PREFIX: &str = "filezoom";
+const LOG_FILE_SUFFIX: &str = "log";
+/// How many daily log files are kept.
+const KEEP_LOG_FILES: usize = 7;
+
+/// Directory holding the log files.
+pub fn log_dir() -> PathBuf {
+    crate::app::settings::config_dirs::user_cache_dir().join("log")
+}
+
+/// Install the global subscriber: the rolling log file, plus stdout when
+/// `console` is set. `RUST_LOG` picks the level (`info` by default).
+///
+/// Keep the returned guard alive until exit; dropping it flushes the file.
+/// Without a usable log directory only the console is logged to.
+pub fn init(console: bool) -> Option<WorkerGuard> {
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
+
+    let appender = RollingFileAppender::builder()
+        .rotation(Rotation::DAILY)
+        .filename_prefix(LOG_FILE_PREFIX)
+        .filename_suffix(LOG_FILE_SUFFIX)
+        .max_log_files(KEEP_LOG_FILES)
+        .build(log_dir())
+        .ok();
+    let (file_layer, guard) = match appender {
+        Some(appender) => {
+            let (writer, guard) = tracing_appender::non_blocking(appender);
+            (Some(fmt::layer().with_ansi(false).with_writer(writer)), Some(guard))
+        }
+        None => (None, None),
+    };
+    let console_layer = console.then(|| fmt::layer().with_ansi(atty::is(atty::Stream::Stdout)).with_writer(io::stdout));
+
+    // Also routes `log` records into `tracing`.
+    let _ = tracing_subscriber::registry().with(filter).with(console_layer).with(file_layer).try_init();
+    guard
+}
+
+/// Log the outcome of file operation `op` on `path` (into `dest` for
+/// copies and moves) and hand `res` back: what was done, or why it failed.
+pub fn log_fs_op<T, E: std::fmt::Display>(op: &str, path: &Path, dest: Option<&Path>, res: Result<T, E>) -> Result<T, E> {
+    let path = path.display();
+    match (&res, dest.map(Path::display)) {
+        (Ok(_), None) => tracing::info!(op, %path, "done"),
+        (Ok(_), Some(dest)) => tracing::info!(op, %path, %dest, "done"),
+        (Err(error), None) => tracing::warn!(op, %path, %error, "failed"),
+        (Err(error), Some(dest)) => tracing::warn!(op, %path, %dest, %error, "failed"),
+    }
+    res
+}
+
+/// The newest log file in `dir`, if there is one.
+pub fn latest_log_file(dir: &Path) -> Option<PathBuf> {
+    std::fs::read_dir(dir)
+        .ok()?
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| {
+            p.file_name().and_then(|n| n.to_str()).is_some_and(|n| {
+                n.starts_with(&format!("{}.", LOG_FILE_PREFIX)) && n.ends_with(&format!(".{}", LOG_FILE_SUFFIX))
+            })
+        })
+        // The date in the name sorts like the files' age.
+        .max()
+}
+
+/// The last `max` lines of the log file at `path`.
+pub fn read_tail(path: &Path, max: usize) -> io::Result<Vec<String>> {
+    let raw = std::fs::read(path)?;
+    let text = String::from_utf8_lossy(&raw);
+    let lines: Vec<&str> = text.lines().collect();
+    Ok(lines[lines.len().saturating_sub(max)..].iter().map(|l| l.to_string()).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_the_newest_log_and_reads_its_tail() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(latest_log_file(dir.path()), None);
+        std::fs::write(dir.path().join("filezoom.2026-01-02.log"), "a\nb\nc\n").unwrap();
+        std::fs::write(dir.path().join("filezoom.2026-01-01.log"), "old\n").unwrap();
+        std::fs::write(dir.path().join("notes.txt"), "x").unwrap();
+
+        let latest = latest_log_file(dir.path()).unwrap();
+        assert!(latest.ends_with("filezoom.2026-01-02.log"));
+        assert_eq!(read_tail(&latest, 2).unwrap(), ["b", "c"]);
+        assert_eq!(read_tail(&latest, 10).unwrap(), ["a", "b", "c"]);
+    }
+}
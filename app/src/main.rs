@@ -1,21 +1,54 @@
 use clap::Parser;
+use std::io::Write;
 
-/// Small CLI wrapper for fileZoom. Keep it minimal: allow starting
-/// directory override and an option to disable mouse capture at startup.
+/// Small CLI wrapper for fileZoom: `fileZoom [LEFT_DIR] [RIGHT_DIR]` plus
+/// startup overrides for settings, all mapped onto `StartOptions`.
 #[derive(clap::Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 struct Cli {
+    /// Directory for the left panel (defaults to the current directory)
+    #[arg(value_name = "LEFT_DIR", conflicts_with = "dir")]
+    left_dir: Option<std::path::PathBuf>,
+
+    /// Directory for the right panel (defaults to LEFT_DIR)
+    #[arg(value_name = "RIGHT_DIR")]
+    right_dir: Option<std::path::PathBuf>,
+
     /// Start the program in this directory instead of the current directory
+    /// (same as LEFT_DIR)
     #[arg(short, long, value_name = "DIR")]
     dir: Option<std::path::PathBuf>,
 
+    /// Show only the active panel, at full width
+    #[arg(long = "single-pane")]
+    single_pane: bool,
+
+    /// Read and write settings from this file instead of the default
+    /// location
+    #[arg(long = "config", value_name = "PATH")]
+    config: Option<std::path::PathBuf>,
+
+    /// Run the copy/move/delete/mkdir commands in FILE without the TUI
+    /// and exit (non-zero if any failed)
+    #[arg(long, value_name = "FILE")]
+    batch: Option<std::path::PathBuf>,
+
+    /// Batch mode: what to do when a target exists (fail, skip, overwrite)
+    #[arg(long = "on-conflict", value_name = "POLICY", default_value = "fail", requires = "batch")]
+    on_conflict: fileZoom::runner::batch::ConflictPolicy,
+
+    /// Batch mode: continue after a failed command instead of stopping
+    #[arg(long = "keep-going", requires = "batch")]
+    keep_going: bool,
+
     /// Disable mouse capture on startup (can be toggled in settings later)
     #[arg(long)]
     no_mouse: bool,
 
-    /// Start with this theme (e.g. `default` or `dark`). When omitted the
-    /// persisted setting (or default) is used. Allowed values: `default`, `dark`.
-    #[arg(long, value_name = "NAME", value_parser = ["default", "dark"])]
+    /// Start with this theme: `default`, `dark`, `light` or a user theme
+    /// from the `themes` config directory. When omitted the persisted
+    /// setting (or default) is used.
+    #[arg(long, value_name = "NAME")]
     theme: Option<String>,
 
     /// Show hidden files at startup (overrides persisted setting).
@@ -26,16 +59,44 @@ struct Cli {
     /// more `v` increases verbosity (0 = default, 1 = info, 2 = debug, 3+ = trace).
     #[arg(short, long = "verbose", action = clap::ArgAction::Count)]
     verbosity: u8,
-    /// Enable structured logging (console + rolling file). When omitted the
-    /// program uses the legacy `env_logger` behaviour.
+    /// Also print the log to the console. The log is always written to a
+    /// rolling file in the cache directory.
     #[arg(long = "enable-logging")]
     enable_logging: bool,
+
+    /// Internal: run one file operation as the privileged helper (see
+    /// `fs_op::elevated`) and exit.
+    #[arg(long = "elevated-op", num_args = 1.., allow_hyphen_values = true, hide = true, value_parser = clap::value_parser!(std::ffi::OsString))]
+    elevated_op: Option<Vec<std::ffi::OsString>>,
+
+    /// Send a command to the running fileZoom and exit: `reveal PATH`,
+    /// `open PATH`, `open-left PATH`, `open-right PATH` or `focus`. With
+    /// none running, start one showing PATH.
+    #[arg(long, value_names = ["COMMAND", "PATH"], num_args = 1..=2, value_parser = clap::value_parser!(std::ffi::OsString))]
+    remote: Option<Vec<std::ffi::OsString>>,
+
+    /// Run as a file picker: Enter on a file, or with files marked, prints
+    /// their paths to stdout, one per line, and exits (non-zero when
+    /// quitting without a choice)
+    #[arg(long = "choose-files")]
+    choose_files: bool,
+
+    /// Log how long each startup phase took and append the timings to
+    /// `startup_profile.log` in the cache directory.
+    #[arg(long = "profile-startup")]
+    profile_startup: bool,
 }
 
 fn main() -> anyhow::Result<()> {
     // Parse CLI args early so we can affect process state (cwd, etc.).
     let cli = Cli::parse();
 
+    // The privileged helper runs as root: it does its one operation and
+    // nothing else, not even logging to the user's cache directory.
+    if let Some(args) = &cli.elevated_op {
+        std::process::exit(fileZoom::fs_op::elevated::run_helper(args));
+    }
+
     // Install a panic hook that will attempt to restore the terminal state
     // (leave alternate screen, disable raw mode) before printing panic
     // information. This prevents the terminal from being left in an unusable
@@ -55,49 +116,32 @@ fn main() -> anyhow::Result<()> {
         std::env::set_var("RUST_LOG", lvl);
     }
 
-    if cli.enable_logging {
-        // Initialize tracing subscriber with console + rolling file appender.
-        // Also bridge `log` records into `tracing` so legacy `log::` calls are captured.
-        use std::io;
-        use tracing_subscriber::{fmt, EnvFilter, prelude::*};
-        use tracing_appender::{non_blocking, rolling};
-        use directories_next::ProjectDirs;
-
-        // Convert `log` records to `tracing` events so existing `log::` calls are not lost.
-        let _ = tracing_log::LogTracer::init();
-
-        // EnvFilter: reads from `RUST_LOG` or similar environment variables.
-        let env_filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
-
-        // Console layer: ANSI enabled when stdout is a TTY.
-        let console_layer = fmt::layer()
-            .with_ansi(atty::is(atty::Stream::Stdout))
-            .with_writer(io::stdout);
-
-        // Determine a directory to place logs in. Prefer the platform-specific
-        // project data dir, but fall back to the current working directory.
-        let base_dir = ProjectDirs::from("net", "macg4dave", "fileZoom")
-            .map(|p| p.data_local_dir().to_path_buf())
-            .unwrap_or_else(|| std::env::current_dir().unwrap_or_else(|_| std::path::PathBuf::from(".")));
-        let log_dir = base_dir.join("log");
-        let _ = std::fs::create_dir_all(&log_dir);
-
-        // File appender (rolling daily) and its guard --- keep the guard alive
-        // for the lifetime of the program by leaking it onto the heap.
-        let file_appender = rolling::daily(log_dir, "filezoom.log");
-        let (non_blocking, guard) = non_blocking(file_appender);
-        let _guard = Box::leak(Box::new(guard));
-
-        let file_layer = fmt::layer().with_ansi(false).with_writer(non_blocking);
-
-        tracing_subscriber::registry()
-            .with(env_filter)
-            .with(console_layer)
-            .with(file_layer)
-            .init();
-    } else {
-        // Legacy behaviour: use env_logger so `RUST_LOG` and `-v` still work.
-        env_logger::init();
+    // Everything is logged to a rolling file in the cache directory (see
+    // `fileZoom::logging`); `--enable-logging` echoes it to the console.
+    let log_guard = fileZoom::logging::init(cli.enable_logging);
+
+    // Batch mode runs the commands headless and never touches the terminal.
+    if let Some(file) = &cli.batch {
+        let opts = fileZoom::runner::batch::BatchOptions { on_conflict: cli.on_conflict, keep_going: cli.keep_going };
+        let report = fileZoom::runner::batch::run_batch_file(file, opts, &mut std::io::stdout().lock())?;
+        // `exit` skips destructors; flush the log first.
+        drop(log_guard);
+        std::process::exit(if report.success() { 0 } else { 1 });
+    }
+
+    // Hand a remote command to the running instance. Without one, this
+    // process becomes it, starting where the command points.
+    let mut remote = None;
+    if let Some(args) = &cli.remote {
+        use fileZoom::runner::ipc::{self, IpcCommand};
+        let verb = args[0].to_string_lossy();
+        let command = IpcCommand::from_args(&verb, args.get(1).map(std::path::Path::new)).map_err(anyhow::Error::msg)?;
+        match ipc::send(&ipc::endpoint(), &command) {
+            Ok(Ok(())) => return Ok(()),
+            Ok(Err(e)) => anyhow::bail!("{}", e),
+            Err(e) => tracing::info!("no running instance ({}), starting one", e),
+        }
+        remote = Some(command);
     }
 
     // Create a shutdown channel and register a Ctrl-C handler that sends
@@ -109,37 +153,15 @@ fn main() -> anyhow::Result<()> {
         let _ = tx_clone.send(());
     })?;
 
-    // If async input support is enabled, spawn a small thread that runs
-    // an EventStream and forwards events into a channel. Install the
-    // receiver so `input::read_event()` will check it before falling back
-    // to the synchronous `crossterm::event::read()` path.
-    #[cfg(feature = "async-input")]
-    {
-        use std::sync::mpsc::channel as mpsc_channel;
-        use std::thread;
-
-        let (async_tx, async_rx) = mpsc_channel::<crossterm::event::Event>();
-        // install the receiver so `read_event()` can poll it
-        fileZoom::input::install_async_event_receiver(async_rx);
-
-        // Spawn a thread to run the async EventStream producer. We use a
-        // simple executor via `futures::executor::block_on` here so we do
-        // not add a full async runtime dependency; this thread will live
-        // for the lifetime of the process when the feature is enabled.
-        thread::spawn(move || {
-            let fut = async move {
-                if let Err(e) = fileZoom::input::async_input::event_listener(move |ev| {
-                    let _ = async_tx.send(ev);
-                })
-                .await
-                {
-                    tracing::error!("async event listener failed: {:#}", e);
-                }
-            };
-            // Block on the future for this thread.
-            futures::executor::block_on(fut);
-        });
-    }
+    // The picker prints to the stdout it was started with; the screen goes
+    // to the terminal.
+    #[cfg(unix)]
+    let mut chosen_out: Box<dyn std::io::Write> = match cli.choose_files {
+        true => Box::new(fileZoom::runner::terminal::detach_stdout()?),
+        false => Box::new(std::io::sink()),
+    };
+    #[cfg(not(unix))]
+    let mut chosen_out: Box<dyn std::io::Write> = Box::new(std::io::stdout());
 
     // Initialize the terminal and hand ownership to the runner so the
     // runner (in main thread) can restore the terminal cleanly on shutdown.
@@ -148,13 +170,40 @@ fn main() -> anyhow::Result<()> {
     // Construct start options from CLI and hand them to the runner. The
     // runner will apply CLI-provided overrides after loading persisted
     // settings so CLI values take precedence.
-    let start_opts = fileZoom::app::StartOptions {
-        start_dir: cli.dir,
+    let mut start_opts = fileZoom::app::StartOptions {
+        start_dir: cli.left_dir.or(cli.dir),
+        right_dir: cli.right_dir,
         mouse_enabled: if cli.no_mouse { Some(false) } else { None },
         theme: cli.theme,
         show_hidden: if cli.show_hidden { Some(true) } else { None },
         verbosity: if cli.verbosity > 0 { Some(cli.verbosity) } else { None },
+        single_pane: if cli.single_pane { Some(true) } else { None },
+        config_path: cli.config,
+        profile_startup: cli.profile_startup,
+        reveal: None,
+        choose_files: cli.choose_files,
     };
+    use fileZoom::runner::ipc::IpcCommand;
+    match remote {
+        Some(IpcCommand::Reveal(path)) => {
+            start_opts.start_dir = path.parent().map(|p| p.to_path_buf());
+            start_opts.reveal = Some(path);
+        }
+        Some(IpcCommand::Open(Some(fileZoom::app::Side::Right), dir)) => start_opts.right_dir = Some(dir),
+        Some(IpcCommand::Open(_, dir)) => start_opts.start_dir = Some(dir),
+        Some(IpcCommand::Focus) | None => {}
+    }
 
-    fileZoom::runner::run_app(terminal, shutdown_rx, start_opts)
+    let chosen = fileZoom::runner::run_app(terminal, shutdown_rx, start_opts)?;
+    if let Some(paths) = chosen {
+        for path in &paths {
+            writeln!(chosen_out, "{}", path.display())?;
+        }
+        chosen_out.flush()?;
+        if paths.is_empty() {
+            drop(log_guard);
+            std::process::exit(1);
+        }
+    }
+    Ok(())
 }
@@ -0,0 +1,377 @@
+//! External plugins.
+//!
+//! Plugins are executables placed in the `plugins` directory under
+//! `project_config_dir()`. They extend fileZoom without recompiling by
+//! registering context-menu (F3) actions and extra panel columns.
+//!
+//! Protocol: for every request fileZoom starts the plugin, writes one JSON
+//! object followed by a newline to its stdin, closes stdin and reads one
+//! JSON object from stdout. A non-zero exit status is an error and stderr
+//! is shown to the user. Requests are tagged by `type`:
+//!
+//! - `{"type":"describe"}` — reply with the manifest:
+//!   `{"name":"git","actions":[{"id":"blame","label":"Git blame"}],"columns":[{"id":"st","title":"Git"}]}`
+//! - `{"type":"action","action":"blame","paths":[...],"cwd":"..."}` — run an
+//!   action; reply `{"message":"optional text","refresh":true}`.
+//! - `{"type":"column","column":"st","paths":[...]}` — reply
+//!   `{"values":[...]}` with one value per path.
+//!
+//! WASM modules (`*.wasm`) are recognised but need a runtime that is not
+//! built into this binary, so they are reported in
+//! `PluginRegistry::errors` instead of being loaded.
+
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::app::settings::config_dirs::project_config_dir;
+
+/// Name of the plugins directory inside the config directory.
+pub const PLUGINS_DIR: &str = "plugins";
+
+/// How long a plugin may take to answer a single request.
+pub const PLUGIN_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Default plugins directory.
+pub fn plugins_dir() -> PathBuf {
+    project_config_dir().join(PLUGINS_DIR)
+}
+
+/// A context-menu action registered by a plugin.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct PluginAction {
+    /// Identifier sent back in `action` requests.
+    pub id: String,
+    /// Label shown in the context menu.
+    pub label: String,
+}
+
+/// A panel column registered by a plugin.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct PluginColumn {
+    /// Identifier sent back in `column` requests.
+    pub id: String,
+    /// Column heading.
+    pub title: String,
+}
+
+/// Reply to a `describe` request.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct PluginManifest {
+    pub name: String,
+    #[serde(default)]
+    pub actions: Vec<PluginAction>,
+    #[serde(default)]
+    pub columns: Vec<PluginColumn>,
+}
+
+/// Reply to an `action` request.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct ActionResponse {
+    /// Text to show the user, if any.
+    #[serde(default)]
+    pub message: Option<String>,
+    /// Whether the panels should be re-read afterwards.
+    #[serde(default)]
+    pub refresh: bool,
+}
+
+#[derive(Deserialize)]
+struct ColumnResponse {
+    values: Vec<String>,
+}
+
+/// Requests written to a plugin's stdin.
+#[derive(Serialize, Debug)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum Request<'a> {
+    Describe,
+    Action { action: &'a str, paths: &'a [PathBuf], cwd: &'a Path },
+    Column { column: &'a str, paths: &'a [PathBuf] },
+}
+
+/// Errors raised while talking to a plugin.
+#[derive(Debug, Error)]
+pub enum PluginError {
+    #[error("failed to run plugin {path}: {source}")]
+    Io { path: PathBuf, source: io::Error },
+    #[error("plugin {path} timed out")]
+    Timeout { path: PathBuf },
+    #[error("plugin {path} exited with {status}: {stderr}")]
+    Failed { path: PathBuf, status: String, stderr: String },
+    #[error("plugin {path} sent an invalid reply: {source}")]
+    Protocol { path: PathBuf, source: serde_json::Error },
+}
+
+/// A loaded plugin executable and its manifest.
+#[derive(Clone, Debug)]
+pub struct Plugin {
+    pub path: PathBuf,
+    pub manifest: PluginManifest,
+}
+
+impl Plugin {
+    /// Start the plugin at `path` and ask for its manifest.
+    pub fn describe(path: &Path) -> Result<Plugin, PluginError> {
+        let manifest = call(path, None, &Request::Describe)?;
+        Ok(Plugin { path: path.to_path_buf(), manifest })
+    }
+
+    /// Run the action `id` on `paths` with `cwd` as the working directory.
+    pub fn run_action(&self, id: &str, paths: &[PathBuf], cwd: &Path) -> Result<ActionResponse, PluginError> {
+        call(&self.path, Some(cwd), &Request::Action { action: id, paths, cwd })
+    }
+
+    /// Values of column `id` for `paths`, padded or truncated to one per path.
+    pub fn column_values(&self, id: &str, paths: &[PathBuf]) -> Result<Vec<String>, PluginError> {
+        let resp: ColumnResponse = call(&self.path, None, &Request::Column { column: id, paths })?;
+        let mut values = resp.values;
+        values.resize(paths.len(), String::new());
+        Ok(values)
+    }
+}
+
+/// All plugins found in a plugins directory.
+#[derive(Clone, Debug, Default)]
+pub struct PluginRegistry {
+    pub plugins: Vec<Plugin>,
+    /// Human-readable reasons for plugins that could not be loaded.
+    pub errors: Vec<String>,
+}
+
+impl PluginRegistry {
+    /// Load plugins from `plugins_dir()`.
+    pub fn load() -> Self {
+        Self::load_from(&plugins_dir())
+    }
+
+    /// Load every plugin in `dir` (sorted by file name). A missing
+    /// directory yields an empty registry.
+    pub fn load_from(dir: &Path) -> Self {
+        let mut reg = PluginRegistry::default();
+        let Ok(rd) = std::fs::read_dir(dir) else { return reg };
+        let mut paths: Vec<PathBuf> = rd.filter_map(|e| e.ok().map(|e| e.path())).collect();
+        paths.sort();
+        for path in paths {
+            if path.extension().is_some_and(|e| e.eq_ignore_ascii_case("wasm")) {
+                reg.errors.push(format!("{}: WASM plugins are not supported by this build", path.display()));
+                continue;
+            }
+            if !is_executable(&path) {
+                continue;
+            }
+            match Plugin::describe(&path) {
+                Ok(p) => reg.plugins.push(p),
+                Err(e) => reg.errors.push(e.to_string()),
+            }
+        }
+        reg
+    }
+
+    /// Whether any plugin registered a panel column.
+    pub fn has_columns(&self) -> bool {
+        self.plugins.iter().any(|p| !p.manifest.columns.is_empty())
+    }
+
+    /// Context-menu labels of all plugin actions, in load order.
+    pub fn action_labels(&self) -> Vec<String> {
+        self.plugins.iter().flat_map(|p| p.manifest.actions.iter().map(|a| a.label.clone())).collect()
+    }
+
+    /// Run the plugin action labelled `label`. Returns `None` when no
+    /// plugin registered that label.
+    pub fn run_action(&self, label: &str, paths: &[PathBuf], cwd: &Path) -> Option<Result<ActionResponse, PluginError>> {
+        self.plugins.iter().find_map(|p| {
+            p.manifest.actions.iter().find(|a| a.label == label).map(|a| p.run_action(&a.id, paths, cwd))
+        })
+    }
+
+    /// Plugin column text for each of `paths`: the values of every column,
+    /// separated by two spaces. Columns whose plugin fails are left blank.
+    pub fn column_text(&self, paths: &[PathBuf]) -> Vec<String> {
+        let mut out = vec![String::new(); paths.len()];
+        for p in &self.plugins {
+            for c in &p.manifest.columns {
+                let values = match p.column_values(&c.id, paths) {
+                    Ok(v) => v,
+                    Err(e) => {
+                        tracing::warn!("plugin column {} failed: {}", c.id, e);
+                        vec![String::new(); paths.len()]
+                    }
+                };
+                for (cell, v) in out.iter_mut().zip(values) {
+                    if !cell.is_empty() {
+                        cell.push_str("  ");
+                    }
+                    cell.push_str(&v);
+                }
+            }
+        }
+        out
+    }
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    std::fs::metadata(path).is_ok_and(|m| m.is_file() && m.permissions().mode() & 0o111 != 0)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.is_file()
+        && path.extension().is_some_and(|e| {
+            ["exe", "bat", "cmd"].iter().any(|x| e.eq_ignore_ascii_case(x))
+        })
+}
+
+/// Send one request to the plugin at `path` (started in `cwd` when given)
+/// and decode its reply.
+fn call<T: for<'de> Deserialize<'de>>(path: &Path, cwd: Option<&Path>, req: &Request) -> Result<T, PluginError> {
+    let io_err = |source| PluginError::Io { path: path.to_path_buf(), source };
+    let mut command = Command::new(path);
+    if let Some(cwd) = cwd {
+        command.current_dir(cwd);
+    }
+    let mut child = command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(io_err)?;
+    let deadline = Instant::now() + PLUGIN_TIMEOUT;
+    // Drain the pipes on helper threads so a chatty plugin cannot block on a
+    // full pipe while we wait for it to exit.
+    let drain = |r: Option<Box<dyn Read + Send>>| {
+        thread::spawn(move || {
+            let mut buf = Vec::new();
+            if let Some(mut r) = r {
+                let _ = r.read_to_end(&mut buf);
+            }
+            buf
+        })
+    };
+    let stdout = drain(child.stdout.take().map(|s| Box::new(s) as Box<dyn Read + Send>));
+    let stderr = drain(child.stderr.take().map(|s| Box::new(s) as Box<dyn Read + Send>));
+    // The request is written from its own thread too: a plugin that does
+    // not read a long request would block the write past the timeout.
+    let mut line = serde_json::to_vec(req).expect("requests always serialize");
+    line.push(b'\n');
+    if let Some(mut stdin) = child.stdin.take() {
+        // A plugin that ignores its input may exit before reading it.
+        thread::spawn(move || {
+            let _ = stdin.write_all(&line);
+        });
+    }
+    let status = loop {
+        if let Some(status) = child.try_wait().map_err(io_err)? {
+            break status;
+        }
+        if Instant::now() >= deadline {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(PluginError::Timeout { path: path.to_path_buf() });
+        }
+        thread::sleep(Duration::from_millis(10));
+    };
+    let out = stdout.join().unwrap_or_default();
+    let err = stderr.join().unwrap_or_default();
+    if !status.success() {
+        return Err(PluginError::Failed {
+            path: path.to_path_buf(),
+            status: status.to_string(),
+            stderr: String::from_utf8_lossy(&err).trim().to_string(),
+        });
+    }
+    serde_json::from_slice(&out).map_err(|source| PluginError::Protocol { path: path.to_path_buf(), source })
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::os::unix::fs::PermissionsExt;
+
+    fn write_plugin(dir: &Path, name: &str, script: &str) -> PathBuf {
+        let p = dir.join(name);
+        std::fs::write(&p, format!("#!/bin/sh\n{}", script)).unwrap();
+        std::fs::set_permissions(&p, std::fs::Permissions::from_mode(0o755)).unwrap();
+        p
+    }
+
+    #[test]
+    fn loads_manifests_and_reports_bad_plugins() {
+        let tmp = tempfile::tempdir().unwrap();
+        write_plugin(
+            tmp.path(),
+            "a-good",
+            r#"read req
+echo '{"name":"good","actions":[{"id":"x","label":"Do X"}],"columns":[{"id":"c","title":"C"}]}'
+"#,
+        );
+        write_plugin(tmp.path(), "b-broken", "echo nope; exit 0\n");
+        write_plugin(tmp.path(), "c-failing", "echo boom >&2; exit 2\n");
+        std::fs::write(tmp.path().join("d-module.wasm"), b"\0asm").unwrap();
+        std::fs::write(tmp.path().join("README"), "not executable").unwrap();
+
+        let reg = PluginRegistry::load_from(tmp.path());
+        assert_eq!(reg.plugins.len(), 1);
+        assert_eq!(reg.plugins[0].manifest.name, "good");
+        assert_eq!(reg.action_labels(), vec!["Do X".to_string()]);
+        assert!(reg.has_columns());
+        assert_eq!(reg.errors.len(), 3);
+        assert!(reg.errors.iter().any(|e| e.contains("boom")));
+        assert!(reg.errors.iter().any(|e| e.contains("WASM")));
+    }
+
+    #[test]
+    fn action_and_column_requests_round_trip() {
+        let tmp = tempfile::tempdir().unwrap();
+        // The plugin echoes the request type back so the test can check the
+        // JSON it was sent.
+        write_plugin(
+            tmp.path(),
+            "echo",
+            r#"read req
+case "$req" in
+  *'"type":"describe"'*) echo '{"name":"e","actions":[{"id":"a","label":"Echo"}],"columns":[{"id":"n","title":"N"}]}' ;;
+  *'"type":"action"'*) printf '{"message":%s,"refresh":true}\n' "$(printf '%s' "$req" | sed 's/"/\\"/g; s/^/"/; s/$/"/')" ;;
+  *'"type":"column"'*) echo '{"values":["one"]}' ;;
+esac
+"#,
+        );
+        let reg = PluginRegistry::load_from(tmp.path());
+        let paths = vec![PathBuf::from("/x/a"), PathBuf::from("/x/b")];
+        let resp = reg.run_action("Echo", &paths, tmp.path()).unwrap().unwrap();
+        assert!(resp.refresh);
+        let msg = resp.message.unwrap();
+        assert!(msg.contains(r#""action":"a""#), "{}", msg);
+        assert!(msg.contains(r#""paths":["/x/a","/x/b"]"#), "{}", msg);
+        assert!(reg.run_action("Missing", &paths, tmp.path()).is_none());
+        // Short replies are padded to one value per path.
+        assert_eq!(reg.column_text(&paths), vec!["one".to_string(), String::new()]);
+    }
+
+    #[test]
+    fn plugin_that_never_reads_a_long_request_times_out() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = write_plugin(tmp.path(), "deaf", "sleep 30\n");
+        let plugin = Plugin { path, manifest: PluginManifest::default() };
+        // Far more than a pipe buffer holds.
+        let paths: Vec<PathBuf> = (0..20_000).map(|i| PathBuf::from(format!("/some/long/directory/name/file-{}", i))).collect();
+        let started = Instant::now();
+        let err = plugin.column_values("c", &paths).unwrap_err();
+        assert!(matches!(err, PluginError::Timeout { .. }), "{}", err);
+        assert!(started.elapsed() < PLUGIN_TIMEOUT + Duration::from_secs(3));
+    }
+
+    #[test]
+    fn missing_directory_is_empty() {
+        let reg = PluginRegistry::load_from(Path::new("/definitely/not/here"));
+        assert!(reg.plugins.is_empty() && reg.errors.is_empty());
+    }
+}
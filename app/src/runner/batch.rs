@@ -0,0 +1,326 @@
+//! Headless batch mode (`fileZoom --batch FILE`).
+//!
+//! Runs a list of copy/move/delete/mkdir operations through the `fs_op`
+//! layer without starting the TUI, for CI jobs and scripts. The batch file
+//! is either one command per line:
+//!
+//! ```text
+//! # comments and blank lines are ignored
+//! mkdir out/logs
+//! copy "my file.txt" out/
+//! move build/app.tar out/app.tar
+//! delete build
+//! ```
+//!
+//! or a JSON array such as `[{"op": "copy", "src": "a", "dst": "b"}]`.
+//! `copy`/`move` into an existing directory place the source inside it;
+//! an existing target is handled by the `ConflictPolicy`.
+
+use std::fmt;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use anyhow::{anyhow, bail, Context, Result};
+use serde::Deserialize;
+
+use crate::fs_op;
+
+/// One batch operation.
+#[derive(Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(tag = "op", rename_all = "lowercase")]
+pub enum BatchOp {
+    Copy { src: PathBuf, dst: PathBuf },
+    Move { src: PathBuf, dst: PathBuf },
+    Delete { path: PathBuf },
+    Mkdir { path: PathBuf },
+}
+
+impl fmt::Display for BatchOp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BatchOp::Copy { src, dst } => write!(f, "copy {} -> {}", src.display(), dst.display()),
+            BatchOp::Move { src, dst } => write!(f, "move {} -> {}", src.display(), dst.display()),
+            BatchOp::Delete { path } => write!(f, "delete {}", path.display()),
+            BatchOp::Mkdir { path } => write!(f, "mkdir {}", path.display()),
+        }
+    }
+}
+
+/// What to do when a copy/move target already exists.
+#[derive(Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ConflictPolicy {
+    /// Treat the operation as failed.
+    #[default]
+    Fail,
+    /// Leave the target alone and continue.
+    Skip,
+    /// Replace the target.
+    Overwrite,
+}
+
+impl FromStr for ConflictPolicy {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "fail" => Ok(ConflictPolicy::Fail),
+            "skip" => Ok(ConflictPolicy::Skip),
+            "overwrite" => Ok(ConflictPolicy::Overwrite),
+            other => bail!("unknown conflict policy `{}` (expected fail, skip or overwrite)", other),
+        }
+    }
+}
+
+/// Options for `run_batch`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct BatchOptions {
+    pub on_conflict: ConflictPolicy,
+    /// Continue with the next operation after a failure instead of stopping.
+    pub keep_going: bool,
+}
+
+/// Counts of how the operations of a batch ended.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BatchReport {
+    pub done: usize,
+    pub skipped: usize,
+    pub failed: usize,
+}
+
+impl BatchReport {
+    pub fn success(&self) -> bool {
+        self.failed == 0
+    }
+}
+
+/// How a single operation ended.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Done,
+    Skipped,
+}
+
+/// Split a command line into words, honouring single and double quotes
+/// and backslash escapes.
+fn split_words(line: &str) -> Result<Vec<String>> {
+    let mut words = Vec::new();
+    let mut cur = String::new();
+    let mut in_word = false;
+    let mut quote: Option<char> = None;
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some('"') | None, '\\') => {
+                cur.push(chars.next().ok_or_else(|| anyhow!("trailing backslash"))?);
+                in_word = true;
+            }
+            (Some(_), c) => cur.push(c),
+            (None, '"' | '\'') => {
+                quote = Some(c);
+                in_word = true;
+            }
+            (None, c) if c.is_whitespace() => {
+                if in_word {
+                    words.push(std::mem::take(&mut cur));
+                    in_word = false;
+                }
+            }
+            (None, c) => {
+                cur.push(c);
+                in_word = true;
+            }
+        }
+    }
+    if quote.is_some() {
+        bail!("unterminated quote");
+    }
+    if in_word {
+        words.push(cur);
+    }
+    Ok(words)
+}
+
+/// Parse a batch file (line commands or a JSON array).
+pub fn parse_batch(text: &str) -> Result<Vec<BatchOp>> {
+    if text.trim_start().starts_with('[') {
+        return serde_json::from_str(text).context("invalid JSON batch");
+    }
+    let mut ops = Vec::new();
+    for (n, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let words = split_words(line).with_context(|| format!("line {}", n + 1))?;
+        let op = match words.iter().map(String::as_str).collect::<Vec<_>>().as_slice() {
+            ["copy" | "cp", src, dst] => BatchOp::Copy { src: src.into(), dst: dst.into() },
+            ["move" | "mv", src, dst] => BatchOp::Move { src: src.into(), dst: dst.into() },
+            ["delete" | "rm", path] => BatchOp::Delete { path: path.into() },
+            ["mkdir", path] => BatchOp::Mkdir { path: path.into() },
+            _ => bail!("line {}: expected `copy SRC DST`, `move SRC DST`, `delete PATH` or `mkdir PATH`", n + 1),
+        };
+        ops.push(op);
+    }
+    Ok(ops)
+}
+
+/// Where `src` ends up when copied or moved to `dst`.
+fn target_path(src: &Path, dst: &Path) -> Result<PathBuf> {
+    if dst.is_dir() {
+        let name = src.file_name().ok_or_else(|| anyhow!("{} has no file name", src.display()))?;
+        Ok(dst.join(name))
+    } else {
+        Ok(dst.to_path_buf())
+    }
+}
+
+/// `p` with its parent directory canonicalised; the last component is kept
+/// as is, so a symlink stands for itself rather than for what it points to.
+fn resolved(p: &Path) -> Result<PathBuf> {
+    let parent = match p.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    let parent = std::fs::canonicalize(parent).with_context(|| format!("cannot resolve {}", parent.display()))?;
+    Ok(match p.file_name() {
+        Some(name) => parent.join(name),
+        None => parent,
+    })
+}
+
+/// Refuse to copy or move `src` onto itself or into itself: clearing the
+/// target first would delete the source.
+fn check_distinct(src: &Path, target: &Path) -> Result<()> {
+    let (src_r, target_r) = (resolved(src)?, resolved(target)?);
+    if target_r == src_r {
+        bail!("{} is the source itself", target.display());
+    }
+    if target_r.starts_with(&src_r) {
+        bail!("{} is inside the source {}", target.display(), src.display());
+    }
+    Ok(())
+}
+
+/// Resolve a conflict on `target`; `Ok(false)` means skip the operation.
+/// With `Overwrite` the target is removed first when `remove` is set;
+/// copies leave it in place because `copy_path` replaces files atomically
+/// and merges into directories.
+fn clear_target(target: &Path, policy: ConflictPolicy, remove: bool) -> Result<bool> {
+    if std::fs::symlink_metadata(target).is_err() {
+        return Ok(true);
+    }
+    match policy {
+        ConflictPolicy::Fail => bail!("{} already exists", target.display()),
+        ConflictPolicy::Skip => Ok(false),
+        ConflictPolicy::Overwrite => {
+            if remove {
+                fs_op::remove::remove_path(target)?;
+            }
+            Ok(true)
+        }
+    }
+}
+
+/// Run one operation.
+pub fn run_op(op: &BatchOp, policy: ConflictPolicy) -> Result<Outcome> {
+    match op {
+        BatchOp::Copy { src, dst } => {
+            let target = target_path(src, dst)?;
+            check_distinct(src, &target)?;
+            if !clear_target(&target, policy, false)? {
+                return Ok(Outcome::Skipped);
+            }
+            fs_op::mv::copy_path(src, &target)?;
+        }
+        BatchOp::Move { src, dst } => {
+            let target = target_path(src, dst)?;
+            check_distinct(src, &target)?;
+            if !clear_target(&target, policy, true)? {
+                return Ok(Outcome::Skipped);
+            }
+            fs_op::mv::move_path(src, &target)?;
+        }
+        BatchOp::Delete { path } => fs_op::remove::remove_path(path)?,
+        BatchOp::Mkdir { path } => fs_op::create::create_dir_all(path)?,
+    }
+    Ok(Outcome::Done)
+}
+
+/// Run `ops` in order, writing one status line per operation to `out`.
+pub fn run_batch(ops: &[BatchOp], opts: BatchOptions, out: &mut impl Write) -> Result<BatchReport> {
+    let mut report = BatchReport::default();
+    for op in ops {
+        match run_op(op, opts.on_conflict) {
+            Ok(Outcome::Done) => {
+                report.done += 1;
+                writeln!(out, "ok      {}", op)?;
+            }
+            Ok(Outcome::Skipped) => {
+                report.skipped += 1;
+                writeln!(out, "skipped {}", op)?;
+            }
+            Err(e) => {
+                report.failed += 1;
+                writeln!(out, "FAILED  {}: {:#}", op, e)?;
+                if !opts.keep_going {
+                    break;
+                }
+            }
+        }
+    }
+    writeln!(out, "{} done, {} skipped, {} failed", report.done, report.skipped, report.failed)?;
+    Ok(report)
+}
+
+/// Read and run the batch file at `path`.
+pub fn run_batch_file(path: &Path, opts: BatchOptions, out: &mut impl Write) -> Result<BatchReport> {
+    let text = std::fs::read_to_string(path).with_context(|| format!("failed to read batch file {}", path.display()))?;
+    let ops = parse_batch(&text).with_context(|| format!("failed to parse batch file {}", path.display()))?;
+    run_batch(&ops, opts, out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_line_and_json_batches() {
+        let ops = parse_batch("# setup\nmkdir out\n\ncp 'a b.txt' out/\nrm \"old dir\"\nmv x\\ y z\n").unwrap();
+        assert_eq!(
+            ops,
+            vec![
+                BatchOp::Mkdir { path: "out".into() },
+                BatchOp::Copy { src: "a b.txt".into(), dst: "out/".into() },
+                BatchOp::Delete { path: "old dir".into() },
+                BatchOp::Move { src: "x y".into(), dst: "z".into() },
+            ]
+        );
+        let json = parse_batch(r#"[{"op": "copy", "src": "a", "dst": "b"}, {"op": "delete", "path": "c"}]"#).unwrap();
+        assert_eq!(json, vec![BatchOp::Copy { src: "a".into(), dst: "b".into() }, BatchOp::Delete { path: "c".into() }]);
+
+        assert!(parse_batch("copy onlyone").unwrap_err().to_string().contains("line 1"));
+        assert!(parse_batch("mkdir 'open").is_err());
+    }
+
+    #[test]
+    fn overwrite_never_clears_the_source_itself() {
+        let tmp = tempfile::tempdir().unwrap();
+        let file = tmp.path().join("a.txt");
+        let dir = tmp.path().join("dir");
+        std::fs::write(&file, "keep").unwrap();
+        std::fs::create_dir(&dir).unwrap();
+
+        for op in [
+            BatchOp::Move { src: file.clone(), dst: file.clone() },
+            BatchOp::Copy { src: file.clone(), dst: tmp.path().join(".").join("a.txt") },
+            BatchOp::Move { src: dir.clone(), dst: dir.join("sub") },
+        ] {
+            assert!(run_op(&op, ConflictPolicy::Overwrite).is_err(), "{op}");
+        }
+        assert_eq!(std::fs::read_to_string(&file).unwrap(), "keep");
+        assert!(dir.is_dir());
+    }
+}
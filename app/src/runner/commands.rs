@@ -9,12 +9,74 @@ use crate::app::{Action, App};
 use crate::fs_op::error::FsOpError;
 
 /// Parseable, textual commands accepted by the command-line input.
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub(crate) enum ParsedCommand {
     TogglePreview,
     MenuNext,
     MenuPrev,
     MenuActivate,
+    ReloadKeys,
+    Theme,
+    /// `mounts`: open the mount picker.
+    Mounts,
+    /// `toggle-gitignored`: show or hide entries ignored by `.gitignore`.
+    ToggleGitignored,
+    /// `profile NAME`: run the named operation profile on the selection.
+    Profile(String),
+    /// `profiles`: list the operation profiles.
+    Profiles,
+    /// `jump WORDS`: go to the best-ranked visited directory matching WORDS.
+    Jump(String),
+    /// `jump-import [PATH]`: merge a zoxide database into the visit ranks.
+    JumpImport(Option<std::path::PathBuf>),
+    /// `tag NAME [COLOUR]`: toggle tag NAME on the selection.
+    Tag(String, Option<String>),
+    /// `untag [NAME]`: remove tag NAME, or every tag, from the selection.
+    Untag(Option<String>),
+    /// `select-tag NAME`: select the entries tagged NAME.
+    SelectTag(String),
+    /// `tags`: list the tags.
+    Tags,
+    /// `log`: show the end of the log file.
+    Log,
+    /// `jobs`: list the background and queued copies and moves.
+    Jobs,
+    /// `tree`: show or hide the directory tree.
+    Tree,
+    /// `flat`: list every file below the active panel's directory.
+    Flat,
+    /// `find [PATTERN]`: list what matches PATTERN below the active panel's
+    /// directory, or ask for the pattern.
+    Find(Option<String>),
+    /// `select [CRITERIA]`: mark the files meeting CRITERIA (see
+    /// `fs_op::criteria`), or ask for them.
+    Select(Option<String>),
+    /// `searches`: list the saved and recent searches to run again.
+    Searches,
+    /// `save-search NAME`: keep the search of the active results panel as
+    /// NAME.
+    SaveSearch(String),
+    /// `du`: open the disk usage analyzer.
+    DiskUsage,
+    /// `link`: link or unlink the panels.
+    Link,
+    /// `touch [TIME]`: set the selection's times to TIME (`now` or a local
+    /// date and time), or ask for it.
+    Touch(Option<String>),
+    /// `chown [SPEC]`: give the selection the owner in SPEC
+    /// (`[-R ]user[:group]`), or ask for it.
+    Chown(Option<String>),
+    /// `attrib SPEC`: set (`+h`), clear (`-r`) or flip (`s`) Windows file
+    /// attributes of the selection.
+    Attrib(String),
+    /// `follow`: follow the end of the previewed file, or stop.
+    Follow,
+    /// `encoding [NAME]`: decode the preview as NAME (`auto` detects it), or
+    /// open the encoding picker.
+    Encoding(Option<String>),
+    /// `copy-path`, `copy-name`, `copy-contents`: copy the selection's
+    /// paths, names or the file's contents to the system clipboard.
+    CopyToClipboard(crate::runner::handlers::clipboard::CopyWhat),
 }
 
 impl ParsedCommand {
@@ -28,10 +90,157 @@ impl ParsedCommand {
             ParsedCommand::MenuNext => app.menu_next(),
             ParsedCommand::MenuPrev => app.menu_prev(),
             ParsedCommand::MenuActivate => app.menu_activate(),
+            ParsedCommand::ReloadKeys => {
+                let (title, content) = match crate::app::settings::runtime_keybinds::reload() {
+                    Ok(()) => ("Keys reloaded".to_string(), format!("Loaded {}", crate::app::settings::keymap::keymap_file_path().display())),
+                    Err(e) => ("Keymap error".to_string(), format!("{:#}\n\nThe keymap was not applied.", e)),
+                };
+                app.mode = crate::app::Mode::Message { title, content, buttons: vec!["OK".to_string()], selected: 0, actions: None };
+            }
+            ParsedCommand::Theme => crate::runner::handlers::theme_picker::open_theme_picker(app),
+            ParsedCommand::Mounts => crate::runner::handlers::mount_picker::open_mount_picker(app),
+            ParsedCommand::ToggleGitignored => {
+                app.settings.hide_gitignored = !app.settings.hide_gitignored;
+                if let Err(e) = app.refresh() {
+                    show_message(app, "Error", format!("Failed to refresh: {}", e));
+                }
+            }
+            ParsedCommand::Profile(name) => match crate::app::settings::profiles::load_profiles() {
+                Ok(profiles) => match profiles.find(&name) {
+                    Some(profile) => crate::runner::profiles::start_profile(app, profile),
+                    None => show_message(app, "Profiles", format!("No profile named `{}`", name)),
+                },
+                Err(e) => show_message(app, "Profiles error", format!("{:#}", e)),
+            },
+            ParsedCommand::Profiles => {
+                let content = match crate::app::settings::profiles::load_profiles() {
+                    Ok(profiles) if profiles.profiles.is_empty() => format!("No profiles in {}", crate::app::settings::profiles::profiles_path().display()),
+                    Ok(profiles) => profiles.profiles.iter().map(|p| format!("{} -> {}", p.name, p.destination.display())).collect::<Vec<_>>().join("\n"),
+                    Err(e) => format!("{:#}", e),
+                };
+                show_message(app, "Profiles", content);
+            }
+            ParsedCommand::Jump(query) => {
+                let cwd = app.active_panel().cwd.clone();
+                match app.frecency.matches(&query, &cwd).first().map(|d| d.path.clone()) {
+                    Some(dir) => {
+                        if let Err(e) = app.go_to(dir) {
+                            show_message(app, "Error", crate::errors::render_fsop_error(&e, None, None, None));
+                        }
+                    }
+                    None => show_message(app, "Jump", format!("No visited directory matches `{}`", query)),
+                }
+            }
+            ParsedCommand::JumpImport(path) => {
+                let Some(path) = path.or_else(crate::app::core::frecency::zoxide_db_path) else {
+                    return show_message(app, "Jump", "No zoxide database found".to_string());
+                };
+                match app.frecency.import_zoxide(&path).and_then(|n| app.frecency.save().map(|_| n)) {
+                    Ok(n) => show_message(app, "Jump", format!("Imported {} directories from {}", n, path.display())),
+                    Err(e) => show_message(app, "Jump error", format!("{:#}", e)),
+                }
+            }
+            ParsedCommand::Tag(name, color) => {
+                let paths = crate::runner::shell::selected_paths(app);
+                let tagged = app.tags.define(&name, color.as_deref()).and_then(|_| app.tags.toggle(&paths, &name));
+                if let Err(e) = tagged.and_then(|_| app.tags.save()) {
+                    show_message(app, "Tag error", format!("{:#}", e));
+                }
+            }
+            ParsedCommand::Untag(name) => {
+                for path in crate::runner::shell::selected_paths(app) {
+                    app.tags.remove(&path, name.as_deref());
+                }
+                if let Err(e) = app.tags.save() {
+                    show_message(app, "Tag error", format!("{:#}", e));
+                }
+            }
+            ParsedCommand::SelectTag(name) => {
+                let tagged: std::collections::HashSet<&std::path::Path> = app.tags.tagged_with(&name).collect();
+                let panel = app.active_panel();
+                let selections: std::collections::HashSet<usize> =
+                    panel.entries.iter().enumerate().filter(|(_, e)| tagged.contains(e.path.as_path())).map(|(i, _)| i).collect();
+                if selections.is_empty() {
+                    return show_message(app, "Tags", format!("No entry here is tagged `{}`", name));
+                }
+                app.active_panel_mut().selections = selections;
+            }
+            ParsedCommand::Tags => {
+                let content = if app.tags.defs.is_empty() {
+                    "No tags. Tag the selection with `:tag NAME [COLOUR]`.".to_string()
+                } else {
+                    app.tags
+                        .defs
+                        .iter()
+                        .map(|d| format!("{} ({}): {} files", d.name, d.color, app.tags.tagged_with(&d.name).count()))
+                        .collect::<Vec<_>>()
+                        .join("\n")
+                };
+                show_message(app, "Tags", content);
+            }
+            ParsedCommand::CopyToClipboard(what) => crate::runner::handlers::clipboard::copy_to_system(app, what),
+            ParsedCommand::Log => crate::runner::handlers::log_view::open_log_view(app),
+            ParsedCommand::Jobs => crate::runner::handlers::job_list::open_job_list(app),
+            ParsedCommand::Tree => app.toggle_tree(),
+            ParsedCommand::DiskUsage => crate::runner::handlers::disk_usage::open_disk_usage(app),
+            ParsedCommand::Link => app.toggle_link(),
+            ParsedCommand::Touch(None) => crate::runner::handlers::normal::handle_change_times_prompt(app),
+            ParsedCommand::Chown(None) => crate::runner::handlers::normal::handle_change_owner_prompt(app),
+            ParsedCommand::Chown(Some(spec)) => crate::runner::handlers::input_mode::change_owner(app, &spec),
+            ParsedCommand::Attrib(spec) => {
+                if let Err(e) = app.change_selection_attributes(&spec) {
+                    show_message(app, "Error", crate::errors::render_fsop_error(&e, None, None, None));
+                }
+            }
+            ParsedCommand::Touch(Some(when)) => {
+                if let Err(e) = app.set_selection_times(&when) {
+                    show_message(app, "Error", crate::errors::render_fsop_error(&e, None, None, None));
+                }
+            }
+            ParsedCommand::Follow => app.toggle_follow(),
+            ParsedCommand::Encoding(None) => crate::runner::handlers::encoding_picker::open_encoding_picker(app),
+            ParsedCommand::Encoding(Some(name)) if name.eq_ignore_ascii_case("auto") => {
+                crate::runner::handlers::encoding_picker::set_preview_encoding(app, None)
+            }
+            ParsedCommand::Encoding(Some(name)) => match crate::app::core::encoding::by_name(&name) {
+                Some(encoding) => crate::runner::handlers::encoding_picker::set_preview_encoding(app, Some(encoding)),
+                None => show_message(app, "Error", format!("Unknown encoding `{}`", name)),
+            },
+            ParsedCommand::Find(None) => crate::runner::handlers::normal::open_search_prompt(app),
+            ParsedCommand::Find(Some(pattern)) => {
+                if let Err(e) = app.start_search(&pattern) {
+                    show_message(app, "Error", crate::errors::render_io_error(&e, None, None, None));
+                }
+            }
+            ParsedCommand::Select(None) => crate::runner::handlers::normal::handle_select_criteria_prompt(app),
+            ParsedCommand::Select(Some(spec)) => match app.select_by_criteria(&spec) {
+                Ok(0) => show_message(app, "Select", "No files here meet the criteria".to_string()),
+                Ok(_) => {}
+                Err(e) => show_message(app, "Error", e),
+            },
+            ParsedCommand::Searches => crate::runner::handlers::search_picker::open_search_picker(app),
+            ParsedCommand::SaveSearch(name) => {
+                let Some(options) = app.active_panel().search_results().map(|s| s.options.clone()) else {
+                    return show_message(app, "Searches", "No search results here to save; search with `/` first".to_string());
+                };
+                app.searches.save_as(&name, options);
+                if let Err(e) = app.searches.save() {
+                    show_message(app, "Searches error", format!("{:#}", e));
+                }
+            }
+            ParsedCommand::Flat => {
+                if let Err(e) = app.toggle_flat() {
+                    show_message(app, "Error", crate::errors::render_io_error(&e, None, None, None));
+                }
+            }
         }
     }
 }
 
+fn show_message(app: &mut App, title: &str, content: String) {
+    app.mode = crate::app::Mode::Message { title: title.to_string(), content, buttons: vec!["OK".to_string()], selected: 0, actions: None };
+}
+
 /// Attempt to parse a short textual command from `input`.
 ///
 /// Returns `Some(ParsedCommand)` when the input matches a known command
@@ -42,7 +251,62 @@ pub(crate) fn parse_command(input: &str) -> Option<ParsedCommand> {
         "menu-next" => Some(ParsedCommand::MenuNext),
         "menu-prev" => Some(ParsedCommand::MenuPrev),
         "menu-activate" => Some(ParsedCommand::MenuActivate),
-        _ => None,
+        "reload-keys" => Some(ParsedCommand::ReloadKeys),
+        "theme" => Some(ParsedCommand::Theme),
+        "mounts" => Some(ParsedCommand::Mounts),
+        "toggle-gitignored" => Some(ParsedCommand::ToggleGitignored),
+        "profiles" => Some(ParsedCommand::Profiles),
+        "jump-import" => Some(ParsedCommand::JumpImport(None)),
+        "tags" => Some(ParsedCommand::Tags),
+        "log" => Some(ParsedCommand::Log),
+        "jobs" => Some(ParsedCommand::Jobs),
+        "tree" => Some(ParsedCommand::Tree),
+        "flat" => Some(ParsedCommand::Flat),
+        "find" => Some(ParsedCommand::Find(None)),
+        "searches" => Some(ParsedCommand::Searches),
+        "select" => Some(ParsedCommand::Select(None)),
+        "du" => Some(ParsedCommand::DiskUsage),
+        "link" => Some(ParsedCommand::Link),
+        "touch" => Some(ParsedCommand::Touch(None)),
+        "chown" => Some(ParsedCommand::Chown(None)),
+        "encoding" => Some(ParsedCommand::Encoding(None)),
+        "follow" => Some(ParsedCommand::Follow),
+        "untag" => Some(ParsedCommand::Untag(None)),
+        "copy-path" => Some(ParsedCommand::CopyToClipboard(crate::runner::handlers::clipboard::CopyWhat::Path)),
+        "copy-name" => Some(ParsedCommand::CopyToClipboard(crate::runner::handlers::clipboard::CopyWhat::Name)),
+        "copy-contents" => Some(ParsedCommand::CopyToClipboard(crate::runner::handlers::clipboard::CopyWhat::Contents)),
+        other => {
+            let arg = |prefix: &str| other.strip_prefix(prefix).map(str::trim).filter(|a| !a.is_empty()).map(str::to_string);
+            if let Some(path) = arg("jump-import ") {
+                Some(ParsedCommand::JumpImport(Some(path.into())))
+            } else if let Some(query) = arg("jump ") {
+                Some(ParsedCommand::Jump(query))
+            } else if let Some(args) = arg("tag ") {
+                let mut words = args.split_whitespace().map(str::to_string);
+                let name = words.next()?;
+                Some(ParsedCommand::Tag(name, words.next()))
+            } else if let Some(name) = arg("untag ") {
+                Some(ParsedCommand::Untag(Some(name)))
+            } else if let Some(spec) = arg("attrib ") {
+                Some(ParsedCommand::Attrib(spec))
+            } else if let Some(spec) = arg("chown ") {
+                Some(ParsedCommand::Chown(Some(spec)))
+            } else if let Some(name) = arg("encoding ") {
+                Some(ParsedCommand::Encoding(Some(name)))
+            } else if let Some(when) = arg("touch ") {
+                Some(ParsedCommand::Touch(Some(when)))
+            } else if let Some(pattern) = arg("find ") {
+                Some(ParsedCommand::Find(Some(pattern)))
+            } else if let Some(name) = arg("save-search ") {
+                Some(ParsedCommand::SaveSearch(name))
+            } else if let Some(spec) = arg("select ") {
+                Some(ParsedCommand::Select(Some(spec)))
+            } else if let Some(name) = arg("select-tag ") {
+                Some(ParsedCommand::SelectTag(name))
+            } else {
+                arg("profile ").map(ParsedCommand::Profile)
+            }
+        }
     }
 }
 
@@ -54,11 +318,27 @@ pub(crate) fn parse_command(input: &str) -> Option<ParsedCommand> {
 pub fn perform_action(app: &mut App, action: Action) -> Result<(), FsOpError> {
     match action {
         Action::DeleteSelected => app.delete_selected(),
+        Action::DeleteMarked => {
+            let summary = app.delete_marked();
+            app.show_summary(summary, None, None);
+            Ok(())
+        }
+        Action::DeleteLinkTarget => app.delete_link_target(),
         Action::CopyTo(p) => app.copy_selected_to(p),
         Action::MoveTo(p) => app.move_selected_to(p),
         Action::RenameTo(name) => app.rename_selected_to(name),
         Action::NewFile(name) => app.new_file(name),
         Action::NewDir(name) => app.new_dir(name),
+        Action::ShowLog => {
+            crate::runner::handlers::log_view::open_log_view(app);
+            Ok(())
+        }
+        Action::MoveMarked(dst) => {
+            crate::runner::handlers::normal::start_marked_operation(app, crate::runner::handlers::normal::Operation::Move, dst);
+            Ok(())
+        }
+        // The key handlers end the event loop (see `confirm::handle_confirm`).
+        Action::Quit => Ok(()),
     }
 }
 
@@ -88,6 +368,20 @@ mod tests {
         assert_eq!(parse_command(" menu-next "), Some(ParsedCommand::MenuNext));
         assert_eq!(parse_command("menu-prev"), Some(ParsedCommand::MenuPrev));
         assert_eq!(parse_command("menu-activate"), Some(ParsedCommand::MenuActivate));
+        assert_eq!(parse_command("reload-keys"), Some(ParsedCommand::ReloadKeys));
+        assert_eq!(parse_command(" theme "), Some(ParsedCommand::Theme));
+        assert_eq!(parse_command("profiles"), Some(ParsedCommand::Profiles));
+        assert_eq!(parse_command("profile Backup to NAS"), Some(ParsedCommand::Profile("Backup to NAS".to_string())));
+        assert_eq!(parse_command("jump proj rmc"), Some(ParsedCommand::Jump("proj rmc".to_string())));
+        assert_eq!(parse_command("jump-import"), Some(ParsedCommand::JumpImport(None)));
+        assert_eq!(parse_command("jump-import /tmp/db.zo"), Some(ParsedCommand::JumpImport(Some("/tmp/db.zo".into()))));
+        assert_eq!(parse_command("tag work"), Some(ParsedCommand::Tag("work".to_string(), None)));
+        assert_eq!(parse_command("tag work #ff8800"), Some(ParsedCommand::Tag("work".to_string(), Some("#ff8800".to_string()))));
+        assert_eq!(parse_command("untag"), Some(ParsedCommand::Untag(None)));
+        assert_eq!(parse_command("untag work"), Some(ParsedCommand::Untag(Some("work".to_string()))));
+        assert_eq!(parse_command("select-tag work"), Some(ParsedCommand::SelectTag("work".to_string())));
+        assert_eq!(parse_command("tags"), Some(ParsedCommand::Tags));
+        assert_eq!(parse_command("copy-name"), Some(ParsedCommand::CopyToClipboard(crate::runner::handlers::clipboard::CopyWhat::Name)));
     }
 
     #[test]
@@ -95,5 +389,9 @@ mod tests {
         assert_eq!(parse_command(""), None);
         assert_eq!(parse_command("unknown"), None);
         assert_eq!(parse_command("toggle_preview"), None);
+        assert_eq!(parse_command("profile  "), None);
+        assert_eq!(parse_command("jump"), None);
+        assert_eq!(parse_command("tag"), None);
+        assert_eq!(parse_command("select-tag "), None);
     }
 }
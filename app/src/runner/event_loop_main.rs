@@ -1,10 +1,13 @@
 use crate::app::App;
-use crate::input::{poll, read_event, InputEvent, MouseEvent, KeyCode};
+use crate::input::async_input::TerminalEvents;
+use crate::input::{poll, read_event, InputEvent, Key, MouseEvent};
 use crate::runner::handlers;
+use crate::runner::redraw::RedrawScheduler;
 use crate::runner::terminal::{restore_terminal, TerminalGuard};
+use crate::runner::wake::{self, Deadline};
 use std::sync::mpsc::Receiver;
 use crate::ui;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 // path types are referenced behind feature gates where needed
 
 #[cfg(feature = "fs-watch")]
@@ -33,17 +36,77 @@ use std::sync::mpsc::channel as mpsc_channel;
 /// take a hard dependency on the watcher types when that feature is
 /// disabled. Keeping it small and pure makes it easy to unit-test.
 #[cfg(feature = "fs-watch")]
-use crate::runner::watch_helpers::affected_sides_from_fs_event;
+use crate::runner::watch_helpers::{affected_sides_from_fs_event, invalidate_cache_for, previewed_sides_from_fs_event};
 
+/// Run the UI until the user quits or `shutdown_rx` receives. Returns the
+/// files picked in file picker mode (`StartOptions::choose_files`).
+///
+/// The loop runs on a single-threaded tokio runtime. It sleeps until
+/// terminal input arrives, another thread wakes it (watcher events, job
+/// progress, finished listings; see `runner::wake`) or a timer of its own
+/// runs out, instead of polling for input every 100ms.
 pub fn run_app(
+    terminal: TerminalGuard,
+    shutdown_rx: Receiver<()>,
+    start_opts: crate::app::StartOptions,
+) -> anyhow::Result<Option<Vec<std::path::PathBuf>>> {
+    let runtime = tokio::runtime::Builder::new_current_thread().enable_all().build()?;
+    runtime.block_on(event_loop(terminal, shutdown_rx, start_opts))
+}
+
+/// When the loop has to wake up without input: to show time passing on
+/// screen and to run its own timers.
+fn timers(app: &App, now: Instant) -> Deadline {
+    let mut deadline = Deadline::default();
+    // The seconds counter of a listing still running.
+    if app.listing_pending() {
+        deadline.within(now, Duration::from_secs(1));
+    }
+    // The spinner of the running jobs.
+    if app.running_jobs() > 0 {
+        deadline.within(now, crate::ui::widgets::footer::SPINNER_FRAME);
+    }
+    for panel in [&app.left, &app.right] {
+        // The countdown of the refresh warning, and the retry itself.
+        if let Some(failure) = &panel.refresh_failure {
+            deadline.within(now, Duration::from_secs(1));
+            deadline.at(failure.retry_at);
+        }
+        // The footer hint of an external change goes away.
+        if let Some((_, at)) = &panel.external_change {
+            let gone = *at + crate::app::core::panel::EXTERNAL_CHANGE_HINT;
+            if gone > now {
+                deadline.at(gone);
+            }
+        }
+    }
+    // An unfinished key sequence times out.
+    if let Some(at) = app.pending_keys.deadline(Duration::from_millis(app.settings.key_sequence_timeout_ms)) {
+        deadline.at(at);
+    }
+    // The clock turns to the next minute.
+    if app.settings.show_clock {
+        let secs = crate::app::core::clock::now().duration_since(std::time::UNIX_EPOCH).map_or(0, |d| d.as_secs() % 60);
+        deadline.within(now, Duration::from_secs(60 - secs));
+    }
+    deadline
+}
+
+async fn event_loop(
     mut terminal: TerminalGuard,
     shutdown_rx: Receiver<()>,
     start_opts: crate::app::StartOptions,
-) -> anyhow::Result<()> {
+) -> anyhow::Result<Option<Vec<std::path::PathBuf>>> {
+
+    if let Some(path) = &start_opts.config_path {
+        crate::app::settings::write_settings::set_config_file_override(Some(path.clone()));
+    }
 
-    // Initialize app using provided start options (may include a start
-    // directory or initial mouse setting).
-    let mut app = App::with_options(&start_opts)?;
+    // Build the app without listing either panel: the settings, theme and
+    // session decide what to list and how, and the listings then run in
+    // the background so the first frame does not wait for them.
+    let mut profile = crate::runner::startup::StartupProfile::new(start_opts.profile_startup);
+    let mut app = App::unlisted(&start_opts)?;
     // Load persisted settings from disk if available and apply.
     if let Ok(s) = crate::app::settings::load_settings() {
         app.settings = s;
@@ -51,7 +114,6 @@ pub fn run_app(
         // correctly reflect the desired layout (for example file-stats).
         app.file_stats_visible = app.settings.file_stats_visible;
     }
-
     // Re-apply CLI-provided startup overrides (CLI should win over persisted settings).
     if let Some(m) = start_opts.mouse_enabled {
         app.settings.mouse_enabled = m;
@@ -59,10 +121,97 @@ pub fn run_app(
     if let Some(s) = start_opts.show_hidden {
         app.settings.show_hidden = s;
     }
+    if let Some(s) = start_opts.single_pane {
+        app.settings.single_pane = s;
+    }
     if let Some(ref theme) = start_opts.theme {
         app.settings.theme = theme.clone();
-        crate::ui::colors::set_theme(theme.as_str());
     }
+    profile.mark("settings");
+
+    crate::ui::colors::set_theme(app.settings.theme.as_str());
+    crate::ui::colors::set_color_depth(crate::ui::colors::ColorDepth::resolve(&app.settings.color_depth));
+    if app.settings.ls_colors {
+        crate::ui::filetype::import_ls_colors();
+    }
+    profile.mark("theme");
+
+    crate::app::settings::keybinds::apply_preset(&app.settings.keybinding_preset);
+    if let Some(e) = crate::app::settings::keybinds::keymap_error() {
+        app.mode = crate::app::Mode::Message {
+            title: "Keymap error".to_string(),
+            content: format!("{}\n\nThe keymap was not applied.", e),
+            buttons: vec!["OK".to_string()],
+            selected: 0,
+            actions: None,
+        };
+    }
+    profile.mark("keymap");
+
+    // Reopen the last session unless a start directory was requested.
+    if app.settings.restore_session && start_opts.start_dir.is_none() && start_opts.right_dir.is_none() {
+        match crate::app::settings::session::load_session() {
+            Ok(Some(session)) => session.restore(&mut app),
+            Ok(None) => {}
+            Err(e) => tracing::warn!("session not restored: {:#}", e),
+        }
+    }
+    // The single-pane setting or flag wins over the layout of the session.
+    if app.settings.single_pane {
+        app.layout = crate::app::PanelLayout::Single;
+    }
+    profile.mark("session");
+
+    // Restore the history of finished jobs.
+    match crate::app::core::jobs::JobHistory::load_from(crate::app::core::jobs::job_history_path()) {
+        Ok(h) => app.jobs = h,
+        Err(e) => tracing::warn!("job history not loaded: {:#}", e),
+    }
+
+    // Restore the visit ranks used by `:jump`.
+    match crate::app::core::frecency::Frecency::load_from(crate::app::core::frecency::frecency_path()) {
+        Ok(db) => app.frecency = db,
+        Err(e) => tracing::warn!("frecency database not loaded: {:#}", e),
+    }
+
+    // Restore the file tags.
+    match crate::app::core::tags::Tags::load_from(crate::app::core::tags::tags_path()) {
+        Ok(tags) => app.tags = tags,
+        Err(e) => tracing::warn!("tag database not loaded: {:#}", e),
+    }
+
+    // Restore the recent and saved searches.
+    match crate::app::core::searches::Searches::load_from(crate::app::core::searches::searches_path()) {
+        Ok(searches) => app.searches = searches,
+        Err(e) => tracing::warn!("searches not loaded: {:#}", e),
+    }
+    profile.mark("history");
+
+    // Load external plugins (context-menu actions and panel columns)
+    // before listing, so plugin columns are filled by the first listing.
+    app.plugins = crate::plugins::PluginRegistry::load();
+    for err in &app.plugins.errors {
+        tracing::warn!("plugin not loaded: {}", err);
+    }
+    profile.mark("plugins");
+
+    #[cfg(feature = "scripting")]
+    if let Err(e) = crate::scripting::load_init_script() {
+        tracing::warn!("init script not loaded: {:#}", e);
+    }
+
+    if let Some(path) = &start_opts.reveal {
+        app.left.select_after_listing(path);
+    }
+    app.start_listing()?;
+
+    // Take commands from `--remote` unless another instance already does.
+    let ipc = match app.settings.ipc_server {
+        true => crate::runner::ipc::IpcServer::start(&crate::runner::ipc::endpoint())
+            .map_err(|e| tracing::info!("not listening for remote commands: {}", e))
+            .ok(),
+        false => None,
+    };
 
     // Track current mouse capture state so we can toggle it at runtime when
     // user changes the `mouse_enabled` setting in the UI. Use a small enum
@@ -84,6 +233,10 @@ pub fn run_app(
         fn as_bool(&self) -> bool { matches!(self, MouseCapture::Enabled) }
     }
 
+    let shutdown_rx = wake::forward(shutdown_rx);
+    let mut input = TerminalEvents::new();
+    let mut redraw = RedrawScheduler::new(app.settings.max_fps);
+
     let mut mouse_capture = MouseCapture::from(app.settings.mouse_enabled);
     if !mouse_capture.as_bool() {
         let _ = crate::runner::terminal::disable_mouse_capture_on_terminal(&mut terminal);
@@ -95,6 +248,8 @@ pub fn run_app(
     #[cfg(feature = "fs-watch")]
     let (fs_tx, fs_rx) = mpsc_channel::<crate::fs_op::watcher::FsEvent>();
     #[cfg(feature = "fs-watch")]
+    let fs_rx = wake::forward(fs_rx);
+    #[cfg(feature = "fs-watch")]
     // Manage watcher join handles and stop senders per side so we can restart
     // watchers when the panel cwd changes during runtime.
     #[allow(unused_assignments)]
@@ -123,15 +278,50 @@ pub fn run_app(
     let mut prev_left = app.left.cwd.clone();
     #[cfg(feature = "fs-watch")]
     let mut prev_right = app.right.cwd.clone();
+    #[cfg(feature = "fs-watch")]
+    let mut fs_debounce = crate::runner::watch_helpers::RefreshDebouncer::new(
+        Duration::from_millis(200),
+        Duration::from_secs(1),
+    );
+    #[cfg(feature = "fs-watch")]
+    let mut preview_throttle = crate::runner::watch_helpers::PreviewThrottle::new(Duration::from_millis(250));
 
     // Main event loop
     loop {
-        // If watcher signalled a filesystem event, trigger a refresh and redraw.
+        // Drain watcher events and refresh each affected panel once its
+        // burst of events has settled.
         #[cfg(feature = "fs-watch")]
-        if let Ok(evt) = fs_rx.try_recv() {
-            let affected = affected_sides_from_fs_event(&evt, &app.left.cwd, &app.right.cwd);
-            for side in affected {
-                let _ = app.refresh_side(side);
+        {
+            let now = Instant::now();
+            // Files shown in a visible preview pane; changes to them also
+            // re-read the preview so e.g. a growing log can be followed.
+            let previewed = |p: &crate::app::Panel| {
+                p.selected_entry().filter(|e| app.preview_on_screen() && !e.is_dir).map(|e| e.path.clone())
+            };
+            let (left_preview, right_preview) = (previewed(&app.left), previewed(&app.right));
+            while let Ok(evt) = fs_rx.try_recv() {
+                invalidate_cache_for(&evt);
+                // Tags follow files renamed outside the app.
+                if let crate::fs_op::watcher::FsEvent::Rename(from, to) = &evt {
+                    if app.tags.rename(from, to) {
+                        if let Err(e) = app.tags.save() {
+                            tracing::warn!("failed to save tag database: {:#}", e);
+                        }
+                    }
+                }
+                for side in affected_sides_from_fs_event(&evt, &app.left.cwd, &app.right.cwd) {
+                    fs_debounce.push(side, now);
+                }
+                for side in previewed_sides_from_fs_event(&evt, left_preview.as_deref(), right_preview.as_deref()) {
+                    preview_throttle.mark(side);
+                }
+            }
+            for side in fs_debounce.take_due(now) {
+                app.refresh_watched(side, now);
+            }
+            app.retry_failed_refreshes(now);
+            for side in preview_throttle.take_due(now) {
+                app.reload_preview_for(side);
             }
         }
 
@@ -169,24 +359,89 @@ pub fn run_app(
             break;
         }
 
-        // Draw once at the top of the loop. Resize events will also trigger
-        // an immediate redraw below when detected in the aggregated events.
-        terminal.draw(|f| ui::ui(f, &app))?;
+        // Pick up background work: copy/move progress, background jobs
+        // and directory listings that outlived `LISTING_WAIT`.
+        app.poll_progress();
+        crate::runner::jobs::poll(&mut app);
+        crate::runner::shell::poll(&mut app)?;
+        crate::runner::handlers::conflict::poll(&mut app);
+        crate::runner::open_files::poll(&mut app);
+        app.poll_listings();
+        if let Some(ipc) = &ipc {
+            crate::runner::ipc::poll(ipc, &mut app);
+        }
+
+        // Precompute page size for navigation handlers: the list rows of
+        // the active panel in the current layout.
+        let ts = terminal.size()?;
+        let layout = crate::ui::layout::ScreenLayout::compute(&app, ratatui::layout::Rect::new(0, 0, ts.width, ts.height));
+        let page_size = (layout.panel(app.active).height as usize).saturating_sub(2).max(1);
 
-        // Precompute page size for navigation handlers.
-        let page_size = (terminal.size()?.height as usize).saturating_sub(4);
+        // A key sequence left unfinished for too long falls back to its
+        // first key's own binding.
+        if handlers::sequence::poll_sequence_timeout(&mut app, page_size)? {
+            break;
+        }
 
-        // Poll for any input for up to 100ms. Use `poll` to avoid blocking
-        // indefinitely and to allow aggregation of bursts of events.
-        if poll(Duration::from_millis(100))? {
+        // Draw when something changed, at most `max_fps` times a second.
+        redraw.set_max_fps(app.settings.max_fps);
+        if app.take_dirty() {
+            redraw.request();
+        }
+        // A program run outside the TUI (vim, sudo) left the screen blank.
+        if crate::runner::terminal::take_repaint() {
+            terminal.clear()?;
+            redraw.request();
+        }
+        let frame_start = Instant::now();
+        if redraw.due(frame_start) {
+            terminal.draw(|f| ui::ui(f, &app))?;
+            app.diagnostics.record_frame(frame_start.elapsed());
+            redraw.drawn(frame_start);
+        }
+        if !profile.is_finished() {
+            if !profile.phases().iter().any(|(name, _)| *name == "first_frame") {
+                profile.mark("first_frame");
+            }
+            if !app.listing_pending() {
+                profile.mark("listing");
+                profile.finish();
+            }
+        }
+
+        // Sleep until input arrives, another thread wakes the loop or one
+        // of its timers runs out.
+        let mut deadline = timers(&app, Instant::now());
+        // A frame held back by the frame limit.
+        if let Some(at) = redraw.next_frame() {
+            deadline.at(at);
+        }
+        // Watcher bursts settle and throttled previews come due.
+        #[cfg(feature = "fs-watch")]
+        for due in [fs_debounce.next_due(), preview_throttle.next_due()].into_iter().flatten() {
+            deadline.at(due);
+        }
+        let first = tokio::select! {
+            event = input.next() => Some(event),
+            _ = wake::woken() => None,
+            // The timers are for things shown on screen.
+            _ = deadline.sleep() => {
+                redraw.request();
+                None
+            }
+        };
+        if let Some(first) = first {
+            // Handled input changes the screen, and a resize needs a frame
+            // for the new size.
+            redraw.request();
             // Collect one or more available events. After the first event
             // arrives, poll briefly to coalesce follow-up events (e.g. many
             // Mouse::Moved events) so we can debounce them.
             let mut events = Vec::new();
-            // Read first event, logging any transient errors and skipping them
-            // so the loop can continue and the RAII guard will restore if
+            // Keep the first event, logging any transient error and skipping
+            // it so the loop can continue and the RAII guard will restore if
             // an error forces early return later.
-            match read_event() {
+            match first {
                 Ok(ev) => events.push(ev),
                 Err(e) => {
                     tracing::error!("failed to read input event: {:#}", e);
@@ -213,14 +468,13 @@ pub fn run_app(
             // - keep all key events (processed in order)
             // - keep non-move mouse events in order
             // - coalesce multiple Mouse::Moved into the last one
-            // - remember last resize and trigger an immediate redraw
+            // - drop resizes; the next frame is drawn at the new size
                 // Removed unused alias for MouseEvent
                 // use crate::input::MouseEvent as AppMouseEvent;
 
-            let mut key_events: Vec<KeyCode> = Vec::new();
+            let mut key_events: Vec<Key> = Vec::new();
             let mut other_mouse: Vec<MouseEvent> = Vec::new();
             let mut last_mouse_move: Option<MouseEvent> = None;
-            let mut last_resize: Option<(u16, u16)> = None;
 
             for ev in events {
                 match ev {
@@ -233,8 +487,8 @@ pub fn run_app(
                             _ => other_mouse.push(m),
                         }
                     }
-                    InputEvent::Resize(w, h) => last_resize = Some((w, h)),
-                    InputEvent::Other => {}
+                    InputEvent::Focus(focused) => crate::runner::notify::set_focused(focused),
+                    InputEvent::Resize(..) | InputEvent::Other => {}
                 }
             }
 
@@ -243,7 +497,56 @@ pub fn run_app(
             // Track whether handlers requested exit so we can break the outer loop
             // and run the normal restore path once.
             let mut should_exit = false;
-            for code in key_events {
+            for key in key_events {
+                app.diagnostics.record_key(&key);
+                // The Keys page of the Settings dialog binds whole chords,
+                // so it sees the key before modifiers are translated.
+                if handlers::keymap_editor::capture_key(&mut app, &key) {
+                    continue;
+                }
+                // Ctrl+O (the `shell` action) drops to a shell in the active
+                // panel's directory. It needs the terminal, so it is handled
+                // here rather than in the key handlers.
+                if crate::app::settings::keybinds::is_shell(&key) && matches!(app.mode, crate::app::Mode::Normal) {
+                    let cwd = app.active_panel().cwd.clone();
+                    if let Err(e) = crate::runner::terminal::suspend_to_shell(&mut terminal, &cwd, app.settings.mouse_enabled) {
+                        app.mode = crate::app::Mode::Message {
+                            title: "Error".to_string(),
+                            content: format!("Failed to start shell: {}", e),
+                            buttons: vec!["OK".to_string()],
+                            selected: 0,
+                            actions: None,
+                        };
+                    }
+                    // Ctrl-C typed inside the shell also reaches our handler;
+                    // it was meant for the shell, not for fileZoom.
+                    while shutdown_rx.try_recv().is_ok() {}
+                    let _ = app.refresh();
+                    continue;
+                }
+                // Ctrl+Q (the `quick_view` action) has no plain key for the
+                // handlers to match on, like `shell` above.
+                if crate::app::settings::keybinds::is_quick_view(&key) && matches!(app.mode, crate::app::Mode::Normal) {
+                    app.toggle_quick_view();
+                    continue;
+                }
+                // Ctrl+U and Alt+I, Alt+Shift+I (`swap_panels`,
+                // `other_panel_here`, `this_panel_there`) point the panels at
+                // each other's directories.
+                if handlers::panels::handle_panels_key(&mut app, &key) {
+                    continue;
+                }
+                // Ctrl+C/X/V (`yank`, `cut`, `paste`) use the internal
+                // clipboard.
+                if handlers::clipboard::handle_clipboard_key(&mut app, &key)? {
+                    continue;
+                }
+                // Ctrl/Alt chords from the keymap arrive as their action's key.
+                let code = if key.modifiers.ctrl || key.modifiers.alt {
+                    crate::app::settings::keybinds::chord_key(&key)
+                } else {
+                    key.code
+                };
                 if handlers::handle_key(&mut app, code, page_size)? {
                     should_exit = true;
                     break;
@@ -266,12 +569,6 @@ pub fn run_app(
                 handlers::handle_mouse(&mut app, m, term_rect)?;
             }
 
-            // If resize occurred in the burst, trigger an immediate redraw so
-            // `ratatui` can update layout before the next loop iteration.
-            if let Some((_w, _h)) = last_resize {
-                terminal.draw(|f| ui::ui(f, &app))?;
-            }
-
             // If the user toggled the mouse setting in handlers, reflect this
             // by enabling/disabling mouse capture on the terminal instance.
             if app.settings.mouse_enabled != mouse_capture.as_bool() {
@@ -288,9 +585,20 @@ pub fn run_app(
         }
     }
 
+    // A picker run does not replace the session the user left.
+    if app.settings.restore_session && app.chooser.is_none() {
+        if let Err(e) = crate::app::settings::session::save_session(&crate::app::settings::session::Session::capture(&app)) {
+            tracing::warn!("failed to save session: {:#}", e);
+        }
+    }
+
+    if let Err(e) = app.frecency.save() {
+        tracing::warn!("failed to save frecency database: {:#}", e);
+    }
+
     // Restore terminal state before exiting.
     restore_terminal(terminal)?;
-    Ok(())
+    Ok(app.chooser)
 }
 
 #[cfg(all(test, feature = "fs-watch"))]
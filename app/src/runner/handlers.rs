@@ -3,23 +3,53 @@
 //! This module keeps the top-level dispatch small and delegates mode-specific
 //! handling into individual submodules (see the public submodules below).
 
+pub mod clipboard;
+pub mod command_output;
 pub mod confirm;
 pub mod conflict;
+pub mod disk_usage;
 pub mod context_menu;
+pub mod editor;
+pub mod encoding_picker;
 pub mod input_mode;
+pub mod job_list;
+pub mod keymap_editor;
+pub mod log_view;
+pub mod mount_picker;
 pub mod mouse;
+pub mod operation_error;
+pub mod panels;
 pub mod normal;
 pub mod progress_mode;
+pub mod search_picker;
+pub mod sequence;
 pub mod settings;
+pub mod summary;
+pub mod theme_picker;
+pub mod tree;
+pub mod user_menu;
 
+pub use command_output::handle_command_output;
 pub use confirm::handle_confirm;
 pub use conflict::handle_conflict;
 pub use context_menu::handle_context_menu;
+pub use disk_usage::handle_disk_usage;
+pub use editor::handle_editor;
+pub use encoding_picker::handle_encoding_picker;
 pub use input_mode::handle_input;
+pub use job_list::handle_job_list;
+pub use keymap_editor::handle_keymap_editor;
+pub use log_view::handle_log_view;
+pub use mount_picker::handle_mount_picker;
 pub use mouse::handle_mouse;
 pub use normal::handle_normal;
+pub use operation_error::handle_operation_error;
 pub use progress_mode::handle_progress;
+pub use search_picker::handle_search_picker;
 pub use settings::handle_settings;
+pub use summary::handle_summary;
+pub use theme_picker::handle_theme_picker;
+pub use user_menu::handle_user_menu;
 
 use crate::app::{App, Mode};
 use crate::app::settings::keybinds;
@@ -49,19 +79,29 @@ pub fn handle_key(app: &mut App, code: KeyCode, page_size: usize) -> anyhow::Res
             selected,
             actions,
         } => {
-            if keybinds::is_left(&code) {
+            // A button's mnemonic selects and presses it.
+            let mnemonic = match code {
+                KeyCode::Char(c) if buttons.len() > 1 => crate::ui::menu_model::mnemonic_match(buttons, c),
+                _ => None,
+            };
+            if let Some(i) = mnemonic {
+                *selected = i;
+            }
+            if mnemonic.is_none() && keybinds::is_left(&code) {
                 if *selected > 0 {
                     *selected -= 1;
                 } else {
                     *selected = buttons.len().saturating_sub(1);
                 }
-            } else if keybinds::is_right(&code) {
+            } else if mnemonic.is_none() && keybinds::is_right(&code) {
                 *selected = (*selected + 1) % buttons.len();
-            } else if keybinds::is_enter(&code) {
+            } else if keybinds::is_enter(&code) || mnemonic.is_some() {
                 // If an action mapping exists, execute the mapped action for
                 // the selected button. Otherwise simply dismiss the dialog.
                 if let Some(act) = crate::ui::dialogs::selection_to_action(*selected, actions.as_deref()) {
                     match crate::runner::commands::perform_action(app, act) {
+                        // Keep a view the action opened (e.g. the log).
+                        Ok(_) if !matches!(app.mode, Mode::Message { .. }) => {}
                         Ok(_) => app.mode = Mode::Normal,
                         Err(e) => {
                             app.mode = Mode::Message {
@@ -84,6 +124,19 @@ pub fn handle_key(app: &mut App, code: KeyCode, page_size: usize) -> anyhow::Res
         Mode::Confirm { .. } => handle_confirm(app, code),
         Mode::Input { .. } => handle_input(app, code),
         Mode::Settings { .. } => handle_settings(app, code),
+        Mode::KeymapEditor { .. } => handle_keymap_editor(app, code),
+        Mode::CommandOutput { .. } => handle_command_output(app, code, page_size),
+        Mode::Editor { .. } => handle_editor(app, code, page_size),
+        Mode::UserMenu { .. } => handle_user_menu(app, code),
+        Mode::ThemePicker { .. } => handle_theme_picker(app, code),
+        Mode::MountPicker { .. } => handle_mount_picker(app, code),
+        Mode::EncodingPicker { .. } => handle_encoding_picker(app, code),
+        Mode::SearchPicker { .. } => handle_search_picker(app, code),
+        Mode::LogView { .. } => handle_log_view(app, code, page_size),
+        Mode::OperationError { .. } => handle_operation_error(app, code),
+        Mode::Jobs { .. } => handle_job_list(app, code),
+        Mode::Summary { .. } => handle_summary(app, code),
+        Mode::DiskUsage { .. } => handle_disk_usage(app, code, page_size),
     }
 }
 
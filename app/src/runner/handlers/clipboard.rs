@@ -0,0 +1,152 @@
+//! Yank (Ctrl+C), cut (Ctrl+X) and paste (Ctrl+V) through the internal
+//! clipboard (`App::clipboard`), and copying paths, names or contents to
+//! the system clipboard (`:copy-path`, `:copy-name`, `:copy-contents`).
+//!
+//! These are chords without a plain key, so the event loop hands them to
+//! `handle_clipboard_key` before normal-mode dispatch, like `shell`. In an
+//! input dialog or on the command line the paste key inserts the system
+//! clipboard's text instead.
+
+use std::io::Read;
+
+use anyhow::{bail, Context};
+
+use crate::app::core::clipboard::Clipboard;
+use crate::app::settings::keybinds;
+use crate::app::{App, Mode};
+use crate::input::Key;
+use crate::runner::handlers::normal::{collect_src_paths, start_operation, Operation};
+
+/// Run the clipboard action bound to `key`. Returns whether `key` was one.
+pub fn handle_clipboard_key(app: &mut App, key: &Key) -> anyhow::Result<bool> {
+    let typing = matches!(app.mode, Mode::Input { .. }) || (matches!(app.mode, Mode::Normal) && app.command_line.is_some());
+    if typing && keybinds::is_paste(key) {
+        match crate::integrations::clipboard::get_text() {
+            Ok(text) => {
+                paste_into_input(app, &text);
+            }
+            Err(e) => tracing::debug!("system clipboard not read: {}", e),
+        }
+        return Ok(true);
+    }
+    if !matches!(app.mode, Mode::Normal) || app.command_line.is_some() || app.active_panel().loading.is_some() {
+        return Ok(false);
+    }
+    if keybinds::is_yank(key) {
+        yank(app, false);
+    } else if keybinds::is_cut(key) {
+        yank(app, true);
+    } else if keybinds::is_paste(key) {
+        paste(app);
+    } else {
+        return Ok(false);
+    }
+    Ok(true)
+}
+
+/// Put the selection of the active panel on the clipboard, to be moved
+/// when `cut` is set. The paths also go to the system clipboard as text.
+pub fn yank(app: &mut App, cut: bool) {
+    let paths = collect_src_paths(app);
+    if paths.is_empty() {
+        return;
+    }
+    let clip = Clipboard { paths, cut, vfs: app.active_panel().vfs.clone() };
+    if let Err(e) = crate::integrations::clipboard::set_text(&clip.text()) {
+        tracing::debug!("system clipboard not set: {}", e);
+    }
+    app.clipboard = Some(clip);
+    app.active_panel_mut().clear_selections();
+}
+
+/// Copy or move the clipboard's paths into the active panel's directory.
+/// A cut is only pasted once.
+pub fn paste(app: &mut App) {
+    let Some(clip) = app.clipboard.clone() else {
+        return;
+    };
+    let dst_dir = app.active_panel().cwd.clone();
+    if clip.cut {
+        if clip.paths.iter().all(|p| p.parent() == Some(dst_dir.as_path())) {
+            // Moving entries onto themselves; keep the clipboard for the
+            // intended panel.
+            return;
+        }
+        app.clipboard = None;
+    }
+    let op = if clip.cut { Operation::Move } else { Operation::Copy };
+    start_operation(app, op, clip.paths, dst_dir, clip.vfs);
+}
+
+/// What `copy_to_system` puts on the system clipboard.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CopyWhat {
+    /// Full paths of the selection, one per line.
+    Path,
+    /// File names of the selection, one per line.
+    Name,
+    /// Contents of the text file under the cursor.
+    Contents,
+}
+
+/// Largest file `CopyWhat::Contents` copies.
+pub const MAX_CONTENTS_BYTES: u64 = 1024 * 1024;
+
+/// The text `copy_to_system` copies for `what`.
+pub fn selection_text(app: &App, what: CopyWhat) -> anyhow::Result<String> {
+    let paths = collect_src_paths(app);
+    if paths.is_empty() {
+        bail!("No entry selected");
+    }
+    let lines = |f: fn(&std::path::Path) -> String| paths.iter().map(|p| f(p)).collect::<Vec<_>>().join("\n");
+    match what {
+        CopyWhat::Path => Ok(lines(|p| p.display().to_string())),
+        CopyWhat::Name => Ok(lines(|p| p.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default())),
+        CopyWhat::Contents => {
+            let Some(entry) = app.active_panel().selected_entry().filter(|e| !e.is_dir) else {
+                bail!("Contents can only be copied from a file");
+            };
+            let mut bytes = Vec::new();
+            app.active_panel()
+                .vfs
+                .open(&entry.path)
+                .and_then(|f| f.take(MAX_CONTENTS_BYTES + 1).read_to_end(&mut bytes))
+                .with_context(|| format!("Cannot read {}", entry.path.display()))?;
+            if bytes.len() as u64 > MAX_CONTENTS_BYTES {
+                bail!("{} is larger than {} KiB", entry.display_name(), MAX_CONTENTS_BYTES / 1024);
+            }
+            String::from_utf8(bytes).map_err(|_| anyhow::anyhow!("{} is not a text file", entry.display_name()))
+        }
+    }
+}
+
+/// Copy `what` of the selection to the system clipboard, showing a message
+/// when that fails.
+pub fn copy_to_system(app: &mut App, what: CopyWhat) {
+    let copied = selection_text(app, what).and_then(|text| Ok(crate::integrations::clipboard::set_text(&text)?));
+    if let Err(e) = copied {
+        app.mode = Mode::Message { title: "Clipboard".to_string(), content: format!("{:#}", e), buttons: vec!["OK".to_string()], selected: 0, actions: None };
+    }
+}
+
+/// Insert the first line of `text` into the open input dialog, or at the
+/// cursor of the command line. Returns whether there was one to insert
+/// into.
+pub fn paste_into_input(app: &mut App, text: &str) -> bool {
+    let line = text.lines().next().unwrap_or_default().trim_end_matches('\r');
+    let cwd = app.active_panel().cwd.clone();
+    if let Mode::Input { buffer, kind, .. } = &mut app.mode {
+        buffer.push_str(line);
+        if *kind == crate::app::InputKind::ChangePath {
+            app.path_jump.update(buffer, &cwd);
+        }
+        return true;
+    }
+    if let Some(cmd) = &mut app.command_line {
+        let at = cmd.buffer.char_indices().nth(cmd.cursor).map_or(cmd.buffer.len(), |(i, _)| i);
+        cmd.buffer.insert_str(at, line);
+        cmd.cursor += line.chars().count();
+        return true;
+    }
+    false
+}
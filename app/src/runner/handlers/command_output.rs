@@ -0,0 +1,34 @@
+//! Key handler for the command output view (`Mode::CommandOutput`).
+//!
+//! The view only scrolls; Esc, Enter or `q` return to normal mode, killing
+//! the command if it still runs.
+
+use crate::app::{App, Mode};
+use crate::input::KeyCode;
+
+/// Handle keyboard events while the app is in `Mode::CommandOutput`.
+///
+/// Returns `Ok(false)` by convention (no special redraw request).
+pub fn handle_command_output(app: &mut App, code: KeyCode, page_size: usize) -> anyhow::Result<bool> {
+    let close = if let Mode::CommandOutput { lines, scroll, .. } = &mut app.mode {
+        let max = lines.len().saturating_sub(1);
+        let page = page_size.max(1);
+        match code {
+            KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q') => true,
+            KeyCode::Down => { *scroll = (*scroll + 1).min(max); false }
+            KeyCode::Up => { *scroll = scroll.saturating_sub(1); false }
+            KeyCode::PageDown => { *scroll = (*scroll + page).min(max); false }
+            KeyCode::PageUp => { *scroll = scroll.saturating_sub(page); false }
+            KeyCode::Home => { *scroll = 0; false }
+            KeyCode::End => { *scroll = max; false }
+            _ => false,
+        }
+    } else {
+        false
+    };
+    if close {
+        app.mode = Mode::Normal;
+        app.shell = None;
+    }
+    Ok(false)
+}
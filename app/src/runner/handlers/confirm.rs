@@ -5,8 +5,8 @@ use crate::app::settings::keybinds;
 
 /// Handle input when the application is in a confirmation dialog.
 ///
-/// The function returns `Ok(false)` for historical compatibility with the
-/// event loop (it currently never requests the app to quit). It will
+/// Returns `Ok(true)` when the confirmed action is `Action::Quit`, asking
+/// the event loop to exit, and `Ok(false)` otherwise. It will
 /// transition `app.mode` back to `Mode::Normal` when the dialog is closed,
 /// and will execute the provided `on_yes` `Action` when the user confirms.
 pub fn handle_confirm(app: &mut App, code: KeyCode) -> anyhow::Result<bool> {
@@ -14,16 +14,19 @@ pub fn handle_confirm(app: &mut App, code: KeyCode) -> anyhow::Result<bool> {
         // Left/right both toggle when there are only two options.
         if keybinds::is_left(&code) || keybinds::is_right(&code) {
             toggle_selected(selected);
-        } else if keybinds::is_enter(&code)
+        } else if (keybinds::is_enter(&code) && *selected == 0)
             || keybinds::is_char(&code, 'y')
             || keybinds::is_char(&code, 'Y')
         {
             // perform the affirmative action
             let action = on_yes.clone();
             app.mode = Mode::Normal;
+            if action == Action::Quit {
+                return Ok(true);
+            }
             execute_action(app, action);
-        } else if keybinds::is_char(&code, 'n') || keybinds::is_esc(&code) {
-            // cancel
+        } else if keybinds::is_enter(&code) || keybinds::is_char(&code, 'n') || keybinds::is_esc(&code) {
+            // cancel, including Enter on "No"
             app.mode = Mode::Normal;
         }
     }
@@ -36,52 +39,34 @@ fn toggle_selected(selected: &mut usize) {
     *selected = 1usize.saturating_sub(*selected);
 }
 
-/// Convert a filesystem operation error into a `Mode::Message` on the app.
-fn set_error_message(app: &mut App, err: &crate::fs_op::error::FsOpError) {
+/// Show the error `err` of `action`. A recoverable error (see
+/// `FsOpError::is_recoverable`) opens `Mode::OperationError` so the action
+/// can be retried; others a `Mode::Message` offering the log.
+fn set_error_message(app: &mut App, err: &crate::fs_op::error::FsOpError, action: Action) {
     let msg = errors::render_fsop_error(err, None, None, None);
+    if err.is_recoverable() {
+        let path = match &action {
+            Action::NewFile(name) | Action::NewDir(name) => Some(app.active_panel().cwd.join(name)),
+            _ => app.active_panel().selected_entry().map(|e| e.path.clone()),
+        };
+        let elevated = if err.is_permission_denied() && crate::fs_op::elevated::available() { app.elevated_op(&action) } else { None };
+        app.mode = Mode::OperationError { path, error: msg, retry: Some(action), elevated, selected: 0 };
+        return;
+    }
     app.mode = Mode::Message {
         title: "Error".to_string(),
         content: msg,
-        buttons: vec!["OK".to_string()],
-        selected: 0,
-        actions: None,
+        buttons: vec!["Show log".to_string(), "OK".to_string()],
+        selected: 1,
+        actions: Some(vec![Action::ShowLog]),
     };
 }
 
-/// Execute an `Action` coming from a confirmation dialog and surface any
-/// filesystem errors as a message mode.
-fn execute_action(app: &mut App, action: Action) {
-    match action {
-        Action::DeleteSelected => {
-            if let Err(err) = app.delete_selected() {
-                set_error_message(app, &err);
-            }
-        }
-        Action::CopyTo(p) => {
-            if let Err(err) = app.copy_selected_to(p) {
-                set_error_message(app, &err);
-            }
-        }
-        Action::MoveTo(p) => {
-            if let Err(err) = app.move_selected_to(p) {
-                set_error_message(app, &err);
-            }
-        }
-        Action::RenameTo(name) => {
-            if let Err(err) = app.rename_selected_to(name) {
-                set_error_message(app, &err);
-            }
-        }
-        Action::NewFile(name) => {
-            if let Err(err) = app.new_file(name) {
-                set_error_message(app, &err);
-            }
-        }
-        Action::NewDir(name) => {
-            if let Err(err) = app.new_dir(name) {
-                set_error_message(app, &err);
-            }
-        }
+/// Execute an `Action` coming from a confirmation dialog (or retried from
+/// an error dialog) and surface any filesystem errors as a dialog.
+pub(crate) fn execute_action(app: &mut App, action: Action) {
+    if let Err(err) = crate::runner::commands::perform_action(app, action.clone()) {
+        set_error_message(app, &err, action);
     }
 }
 
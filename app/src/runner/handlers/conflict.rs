@@ -1,3 +1,11 @@
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::TryRecvError;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::app::core::panel::Pending;
+use crate::app::types::Entry;
 use crate::app::{App, Mode};
 use crate::app::settings::keybinds;
 use crate::input::KeyCode;
@@ -6,6 +14,24 @@ use crate::runner::progress::OperationDecision;
 const RESOLVING_TITLE: &str = "Resolving";
 const APPLYING_MSG: &str = "Applying decision";
 const CANCELLING_MSG: &str = "Cancelling";
+const COMPARING_MSG: &str = "Comparing the contents...";
+
+/// How long `d` waits for the comparison before leaving it to finish in
+/// the background; small files are compared within it.
+pub const COMPARE_WAIT: Duration = Duration::from_millis(100);
+
+/// A content comparison (`d`) still reading the files on a worker thread.
+/// Dropping it, e.g. when the dialog closes, stops the worker.
+pub struct ContentCompare {
+    pending: Pending<String>,
+    cancel: Arc<AtomicBool>,
+}
+
+impl Drop for ContentCompare {
+    fn drop(&mut self) {
+        self.cancel.store(true, Ordering::Relaxed);
+    }
+}
 
 /// Map the user's current selection and the `apply_all` toggle to an
 /// `OperationDecision` value.
@@ -31,27 +57,78 @@ fn map_selection_to_decision(selected: usize, apply_all: bool) -> OperationDecis
 
 /// Helper to send a decision to the worker (if present) and transition the
 /// UI into a `Mode::Progress` state with the provided message and cancel flag.
-fn send_decision_and_enter_progress(app: &mut App, decision: OperationDecision, message: &str, cancelled: bool) {
+pub(super) fn send_decision_and_enter_progress(app: &mut App, decision: OperationDecision, message: &str, cancelled: bool) {
     if let Some(tx) = &app.op_decision_tx {
         let _ = tx.send(decision);
     }
+    app.compare = None;
     app.mode = Mode::Progress {
         title: RESOLVING_TITLE.to_string(),
         processed: 0,
         total: 0,
         message: message.to_string(),
         cancelled,
+        verify: app.op_skip_verify.as_ref().map(|_| (0, 0)),
     };
 }
 
+/// What comparing the contents of the conflicting source and target (at
+/// `path`) tells, or the still-running comparison.
+fn compare_contents(src: Option<&Entry>, dst: Option<&Entry>, path: &Path) -> Result<String, ContentCompare> {
+    let src = match (src, dst) {
+        (None, _) => return Ok("The source cannot be read to compare it".to_string()),
+        (Some(s), Some(d)) if s.is_dir || d.is_dir => return Ok("Only files can be compared".to_string()),
+        (Some(s), _) => s.path.clone(),
+    };
+    let cancel = Arc::new(AtomicBool::new(false));
+    let stop = cancel.clone();
+    let target = path.to_path_buf();
+    let work = move || match crate::fs_op::compare::compare_files_until(&src, &target, &|| stop.load(Ordering::Relaxed)) {
+        Ok(cmp) => cmp.to_string(),
+        Err(e) => format!("Cannot compare: {}", e),
+    };
+    Pending::run(path.to_path_buf(), COMPARE_WAIT, work).map_err(|pending| ContentCompare { pending, cancel })
+}
+
+/// Show the result of a comparison that outlived `COMPARE_WAIT` in the
+/// conflict dialog it was started from. Call it once per frame.
+pub fn poll(app: &mut App) {
+    let Some(run) = &app.compare else {
+        return;
+    };
+    let text = match run.pending.try_take() {
+        Ok(text) => text,
+        Err(TryRecvError::Empty) => return,
+        Err(TryRecvError::Disconnected) => "Cannot compare: the worker thread stopped".to_string(),
+    };
+    let run = app.compare.take();
+    if let (Some(run), Mode::Conflict { path, compare, .. }) = (run, &mut app.mode) {
+        if *path == run.pending.path {
+            *compare = Some(text);
+            app.dirty = true;
+        }
+    }
+}
+
 /// Handle key events when the application is in a conflict resolution mode.
 ///
 /// Returns `Ok(false)` currently (keeps existing behaviour). The function
 /// mutates `app.mode` and may send an `OperationDecision` to a background
 /// worker via `app.op_decision_tx`.
 pub fn handle_conflict(app: &mut App, code: KeyCode) -> anyhow::Result<bool> {
-    if let Mode::Conflict { path: _, selected, apply_all } = &mut app.mode {
-            if keybinds::is_left(&code) {
+    if let Mode::Conflict { path, selected, apply_all, src, dst, compare, .. } = &mut app.mode {
+            if keybinds::is_char(&code, 'd') || keybinds::is_char(&code, 'D') {
+                match compare_contents(src.as_deref(), dst.as_deref(), path) {
+                    Ok(text) => {
+                        *compare = Some(text);
+                        app.compare = None;
+                    }
+                    Err(run) => {
+                        *compare = Some(COMPARING_MSG.to_string());
+                        app.compare = Some(run);
+                    }
+                }
+            } else if keybinds::is_left(&code) {
                 *selected = (*selected).saturating_sub(1);
             } else if keybinds::is_right(&code) {
                 *selected = (*selected + 1).min(2);
@@ -11,6 +11,14 @@ enum ContextAction {
     View,
     Edit,
     Permissions,
+    /// Set the selection's access and modification times.
+    ChangeTimes,
+    /// Set the selection's owner and group.
+    ChangeOwner,
+    /// Copy the entry's path, name or contents to the system clipboard.
+    CopyToClipboard(crate::runner::handlers::clipboard::CopyWhat),
+    /// Close the menu without doing anything.
+    Cancel,
     /// Any action label we don't specifically recognise.
     Other(String),
 }
@@ -22,6 +30,12 @@ impl ContextAction {
             "View" | "Open" => ContextAction::View,
             "Edit" => ContextAction::Edit,
             "Permissions" | "Inspect Permissions" => ContextAction::Permissions,
+            "Change times" | "Touch" => ContextAction::ChangeTimes,
+            "Change owner" | "Chown" => ContextAction::ChangeOwner,
+            "Copy path" => ContextAction::CopyToClipboard(crate::runner::handlers::clipboard::CopyWhat::Path),
+            "Copy name" => ContextAction::CopyToClipboard(crate::runner::handlers::clipboard::CopyWhat::Name),
+            "Copy contents" => ContextAction::CopyToClipboard(crate::runner::handlers::clipboard::CopyWhat::Contents),
+            "Cancel" => ContextAction::Cancel,
             other => ContextAction::Other(other.to_string()),
         }
     }
@@ -51,13 +65,21 @@ pub fn handle_context_menu(app: &mut App, code: KeyCode) -> anyhow::Result<bool>
     // it after the match to avoid borrowing `app.mode` while it's being
     // inspected.
     let mut pending_mode: Option<Mode> = None;
+    // Set when a plugin action asks for the panels to be re-read.
+    let mut refresh = false;
+    // Clipboard copy to run once the menu is closed; it may open a message.
+    let mut copy = None;
+    // Dialog to open in place of the menu.
+    let mut follow_up: Option<fn(&mut App)> = None;
 
     if let Mode::ContextMenu {
             title: _,
             options,
             selected,
-            path: _,
+            path,
+            ..
         } = &mut app.mode {
+            let menu_path = path.clone();
             // Navigation: move selection left/up or right/down.
             if keybinds::is_left(&code) || keybinds::is_up(&code) {
                 *selected = selected.saturating_sub(1);
@@ -91,7 +113,7 @@ pub fn handle_context_menu(app: &mut App, code: KeyCode) -> anyhow::Result<bool>
 
                                 if use_integrated {
                                     pending_mode = match crate::app::text_editors::vim_support::spawn_vim(&e.path) {
-                                        Ok(_) => Some(build_message("Edit", format!("Launched vim for: {}", e.name))),
+                                        Ok(_) => Some(build_message("Edit", format!("Launched vim for: {}", e.display_name()))),
                                         Err(_) => Some(build_message("Edit", "Failed to launch vim".to_string())),
                                     };
                                 } else {
@@ -113,11 +135,11 @@ pub fn handle_context_menu(app: &mut App, code: KeyCode) -> anyhow::Result<bool>
                                         {
                                             use std::os::unix::fs::PermissionsExt;
                                             let mode = md.permissions().mode();
-                                            pending_mode = Some(build_message("Permissions", format!("{}: {:o}", e.name, mode)));
+                                            pending_mode = Some(build_message("Permissions", format!("{}: {:o}", e.display_name(), mode)));
                                         }
                                         #[cfg(not(unix))]
                                         {
-                                            pending_mode = Some(build_message("Permissions", format!("{}: (platform-specific metadata)", e.name)));
+                                            pending_mode = Some(build_message("Permissions", format!("{}: (platform-specific metadata)", e.display_name())));
                                         }
                                     }
                                     Err(_) => pending_mode = Some(build_message("Permissions", "Cannot read metadata".to_string())),
@@ -126,7 +148,25 @@ pub fn handle_context_menu(app: &mut App, code: KeyCode) -> anyhow::Result<bool>
                                 pending_mode = Some(build_message("Permissions", "No entry selected".to_string()));
                             }
                         }
-                        ContextAction::Other(label) => pending_mode = Some(build_message("Action", format!("Action '{}' not implemented", label))),
+                        ContextAction::ChangeTimes => follow_up = Some(crate::runner::handlers::normal::handle_change_times_prompt),
+                        ContextAction::ChangeOwner => follow_up = Some(crate::runner::handlers::normal::handle_change_owner_prompt),
+                        ContextAction::CopyToClipboard(what) => copy = Some(what),
+                        ContextAction::Cancel => {}
+                        ContextAction::Other(label) => {
+                            let cwd = app.active_panel().cwd.clone();
+                            let paths = vec![menu_path.clone()];
+                            pending_mode = Some(match app.plugins.run_action(&label, &paths, &cwd) {
+                                Some(Ok(resp)) => {
+                                    refresh = resp.refresh;
+                                    match resp.message {
+                                        Some(msg) => build_message(&label, msg),
+                                        None => Mode::Normal,
+                                    }
+                                }
+                                Some(Err(e)) => build_message("Plugin error", e.to_string()),
+                                None => build_message("Action", format!("Action '{}' not implemented", label)),
+                            });
+                        }
                     }
                 }
             }
@@ -135,6 +175,15 @@ pub fn handle_context_menu(app: &mut App, code: KeyCode) -> anyhow::Result<bool>
     if let Some(m) = pending_mode {
         app.mode = m;
     }
+    if let Some(open) = follow_up {
+        open(app);
+    }
+    if let Some(what) = copy {
+        crate::runner::handlers::clipboard::copy_to_system(app, what);
+    }
+    if refresh {
+        app.refresh()?;
+    }
 
     Ok(false)
 }
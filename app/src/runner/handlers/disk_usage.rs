@@ -0,0 +1,110 @@
+//! Key handler for the disk usage analyzer (`Mode::DiskUsage`).
+//!
+//! Up/Down, PgUp/PgDn, Home and End move through the entries, Enter or
+//! Right analyzes the selected directory, Backspace or Left its parent,
+//! `d` or Delete deletes the selected entry (asking first when
+//! `confirm_delete` is set) and Esc or `q` close the analyzer.
+
+use crate::app::core::usage::DiskUsage;
+use crate::app::settings::keybinds;
+use crate::app::{App, Mode};
+use crate::input::KeyCode;
+
+/// Open the analyzer on the active panel's directory.
+pub fn open_disk_usage(app: &mut App) {
+    let panel = app.active_panel();
+    if !panel.vfs.is_local() {
+        app.mode = Mode::Message {
+            title: "Disk usage".to_string(),
+            content: format!("Disk usage is not available on {}", panel.vfs.name()),
+            buttons: vec!["OK".to_string()],
+            selected: 0,
+            actions: None,
+        };
+        return;
+    }
+    app.usage = Some(DiskUsage::start(panel.cwd.clone()));
+    app.mode = Mode::DiskUsage { selected: 0, confirm_delete: false };
+}
+
+/// Handle keyboard events while the app is in `Mode::DiskUsage`.
+pub fn handle_disk_usage(app: &mut App, code: KeyCode, page_size: usize) -> anyhow::Result<bool> {
+    let Mode::DiskUsage { selected, confirm_delete } = app.mode else {
+        return Ok(false);
+    };
+    let Some(usage) = &app.usage else {
+        app.mode = Mode::Normal;
+        return Ok(false);
+    };
+    let last = usage.entries.len().saturating_sub(1);
+    let selected = selected.min(last);
+    let entry = usage.entries.get(selected).cloned();
+
+    if confirm_delete {
+        if keybinds::is_enter(&code) || keybinds::is_char(&code, 'y') || keybinds::is_char(&code, 'Y') {
+            delete(app, selected);
+        }
+        // Anything else keeps the entry.
+        app.mode = Mode::DiskUsage { selected, confirm_delete: false };
+        return Ok(false);
+    }
+
+    let mut next = selected;
+    if keybinds::is_up(&code) {
+        next = selected.saturating_sub(1);
+    } else if keybinds::is_down(&code) {
+        next = (selected + 1).min(last);
+    } else if code == KeyCode::PageUp {
+        next = selected.saturating_sub(page_size.max(1));
+    } else if code == KeyCode::PageDown {
+        next = (selected + page_size.max(1)).min(last);
+    } else if code == KeyCode::Home {
+        next = 0;
+    } else if code == KeyCode::End {
+        next = last;
+    } else if keybinds::is_enter(&code) || keybinds::is_right(&code) {
+        if let Some(entry) = entry.filter(|e| e.is_dir) {
+            app.usage = Some(DiskUsage::start(entry.path));
+            next = 0;
+        }
+    } else if code == KeyCode::Backspace || keybinds::is_left(&code) {
+        if let Some(parent) = usage.dir.parent().map(|p| p.to_path_buf()) {
+            app.usage = Some(DiskUsage::start(parent));
+            next = 0;
+        }
+    } else if keybinds::is_char(&code, 'd') || code == KeyCode::Delete {
+        if entry.is_some() {
+            if app.settings.confirm_delete {
+                app.mode = Mode::DiskUsage { selected, confirm_delete: true };
+                return Ok(false);
+            }
+            delete(app, selected);
+        }
+    } else if keybinds::is_esc(&code) || keybinds::is_char(&code, 'q') {
+        app.usage = None;
+        app.mode = Mode::Normal;
+        return Ok(false);
+    }
+    app.mode = Mode::DiskUsage { selected: next, confirm_delete: false };
+    Ok(false)
+}
+
+/// Delete entry `index` of the analyzer from disk and from the list, and
+/// refresh the panels. A failure is shown in the analyzer.
+fn delete(app: &mut App, index: usize) {
+    let Some(usage) = app.usage.as_mut() else {
+        return;
+    };
+    let Some(entry) = usage.entries.get(index) else {
+        return;
+    };
+    match crate::fs_op::vfs::local().remove(&entry.path) {
+        Ok(()) => {
+            tracing::info!(path = %entry.path.display(), bytes = entry.bytes, "deleted from the disk usage analyzer");
+            usage.entries.remove(index);
+            usage.error = None;
+            let _ = app.refresh();
+        }
+        Err(e) => usage.error = Some(format!("Cannot delete {}: {}", entry.name, e)),
+    }
+}
@@ -0,0 +1,124 @@
+//! Key handler for the integrated text editor (`Mode::Editor`).
+//!
+//! Editing keys are forwarded to `EditorState`; function keys mirror the
+//! classic commander layout: F2 saves, F7 searches, F9 jumps to a line and
+//! Esc/F10 closes the editor. Closing with unsaved changes requires a second
+//! press so edits are not lost by accident. When saving is refused for lack
+//! of permission, the next F2 saves through the privileged helper.
+
+use crate::app::text_editors::builtin::{EditorPrompt, EditorState};
+use crate::app::{App, Mode};
+use crate::input::KeyCode;
+
+/// Handle keyboard events while the app is in `Mode::Editor`.
+///
+/// Returns `Ok(false)` by convention (no special redraw request).
+pub fn handle_editor(app: &mut App, code: KeyCode, page_size: usize) -> anyhow::Result<bool> {
+    let close = match &mut app.mode {
+        Mode::Editor { editor } => {
+            if editor.prompt.is_some() {
+                handle_prompt(editor, code);
+                false
+            } else {
+                handle_edit_key(editor, code, page_size)
+            }
+        }
+        _ => false,
+    };
+    if close {
+        app.mode = Mode::Normal;
+        app.refresh()?;
+    } else if let Mode::Editor { editor } = &mut app.mode {
+        editor.ensure_cursor_visible(page_size.max(1));
+    }
+    Ok(false)
+}
+
+/// Handle a key while a search or go-to-line prompt is open.
+fn handle_prompt(editor: &mut EditorState, code: KeyCode) {
+    let Some(prompt) = editor.prompt.as_mut() else { return };
+    let buffer = match prompt {
+        EditorPrompt::Search(b) | EditorPrompt::GotoLine(b) => b,
+    };
+    match code {
+        KeyCode::Esc => editor.prompt = None,
+        KeyCode::Backspace => {
+            buffer.pop();
+        }
+        KeyCode::Char(c) => buffer.push(c),
+        KeyCode::Enter => match editor.prompt.take() {
+            Some(EditorPrompt::Search(q)) => {
+                editor.search(&q);
+            }
+            Some(EditorPrompt::GotoLine(n)) => match n.trim().parse::<usize>() {
+                Ok(line) => editor.goto_line(line),
+                Err(_) => editor.status = format!("Invalid line number: {}", n),
+            },
+            None => {}
+        },
+        _ => {}
+    }
+}
+
+/// Save the buffer as root: write it to a private temporary file and have
+/// the privileged helper copy that into the edited file. The file gets a
+/// random name and is created exclusively (mode 0600), so no other user
+/// can plant or swap the content the helper copies as root.
+fn save_as_root(editor: &mut EditorState) -> Result<(), String> {
+    let mut tmp = tempfile::Builder::new().prefix("filezoom-save-").tempfile().map_err(|e| format!("cannot create a temporary file: {}", e))?;
+    std::io::Write::write_all(&mut tmp, editor.text().as_bytes())
+        .and_then(|()| tmp.as_file().sync_all())
+        .map_err(|e| format!("cannot write {}: {}", tmp.path().display(), e))?;
+    // The temporary file is removed when `tmp` is dropped.
+    crate::fs_op::elevated::run(&crate::fs_op::elevated::ElevatedOp::WriteFrom { from: tmp.path().to_path_buf(), to: editor.path.clone() })?;
+    editor.mark_saved();
+    Ok(())
+}
+
+/// Handle an editing key. Returns `true` when the editor should close.
+fn handle_edit_key(editor: &mut EditorState, code: KeyCode, page_size: usize) -> bool {
+    match code {
+        KeyCode::Esc | KeyCode::F(10) => {
+            if editor.dirty && !editor.confirm_discard {
+                editor.confirm_discard = true;
+                editor.status = "Unsaved changes: F2 to save, Esc again to discard".to_string();
+                return false;
+            }
+            return true;
+        }
+        KeyCode::F(2) if editor.save_as_root => {
+            if let Err(e) = save_as_root(editor) {
+                editor.status = format!("Save as root failed: {}", e);
+            }
+        }
+        KeyCode::F(2) => match editor.save() {
+            Err(e) if e.kind() == std::io::ErrorKind::PermissionDenied && crate::fs_op::elevated::available() => {
+                editor.save_as_root = true;
+                editor.status = "Permission denied: F2 again to save as root".to_string();
+            }
+            Err(e) => editor.status = format!("Save failed: {}", e),
+            Ok(()) => {}
+        },
+        KeyCode::F(7) => {
+            let last = editor.last_search.clone().unwrap_or_default();
+            editor.prompt = Some(EditorPrompt::Search(last));
+        }
+        KeyCode::F(9) => editor.prompt = Some(EditorPrompt::GotoLine(String::new())),
+        KeyCode::Insert => editor.overwrite = !editor.overwrite,
+        KeyCode::Char(c) => editor.insert_char(c),
+        KeyCode::Tab => editor.insert_char('\t'),
+        KeyCode::Enter => editor.newline(),
+        KeyCode::Backspace => editor.backspace(),
+        KeyCode::Delete => editor.delete(),
+        KeyCode::Left => editor.move_left(),
+        KeyCode::Right => editor.move_right(),
+        KeyCode::Up => editor.move_up(1),
+        KeyCode::Down => editor.move_down(1),
+        KeyCode::PageUp => editor.move_up(page_size.max(1)),
+        KeyCode::PageDown => editor.move_down(page_size.max(1)),
+        KeyCode::Home => editor.home(),
+        KeyCode::End => editor.end(),
+        _ => {}
+    }
+    false
+}
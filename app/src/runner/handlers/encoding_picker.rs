@@ -0,0 +1,48 @@
+//! Key handler for the preview encoding picker (`Mode::EncodingPicker`).
+//!
+//! Opened with `e` in a focused preview or with `:encoding`. Up/Down move
+//! through "Auto" and `encoding::CHOICES`, Enter decodes the active panel's
+//! preview with the selected encoding, Esc or `q` close the picker.
+
+use crate::app::core::encoding::{by_name, CHOICES};
+use crate::app::settings::keybinds;
+use crate::app::{App, Mode};
+use crate::input::KeyCode;
+
+/// Open the picker on the encoding the active panel's preview uses.
+pub fn open_encoding_picker(app: &mut App) {
+    let selected = app
+        .active_panel()
+        .preview_encoding
+        .and_then(|e| CHOICES.iter().position(|&name| name == e.name()))
+        .map_or(0, |i| i + 1);
+    app.mode = Mode::EncodingPicker { selected };
+}
+
+/// Decode the active panel's preview as `encoding` (`None` detects it)
+/// and show it from the top.
+pub fn set_preview_encoding(app: &mut App, encoding: Option<&'static encoding_rs::Encoding>) {
+    app.active_panel_mut().preview_encoding = encoding;
+    app.update_preview_for(app.active);
+}
+
+/// Handle keyboard events while the app is in `Mode::EncodingPicker`.
+pub fn handle_encoding_picker(app: &mut App, code: KeyCode) -> anyhow::Result<bool> {
+    let Mode::EncodingPicker { selected } = &mut app.mode else {
+        return Ok(false);
+    };
+    if keybinds::is_up(&code) {
+        *selected = selected.saturating_sub(1);
+    } else if keybinds::is_down(&code) {
+        if *selected < CHOICES.len() {
+            *selected += 1;
+        }
+    } else if keybinds::is_enter(&code) {
+        let encoding = selected.checked_sub(1).and_then(|i| CHOICES.get(i)).and_then(|name| by_name(name));
+        app.mode = Mode::Normal;
+        set_preview_encoding(app, encoding);
+    } else if keybinds::is_esc(&code) || keybinds::is_char(&code, 'q') {
+        app.mode = Mode::Normal;
+    }
+    Ok(false)
+}
@@ -6,9 +6,9 @@
 //! `InputKind`.
 
 use std::mem;
-use std::path::PathBuf;
+use std::path::Path;
 
-use crate::app::{App, InputKind, Mode};
+use crate::app::{Action, App, InputKind, Mode};
 use crate::app::settings::keybinds;
 use crate::errors;
 use crate::input::KeyCode;
@@ -17,12 +17,26 @@ use crate::input::KeyCode;
 ///
 /// Returns `Ok(false)` by convention (no special redraw request).
 pub fn handle_input(app: &mut App, code: KeyCode) -> anyhow::Result<bool> {
+    if handle_completion_key(app, &code) {
+        return Ok(false);
+    }
+    let cwd = app.active_panel().cwd.clone();
+    let mut edited = false;
     // Fast-path: only handle keys when we're in input mode.
     if let Mode::Input { prompt: _, buffer, kind } = &mut app.mode {
         if keybinds::is_enter(&code) {
-            // Take ownership of the buffer without cloning.
-            let input = mem::take(buffer);
+            // Take ownership of the buffer without cloning; a completion
+            // chosen with Up/Down is used instead of the typed path.
+            let input = match (*kind, app.path_jump.selected, app.owners.selected) {
+                (InputKind::ChangePath, Some(_), _) => app.path_jump.completion().unwrap_or_default().to_string(),
+                (InputKind::ChangeOwner, _, Some(_)) => app.owners.completion().unwrap_or_default().to_string(),
+                _ => mem::take(buffer),
+            };
             let kind_snapshot = *kind;
+            app.path_jump.close();
+            app.owners.close();
+            let template = app.templates.chosen().map(Path::to_path_buf);
+            app.templates.close();
 
             // Leave input mode before performing potentially-failing IO so
             // the UI can reliably render error dialogs.
@@ -30,14 +44,18 @@ pub fn handle_input(app: &mut App, code: KeyCode) -> anyhow::Result<bool> {
 
             match kind_snapshot {
                 InputKind::Copy => {
-                    let dst = PathBuf::from(&input);
+                    let dst = cwd.join(&input);
                     if let Err(e) = app.copy_selected_to(dst) {
                         set_error_message(app, errors::render_fsop_error(&e, None, None, None));
                     }
                 }
                 InputKind::Move => {
-                    let dst = PathBuf::from(&input);
-                    if let Err(e) = app.move_selected_to(dst) {
+                    let dst = cwd.join(&input);
+                    if let Some(mode) = app.precheck_special(&Action::MoveTo(dst.clone())) {
+                        app.mode = mode;
+                    } else if let Some(mode) = confirm_cross_fs_move(app, &dst) {
+                        app.mode = mode;
+                    } else if let Err(e) = app.move_selected_to(dst) {
                         set_error_message(app, errors::render_fsop_error(&e, None, None, None));
                     }
                 }
@@ -47,7 +65,11 @@ pub fn handle_input(app: &mut App, code: KeyCode) -> anyhow::Result<bool> {
                     }
                 }
                 InputKind::NewFile => {
-                    if let Err(e) = app.new_file(input) {
+                    let created = match template {
+                        Some(template) => app.new_from_template(input, &template),
+                        None => app.new_file(input),
+                    };
+                    if let Err(e) = created {
                         set_error_message(app, errors::render_fsop_error(&e, None, None, None));
                     }
                 }
@@ -56,27 +78,164 @@ pub fn handle_input(app: &mut App, code: KeyCode) -> anyhow::Result<bool> {
                         set_error_message(app, errors::render_fsop_error(&e, None, None, None));
                     }
                 }
-                InputKind::ChangePath => {
-                    let p = PathBuf::from(&input);
-                    let panel = app.active_panel_mut();
-                    panel.cwd = p;
-                    if let Err(e) = app.refresh() {
+                InputKind::ChangePath => match crate::fs_op::path::resolve_path(&input, &cwd) {
+                    Ok(dir) => {
+                        if let Err(e) = app.go_to(dir) {
+                            set_error_message(app, errors::render_fsop_error(&e, None, None, None));
+                        }
+                    }
+                    Err(e) => set_error_message(app, e.to_string()),
+                },
+                InputKind::ChangeTimes => {
+                    if let Err(e) = app.set_selection_times(&input) {
+                        set_error_message(app, errors::render_fsop_error(&e, None, None, None));
+                    }
+                }
+                InputKind::ChangeOwner => change_owner(app, &input),
+                InputKind::SelectCriteria => match app.select_by_criteria(&input) {
+                    Ok(0) => {
+                        app.mode = Mode::Message {
+                            title: "Select".to_string(),
+                            content: "No files here meet the criteria".to_string(),
+                            buttons: vec!["OK".to_string()],
+                            selected: 0,
+                            actions: None,
+                        }
+                    }
+                    Ok(_) => {}
+                    Err(e) => set_error_message(app, e),
+                },
+                InputKind::Search => {
+                    let searched = if input.is_empty() { app.close_search() } else { app.start_search(&input) };
+                    if let Err(e) = searched {
                         set_error_message(app, errors::render_io_error(&e, None, None, None));
                     }
                 }
             }
         } else if keybinds::is_backspace(&code) {
             buffer.pop();
+            edited = true;
         } else if keybinds::is_esc(&code) {
             app.mode = Mode::Normal;
+            app.path_jump.close();
+            app.templates.close();
+            app.owners.close();
         } else if let KeyCode::Char(c) = code {
             buffer.push(c);
+            edited = true;
         }
     }
+    if edited {
+        update_completion(app, &cwd);
+    }
 
     Ok(false)
 }
 
+/// Refresh the suggestions of the dialogs that complete what is typed.
+fn update_completion(app: &mut App, cwd: &Path) {
+    match &mut app.mode {
+        Mode::Input { buffer, kind: InputKind::ChangePath, .. } => app.path_jump.update(buffer, cwd),
+        Mode::Input { buffer, kind: InputKind::ChangeOwner, .. } => app.owners.update(buffer),
+        Mode::Input { prompt, buffer, kind: InputKind::Search } => *prompt = super::normal::search_prompt(cwd, buffer),
+        _ => {}
+    }
+}
+
+/// Apply the change-owner dialog's `input` and report the entries that
+/// kept their owner.
+#[cfg(unix)]
+pub(crate) fn change_owner(app: &mut App, input: &str) {
+    /// Failures listed before the rest are only counted.
+    const MAX_LISTED: usize = 10;
+    let (spec, recursive) = crate::app::core::owners::split_recursive(input);
+    match app.chown_selection(spec, recursive) {
+        Ok(failures) if failures.is_empty() => {}
+        Ok(failures) => {
+            let mut lines: Vec<String> = failures.iter().take(MAX_LISTED).map(|(path, e)| format!("{}: {}", path.display(), e)).collect();
+            if failures.len() > MAX_LISTED {
+                lines.push(format!("...and {} more", failures.len() - MAX_LISTED));
+            }
+            let content = format!("{} entries kept their owner:\n{}", failures.len(), lines.join("\n"));
+            app.mode = Mode::Message { title: "Change owner".to_string(), content, buttons: vec!["OK".to_string()], selected: 0, actions: None };
+        }
+        Err(e) => set_error_message(app, errors::render_fsop_error(&e, None, None, None)),
+    }
+}
+
+#[cfg(not(unix))]
+pub(crate) fn change_owner(app: &mut App, _input: &str) {
+    set_error_message(app, "Changing owners is only supported on Unix".to_string());
+}
+
+/// Completion keys of the change-path and change-owner dialogs: Tab puts
+/// the highlighted (or best) completion in the input, Up/Down choose one.
+/// Returns whether `code` was handled.
+fn handle_completion_key(app: &mut App, code: &KeyCode) -> bool {
+    // The new-file dialog chooses its template instead.
+    if matches!(app.mode, Mode::Input { kind: InputKind::NewFile, .. }) {
+        match code {
+            KeyCode::Up => app.templates.move_selection(-1),
+            KeyCode::Down => app.templates.move_selection(1),
+            _ => return false,
+        }
+        return true;
+    }
+    if let Mode::Input { buffer, kind: InputKind::ChangeOwner, .. } = &mut app.mode {
+        match code {
+            KeyCode::Tab => {
+                if let Some(text) = app.owners.completion() {
+                    *buffer = text.to_string();
+                    app.owners.update(buffer);
+                }
+            }
+            KeyCode::Up => app.owners.move_selection(-1),
+            KeyCode::Down => app.owners.move_selection(1),
+            _ => return false,
+        }
+        return true;
+    }
+    let cwd = app.active_panel().cwd.clone();
+    // Tab switches the search between substring, regex and fuzzy matching.
+    if let Mode::Input { prompt, buffer, kind: InputKind::Search } = &mut app.mode {
+        if *code != KeyCode::Tab {
+            return false;
+        }
+        *buffer = crate::fs_op::search::with_next_mode(buffer);
+        *prompt = super::normal::search_prompt(&cwd, buffer);
+        return true;
+    }
+    let Mode::Input { buffer, kind: InputKind::ChangePath, .. } = &mut app.mode else {
+        return false;
+    };
+    match code {
+        KeyCode::Tab => {
+            if let Some(text) = app.path_jump.completion() {
+                *buffer = text.to_string();
+                app.path_jump.update(buffer, &cwd);
+            }
+        }
+        KeyCode::Up => app.path_jump.move_selection(-1),
+        KeyCode::Down => app.path_jump.move_selection(1),
+        _ => return false,
+    }
+    true
+}
+
+/// The confirmation for moving the selected entry to `dst` when that
+/// crosses filesystems and `confirm_cross_fs_move` is set.
+fn confirm_cross_fs_move(app: &App, dst: &std::path::Path) -> Option<Mode> {
+    if !app.settings.confirm_cross_fs_move || !app.active_panel().vfs.is_local() {
+        return None;
+    }
+    let entry = app.active_panel().selected_entry()?;
+    if !crate::fs_op::helpers::crosses_filesystems(&entry.path, dst) {
+        return None;
+    }
+    let msg = format!("Move {} to another filesystem?\nIt is copied and then deleted. (y/n)", entry.display_name());
+    Some(Mode::Confirm { msg, on_yes: Action::MoveTo(dst.to_path_buf()), selected: 0 })
+}
+
 /// Set a simple "Error" message dialog on the app.
 fn set_error_message(app: &mut App, content: String) {
     app.mode = Mode::Message {
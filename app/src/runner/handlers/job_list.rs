@@ -0,0 +1,51 @@
+//! Key handler for the jobs dialog (`Mode::Jobs`).
+//!
+//! Up/Down move through the background and queued jobs, Enter brings the
+//! selected running job into the progress dialog, `p` pauses or resumes
+//! it, `c` or Delete cancels it, `+`/`-` move it up or down the queue, `s`
+//! switches between running jobs one at a time and side by side
+//! (`sequential_jobs`, for this session) and Esc or `q` close the dialog.
+
+use crate::app::settings::keybinds;
+use crate::app::{App, Mode};
+use crate::input::KeyCode;
+
+/// Open the jobs dialog.
+pub fn open_job_list(app: &mut App) {
+    app.mode = Mode::Jobs { selected: 0 };
+}
+
+/// Handle keyboard events while the app is in `Mode::Jobs`.
+pub fn handle_job_list(app: &mut App, code: KeyCode) -> anyhow::Result<bool> {
+    let Mode::Jobs { selected } = app.mode else {
+        return Ok(false);
+    };
+    let count = app.scheduler.jobs().len();
+    // Jobs leave the list as they finish.
+    let selected = selected.min(count.saturating_sub(1));
+    let mut next = selected;
+    if keybinds::is_up(&code) {
+        next = selected.saturating_sub(1);
+    } else if keybinds::is_down(&code) {
+        next = (selected + 1).min(count.saturating_sub(1));
+    } else if keybinds::is_enter(&code) {
+        // Attaching replaces the dialog with the job's progress.
+        crate::runner::jobs::attach(app, selected);
+        return Ok(false);
+    } else if keybinds::is_char(&code, 'p') {
+        app.scheduler.toggle_pause(selected);
+    } else if keybinds::is_char(&code, 'c') || code == KeyCode::Delete {
+        app.scheduler.cancel(selected);
+    } else if keybinds::is_char(&code, 's') {
+        app.settings.sequential_jobs = !app.settings.sequential_jobs;
+    } else if keybinds::is_char(&code, '+') {
+        next = app.scheduler.reorder(selected, true);
+    } else if keybinds::is_char(&code, '-') {
+        next = app.scheduler.reorder(selected, false);
+    } else if keybinds::is_esc(&code) || keybinds::is_char(&code, 'q') {
+        app.mode = Mode::Normal;
+        return Ok(false);
+    }
+    app.mode = Mode::Jobs { selected: next };
+    Ok(false)
+}
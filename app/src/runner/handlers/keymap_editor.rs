@@ -0,0 +1,110 @@
+//! Key handler for the Keys page of the Settings dialog (`Mode::KeymapEditor`).
+
+use crate::app::settings::keybinds;
+use crate::app::settings::keymap_editor::{KeymapEditor, KEYMAP_BUTTONS};
+use crate::app::{App, Mode};
+use crate::input::{Key, KeyCode};
+
+/// Action rows visible at once on the Keys page; must match the renderer.
+pub const KEYMAP_VISIBLE_ROWS: usize = 14;
+
+/// Open the Keys page of the Settings dialog.
+pub fn open_keymap_editor(app: &mut App) {
+    app.mode = Mode::KeymapEditor { editor: Box::new(KeymapEditor::from_runtime()) };
+}
+
+/// Feed a raw key event (with modifiers) to the Keys page while it waits
+/// for a chord. Returns `true` when the key was consumed; the event loop
+/// calls this before modifier chords are translated for `handle_key`.
+pub fn capture_key(app: &mut App, key: &Key) -> bool {
+    match &mut app.mode {
+        Mode::KeymapEditor { editor } if editor.capturing => {
+            editor.capture(key);
+            true
+        }
+        _ => false,
+    }
+}
+
+/// Handle keys on the Keys page of the Settings dialog.
+///
+/// Up/Down move through the actions and the Save / Reset / Cancel buttons,
+/// Enter rebinds the selected action (the next key pressed becomes its
+/// chord), Delete restores its preset keys and Tab returns to the General
+/// page. Unsaved changes are dropped when the page is left.
+pub fn handle_keymap_editor(app: &mut App, code: KeyCode) -> anyhow::Result<bool> {
+    let Mode::KeymapEditor { editor } = &mut app.mode else { return Ok(false) };
+
+    if editor.capturing {
+        // Keys without modifiers arrive here in tests and headless runs.
+        editor.capture(&Key::simple(code));
+        return Ok(false);
+    }
+
+    if editor.conflict.is_some() {
+        if keybinds::is_enter(&code) {
+            editor.take_conflicting();
+        } else {
+            editor.cancel();
+        }
+        return Ok(false);
+    }
+
+    if keybinds::is_esc(&code) {
+        app.mode = Mode::Normal;
+        return Ok(false);
+    }
+    if keybinds::is_tab(&code) {
+        app.mode = Mode::Settings { selected: 0 };
+        return Ok(false);
+    }
+    // A button's mnemonic selects and presses it.
+    let mnemonic = match code {
+        KeyCode::Char(c) => crate::ui::menu_model::mnemonic_match(&KEYMAP_BUTTONS, c),
+        _ => None,
+    };
+    if let Some(i) = mnemonic {
+        editor.selected = editor.rows() - KEYMAP_BUTTONS.len() + i;
+    } else if keybinds::is_up(&code) {
+        editor.move_selection(-1);
+    } else if keybinds::is_down(&code) {
+        editor.move_selection(1);
+    } else if keybinds::is_page_up(&code) {
+        editor.selected = editor.selected.saturating_sub(KEYMAP_VISIBLE_ROWS);
+    } else if keybinds::is_page_down(&code) {
+        editor.selected = (editor.selected + KEYMAP_VISIBLE_ROWS).min(editor.rows() - 1);
+    } else if code == KeyCode::Delete {
+        editor.reset_selected();
+    }
+    if keybinds::is_enter(&code) || mnemonic.is_some() {
+        match editor.selected_button() {
+            None => editor.start_capture(),
+            Some(0) => {
+                app.mode = match editor.save() {
+                    Ok(()) => Mode::Message {
+                        title: "Keys Saved".to_string(),
+                        content: format!("Key bindings written to {}", crate::app::settings::keymap::keymap_file_path().display()),
+                        buttons: vec!["OK".to_string()],
+                        selected: 0,
+                        actions: None,
+                    },
+                    Err(e) => Mode::Message {
+                        title: "Error".to_string(),
+                        content: format!("Failed to save key bindings: {:#}", e),
+                        buttons: vec!["OK".to_string()],
+                        selected: 0,
+                        actions: None,
+                    },
+                };
+                return Ok(false);
+            }
+            Some(1) => editor.reset_all(),
+            Some(_) => {
+                app.mode = Mode::Normal;
+                return Ok(false);
+            }
+        }
+    }
+    editor.scroll_into_view(KEYMAP_VISIBLE_ROWS);
+    Ok(false)
+}
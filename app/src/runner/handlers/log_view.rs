@@ -0,0 +1,53 @@
+//! The log viewer (`Mode::LogView`), opened with `:log`, from the File
+//! menu or from the error dialog of a failed operation.
+//!
+//! It shows the end of the newest log file and scrolls back from there;
+//! `r` reloads it, Esc, Enter or `q` close it.
+
+use crate::app::{App, Mode};
+use crate::input::KeyCode;
+
+/// How many lines from the end of the log are loaded.
+const LOG_VIEW_LINES: usize = 2000;
+
+/// Open the log viewer on the newest log file.
+pub fn open_log_view(app: &mut App) {
+    let path = crate::logging::latest_log_file(&crate::logging::log_dir());
+    let lines = match &path {
+        Some(p) => crate::logging::read_tail(p, LOG_VIEW_LINES).unwrap_or_else(|e| vec![format!("Cannot read {}: {}", p.display(), e)]),
+        None => Vec::new(),
+    };
+    app.mode = Mode::LogView { path, lines, scroll: 0 };
+}
+
+/// Handle keyboard events while the app is in `Mode::LogView`.
+pub fn handle_log_view(app: &mut App, code: KeyCode, page_size: usize) -> anyhow::Result<bool> {
+    let mut reload = false;
+    let close = if let Mode::LogView { lines, scroll, .. } = &mut app.mode {
+        let page = page_size.max(1);
+        // Scrolled all the way up, the first line is at the top.
+        let max = lines.len().saturating_sub(page);
+        match code {
+            KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q') => true,
+            KeyCode::Char('r') => {
+                reload = true;
+                false
+            }
+            KeyCode::Up => { *scroll = (*scroll + 1).min(max); false }
+            KeyCode::Down => { *scroll = scroll.saturating_sub(1); false }
+            KeyCode::PageUp => { *scroll = (*scroll + page).min(max); false }
+            KeyCode::PageDown => { *scroll = scroll.saturating_sub(page); false }
+            KeyCode::Home => { *scroll = max; false }
+            KeyCode::End => { *scroll = 0; false }
+            _ => false,
+        }
+    } else {
+        false
+    };
+    if close {
+        app.mode = Mode::Normal;
+    } else if reload {
+        open_log_view(app);
+    }
+    Ok(false)
+}
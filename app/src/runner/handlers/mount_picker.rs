@@ -0,0 +1,54 @@
+//! Key handler for the mount picker (`Mode::MountPicker`).
+//!
+//! Up/Down move through the mounted filesystems, Enter switches the active
+//! panel to the selected one's root, Esc or `q` close the picker.
+
+use crate::app::settings::keybinds;
+use crate::app::{App, Mode};
+use crate::input::KeyCode;
+
+/// Open the mount picker on the filesystem holding the active panel's
+/// directory.
+pub fn open_mount_picker(app: &mut App) {
+    let mounts = crate::fs_op::mounts::list_mounts();
+    let cwd = &app.active_panel().cwd;
+    // The deepest mount point above the directory is the one it is on.
+    let selected = mounts
+        .iter()
+        .enumerate()
+        .filter(|(_, m)| cwd.starts_with(&m.root))
+        .max_by_key(|(_, m)| m.root.components().count())
+        .map_or(0, |(i, _)| i);
+    app.mode = Mode::MountPicker { mounts, selected };
+}
+
+/// Handle keyboard events while the app is in `Mode::MountPicker`.
+pub fn handle_mount_picker(app: &mut App, code: KeyCode) -> anyhow::Result<bool> {
+    let Mode::MountPicker { mounts, selected } = &mut app.mode else {
+        return Ok(false);
+    };
+    if keybinds::is_up(&code) {
+        *selected = selected.saturating_sub(1);
+    } else if keybinds::is_down(&code) {
+        if *selected + 1 < mounts.len() {
+            *selected += 1;
+        }
+    } else if keybinds::is_enter(&code) {
+        let Some(root) = mounts.get(*selected).map(|m| m.root.clone()) else {
+            return Ok(false);
+        };
+        app.mode = Mode::Normal;
+        if let Err(e) = app.go_to(root) {
+            app.mode = Mode::Message {
+                title: "Error".to_string(),
+                content: crate::errors::render_fsop_error(&e, None, None, None),
+                buttons: vec!["OK".to_string()],
+                selected: 0,
+                actions: None,
+            };
+        }
+    } else if keybinds::is_esc(&code) || keybinds::is_char(&code, 'q') {
+        app.mode = Mode::Normal;
+    }
+    Ok(false)
+}
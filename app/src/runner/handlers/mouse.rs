@@ -1,7 +1,9 @@
-use crate::app::{App, Mode, Side};
+use crate::app::{App, Mode, ScrollTarget, Side};
 use crate::input::mouse::{MouseButton, MouseEvent, MouseEventKind};
 use anyhow::Result;
-use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use crate::app::PanelLayout;
+use crate::ui::layout::ScreenLayout;
+use ratatui::layout::Rect;
 use std::time::Instant;
 
 /// Handle a terminal mouse event for the application UI.
@@ -12,12 +14,13 @@ use std::time::Instant;
 pub fn handle_mouse(app: &mut App, me: MouseEvent, term_rect: Rect) -> Result<bool> {
     use crate::ui::menu;
 
-    // Build vertical layout once; reused by several handlers.
-    let chunks = split_vertical(term_rect);
+    // Same rects as the last frame drawn by `ui::ui`.
+    let layout = ScreenLayout::compute(app, term_rect);
+    let chunks = [layout.menu, layout.header, layout.main, layout.footer];
+    let main_chunks = [layout.left, layout.right];
 
     // Fast path: scroll events (wheel) affect the active panel under cursor.
     if matches!(me.kind, MouseEventKind::ScrollUp | MouseEventKind::ScrollDown) {
-        let main_chunks = split_main(chunks[2]);
         return handle_scroll(app, &me, &main_chunks);
     }
 
@@ -28,6 +31,10 @@ pub fn handle_mouse(app: &mut App, me: MouseEvent, term_rect: Rect) -> Result<bo
         }
     }
 
+    if handle_dialog_click(app, &me, term_rect)? {
+        return Ok(true);
+    }
+
     // If a submenu is open allow clicks on the status row (below the menu)
     // to activate submenu entries (header area only provides one extra
     // row in the compact layout so map that row to the first item).
@@ -99,9 +106,24 @@ pub fn handle_mouse(app: &mut App, me: MouseEvent, term_rect: Rect) -> Result<bo
         return Ok(false);
     }
 
-    // Panels area
-    let main_chunks = split_main(chunks[2]);
+    // A left click anywhere else closes an open submenu.
+    if app.menu_state.open && matches!(me.kind, MouseEventKind::Down(MouseButton::Left)) {
+        app.close_menu();
+    }
 
+    if handle_scrollbar(app, &me, &layout) {
+        return Ok(true);
+    }
+
+    if handle_breadcrumb_click(app, &me, &layout) {
+        return Ok(true);
+    }
+
+    if handle_tree_click(app, &me, &layout) {
+        return Ok(true);
+    }
+
+    // Panels area
     // Try to handle direct clicks on panels (select, context menu, start drag, double-click)
     if me.column >= main_chunks[0].x
         && me.column < main_chunks[0].x + main_chunks[0].width
@@ -135,30 +157,6 @@ pub fn handle_mouse(app: &mut App, me: MouseEvent, term_rect: Rect) -> Result<bo
 
 // --- Small helpers ---
 
-fn split_vertical(term_rect: Rect) -> Vec<Rect> {
-    let segs = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints(
-            [
-                Constraint::Length(1),
-                Constraint::Length(3),
-                Constraint::Min(0),
-                Constraint::Length(1),
-            ]
-            .as_ref(),
-        )
-        .split(term_rect);
-    segs.iter().cloned().collect()
-}
-
-fn split_main(area: Rect) -> Vec<Rect> {
-    let segs = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)].as_ref())
-        .split(area);
-    segs.iter().cloned().collect()
-}
-
 fn list_height(area: Rect) -> usize {
     area.height.saturating_sub(2) as usize
 }
@@ -189,6 +187,157 @@ fn handle_scroll(app: &mut App, me: &MouseEvent, main_chunks: &[Rect]) -> Result
     Ok(false)
 }
 
+/// A left click on a breadcrumb in a panel's top border makes that panel
+/// active and switches it to the clicked ancestor.
+fn handle_breadcrumb_click(app: &mut App, me: &MouseEvent, layout: &ScreenLayout) -> bool {
+    if !matches!(me.kind, MouseEventKind::Down(MouseButton::Left)) {
+        return false;
+    }
+    for side in [Side::Left, Side::Right] {
+        let area = layout.panel(side);
+        if me.row != area.y || !contained_in(me, area) {
+            continue;
+        }
+        let bar = crate::ui::widgets::breadcrumbs::Breadcrumbs::new(&app.panel(side).cwd, area);
+        let Some(crumb) = bar.at(me.column) else {
+            return false;
+        };
+        app.active = side;
+        if let Err(err) = app.go_to(crumb.path.clone()) {
+            let msg = crate::errors::render_fsop_error(&err, None, None, None);
+            app.mode = Mode::Message { title: "Error".to_string(), content: msg, buttons: vec!["OK".to_string()], selected: 0, actions: None };
+        }
+        return true;
+    }
+    false
+}
+
+/// A left click on a node of the directory tree focuses the tree and sends
+/// the active panel to that directory; one on its `▸`/`▾` marker expands
+/// or collapses it instead.
+fn handle_tree_click(app: &mut App, me: &MouseEvent, layout: &ScreenLayout) -> bool {
+    let Some(area) = layout.tree else {
+        return false;
+    };
+    if !matches!(me.kind, MouseEventKind::Down(MouseButton::Left)) || !contained_in(me, area) {
+        return false;
+    }
+    let inner_top = area.y + 1;
+    let height = area.height.saturating_sub(2) as usize;
+    if me.row < inner_top || me.row >= inner_top + height as u16 {
+        return true;
+    }
+    let index = app.tree.view_offset(height) + (me.row - inner_top) as usize;
+    let Some(node) = app.tree.nodes.get(index) else {
+        return true;
+    };
+    let marker = area.x + 1 + 2 * node.depth as u16;
+    let expanded = node.expanded;
+    app.tree.focused = true;
+    app.tree.selected = index;
+    app.tree.offset = app.tree.view_offset(height);
+    if me.column == marker || me.column == marker + 1 {
+        if expanded {
+            app.tree.collapse(index);
+        } else {
+            app.tree.expand(index, app.settings.show_hidden);
+        }
+    } else {
+        crate::runner::handlers::tree::open_selected(app);
+    }
+    true
+}
+
+/// A left click on a button of the conflict, confirmation or message dialog
+/// presses it, and one on a context menu entry runs that entry, as if it
+/// had been picked with the keyboard. A click outside a context menu closes
+/// it. Returns whether the event was consumed.
+fn handle_dialog_click(app: &mut App, me: &MouseEvent, term_rect: Rect) -> Result<bool> {
+    use crate::ui::widgets::dialog;
+    if !matches!(me.kind, MouseEventKind::Down(MouseButton::Left)) {
+        return Ok(false);
+    }
+    let button = match &mut app.mode {
+        Mode::ContextMenu { title, options, selected, at, .. } => {
+            let rect = dialog::context_menu_rect(term_rect, title, options, *at);
+            if !contained_in(me, rect) {
+                app.mode = Mode::Normal;
+                return Ok(true);
+            }
+            match me.row.checked_sub(rect.y + 1).map(usize::from) {
+                Some(i) if i < options.len() => *selected = i,
+                _ => return Ok(true),
+            }
+            None
+        }
+        _ => match dialog::button_at(app, term_rect, me.column, me.row) {
+            Some(i) => Some(i),
+            None => return Ok(false),
+        },
+    };
+    if let (Some(i), Mode::Conflict { selected, .. } | Mode::Confirm { selected, .. } | Mode::Message { selected, .. } | Mode::OperationError { selected, .. }) = (button, &mut app.mode) {
+        *selected = i;
+    }
+    super::handle_key(app, crate::input::KeyCode::Enter, 0)?;
+    Ok(true)
+}
+
+/// A left click on the scrollbar of a panel or of the preview scrolls it to
+/// the matching position, and dragging keeps scrolling it until the button
+/// is released. Returns whether the event was consumed.
+fn handle_scrollbar(app: &mut App, me: &MouseEvent, layout: &ScreenLayout) -> bool {
+    match me.kind {
+        MouseEventKind::Down(MouseButton::Left) => {
+            let on_bar = |area: Rect, total: usize| {
+                area.width > 0 && me.column + 1 == area.right() && me.row > area.y && me.row + 1 < area.bottom() && total > list_height(area)
+            };
+            let panel = [Side::Left, Side::Right].into_iter().find(|&s| on_bar(layout.panel(s), app.panel(s).entries.len()));
+            let preview = layout.preview.is_some_and(|a| on_bar(a, app.active_panel().preview.lines().count()));
+            app.scroll_drag = match (panel, preview) {
+                (Some(side), _) => Some(ScrollTarget::Panel(side)),
+                (None, true) => Some(ScrollTarget::Preview),
+                (None, false) => return false,
+            };
+        }
+        MouseEventKind::Drag(MouseButton::Left) if app.scroll_drag.is_some() => {}
+        MouseEventKind::Up(MouseButton::Left) if app.scroll_drag.is_some() => {
+            app.scroll_drag = None;
+            return true;
+        }
+        _ => return false,
+    }
+    match app.scroll_drag {
+        Some(ScrollTarget::Panel(side)) => {
+            let area = layout.panel(side);
+            let rows = list_height(area);
+            let max = app.panel(side).entries.len().saturating_sub(rows);
+            app.active = side;
+            app.panel_mut(side).scroll_to(track_position(area, me.row, max), rows);
+        }
+        Some(ScrollTarget::Preview) => {
+            if let Some(area) = layout.preview {
+                let panel = app.active_panel_mut();
+                let max = panel.preview.lines().count().saturating_sub(1);
+                panel.preview_offset = track_position(area, me.row, max);
+            }
+        }
+        None => {}
+    }
+    true
+}
+
+/// Position in `0..=max` that `row` stands for on the scrollbar along the
+/// right border of `area`; rows past either end of the track clamp.
+fn track_position(area: Rect, row: u16, max: usize) -> usize {
+    let top = area.y + 1;
+    let len = list_height(area).max(1);
+    let at = row.clamp(top, top + len as u16 - 1) - top;
+    if len == 1 {
+        return 0;
+    }
+    (usize::from(at) * max + (len - 1) / 2) / (len - 1)
+}
+
 fn contained_in(me: &MouseEvent, area: Rect) -> bool {
     me.column >= area.x
         && me.column < area.x + area.width
@@ -197,7 +346,7 @@ fn contained_in(me: &MouseEvent, area: Rect) -> bool {
 }
 
 fn handle_settings_modal(app: &mut App, me: &MouseEvent, term_rect: Rect) -> Result<bool> {
-    let rect = crate::ui::modal::centered_rect(term_rect, 60, 10);
+    let rect = crate::ui::modal::centered_rect(term_rect, 60, super::settings::SETTINGS_HEIGHT);
     if !contained_in(me, rect) {
         return Ok(false);
     }
@@ -225,8 +374,9 @@ fn handle_settings_modal(app: &mut App, me: &MouseEvent, term_rect: Rect) -> Res
     }
 
     if me.row == footer_row && matches!(me.kind, MouseEventKind::Down(MouseButton::Left)) {
-        let mid = rect.x + rect.width / 2;
-        if me.column < mid {
+        // Footer thirds map to the Save / Restore / Cancel buttons.
+        let third = rect.width / 3;
+        if me.column < rect.x + third {
             // Save
             match crate::app::settings::save_settings(&app.settings) {
                 Ok(_) => {
@@ -248,6 +398,8 @@ fn handle_settings_modal(app: &mut App, me: &MouseEvent, term_rect: Rect) -> Res
                     };
                 }
             }
+        } else if me.column < rect.x + 2 * third {
+            app.mode = super::settings::restore_previous(app);
         } else {
             app.mode = Mode::Normal;
         }
@@ -280,14 +432,20 @@ fn handle_panel_click(area: Rect, side: Side, app: &mut App, me: &MouseEvent) ->
         app.drag_button = Some(MouseButton::Left);
     }
 
-    // Double-click detection
+    // Double-click detection: a second left click on the same row of this
+    // panel within `mouse_double_click_ms` acts like Enter.
     if matches!(me.kind, MouseEventKind::Down(MouseButton::Left)) && app.settings.mouse_enabled {
         if let (Some(prev_t), Some((pc, pr))) = (app.last_mouse_click_time, app.last_mouse_click_pos) {
             let elapsed = Instant::now().saturating_duration_since(prev_t);
-            if pc == me.column && pr == me.row && elapsed.as_millis() <= app.settings.mouse_double_click_ms as u128 {
-                let _ = app.enter();
+            let same_row = pr == me.row && pc >= area.x && pc < area.x + area.width;
+            if same_row && elapsed.as_millis() <= app.settings.mouse_double_click_ms as u128 {
                 app.last_mouse_click_time = None;
                 app.last_mouse_click_pos = None;
+                app.drag_active = false;
+                app.drag_start = None;
+                app.drag_current = None;
+                app.drag_button = None;
+                super::normal::handle_enter(app)?;
                 return Ok(true);
             }
         }
@@ -295,21 +453,10 @@ fn handle_panel_click(area: Rect, side: Side, app: &mut App, me: &MouseEvent) ->
         app.last_mouse_click_pos = Some((me.column, me.row));
     }
 
-    // Right-click: open context menu for selected entry
-    if matches!(me.kind, MouseEventKind::Down(MouseButton::Right)) {
-        if let Some(e) = app.panel_mut(side).selected_entry().cloned() {
-            let options = if app.settings.context_actions.is_empty() {
-                vec!["View".into(), "Edit".into(), "Permissions".into(), "Cancel".into()]
-            } else {
-                app.settings.context_actions.clone()
-            };
-            app.mode = Mode::ContextMenu {
-                title: format!("Actions: {}", e.name),
-                options,
-                selected: 0,
-                path: e.path.clone(),
-            };
-        }
+    // Right-click: open the context menu of the clicked entry next to the
+    // pointer. Clicks on the header or parent rows have no menu.
+    if matches!(me.kind, MouseEventKind::Down(MouseButton::Right)) && app.panel(side).selected_entry().is_some() {
+        super::normal::handle_context_actions(app, Some((me.column, me.row)));
     }
 
     // For drag/up events, don't mark consumed here so outer handler can process them.
@@ -321,8 +468,10 @@ fn handle_panel_click(area: Rect, side: Side, app: &mut App, me: &MouseEvent) ->
 }
 
 fn handle_drag_update(main_chunks: &[Rect], app: &mut App, me: &MouseEvent) -> Result<bool> {
+    // Stacked panels share their columns, so the row picks the panel.
+    let stacked = app.layout == PanelLayout::Vertical;
     let try_update = |area: Rect, side: Side, app: &mut App, me: &MouseEvent| -> bool {
-        if !(me.column >= area.x && me.column < area.x + area.width) {
+        if !(me.column >= area.x && me.column < area.x + area.width) || (stacked && !contained_in(me, area)) {
             return false;
         }
         if app.drag_active && app.drag_button == Some(MouseButton::Left) {
@@ -381,6 +530,17 @@ mod tests {
     use crate::input::mouse::{MouseButton, MouseEvent, MouseEventKind};
     use ratatui::layout::Rect;
 
+    #[test]
+    fn track_position_spans_the_track() {
+        // Rows 1..=10 inside the borders of a 12-row area.
+        let area = Rect::new(0, 0, 20, 12);
+        assert_eq!(track_position(area, 1, 90), 0);
+        assert_eq!(track_position(area, 10, 90), 90);
+        assert_eq!(track_position(area, 0, 90), 0);
+        assert_eq!(track_position(area, 11, 90), 90);
+        assert_eq!(track_position(area, 5, 9), 4);
+    }
+
     #[test]
     fn contained_in_detects_points() {
         let r = Rect::new(5, 3, 10, 6);
@@ -1,10 +1,15 @@
 use crate::app::{Action, App, InputKind, Mode, Side};
 use crate::errors;
+use crate::fs_op::path::{display_path, MAX_DISPLAY_PATH_CHARS};
 use crate::input::KeyCode;
-use crate::runner::progress::{OperationDecision, ProgressUpdate};
+use crate::runner::progress::{OperationDecision, OperationSummary, ProgressPhase, ProgressUpdate};
 use std::path::PathBuf;
-use fs_extra::copy_items;
-use fs_extra::dir::CopyOptions as FsCopyOptions;
+use std::time::{Duration, Instant};
+use crate::fs_op::options::CopyOptions;
+use crate::fs_op::vfs::VfsProvider;
+use crate::fs_op::meter::Meter;
+use crate::app::settings::write_settings::Settings;
+use crate::runner::jobs::{JobHandle, JobSpec};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{mpsc, Arc};
 
@@ -17,8 +22,92 @@ pub fn handle_normal(app: &mut App, code: KeyCode, page_size: usize) -> anyhow::
         return crate::ui::command_line::handle_input(app, code);
     }
 
+    // With the top menu focused, a label's mnemonic opens or runs it.
+    if let KeyCode::Char(c) = code {
+        if app.menu_focused && app.menu_mnemonic(c) {
+            return Ok(false);
+        }
+    }
+
+    // Keys that start or continue a key sequence (`g g`) wait for the
+    // rest of it; the top menu takes keys one at a time.
+    if !app.menu_focused {
+        if let Some(exit) = crate::runner::handlers::sequence::handle_sequence_key(app, code, page_size)? {
+            return Ok(exit);
+        }
+    }
+    dispatch_key(app, code, page_size)
+}
+
+/// Run the normal-mode action bound to `code` in the active key table.
+pub(crate) fn dispatch_key(app: &mut App, code: KeyCode, page_size: usize) -> anyhow::Result<bool> {
+    if crate::app::settings::keybinds::is_go_home(&code) {
+        handle_go_home(app);
+        return Ok(false);
+    }
+    // Keys from the active keybinding preset arrive as the default key of
+    // their action, which is what the table in `run_default_key` matches on.
+    let code = crate::app::settings::keybinds::canonical_key(&code);
+    run_default_key(app, code, page_size)
+}
+
+/// Run the normal-mode action whose default key is `code`.
+pub(crate) fn run_default_key(app: &mut App, code: KeyCode, page_size: usize) -> anyhow::Result<bool> {
+    // While the active panel is still loading only quitting, switching
+    // panels and cancelling the listing (Esc) apply.
+    if app.active_panel().loading.is_some() {
+        match code {
+            KeyCode::Esc => {
+                app.cancel_listing(app.active);
+                return Ok(false);
+            }
+            KeyCode::Char('q') | KeyCode::Tab => {}
+            _ => return Ok(false),
+        }
+    }
+
+    // A focused preview pane takes the scrolling keys, `e` (encoding
+    // picker), `F` (follow the end of the file) and, for JSON, Enter, `-`
+    // and `+` (fold); Esc and Tab hand the focus back to the panel.
+    if app.preview_focused && !app.menu_focused {
+        let page = page_size.max(1) as isize;
+        if matches!(code, KeyCode::Up | KeyCode::PageUp | KeyCode::Home) {
+            app.unpin_follow(page_size.max(1));
+        }
+        let handled = match code {
+            KeyCode::Down => { app.scroll_preview(1); true }
+            KeyCode::Up => { app.scroll_preview(-1); true }
+            KeyCode::PageDown => { app.scroll_preview(page); true }
+            KeyCode::PageUp => { app.scroll_preview(-page); true }
+            KeyCode::Home => { app.active_panel_mut().preview_offset = 0; true }
+            KeyCode::End => { app.scroll_preview(isize::MAX); true }
+            KeyCode::Esc | KeyCode::Tab => { app.preview_focused = false; true }
+            KeyCode::Char('e') => { crate::runner::handlers::encoding_picker::open_encoding_picker(app); true }
+            KeyCode::Char('F') => { app.toggle_follow(); true }
+            KeyCode::Enter => app.toggle_preview_fold(),
+            KeyCode::Char('-') => app.fold_preview_all(true),
+            KeyCode::Char('+') => app.fold_preview_all(false),
+            _ => false,
+        };
+        if handled {
+            return Ok(false);
+        }
+    }
+
+    // A focused directory tree takes the navigation keys (see
+    // `handlers::tree`).
+    if app.tree.focused && app.tree.visible && !app.menu_focused && crate::runner::handlers::tree::handle_tree_key(app, code, page_size) {
+        return Ok(false);
+    }
+
+    // In the file picker Enter on a file, or with files marked, picks them
+    // and quits.
+    if code == KeyCode::Enter && !app.menu_focused && choose_files(app) {
+        return Ok(true);
+    }
+
     match code {
-        KeyCode::Char('q') => return Ok(true),
+        KeyCode::Char('q') => return Ok(handle_quit(app)),
         // When the top menu has focus, Up/Down navigate submenu (if open).
         KeyCode::Down if app.menu_focused && app.menu_state.open => app.menu_sub_next(),
         KeyCode::Up if app.menu_focused && app.menu_state.open => app.menu_sub_prev(),
@@ -32,9 +121,7 @@ pub fn handle_normal(app: &mut App, code: KeyCode, page_size: usize) -> anyhow::
         KeyCode::Char('d') => handle_delete_prompt(app),
         KeyCode::Char('c') => handle_copy_prompt(app),
         KeyCode::Char('m') => handle_move_prompt(app),
-        KeyCode::Char('n') => {
-            app.mode = Mode::Input { prompt: "New file name:".to_string(), buffer: String::new(), kind: InputKind::NewFile };
-        }
+        KeyCode::Char('n') => app.open_new_file(),
         KeyCode::Char('N') => {
             app.mode = Mode::Input { prompt: "New dir name:".to_string(), buffer: String::new(), kind: InputKind::NewDir };
         }
@@ -42,12 +129,20 @@ pub fn handle_normal(app: &mut App, code: KeyCode, page_size: usize) -> anyhow::
         KeyCode::Char('s') => { app.sort = app.sort.next(); app.refresh()?; }
         KeyCode::Char('S') => { use crate::app::types::SortOrder::*; app.sort_order = match app.sort_order { Ascending => Descending, Descending => Ascending }; app.refresh()?; }
         KeyCode::Char(' ') => app.active_panel_mut().toggle_selection(),
+        // In quick view the other panel is the preview, so Tab focuses it.
+        KeyCode::Tab if app.other_panel_shows(crate::app::OtherPanel::QuickView) => app.preview_focused = true,
         KeyCode::Tab => { app.active = match app.active { Side::Left => Side::Right, Side::Right => Side::Left }; }
         KeyCode::F(5) => handle_operation_start(app, Operation::Copy)?,
         KeyCode::F(6) => handle_operation_start(app, Operation::Move)?,
         KeyCode::F(1) => app.menu_focused = !app.menu_focused,
         KeyCode::Left if app.menu_focused => app.menu_prev(),
         KeyCode::Right if app.menu_focused => app.menu_next(),
+        // On the breadcrumb row Left/Right pick an ancestor.
+        KeyCode::Left if app.active_panel().selected == 0 => app.active_panel_mut().move_crumb(1),
+        KeyCode::Right if app.active_panel().selected == 0 => app.active_panel_mut().move_crumb(-1),
+        KeyCode::Char('b') => app.focus_breadcrumbs(),
+        KeyCode::Char('/') => open_search_prompt(app),
+        KeyCode::Char('+') => handle_select_criteria_prompt(app),
         KeyCode::Enter if app.menu_focused => {
             // If there is a submenu for the current top label, open or activate accordingly.
             let model = crate::ui::menu_model::MenuModel::default_model();
@@ -76,17 +171,44 @@ pub fn handle_normal(app: &mut App, code: KeyCode, page_size: usize) -> anyhow::
                 app.menu_focused = false;
             }
         }
-        KeyCode::Home => app.active_panel_mut().selected = 0,
+        KeyCode::Home => {
+            let panel = app.active_panel_mut();
+            panel.selected = 0;
+            panel.crumb = 0;
+        }
         KeyCode::End => handle_end_key(app),
         KeyCode::Char('p') => app.toggle_preview(),
-        KeyCode::F(3) => handle_context_actions(app),
+        KeyCode::Char('P') => app.toggle_preview_focus(),
+        KeyCode::Char('i') => app.toggle_info_panel(),
+        KeyCode::Char('e') => app.toggle_tree(),
+        KeyCode::Char('F') => {
+            if let Err(err) = app.toggle_flat() {
+                app.mode = make_message_mode("Error", errors::render_io_error(&err, None, None, None));
+            }
+        }
+        KeyCode::F(3) => handle_context_actions(app, None),
+        KeyCode::F(2) => handle_open_user_menu(app),
+        KeyCode::F(4) => handle_open_editor(app),
+        KeyCode::F(12) => app.diagnostics.visible = !app.diagnostics.visible,
+        KeyCode::Char('J') => {
+            app.mode = Mode::Message { title: "Job history".to_string(), content: app.jobs.dialog_text(), buttons: vec!["OK".to_string()], selected: 0, actions: None };
+        }
+        KeyCode::Char('B') => crate::runner::handlers::job_list::open_job_list(app),
+        KeyCode::Char('U') => crate::runner::handlers::disk_usage::open_disk_usage(app),
+        KeyCode::Char('K') => app.toggle_link(),
+        KeyCode::Char(':') => crate::ui::command_line::open(app),
         KeyCode::Char('t') => crate::ui::colors::toggle(),
+        KeyCode::Char('T') => crate::runner::handlers::theme_picker::open_theme_picker(app),
+        KeyCode::Char('M') => crate::runner::handlers::mount_picker::open_mount_picker(app),
+        KeyCode::Char('L') => app.cycle_layout(),
         KeyCode::Char('?') => {
-            let content = "Keys:\n\nq: quit\nF1: toggle menu focus\nLeft/Right: menu navigation when focused\nEnter: open/activate\nBackspace: up\nd: delete\nc: copy\nm: move\nn/N: new file/dir\nR: rename\ns/S: sort (toggle desc)\nTab: switch panels\n?: show this help\n".to_string();
+            let content = crate::app::settings::keybinds::help_text();
             app.mode = Mode::Message { title: "Help".to_string(), content, buttons: vec!["OK".to_string()], selected: 0, actions: None };
         }
         KeyCode::Char('>') => app.active_panel_mut().preview_offset = app.active_panel_mut().preview_offset.saturating_add(5),
         KeyCode::Char('<') => app.active_panel_mut().preview_offset = app.active_panel_mut().preview_offset.saturating_sub(5),
+        #[cfg(feature = "scripting")]
+        KeyCode::Char(c) => { crate::scripting::fire(app, crate::scripting::Hook::Key(c)); }
         _ => {}
     }
 
@@ -100,7 +222,14 @@ pub fn handle_normal(app: &mut App, code: KeyCode, page_size: usize) -> anyhow::
 /// Used by `handle_operation_start` to decide whether the background
 /// worker should perform a copy (F5) or a move (F6).
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
-enum Operation { Copy, Move }
+pub(crate) enum Operation { Copy, Move }
+
+impl Operation {
+    /// Label of the operation in the job history, e.g. "Copy".
+    pub(crate) fn name(self) -> &'static str {
+        match self { Operation::Copy => "Copy", Operation::Move => "Move" }
+    }
+}
 
 /// Helper to construct a simple `Mode::Message` with an OK button.
 ///
@@ -112,16 +241,28 @@ fn make_message_mode(title: &str, content: String) -> Mode {
 /// Handle an Enter key press when not focused on the top menu.
 ///
 /// Behaviour:
-/// - If the selected row is the header (index 0), enters a change-path input mode.
+/// - If the selected row is the breadcrumb row (index 0), jumps to the picked
+///   ancestor, or enters a change-path input mode when the panel's own
+///   directory is picked.
 /// - If the selected row points to the parent entry and `go_up` is available, attempt to go up.
 /// - Otherwise attempt to `enter` the selected entry (open directory or preview file).
 ///
 /// Any filesystem errors are rendered via `errors::render_fsop_error` and shown
 /// to the user in a `Mode::Message`.
-fn handle_enter(app: &mut App) -> anyhow::Result<()> {
+pub(crate) fn handle_enter(app: &mut App) -> anyhow::Result<()> {
     let panel = app.active_panel_mut();
+    if panel.selected == 0 && panel.crumb > 0 {
+        let dir = panel.crumb_dir().to_path_buf();
+        if let Err(err) = app.go_to(dir) {
+            let msg = errors::render_fsop_error(&err, None, None, None);
+            app.mode = make_message_mode("Error", msg);
+        }
+        return Ok(());
+    }
     if panel.selected == 0 {
-        let prompt = format!("Change path (current: {}):", panel.cwd.display());
+        let prompt = format!("Change path (current: {}):", display_path(&panel.cwd, MAX_DISPLAY_PATH_CHARS));
+        let cwd = panel.cwd.clone();
+        app.path_jump.update("", &cwd);
         app.mode = Mode::Input { prompt, buffer: String::new(), kind: InputKind::ChangePath };
         return Ok(());
     }
@@ -134,7 +275,7 @@ fn handle_enter(app: &mut App) -> anyhow::Result<()> {
         }
     } else if let Some(e) = panel.selected_entry().cloned() {
         if let Err(err) = app.enter() {
-            let path_s = e.path.display().to_string();
+            let path_s = display_path(&e.path, MAX_DISPLAY_PATH_CHARS);
             let msg = errors::render_fsop_error(&err, Some(&path_s), None, None);
             app.mode = make_message_mode("Error", msg);
         }
@@ -142,6 +283,22 @@ fn handle_enter(app: &mut App) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Confirm the marked files, or the selected one unless it is a
+/// directory, as the choice of the file picker (`--choose-files`).
+/// Returns whether anything was picked.
+fn choose_files(app: &mut App) -> bool {
+    if app.chooser.is_none() {
+        return false;
+    }
+    let panel = app.active_panel();
+    if panel.selections.is_empty() && panel.selected_entry().is_none_or(|e| e.is_dir) {
+        return false;
+    }
+    let paths = crate::runner::shell::selected_paths(app);
+    app.chooser = Some(paths);
+    true
+}
+
 /// Attempt to move the active panel up one directory.
 ///
 /// On error the function will render an error message into `app.mode` so the
@@ -154,8 +311,23 @@ fn handle_go_up(app: &mut App) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Switch the active panel to the home directory, showing an error
+/// message on failure.
+pub(crate) fn handle_go_home(app: &mut App) {
+    if app.active_panel().loading.is_some() {
+        return;
+    }
+    if let Err(err) = app.go_home() {
+        let msg = errors::render_fsop_error(&err, None, None, None);
+        app.mode = make_message_mode("Error", msg);
+    }
+}
+
 /// Refresh the active panels, showing an error message on failure.
 fn handle_refresh(app: &mut App) -> anyhow::Result<()> {
+    if let Some(search) = app.active_panel().search_results() {
+        search.rerun();
+    }
     if let Err(err) = app.refresh() {
         let msg = errors::render_io_error(&err, None, None, None);
         app.mode = make_message_mode("Error", msg);
@@ -163,22 +335,97 @@ fn handle_refresh(app: &mut App) -> anyhow::Result<()> {
     Ok(())
 }
 
-/// Prompt the user to confirm deletion of the currently selected entry.
+/// Ask for the ages and sizes of the files to mark in the active panel.
+pub(crate) fn handle_select_criteria_prompt(app: &mut App) {
+    let prompt = "Select files by criteria (e.g. `older 30d`, `newer 2024-01-01 older 2024-07-01`, `>100M`):".to_string();
+    app.mode = Mode::Input { prompt, buffer: String::new(), kind: InputKind::SelectCriteria };
+}
+
+/// Ask for a name or glob to search for below the active panel's
+/// directory; in search results the current query is offered for refining.
+pub(crate) fn open_search_prompt(app: &mut App) {
+    let panel = app.active_panel();
+    let buffer = panel.search_results().map(|s| s.options.query()).unwrap_or_default();
+    let prompt = search_prompt(&panel.cwd, &buffer);
+    app.mode = Mode::Input { prompt, buffer, kind: InputKind::Search };
+}
+
+/// Prompt of the search dialog below `dir`, naming the match mode `query`
+/// asks for.
+pub(crate) fn search_prompt(dir: &std::path::Path, query: &str) -> String {
+    let mode = crate::fs_op::search::SearchOptions::parse(query, false).mode;
+    format!("Find below {} [{}] (Tab: mode, empty leaves results):", display_path(dir, MAX_DISPLAY_PATH_CHARS), mode.label())
+}
+
+/// Quit, unless copies or moves are still running or queued and
+/// `confirm_quit_with_jobs` is set: then ask first. Returns whether to quit
+/// now.
+fn handle_quit(app: &mut App) -> bool {
+    let jobs = usize::from(app.op_progress_rx.is_some()) + app.scheduler.jobs().len();
+    if jobs == 0 || !app.settings.confirm_quit_with_jobs {
+        return true;
+    }
+    let msg = match jobs {
+        1 => "A copy or move is still running and stops when fileZoom quits.\nQuit anyway? (y/n)".to_string(),
+        n => format!("{} copies or moves are still running or queued and stop when fileZoom quits.\nQuit anyway? (y/n)", n),
+    };
+    app.mode = Mode::Confirm { msg, on_yes: Action::Quit, selected: 1 };
+    false
+}
+
+/// Prompt the user to confirm deletion of the currently selected entry, or
+/// of the marked ones when there are any.
 ///
-/// If there is no selected entry this is a no-op.
+/// If there is no selected entry this is a no-op. Without `confirm_delete`
+/// the entry is deleted right away, unless other processes hold it open.
 fn handle_delete_prompt(app: &mut App) {
-    let panel = app.active_panel_mut();
-    if let Some(e) = panel.selected_entry() {
-        let msg = format!("Delete {}? (y/n)", e.name);
-        app.mode = Mode::Confirm { msg, on_yes: Action::DeleteSelected, selected: 0 };
+    #[cfg(feature = "scripting")]
+    let note = match crate::scripting::fire(app, crate::scripting::Hook::BeforeDelete) {
+        Some(outcome) if outcome.cancel => return,
+        Some(outcome) => outcome.message,
+        None => None,
+    };
+    delete_prompt(app);
+    // The prompt replaced the dialog `fire` opened for the script's message.
+    #[cfg(feature = "scripting")]
+    if let Some(note) = note {
+        match &mut app.mode {
+            Mode::Confirm { msg, .. } => *msg = format!("{}\n\n{}", note, msg),
+            Mode::Normal => app.mode = make_message_mode("Script", note),
+            _ => {}
+        }
+    }
+}
+
+fn delete_prompt(app: &mut App) {
+    if !app.active_panel().selections.is_empty() {
+        handle_delete_marked_prompt(app);
+        return;
+    }
+    if let Some(mode) = app.precheck_special(&Action::DeleteSelected) {
+        app.mode = mode;
+        return;
+    }
+    if let Some(e) = app.active_panel().selected_entry() {
+        let msg = format!("Delete {}? (y/n)", e.display_name());
+        let paths = collect_src_paths(app);
+        crate::runner::open_files::confirm(app, msg, Action::DeleteSelected, paths);
     }
 }
 
+/// Prompt the user to confirm deletion of the marked entries, which ends
+/// in the summary dialog (see `App::delete_marked`).
+fn handle_delete_marked_prompt(app: &mut App) {
+    let paths = collect_src_paths(app);
+    let msg = format!("Delete {} marked item(s)? (y/n)", paths.len());
+    crate::runner::open_files::confirm(app, msg, Action::DeleteMarked, paths);
+}
+
 /// Prompt the user for a destination path to copy the currently selected entry.
 fn handle_copy_prompt(app: &mut App) {
     let panel = app.active_panel_mut();
     if let Some(e) = panel.selected_entry() {
-        let prompt = format!("Copy {} to:", e.name);
+        let prompt = format!("Copy {} to:", e.display_name());
         app.mode = Mode::Input { prompt, buffer: String::new(), kind: InputKind::Copy };
     }
 }
@@ -187,7 +434,7 @@ fn handle_copy_prompt(app: &mut App) {
 fn handle_move_prompt(app: &mut App) {
     let panel = app.active_panel_mut();
     if let Some(e) = panel.selected_entry() {
-        let prompt = format!("Move {} to:", e.name);
+        let prompt = format!("Move {} to:", e.display_name());
         app.mode = Mode::Input { prompt, buffer: String::new(), kind: InputKind::Move };
     }
 }
@@ -196,11 +443,50 @@ fn handle_move_prompt(app: &mut App) {
 fn handle_rename_prompt(app: &mut App) {
     let panel = app.active_panel_mut();
     if let Some(e) = panel.entries.get(panel.selected) {
-        let prompt = format!("Rename {} to:", e.name);
+        let prompt = format!("Rename {} to:", e.display_name());
         app.mode = Mode::Input { prompt, buffer: String::new(), kind: InputKind::Rename };
     }
 }
 
+/// Ask for new access and modification times for the selection, starting
+/// from the modification time of the entry under the cursor.
+pub(crate) fn handle_change_times_prompt(app: &mut App) {
+    let marked = app.active_panel().selections.len();
+    let Some(e) = app.active_panel().selected_entry() else {
+        app.mode = make_message_mode("Change times", "No entry selected".to_string());
+        return;
+    };
+    let what = if marked > 1 { format!("{} entries", marked) } else { e.display_name().into_owned() };
+    let buffer = e.modified.map(|t| t.format("%Y-%m-%d %H:%M:%S").to_string()).unwrap_or_else(|| "now".to_string());
+    let prompt = format!("Change times of {} (now or YYYY-MM-DD [HH:MM[:SS]]):", what);
+    app.mode = Mode::Input { prompt, buffer, kind: InputKind::ChangeTimes };
+}
+
+/// Ask for a new owner and group for the selection, starting from those of
+/// the entry under the cursor, with the system users and groups offered
+/// for completion.
+pub(crate) fn handle_change_owner_prompt(app: &mut App) {
+    if cfg!(not(unix)) {
+        app.mode = make_message_mode("Change owner", "Changing owners is only supported on Unix".to_string());
+        return;
+    }
+    let marked = app.active_panel().selections.len();
+    let Some(e) = app.active_panel().selected_entry() else {
+        app.mode = make_message_mode("Change owner", "No entry selected".to_string());
+        return;
+    };
+    let what = if marked > 1 { format!("{} entries", marked) } else { e.display_name().into_owned() };
+    let name = |name: &Option<String>, id: Option<u32>| name.clone().or_else(|| id.map(|id| id.to_string())).unwrap_or_default();
+    let buffer = match (name(&e.owner, e.uid), name(&e.group, e.gid)) {
+        (user, group) if user.is_empty() && group.is_empty() => String::new(),
+        (user, group) => format!("{}:{}", user, group),
+    };
+    let prompt = format!("Change owner of {} ([-R ]user[:group]):", what);
+    app.owners.open();
+    app.owners.update(&buffer);
+    app.mode = Mode::Input { prompt, buffer, kind: InputKind::ChangeOwner };
+}
+
 /// Move selection to the last entry in the active panel (End key behaviour).
 fn handle_end_key(app: &mut App) {
     let panel = app.active_panel_mut();
@@ -211,35 +497,84 @@ fn handle_end_key(app: &mut App) {
     }
 }
 
-/// Open the context actions menu for the currently selected entry.
+/// Open the context actions menu for the currently selected entry, next to
+/// the screen cell `at` when opened by a right-click.
 ///
 /// If custom context actions are configured in settings they are used,
 /// otherwise a sensible default set is presented. If no entry is selected
 /// a short message is shown.
-fn handle_context_actions(app: &mut App) {
+pub(crate) fn handle_context_actions(app: &mut App, at: Option<(u16, u16)>) {
     let panel = app.active_panel();
     if let Some(e) = panel.selected_entry() {
-        let options = if app.settings.context_actions.is_empty() {
-            vec!["View".to_string(), "Edit".to_string(), "Permissions".to_string(), "Cancel".to_string()]
+        let mut options = if app.settings.context_actions.is_empty() {
+            let mut options = vec!["View".to_string(), "Edit".to_string(), "Permissions".to_string(), "Change times".to_string()];
+            if cfg!(unix) {
+                options.push("Change owner".to_string());
+            }
+            options.extend(["Copy path".to_string(), "Copy name".to_string(), "Cancel".to_string()]);
+            options
         } else {
             app.settings.context_actions.clone()
         };
-        app.mode = Mode::ContextMenu { title: format!("Actions: {}", e.name), options, selected: 0, path: e.path.clone() };
+        // Plugin actions go before a trailing "Cancel".
+        let end = options.len() - usize::from(options.last().is_some_and(|o| o == "Cancel"));
+        options.splice(end..end, app.plugins.action_labels());
+        app.mode = Mode::ContextMenu { title: format!("Actions: {}", e.display_name()), options, selected: 0, path: e.path.clone(), at };
     } else {
         app.mode = make_message_mode("Actions", "No entry selected".to_string());
     }
 }
 
+/// Open the selected file in the integrated editor (`Mode::Editor`).
+///
+/// Directories and files the editor refuses (binary or too large) result in
+/// a message dialog instead.
+fn handle_open_editor(app: &mut App) {
+    let Some(e) = app.active_panel().selected_entry() else {
+        app.mode = make_message_mode("Edit", "No entry selected".to_string());
+        return;
+    };
+    if e.is_dir {
+        app.mode = make_message_mode("Edit", format!("{} is a directory", e.display_name()));
+        return;
+    }
+    let path = e.path.clone();
+    match crate::app::text_editors::EditorState::open(&path) {
+        Ok(editor) => app.mode = Mode::Editor { editor: Box::new(editor) },
+        Err(err) => app.mode = make_message_mode("Error", errors::render_io_error(&err, Some(&path.display().to_string()), None, None)),
+    }
+}
+
+/// Open the user command menu (`Mode::UserMenu`) from `user_menu.toml`.
+///
+/// When the file is missing or has no entries a message explains where to
+/// create it; parse errors are shown as-is.
+fn handle_open_user_menu(app: &mut App) {
+    use crate::app::settings::user_menu;
+    match user_menu::load_user_menu() {
+        Ok(menu) if menu.entries.is_empty() => {
+            let content = format!("No user menu entries. Define [[entry]] tables (title, key, command) in {}", user_menu::user_menu_path().display());
+            app.mode = make_message_mode("User menu", content);
+        }
+        Ok(menu) => app.mode = Mode::UserMenu { entries: menu.entries, selected: 0 },
+        Err(e) => app.mode = make_message_mode("Error", format!("{:#}", e)),
+    }
+}
+
 /// Collect the source paths that should be acted on for copy/move operations.
 ///
 /// Preference order:
-/// 1. If the panel has multi-selections, return all selected entries.
+/// 1. If the panel has multi-selections, return all selected entries in
+///    listing order.
 /// 2. Otherwise return the single selected entry (if any).
 /// 3. Otherwise return an empty vector.
-fn collect_src_paths(app: &App) -> Vec<PathBuf> {
+pub(crate) fn collect_src_paths(app: &App) -> Vec<PathBuf> {
     let panel = app.active_panel();
     if !panel.selections.is_empty() {
-        panel.selections.iter().filter_map(|&idx| panel.entries.get(idx).map(|e| e.path.clone())).collect()
+        // Listing order, so items are processed (and stop on errors) predictably.
+        let mut indices: Vec<usize> = panel.selections.iter().copied().collect();
+        indices.sort_unstable();
+        indices.into_iter().filter_map(|idx| panel.entries.get(idx).map(|e| e.path.clone())).collect()
     } else if let Some(si) = app.selected_index() {
         panel.entries.get(si).map(|e| vec![e.path.clone()]).unwrap_or_default()
     } else {
@@ -281,28 +616,173 @@ fn collect_src_paths(app: &App) -> Vec<PathBuf> {
 /// when `conflict` is Some(path) and send an `OperationDecision` down the
 /// decision channel. When `done == true` the UI should stop tracking progress
 /// and show `error` if present.
+///
+/// With `confirm_cross_fs_move` set, a move reaching another filesystem is
+/// confirmed first.
 fn handle_operation_start(app: &mut App, op: Operation) -> anyhow::Result<()> {
     let src_paths = collect_src_paths(app);
     if src_paths.is_empty() { return Ok(()); }
 
     let dst_dir = match app.active { Side::Left => app.right.cwd.clone(), Side::Right => app.left.cwd.clone() };
+    if op == Operation::Move
+        && app.settings.confirm_cross_fs_move
+        && app.active_panel().vfs.is_local()
+        && src_paths.iter().any(|src| crate::fs_op::helpers::crosses_filesystems(src, &dst_dir))
+    {
+        let msg = format!("Move {} item(s) to another filesystem?\nThey are copied and then deleted. (y/n)", src_paths.len());
+        app.mode = Mode::Confirm { msg, on_yes: Action::MoveMarked(dst_dir), selected: 0 };
+        return Ok(());
+    }
+    start_marked_operation(app, op, dst_dir);
+    Ok(())
+}
+
+/// Copy or move the marked entries of the active panel, or its selected
+/// one, into `dst_dir` in the background.
+pub(crate) fn start_marked_operation(app: &mut App, op: Operation, dst_dir: PathBuf) {
+    let src_paths = collect_src_paths(app);
+    if src_paths.is_empty() { return; }
+    let vfs = app.active_panel().vfs.clone();
+    start_operation(app, op, src_paths, dst_dir, vfs);
+}
 
+/// Copy or move `src_paths`, read through `vfs`, into `dst_dir` in the
+/// background (see `handle_operation_start`). While another job is in the
+/// progress dialog, or `max_concurrent_jobs` jobs run, it is queued instead
+/// (see `runner::jobs`); with `sequential_jobs` only one job runs at a time.
+pub(crate) fn start_operation(app: &mut App, op: Operation, src_paths: Vec<PathBuf>, dst_dir: PathBuf, vfs: Arc<dyn VfsProvider>) {
+    let spec = JobSpec { op, src_paths, dst_dir, vfs };
+    let running = app.running_jobs();
+    if app.op_progress_rx.is_some() || running >= app.settings.job_slots() {
+        let label = spec.label();
+        app.scheduler.queue(spec);
+        let position = app.scheduler.queue_position(app.scheduler.jobs().len() - 1).unwrap_or(1);
+        let when = if app.settings.sequential_jobs {
+            "Jobs run one at a time, in queue order.".to_string()
+        } else {
+            format!("It starts when one of the {} running job(s) finishes.", running)
+        };
+        let content = format!("{}\nis #{} in the queue. {}\nPress B to see the jobs.", label, position, when);
+        app.mode = make_message_mode("Queued", content);
+        return;
+    }
+    let total = spec.src_paths.len();
+    app.scheduler.foreground = Some(spec.label());
+    let handle = spawn_operation(&app.settings, spec);
+    app.mode = Mode::Progress {
+        title: match op { Operation::Copy => "Copying".to_string(), Operation::Move => "Moving".to_string() },
+        processed: 0,
+        total,
+        message: "Starting".to_string(),
+        cancelled: false,
+        verify: handle.skip_verify.as_ref().map(|_| (0, 0)),
+    };
+    app.op_decision_tx = handle.decision_tx;
+    app.op_progress_rx = Some(handle.progress_rx);
+    app.op_skip_verify = handle.skip_verify;
+    app.op_cancel_flag = Some(handle.cancel);
+    app.op_meter = handle.meter;
+    app.jobs.start(op.name());
+}
+
+/// Spawn the worker running `spec` and return its channels.
+pub(crate) fn spawn_operation(settings: &Settings, spec: JobSpec) -> JobHandle {
+    let JobSpec { op, src_paths, dst_dir, vfs } = spec;
     let (tx, rx) = mpsc::channel();
     let (dec_tx, dec_rx) = mpsc::channel::<OperationDecision>();
-    app.op_decision_tx = Some(dec_tx.clone());
-    app.op_progress_rx = Some(rx);
-    let total = src_paths.len();
-    app.mode = Mode::Progress { title: match op { Operation::Copy => "Copying".to_string(), Operation::Move => "Moving".to_string() }, processed: 0, total, message: "Starting".to_string(), cancelled: false };
-
+    // Providers other than the local filesystem copy through the panel's
+    // VFS, one item at a time; only local copies can be verified.
+    let vfs = Some(vfs).filter(|v| !v.is_local());
+    let skip_verify = (op == Operation::Copy && settings.verify_after_copy && vfs.is_none()).then(|| Arc::new(AtomicBool::new(false)));
     let cancel_flag = Arc::new(AtomicBool::new(false));
-    app.op_cancel_flag = Some(cancel_flag.clone());
+    let meter = Meter::new(cancel_flag.clone());
+    // Without `confirm_overwrite` existing targets are overwritten as if
+    // "Overwrite all" had been chosen up front.
+    let overwrite = !settings.confirm_overwrite;
 
     match op {
-        Operation::Copy => spawn_copy_worker(src_paths, dst_dir, tx, dec_rx, cancel_flag),
-        Operation::Move => spawn_move_worker(src_paths, dst_dir, tx, dec_rx, cancel_flag),
+        Operation::Copy => spawn_copy_worker(src_paths, dst_dir, vfs, settings.copy_buffer_kib, skip_verify.clone(), overwrite, meter.clone(), tx, dec_rx, cancel_flag.clone()),
+        Operation::Move => spawn_move_worker(src_paths, dst_dir, settings.copy_buffer_kib, overwrite, meter.clone(), tx, dec_rx, cancel_flag.clone()),
     }
+    JobHandle { progress_rx: crate::runner::wake::forward(rx), cancel: cancel_flag, decision_tx: Some(dec_tx), skip_verify, meter: Some(meter) }
+}
 
-    Ok(())
+/// Error text for a failed copy/move item. Running out of space names the
+/// target that could not be written; other errors are shown as-is.
+fn item_error(e: &std::io::Error, target: &std::path::Path) -> String {
+    use std::io::ErrorKind;
+    match e.kind() {
+        ErrorKind::StorageFull | ErrorKind::QuotaExceeded => {
+            errors::render_io_error(e, Some(&display_path(target, MAX_DISPLAY_PATH_CHARS)), None, None)
+        }
+        _ => e.to_string(),
+    }
+}
+
+/// How often a worker waiting for the user's decision checks its cancel
+/// flag.
+const DECISION_POLL: Duration = Duration::from_millis(50);
+
+/// Wait for the user's decision on `dec_rx`. Setting `cancel` counts as
+/// `Cancel`: a job cancelled from the jobs list before its question was
+/// seen only gets the flag.
+fn wait_for_decision(dec_rx: &mpsc::Receiver<OperationDecision>, cancel: &AtomicBool) -> Result<OperationDecision, mpsc::RecvError> {
+    loop {
+        match dec_rx.recv_timeout(DECISION_POLL) {
+            Ok(decision) => return Ok(decision),
+            Err(mpsc::RecvTimeoutError::Timeout) if cancel.load(Ordering::SeqCst) => return Ok(OperationDecision::Cancel),
+            Err(mpsc::RecvTimeoutError::Timeout) => {}
+            Err(mpsc::RecvTimeoutError::Disconnected) => return Err(mpsc::RecvError),
+        }
+    }
+}
+
+/// Run `attempt` on the item `src` (going to `target`) until it succeeds.
+/// A recoverable failure (`fs_op::error::is_recoverable`) is reported on
+/// `tx` as `failed` and the user's decision read from `dec_rx` (or
+/// `cancel`, see `wait_for_decision`): Retry runs `attempt` again, Skip
+/// gives up on the item. Returns `None` when the item was done, the error
+/// it was skipped after, or the error ending the whole operation.
+#[allow(clippy::too_many_arguments)]
+fn retry_item(
+    src: &std::path::Path,
+    target: &std::path::Path,
+    processed: usize,
+    total: usize,
+    tx: &mpsc::Sender<ProgressUpdate>,
+    dec_rx: &mpsc::Receiver<OperationDecision>,
+    cancel: &AtomicBool,
+    mut attempt: impl FnMut() -> std::io::Result<()>,
+) -> Result<Option<String>, String> {
+    loop {
+        let e = match attempt() {
+            Ok(()) => return Ok(None),
+            Err(e) => e,
+        };
+        let err = item_error(&e, target);
+        if !crate::fs_op::error::is_recoverable(&e) {
+            return Err(err);
+        }
+        let _ = tx.send(ProgressUpdate { message: Some(format!("Error: {}", err)), error: Some(err.clone()), failed: Some(src.to_path_buf()), ..ProgressUpdate::new(processed, total) });
+        match wait_for_decision(dec_rx, cancel) {
+            Ok(OperationDecision::Retry) => {}
+            Ok(OperationDecision::Skip | OperationDecision::SkipAll) => return Ok(Some(err)),
+            Ok(_) => return Err("Cancelled by user".to_string()),
+            Err(_) => return Err("Decision channel closed".to_string()),
+        }
+    }
+}
+
+/// Bytes of each of `src_paths` (read through `vfs` when given), for the
+/// byte counts of the progress dialog.
+fn item_sizes(src_paths: &[PathBuf], vfs: Option<&dyn VfsProvider>, cancel: &AtomicBool) -> Vec<u64> {
+    src_paths
+        .iter()
+        .map(|p| match vfs {
+            Some(v) => v.stat(p).map(|e| if e.is_dir { 0 } else { e.size }).unwrap_or(0),
+            None => crate::fs_op::meter::total_bytes(std::slice::from_ref(p), cancel),
+        })
+        .collect()
 }
 
 /// Spawn a background thread that performs copy operations.
@@ -319,120 +799,286 @@ fn handle_operation_start(app: &mut App, op: Operation) -> anyhow::Result<()> {
 ///   conflicts are possible.
 /// - Preserves metadata after a successful batch copy via
 ///   `crate::fs_op::metadata::preserve_all_metadata`.
-fn spawn_copy_worker(src_paths: Vec<PathBuf>, dst_dir: PathBuf, tx: mpsc::Sender<ProgressUpdate>, dec_rx: mpsc::Receiver<OperationDecision>, cancel_flag: Arc<AtomicBool>) {
+/// - With a `vfs` (a non-local panel backend) every item is checked and
+///   copied through that provider instead of `std::fs`.
+/// - With a `skip_verify` flag the copies are verified afterwards (see
+///   `finish_copy`).
+#[allow(clippy::too_many_arguments)]
+fn spawn_copy_worker(src_paths: Vec<PathBuf>, dst_dir: PathBuf, vfs: Option<Arc<dyn VfsProvider>>, buffer_kib: u32, skip_verify: Option<Arc<AtomicBool>>, overwrite: bool, meter: Arc<Meter>, tx: mpsc::Sender<ProgressUpdate>, dec_rx: mpsc::Receiver<OperationDecision>, cancel_flag: Arc<AtomicBool>) {
     std::thread::spawn(move || {
+        Meter::install(&meter);
+        let started = Instant::now();
+        let mut summary = OperationSummary::default();
         let total = src_paths.len();
+        let sizes = item_sizes(&src_paths, vfs.as_deref(), &cancel_flag);
+        meter.set_total(sizes.iter().sum());
+        let exists = |p: &std::path::Path| match &vfs {
+            Some(v) => v.stat(p).is_ok(),
+            None => p.exists(),
+        };
+        let is_dir = |p: &std::path::Path| match &vfs {
+            Some(v) => v.stat(p).map(|e| e.is_dir).unwrap_or(false),
+            None => p.is_dir(),
+        };
         // Fast-path: if none of the targets already exist, use batch copy.
-        let any_conflict = src_paths.iter().any(|src| src.file_name().map(|fname| dst_dir.join(fname).exists()).unwrap_or(false));
+        // Sources whose names differ only in case may land on one file
+        // (case-insensitive destinations), so those go one by one too.
+        let any_conflict = vfs.is_some()
+            || crate::fs_op::path::names_collide(&src_paths)
+            || src_paths.iter().any(|src| src.file_name().map(|fname| dst_dir.join(fname).exists()).unwrap_or(false));
 
         if !any_conflict {
-            let mut options = FsCopyOptions::new();
+            // Size the batch buffer for the largest top-level file.
+            let largest = src_paths.iter().filter_map(|p| std::fs::metadata(p).ok()).filter(|m| m.is_file()).map(|m| m.len()).max().unwrap_or(0);
+            let buffer_size = crate::fs_op::options::resolve_buffer_size(buffer_kib, largest, &dst_dir);
+            let mut options = CopyOptions { buffer_size, ..Default::default() }.to_fs_extra_dir();
             options.copy_inside = false;
-            options.overwrite = false;
-            options.buffer_size = 64 * 1024;
-            match copy_items(&src_paths, &dst_dir, &options) {
+            let copied = crate::fs_op::meter::copy_items(&src_paths, &dst_dir, &options)
+                .and_then(crate::fs_op::test_helpers::consume_simulated_space);
+            match copied {
                 Ok(_) => {
                     for src in &src_paths {
                         if let Some(fname) = src.file_name() {
                             let target = dst_dir.join(fname);
                             let _ = crate::fs_op::metadata::preserve_all_metadata(src, &target);
+                            tracing::info!(op = "copy", path = %src.display(), dest = %target.display(), "done");
                         }
                     }
                     for (i, src) in src_paths.iter().enumerate() {
-                        let _ = tx.send(ProgressUpdate { processed: i + 1, total, message: Some(format!("Copied {}", src.display())), done: false, error: None, conflict: None });
+                        let _ = tx.send(ProgressUpdate { processed: i + 1, total, message: Some(format!("Copied {}", display_path(src, MAX_DISPLAY_PATH_CHARS))), done: false, error: None, conflict: None, conflict_src: None, failed: None, phase: ProgressPhase::Copying, summary: None });
                     }
-                    let _ = tx.send(ProgressUpdate { processed: total, total, message: Some("Completed".to_string()), done: true, error: None, conflict: None });
+                    let copied: Vec<(PathBuf, PathBuf)> = src_paths.iter().filter_map(|src| src.file_name().map(|f| (src.clone(), dst_dir.join(f)))).collect();
+                    summary.succeeded = total;
+                    summary.bytes = sizes.iter().sum();
+                    finish_copy(&copied, total, &tx, &cancel_flag, skip_verify.as_deref(), summary.ended(started));
                     return;
                 }
                 Err(e) => {
-                    let _ = tx.send(ProgressUpdate { processed: 0, total, message: Some(format!("Error: {}", e)), done: true, error: Some(format!("{}", e)), conflict: None });
-                    return;
+                    // None of the targets existed before, so remove whatever
+                    // was written: a failed batch (e.g. out of space) leaves
+                    // no partial copies behind.
+                    for src in &src_paths {
+                        if let Some(fname) = src.file_name() {
+                            let _ = crate::fs_op::remove::remove_path(dst_dir.join(fname));
+                        }
+                    }
+                    let err = item_error(&e, &dst_dir);
+                    tracing::warn!(op = "copy", items = total, dest = %dst_dir.display(), error = %err, "failed, partial copies removed");
+                    // Go through the items one by one below, so the one
+                    // failing can be retried or skipped.
+                    if !crate::fs_op::error::is_recoverable(&e) {
+                        summary.fail(dst_dir.clone(), err.clone());
+                        let _ = tx.send(ProgressUpdate { processed: 0, total, message: Some(format!("Error: {}", err)), done: true, error: Some(err), conflict: None, conflict_src: None, failed: None, phase: ProgressPhase::Copying, summary: Some(summary.ended(started)) });
+                        return;
+                    }
                 }
             }
         }
 
         // Per-item handling when conflicts may occur.
-        let mut overwrite_all = false;
+        let mut overwrite_all = overwrite;
         let mut skip_all = false;
+        let mut copied = Vec::new();
+        let mut bytes_before = 0;
         for (i, src) in src_paths.into_iter().enumerate() {
+            meter.settle(bytes_before);
+            bytes_before += sizes[i];
+            meter.wait_while_paused();
             if cancel_flag.load(Ordering::SeqCst) {
-                let _ = tx.send(ProgressUpdate { processed: i, total, message: Some("Cancelled".to_string()), done: true, error: Some("Cancelled".to_string()), conflict: None });
+                let _ = tx.send(ProgressUpdate { processed: i, total, message: Some("Cancelled".to_string()), done: true, error: Some("Cancelled".to_string()), conflict: None, conflict_src: None, failed: None, phase: ProgressPhase::Copying, summary: Some(summary.ended(started)) });
                 return;
             }
             let target = src.file_name().map(|f| dst_dir.join(f)).unwrap_or_else(|| dst_dir.clone());
+            // Symlinks are copied as links, matching `copy_recursive`.
+            let src_len = std::fs::metadata(&src).map(|m| m.len()).unwrap_or(0);
+            let buffer_size = crate::fs_op::options::resolve_buffer_size(buffer_kib, src_len, &dst_dir);
+            let mut item_opts = CopyOptions { follow_symlinks: false, buffer_size, ..Default::default() };
 
-            if target.exists() {
+            if exists(&target) {
                 if skip_all {
-                    let _ = tx.send(ProgressUpdate { processed: i, total, message: Some(format!("Skipped {}", src.display())), done: false, error: None, conflict: None });
+                    summary.skipped += 1;
+                    let _ = tx.send(ProgressUpdate { processed: i, total, message: Some(format!("Skipped {}", display_path(&src, MAX_DISPLAY_PATH_CHARS))), done: false, error: None, conflict: None, conflict_src: None, failed: None, phase: ProgressPhase::Copying, summary: None });
                     continue;
                 }
                 if !overwrite_all {
-                    let _ = tx.send(ProgressUpdate { processed: i, total, message: Some("Conflict".to_string()), done: false, error: None, conflict: Some(target.clone()) });
-                    match dec_rx.recv() {
-                        Ok(OperationDecision::Cancel) => { let _ = tx.send(ProgressUpdate { processed: i, total, message: Some("Cancelled by user".to_string()), done: true, error: Some("Cancelled by user".to_string()), conflict: None }); return; }
-                        Ok(OperationDecision::Skip) => { let _ = tx.send(ProgressUpdate { processed: i, total, message: Some(format!("Skipped {}", src.display())), done: false, error: None, conflict: None }); continue; }
-                        Ok(OperationDecision::SkipAll) => { skip_all = true; let _ = tx.send(ProgressUpdate { processed: i, total, message: Some(format!("Skipped {} (all)", src.display())), done: false, error: None, conflict: None }); continue; }
+                    let _ = tx.send(ProgressUpdate { processed: i, total, message: Some("Conflict".to_string()), done: false, error: None, conflict: Some(target.clone()), conflict_src: Some(src.clone()), failed: None, phase: ProgressPhase::Copying, summary: None });
+                    match wait_for_decision(&dec_rx, &cancel_flag) {
+                        Ok(OperationDecision::Cancel | OperationDecision::Retry) => { let _ = tx.send(ProgressUpdate { processed: i, total, message: Some("Cancelled by user".to_string()), done: true, error: Some("Cancelled by user".to_string()), conflict: None, conflict_src: None, failed: None, phase: ProgressPhase::Copying, summary: Some(summary.ended(started)) }); return; }
+                        Ok(OperationDecision::Skip) => { summary.skipped += 1; let _ = tx.send(ProgressUpdate { processed: i, total, message: Some(format!("Skipped {}", display_path(&src, MAX_DISPLAY_PATH_CHARS))), done: false, error: None, conflict: None, conflict_src: None, failed: None, phase: ProgressPhase::Copying, summary: None }); continue; }
+                        Ok(OperationDecision::SkipAll) => { skip_all = true; summary.skipped += 1; let _ = tx.send(ProgressUpdate { processed: i, total, message: Some(format!("Skipped {} (all)", display_path(&src, MAX_DISPLAY_PATH_CHARS))), done: false, error: None, conflict: None, conflict_src: None, failed: None, phase: ProgressPhase::Copying, summary: None }); continue; }
                         Ok(OperationDecision::OverwriteAll) => { overwrite_all = true; }
                         Ok(OperationDecision::Overwrite) => {}
-                        Err(_) => { let _ = tx.send(ProgressUpdate { processed: i, total, message: Some("Decision channel closed".to_string()), done: true, error: Some("Decision channel closed".to_string()), conflict: None }); return; }
+                        Err(_) => { let _ = tx.send(ProgressUpdate { processed: i, total, message: Some("Decision channel closed".to_string()), done: true, error: Some("Decision channel closed".to_string()), conflict: None, conflict_src: None, failed: None, phase: ProgressPhase::Copying, summary: Some(summary.ended(started)) }); return; }
+                    }
+                }
+                // Files are replaced atomically by the copy itself; a
+                // directory on either side is replaced wholesale.
+                match &vfs {
+                    Some(v) if is_dir(&target) || is_dir(&src) => {
+                        let _ = v.remove(&target);
+                    }
+                    Some(_) => {}
+                    None if target.is_dir() => {
+                        let _ = std::fs::remove_dir_all(&target);
                     }
+                    None if src.is_dir() => {
+                        let _ = std::fs::remove_file(&target);
+                    }
+                    None => {}
                 }
-                let _ = if target.is_dir() { std::fs::remove_dir_all(&target) } else { std::fs::remove_file(&target) };
+                item_opts.overwrite = true;
             }
 
-            let res = if src.is_dir() {
-                crate::fs_op::copy::copy_recursive(&src, &target)
-            } else if let Err(e) = crate::fs_op::helpers::ensure_parent_exists(&target) {
-                Err(e)
-            } else {
-                crate::fs_op::helpers::atomic_copy_file(&src, &target).map(|_| ())
+            let attempt = || {
+                let res = if let Some(v) = &vfs {
+                    v.copy(&src, &target)
+                } else if src.is_dir() {
+                    crate::fs_op::copy::copy_recursive_with(&src, &target, &item_opts)
+                } else if let Err(e) = crate::fs_op::helpers::ensure_parent_exists(&target) {
+                    Err(e)
+                } else {
+                    crate::fs_op::helpers::atomic_copy_file_with(&src, &target, &item_opts).map(|_| ())
+                };
+                crate::logging::log_fs_op("copy", &src, Some(&target), res)
             };
-            if let Err(e) = res { let _ = tx.send(ProgressUpdate { processed: i, total, message: Some(format!("Error: {}", e)), done: true, error: Some(format!("{}", e)), conflict: None }); return; }
-            let _ = tx.send(ProgressUpdate { processed: i + 1, total, message: Some(format!("Copied {}", src.display())), done: false, error: None, conflict: None });
+            match retry_item(&src, &target, i, total, &tx, &dec_rx, &cancel_flag, attempt) {
+                Ok(None) => {}
+                Ok(Some(err)) => { summary.fail(src.clone(), err); let _ = tx.send(ProgressUpdate { processed: i, total, message: Some(format!("Skipped {}", display_path(&src, MAX_DISPLAY_PATH_CHARS))), done: false, error: None, conflict: None, conflict_src: None, failed: None, phase: ProgressPhase::Copying, summary: None }); continue; }
+                Err(err) => { summary.fail(src.clone(), err.clone()); let _ = tx.send(ProgressUpdate { processed: i, total, message: Some(format!("Error: {}", err)), done: true, error: Some(err), conflict: None, conflict_src: None, failed: None, phase: ProgressPhase::Copying, summary: Some(summary.ended(started)) }); return; }
+            }
+            let _ = tx.send(ProgressUpdate { processed: i + 1, total, message: Some(format!("Copied {}", display_path(&src, MAX_DISPLAY_PATH_CHARS))), done: false, error: None, conflict: None, conflict_src: None, failed: None, phase: ProgressPhase::Copying, summary: None });
+            summary.succeeded += 1;
+            summary.bytes += sizes[i];
+            copied.push((src, target));
         }
-        let _ = tx.send(ProgressUpdate { processed: total, total, message: Some("Completed".to_string()), done: true, error: None, conflict: None });
+        meter.settle(bytes_before);
+        finish_copy(&copied, total, &tx, &cancel_flag, skip_verify.as_deref(), summary.ended(started));
     });
 }
 
+/// Send the final update of a copy of `total` items, carrying its
+/// `summary`. With a `skip_verify` flag the `copied` (source, target) pairs
+/// are verified first (see `verify_copies`).
+fn finish_copy(copied: &[(PathBuf, PathBuf)], total: usize, tx: &mpsc::Sender<ProgressUpdate>, cancel_flag: &AtomicBool, skip_verify: Option<&AtomicBool>, summary: OperationSummary) {
+    let (message, error, phase) = match skip_verify {
+        None => ("Completed".to_string(), None, ProgressPhase::Copying),
+        Some(skip_verify) => match verify_copies(copied, tx, cancel_flag, skip_verify) {
+            Ok(message) => (message, None, ProgressPhase::Verifying),
+            Err(err) => (err.clone(), Some(err), ProgressPhase::Verifying),
+        },
+    };
+    let _ = tx.send(ProgressUpdate { processed: total, total, message: Some(message), done: true, error, conflict: None, conflict_src: None, failed: None, phase, summary: Some(summary) });
+}
+
+/// Compare every file of the `copied` (source, target) pairs with its
+/// source, reporting a `ProgressPhase::Verifying` pass on `tx`. Setting
+/// `skip_verify` ends the pass early. Returns the closing message, or an
+/// error when the pass was cancelled or files differ.
+pub(crate) fn verify_copies(copied: &[(PathBuf, PathBuf)], tx: &mpsc::Sender<ProgressUpdate>, cancel_flag: &AtomicBool, skip_verify: &AtomicBool) -> Result<String, String> {
+    use crate::fs_op::hash::{hash_files_until, HashOptions};
+    let pairs = crate::fs_op::hash::copied_file_pairs(copied);
+    // Each source is hashed right before its copy, so a pair is done once
+    // both digests are in.
+    let paths: Vec<PathBuf> = pairs.iter().flat_map(|(src, target)| [src.clone(), target.clone()]).collect();
+    let report = |j: usize| {
+        let Some((_, target)) = pairs.get(j) else { return };
+        let message = format!("Verifying {}", display_path(target, MAX_DISPLAY_PATH_CHARS));
+        let _ = tx.send(ProgressUpdate { message: Some(message), ..ProgressUpdate::new(j, pairs.len()) }.in_phase(ProgressPhase::Verifying));
+    };
+    report(0);
+    let stop = || cancel_flag.load(Ordering::SeqCst) || skip_verify.load(Ordering::SeqCst);
+    let digests = hash_files_until(&paths, &HashOptions::default(), &stop, &mut |n| report(n / 2));
+    if cancel_flag.load(Ordering::SeqCst) {
+        return Err("Cancelled".to_string());
+    }
+    let interrupted = |r: &crate::fs_op::hash::HashResult| r.digest.as_ref().is_err_and(|e| e.kind() == std::io::ErrorKind::Interrupted);
+    let mut differing = Vec::new();
+    for (j, (pair, digests)) in pairs.iter().zip(digests.chunks(2)).enumerate() {
+        if digests.iter().any(interrupted) {
+            return Ok(format!("Verification skipped after {} of {} files", j, pairs.len()));
+        }
+        // A file that cannot be read back counts as differing.
+        if !matches!((&digests[0].digest, &digests[1].digest), (Ok(a), Ok(b)) if a == b) {
+            differing.push(display_path(&pair.1, MAX_DISPLAY_PATH_CHARS));
+        }
+    }
+    if differing.is_empty() {
+        Ok(format!("All {} copied files verified", pairs.len()))
+    } else {
+        Err(format!("Verification failed: {} of {} files differ from the source:\n{}", differing.len(), pairs.len(), differing.join("\n")))
+    }
+}
+
 /// Spawn a background thread that performs move (rename) operations.
 ///
 /// The worker semantics mirror `spawn_copy_worker` but use
 /// `atomic_rename_or_copy` to attempt a rename and fall back to copying
 /// when necessary. Progress, conflict decisions, and cancellation behave
 /// the same as for the copy worker.
-fn spawn_move_worker(src_paths: Vec<PathBuf>, dst_dir: PathBuf, tx: mpsc::Sender<ProgressUpdate>, dec_rx: mpsc::Receiver<OperationDecision>, cancel_flag: Arc<AtomicBool>) {
+#[allow(clippy::too_many_arguments)]
+fn spawn_move_worker(src_paths: Vec<PathBuf>, dst_dir: PathBuf, buffer_kib: u32, overwrite: bool, meter: Arc<Meter>, tx: mpsc::Sender<ProgressUpdate>, dec_rx: mpsc::Receiver<OperationDecision>, cancel_flag: Arc<AtomicBool>) {
     std::thread::spawn(move || {
-        let mut overwrite_all = false;
+        Meter::install(&meter);
+        let started = Instant::now();
+        let mut summary = OperationSummary::default();
+        let mut overwrite_all = overwrite;
         let mut skip_all = false;
         let total = src_paths.len();
+        let sizes = item_sizes(&src_paths, None, &cancel_flag);
+        meter.set_total(sizes.iter().sum());
+        let mut bytes_before = 0;
         for (i, src) in src_paths.into_iter().enumerate() {
-            if cancel_flag.load(Ordering::SeqCst) { let _ = tx.send(ProgressUpdate { processed: i, total, message: Some("Cancelled".to_string()), done: true, error: Some("Cancelled".to_string()), conflict: None }); return; }
+            meter.settle(bytes_before);
+            bytes_before += sizes[i];
+            meter.wait_while_paused();
+            if cancel_flag.load(Ordering::SeqCst) { let _ = tx.send(ProgressUpdate { processed: i, total, message: Some("Cancelled".to_string()), done: true, error: Some("Cancelled".to_string()), conflict: None, conflict_src: None, failed: None, phase: ProgressPhase::Copying, summary: Some(summary.ended(started)) }); return; }
             let target = src.file_name().map(|f| dst_dir.join(f)).unwrap_or_else(|| dst_dir.clone());
+            // Symlinks are copied as links, matching `copy_recursive`.
+            let src_len = std::fs::metadata(&src).map(|m| m.len()).unwrap_or(0);
+            let buffer_size = crate::fs_op::options::resolve_buffer_size(buffer_kib, src_len, &dst_dir);
+            let mut item_opts = CopyOptions { follow_symlinks: false, buffer_size, ..Default::default() };
 
             if target.exists() {
-                if skip_all { let _ = tx.send(ProgressUpdate { processed: i, total, message: Some(format!("Skipped {}", src.display())), done: false, error: None, conflict: None }); continue; }
+                if skip_all { summary.skipped += 1; let _ = tx.send(ProgressUpdate { processed: i, total, message: Some(format!("Skipped {}", display_path(&src, MAX_DISPLAY_PATH_CHARS))), done: false, error: None, conflict: None, conflict_src: None, failed: None, phase: ProgressPhase::Copying, summary: None }); continue; }
                 if !overwrite_all {
-                    let _ = tx.send(ProgressUpdate { processed: i, total, message: Some("Conflict".to_string()), done: false, error: None, conflict: Some(target.clone()) });
-                    match dec_rx.recv() {
-                        Ok(OperationDecision::Cancel) => { let _ = tx.send(ProgressUpdate { processed: i, total, message: Some("Cancelled by user".to_string()), done: true, error: Some("Cancelled by user".to_string()), conflict: None }); return; }
-                        Ok(OperationDecision::Skip) => { let _ = tx.send(ProgressUpdate { processed: i, total, message: Some(format!("Skipped {}", src.display())), done: false, error: None, conflict: None }); continue; }
-                        Ok(OperationDecision::SkipAll) => { skip_all = true; let _ = tx.send(ProgressUpdate { processed: i, total, message: Some(format!("Skipped {} (all)", src.display())), done: false, error: None, conflict: None }); continue; }
+                    let _ = tx.send(ProgressUpdate { processed: i, total, message: Some("Conflict".to_string()), done: false, error: None, conflict: Some(target.clone()), conflict_src: Some(src.clone()), failed: None, phase: ProgressPhase::Copying, summary: None });
+                    match wait_for_decision(&dec_rx, &cancel_flag) {
+                        Ok(OperationDecision::Cancel | OperationDecision::Retry) => { let _ = tx.send(ProgressUpdate { processed: i, total, message: Some("Cancelled by user".to_string()), done: true, error: Some("Cancelled by user".to_string()), conflict: None, conflict_src: None, failed: None, phase: ProgressPhase::Copying, summary: Some(summary.ended(started)) }); return; }
+                        Ok(OperationDecision::Skip) => { summary.skipped += 1; let _ = tx.send(ProgressUpdate { processed: i, total, message: Some(format!("Skipped {}", display_path(&src, MAX_DISPLAY_PATH_CHARS))), done: false, error: None, conflict: None, conflict_src: None, failed: None, phase: ProgressPhase::Copying, summary: None }); continue; }
+                        Ok(OperationDecision::SkipAll) => { skip_all = true; summary.skipped += 1; let _ = tx.send(ProgressUpdate { processed: i, total, message: Some(format!("Skipped {} (all)", display_path(&src, MAX_DISPLAY_PATH_CHARS))), done: false, error: None, conflict: None, conflict_src: None, failed: None, phase: ProgressPhase::Copying, summary: None }); continue; }
                         Ok(OperationDecision::OverwriteAll) => { overwrite_all = true; }
                         Ok(OperationDecision::Overwrite) => {}
-                        Err(_) => { let _ = tx.send(ProgressUpdate { processed: i, total, message: Some("Decision channel closed".to_string()), done: true, error: Some("Decision channel closed".to_string()), conflict: None }); return; }
+                        Err(_) => { let _ = tx.send(ProgressUpdate { processed: i, total, message: Some("Decision channel closed".to_string()), done: true, error: Some("Decision channel closed".to_string()), conflict: None, conflict_src: None, failed: None, phase: ProgressPhase::Copying, summary: Some(summary.ended(started)) }); return; }
                     }
                 }
-                let _ = if target.is_dir() { std::fs::remove_dir_all(&target) } else { std::fs::remove_file(&target) };
+                // Files are replaced atomically by the copy itself; a
+                // directory on either side is replaced wholesale.
+                if target.is_dir() {
+                    let _ = std::fs::remove_dir_all(&target);
+                } else if src.is_dir() {
+                    let _ = std::fs::remove_file(&target);
+                }
+                item_opts.overwrite = true;
             }
 
-            let res = if let Err(e) = crate::fs_op::helpers::ensure_parent_exists(&target) {
-                Err(e)
-            } else {
-                crate::fs_op::helpers::atomic_rename_or_copy(&src, &target).map(|_| ())
+            let attempt = || {
+                let res = if let Err(e) = crate::fs_op::helpers::ensure_parent_exists(&target) {
+                    Err(e)
+                } else {
+                    crate::fs_op::helpers::atomic_rename_or_copy_with(&src, &target, &item_opts).map(|_| ())
+                };
+                crate::logging::log_fs_op("move", &src, Some(&target), res)
             };
-            if let Err(e) = res { let _ = tx.send(ProgressUpdate { processed: i, total, message: Some(format!("Error: {}", e)), done: true, error: Some(format!("{}", e)), conflict: None }); return; }
-            let _ = tx.send(ProgressUpdate { processed: i + 1, total, message: Some(format!("Moved {}", src.display())), done: false, error: None, conflict: None });
+            match retry_item(&src, &target, i, total, &tx, &dec_rx, &cancel_flag, attempt) {
+                Ok(None) => {}
+                Ok(Some(err)) => { summary.fail(src.clone(), err); let _ = tx.send(ProgressUpdate { processed: i, total, message: Some(format!("Skipped {}", display_path(&src, MAX_DISPLAY_PATH_CHARS))), done: false, error: None, conflict: None, conflict_src: None, failed: None, phase: ProgressPhase::Copying, summary: None }); continue; }
+                Err(err) => { summary.fail(src.clone(), err.clone()); let _ = tx.send(ProgressUpdate { processed: i, total, message: Some(format!("Error: {}", err)), done: true, error: Some(err), conflict: None, conflict_src: None, failed: None, phase: ProgressPhase::Copying, summary: Some(summary.ended(started)) }); return; }
+            }
+            let _ = tx.send(ProgressUpdate { processed: i + 1, total, message: Some(format!("Moved {}", display_path(&src, MAX_DISPLAY_PATH_CHARS))), done: false, error: None, conflict: None, conflict_src: None, failed: None, phase: ProgressPhase::Copying, summary: None });
+            summary.succeeded += 1;
+            summary.bytes += sizes[i];
         }
-        let _ = tx.send(ProgressUpdate { processed: total, total, message: Some("Completed".to_string()), done: true, error: None, conflict: None });
+        meter.settle(bytes_before);
+        let _ = tx.send(ProgressUpdate { processed: total, total, message: Some("Completed".to_string()), done: true, error: None, conflict: None, conflict_src: None, failed: None, phase: ProgressPhase::Copying, summary: Some(summary.ended(started)) });
     });
 }
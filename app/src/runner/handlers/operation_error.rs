@@ -0,0 +1,73 @@
+//! Key handler for the error dialog of a recoverable failure
+//! (`Mode::OperationError`).
+//!
+//! For a running copy or move the decision goes back to the worker, which
+//! waits on its decision channel: Retry tries the item again, Skip goes on
+//! with the next one and Abort ends the job. For an action run from a
+//! confirmation, Retry runs the action again, and "Retry as root" runs it
+//! through the privileged helper (`fs_op::elevated`).
+
+use crate::app::{App, Mode};
+use crate::app::settings::keybinds;
+use crate::input::KeyCode;
+use crate::runner::progress::OperationDecision;
+use crate::ui::widgets::dialog::operation_error_buttons;
+
+/// Handle key events in `Mode::OperationError`: Left/Right pick a button,
+/// Enter or a button's mnemonic presses it, Esc aborts.
+pub fn handle_operation_error(app: &mut App, code: KeyCode) -> anyhow::Result<bool> {
+    let Mode::OperationError { retry, elevated, selected, .. } = &mut app.mode else {
+        return Ok(false);
+    };
+    let buttons = operation_error_buttons(retry.is_none(), elevated.is_some());
+    let abort = buttons.len() - 1;
+    let pressed = match code {
+        KeyCode::Char(c) => crate::ui::menu_model::mnemonic_match(buttons, c),
+        _ if keybinds::is_enter(&code) => Some(*selected),
+        _ if keybinds::is_esc(&code) => Some(abort),
+        _ => None,
+    };
+    if keybinds::is_left(&code) {
+        *selected = selected.saturating_sub(1);
+    } else if keybinds::is_right(&code) {
+        *selected = (*selected + 1).min(abort);
+    }
+    let Some(pressed) = pressed else {
+        return Ok(false);
+    };
+
+    match (retry.take(), elevated.take()) {
+        (Some(_), Some(op)) if pressed == 1 => {
+            app.mode = Mode::Normal;
+            match crate::fs_op::elevated::run(&op) {
+                Ok(()) => {
+                    let _ = app.refresh();
+                }
+                Err(e) => {
+                    app.mode = Mode::Message {
+                        title: "Error".to_string(),
+                        content: e,
+                        buttons: vec!["Show log".to_string(), "OK".to_string()],
+                        selected: 1,
+                        actions: Some(vec![crate::app::Action::ShowLog]),
+                    };
+                }
+            }
+        }
+        (Some(action), _) => {
+            app.mode = Mode::Normal;
+            if pressed == 0 {
+                super::confirm::execute_action(app, action);
+            }
+        }
+        (None, _) => {
+            let (decision, message) = match pressed {
+                0 => (OperationDecision::Retry, "Retrying"),
+                p if p == abort => (OperationDecision::Cancel, "Cancelling"),
+                _ => (OperationDecision::Skip, "Skipping"),
+            };
+            super::conflict::send_decision_and_enter_progress(app, decision, message, decision == OperationDecision::Cancel);
+        }
+    }
+    Ok(false)
+}
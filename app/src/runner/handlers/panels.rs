@@ -0,0 +1,45 @@
+//! Swap the panels (Ctrl+U), show the active panel's directory in the
+//! other one (Alt+I) and the other panel's directory in the active one
+//! (Alt+Shift+I).
+//!
+//! Like the clipboard keys these are chords without a plain key, so the
+//! event loop hands them to `handle_panels_key` before normal-mode
+//! dispatch.
+
+use crate::app::settings::keybinds;
+use crate::app::{App, Mode, Side};
+use crate::input::Key;
+
+/// Run the panel action bound to `key`. Returns whether `key` was one.
+pub fn handle_panels_key(app: &mut App, key: &Key) -> bool {
+    if !matches!(app.mode, Mode::Normal) || app.command_line.is_some() {
+        return false;
+    }
+    let other = match app.active {
+        Side::Left => Side::Right,
+        Side::Right => Side::Left,
+    };
+    let result = if keybinds::is_swap_panels(key) {
+        // A listing still running belongs to its side, not to its panel.
+        if app.left.loading.is_none() && app.right.loading.is_none() {
+            app.swap_panels();
+        }
+        Ok(())
+    } else if keybinds::is_other_panel_here(key) {
+        app.show_other_dir_in(other)
+    } else if keybinds::is_this_panel_there(key) {
+        app.show_other_dir_in(app.active)
+    } else {
+        return false;
+    };
+    if let Err(e) = result {
+        app.mode = Mode::Message {
+            title: "Error".to_string(),
+            content: crate::errors::render_fsop_error(&e, None, None, None),
+            buttons: vec!["OK".to_string()],
+            selected: 0,
+            actions: None,
+        };
+    }
+    true
+}
@@ -5,11 +5,14 @@ use std::sync::atomic::Ordering;
 
 /// Handle input while the UI is in `Progress` mode.
 ///
-/// Currently this only handles the Escape key which signals cancellation
-/// of the in-flight background operation. When `Esc` is received the
-/// optional `op_cancel_flag` is consumed (taken) and set to `true` so
-/// background workers may observe the request to stop. The UI `Mode` is
-/// updated in-place to reflect a cancelling state.
+/// The Escape key signals cancellation of the in-flight background
+/// operation. When `Esc` is received the optional `op_cancel_flag` is
+/// consumed (taken) and set to `true` so background workers may observe the
+/// request to stop. The UI `Mode` is updated in-place to reflect a
+/// cancelling state. `s` likewise sets `op_skip_verify` so a verified copy
+/// finishes without (the rest of) its verification pass, `p` pauses or
+/// resumes the copy through `op_meter` and `b` sends the job to the
+/// background (see `runner::jobs`).
 ///
 /// Returns `Ok(false)` to indicate no immediate screen redraw request is
 /// required by the caller.
@@ -24,6 +27,16 @@ pub fn handle_progress(app: &mut App, code: KeyCode) -> anyhow::Result<bool> {
             *message = "Cancelling...".to_string();
             *cancelled = true;
         }
+    } else if let KeyCode::Char('b') = code {
+        crate::runner::jobs::detach(app);
+    } else if let (KeyCode::Char('p'), Some(meter)) = (code, &app.op_meter) {
+        meter.set_paused(!meter.paused());
+    } else if let (KeyCode::Char('s'), Some(flag)) = (code, app.op_skip_verify.take()) {
+        flag.store(true, Ordering::SeqCst);
+        if let Mode::Progress { message, verify, .. } = &mut app.mode {
+            *message = "Skipping verification...".to_string();
+            *verify = None;
+        }
     }
 
     Ok(false)
@@ -57,6 +70,9 @@ mod tests {
             settings: crate::app::settings::write_settings::Settings::default(),
             op_progress_rx: None,
             op_cancel_flag: None,
+            op_skip_verify: None,
+            op_meter: None,
+            scheduler: Default::default(),
             op_decision_tx: None,
             last_mouse_click_time: None,
             last_mouse_click_pos: None,
@@ -64,6 +80,30 @@ mod tests {
             drag_start: None,
             drag_current: None,
             drag_button: None,
+            scroll_drag: None,
+            dirty: false,
+            plugins: Default::default(),
+            diagnostics: Default::default(),
+            jobs: Default::default(),
+            pending_keys: Default::default(),
+            path_jump: Default::default(),
+            frecency: Default::default(),
+            tags: Default::default(),
+            searches: Default::default(),
+            clipboard: None,
+            other_panel: Default::default(),
+            info: Default::default(),
+            tree: Default::default(),
+            usage: None,
+            shell: None,
+            compare: None,
+            open_files: None,
+            chooser: None,
+            link: None,
+            templates: Default::default(),
+            owners: Default::default(),
+            preview_focused: Default::default(),
+            layout: Default::default(),
         };
 
         // Prepare a cancel flag shared with the handler.
@@ -77,6 +117,7 @@ mod tests {
             total: 10,
             message: "Working".into(),
             cancelled: false,
+            verify: None,
         };
 
         // Invoke handler with Escape.
@@ -116,6 +157,9 @@ mod tests {
             settings: crate::app::settings::write_settings::Settings::default(),
             op_progress_rx: None,
             op_cancel_flag: None,
+            op_skip_verify: None,
+            op_meter: None,
+            scheduler: Default::default(),
             op_decision_tx: None,
             last_mouse_click_time: None,
             last_mouse_click_pos: None,
@@ -123,6 +167,30 @@ mod tests {
             drag_start: None,
             drag_current: None,
             drag_button: None,
+            scroll_drag: None,
+            dirty: false,
+            plugins: Default::default(),
+            diagnostics: Default::default(),
+            jobs: Default::default(),
+            pending_keys: Default::default(),
+            path_jump: Default::default(),
+            frecency: Default::default(),
+            tags: Default::default(),
+            searches: Default::default(),
+            clipboard: None,
+            other_panel: Default::default(),
+            info: Default::default(),
+            tree: Default::default(),
+            usage: None,
+            shell: None,
+            compare: None,
+            open_files: None,
+            chooser: None,
+            link: None,
+            templates: Default::default(),
+            owners: Default::default(),
+            preview_focused: Default::default(),
+            layout: Default::default(),
         };
 
         // Prepare a cancel flag and set it, but keep it attached to app.
@@ -136,6 +204,7 @@ mod tests {
             total: 20,
             message: "Working".into(),
             cancelled: false,
+            verify: None,
         };
 
         // Invoke handler with a non-Esc key (Char)
@@ -176,6 +245,9 @@ mod tests {
             settings: crate::app::settings::write_settings::Settings::default(),
             op_progress_rx: None,
             op_cancel_flag: None,
+            op_skip_verify: None,
+            op_meter: None,
+            scheduler: Default::default(),
             op_decision_tx: None,
             last_mouse_click_time: None,
             last_mouse_click_pos: None,
@@ -183,6 +255,30 @@ mod tests {
             drag_start: None,
             drag_current: None,
             drag_button: None,
+            scroll_drag: None,
+            dirty: false,
+            plugins: Default::default(),
+            diagnostics: Default::default(),
+            jobs: Default::default(),
+            pending_keys: Default::default(),
+            path_jump: Default::default(),
+            frecency: Default::default(),
+            tags: Default::default(),
+            searches: Default::default(),
+            clipboard: None,
+            other_panel: Default::default(),
+            info: Default::default(),
+            tree: Default::default(),
+            usage: None,
+            shell: None,
+            compare: None,
+            open_files: None,
+            chooser: None,
+            link: None,
+            templates: Default::default(),
+            owners: Default::default(),
+            preview_focused: Default::default(),
+            layout: Default::default(),
         };
 
         // Put the app into Progress mode with initial values and no flag.
@@ -192,6 +288,7 @@ mod tests {
             total: 30,
             message: "Working".into(),
             cancelled: false,
+            verify: None,
         };
 
         // Invoke handler with a non-Esc key (Enter)
@@ -0,0 +1,54 @@
+//! Key handler for the recent and saved searches (`Mode::SearchPicker`).
+//!
+//! Opened with `:searches`. Up/Down move through the saved searches and
+//! then the recent ones (see `app::core::searches`), Enter runs the
+//! selected one from the active panel's directory, `d` or Delete forgets
+//! it, Esc or `q` close the picker.
+
+use crate::app::settings::keybinds;
+use crate::app::{App, Mode};
+use crate::input::KeyCode;
+
+/// Open the picker on the most recently saved or run search.
+pub fn open_search_picker(app: &mut App) {
+    app.mode = Mode::SearchPicker { selected: 0 };
+}
+
+/// Handle keyboard events while the app is in `Mode::SearchPicker`.
+pub fn handle_search_picker(app: &mut App, code: KeyCode) -> anyhow::Result<bool> {
+    let Mode::SearchPicker { selected } = &mut app.mode else {
+        return Ok(false);
+    };
+    let count = app.searches.entries().len();
+    if keybinds::is_up(&code) {
+        *selected = selected.saturating_sub(1);
+    } else if keybinds::is_down(&code) {
+        if *selected + 1 < count {
+            *selected += 1;
+        }
+    } else if keybinds::is_enter(&code) {
+        let Some(options) = app.searches.entries().get(*selected).map(|(_, o)| (*o).clone()) else {
+            return Ok(false);
+        };
+        app.mode = Mode::Normal;
+        if let Err(e) = app.run_search(options) {
+            app.mode = Mode::Message {
+                title: "Error".to_string(),
+                content: crate::errors::render_io_error(&e, None, None, None),
+                buttons: vec!["OK".to_string()],
+                selected: 0,
+                actions: None,
+            };
+        }
+    } else if keybinds::is_char(&code, 'd') || code == KeyCode::Delete {
+        let index = *selected;
+        *selected = index.min(count.saturating_sub(2));
+        app.searches.remove_entry(index);
+        if let Err(e) = app.searches.save() {
+            tracing::warn!("searches not saved: {:#}", e);
+        }
+    } else if keybinds::is_esc(&code) || keybinds::is_char(&code, 'q') {
+        app.mode = Mode::Normal;
+    }
+    Ok(false)
+}
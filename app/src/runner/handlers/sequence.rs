@@ -0,0 +1,85 @@
+//! Multi-key sequences (`g g`, `g h`, `Space r`) in normal mode.
+//!
+//! A key that starts a sequence is held in `App::pending_keys` until the
+//! sequence is finished, broken or times out. A broken sequence lets its
+//! first key do what it is bound to on its own and then handles the new
+//! key as usual; Esc just drops the typed keys. The timeout comes from
+//! `Settings::key_sequence_timeout_ms` and is checked by the event loop
+//! through `poll_sequence_timeout`.
+
+use std::time::{Duration, Instant};
+
+use crate::app::settings::keymap::KeyChord;
+use crate::app::settings::runtime_keybinds::{self, Keybinds, SequenceMatch};
+use crate::app::{App, Mode};
+use crate::input::KeyCode;
+use crate::runner::handlers::normal;
+
+/// Feed a normal-mode key to the sequence layer. Returns `None` when the
+/// key is not part of a sequence and should be dispatched as usual,
+/// otherwise `Some(exit)` with the result of the action it completed.
+pub fn handle_sequence_key(app: &mut App, code: KeyCode, page_size: usize) -> anyhow::Result<Option<bool>> {
+    let key = KeyChord::plain(code);
+    let mut keys = app.pending_keys.keys.clone();
+    keys.push(key);
+    let matched = runtime_keybinds::get().sequence_match(&keys);
+    match matched {
+        SequenceMatch::Prefix => {
+            app.pending_keys.push(key);
+            Ok(Some(false))
+        }
+        SequenceMatch::Complete(action) => {
+            app.pending_keys.take();
+            run_action(app, &action, page_size).map(Some)
+        }
+        SequenceMatch::None if app.pending_keys.is_empty() => Ok(None),
+        SequenceMatch::None => {
+            let typed = app.pending_keys.take();
+            if code == KeyCode::Esc {
+                return Ok(Some(false));
+            }
+            if replay(app, &typed, page_size)? {
+                return Ok(Some(true));
+            }
+            handle_sequence_key(app, code, page_size)
+        }
+    }
+}
+
+/// Give up on a sequence whose last key was typed longer ago than the
+/// configured timeout, letting its first key act on its own (vim's `g`
+/// moves to the top when no second key follows). Returns `Ok(true)` when
+/// that action asks to exit.
+pub fn poll_sequence_timeout(app: &mut App, page_size: usize) -> anyhow::Result<bool> {
+    // A dialog opened by the mouse ends the sequence.
+    if !matches!(app.mode, Mode::Normal) {
+        app.pending_keys.take();
+        return Ok(false);
+    }
+    let timeout = Duration::from_millis(app.settings.key_sequence_timeout_ms);
+    if !app.pending_keys.expired(Instant::now(), timeout) {
+        return Ok(false);
+    }
+    let typed = app.pending_keys.take();
+    replay(app, &typed, page_size)
+}
+
+/// Run the single-key binding of a lone typed key, if it has one.
+fn replay(app: &mut App, typed: &[KeyChord], page_size: usize) -> anyhow::Result<bool> {
+    match typed {
+        [key] => normal::dispatch_key(app, key.code, page_size),
+        _ => Ok(false),
+    }
+}
+
+/// Run `action` through its default key, like a single key bound to it.
+fn run_action(app: &mut App, action: &str, page_size: usize) -> anyhow::Result<bool> {
+    if action == "go_home" {
+        normal::handle_go_home(app);
+        return Ok(false);
+    }
+    match Keybinds::default_key(action) {
+        Some(code) => normal::run_default_key(app, code, page_size),
+        None => Ok(false),
+    }
+}
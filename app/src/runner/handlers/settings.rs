@@ -1,8 +1,23 @@
 use crate::app::Mode;
 use crate::input::KeyCode;
 use crate::app::settings::keybinds;
+use crate::app::settings::KeybindPreset;
 use crate::app::App;
 
+/// Number of fields in the Settings dialog, above its buttons.
+pub const SETTINGS_FIELDS: usize = 13;
+
+/// Number of selectable rows in the Settings dialog (fields plus the
+/// Save, Restore and Cancel buttons).
+pub const SETTINGS_ROWS: usize = SETTINGS_FIELDS + SETTINGS_BUTTONS.len();
+
+/// Height of the Settings dialog: the fields, a blank row and the buttons
+/// inside its border.
+pub const SETTINGS_HEIGHT: u16 = SETTINGS_FIELDS as u16 + 4;
+
+/// Buttons below the Settings fields, in row order.
+pub const SETTINGS_BUTTONS: [&str; 3] = ["Save", "Restore", "Cancel"];
+
 /// Adjust the double-click timeout (milliseconds) by `step` and clamp to
 /// the supported range [100, 5000]. The `step` may be negative.
 fn adjust_double_click_ms(value: &mut u64, step: i64) {
@@ -10,13 +25,61 @@ fn adjust_double_click_ms(value: &mut u64, step: i64) {
     *value = new.clamp(100, 5000) as u64;
 }
 
+/// Copy buffer sizes (KiB) the Settings dialog cycles through; `0` sizes
+/// the buffer per file.
+pub const COPY_BUFFER_CHOICES: [u32; 5] = [0, 64, 256, 1024, 4096];
+
+/// Label of the `copy_buffer_kib` setting in the Settings dialog.
+pub fn copy_buffer_label(kib: u32) -> String {
+    match kib {
+        0 => "Auto".to_string(),
+        k if k >= 1024 && k % 1024 == 0 => format!("{}M", k / 1024),
+        k => format!("{}K", k),
+    }
+}
+
+/// `color_depth` values the Settings dialog cycles through.
+pub const COLOR_DEPTH_CHOICES: [&str; 4] = ["auto", "truecolor", "256", "16"];
+
+/// Highest `max_concurrent_jobs` the Settings dialog offers.
+const MAX_JOBS_LIMIT: usize = 16;
+
+/// Restore the newest settings backup into `app` and return the message
+/// dialog reporting the outcome.
+pub(crate) fn restore_previous(app: &mut App) -> Mode {
+    let (title, content) = match crate::app::settings::restore_previous_settings() {
+        Ok(Some(settings)) => {
+            app.settings = settings;
+            app.file_stats_visible = app.settings.file_stats_visible;
+            crate::ui::colors::set_theme(app.settings.theme.as_str());
+            crate::ui::colors::set_color_depth(crate::ui::colors::ColorDepth::resolve(&app.settings.color_depth));
+            keybinds::apply_preset(&app.settings.keybinding_preset);
+            ("Settings Restored", "Previous configuration restored".to_string())
+        }
+        Ok(None) => ("Settings", "No configuration backup to restore".to_string()),
+        Err(e) => ("Error", format!("Failed to restore settings: {}", e)),
+    };
+    Mode::Message {
+        title: title.to_string(),
+        content,
+        buttons: vec!["OK".to_string()],
+        selected: 0,
+        actions: None,
+    }
+}
+
 /// Handle keys while the Settings modal is active.
 ///
 /// Returns `Ok(false)` to match the handler convention used elsewhere in
 /// the application (non-consuming by default). The function mutates
 /// `app.mode` and `app.settings` in-place based on key input.
 pub fn handle_settings(app: &mut App, code: KeyCode) -> anyhow::Result<bool> {
-    // Selected indices: 0 = mouse_enabled, 1 = double_click_ms, 2 = Show CLI listing, 3 = Save, 4 = Cancel
+    // Selected indices: 0 = mouse_enabled, 1 = double_click_ms, 2 = Show CLI listing,
+    // 3 = keybinding preset, 4 = restore session, 5..=8 = confirm delete,
+    // overwrite, cross-filesystem move and quit with jobs, 9 = sequential
+    // jobs, 10 = max concurrent jobs, 11 = copy buffer size, 12 = colour
+    // depth, then the Save, Restore previous configuration and Cancel
+    // buttons
     if let Mode::Settings { selected } = &mut app.mode {
         // Escape always exits settings.
         if keybinds::is_esc(&code) {
@@ -24,31 +87,52 @@ pub fn handle_settings(app: &mut App, code: KeyCode) -> anyhow::Result<bool> {
             return Ok(false);
         }
 
-        // Navigation: up/down wrap within 0..=4
+        // Tab switches to the Keys page.
+        if keybinds::is_tab(&code) {
+            crate::runner::handlers::keymap_editor::open_keymap_editor(app);
+            return Ok(false);
+        }
+
+        // Navigation: up/down wrap within 0..SETTINGS_ROWS
         if keybinds::is_up(&code) {
-            *selected = (*selected + 5 - 1) % 5; // safe wrap subtract
+            *selected = (*selected + SETTINGS_ROWS - 1) % SETTINGS_ROWS; // safe wrap subtract
             return Ok(false);
         }
 
         if keybinds::is_down(&code) {
-            *selected = (*selected + 1) % 5;
+            *selected = (*selected + 1) % SETTINGS_ROWS;
             return Ok(false);
         }
 
-        // Left/Right/+/ - only affect fields when selected == 1
-        if *selected == 1 {
-            if keybinds::is_left(&code) || keybinds::is_char(&code, '-') {
-                adjust_double_click_ms(&mut app.settings.mouse_double_click_ms, -50);
-                return Ok(false);
-            }
-            if keybinds::is_right(&code) || keybinds::is_char(&code, '+') {
-                adjust_double_click_ms(&mut app.settings.mouse_double_click_ms, 50);
-                return Ok(false);
-            }
+        // Left/Right/+/ - only affect the numeric fields (rows 1 and 10)
+        let step = if keybinds::is_left(&code) || keybinds::is_char(&code, '-') {
+            -1
+        } else if keybinds::is_right(&code) || keybinds::is_char(&code, '+') {
+            1
+        } else {
+            0
+        };
+        if step != 0 && *selected == 1 {
+            adjust_double_click_ms(&mut app.settings.mouse_double_click_ms, 50 * step);
+            return Ok(false);
+        }
+        if step != 0 && *selected == 10 {
+            let jobs = app.settings.max_concurrent_jobs.saturating_add_signed(step as isize);
+            app.settings.max_concurrent_jobs = jobs.clamp(1, MAX_JOBS_LIMIT);
+            return Ok(false);
+        }
+
+        // A button's mnemonic selects and presses it.
+        let mnemonic = match code {
+            KeyCode::Char(c) => crate::ui::menu_model::mnemonic_match(&SETTINGS_BUTTONS, c),
+            _ => None,
+        };
+        if let Some(i) = mnemonic {
+            *selected = SETTINGS_FIELDS + i;
         }
 
         // Activate / toggle / enter
-        if keybinds::is_enter(&code) || keybinds::is_toggle_selection(&code) {
+        if keybinds::is_enter(&code) || keybinds::is_toggle_selection(&code) || mnemonic.is_some() {
                 match *selected {
                 0 => {
                     app.settings.mouse_enabled = !app.settings.mouse_enabled;
@@ -60,6 +144,46 @@ pub fn handle_settings(app: &mut App, code: KeyCode) -> anyhow::Result<bool> {
                     app.settings.show_cli_listing = !app.settings.show_cli_listing;
                 }
                 3 => {
+                    // Cycle the preset and apply it right away.
+                    let next = KeybindPreset::from_name(&app.settings.keybinding_preset).unwrap_or_default().next();
+                    app.settings.keybinding_preset = keybinds::apply_preset(next.name()).name().to_string();
+                }
+                4 => {
+                    app.settings.restore_session = !app.settings.restore_session;
+                }
+                5 => {
+                    app.settings.confirm_delete = !app.settings.confirm_delete;
+                }
+                6 => {
+                    app.settings.confirm_overwrite = !app.settings.confirm_overwrite;
+                }
+                7 => {
+                    app.settings.confirm_cross_fs_move = !app.settings.confirm_cross_fs_move;
+                }
+                8 => {
+                    app.settings.confirm_quit_with_jobs = !app.settings.confirm_quit_with_jobs;
+                }
+                9 => {
+                    app.settings.sequential_jobs = !app.settings.sequential_jobs;
+                }
+                10 => {
+                    // Numeric field: Enter does nothing
+                }
+                11 => {
+                    // Cycle the buffer size; a size set in the file that
+                    // is not offered goes back to Auto.
+                    let current = COPY_BUFFER_CHOICES.iter().position(|&k| k == app.settings.copy_buffer_kib);
+                    let next = current.map_or(0, |i| (i + 1) % COPY_BUFFER_CHOICES.len());
+                    app.settings.copy_buffer_kib = COPY_BUFFER_CHOICES[next];
+                }
+                12 => {
+                    // Cycle the colour depth and redraw with it right away.
+                    let current = COLOR_DEPTH_CHOICES.iter().position(|d| d.eq_ignore_ascii_case(app.settings.color_depth.trim()));
+                    let next = current.map_or(0, |i| (i + 1) % COLOR_DEPTH_CHOICES.len());
+                    app.settings.color_depth = COLOR_DEPTH_CHOICES[next].to_string();
+                    crate::ui::colors::set_color_depth(crate::ui::colors::ColorDepth::resolve(&app.settings.color_depth));
+                }
+                n if n == SETTINGS_FIELDS => {
                     // Save settings and show a message modal on success/failure
                     match crate::app::settings::save_settings(&app.settings) {
                         Ok(_) => {
@@ -82,7 +206,10 @@ pub fn handle_settings(app: &mut App, code: KeyCode) -> anyhow::Result<bool> {
                         }
                     }
                 }
-                4 => {
+                n if n == SETTINGS_FIELDS + 1 => {
+                    app.mode = restore_previous(app);
+                }
+                n if n == SETTINGS_FIELDS + 2 => {
                     app.mode = Mode::Normal;
                 }
                 _ => {}
@@ -0,0 +1,31 @@
+//! Key handler for the summary of an operation that ended
+//! (`Mode::Summary`).
+//!
+//! Up/Down, PageUp/PageDown and Home/End scroll the failures, `l` opens
+//! the log they were written to, Enter, Esc or `q` close the dialog.
+
+use crate::app::{App, Mode};
+use crate::input::KeyCode;
+use crate::ui::widgets::dialog::SUMMARY_FAILURE_ROWS;
+
+/// Handle keyboard events while the app is in `Mode::Summary`.
+pub fn handle_summary(app: &mut App, code: KeyCode) -> anyhow::Result<bool> {
+    let Mode::Summary { summary, scroll, .. } = &mut app.mode else {
+        return Ok(false);
+    };
+    // Scrolled all the way down, the last page of failures is shown.
+    let page = SUMMARY_FAILURE_ROWS;
+    let max = summary.failed().saturating_sub(page);
+    match code {
+        KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q') => app.mode = Mode::Normal,
+        KeyCode::Char('l') => crate::runner::handlers::log_view::open_log_view(app),
+        KeyCode::Up => *scroll = scroll.saturating_sub(1),
+        KeyCode::Down => *scroll = (*scroll + 1).min(max),
+        KeyCode::PageUp => *scroll = scroll.saturating_sub(page),
+        KeyCode::PageDown => *scroll = (*scroll + page).min(max),
+        KeyCode::Home => *scroll = 0,
+        KeyCode::End => *scroll = max,
+        _ => {}
+    }
+    Ok(false)
+}
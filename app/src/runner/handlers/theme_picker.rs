@@ -0,0 +1,59 @@
+//! Key handler for the theme picker (`Mode::ThemePicker`).
+//!
+//! Up/Down move through the themes and preview the selected one, Enter
+//! keeps it and saves its name in the settings, Esc goes back to the theme
+//! that was active when the picker opened.
+
+use crate::app::settings::keybinds;
+use crate::app::{App, Mode};
+use crate::input::KeyCode;
+
+/// Open the theme picker on the current theme.
+pub fn open_theme_picker(app: &mut App) {
+    let themes = crate::ui::themes::theme_names();
+    let original = app.settings.theme.clone();
+    let selected = themes.iter().position(|t| *t == original).unwrap_or(0);
+    app.mode = Mode::ThemePicker { themes, selected, original, error: None };
+}
+
+/// Handle keyboard events while the app is in `Mode::ThemePicker`.
+pub fn handle_theme_picker(app: &mut App, code: KeyCode) -> anyhow::Result<bool> {
+    let Mode::ThemePicker { themes, selected, original, error } = &mut app.mode else {
+        return Ok(false);
+    };
+    if keybinds::is_esc(&code) {
+        crate::ui::colors::set_theme(original);
+        app.mode = Mode::Normal;
+        return Ok(false);
+    }
+    let moved = if keybinds::is_up(&code) && *selected > 0 {
+        *selected -= 1;
+        true
+    } else if keybinds::is_down(&code) && *selected + 1 < themes.len() {
+        *selected += 1;
+        true
+    } else {
+        false
+    };
+    let Some(name) = themes.get(*selected).cloned() else { return Ok(false) };
+    if moved {
+        *error = crate::ui::colors::apply_theme(&name).err().map(|e| format!("{:#}", e));
+    } else if keybinds::is_enter(&code) {
+        if let Err(e) = crate::ui::colors::apply_theme(&name) {
+            *error = Some(format!("{:#}", e));
+            return Ok(false);
+        }
+        app.settings.theme = name;
+        app.mode = match crate::app::settings::save_settings(&app.settings) {
+            Ok(()) => Mode::Normal,
+            Err(e) => Mode::Message {
+                title: "Error".to_string(),
+                content: format!("Theme applied but the settings were not saved: {:#}", e),
+                buttons: vec!["OK".to_string()],
+                selected: 0,
+                actions: None,
+            },
+        };
+    }
+    Ok(false)
+}
@@ -0,0 +1,61 @@
+//! Keys of the directory tree sidebar while it has the focus.
+//!
+//! Up/Down and PgUp/PgDn move through the nodes, Right expands a node (or
+//! steps into it), Left collapses it (or steps to its parent), Enter sends
+//! the active panel to the selected directory, Tab hands the focus back to
+//! the panel and Esc hides the tree.
+
+use crate::app::{App, Mode};
+use crate::input::KeyCode;
+
+/// Handle `code` for the focused tree. Returns whether the tree used it;
+/// other keys fall through to the panel.
+pub fn handle_tree_key(app: &mut App, code: KeyCode, page_size: usize) -> bool {
+    let show_hidden = app.settings.show_hidden;
+    let page = page_size.max(1) as isize;
+    match code {
+        KeyCode::Down => app.tree.move_selection(1),
+        KeyCode::Up => app.tree.move_selection(-1),
+        KeyCode::PageDown => app.tree.move_selection(page),
+        KeyCode::PageUp => app.tree.move_selection(-page),
+        KeyCode::Home => app.tree.selected = 0,
+        KeyCode::End => app.tree.move_selection(isize::MAX),
+        KeyCode::Right => app.tree.expand_selected(show_hidden),
+        KeyCode::Left => app.tree.collapse_selected(),
+        KeyCode::Enter => open_selected(app),
+        KeyCode::Tab => app.tree.focused = false,
+        KeyCode::Esc => {
+            app.tree.visible = false;
+            app.tree.focused = false;
+        }
+        _ => return false,
+    }
+    app.tree.scroll_to_selection(page_size);
+    true
+}
+
+/// Send the active panel to the selected directory.
+pub(crate) fn open_selected(app: &mut App) {
+    let Some(dir) = app.tree.selected_path().map(|p| p.to_path_buf()) else {
+        return;
+    };
+    if !app.active_panel().vfs.is_local() {
+        app.mode = Mode::Message {
+            title: "Tree".to_string(),
+            content: format!("The tree shows local directories; this panel is on {}", app.active_panel().vfs.name()),
+            buttons: vec!["OK".to_string()],
+            selected: 0,
+            actions: None,
+        };
+        return;
+    }
+    if let Err(e) = app.go_to(dir) {
+        app.mode = Mode::Message {
+            title: "Error".to_string(),
+            content: crate::errors::render_fsop_error(&e, None, None, None),
+            buttons: vec!["OK".to_string()],
+            selected: 0,
+            actions: None,
+        };
+    }
+}
@@ -0,0 +1,36 @@
+//! Key handler for the user-defined command menu (`Mode::UserMenu`).
+//!
+//! Up/Down move the selection and Enter runs it; an entry's own `key` runs
+//! it directly. Esc or `q` (unless bound to an entry) close the menu.
+
+use crate::app::{App, Mode};
+use crate::app::settings::keybinds;
+use crate::input::KeyCode;
+
+/// Handle keyboard events while the app is in `Mode::UserMenu`.
+///
+/// Returns `Ok(false)` by convention (no special redraw request).
+pub fn handle_user_menu(app: &mut App, code: KeyCode) -> anyhow::Result<bool> {
+    let Mode::UserMenu { entries, selected } = &mut app.mode else {
+        return Ok(false);
+    };
+    let mut run = None;
+    if let Some(e) = entries.iter().find(|e| e.key_code() == Some(code)) {
+        run = Some(e.command.clone());
+    } else if keybinds::is_up(&code) {
+        *selected = selected.saturating_sub(1);
+    } else if keybinds::is_down(&code) {
+        if *selected + 1 < entries.len() {
+            *selected += 1;
+        }
+    } else if keybinds::is_enter(&code) {
+        run = entries.get(*selected).map(|e| e.command.clone());
+    } else if keybinds::is_esc(&code) || keybinds::is_char(&code, 'q') {
+        app.mode = Mode::Normal;
+    }
+    if let Some(command) = run {
+        app.mode = Mode::Normal;
+        crate::runner::shell::run_for_app(app, &command)?;
+    }
+    Ok(false)
+}
@@ -0,0 +1,389 @@
+//! Commands from other programs to a running fileZoom.
+//!
+//! A running instance listens on a Unix socket (a named pipe on Windows,
+//! see `endpoint`) so shell aliases and editors can point it somewhere
+//! instead of starting a second instance: `fileZoom --remote reveal
+//! src/main.rs` sends `reveal /abs/src/main.rs` and exits. The protocol is
+//! one command line per connection, answered with `ok` or `error: ...`:
+//!
+//! - `reveal PATH` selects PATH in the active panel, listing its directory;
+//! - `open PATH`, `open-left PATH`, `open-right PATH` switch the active,
+//!   left or right panel (which becomes active) to the directory PATH;
+//! - `focus` rings the terminal bell, which most terminals turn into an
+//!   attention request for their window.
+//!
+//! Paths must be absolute; `--remote` resolves them against the caller's
+//! directory. The listener runs on its own thread and hands commands to the
+//! event loop, which applies them with `poll` once no dialog or editor is
+//! open; until then new commands are answered with `error: busy`.
+//!
+//! On Unix the socket lives in a directory only the user can enter, and
+//! both ends refuse a socket (or directory) owned by someone else, so
+//! another local user can neither send commands nor pose as the listener.
+
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{Receiver, Sender};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::app::{App, Mode, Side};
+
+/// A command received from another program.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum IpcCommand {
+    Reveal(PathBuf),
+    /// Switch the panel on the given side, or the active one, to a
+    /// directory.
+    Open(Option<Side>, PathBuf),
+    Focus,
+}
+
+impl IpcCommand {
+    /// Parse one command line (see the module docs).
+    pub fn parse(line: &str) -> Result<Self, String> {
+        let line = line.trim_end_matches(['\r', '\n']);
+        let (verb, arg) = line.split_once(' ').unwrap_or((line, ""));
+        let path = || -> Result<PathBuf, String> {
+            let path = PathBuf::from(arg);
+            match path.is_absolute() {
+                true => Ok(path),
+                false if arg.is_empty() => Err(format!("`{}` needs a path", verb)),
+                false => Err(format!("not an absolute path: {}", arg)),
+            }
+        };
+        match verb {
+            "reveal" => Ok(IpcCommand::Reveal(path()?)),
+            "open" => Ok(IpcCommand::Open(None, path()?)),
+            "open-left" => Ok(IpcCommand::Open(Some(Side::Left), path()?)),
+            "open-right" => Ok(IpcCommand::Open(Some(Side::Right), path()?)),
+            "focus" => Ok(IpcCommand::Focus),
+            other => Err(format!("unknown command `{}`", other)),
+        }
+    }
+
+    /// The command for `fileZoom --remote VERB [PATH]`, with PATH made
+    /// absolute against the current directory.
+    pub fn from_args(verb: &str, path: Option<&Path>) -> Result<Self, String> {
+        let path = match path {
+            Some(p) => std::path::absolute(p).map_err(|e| format!("{}: {}", p.display(), e))?,
+            None => PathBuf::new(),
+        };
+        Self::parse(format!("{} {}", verb, path.display()).trim_end())
+    }
+
+    /// The line that sends this command.
+    pub fn to_line(&self) -> String {
+        match self {
+            IpcCommand::Reveal(p) => format!("reveal {}", p.display()),
+            IpcCommand::Open(None, p) => format!("open {}", p.display()),
+            IpcCommand::Open(Some(Side::Left), p) => format!("open-left {}", p.display()),
+            IpcCommand::Open(Some(Side::Right), p) => format!("open-right {}", p.display()),
+            IpcCommand::Focus => "focus".to_string(),
+        }
+    }
+
+    /// Check the command can be applied, so the sender learns of a
+    /// missing path instead of the user finding an error dialog.
+    fn validate(&self) -> Result<(), String> {
+        match self {
+            IpcCommand::Reveal(p) if !p.exists() => Err(format!("no such file: {}", p.display())),
+            IpcCommand::Open(_, p) if !p.is_dir() => Err(format!("not a directory: {}", p.display())),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// The answer while the instance is not browsing (see `poll`).
+pub const BUSY: &str = "busy: close the open dialog in fileZoom first";
+
+/// How long a client may take to send its command and read the answer;
+/// the listener serves one connection at a time.
+pub const CLIENT_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Where the running instance listens: `$XDG_RUNTIME_DIR/fileZoom.sock`,
+/// or `fileZoom.sock` in a private directory named after the user in the
+/// temporary directory, on Unix; a pipe named after the user on Windows.
+pub fn endpoint() -> PathBuf {
+    #[cfg(unix)]
+    {
+        match std::env::var_os("XDG_RUNTIME_DIR").filter(|d| !d.is_empty()) {
+            Some(dir) => PathBuf::from(dir).join("fileZoom.sock"),
+            None => std::env::temp_dir().join(format!("fileZoom-{}", nix::unistd::getuid())).join("fileZoom.sock"),
+        }
+    }
+    #[cfg(not(unix))]
+    {
+        let user = std::env::var("USERNAME").unwrap_or_default();
+        PathBuf::from(format!(r"\\.\pipe\fileZoom-{}", user))
+    }
+}
+
+/// Fail unless `meta` belongs to the user running fileZoom.
+#[cfg(unix)]
+fn check_owner(path: &Path, meta: &std::fs::Metadata) -> io::Result<()> {
+    use std::os::unix::fs::MetadataExt;
+    if meta.uid() != nix::unistd::getuid().as_raw() {
+        return Err(io::Error::new(io::ErrorKind::PermissionDenied, format!("{} belongs to another user", path.display())));
+    }
+    Ok(())
+}
+
+/// Create `dir` for the socket, or check the existing one: it must be a
+/// directory of the user that nobody else can enter.
+#[cfg(unix)]
+fn private_dir(dir: &Path) -> io::Result<()> {
+    use std::os::unix::fs::{DirBuilderExt, PermissionsExt};
+    match std::fs::DirBuilder::new().mode(0o700).create(dir) {
+        Ok(()) => {}
+        Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {}
+        Err(e) => return Err(e),
+    }
+    let meta = std::fs::symlink_metadata(dir)?;
+    check_owner(dir, &meta)?;
+    if !meta.is_dir() || meta.permissions().mode() & 0o077 != 0 {
+        return Err(io::Error::new(io::ErrorKind::PermissionDenied, format!("{} is open to other users", dir.display())));
+    }
+    Ok(())
+}
+
+/// Read one command from `stream`, pass it on to `tx` and answer.
+fn serve_one(stream: impl io::Read + Write, tx: &Sender<IpcCommand>, busy: &AtomicBool) -> io::Result<()> {
+    let mut reader = BufReader::new(stream);
+    let mut line = String::new();
+    reader.read_line(&mut line)?;
+    let reply = match IpcCommand::parse(&line).and_then(|cmd| cmd.validate().map(|_| cmd)) {
+        Ok(_) if busy.load(Ordering::SeqCst) => format!("error: {}", BUSY),
+        Ok(cmd) => {
+            tracing::info!(command = %cmd.to_line(), "remote command");
+            match tx.send(cmd) {
+                Ok(()) => "ok".to_string(),
+                Err(_) => "error: fileZoom is shutting down".to_string(),
+            }
+        }
+        Err(e) => format!("error: {}", e),
+    };
+    let stream = reader.get_mut();
+    stream.write_all(reply.as_bytes())?;
+    stream.write_all(b"\n")?;
+    stream.flush()
+}
+
+/// The listener of a running instance. Dropping it removes the socket.
+pub struct IpcServer {
+    path: PathBuf,
+    commands: Receiver<IpcCommand>,
+    /// Set by `poll` while commands cannot be applied.
+    busy: Arc<AtomicBool>,
+}
+
+impl IpcServer {
+    /// Listen on `path`, whose directory must be private to the user (see
+    /// `private_dir`). Fails when another instance already listens; a
+    /// socket left behind by one that exited is replaced.
+    #[cfg(unix)]
+    pub fn start(path: &Path) -> io::Result<Self> {
+        use std::os::unix::fs::PermissionsExt;
+        use std::os::unix::net::{UnixListener, UnixStream};
+        if let Some(dir) = path.parent() {
+            private_dir(dir)?;
+        }
+        if let Ok(meta) = std::fs::symlink_metadata(path) {
+            check_owner(path, &meta)?;
+            if UnixStream::connect(path).is_ok() {
+                return Err(io::Error::new(io::ErrorKind::AddrInUse, format!("another fileZoom listens on {}", path.display())));
+            }
+            std::fs::remove_file(path)?;
+        }
+        // The directory keeps others out before the mode below is set.
+        let listener = UnixListener::bind(path)?;
+        std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))?;
+        let (tx, rx) = std::sync::mpsc::channel();
+        let busy = Arc::new(AtomicBool::new(false));
+        let server_busy = busy.clone();
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                match stream {
+                    Ok(stream) => {
+                        // A client that never finishes its line would hold
+                        // up every other one.
+                        let _ = stream.set_read_timeout(Some(CLIENT_TIMEOUT));
+                        let _ = stream.set_write_timeout(Some(CLIENT_TIMEOUT));
+                        if let Err(e) = serve_one(stream, &tx, &server_busy) {
+                            tracing::warn!("remote command failed: {}", e);
+                        }
+                    }
+                    Err(e) => tracing::warn!("remote connection failed: {}", e),
+                }
+            }
+        });
+        Ok(IpcServer { path: path.to_path_buf(), commands: crate::runner::wake::forward(rx), busy })
+    }
+
+    /// Listen on the pipe `path`. Fails when another instance already
+    /// does.
+    #[cfg(windows)]
+    pub fn start(path: &Path) -> io::Result<Self> {
+        use tokio::io::{AsyncBufReadExt, AsyncWriteExt};
+        use tokio::net::windows::named_pipe::ServerOptions;
+        let name = path.as_os_str().to_owned();
+        let runtime = tokio::runtime::Builder::new_current_thread().enable_io().build()?;
+        // Creating the first instance fails while another fileZoom owns
+        // the pipe.
+        let first = runtime.block_on(async { ServerOptions::new().first_pipe_instance(true).create(&name) })?;
+        let (tx, rx) = std::sync::mpsc::channel();
+        let busy = Arc::new(AtomicBool::new(false));
+        let server_busy = busy.clone();
+        std::thread::spawn(move || {
+            runtime.block_on(async move {
+                let mut server = first;
+                loop {
+                    if let Err(e) = server.connect().await {
+                        tracing::warn!("remote connection failed: {}", e);
+                        return;
+                    }
+                    let client = server;
+                    server = match ServerOptions::new().create(&name) {
+                        Ok(next) => next,
+                        Err(e) => {
+                            tracing::warn!("remote commands stopped: {}", e);
+                            return;
+                        }
+                    };
+                    let mut reader = tokio::io::BufReader::new(client);
+                    let mut line = String::new();
+                    if reader.read_line(&mut line).await.is_err() {
+                        continue;
+                    }
+                    // `serve_one` does the same over a blocking stream.
+                    let reply = match IpcCommand::parse(&line).and_then(|cmd| cmd.validate().map(|_| cmd)) {
+                        Ok(_) if server_busy.load(Ordering::SeqCst) => format!("error: {}", BUSY),
+                        Ok(cmd) => match tx.send(cmd) {
+                            Ok(()) => "ok".to_string(),
+                            Err(_) => return,
+                        },
+                        Err(e) => format!("error: {}", e),
+                    };
+                    let _ = reader.get_mut().write_all(format!("{}\n", reply).as_bytes()).await;
+                }
+            });
+        });
+        Ok(IpcServer { path: path.to_path_buf(), commands: crate::runner::wake::forward(rx), busy })
+    }
+
+    /// Commands received since the last call.
+    pub fn commands(&self) -> impl Iterator<Item = IpcCommand> + '_ {
+        self.commands.try_iter()
+    }
+}
+
+impl Drop for IpcServer {
+    fn drop(&mut self) {
+        #[cfg(unix)]
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+/// Send `command` to the instance listening on `path` and return its
+/// answer: `Ok(())` for `ok`, the message of an `error: ...` otherwise.
+/// `Err(NotFound | ConnectionRefused)` means nothing listens, and
+/// `Err(PermissionDenied)` that the socket belongs to another user.
+pub fn send(path: &Path, command: &IpcCommand) -> io::Result<Result<(), String>> {
+    #[cfg(unix)]
+    let mut stream = {
+        check_owner(path, &std::fs::symlink_metadata(path)?)?;
+        let stream = std::os::unix::net::UnixStream::connect(path)?;
+        stream.set_read_timeout(Some(CLIENT_TIMEOUT))?;
+        stream.set_write_timeout(Some(CLIENT_TIMEOUT))?;
+        stream
+    };
+    #[cfg(not(unix))]
+    let mut stream = std::fs::OpenOptions::new().read(true).write(true).open(path)?;
+    stream.write_all(format!("{}\n", command.to_line()).as_bytes())?;
+    stream.flush()?;
+    let mut reply = String::new();
+    BufReader::new(stream).read_line(&mut reply)?;
+    let reply = reply.trim_end();
+    Ok(match reply.strip_prefix("error: ") {
+        Some(error) => Err(error.to_string()),
+        None if reply == "ok" => Ok(()),
+        None => Err(format!("unexpected answer `{}`", reply)),
+    })
+}
+
+/// Apply the commands received so far, unless a dialog or the editor is
+/// open: those keep them queued and have new ones answered with
+/// `error: busy`. Call it once per frame.
+pub fn poll(server: &IpcServer, app: &mut App) {
+    while matches!(app.mode, Mode::Normal) {
+        match server.commands().next() {
+            Some(command) => apply(app, command),
+            None => break,
+        }
+    }
+    server.busy.store(!matches!(app.mode, Mode::Normal), Ordering::SeqCst);
+}
+
+/// Apply `command` to `app`, which must be browsing (`Mode::Normal`); a
+/// directory that cannot be listed is shown as an error.
+pub fn apply(app: &mut App, command: IpcCommand) {
+    let result = match command {
+        IpcCommand::Reveal(path) => {
+            let (Some(dir), Some(name)) = (path.parent(), path.file_name()) else {
+                return apply(app, IpcCommand::Open(None, path));
+            };
+            app.active_panel_mut().select_after_listing(&path);
+            if app.active_panel().cwd == dir && app.active_panel_mut().select_named(name) {
+                app.update_preview_for(app.active);
+                Ok(())
+            } else {
+                app.go_to(dir.to_path_buf())
+            }
+        }
+        IpcCommand::Open(side, dir) => {
+            if let Some(side) = side {
+                app.active = side;
+            }
+            app.go_to(dir)
+        }
+        IpcCommand::Focus => {
+            let mut stdout = io::stdout();
+            let _ = stdout.write_all(b"\x07");
+            let _ = stdout.flush();
+            Ok(())
+        }
+    };
+    if let Err(e) = result {
+        app.mode = Mode::Message {
+            title: "Error".to_string(),
+            content: crate::errors::render_fsop_error(&e, None, None, None),
+            buttons: vec!["OK".to_string()],
+            selected: 0,
+            actions: None,
+        };
+    }
+    app.dirty = true;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_commands() {
+        let abs = std::env::temp_dir();
+        let line = |verb: &str| format!("{} {}\n", verb, abs.display());
+        assert_eq!(IpcCommand::parse(&line("reveal")), Ok(IpcCommand::Reveal(abs.clone())));
+        assert_eq!(IpcCommand::parse(&line("open-right")), Ok(IpcCommand::Open(Some(Side::Right), abs.clone())));
+        assert_eq!(IpcCommand::parse("focus"), Ok(IpcCommand::Focus));
+        assert!(IpcCommand::parse("open relative/dir").unwrap_err().contains("absolute"));
+        assert!(IpcCommand::parse("open").unwrap_err().contains("needs a path"));
+        assert!(IpcCommand::parse("launch /x").unwrap_err().contains("unknown"));
+        let cmd = IpcCommand::Open(Some(Side::Left), abs);
+        assert_eq!(IpcCommand::parse(&cmd.to_line()), Ok(cmd));
+        let relative = IpcCommand::from_args("reveal", Some(Path::new("Cargo.toml"))).unwrap();
+        assert_eq!(relative, IpcCommand::Reveal(std::env::current_dir().unwrap().join("Cargo.toml")));
+        assert_eq!(IpcCommand::from_args("focus", None), Ok(IpcCommand::Focus));
+    }
+}
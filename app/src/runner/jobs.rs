@@ -0,0 +1,367 @@
+//! Background jobs: copies and moves running or waiting besides the one in
+//! the progress dialog.
+//!
+//! The progress dialog follows one job, the foreground job, through the
+//! `op_*` fields of `App`. Pressing `b` there hands it to the `Scheduler`,
+//! which lets it run on in the background. Copies and moves started while
+//! another job is in the foreground, or while `max_concurrent_jobs` jobs
+//! run (one with `sequential_jobs`), are queued here and started in order
+//! as slots free up (`poll`).
+//!
+//! The jobs dialog (`B`, `Mode::Jobs`) lists them. A background job that
+//! finds an existing target or a recoverable error waits until it is
+//! brought back into the progress dialog, where the question is asked.
+
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{Receiver, Sender, TryRecvError};
+use std::sync::Arc;
+use std::time::Instant;
+
+use crate::app::{App, Mode};
+use crate::fs_op::meter::{Meter, MeterStats};
+use crate::fs_op::path::display_path;
+use crate::fs_op::vfs::VfsProvider;
+use crate::runner::handlers::normal::Operation;
+use crate::runner::progress::{OperationDecision, ProgressPhase, ProgressUpdate};
+
+/// The channels and flags of a running job, as `start_operation` sets them
+/// up in the `op_*` fields of `App`.
+pub struct JobHandle {
+    pub progress_rx: Receiver<ProgressUpdate>,
+    pub cancel: Arc<AtomicBool>,
+    pub decision_tx: Option<Sender<OperationDecision>>,
+    pub skip_verify: Option<Arc<AtomicBool>>,
+    pub meter: Option<Arc<Meter>>,
+}
+
+/// A copy or move to start later.
+#[derive(Clone)]
+pub struct JobSpec {
+    pub(crate) op: Operation,
+    pub src_paths: Vec<PathBuf>,
+    pub dst_dir: PathBuf,
+    pub vfs: Arc<dyn VfsProvider>,
+}
+
+impl JobSpec {
+    /// What the jobs dialog calls the job, e.g. "Copy 3 item(s) to /tmp".
+    pub fn label(&self) -> String {
+        format!("{} {} item(s) to {}", self.op.name(), self.src_paths.len(), display_path(&self.dst_dir, 40))
+    }
+}
+
+/// Whether a job waits for a slot or runs.
+pub enum JobState {
+    Queued(JobSpec),
+    Running(JobHandle),
+}
+
+/// One job of the jobs dialog.
+pub struct Job {
+    pub label: String,
+    /// Operation label recorded in the job history, e.g. "Copy".
+    pub operation: String,
+    pub state: JobState,
+    pub processed: usize,
+    pub total: usize,
+    pub message: String,
+    /// Conflict or recoverable error the worker waits to have answered.
+    pub waiting: Option<ProgressUpdate>,
+    /// When the job started; `None` while queued.
+    pub started: Option<Instant>,
+}
+
+impl Job {
+    fn handle(&self) -> Option<&JobHandle> {
+        match &self.state {
+            JobState::Running(handle) => Some(handle),
+            JobState::Queued(_) => None,
+        }
+    }
+
+    /// Whether the job waits for a slot to start.
+    pub fn is_queued(&self) -> bool {
+        matches!(self.state, JobState::Queued(_))
+    }
+
+    /// State shown in the jobs dialog.
+    pub fn status(&self) -> &'static str {
+        match self.handle() {
+            None => "Queued",
+            Some(h) if h.cancel.load(Ordering::SeqCst) => "Cancelling",
+            Some(_) if self.waiting.is_some() => "Waiting",
+            Some(h) if h.meter.as_ref().is_some_and(|m| m.paused()) => "Paused",
+            Some(_) => "Running",
+        }
+    }
+
+    /// Bytes and speed of a running copy or move.
+    pub fn stats(&self, now: Instant) -> Option<MeterStats> {
+        self.handle()?.meter.as_ref().map(|m| m.stats(now))
+    }
+
+    /// Take in an update from the worker.
+    fn apply(&mut self, update: ProgressUpdate) {
+        if let Some(path) = &update.conflict {
+            self.message = format!("{} exists; Enter to answer", display_path(path, 40));
+            self.waiting = Some(update);
+            return;
+        }
+        if update.failed.is_some() {
+            self.message = format!("{}; Enter to answer", update.error.as_deref().unwrap_or("Failed"));
+            self.waiting = Some(update);
+            return;
+        }
+        self.waiting = None;
+        // The item counts belong to the copy, not the verification after it.
+        if update.phase == ProgressPhase::Copying {
+            self.processed = update.processed;
+            self.total = update.total;
+        }
+        if let Some(message) = update.message {
+            self.message = message;
+        }
+    }
+}
+
+/// Jobs running in the background and jobs waiting to start, in the order
+/// queued jobs start in.
+#[derive(Default)]
+pub struct Scheduler {
+    jobs: Vec<Job>,
+    /// Label of the job in the progress dialog, kept for when it is sent
+    /// to the background.
+    pub(crate) foreground: Option<String>,
+}
+
+impl Scheduler {
+    pub fn jobs(&self) -> &[Job] {
+        &self.jobs
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.jobs.is_empty()
+    }
+
+    /// Number of jobs running in the background.
+    pub fn running(&self) -> usize {
+        self.jobs.iter().filter(|j| j.handle().is_some()).count()
+    }
+
+    /// Place of job `index` among the queued jobs, counting from 1 for the
+    /// one starting next. `None` when it is not queued.
+    pub fn queue_position(&self, index: usize) -> Option<usize> {
+        self.jobs.get(index).filter(|j| j.is_queued())?;
+        Some(self.jobs[..=index].iter().filter(|j| j.is_queued()).count())
+    }
+
+    /// Queue `spec` behind the other jobs.
+    pub fn queue(&mut self, spec: JobSpec) {
+        self.jobs.push(Job {
+            label: spec.label(),
+            operation: spec.op.name().to_string(),
+            processed: 0,
+            total: spec.src_paths.len(),
+            message: "Queued".to_string(),
+            state: JobState::Queued(spec),
+            waiting: None,
+            started: None,
+        });
+    }
+
+    /// Swap job `index` with the one before it (`up`) or after it. Returns
+    /// the job's new index.
+    pub fn reorder(&mut self, index: usize, up: bool) -> usize {
+        let other = if up { index.checked_sub(1) } else { Some(index + 1) };
+        match other.filter(|&o| o < self.jobs.len() && index < self.jobs.len()) {
+            Some(other) => {
+                self.jobs.swap(index, other);
+                other
+            }
+            None => index,
+        }
+    }
+
+    /// Pause or resume job `index`, when it is running.
+    pub fn toggle_pause(&mut self, index: usize) {
+        if let Some(meter) = self.jobs.get(index).and_then(Job::handle).and_then(|h| h.meter.as_ref()) {
+            meter.set_paused(!meter.paused());
+        }
+    }
+
+    /// Cancel job `index`: a queued job is dropped, a running one asked to
+    /// stop.
+    pub fn cancel(&mut self, index: usize) {
+        let Some(job) = self.jobs.get(index) else {
+            return;
+        };
+        match &job.state {
+            JobState::Queued(_) => {
+                self.jobs.remove(index);
+            }
+            JobState::Running(handle) => {
+                handle.cancel.store(true, Ordering::SeqCst);
+                // Answer a question the worker may have asked since the
+                // last poll; a worker not waiting stops on the flag.
+                if let Some(tx) = &handle.decision_tx {
+                    let _ = tx.send(OperationDecision::Cancel);
+                }
+            }
+        }
+    }
+
+    /// Start the first queued job with `spawn`. Returns whether there was
+    /// one.
+    fn start_next(&mut self, spawn: impl FnOnce(JobSpec) -> JobHandle) -> bool {
+        let Some(job) = self.jobs.iter_mut().find(|j| matches!(j.state, JobState::Queued(_))) else {
+            return false;
+        };
+        let JobState::Queued(spec) = &job.state else {
+            return false;
+        };
+        job.state = JobState::Running(spawn(spec.clone()));
+        job.started = Some(Instant::now());
+        job.message = "Starting".to_string();
+        true
+    }
+
+    /// Take in the updates of the running jobs. Returns the jobs that
+    /// ended, with their final update, and whether anything changed.
+    fn poll(&mut self) -> (Vec<(Job, ProgressUpdate)>, bool) {
+        let mut finished = Vec::new();
+        let mut changed = false;
+        let mut i = 0;
+        while i < self.jobs.len() {
+            let Some(handle) = self.jobs[i].handle() else {
+                i += 1;
+                continue;
+            };
+            let mut updates = Vec::new();
+            let mut gone = false;
+            loop {
+                match handle.progress_rx.try_recv() {
+                    Ok(update) => updates.push(update),
+                    Err(TryRecvError::Empty) => break,
+                    Err(TryRecvError::Disconnected) => {
+                        gone = true;
+                        break;
+                    }
+                }
+            }
+            changed |= !updates.is_empty();
+            let job = &mut self.jobs[i];
+            let mut last = None;
+            for update in updates {
+                if update.done {
+                    last = Some(update);
+                    break;
+                }
+                job.apply(update);
+            }
+            if last.is_none() && !gone {
+                i += 1;
+                continue;
+            }
+            let job = self.jobs.remove(i);
+            let update = last.unwrap_or_else(|| ProgressUpdate::done_with_error(job.processed, job.total, Some("The job stopped unexpectedly".to_string())));
+            finished.push((job, update));
+            changed = true;
+        }
+        (finished, changed)
+    }
+}
+
+/// Move the channels of the foreground job into `handle`'s.
+fn install(app: &mut App, handle: JobHandle) {
+    app.op_progress_rx = Some(handle.progress_rx);
+    app.op_cancel_flag = Some(handle.cancel);
+    app.op_decision_tx = handle.decision_tx;
+    app.op_skip_verify = handle.skip_verify;
+    app.op_meter = handle.meter;
+}
+
+/// Send the job in the progress dialog to the background. Does nothing
+/// once it is being cancelled. Returns whether it was sent.
+pub fn detach(app: &mut App) -> bool {
+    let Some(cancel) = app.op_cancel_flag.clone() else {
+        return false;
+    };
+    let Some(progress_rx) = app.op_progress_rx.take() else {
+        return false;
+    };
+    app.op_cancel_flag = None;
+    let handle = JobHandle { progress_rx, cancel, decision_tx: app.op_decision_tx.take(), skip_verify: app.op_skip_verify.take(), meter: app.op_meter.take() };
+    let (operation, started) = app.jobs.detach().unwrap_or_else(|| (String::new(), Instant::now()));
+    let (processed, total, message) = match &app.mode {
+        Mode::Progress { processed, total, message, .. } => (*processed, *total, message.clone()),
+        _ => (0, 0, String::new()),
+    };
+    let label = app.scheduler.foreground.take().unwrap_or_else(|| operation.clone());
+    app.scheduler.jobs.push(Job { label, operation, state: JobState::Running(handle), processed, total, message, waiting: None, started: Some(started) });
+    app.mode = Mode::Normal;
+    true
+}
+
+/// Bring running job `index` into the progress dialog, asking the question
+/// it waits on, if any. Does nothing while another job is there. Returns
+/// whether it was brought.
+pub fn attach(app: &mut App, index: usize) -> bool {
+    if app.op_progress_rx.is_some() || app.scheduler.jobs.get(index).and_then(Job::handle).is_none() {
+        return false;
+    }
+    let job = app.scheduler.jobs.remove(index);
+    let JobState::Running(handle) = job.state else {
+        return false;
+    };
+    install(app, handle);
+    app.jobs.resume(job.operation, job.started.unwrap_or_else(Instant::now));
+    app.scheduler.foreground = Some(job.label.clone());
+    app.mode = Mode::Progress {
+        title: job.label,
+        processed: job.processed,
+        total: job.total,
+        message: job.message,
+        cancelled: false,
+        verify: app.op_skip_verify.as_ref().map(|_| (0, 0)),
+    };
+    if let Some(update) = job.waiting {
+        app.ask_worker_question(&update);
+    }
+    true
+}
+
+/// Pick up the progress of the background jobs, record the ones that
+/// ended in the job history (notifying of long ones, see
+/// `runner::notify`) and start queued jobs while fewer than
+/// `Settings::job_slots` run. Call it once per frame.
+pub fn poll(app: &mut App) {
+    let (finished, changed) = app.scheduler.poll();
+    for (job, update) in &finished {
+        match &update.error {
+            Some(error) => tracing::warn!(job = %job.label, processed = update.processed, total = update.total, %error, "background job failed"),
+            None => tracing::info!(job = %job.label, processed = update.processed, total = update.total, "background job finished"),
+        }
+        if let Some(summary) = &update.summary {
+            summary.log_failures();
+        }
+        let started = job.started.unwrap_or_else(Instant::now);
+        crate::runner::notify::job_finished(&app.settings, &job.label, started.elapsed(), update.error.as_deref());
+        if let Err(e) = app.jobs.record(job.operation.clone(), started, update.processed, update.total, update.error.clone()) {
+            tracing::warn!("failed to save job history: {:#}", e);
+        }
+    }
+    if !finished.is_empty() {
+        let _ = app.refresh();
+    }
+    if changed {
+        app.dirty = true;
+    }
+    while app.running_jobs() < app.settings.job_slots() {
+        let settings = &app.settings;
+        if !app.scheduler.start_next(|spec| crate::runner::handlers::normal::spawn_operation(settings, spec)) {
+            break;
+        }
+        app.dirty = true;
+    }
+}
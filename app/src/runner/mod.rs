@@ -2,13 +2,28 @@
 //!
 //! This module is intentionally thin; implementation lives in submodules to
 //! keep code organized: `terminal` for terminal setup, `event_loop` for the
-//! main loop, and `commands` for pure helpers that mutate `App` state.
+//! main loop, `wake` for waking it from other threads, `jobs` for copies
+//! and moves running in the background, `ipc` for commands sent by other
+//! programs, `open_files` for the in-use warnings of delete and overwrite
+//! dialogs, and `commands` for pure helpers that mutate `App` state.
 
+pub mod batch;
 pub mod commands;
 pub mod event_loop_main;
 pub mod handlers;
+pub mod ipc;
+pub mod jobs;
+pub mod notify;
+pub mod open_files;
+pub mod profiles;
 pub mod progress;
+pub mod redraw;
+pub mod shell;
+pub mod startup;
 pub mod terminal;
+#[cfg(all(unix, feature = "termion-backend"))]
+pub mod termion_backend;
+pub mod wake;
 #[cfg(feature = "fs-watch")]
 pub mod watch_helpers;
 
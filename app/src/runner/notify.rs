@@ -0,0 +1,99 @@
+//! Telling the user a long copy or move finished while they looked away.
+//!
+//! When a job ran for at least `notify_after_secs` and the terminal is not
+//! known to have the focus, `job_finished` rings the terminal bell and
+//! sends an OSC 9 notification, which terminals such as iTerm2, kitty,
+//! WezTerm and Windows Terminal show on the desktop. Built with the
+//! `desktop-notify` feature it also posts a native desktop notification
+//! (`notify-rust`). Terminals report focus changes to `set_focused`; on
+//! those that do not, every long job notifies.
+
+use std::io::Write;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::time::Duration;
+
+const FOCUS_UNKNOWN: u8 = 0;
+const FOCUS_IN: u8 = 1;
+const FOCUS_OUT: u8 = 2;
+
+static FOCUS: AtomicU8 = AtomicU8::new(FOCUS_UNKNOWN);
+
+/// Record that the terminal gained (`true`) or lost the focus.
+pub fn set_focused(focused: bool) {
+    FOCUS.store(if focused { FOCUS_IN } else { FOCUS_OUT }, Ordering::Relaxed);
+}
+
+/// Whether the terminal has the focus; `None` until it reported a change.
+pub fn focused() -> Option<bool> {
+    match FOCUS.load(Ordering::Relaxed) {
+        FOCUS_IN => Some(true),
+        FOCUS_OUT => Some(false),
+        _ => None,
+    }
+}
+
+/// Whether a job that took `elapsed` is worth a notification: it took at
+/// least `threshold_secs` (`0` never notifies) and the terminal is not
+/// known to have the focus.
+pub fn should_notify(threshold_secs: u64, elapsed: Duration, focused: Option<bool>) -> bool {
+    threshold_secs > 0 && elapsed >= Duration::from_secs(threshold_secs) && focused != Some(true)
+}
+
+/// The text of a notification for job `label` that took `elapsed` and
+/// ended with `error`, if any.
+pub fn message(label: &str, elapsed: Duration, error: Option<&str>) -> String {
+    let took = crate::ui::widgets::dialog::format_eta(elapsed);
+    match error {
+        Some(error) => format!("{} failed after {}: {}", label, took, error),
+        None => format!("{} finished in {}", label, took),
+    }
+}
+
+/// The bell and OSC 9 sequence showing `message`. Control characters,
+/// which would end the sequence early, become spaces.
+pub fn osc9(message: &str) -> String {
+    let text: String = message.chars().map(|c| if c.is_control() { ' ' } else { c }).collect();
+    format!("\x07\x1b]9;{}\x1b\\", text)
+}
+
+/// Notify the user that job `label` ended after `elapsed` with `error`, if
+/// `settings` and the focus call for it (see `should_notify`).
+pub fn job_finished(settings: &crate::app::settings::write_settings::Settings, label: &str, elapsed: Duration, error: Option<&str>) {
+    if !should_notify(settings.notify_after_secs, elapsed, focused()) {
+        return;
+    }
+    let text = message(label, elapsed, error);
+    tracing::debug!(%text, "notifying");
+    let mut stdout = std::io::stdout();
+    let _ = stdout.write_all(osc9(&text).as_bytes());
+    let _ = stdout.flush();
+    #[cfg(feature = "desktop-notify")]
+    // D-Bus can take a while to answer; the UI does not wait for it.
+    std::thread::spawn(move || {
+        if let Err(e) = notify_rust::Notification::new().summary("fileZoom").body(&text).show() {
+            tracing::warn!("desktop notification failed: {}", e);
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_long_jobs_out_of_focus_notify() {
+        let long = Duration::from_secs(45);
+        assert!(should_notify(30, long, Some(false)));
+        assert!(should_notify(30, long, None));
+        assert!(!should_notify(30, long, Some(true)));
+        assert!(!should_notify(30, Duration::from_secs(29), None));
+        assert!(!should_notify(0, long, None));
+    }
+
+    #[test]
+    fn osc9_carries_the_message_on_one_line() {
+        let text = message("Copy 3 item(s) to /tmp", Duration::from_secs(75), Some("disk\nfull"));
+        assert_eq!(text, "Copy 3 item(s) to /tmp failed after 1:15: disk\nfull");
+        assert_eq!(osc9(&text), "\x07\x1b]9;Copy 3 item(s) to /tmp failed after 1:15: disk full\x1b\\");
+    }
+}
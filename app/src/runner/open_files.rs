@@ -0,0 +1,144 @@
+//! Warning about files open in other processes (`fs_op::open_files`)
+//! before a delete or an overwrite, without blocking the UI.
+//!
+//! The scan reads the descriptor table of every process, which can take a
+//! while on a busy machine. `check` waits up to `OPEN_FILES_WAIT` for it; a
+//! slower scan goes on in the background while the dialog shows
+//! `CHECKING_NOTE`, and `poll` puts its warning in the dialog once done.
+
+use std::path::PathBuf;
+use std::sync::mpsc::TryRecvError;
+use std::time::Duration;
+
+use crate::app::core::panel::Pending;
+use crate::app::{Action, App, Mode};
+use crate::fs_op::open_files::{describe_open_files, find_open_files, OpenFileUse};
+
+/// How long a prompt waits for the scan before showing without it.
+pub const OPEN_FILES_WAIT: Duration = Duration::from_millis(150);
+
+/// Shown in place of the warning while the scan runs.
+pub const CHECKING_NOTE: &str = "Checking whether other processes have them open...";
+
+/// A scan still running, and the dialog waiting for it.
+pub struct OpenFilesCheck {
+    pending: Pending<Vec<OpenFileUse>>,
+    /// The action of the delete confirmation to update, or `None` for the
+    /// overwrite conflict dialog about `pending.path`.
+    on_yes: Option<Action>,
+}
+
+/// Scan for processes holding `paths` (or anything below them) open.
+/// Returns the warning to show, or the scan when it outlives
+/// `OPEN_FILES_WAIT`.
+pub fn check(paths: Vec<PathBuf>) -> Result<Option<String>, Pending<Vec<OpenFileUse>>> {
+    let first = paths.first().cloned().unwrap_or_default();
+    Pending::run(first, OPEN_FILES_WAIT, move || find_open_files(&paths)).map(|uses| describe_open_files(&uses))
+}
+
+/// Ask to confirm `action` on `paths` with `msg`, warning about any of
+/// them open in another process. Without `confirm_delete` the action runs
+/// right away unless something is open.
+pub(crate) fn confirm(app: &mut App, msg: String, action: Action, paths: Vec<PathBuf>) {
+    let warning = if app.settings.warn_open_files {
+        match check(paths) {
+            Ok(warning) => warning,
+            Err(pending) => {
+                app.open_files = Some(OpenFilesCheck { pending, on_yes: Some(action.clone()) });
+                app.mode = Mode::Confirm { msg: format!("{}\n\n{}", msg, CHECKING_NOTE), on_yes: action, selected: 0 };
+                return;
+            }
+        }
+    } else {
+        None
+    };
+    match warning {
+        Some(warning) => app.mode = Mode::Confirm { msg: format!("{}\n\n{}", msg, warning), on_yes: action, selected: 0 },
+        None if !app.settings.confirm_delete => crate::runner::handlers::confirm::execute_action(app, action),
+        None => app.mode = Mode::Confirm { msg, on_yes: action, selected: 0 },
+    }
+}
+
+/// The `in_use` warning of the overwrite conflict dialog for `path`; a
+/// slow scan is left in `app.open_files` for `poll`.
+pub(crate) fn conflict_warning(app: &mut App, path: PathBuf) -> Option<String> {
+    if !app.settings.warn_open_files {
+        return None;
+    }
+    match check(vec![path]) {
+        Ok(warning) => warning,
+        Err(pending) => {
+            app.open_files = Some(OpenFilesCheck { pending, on_yes: None });
+            Some(CHECKING_NOTE.to_string())
+        }
+    }
+}
+
+/// Put the result of a scan that outlived `OPEN_FILES_WAIT` into the
+/// dialog it was started for, if that is still open. Call it once per
+/// frame.
+pub fn poll(app: &mut App) {
+    let Some(check) = &app.open_files else {
+        return;
+    };
+    let uses = match check.pending.try_take() {
+        Ok(uses) => uses,
+        Err(TryRecvError::Empty) => return,
+        Err(TryRecvError::Disconnected) => Vec::new(),
+    };
+    let Some(check) = app.open_files.take() else {
+        return;
+    };
+    let warning = describe_open_files(&uses);
+    app.dirty = true;
+    match (check.on_yes, &mut app.mode) {
+        (Some(action), Mode::Confirm { msg, on_yes, .. }) if *on_yes == action && msg.ends_with(CHECKING_NOTE) => {
+            let base = msg[..msg.len() - CHECKING_NOTE.len()].trim_end().to_string();
+            match warning {
+                Some(warning) => *msg = format!("{}\n\n{}", base, warning),
+                None if !app.settings.confirm_delete => crate::runner::handlers::confirm::execute_action(app, action),
+                None => *msg = base,
+            }
+        }
+        (None, Mode::Conflict { path, in_use, .. }) if *path == check.pending.path => *in_use = warning,
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn slow_scan(uses: Vec<OpenFileUse>) -> Pending<Vec<OpenFileUse>> {
+        let work = move || {
+            std::thread::sleep(Duration::from_millis(20));
+            uses
+        };
+        Pending::run(PathBuf::from("/tmp/x"), Duration::ZERO, work).unwrap_err()
+    }
+
+    fn finish(app: &mut App) {
+        while app.open_files.is_some() {
+            std::thread::sleep(Duration::from_millis(5));
+            poll(app);
+        }
+    }
+
+    #[test]
+    fn a_slow_scan_updates_the_prompt_it_was_started_for() {
+        let mut app = App::new().unwrap();
+        let held = OpenFileUse { path: PathBuf::from("/tmp/x"), pid: 42, process: "sh".into() };
+        let pending = format!("Delete x? (y/n)\n\n{}", CHECKING_NOTE);
+
+        app.mode = Mode::Confirm { msg: pending.clone(), on_yes: Action::DeleteSelected, selected: 0 };
+        app.open_files = Some(OpenFilesCheck { pending: slow_scan(vec![held]), on_yes: Some(Action::DeleteSelected) });
+        finish(&mut app);
+        let Mode::Confirm { msg, .. } = &app.mode else { panic!("{:?}", app.mode) };
+        assert!(msg.starts_with("Delete x? (y/n)\n\nWarning: in use by another process:"), "{msg}");
+
+        app.mode = Mode::Confirm { msg: pending, on_yes: Action::DeleteSelected, selected: 0 };
+        app.open_files = Some(OpenFilesCheck { pending: slow_scan(Vec::new()), on_yes: Some(Action::DeleteSelected) });
+        finish(&mut app);
+        assert!(matches!(&app.mode, Mode::Confirm { msg, .. } if msg == "Delete x? (y/n)"));
+    }
+}
@@ -0,0 +1,151 @@
+//! Running operation profiles (`app::settings::profiles`) against the
+//! active panel's selection.
+//!
+//! A profile runs like F5/F6 in `Mode::Progress` and reports through the
+//! same `ProgressUpdate` channel, but never asks about conflicts: the
+//! profile's `ConflictPolicy` decides. A move only removes its sources once
+//! every file was copied (and verified, when the profile verifies).
+
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
+
+use crate::app::settings::profiles::{OperationProfile, ProfileOperation};
+use crate::app::{App, Mode};
+use crate::fs_op::options::CopyOptions;
+use crate::fs_op::path::{display_path, MAX_DISPLAY_PATH_CHARS};
+use crate::fs_op::transfer::{self, PlannedItem, Throttle};
+use crate::runner::batch::ConflictPolicy;
+use crate::runner::progress::{ProgressPhase, ProgressUpdate};
+
+/// Run `profile` on the selection of the active panel (or the entry under
+/// the cursor). A relative destination is taken from the panel's
+/// directory. Does nothing when there is nothing to act on.
+pub fn start_profile(app: &mut App, profile: &OperationProfile) {
+    let src_paths = crate::runner::handlers::normal::collect_src_paths(app);
+    if src_paths.is_empty() {
+        return;
+    }
+    let dst_dir = app.active_panel().cwd.join(&profile.destination);
+
+    let (tx, rx) = mpsc::channel();
+    app.op_progress_rx = Some(crate::runner::wake::forward(rx));
+    app.op_decision_tx = None;
+    let skip_verify = profile.verify.then(|| Arc::new(AtomicBool::new(false)));
+    app.op_skip_verify = skip_verify.clone();
+    app.mode = Mode::Progress {
+        title: profile.name.clone(),
+        processed: 0,
+        total: 0,
+        message: "Starting".to_string(),
+        cancelled: false,
+        verify: skip_verify.as_ref().map(|_| (0, 0)),
+    };
+    let cancel_flag = Arc::new(AtomicBool::new(false));
+    app.op_cancel_flag = Some(cancel_flag.clone());
+    app.op_meter = None;
+    app.scheduler.foreground = None;
+    app.jobs.start(&profile.name);
+
+    let profile = profile.clone();
+    std::thread::spawn(move || {
+        let (total, result) = run_profile(&profile, &src_paths, &dst_dir, &tx, &cancel_flag, skip_verify.as_deref());
+        let phase = if profile.verify { ProgressPhase::Verifying } else { ProgressPhase::Copying };
+        let update = match result {
+            Ok(message) => ProgressUpdate { message: Some(message), done: true, ..ProgressUpdate::new(total, total) },
+            Err(err) => ProgressUpdate::done_with_error(total, total, Some(err)),
+        };
+        let _ = tx.send(update.in_phase(phase));
+    });
+}
+
+/// Copy (or move) `src_paths` into `dst_dir` as `profile` says. Returns the
+/// number of files to copy and the closing message or error.
+fn run_profile(
+    profile: &OperationProfile,
+    src_paths: &[PathBuf],
+    dst_dir: &std::path::Path,
+    tx: &mpsc::Sender<ProgressUpdate>,
+    cancel_flag: &AtomicBool,
+    skip_verify: Option<&AtomicBool>,
+) -> (usize, Result<String, String>) {
+    let transfer::Plan { items, errors } = transfer::plan(src_paths, dst_dir, &profile.excludes);
+    let total = items.iter().filter(|i| !i.is_dir).count();
+    let mut throttle = profile.throttle_mb_s.map(Throttle::new);
+    let mut copied = Vec::new();
+    let mut skipped = 0;
+    for PlannedItem { src, target, is_dir } in &items {
+        if cancel_flag.load(Ordering::SeqCst) {
+            return (total, Err("Cancelled".to_string()));
+        }
+        if *is_dir {
+            if let Err(e) = std::fs::create_dir_all(target) {
+                return (total, Err(format!("{}: {}", display_path(target, MAX_DISPLAY_PATH_CHARS), e)));
+            }
+            continue;
+        }
+        if std::fs::symlink_metadata(target).is_ok() {
+            match profile.on_conflict {
+                ConflictPolicy::Fail => return (total, Err(format!("Target exists: {}", display_path(target, MAX_DISPLAY_PATH_CHARS)))),
+                ConflictPolicy::Skip => {
+                    skipped += 1;
+                    continue;
+                }
+                ConflictPolicy::Overwrite if target.is_dir() => {
+                    let _ = std::fs::remove_dir_all(target);
+                }
+                ConflictPolicy::Overwrite => {}
+            }
+        }
+        let is_link = std::fs::symlink_metadata(src).map(|m| m.file_type().is_symlink()).unwrap_or(false);
+        let res = match throttle.as_mut() {
+            Some(throttle) if !is_link => transfer::copy_file_throttled(src, target, throttle, cancel_flag).map(|_| ()),
+            _ => crate::fs_op::helpers::atomic_copy_file_with(src, target, &CopyOptions { overwrite: true, preserve_metadata: false, follow_symlinks: false, ..Default::default() }).map(|_| ()),
+        };
+        let res = crate::logging::log_fs_op("copy", src, Some(target), res);
+        if let Err(e) = res {
+            if cancel_flag.load(Ordering::SeqCst) {
+                return (total, Err("Cancelled".to_string()));
+            }
+            return (total, Err(format!("{}: {}", display_path(target, MAX_DISPLAY_PATH_CHARS), e)));
+        }
+        copied.push((src.clone(), target.clone()));
+        let _ = tx.send(ProgressUpdate { message: Some(format!("Copied {}", display_path(src, MAX_DISPLAY_PATH_CHARS))), ..ProgressUpdate::new(copied.len() + skipped, total) });
+    }
+    if profile.preserve {
+        // Last, so writing the files does not bump the directory times.
+        for src in src_paths {
+            if let Some(name) = src.file_name() {
+                let _ = crate::fs_op::metadata::preserve_all_metadata(src, &dst_dir.join(name));
+            }
+        }
+    }
+
+    let mut message = format!("{} files copied, {} skipped", copied.len(), skipped);
+    // Unreadable entries were left out of the plan; say so rather than
+    // reporting a complete copy.
+    if let Some((path, e)) = errors.first() {
+        let noun = if errors.len() == 1 { "entry" } else { "entries" };
+        message = format!("{}\n{} {} could not be read, e.g. {}: {}", message, errors.len(), noun, display_path(path, MAX_DISPLAY_PATH_CHARS), e);
+    }
+    if let Some(skip_verify) = skip_verify {
+        match crate::runner::handlers::normal::verify_copies(&copied, tx, cancel_flag, skip_verify) {
+            Ok(verified) => message = format!("{}\n{}", message, verified),
+            Err(err) => return (total, Err(err)),
+        }
+    }
+    if profile.operation == ProfileOperation::Move {
+        for (src, _) in &copied {
+            if let Err(e) = std::fs::remove_file(src) {
+                return (total, Err(format!("Copied, but could not remove {}: {}", display_path(src, MAX_DISPLAY_PATH_CHARS), e)));
+            }
+        }
+        // Deepest first; directories still holding skipped or excluded
+        // entries stay where they are.
+        for item in items.iter().rev().filter(|i| i.is_dir) {
+            let _ = std::fs::remove_dir(&item.src);
+        }
+        message = message.replacen("copied", "moved", 1);
+    }
+    (total, Ok(message))
+}
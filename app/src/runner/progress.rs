@@ -1,5 +1,6 @@
 use std::fmt;
 use std::path::PathBuf;
+use std::time::{Duration, Instant};
 
 /// User decision sent from the UI to a background worker when a
 /// conflicting target is reported during a file operation.
@@ -13,6 +14,7 @@ use std::path::PathBuf;
 /// - `Skip`: skip this item.
 /// - `OverwriteAll`: overwrite this and all subsequent conflicts.
 /// - `SkipAll`: skip this and all subsequent conflicts.
+/// - `Retry`: try a failed item again.
 /// - `Cancel`: abort the whole operation.
 // (see `OperationDecision` above)
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
@@ -29,6 +31,9 @@ pub enum OperationDecision {
     /// Skip this and all subsequent conflicts.
     SkipAll,
 
+    /// Try the item reported as `failed` again.
+    Retry,
+
     /// Cancel the whole operation immediately.
     Cancel,
 }
@@ -41,12 +46,77 @@ impl fmt::Display for OperationDecision {
             Skip => "Skip",
             OverwriteAll => "OverwriteAll",
             SkipAll => "SkipAll",
+            Retry => "Retry",
             Cancel => "Cancel",
         };
         write!(f, "{}", s)
     }
 }
 
+/// Phase of an operation a `ProgressUpdate` reports on. A copy with
+/// `verify_after_copy` set runs a second, `Verifying` pass over the copied
+/// files; `processed`/`total` then count files checked.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum ProgressPhase {
+    /// Copying or moving the selected items.
+    #[default]
+    Copying,
+    /// Comparing checksums of the copies with their sources.
+    Verifying,
+}
+
+impl fmt::Display for ProgressPhase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ProgressPhase::Copying => "Copying",
+            ProgressPhase::Verifying => "Verifying",
+        })
+    }
+}
+
+/// What a copy, move or delete of several items came to, shown in the
+/// summary dialog (`Mode::Summary`) once it ends.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct OperationSummary {
+    /// Items done.
+    pub succeeded: usize,
+    /// Items left alone because their target existed.
+    pub skipped: usize,
+    /// Items that failed, with their error; skipped after a recoverable
+    /// error or ending the operation.
+    pub failures: Vec<(PathBuf, String)>,
+    /// Bytes of the items done.
+    pub bytes: u64,
+    /// Time from the start of the operation to its end.
+    pub elapsed: Duration,
+}
+
+impl OperationSummary {
+    /// Number of items that failed.
+    #[must_use]
+    pub fn failed(&self) -> usize {
+        self.failures.len()
+    }
+
+    /// Record `path` as failed with `error`.
+    pub fn fail(&mut self, path: PathBuf, error: impl Into<String>) {
+        self.failures.push((path, error.into()));
+    }
+
+    /// Write each failure to the log.
+    pub fn log_failures(&self) {
+        for (path, error) in &self.failures {
+            tracing::warn!(path = %path.display(), %error, "item failed");
+        }
+    }
+
+    /// A copy with `elapsed` measured from `started`, for the final update.
+    #[must_use]
+    pub fn ended(&self, started: Instant) -> Self {
+        Self { elapsed: started.elapsed(), ..self.clone() }
+    }
+}
+
 /// ProgressUpdate is sent by background workers to the UI to report
 /// progress and to request conflict resolution.
 ///
@@ -56,9 +126,14 @@ impl fmt::Display for OperationDecision {
 /// - `done`: true when the worker finished (either successfully or
 ///   due to an error/cancellation).
 /// - `error`: optional error message when `done == true` and an error
-///   occurred.
+///   occurred, or the error of a `failed` item.
 /// - `conflict`: when `Some(path)`, the worker is blocked waiting for
 ///   an `OperationDecision` from the UI for that `path`.
+/// - `failed`: when `Some(path)`, that item failed with a recoverable error
+///   and the worker is blocked waiting for Retry, Skip or Cancel.
+/// - `phase`: which pass the counters belong to; a verified copy moves on
+///   to `ProgressPhase::Verifying` after the last item is copied.
+/// - `summary`: on the final update, what the operation came to.
 ///
 /// Example sequence:
 /// 1. Worker -> ProgressUpdate { processed:0, total:N, message:Some("Starting"), done:false, conflict:None }
@@ -87,27 +162,65 @@ pub struct ProgressUpdate {
     /// If present, the worker has hit a conflict for this `PathBuf` and is
     /// waiting for an `OperationDecision` from the UI thread.
     pub conflict: Option<PathBuf>,
+
+    /// Source item of a reported `conflict`, so the UI can compare both
+    /// sides. `None` for non-conflict updates or when the worker does not
+    /// know the source.
+    pub conflict_src: Option<PathBuf>,
+
+    /// If present, this item failed with a recoverable error (see
+    /// `fs_op::error::is_recoverable`), described by `error`, and the
+    /// worker is waiting for `Retry`, `Skip` or `Cancel`.
+    pub failed: Option<PathBuf>,
+
+    /// Pass of the operation `processed`/`total` count.
+    pub phase: ProgressPhase,
+
+    /// Counts, bytes and failures of the whole operation, sent with the
+    /// final (`done`) update by workers that keep them.
+    pub summary: Option<OperationSummary>,
 }
 
 impl ProgressUpdate {
     /// Create a new progress update with minimal state.
     #[must_use]
     pub fn new(processed: usize, total: usize) -> Self {
-        Self { processed, total, message: None, done: false, error: None, conflict: None }
+        Self { processed, total, message: None, done: false, error: None, conflict: None, conflict_src: None, failed: None, phase: ProgressPhase::Copying, summary: None }
     }
 
     /// Create a progress update that marks the operation done with an optional
     /// error message.
     #[must_use]
     pub fn done_with_error(processed: usize, total: usize, error: Option<String>) -> Self {
-        Self { processed, total, message: error.clone(), done: true, error, conflict: None }
+        Self { processed, total, message: error.clone(), done: true, error, conflict: None, conflict_src: None, failed: None, phase: ProgressPhase::Copying, summary: None }
     }
 
     /// Convenience constructor for a conflict update. The returned struct has
     /// `done == false` and `error == None`.
     #[must_use]
     pub fn conflict(path: PathBuf, processed: usize, total: usize, message: Option<String>) -> Self {
-        Self { processed, total, message, done: false, error: None, conflict: Some(path) }
+        Self { processed, total, message, done: false, error: None, conflict: Some(path), conflict_src: None, failed: None, phase: ProgressPhase::Copying, summary: None }
+    }
+
+    /// Attach the source path of a conflict update.
+    #[must_use]
+    pub fn with_conflict_src(mut self, src: PathBuf) -> Self {
+        self.conflict_src = Some(src);
+        self
+    }
+
+    /// Attach the `summary` of the operation to its final update.
+    #[must_use]
+    pub fn with_summary(mut self, summary: OperationSummary) -> Self {
+        self.summary = Some(summary);
+        self
+    }
+
+    /// Mark the update as part of `phase`.
+    #[must_use]
+    pub fn in_phase(mut self, phase: ProgressPhase) -> Self {
+        self.phase = phase;
+        self
     }
 
     /// Returns true if the operation is finished.
@@ -145,7 +258,7 @@ impl fmt::Display for ProgressUpdate {
 
 #[cfg(test)]
 mod tests {
-    use super::{OperationDecision, ProgressUpdate};
+    use super::{OperationDecision, ProgressPhase, ProgressUpdate};
     use std::path::PathBuf;
 
     #[test]
@@ -170,5 +283,8 @@ mod tests {
         q.done = true;
         q.error = Some("oh no".to_string());
         assert_eq!(format!("{}", q), "done 4/10 (error: oh no)");
+
+        let v = ProgressUpdate::new(1, 2).in_phase(ProgressPhase::Verifying);
+        assert_eq!((v.phase, v.phase.to_string()), (ProgressPhase::Verifying, "Verifying".to_string()));
     }
 }
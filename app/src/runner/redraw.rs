@@ -0,0 +1,91 @@
+//! When the event loop draws a frame.
+//!
+//! The loop only draws when something on screen changed: input was
+//! handled, a timer for something shown ran out, or `App::take_dirty`
+//! reports a change made in the background. Changes arriving faster than
+//! `Settings::max_fps` are drawn together in the next frame, so a busy
+//! copy or a flood of watcher events does not redraw for each one. This
+//! keeps an idle app from drawing at all and spares slow links frames
+//! nobody gets to see.
+
+use std::time::{Duration, Instant};
+
+/// Decides when the next frame is drawn.
+#[derive(Clone, Debug)]
+pub struct RedrawScheduler {
+    /// Shortest time between two frames.
+    interval: Duration,
+    last_frame: Option<Instant>,
+    /// A frame was asked for and not drawn yet.
+    requested: bool,
+}
+
+impl RedrawScheduler {
+    /// A scheduler drawing at most `max_fps` frames a second (`0` for no
+    /// limit). The first frame is requested already.
+    pub fn new(max_fps: u32) -> Self {
+        RedrawScheduler { interval: Self::interval_for(max_fps), last_frame: None, requested: true }
+    }
+
+    fn interval_for(max_fps: u32) -> Duration {
+        if max_fps == 0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs(1) / max_fps
+        }
+    }
+
+    /// Follow a change of the `max_fps` setting.
+    pub fn set_max_fps(&mut self, max_fps: u32) {
+        self.interval = Self::interval_for(max_fps);
+    }
+
+    /// Ask for a frame.
+    pub fn request(&mut self) {
+        self.requested = true;
+    }
+
+    /// When the requested frame can be drawn, if one was requested.
+    pub fn next_frame(&self) -> Option<Instant> {
+        if !self.requested {
+            return None;
+        }
+        Some(self.last_frame.map_or_else(Instant::now, |t| t + self.interval))
+    }
+
+    /// Whether to draw at `now`.
+    pub fn due(&self, now: Instant) -> bool {
+        self.requested && self.last_frame.is_none_or(|t| t + self.interval <= now)
+    }
+
+    /// Note a frame drawn at `now`.
+    pub fn drawn(&mut self, now: Instant) {
+        self.requested = false;
+        self.last_frame = Some(now);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn draws_only_requested_frames_at_the_limit() {
+        let t0 = Instant::now();
+        let mut r = RedrawScheduler::new(10);
+        assert!(r.due(t0));
+        r.drawn(t0);
+        assert_eq!(r.next_frame(), None);
+        assert!(!r.due(t0 + Duration::from_secs(5)));
+
+        r.request();
+        assert_eq!(r.next_frame(), Some(t0 + Duration::from_millis(100)));
+        assert!(!r.due(t0 + Duration::from_millis(50)));
+        assert!(r.due(t0 + Duration::from_millis(100)));
+
+        r.set_max_fps(0);
+        r.drawn(t0);
+        r.request();
+        assert!(r.due(t0));
+    }
+}
@@ -0,0 +1,344 @@
+//! Shell command execution for the command-line bar.
+//!
+//! Commands typed into the command line that are not built-in commands are
+//! run through the platform shell (`sh -c` on Unix, `cmd /C` on Windows)
+//! with the active panel's directory as the working directory. Before
+//! running, `%f` expands to the shell-quoted selection, `%d` to the quoted
+//! current directory and `%%` to a literal `%`. The user menu runs its
+//! command templates through the same path (`run_for_app`).
+//!
+//! Commands run on a worker thread. One that is not done within
+//! `SHELL_WAIT` leaves the results view showing it as running (`ShellRun`
+//! in `App::shell`) until `poll` takes in its output; closing the view
+//! kills it. Each stream keeps at most `MAX_OUTPUT_BYTES`.
+
+use std::ffi::{OsStr, OsString};
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::TryRecvError;
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+use crate::app::core::panel::Pending;
+use crate::app::{App, Mode};
+
+/// How long `run_for_app` waits for a command before showing it as
+/// running.
+pub const SHELL_WAIT: Duration = Duration::from_millis(200);
+
+/// Output kept of each of stdout and stderr; the rest is read and dropped.
+pub const MAX_OUTPUT_BYTES: usize = 1024 * 1024;
+
+/// How often the worker checks whether the command ended or was cancelled.
+const POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+/// Captured result of a shell command.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ShellOutput {
+    /// Exit code, or `None` if the process was terminated by a signal.
+    pub status: Option<i32>,
+    /// Captured standard output.
+    pub stdout: String,
+    /// Captured standard error.
+    pub stderr: String,
+}
+
+impl ShellOutput {
+    /// Combine stdout and stderr into display lines for the results view.
+    pub fn lines(&self) -> Vec<String> {
+        self.stdout.lines().chain(self.stderr.lines()).map(str::to_string).collect()
+    }
+}
+
+/// Quote `s` so the shell treats it as a single word.
+pub fn shell_quote(s: &str) -> String {
+    if cfg!(windows) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        format!("'{}'", s.replace('\'', "'\\''"))
+    }
+}
+
+/// `shell_quote` for a path. On Unix the bytes of names that are not valid
+/// UTF-8 are kept, so the command gets the file that was selected.
+fn shell_quote_os(s: &OsStr) -> OsString {
+    #[cfg(unix)]
+    {
+        use std::os::unix::ffi::{OsStrExt, OsStringExt};
+        let mut out = vec![b'\''];
+        for &b in s.as_bytes() {
+            if b == b'\'' {
+                out.extend_from_slice(b"'\\''");
+            } else {
+                out.push(b);
+            }
+        }
+        out.push(b'\'');
+        OsString::from_vec(out)
+    }
+    #[cfg(not(unix))]
+    {
+        OsString::from(shell_quote(&s.to_string_lossy()))
+    }
+}
+
+/// Expand `%f`, `%d` and `%%` placeholders in `cmd`.
+///
+/// `%f` becomes the space-separated, quoted `selection` and `%d` the quoted
+/// `cwd`. Unknown `%x` sequences are left untouched. The result is an
+/// `OsString` so paths that are not valid UTF-8 pass through unchanged.
+pub fn expand_placeholders(cmd: &str, selection: &[PathBuf], cwd: &Path) -> OsString {
+    let mut out = OsString::with_capacity(cmd.len());
+    let mut rest = cmd;
+    while let Some(i) = rest.find('%') {
+        out.push(&rest[..i]);
+        let tail = &rest[i + 1..];
+        rest = match tail.chars().next() {
+            Some('f') => {
+                for (n, p) in selection.iter().enumerate() {
+                    if n > 0 {
+                        out.push(" ");
+                    }
+                    out.push(shell_quote_os(p.as_os_str()));
+                }
+                &tail[1..]
+            }
+            Some('d') => {
+                out.push(shell_quote_os(cwd.as_os_str()));
+                &tail[1..]
+            }
+            Some('%') => {
+                out.push("%");
+                &tail[1..]
+            }
+            _ => {
+                out.push("%");
+                tail
+            }
+        };
+    }
+    out.push(rest);
+    out
+}
+
+/// A command still running for the results view. Dropping it kills the
+/// command.
+#[derive(Debug)]
+pub struct ShellRun {
+    /// The command as typed, before placeholders were expanded.
+    pub command: String,
+    pending: Pending<io::Result<ShellOutput>>,
+    cancel: Arc<AtomicBool>,
+}
+
+impl Drop for ShellRun {
+    fn drop(&mut self) {
+        self.cancel.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Run `cmd` through the platform shell in `cwd`, capturing its output.
+///
+/// Standard input is closed so interactive programs fail fast instead of
+/// blocking the UI.
+pub fn run_shell(cmd: impl AsRef<OsStr>, cwd: &Path) -> io::Result<ShellOutput> {
+    run_shell_until(cmd.as_ref(), cwd, &AtomicBool::new(false))
+}
+
+/// `run_shell` that kills the command (and on Unix its process group) once
+/// `cancel` is set.
+pub fn run_shell_until(cmd: &OsStr, cwd: &Path, cancel: &AtomicBool) -> io::Result<ShellOutput> {
+    let mut command = if cfg!(windows) {
+        let mut c = Command::new("cmd");
+        c.arg("/C").arg(cmd);
+        c
+    } else {
+        let mut c = Command::new("sh");
+        c.arg("-c").arg(cmd);
+        c
+    };
+    #[cfg(unix)]
+    std::os::unix::process::CommandExt::process_group(&mut command, 0);
+    let mut child = command.current_dir(cwd).stdin(Stdio::null()).stdout(Stdio::piped()).stderr(Stdio::piped()).spawn()?;
+    let stdout = drain(child.stdout.take());
+    let stderr = drain(child.stderr.take());
+    let status = loop {
+        if cancel.load(Ordering::Relaxed) {
+            kill(&mut child);
+            break child.wait()?;
+        }
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        std::thread::sleep(POLL_INTERVAL);
+    };
+    Ok(ShellOutput {
+        status: status.code(),
+        stdout: stdout.join().unwrap_or_default(),
+        stderr: stderr.join().unwrap_or_default(),
+    })
+}
+
+/// Kill `child` and whatever it started in its process group.
+fn kill(child: &mut Child) {
+    #[cfg(unix)]
+    if let Ok(pid) = libc::pid_t::try_from(child.id()) {
+        // SAFETY: `kill` only sends a signal; the group is the child's own.
+        unsafe { libc::kill(-pid, libc::SIGKILL) };
+    }
+    let _ = child.kill();
+}
+
+/// Read `pipe` to the end on a thread, keeping the first `MAX_OUTPUT_BYTES`.
+fn drain(pipe: Option<impl Read + Send + 'static>) -> JoinHandle<String> {
+    std::thread::spawn(move || {
+        let Some(mut pipe) = pipe else {
+            return String::new();
+        };
+        let (mut kept, mut dropped) = (Vec::new(), 0usize);
+        let mut chunk = [0u8; 8192];
+        while let Ok(n @ 1..) = pipe.read(&mut chunk) {
+            let take = n.min(MAX_OUTPUT_BYTES - kept.len());
+            kept.extend_from_slice(&chunk[..take]);
+            dropped += n - take;
+        }
+        let mut text = String::from_utf8_lossy(&kept).into_owned();
+        if dropped > 0 {
+            text.push_str(&format!("\n[{} more bytes not shown]", dropped));
+        }
+        text
+    })
+}
+
+/// Expand placeholders in `input`, run it in the active panel's directory
+/// and switch `app` to the results view (`Mode::CommandOutput`), or to an
+/// error dialog when the shell cannot be started.
+///
+/// Used by both the command line and the user menu.
+pub fn run_for_app(app: &mut App, input: &str) -> anyhow::Result<()> {
+    let cwd = app.active_panel().cwd.clone();
+    let expanded = expand_placeholders(input, &selected_paths(app), &cwd);
+    let cancel = Arc::new(AtomicBool::new(false));
+    let work_cancel = cancel.clone();
+    let result = Pending::run(cwd.clone(), SHELL_WAIT, move || run_shell_until(&expanded, &cwd, &work_cancel));
+    match result {
+        Ok(result) => show_result(app, input, result),
+        Err(pending) => {
+            app.mode = Mode::CommandOutput { command: input.to_string(), lines: Vec::new(), scroll: 0, status: None, running: true };
+            app.shell = Some(ShellRun { command: input.to_string(), pending, cancel });
+            Ok(())
+        }
+    }
+}
+
+/// Show the output of a command that was still running once it ends.
+/// Called every tick from the event loop.
+pub fn poll(app: &mut App) -> anyhow::Result<()> {
+    let Some(run) = &app.shell else {
+        return Ok(());
+    };
+    let result = match run.pending.try_take() {
+        Ok(result) => result,
+        Err(TryRecvError::Empty) => return Ok(()),
+        Err(TryRecvError::Disconnected) => Err(io::Error::other("the command's worker thread stopped")),
+    };
+    let command = app.shell.take().map(|run| run.command.clone()).unwrap_or_default();
+    app.dirty = true;
+    show_result(app, &command, result)
+}
+
+fn show_result(app: &mut App, command: &str, result: io::Result<ShellOutput>) -> anyhow::Result<()> {
+    match result {
+        Ok(out) => {
+            app.mode =
+                Mode::CommandOutput { command: command.to_string(), lines: out.lines(), scroll: 0, status: out.status, running: false };
+            // The command may have changed the directory contents.
+            app.refresh()?;
+        }
+        Err(e) => {
+            app.mode = Mode::Message {
+                title: "Error".to_string(),
+                content: crate::errors::render_io_error(&e, None, None, None),
+                buttons: vec!["OK".to_string()],
+                selected: 0,
+                actions: None,
+            };
+        }
+    }
+    Ok(())
+}
+
+/// Paths `%f` expands to: the multi-selection if any, else the current entry.
+pub(crate) fn selected_paths(app: &App) -> Vec<PathBuf> {
+    let panel = app.active_panel();
+    if !panel.selections.is_empty() {
+        let mut idx: Vec<usize> = panel.selections.iter().copied().collect();
+        idx.sort_unstable();
+        idx.iter().filter_map(|&i| panel.entries.get(i).map(|e| e.path.clone())).collect()
+    } else {
+        panel.selected_entry().map(|e| vec![e.path.clone()]).unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expands_placeholders() {
+        let sel = vec![PathBuf::from("/tmp/a b"), PathBuf::from("/tmp/c")];
+        let s = expand_placeholders("ls %f in %d 100%% %x", &sel, Path::new("/tmp"));
+        if cfg!(windows) {
+            assert_eq!(s, "ls \"/tmp/a b\" \"/tmp/c\" in \"/tmp\" 100% %x");
+        } else {
+            assert_eq!(s, "ls '/tmp/a b' '/tmp/c' in '/tmp' 100% %x");
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn quote_escapes_single_quotes() {
+        assert_eq!(shell_quote("it's"), "'it'\\''s'");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn expansion_keeps_non_utf8_names() {
+        use std::os::unix::ffi::{OsStrExt, OsStringExt};
+        let name = OsStr::from_bytes(b"caf\xe9's");
+        let s = expand_placeholders("cat %f", &[PathBuf::from(name)], Path::new("/tmp"));
+        assert_eq!(s.into_vec(), b"cat 'caf\xe9'\\''s'".to_vec());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn runs_in_cwd_and_captures_output() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("marker.txt"), "").unwrap();
+        let out = run_shell("ls; echo oops >&2; exit 3", dir.path()).unwrap();
+        assert_eq!(out.status, Some(3));
+        assert_eq!(out.lines(), vec!["marker.txt".to_string(), "oops".to_string()]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn output_is_capped_and_cancel_kills_the_command() {
+        let dir = tempfile::tempdir().unwrap();
+        let out = run_shell(format!("head -c {} /dev/zero | tr '\\0' x", MAX_OUTPUT_BYTES + 10), dir.path()).unwrap();
+        assert_eq!(out.stdout.len(), MAX_OUTPUT_BYTES + "\n[10 more bytes not shown]".len());
+        assert!(out.stdout.ends_with("x\n[10 more bytes not shown]"));
+
+        let cancel = Arc::new(AtomicBool::new(false));
+        let flag = cancel.clone();
+        let started = std::time::Instant::now();
+        let worker = std::thread::spawn(move || run_shell_until(OsStr::new("sleep 30; echo late"), Path::new("/"), &flag));
+        std::thread::sleep(Duration::from_millis(100));
+        cancel.store(true, Ordering::Relaxed);
+        let out = worker.join().unwrap().unwrap();
+        assert!(started.elapsed() < Duration::from_secs(10));
+        assert_eq!((out.status, out.stdout.as_str()), (None, ""));
+    }
+}
@@ -0,0 +1,102 @@
+//! Startup phase timings (`--profile-startup`).
+//!
+//! `run_app` marks the end of each setup phase, the first frame and the
+//! moment both initial listings are in. With profiling on, the timings are
+//! logged and appended as one line to `startup_profile.log` in
+//! `user_cache_dir()`, so runs can be compared over time.
+
+use std::io::Write;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use crate::app::settings::config_dirs::user_cache_dir;
+
+/// File name of the timings log inside the cache directory.
+pub const STARTUP_PROFILE_FILE: &str = "startup_profile.log";
+
+/// Durations of the startup phases, in order.
+#[derive(Debug)]
+pub struct StartupProfile {
+    enabled: bool,
+    started: Instant,
+    last: Instant,
+    phases: Vec<(&'static str, Duration)>,
+    finished: bool,
+}
+
+/// Location of the timings log.
+pub fn startup_profile_path() -> PathBuf {
+    user_cache_dir().join(STARTUP_PROFILE_FILE)
+}
+
+impl StartupProfile {
+    /// Start timing now. A disabled profile records phases but never
+    /// reports them.
+    pub fn new(enabled: bool) -> Self {
+        let now = Instant::now();
+        StartupProfile { enabled, started: now, last: now, phases: Vec::new(), finished: false }
+    }
+
+    /// End the phase called `name`, which began at the previous mark.
+    pub fn mark(&mut self, name: &'static str) {
+        let now = Instant::now();
+        self.phases.push((name, now - self.last));
+        self.last = now;
+    }
+
+    /// Recorded phases, in order.
+    pub fn phases(&self) -> &[(&'static str, Duration)] {
+        &self.phases
+    }
+
+    /// One-line summary: the total, then `phase=duration` for each phase,
+    /// in milliseconds.
+    pub fn summary(&self) -> String {
+        let ms = |d: Duration| format!("{:.1}ms", d.as_secs_f64() * 1000.0);
+        let mut line = format!("total={}", ms(self.last - self.started));
+        for (name, d) in &self.phases {
+            line.push_str(&format!(" {}={}", name, ms(*d)));
+        }
+        line
+    }
+
+    /// Report the timings once, if enabled: log them and append them to
+    /// the timings log.
+    pub fn finish(&mut self) {
+        if !self.enabled || std::mem::replace(&mut self.finished, true) {
+            return;
+        }
+        let summary = self.summary();
+        tracing::info!("startup: {}", summary);
+        let path = startup_profile_path();
+        let written = path.parent().map_or(Ok(()), std::fs::create_dir_all).and_then(|_| {
+            let mut file = std::fs::OpenOptions::new().create(true).append(true).open(&path)?;
+            writeln!(file, "{} {}", crate::app::core::clock::now_local().format("%Y-%m-%dT%H:%M:%S"), summary)
+        });
+        if let Err(e) = written {
+            tracing::warn!("failed to write {}: {}", path.display(), e);
+        }
+    }
+
+    /// Whether `finish` already ran (or nothing is reported).
+    pub fn is_finished(&self) -> bool {
+        self.finished || !self.enabled
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn summary_lists_phases_in_order() {
+        let mut profile = StartupProfile::new(false);
+        profile.mark("settings");
+        profile.mark("first_frame");
+        let names: Vec<&str> = profile.phases().iter().map(|(n, _)| *n).collect();
+        assert_eq!(names, ["settings", "first_frame"]);
+        let summary = profile.summary();
+        assert!(summary.starts_with("total=") && summary.contains(" settings=") && summary.ends_with("ms"), "{}", summary);
+        assert!(profile.is_finished());
+    }
+}
@@ -1,10 +1,14 @@
-use crossterm::event::{DisableMouseCapture, EnableMouseCapture};
+use crossterm::event::{
+    DisableFocusChange, DisableMouseCapture, EnableFocusChange, EnableMouseCapture, KeyboardEnhancementFlags, PopKeyboardEnhancementFlags,
+    PushKeyboardEnhancementFlags,
+};
 use crossterm::cursor::{Hide, Show};
 use crossterm::queue;
 use crossterm::terminal::{
     disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen,
 };
-use ratatui::backend::CrosstermBackend;
+use ratatui::backend::{Backend, CrosstermBackend};
+use crate::input::keyboard::{keyboard_protocol, set_keyboard_protocol, KeyboardProtocol};
 use ratatui::Terminal;
 use std::fmt;
 use std::io;
@@ -53,66 +57,159 @@ impl From<anyhow::Error> for TerminalError {
 // Note: `Terminal::new` returns an `io::Error` on failure in current `tui`.
 // If this changes, add a dedicated variant and `From` impl.
 
+/// Terminal setup and teardown for one ratatui backend.
+///
+/// The runner only talks to the terminal through this trait, so another
+/// backend can be swapped in with a cargo feature: crossterm is the default
+/// and `termion-backend` selects termion on Unix (see `DefaultBackend`).
+/// Input is abstracted the same way by `crate::input::EventSource`.
+pub trait TerminalBackend: Backend + Write + Sized {
+    /// Take over stdout: alternate screen, hidden cursor, mouse capture
+    /// and raw mode.
+    fn enter() -> Result<Self, TerminalError>;
+
+    /// Give the terminal back: undo everything `enter` and `resume` did.
+    fn leave(&mut self) -> Result<(), TerminalError>;
+
+    /// Take the terminal again after `leave` (mouse capture only when
+    /// `mouse_enabled`).
+    fn resume(&mut self, mouse_enabled: bool) -> Result<(), TerminalError>;
+
+    /// Turn mouse reporting on or off.
+    fn set_mouse_capture(&mut self, enabled: bool) -> Result<(), TerminalError>;
+
+    /// Best-effort `leave` without the backend, for panic and signal
+    /// handlers. Errors are ignored.
+    fn force_restore();
+}
+
+impl TerminalBackend for CrosstermBackend<Stdout> {
+    fn enter() -> Result<Self, TerminalError> {
+        let mut stdout = io::stdout();
+        // Enter alternate screen and enable mouse capture and focus
+        // reports (queued then flushed).
+        queue!(stdout, EnterAlternateScreen, EnableMouseCapture, EnableFocusChange, Hide)
+            .map_err(TerminalError::from)?;
+        stdout.flush().map_err(TerminalError::from)?;
+        enable_raw_mode().map_err(TerminalError::from)?;
+        // The capability query needs raw mode; terminals that do not answer
+        // keep the legacy key codes.
+        let kitty = crossterm::terminal::supports_keyboard_enhancement().unwrap_or(false);
+        set_keyboard_protocol(if kitty { KeyboardProtocol::Kitty } else { KeyboardProtocol::Legacy });
+        push_keyboard_flags(&mut stdout).map_err(TerminalError::from)?;
+        Ok(CrosstermBackend::new(stdout))
+    }
+
+    fn leave(&mut self) -> Result<(), TerminalError> {
+        // Try to disable raw mode first; return if that fails.
+        disable_raw_mode().map_err(TerminalError::from)?;
+        pop_keyboard_flags(self).map_err(TerminalError::from)?;
+        queue!(self, DisableMouseCapture, DisableFocusChange, LeaveAlternateScreen, Show)
+            .map_err(TerminalError::from)?;
+        Write::flush(self).map_err(TerminalError::from)
+    }
+
+    fn resume(&mut self, mouse_enabled: bool) -> Result<(), TerminalError> {
+        queue!(self, EnterAlternateScreen, EnableFocusChange, Hide).map_err(TerminalError::from)?;
+        if mouse_enabled {
+            queue!(self, EnableMouseCapture).map_err(TerminalError::from)?;
+        }
+        push_keyboard_flags(self).map_err(TerminalError::from)?;
+        Write::flush(self).map_err(TerminalError::from)?;
+        enable_raw_mode().map_err(TerminalError::from)
+    }
+
+    fn set_mouse_capture(&mut self, enabled: bool) -> Result<(), TerminalError> {
+        if enabled {
+            queue!(self, EnableMouseCapture).map_err(TerminalError::from)?;
+        } else {
+            queue!(self, DisableMouseCapture).map_err(TerminalError::from)?;
+        }
+        Write::flush(self).map_err(TerminalError::from)
+    }
+
+    fn force_restore() {
+        // Attempt to disable raw mode first.
+        let _ = disable_raw_mode();
+        // Try to leave alternate screen, disable mouse capture and show cursor.
+        let mut stdout = io::stdout();
+        let _ = pop_keyboard_flags(&mut stdout);
+        let _ = queue!(stdout, DisableMouseCapture, DisableFocusChange, LeaveAlternateScreen, Show);
+        let _ = stdout.flush();
+        let _ = crossterm::execute!(io::stdout(), crossterm::cursor::Show);
+    }
+}
+
+/// Ask a kitty-protocol terminal for unambiguous key codes (Ctrl+Shift,
+/// Super) once `enter` found it supports them; no-op otherwise.
+pub(crate) fn push_keyboard_flags(w: &mut impl Write) -> io::Result<()> {
+    if keyboard_protocol() == KeyboardProtocol::Kitty {
+        let flags = KeyboardEnhancementFlags::DISAMBIGUATE_ESCAPE_CODES
+            | KeyboardEnhancementFlags::REPORT_ALTERNATE_KEYS;
+        queue!(w, PushKeyboardEnhancementFlags(flags))?;
+        w.flush()?;
+    }
+    Ok(())
+}
+
+/// Undo `push_keyboard_flags`.
+pub(crate) fn pop_keyboard_flags(w: &mut impl Write) -> io::Result<()> {
+    if keyboard_protocol() == KeyboardProtocol::Kitty {
+        queue!(w, PopKeyboardEnhancementFlags)?;
+        w.flush()?;
+    }
+    Ok(())
+}
+
+/// Backend of the fileZoom binary: crossterm unless the `termion-backend`
+/// feature is enabled on a Unix target.
+#[cfg(not(all(unix, feature = "termion-backend")))]
+pub type DefaultBackend = CrosstermBackend<Stdout>;
+/// Backend of the fileZoom binary: crossterm unless the `termion-backend`
+/// feature is enabled on a Unix target.
+#[cfg(all(unix, feature = "termion-backend"))]
+pub type DefaultBackend = super::termion_backend::TermionTerminal;
+
 /// RAII wrapper around a `Terminal` which restores the terminal state on Drop
 /// (leave alternate screen, disable mouse capture, disable raw mode).
-pub struct TerminalGuard {
-    terminal: Terminal<CrosstermBackend<Stdout>>,
+pub struct TerminalGuard<B: TerminalBackend = DefaultBackend> {
+    terminal: Terminal<B>,
     restored: bool,
 }
 
-impl Deref for TerminalGuard {
-    type Target = Terminal<CrosstermBackend<Stdout>>;
+impl<B: TerminalBackend> Deref for TerminalGuard<B> {
+    type Target = Terminal<B>;
     fn deref(&self) -> &Self::Target {
         &self.terminal
     }
 }
 
-impl DerefMut for TerminalGuard {
+impl<B: TerminalBackend> DerefMut for TerminalGuard<B> {
     fn deref_mut(&mut self) -> &mut Self::Target {
         &mut self.terminal
     }
 }
 
-impl TerminalGuard {
+impl<B: TerminalBackend> TerminalGuard<B> {
     /// Create a new terminal guard. This will enter the alternate screen,
     /// enable mouse capture and enable raw mode. If creation fails, the
     /// terminal is not left in raw mode.
     pub fn new() -> Result<Self, TerminalError> {
-        let mut stdout = io::stdout();
-        // Enter alternate screen and enable mouse capture (queued then flushed).
-        // Also hide the cursor and enable bracketed paste if available.
-        queue!(stdout, EnterAlternateScreen, EnableMouseCapture, Hide)
-            .map_err(TerminalError::from)?;
-        stdout.flush().map_err(TerminalError::from)?;
-        let backend = CrosstermBackend::new(stdout);
-        let terminal = Terminal::new(backend).map_err(TerminalError::from)?;
-        // Only enable raw mode after Terminal::new succeeds so failures don't leave raw mode enabled.
-        enable_raw_mode().map_err(TerminalError::from)?;
-        Ok(TerminalGuard {
-            terminal,
-            restored: false,
-        })
+        let backend = B::enter()?;
+        match Terminal::new(backend) {
+            Ok(terminal) => Ok(TerminalGuard { terminal, restored: false }),
+            Err(e) => {
+                B::force_restore();
+                Err(TerminalError::from(e))
+            }
+        }
     }
 
     /// Consume the guard and explicitly restore terminal state. This is
     /// equivalent to letting the guard be dropped but returns any IO error.
     pub fn restore(mut self) -> Result<(), TerminalError> {
         if !self.restored {
-            // Try to disable raw mode first; ignore errors on subsequent steps but return if raw mode disable fails.
-            disable_raw_mode().map_err(TerminalError::from)?;
-            // Leave alternate screen and disable mouse capture (queued then flushed).
-            queue!(
-                self.terminal.backend_mut(),
-                DisableMouseCapture,
-                LeaveAlternateScreen,
-                Show
-            )
-            .map_err(TerminalError::from)?;
-            // flush backend if possible
-            if let Err(e) = self.terminal.backend_mut().flush() {
-                // best effort: report as Io error
-                return Err(e.into());
-            }
+            self.terminal.backend_mut().leave()?;
             self.terminal.show_cursor().map_err(TerminalError::from)?;
             self.restored = true;
         }
@@ -120,20 +217,13 @@ impl TerminalGuard {
     }
 }
 
-impl Drop for TerminalGuard {
+impl<B: TerminalBackend> Drop for TerminalGuard<B> {
     fn drop(&mut self) {
         if self.restored {
             return;
         }
         // Best-effort restore on drop. Errors are ignored here to avoid panics during unwinding.
-        let _ = disable_raw_mode();
-        let _ = queue!(
-            self.terminal.backend_mut(),
-            DisableMouseCapture,
-            LeaveAlternateScreen,
-            Show
-        );
-        let _ = self.terminal.backend_mut().flush();
+        let _ = self.terminal.backend_mut().leave();
         let _ = self.terminal.show_cursor();
         self.restored = true;
     }
@@ -144,45 +234,112 @@ pub fn init_terminal() -> Result<TerminalGuard, TerminalError> {
     TerminalGuard::new()
 }
 
+/// Keep the screen off a redirected stdout, so `$(fileZoom
+/// --choose-files)` captures only the chosen paths: stdout is pointed at
+/// the controlling terminal and the returned file is the original stdout.
+/// When stdout is the terminal already, the returned file is a copy of it.
+#[cfg(unix)]
+pub fn detach_stdout() -> io::Result<std::fs::File> {
+    use std::io::IsTerminal;
+    use std::os::fd::{AsFd, AsRawFd};
+    let original = std::fs::File::from(io::stdout().as_fd().try_clone_to_owned()?);
+    if !io::stdout().is_terminal() {
+        let tty = std::fs::OpenOptions::new().read(true).write(true).open("/dev/tty")?;
+        if unsafe { libc::dup2(tty.as_raw_fd(), libc::STDOUT_FILENO) } < 0 {
+            return Err(io::Error::last_os_error());
+        }
+    }
+    Ok(original)
+}
+
 /// Enable mouse capture on an existing terminal instance.
-pub fn enable_mouse_capture_on_terminal(terminal: &mut TerminalGuard) -> Result<(), TerminalError> {
-    queue!(terminal.backend_mut(), EnableMouseCapture).map_err(TerminalError::from)?;
-    terminal
-        .backend_mut()
-        .flush()
-        .map_err(TerminalError::from)?;
-    Ok(())
+pub fn enable_mouse_capture_on_terminal<B: TerminalBackend>(terminal: &mut TerminalGuard<B>) -> Result<(), TerminalError> {
+    terminal.backend_mut().set_mouse_capture(true)
 }
 
 /// Disable mouse capture on an existing terminal instance.
-pub fn disable_mouse_capture_on_terminal(
-    terminal: &mut TerminalGuard,
+pub fn disable_mouse_capture_on_terminal<B: TerminalBackend>(
+    terminal: &mut TerminalGuard<B>,
 ) -> Result<(), TerminalError> {
-    queue!(terminal.backend_mut(), DisableMouseCapture).map_err(TerminalError::from)?;
-    terminal
-        .backend_mut()
-        .flush()
-        .map_err(TerminalError::from)?;
-    Ok(())
+    terminal.backend_mut().set_mouse_capture(false)
 }
 
 /// Restore terminal state (leave alternate screen + disable raw mode) and show cursor.
-pub fn restore_terminal(terminal: TerminalGuard) -> Result<(), TerminalError> {
+pub fn restore_terminal<B: TerminalBackend>(terminal: TerminalGuard<B>) -> Result<(), TerminalError> {
     terminal.restore()
 }
 
+/// Return the shell to launch when suspending to a shell.
+///
+/// Uses `$SHELL` on Unix (falling back to `/bin/sh`) and `%COMSPEC%` on
+/// Windows (falling back to `cmd.exe`). Empty values are ignored.
+pub fn user_shell() -> std::path::PathBuf {
+    let (var, fallback) = if cfg!(windows) { ("COMSPEC", "cmd.exe") } else { ("SHELL", "/bin/sh") };
+    std::env::var_os(var)
+        .filter(|v| !v.is_empty())
+        .map(std::path::PathBuf::from)
+        .unwrap_or_else(|| std::path::PathBuf::from(fallback))
+}
+
+/// Temporarily hand the terminal back to the user by running an interactive
+/// shell in `cwd`, then re-enter the TUI once the shell exits.
+///
+/// The alternate screen, raw mode and mouse capture are released before the
+/// shell starts and re-established afterwards (mouse capture only when
+/// `mouse_enabled`). The terminal is cleared so the next draw repaints the
+/// whole screen. A failure to spawn the shell is returned after the TUI
+/// has been restored.
+pub fn suspend_to_shell<B: TerminalBackend>(
+    terminal: &mut TerminalGuard<B>,
+    cwd: &std::path::Path,
+    mouse_enabled: bool,
+) -> Result<std::process::ExitStatus, TerminalError> {
+    terminal.backend_mut().leave()?;
+    println!("Type `exit` to return to fileZoom.");
+
+    let status = std::process::Command::new(user_shell()).current_dir(cwd).status();
+
+    terminal.backend_mut().resume(mouse_enabled)?;
+    terminal.clear().map_err(TerminalError::from)?;
+    status.map_err(TerminalError::from)
+}
+
+/// Set when a program ran outside the TUI; see `take_repaint`.
+static REPAINT: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+
+/// Run `f`, which starts a program talking to the user (an editor, `sudo`
+/// asking for a password), with the terminal handed back: out of raw mode
+/// and the alternate screen, mouse capture off and the cursor shown. The
+/// TUI is re-entered afterwards even when `f` failed; `f`'s error is
+/// returned first.
+pub fn outside_tui<T>(f: impl FnOnce() -> io::Result<T>) -> io::Result<T> {
+    disable_raw_mode()?;
+    let mut stdout = io::stdout();
+    let _ = pop_keyboard_flags(&mut stdout);
+    let _ = crossterm::execute!(stdout, LeaveAlternateScreen, DisableMouseCapture, DisableFocusChange, Show);
+
+    let res = f();
+
+    let _ = crossterm::execute!(stdout, Hide, EnableMouseCapture, EnableFocusChange, EnterAlternateScreen);
+    let _ = push_keyboard_flags(&mut stdout);
+    REPAINT.store(true, std::sync::atomic::Ordering::SeqCst);
+    let raw = enable_raw_mode();
+    let res = res?;
+    raw.map(|()| res)
+}
+
+/// Whether `outside_tui` ran since the last call: the alternate screen was
+/// left, so the next frame has to repaint everything.
+pub fn take_repaint() -> bool {
+    REPAINT.swap(false, std::sync::atomic::Ordering::SeqCst)
+}
+
 /// Best-effort force restore of the terminal state without owning a `TerminalGuard`.
 /// This is intended for use from signal handlers or panic hooks where ownership of the
 /// application's `TerminalGuard` is not available. It performs the same steps as
-/// `TerminalGuard::restore` but uses `stdout` directly and ignores most errors.
+/// `TerminalGuard::restore` for the `DefaultBackend` but ignores errors.
 pub fn force_restore() {
-    // Attempt to disable raw mode first.
-    let _ = disable_raw_mode();
-    // Try to leave alternate screen, disable mouse capture and show cursor.
-    let mut stdout = io::stdout();
-    let _ = queue!(stdout, DisableMouseCapture, LeaveAlternateScreen, Show);
-    let _ = stdout.flush();
-    let _ = crossterm::execute!(io::stdout(), crossterm::cursor::Show);
+    DefaultBackend::force_restore();
 }
 
 #[cfg(test)]
@@ -195,4 +352,9 @@ mod tests {
         force_restore();
         force_restore();
     }
+
+    #[test]
+    fn user_shell_is_never_empty() {
+        assert!(!user_shell().as_os_str().is_empty());
+    }
 }
@@ -0,0 +1,83 @@
+//! termion implementation of `TerminalBackend` (feature `termion-backend`,
+//! Unix only).
+//!
+//! termion keeps raw mode in an RAII `RawTerminal`, which ratatui's
+//! `TermionBackend` does not give access to once it owns it. The
+//! `RawTerminal` is therefore kept here, next to the backend, so raw mode
+//! can be suspended around a shell and from `force_restore`. The alternate
+//! screen, cursor and mouse reporting are plain escape sequences.
+
+use std::io::{self, Stdout, Write};
+use std::sync::Mutex;
+
+use ratatui::backend::TermionBackend;
+use termion::cursor::{Hide, Show};
+use termion::raw::{IntoRawMode, RawTerminal};
+use termion::screen::{ToAlternateScreen, ToMainScreen};
+
+use super::terminal::{TerminalBackend, TerminalError};
+
+/// The termion backend as used by the runner.
+pub type TermionTerminal = TermionBackend<Stdout>;
+
+/// Same sequences as termion's `MouseTerminal` (X10, button motion, urxvt
+/// and SGR reporting), which only offers them through its RAII wrapper.
+const MOUSE_ON: &str = "\x1b[?1000h\x1b[?1002h\x1b[?1015h\x1b[?1006h";
+const MOUSE_OFF: &str = "\x1b[?1006l\x1b[?1015l\x1b[?1002l\x1b[?1000l";
+
+/// Raw mode of stdout, set by `enter`. Dropping it restores the original
+/// terminal attributes.
+static RAW: Mutex<Option<RawTerminal<Stdout>>> = Mutex::new(None);
+
+/// Run `f` on the raw-mode handle, if raw mode was entered.
+fn with_raw(f: impl FnOnce(&RawTerminal<Stdout>) -> io::Result<()>) -> io::Result<()> {
+    match RAW.lock().unwrap_or_else(|e| e.into_inner()).as_ref() {
+        Some(raw) => f(raw),
+        None => Ok(()),
+    }
+}
+
+impl TerminalBackend for TermionTerminal {
+    fn enter() -> Result<Self, TerminalError> {
+        let mut stdout = io::stdout();
+        write!(stdout, "{}{}{}", ToAlternateScreen, MOUSE_ON, Hide).map_err(TerminalError::from)?;
+        stdout.flush().map_err(TerminalError::from)?;
+        let raw = io::stdout().into_raw_mode().map_err(TerminalError::from)?;
+        *RAW.lock().unwrap_or_else(|e| e.into_inner()) = Some(raw);
+        Ok(TermionBackend::new(stdout))
+    }
+
+    fn leave(&mut self) -> Result<(), TerminalError> {
+        with_raw(|raw| raw.suspend_raw_mode()).map_err(TerminalError::from)?;
+        write!(self, "{}{}{}", MOUSE_OFF, ToMainScreen, Show).map_err(TerminalError::from)?;
+        Write::flush(self).map_err(TerminalError::from)
+    }
+
+    fn resume(&mut self, mouse_enabled: bool) -> Result<(), TerminalError> {
+        write!(self, "{}{}", ToAlternateScreen, Hide).map_err(TerminalError::from)?;
+        if mouse_enabled {
+            self.write_all(MOUSE_ON.as_bytes()).map_err(TerminalError::from)?;
+        }
+        Write::flush(self).map_err(TerminalError::from)?;
+        with_raw(|raw| raw.activate_raw_mode()).map_err(TerminalError::from)
+    }
+
+    fn set_mouse_capture(&mut self, enabled: bool) -> Result<(), TerminalError> {
+        let seq = if enabled { MOUSE_ON } else { MOUSE_OFF };
+        self.write_all(seq.as_bytes()).map_err(TerminalError::from)?;
+        Write::flush(self).map_err(TerminalError::from)
+    }
+
+    fn force_restore() {
+        // `try_lock`: a panic hook must not wait on a lock held by the
+        // panicking thread.
+        if let Ok(raw) = RAW.try_lock() {
+            if let Some(raw) = raw.as_ref() {
+                let _ = raw.suspend_raw_mode();
+            }
+        }
+        let mut stdout = io::stdout();
+        let _ = write!(stdout, "{}{}{}", MOUSE_OFF, ToMainScreen, Show);
+        let _ = stdout.flush();
+    }
+}
@@ -0,0 +1,100 @@
+//! Wake-ups of the event loop.
+//!
+//! The event loop sleeps in `tokio::select!` until terminal input arrives,
+//! a timer it set runs out (a key sequence, a watcher debounce, the job
+//! spinner) or a background thread calls `wake`. Threads keep sending
+//! their results over `std::sync::mpsc` channels; `wake` only tells the
+//! loop to look at them. `forward` does that for a whole channel, and
+//! `Pending` workers wake the loop once their result is sent.
+
+use std::sync::mpsc::{self, Receiver};
+use std::time::{Duration, Instant};
+
+use once_cell::sync::Lazy;
+use tokio::sync::Notify;
+
+static WAKE: Lazy<Notify> = Lazy::new(Notify::new);
+
+/// Wake the event loop. A wake-up while the loop is busy is kept until it
+/// next waits, so none is lost.
+pub fn wake() {
+    WAKE.notify_one();
+}
+
+/// Resolves after the next `wake`.
+pub async fn woken() {
+    WAKE.notified().await
+}
+
+/// A receiver of everything sent to `rx`, waking the event loop for each
+/// message. The receiver disconnects once `rx` has and everything was
+/// passed on.
+pub fn forward<T: Send + 'static>(rx: Receiver<T>) -> Receiver<T> {
+    let (tx, out) = mpsc::channel();
+    std::thread::spawn(move || {
+        for msg in rx {
+            if tx.send(msg).is_err() {
+                break;
+            }
+            wake();
+        }
+        // Let the loop see the disconnect.
+        drop(tx);
+        wake();
+    });
+    out
+}
+
+/// The earliest of the times the event loop has to wake up on its own.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Deadline(Option<Instant>);
+
+impl Deadline {
+    /// Wake up at `at` at the latest.
+    pub fn at(&mut self, at: Instant) {
+        self.0 = Some(self.0.map_or(at, |t| t.min(at)));
+    }
+
+    /// Wake up `after` from `now` at the latest.
+    pub fn within(&mut self, now: Instant, after: Duration) {
+        self.at(now + after);
+    }
+
+    pub fn instant(&self) -> Option<Instant> {
+        self.0
+    }
+
+    /// Resolves at the deadline, or never without one.
+    pub async fn sleep(self) {
+        match self.0 {
+            Some(t) => tokio::time::sleep_until(t.into()).await,
+            None => std::future::pending().await,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deadline_keeps_the_earliest_time() {
+        let now = Instant::now();
+        let mut d = Deadline::default();
+        assert_eq!(d.instant(), None);
+        d.within(now, Duration::from_secs(5));
+        d.within(now, Duration::from_secs(1));
+        d.at(now + Duration::from_secs(3));
+        assert_eq!(d.instant(), Some(now + Duration::from_secs(1)));
+    }
+
+    #[test]
+    fn forward_passes_messages_on_and_disconnects() {
+        let (tx, rx) = mpsc::channel();
+        let rx = forward(rx);
+        tx.send(1).unwrap();
+        tx.send(2).unwrap();
+        drop(tx);
+        assert_eq!(rx.iter().collect::<Vec<_>>(), [1, 2]);
+    }
+}
@@ -33,9 +33,151 @@ pub(crate) fn affected_sides_from_fs_event(
     affected
 }
 
+/// Drop metadata cache entries for the paths named by `evt`.
+pub(crate) fn invalidate_cache_for(evt: &crate::fs_op::watcher::FsEvent) {
+    use crate::fs_op::watcher::FsEvent;
+    let cache = crate::fs_op::cache::global();
+    match evt {
+        FsEvent::Create(p) | FsEvent::Modify(p) | FsEvent::Remove(p) => cache.invalidate(p),
+        FsEvent::Rename(a, b) => {
+            cache.invalidate(a);
+            cache.invalidate(b);
+        }
+        FsEvent::Other => {}
+    }
+}
+
+/// Coalesces bursts of watcher events into one refresh per panel.
+///
+/// A side becomes due once no event touched it for `quiet`, or at the latest
+/// `max_wait` after the first event of the burst, so a directory that is
+/// written to continuously (for example during a large copy) still updates
+/// periodically.
+#[derive(Debug)]
+pub(crate) struct RefreshDebouncer {
+    quiet: std::time::Duration,
+    max_wait: std::time::Duration,
+    /// Per side (Left, Right): first and latest event time of the pending burst.
+    pending: [Option<(std::time::Instant, std::time::Instant)>; 2],
+}
+
+impl RefreshDebouncer {
+    pub(crate) fn new(quiet: std::time::Duration, max_wait: std::time::Duration) -> Self {
+        RefreshDebouncer { quiet, max_wait, pending: [None, None] }
+    }
+
+    fn slot(side: crate::app::Side) -> usize {
+        match side {
+            crate::app::Side::Left => 0,
+            crate::app::Side::Right => 1,
+        }
+    }
+
+    /// Record an event affecting `side` at `now`.
+    pub(crate) fn push(&mut self, side: crate::app::Side, now: std::time::Instant) {
+        let slot = &mut self.pending[Self::slot(side)];
+        *slot = Some(match *slot {
+            Some((first, _)) => (first, now),
+            None => (now, now),
+        });
+    }
+
+    /// Sides whose burst has settled by `now`; they are removed from the
+    /// pending set.
+    pub(crate) fn take_due(&mut self, now: std::time::Instant) -> Vec<crate::app::Side> {
+        use crate::app::Side;
+        let mut due = Vec::new();
+        for side in [Side::Left, Side::Right] {
+            let slot = &mut self.pending[Self::slot(side)];
+            if let Some((first, last)) = *slot {
+                if now.duration_since(last) >= self.quiet || now.duration_since(first) >= self.max_wait {
+                    *slot = None;
+                    due.push(side);
+                }
+            }
+        }
+        due
+    }
+
+    /// When the next pending side becomes due, if any.
+    pub(crate) fn next_due(&self) -> Option<std::time::Instant> {
+        self.pending.iter().flatten().map(|&(first, last)| (last + self.quiet).min(first + self.max_wait)).min()
+    }
+}
+
+/// Sides whose currently previewed file is the target of a `Modify` event.
+///
+/// `left`/`right` are the paths of the files shown in each panel's preview
+/// (`None` for directories or when nothing is selected).
+pub(crate) fn previewed_sides_from_fs_event(
+    evt: &crate::fs_op::watcher::FsEvent,
+    left: Option<&std::path::Path>,
+    right: Option<&std::path::Path>,
+) -> Vec<crate::app::Side> {
+    use crate::app::Side;
+    use crate::fs_op::watcher::FsEvent;
+
+    let FsEvent::Modify(p) = evt else {
+        return Vec::new();
+    };
+    [(Side::Left, left), (Side::Right, right)]
+        .into_iter()
+        .filter(|(_, previewed)| *previewed == Some(p.as_path()))
+        .map(|(side, _)| side)
+        .collect()
+}
+
+/// Limits how often a panel's preview is re-read while its file keeps
+/// changing. The first change refreshes immediately; further changes within
+/// `interval` are folded into one refresh at the end of the interval.
+#[derive(Debug)]
+pub(crate) struct PreviewThrottle {
+    interval: std::time::Duration,
+    /// Per side (Left, Right): time of the last refresh.
+    last: [Option<std::time::Instant>; 2],
+    /// Per side: a change is waiting to be shown.
+    pending: [bool; 2],
+}
+
+impl PreviewThrottle {
+    pub(crate) fn new(interval: std::time::Duration) -> Self {
+        PreviewThrottle { interval, last: [None, None], pending: [false, false] }
+    }
+
+    /// Note a change to the file previewed on `side`.
+    pub(crate) fn mark(&mut self, side: crate::app::Side) {
+        self.pending[RefreshDebouncer::slot(side)] = true;
+    }
+
+    /// Sides whose preview should be re-read at `now`.
+    pub(crate) fn take_due(&mut self, now: std::time::Instant) -> Vec<crate::app::Side> {
+        use crate::app::Side;
+        let mut due = Vec::new();
+        for side in [Side::Left, Side::Right] {
+            let i = RefreshDebouncer::slot(side);
+            let ready = self.last[i].is_none_or(|t| now.duration_since(t) >= self.interval);
+            if self.pending[i] && ready {
+                self.pending[i] = false;
+                self.last[i] = Some(now);
+                due.push(side);
+            }
+        }
+        due
+    }
+
+    /// When the next pending preview refresh is due, if any.
+    pub(crate) fn next_due(&self) -> Option<std::time::Instant> {
+        (0..2)
+            .filter(|&i| self.pending[i])
+            .map(|i| self.last[i].map_or_else(std::time::Instant::now, |t| t + self.interval))
+            .min()
+    }
+}
+
 #[cfg(all(test, feature = "fs-watch"))]
 mod tests {
-    use super::affected_sides_from_fs_event;
+    use super::{affected_sides_from_fs_event, previewed_sides_from_fs_event, PreviewThrottle, RefreshDebouncer};
+    use std::time::{Duration, Instant};
     use crate::fs_op::watcher::FsEvent;
     use crate::app::Side;
 
@@ -57,4 +199,45 @@ mod tests {
         sides.sort_by_key(|s| match s { Side::Left => 0, Side::Right => 1 });
         assert_eq!(sides, vec![Side::Left, Side::Right]);
     }
+
+    #[test]
+    fn debouncer_waits_for_quiet_period_and_caps_bursts() {
+        let mut d = RefreshDebouncer::new(Duration::from_millis(200), Duration::from_secs(1));
+        let t0 = Instant::now();
+        d.push(Side::Left, t0);
+        d.push(Side::Left, t0 + Duration::from_millis(150));
+        assert_eq!(d.next_due(), Some(t0 + Duration::from_millis(350)));
+        assert!(d.take_due(t0 + Duration::from_millis(300)).is_empty());
+        assert_eq!(d.take_due(t0 + Duration::from_millis(350)), vec![Side::Left]);
+        assert!(d.take_due(t0 + Duration::from_secs(5)).is_empty());
+        assert_eq!(d.next_due(), None);
+
+        // A continuous burst still refreshes once `max_wait` has passed.
+        let t1 = t0 + Duration::from_secs(10);
+        for i in 0..12u64 {
+            d.push(Side::Right, t1 + Duration::from_millis(i * 100));
+        }
+        assert_eq!(d.take_due(t1 + Duration::from_millis(1100)), vec![Side::Right]);
+    }
+
+    #[test]
+    fn modify_of_previewed_file_is_throttled_per_side() {
+        let log = std::path::Path::new("/tmp/left/app.log");
+        let ev = FsEvent::Modify(log.to_path_buf());
+        assert_eq!(previewed_sides_from_fs_event(&ev, Some(log), None), vec![Side::Left]);
+        assert!(previewed_sides_from_fs_event(&FsEvent::Create(log.to_path_buf()), Some(log), None).is_empty());
+        assert!(previewed_sides_from_fs_event(&ev, Some(std::path::Path::new("/tmp/left/other")), None).is_empty());
+
+        let mut t = PreviewThrottle::new(Duration::from_millis(250));
+        let t0 = Instant::now();
+        assert!(t.take_due(t0).is_empty());
+        t.mark(Side::Left);
+        assert_eq!(t.take_due(t0), vec![Side::Left]);
+        assert_eq!(t.next_due(), None);
+        t.mark(Side::Left);
+        assert_eq!(t.next_due(), Some(t0 + Duration::from_millis(250)));
+        assert!(t.take_due(t0 + Duration::from_millis(100)).is_empty());
+        assert_eq!(t.take_due(t0 + Duration::from_millis(250)), vec![Side::Left]);
+        assert!(t.take_due(t0 + Duration::from_secs(1)).is_empty());
+    }
 }
@@ -0,0 +1,268 @@
+//! Scripting hooks (feature `scripting`).
+//!
+//! A script engine registers for hooks (`on_enter_directory`,
+//! `on_before_delete` and `on_key` for otherwise unbound keys) and is
+//! called with a read-only `HookContext` snapshot of the active panel.
+//! Scripts never touch `App` directly: they answer with a `HookOutcome`
+//! that may cancel the triggering action, show a message, or request
+//! `ScriptOp`s which the host performs through `fs_op`. Deleting is
+//! deliberately not part of that surface.
+//!
+//! Language backends implement `ScriptEngine` and are installed once at
+//! startup with `install_engine`; without an engine every hook is a no-op.
+//! The built-in backend is Rhai (`RhaiEngine`), which `load_init_script`
+//! installs for `init.rhai` in the config directory.
+
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use once_cell::sync::Lazy;
+
+use crate::app::core::App;
+use crate::app::settings::config_dirs::project_config_dir;
+
+mod rhai_engine;
+
+pub use rhai_engine::RhaiEngine;
+
+/// File name of the startup script inside the config directory.
+pub const INIT_SCRIPT_FILE: &str = "init.rhai";
+
+/// Events scripts can bind to.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Hook {
+    /// The active panel changed directory.
+    EnterDirectory,
+    /// The user asked to delete the selection (before confirmation).
+    BeforeDelete,
+    /// A key that has no built-in binding in normal mode.
+    Key(char),
+}
+
+impl Hook {
+    /// Name scripts use to bind the hook.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Hook::EnterDirectory => "on_enter_directory",
+            Hook::BeforeDelete => "on_before_delete",
+            Hook::Key(_) => "on_key",
+        }
+    }
+}
+
+/// Read-only view of the active panel passed to hooks.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct HookContext {
+    /// Working directory of the active panel.
+    pub cwd: PathBuf,
+    /// The multi-selection if any, else the entry under the cursor.
+    pub selected: Vec<PathBuf>,
+    /// Names of all entries in the active panel.
+    pub entries: Vec<String>,
+    /// The pressed key for `Hook::Key`.
+    pub key: Option<char>,
+}
+
+/// Filesystem operations a script may request.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ScriptOp {
+    CreateFile(PathBuf),
+    CreateDir(PathBuf),
+    Copy { from: PathBuf, to: PathBuf },
+    Move { from: PathBuf, to: PathBuf },
+    /// Change the active panel's directory.
+    ChangeDir(PathBuf),
+}
+
+/// What a hook asks the host to do.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct HookOutcome {
+    /// Abort the triggering action (only meaningful for `BeforeDelete`).
+    pub cancel: bool,
+    /// Message to show the user.
+    pub message: Option<String>,
+    /// Operations to perform, in order.
+    pub ops: Vec<ScriptOp>,
+}
+
+/// A scripting language backend.
+pub trait ScriptEngine: Send {
+    /// Whether a script is bound to `hook`; unbound hooks are skipped
+    /// without building a context.
+    fn handles(&self, hook: &Hook) -> bool;
+    /// Run the script bound to `hook`.
+    fn call(&mut self, hook: &Hook, ctx: &HookContext) -> anyhow::Result<HookOutcome>;
+}
+
+static ENGINE: Lazy<Mutex<Option<Box<dyn ScriptEngine>>>> = Lazy::new(|| Mutex::new(None));
+
+/// Install the script engine, replacing any previous one.
+pub fn install_engine(engine: Box<dyn ScriptEngine>) {
+    *ENGINE.lock().unwrap_or_else(|e| e.into_inner()) = Some(engine);
+}
+
+/// Remove the installed engine.
+pub fn clear_engine() {
+    *ENGINE.lock().unwrap_or_else(|e| e.into_inner()) = None;
+}
+
+/// Location of the startup script.
+pub fn init_script_path() -> PathBuf {
+    project_config_dir().join(INIT_SCRIPT_FILE)
+}
+
+/// Install a `RhaiEngine` for the startup script, if there is one.
+///
+/// Returns whether a script was installed; a script that does not compile
+/// or whose top level fails is an error and leaves the hooks unbound.
+pub fn load_init_script() -> anyhow::Result<bool> {
+    let path = init_script_path();
+    if !path.is_file() {
+        return Ok(false);
+    }
+    install_engine(Box::new(RhaiEngine::from_file(&path)?));
+    Ok(true)
+}
+
+/// Whether an engine is installed and bound to `hook`.
+pub fn is_bound(hook: &Hook) -> bool {
+    ENGINE.lock().unwrap_or_else(|e| e.into_inner()).as_ref().is_some_and(|e| e.handles(hook))
+}
+
+fn context_for(app: &App, hook: &Hook) -> HookContext {
+    let panel = app.active_panel();
+    let selected = if !panel.selections.is_empty() {
+        let mut idx: Vec<usize> = panel.selections.iter().copied().collect();
+        idx.sort_unstable();
+        idx.iter().filter_map(|&i| panel.entries.get(i).map(|e| e.path.clone())).collect()
+    } else {
+        panel.selected_entry().map(|e| vec![e.path.clone()]).unwrap_or_default()
+    };
+    HookContext {
+        cwd: panel.cwd.clone(),
+        selected,
+        entries: panel.entries.iter().map(|e| e.display_name().into_owned()).collect(),
+        key: match hook {
+            Hook::Key(c) => Some(*c),
+            _ => None,
+        },
+    }
+}
+
+/// Run `hook` for `app`, perform the requested operations and show the
+/// outcome's message (if any) in a message dialog.
+///
+/// Returns `None` when no script is bound. Script errors become a message
+/// in the outcome rather than failing the caller. Operations run in order
+/// and stop at the first failure, which is reported the same way.
+pub fn fire(app: &mut App, hook: Hook) -> Option<HookOutcome> {
+    if !is_bound(&hook) {
+        return None;
+    }
+    let ctx = context_for(app, &hook);
+    let result = {
+        let mut guard = ENGINE.lock().unwrap_or_else(|e| e.into_inner());
+        guard.as_mut()?.call(&hook, &ctx)
+    };
+    let mut outcome = match result {
+        Ok(o) => o,
+        Err(e) => HookOutcome { message: Some(format!("{} failed: {:#}", hook.name(), e)), ..Default::default() },
+    };
+    let ops = std::mem::take(&mut outcome.ops);
+    if !ops.is_empty() {
+        if let Err(e) = apply_ops(app, &ops) {
+            outcome.message = Some(format!("{}: {:#}", hook.name(), e));
+        }
+        let _ = app.refresh();
+    }
+    if let Some(msg) = &outcome.message {
+        app.mode = crate::app::Mode::Message {
+            title: "Script".to_string(),
+            content: msg.clone(),
+            buttons: vec!["OK".to_string()],
+            selected: 0,
+            actions: None,
+        };
+    }
+    Some(outcome)
+}
+
+fn apply_ops(app: &mut App, ops: &[ScriptOp]) -> anyhow::Result<()> {
+    for op in ops {
+        match op {
+            ScriptOp::CreateFile(p) => crate::fs_op::create::create_file(p)?,
+            ScriptOp::CreateDir(p) => crate::fs_op::create::create_dir_all(p)?,
+            ScriptOp::Copy { from, to } => crate::fs_op::mv::copy_path(from, to)?,
+            ScriptOp::Move { from, to } => crate::fs_op::mv::move_path(from, to)?,
+            ScriptOp::ChangeDir(p) => {
+                if !p.is_dir() {
+                    anyhow::bail!("{} is not a directory", p.display());
+                }
+                app.active_panel_mut().cwd = p.clone();
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Serialises the tests that install the process-wide engine.
+    static ENGINE_TESTS: Mutex<()> = Mutex::new(());
+
+    struct Recorder;
+
+    impl ScriptEngine for Recorder {
+        fn handles(&self, hook: &Hook) -> bool {
+            matches!(hook, Hook::Key('x'))
+        }
+
+        fn call(&mut self, _hook: &Hook, ctx: &HookContext) -> anyhow::Result<HookOutcome> {
+            Ok(HookOutcome {
+                message: Some(format!("{} entries", ctx.entries.len())),
+                ops: vec![ScriptOp::CreateDir(ctx.cwd.join("made"))],
+                ..Default::default()
+            })
+        }
+    }
+
+    #[test]
+    fn fire_runs_bound_hooks_and_applies_ops() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::write(tmp.path().join("a"), "").unwrap();
+        let opts = crate::app::StartOptions { start_dir: Some(tmp.path().to_path_buf()), ..Default::default() };
+        let mut app = App::with_options(&opts).unwrap();
+
+        let _lock = ENGINE_TESTS.lock().unwrap_or_else(|e| e.into_inner());
+        install_engine(Box::new(Recorder));
+        assert!(fire(&mut app, Hook::Key('y')).is_none());
+        let outcome = fire(&mut app, Hook::Key('x')).unwrap();
+        clear_engine();
+
+        assert_eq!(outcome.message.as_deref(), Some("1 entries"));
+        assert!(tmp.path().join("made").is_dir());
+        assert!(app.left.entries.iter().any(|e| e.name == "made"));
+        assert!(matches!(app.mode, crate::app::Mode::Message { .. }));
+    }
+
+    #[test]
+    fn before_delete_message_is_shown_with_the_confirmation() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::write(tmp.path().join("a.txt"), "").unwrap();
+        let opts = crate::app::StartOptions { start_dir: Some(tmp.path().to_path_buf()), ..Default::default() };
+        let mut app = App::with_options(&opts).unwrap();
+        app.settings.confirm_delete = true;
+        assert!(app.left.select_named("a.txt"));
+        let script = r#"fn on_before_delete(ctx) { `${ctx.selected.len()} file(s) go to the bin` }"#;
+
+        let _lock = ENGINE_TESTS.lock().unwrap_or_else(|e| e.into_inner());
+        install_engine(Box::new(RhaiEngine::new(script).unwrap()));
+        crate::runner::handlers::handle_key(&mut app, crate::input::KeyCode::Char('d'), 10).unwrap();
+        clear_engine();
+
+        let crate::app::Mode::Confirm { msg, .. } = &app.mode else { panic!("expected a confirmation, got {:?}", app.mode) };
+        assert!(msg.starts_with("1 file(s) go to the bin\n\nDelete a.txt?"), "{msg}");
+    }
+}
@@ -0,0 +1,199 @@
+//! Rhai backend for the script hooks.
+//!
+//! A script binds a hook by defining a function of that name taking the
+//! context map, e.g. `fn on_before_delete(ctx) { ... }`. The map has
+//! `cwd` (string), `selected` and `entries` (arrays of strings) and `key`
+//! (a one-character string for `on_key`, else `()`). The function answers
+//! with one of:
+//!
+//! - `()`: nothing to do;
+//! - a string: a message to show;
+//! - a map with any of `cancel` (bool), `message` (string) and `ops`, an
+//!   array of `#{ op: "create_file" | "create_dir" | "cd", path: ... }` and
+//!   `#{ op: "copy" | "move", from: ..., to: ... }` maps. Relative paths
+//!   are taken from `cwd`.
+//!
+//! `print` and `debug` go to the log, since the terminal belongs to the UI,
+//! and a run is cut off after `MAX_OPERATIONS` steps so a runaway loop
+//! cannot hang the UI.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, bail, Context};
+use rhai::{Array, CallFnOptions, Dynamic, Engine, Map, Scope, AST};
+
+use super::{Hook, HookContext, HookOutcome, ScriptEngine, ScriptOp};
+
+/// Rhai operations one hook call may take.
+pub const MAX_OPERATIONS: u64 = 1_000_000;
+
+/// A compiled Rhai script and the scope its top level left behind.
+pub struct RhaiEngine {
+    engine: Engine,
+    ast: AST,
+    scope: Scope<'static>,
+}
+
+impl RhaiEngine {
+    /// Compile `source` and run its top level once.
+    pub fn new(source: &str) -> anyhow::Result<Self> {
+        let mut engine = Engine::new();
+        engine.set_max_operations(MAX_OPERATIONS);
+        // The release defaults, which debug builds lower.
+        engine.set_max_expr_depths(64, 32);
+        engine.on_print(|s| tracing::info!("script: {}", s));
+        engine.on_debug(|s, _, pos| tracing::debug!("script {}: {}", pos, s));
+        let ast = engine.compile(source).map_err(|e| anyhow!("{}", e))?;
+        let mut scope = Scope::new();
+        engine.run_ast_with_scope(&mut scope, &ast).map_err(|e| anyhow!("{}", e))?;
+        Ok(RhaiEngine { engine, ast, scope })
+    }
+
+    /// Compile the script in the file at `path`.
+    pub fn from_file(path: &Path) -> anyhow::Result<Self> {
+        let source = std::fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+        Self::new(&source).with_context(|| format!("in {}", path.display()))
+    }
+}
+
+impl ScriptEngine for RhaiEngine {
+    fn handles(&self, hook: &Hook) -> bool {
+        self.ast.iter_functions().any(|f| f.name == hook.name() && f.params.len() == 1)
+    }
+
+    fn call(&mut self, hook: &Hook, ctx: &HookContext) -> anyhow::Result<HookOutcome> {
+        let options = CallFnOptions::new().eval_ast(false);
+        let result = self
+            .engine
+            .call_fn_with_options::<Dynamic>(options, &mut self.scope, &self.ast, hook.name(), (context_map(ctx),))
+            .map_err(|e| anyhow!("{}", e))?;
+        outcome_from(result, &ctx.cwd)
+    }
+}
+
+fn path_string(p: &Path) -> Dynamic {
+    p.to_string_lossy().into_owned().into()
+}
+
+fn context_map(ctx: &HookContext) -> Dynamic {
+    let mut map = Map::new();
+    map.insert("cwd".into(), path_string(&ctx.cwd));
+    map.insert("selected".into(), ctx.selected.iter().map(|p| path_string(p)).collect::<Array>().into());
+    map.insert("entries".into(), ctx.entries.iter().cloned().map(Dynamic::from).collect::<Array>().into());
+    map.insert("key".into(), ctx.key.map_or(Dynamic::UNIT, |c| c.to_string().into()));
+    map.into()
+}
+
+fn outcome_from(value: Dynamic, cwd: &Path) -> anyhow::Result<HookOutcome> {
+    if value.is_unit() {
+        return Ok(HookOutcome::default());
+    }
+    if value.is_string() {
+        return Ok(HookOutcome { message: Some(value.into_string().unwrap_or_default()), ..Default::default() });
+    }
+    let type_name = value.type_name();
+    let Some(mut map) = value.try_cast::<Map>() else {
+        bail!("expected (), a string or a map, got {}", type_name);
+    };
+    let mut outcome = HookOutcome::default();
+    if let Some(cancel) = map.remove("cancel") {
+        outcome.cancel = cancel.as_bool().map_err(|t| anyhow!("cancel: expected a bool, got {}", t))?;
+    }
+    if let Some(message) = map.remove("message") {
+        outcome.message = Some(message.into_string().map_err(|t| anyhow!("message: expected a string, got {}", t))?);
+    }
+    if let Some(ops) = map.remove("ops") {
+        let ops = ops.into_array().map_err(|t| anyhow!("ops: expected an array, got {}", t))?;
+        for (i, op) in ops.into_iter().enumerate() {
+            outcome.ops.push(op_from(op, cwd).with_context(|| format!("ops[{}]", i))?);
+        }
+    }
+    Ok(outcome)
+}
+
+fn op_from(value: Dynamic, cwd: &Path) -> anyhow::Result<ScriptOp> {
+    let type_name = value.type_name();
+    let Some(map) = value.try_cast::<Map>() else {
+        bail!("expected a map, got {}", type_name);
+    };
+    let path = |key: &str| -> anyhow::Result<PathBuf> {
+        let value = map.get(key).cloned().ok_or_else(|| anyhow!("missing `{}`", key))?;
+        let s = value.into_string().map_err(|t| anyhow!("{}: expected a string, got {}", key, t))?;
+        Ok(cwd.join(s))
+    };
+    let name = map.get("op").cloned().unwrap_or(Dynamic::UNIT).into_string().unwrap_or_default();
+    Ok(match name.as_str() {
+        "create_file" => ScriptOp::CreateFile(path("path")?),
+        "create_dir" => ScriptOp::CreateDir(path("path")?),
+        "cd" => ScriptOp::ChangeDir(path("path")?),
+        "copy" => ScriptOp::Copy { from: path("from")?, to: path("to")? },
+        "move" => ScriptOp::Move { from: path("from")?, to: path("to")? },
+        other => bail!("unknown op `{}`", other),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ctx(key: Option<char>) -> HookContext {
+        HookContext {
+            cwd: PathBuf::from("/work"),
+            selected: vec![PathBuf::from("/work/a.txt"), PathBuf::from("/work/b.log")],
+            entries: vec!["a.txt".into(), "b.log".into(), "c".into()],
+            key,
+        }
+    }
+
+    #[test]
+    fn runs_the_function_bound_to_a_hook() {
+        let script = r#"
+            fn on_before_delete(ctx) {
+                let logs = ctx.selected.filter(|p| p.ends_with(".log"));
+                if logs.is_empty() { return; }
+                #{ cancel: true, message: `keeping ${logs.len()} log(s) of ${ctx.entries.len()} entries` }
+            }
+            fn on_key(ctx) {
+                if ctx.key != "b" { return `no binding for ${ctx.key}`; }
+                #{ ops: [#{ op: "create_dir", path: "backup" },
+                         #{ op: "copy", from: ctx.selected[0], to: "backup/a.txt" }] }
+            }
+        "#;
+        let mut engine = RhaiEngine::new(script).unwrap();
+        assert!(engine.handles(&Hook::BeforeDelete) && engine.handles(&Hook::Key('x')));
+        assert!(!engine.handles(&Hook::EnterDirectory));
+
+        let outcome = engine.call(&Hook::BeforeDelete, &ctx(None)).unwrap();
+        assert!(outcome.cancel);
+        assert_eq!(outcome.message.as_deref(), Some("keeping 1 log(s) of 3 entries"));
+
+        let outcome = engine.call(&Hook::Key('q'), &ctx(Some('q'))).unwrap();
+        assert_eq!(outcome, HookOutcome { message: Some("no binding for q".into()), ..Default::default() });
+
+        let outcome = engine.call(&Hook::Key('b'), &ctx(Some('b'))).unwrap();
+        assert_eq!(
+            outcome.ops,
+            vec![
+                ScriptOp::CreateDir(PathBuf::from("/work/backup")),
+                ScriptOp::Copy { from: PathBuf::from("/work/a.txt"), to: PathBuf::from("/work/backup/a.txt") },
+            ]
+        );
+    }
+
+    #[test]
+    fn script_errors_are_reported() {
+        assert!(RhaiEngine::new("fn on_key(ctx) {").is_err());
+
+        let script = r#"
+            fn on_key(ctx) { #{ ops: [#{ op: "delete", path: "a.txt" }] } }
+            fn on_enter_directory(ctx) { loop {} }
+            fn on_before_delete(ctx) { 42 }
+        "#;
+        let mut engine = RhaiEngine::new(script).unwrap();
+        let err = engine.call(&Hook::Key('x'), &ctx(Some('x'))).unwrap_err();
+        assert_eq!(format!("{:#}", err), "ops[0]: unknown op `delete`");
+        assert!(engine.call(&Hook::EnterDirectory, &ctx(None)).is_err());
+        let err = engine.call(&Hook::BeforeDelete, &ctx(None)).unwrap_err();
+        assert!(err.to_string().contains("got i64"), "{err}");
+    }
+}
@@ -0,0 +1,95 @@
+// Controllable clock for tests of time-dependent logic.
+
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::app::core::clock::{self, Clock};
+
+/// A clock that only moves when told to.
+///
+/// `install` makes it the current thread's clock until the returned guard
+/// is dropped; clones share the same time, so a test can keep a handle and
+/// `advance` it while the code under test reads `clock::now()`.
+#[derive(Clone, Debug)]
+pub struct FakeClock {
+	now: Arc<Mutex<SystemTime>>,
+}
+
+impl FakeClock {
+	/// A clock frozen at `t`.
+	pub fn at(t: SystemTime) -> Self {
+		FakeClock { now: Arc::new(Mutex::new(t)) }
+	}
+
+	/// A clock frozen at `secs` seconds after the Unix epoch.
+	pub fn at_unix(secs: u64) -> Self {
+		Self::at(UNIX_EPOCH + Duration::from_secs(secs))
+	}
+
+	/// Move the clock forward by `d`.
+	pub fn advance(&self, d: Duration) {
+		*self.now.lock().unwrap() += d;
+	}
+
+	/// Jump to `t`.
+	pub fn set(&self, t: SystemTime) {
+		*self.now.lock().unwrap() = t;
+	}
+
+	/// Use this clock for `clock::now()` on the current thread until the
+	/// guard is dropped.
+	pub fn install(&self) -> ClockGuard {
+		let previous = clock::set_thread_clock(Some(Arc::new(self.clone())));
+		ClockGuard { previous }
+	}
+
+	/// Set the mtime of `path` to `ago` before this clock's current time,
+	/// for fixtures with deterministic timestamps.
+	pub fn set_mtime_ago(&self, path: &Path, ago: Duration) -> std::io::Result<()> {
+		let t = self.now() - ago;
+		filetime::set_file_mtime(path, filetime::FileTime::from_system_time(t))
+	}
+}
+
+impl Clock for FakeClock {
+	fn now(&self) -> SystemTime {
+		*self.now.lock().unwrap()
+	}
+}
+
+/// Restores the previous thread clock when dropped.
+#[must_use = "the fake clock is uninstalled when the guard is dropped"]
+pub struct ClockGuard {
+	previous: Option<Arc<dyn Clock>>,
+}
+
+impl Drop for ClockGuard {
+	fn drop(&mut self) {
+		clock::set_thread_clock(self.previous.take());
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn installed_clock_drives_now_until_dropped() {
+		let fake = FakeClock::at_unix(1_700_000_000);
+		{
+			let _guard = fake.install();
+			assert_eq!(clock::now(), UNIX_EPOCH + Duration::from_secs(1_700_000_000));
+			fake.advance(Duration::from_secs(90));
+			assert_eq!(clock::now(), UNIX_EPOCH + Duration::from_secs(1_700_000_090));
+
+			let tmp = tempfile::tempdir().unwrap();
+			let p = tmp.path().join("old.txt");
+			std::fs::write(&p, "x").unwrap();
+			fake.set_mtime_ago(&p, Duration::from_secs(3600)).unwrap();
+			let mtime = std::fs::metadata(&p).unwrap().modified().unwrap();
+			assert_eq!(clock::now().duration_since(mtime).unwrap(), Duration::from_secs(3600));
+		}
+		assert!(clock::now() > UNIX_EPOCH + Duration::from_secs(1_700_000_090), "system clock is back");
+	}
+}
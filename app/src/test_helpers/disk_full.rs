@@ -0,0 +1,29 @@
+//! Simulated "no space left on device" for copy tests.
+//!
+//! `simulate_disk_full(n)` lets copies write `n` more bytes and then fail
+//! with ENOSPC, so cleanup and error reporting can be tested without a full
+//! filesystem. The limit is lifted when the returned guard is dropped; the
+//! guard also holds the fs_op test lock, so such tests run one at a time.
+//! For a real full filesystem see `building::disk_full`.
+
+use std::sync::MutexGuard;
+
+use crate::fs_op::test_helpers as hooks;
+
+/// Keeps the simulated limit in place until dropped.
+pub struct DiskFullGuard {
+    _lock: MutexGuard<'static, ()>,
+}
+
+/// Let copies write `free_bytes` more bytes before failing with ENOSPC.
+pub fn simulate_disk_full(free_bytes: u64) -> DiskFullGuard {
+    let lock = hooks::acquire_test_lock();
+    hooks::set_simulated_free_space(Some(free_bytes));
+    DiskFullGuard { _lock: lock }
+}
+
+impl Drop for DiskFullGuard {
+    fn drop(&mut self) {
+        hooks::set_simulated_free_space(None);
+    }
+}
@@ -30,6 +30,20 @@ pub fn gen_name(i: usize, rng: &mut impl RngCore) -> String {
     }
 }
 
+/// Reference time (Unix seconds) that generated mtimes are offset from.
+/// Set `FILEZOOM_FIXTURE_NOW` to get the same timestamps on every run.
+pub fn fixture_now() -> i64 {
+    std::env::var("FILEZOOM_FIXTURE_NOW")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or_else(|| {
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_secs() as i64
+        })
+}
+
 /// Apply a variety of optional, OS-dependent metadata and features to a generated file.
 /// This keeps that logic isolated and makes it easier to test/reuse.
 pub fn apply_advanced_attrs(
@@ -71,10 +85,7 @@ pub fn apply_advanced_attrs(
         if rng.next_u32() % 100 < 50 {
             let days = rng.next_u64() as i64 % 365;
             let secs = rng.next_u64() as i64 % 86400;
-            let now = std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap()
-                .as_secs() as i64;
+            let now = fixture_now();
             let new_time = now - (days * 86400 + secs);
             let ft = FileTime::from_unix_time(new_time, 0);
             let _ = filetime::set_file_mtime(fullpath, ft);
@@ -127,6 +138,109 @@ pub fn apply_advanced_attrs(
     created
 }
 
+/// Directory (inside the fixtures dir) holding the permission scenarios.
+pub const PERMISSION_SCENARIOS_DIR: &str = "perm_denied";
+
+/// Uid/gid that `other_owner.txt` is given when running as root (`nobody`).
+pub const OTHER_OWNER_ID: u32 = 65534;
+
+/// Create a set of entries the current user cannot fully access so the
+/// app's permission-error handling can be exercised end-to-end:
+///
+/// - `no_access_dir/` with mode 000 (cannot be listed or entered)
+/// - `unreadable.txt` with mode 000
+/// - `unreadable_tree/listable/...` with a `write_only/` directory (mode
+///   300: can be entered but not listed) and a `locked/` subtree (mode 000)
+/// - `other_owner.txt` owned by `OTHER_OWNER_ID` (only when running as
+///   root, as inside the Docker image) with mode 600
+///
+/// Modes are applied after the contents are written. Root ignores them, so
+/// run the app as an unprivileged user to see the errors. Returns the
+/// created paths for the manifest.
+pub fn create_permission_scenarios(fixtures_dir: &Path) -> Vec<PathBuf> {
+    let mut created: Vec<PathBuf> = Vec::new();
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let base = fixtures_dir.join(PERMISSION_SCENARIOS_DIR);
+        let tree = base.join("unreadable_tree");
+        let no_access = base.join("no_access_dir");
+        let write_only = tree.join("listable").join("write_only");
+        let locked = tree.join("listable").join("locked");
+        for dir in [&no_access, &write_only, &locked.join("deeper")] {
+            if let Err(e) = fs::create_dir_all(dir) {
+                eprintln!("failed to create {}: {}", dir.display(), e);
+                return created;
+            }
+        }
+        let files = [
+            no_access.join("hidden.txt"),
+            base.join("unreadable.txt"),
+            tree.join("listable").join("readable.txt"),
+            write_only.join("known_name.txt"),
+            locked.join("deeper").join("buried.txt"),
+            base.join("other_owner.txt"),
+        ];
+        for f in &files {
+            let _ = fs::write(f, "permission fixture\n");
+        }
+        created.extend(files.iter().cloned());
+        created.extend([no_access.clone(), write_only.clone(), locked.clone()]);
+
+        // SAFETY: geteuid has no preconditions.
+        if unsafe { libc::geteuid() } == 0 {
+            let other = base.join("other_owner.txt");
+            if let Err(e) = std::os::unix::fs::chown(&other, Some(OTHER_OWNER_ID), Some(OTHER_OWNER_ID)) {
+                eprintln!("failed to chown {}: {}", other.display(), e);
+            }
+            let _ = fs::set_permissions(&other, fs::Permissions::from_mode(0o600));
+        }
+
+        // Innermost first so the parents are still writable while we work.
+        for (path, mode) in [
+            (base.join("unreadable.txt"), 0o000),
+            (write_only.clone(), 0o300),
+            (locked.clone(), 0o000),
+            (no_access.clone(), 0o000),
+        ] {
+            if let Err(e) = fs::set_permissions(&path, fs::Permissions::from_mode(mode)) {
+                eprintln!("failed to set mode {:o} on {}: {}", mode, path.display(), e);
+            }
+        }
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = fixtures_dir;
+        println!("Permission scenarios are only supported on Unix");
+    }
+    created
+}
+
+/// Give the permission scenarios back their owner's access so the tree can
+/// be copied or removed.
+pub fn unlock_permission_scenarios(fixtures_dir: &Path) {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fn walk(dir: &Path) {
+            let _ = fs::set_permissions(dir, fs::Permissions::from_mode(0o755));
+            if let Ok(rd) = fs::read_dir(dir) {
+                for e in rd.flatten() {
+                    let p = e.path();
+                    if e.file_type().map(|t| t.is_dir()).unwrap_or(false) {
+                        walk(&p);
+                    } else {
+                        let _ = fs::set_permissions(&p, fs::Permissions::from_mode(0o644));
+                    }
+                }
+            }
+        }
+        walk(&fixtures_dir.join(PERMISSION_SCENARIOS_DIR));
+    }
+    #[cfg(not(unix))]
+    let _ = fixtures_dir;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -140,4 +254,23 @@ mod tests {
         let n8 = gen_name(8, &mut rng);
         assert!(n8.starts_with("very-long-name-"));
     }
+
+    #[cfg(unix)]
+    #[test]
+    fn permission_scenarios_have_restricted_modes() {
+        use std::os::unix::fs::PermissionsExt;
+        let tmp = tempfile::tempdir().unwrap();
+        let created = create_permission_scenarios(tmp.path());
+        let base = tmp.path().join(PERMISSION_SCENARIOS_DIR);
+        assert!(created.contains(&base.join("unreadable.txt")));
+        let mode = |p: &Path| fs::metadata(p).unwrap().permissions().mode() & 0o777;
+        assert_eq!(mode(&base.join("no_access_dir")), 0o000);
+        assert_eq!(mode(&base.join("unreadable.txt")), 0o000);
+        assert_eq!(mode(&base.join("unreadable_tree/listable/write_only")), 0o300);
+        assert_eq!(mode(&base.join("unreadable_tree/listable/locked")), 0o000);
+
+        unlock_permission_scenarios(tmp.path());
+        assert_eq!(mode(&base.join("no_access_dir")), 0o755);
+        assert!(base.join("no_access_dir/hidden.txt").exists());
+    }
 }
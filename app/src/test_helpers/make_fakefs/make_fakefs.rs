@@ -5,6 +5,7 @@ mod advanced;
 mod build;
 mod fixtures;
 mod run;
+mod serve;
 
 use std::env;
 use std::path::PathBuf;
@@ -17,7 +18,7 @@ use std::process::exit;
 fn main() {
     let args: Vec<String> = env::args().collect();
     if args.len() < 2 {
-        eprintln!("Usage: make_fakefs <build|generate-fixtures|apply-permissions|run>");
+        eprintln!("Usage: make_fakefs <build|generate-fixtures|apply-permissions|permission-scenarios|run|serve>");
         exit(1);
     }
     let cmd = args[1].as_str();
@@ -39,6 +40,18 @@ fn main() {
             };
             fixtures::apply_permissions(&fixtures);
         }
+        "permission-scenarios" => {
+            // Creates mode-000 dirs, unreadable subtrees and (as root) files
+            // owned by another user. Run inside the image: such entries
+            // cannot be copied into a build context by an unprivileged user.
+            let fixtures = args.get(2).map(PathBuf::from).unwrap_or_else(|| PathBuf::from("."));
+            let created = advanced::create_permission_scenarios(&fixtures);
+            println!("Created {} permission fixtures under {}", created.len(), fixtures.display());
+        }
+        "unlock-permission-scenarios" => {
+            let fixtures = args.get(2).map(PathBuf::from).unwrap_or_else(|| PathBuf::from("."));
+            advanced::unlock_permission_scenarios(&fixtures);
+        }
         "run" | "run-isolated" => {
             // parse optional flags
             let mut terminal_override: Option<&str> = None;
@@ -72,8 +85,9 @@ fn main() {
             }
             run::run_image_isolated(terminal_override, foreground);
         }
+        "serve" => serve::serve(&args[2..]),
         _ => {
-            eprintln!("Usage: make_fakefs <build|generate-fixtures|apply-permissions|run>");
+            eprintln!("Usage: make_fakefs <build|generate-fixtures|apply-permissions|permission-scenarios|run|serve>");
             exit(1);
         }
     }
@@ -0,0 +1,55 @@
+use std::path::PathBuf;
+use std::process::exit;
+use std::time::Duration;
+
+use fileZoom::building::remote_servers::{self, RemoteProtocol};
+
+use crate::fixtures;
+
+/// `make_fakefs serve <sftp|ftp|webdav> [DIR] [--port N]`: start a test
+/// server for `protocol` serving DIR (or freshly generated fixtures) and
+/// leave it running.
+pub fn serve(args: &[String]) {
+    let Some(protocol) = args.first().and_then(|a| RemoteProtocol::from_name(a)) else {
+        eprintln!("Usage: make_fakefs serve <sftp|ftp|webdav> [DIR] [--port N]");
+        exit(1);
+    };
+    let mut port = protocol.default_port();
+    let mut dir: Option<PathBuf> = None;
+    let mut i = 1;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--port" => {
+                port = match args.get(i + 1).and_then(|p| p.parse().ok()) {
+                    Some(p) => p,
+                    None => {
+                        eprintln!("--port requires a port number");
+                        exit(1);
+                    }
+                };
+                i += 2;
+            }
+            other => {
+                dir = Some(PathBuf::from(other));
+                i += 1;
+            }
+        }
+    }
+    let dir = dir.unwrap_or_else(fixtures::generate_fixtures);
+
+    let server = match remote_servers::start_server(protocol, &dir, port) {
+        Ok(s) => s,
+        Err(e) => {
+            eprintln!("Failed to start {} server: {:#}", protocol.name(), e);
+            exit(1);
+        }
+    };
+    if let Err(e) = server.wait_ready(Duration::from_secs(30)) {
+        eprintln!("{:#}", e);
+        exit(1);
+    }
+    let url = server.url();
+    let container = server.detach();
+    println!("Serving {} at {}", dir.display(), url);
+    println!("Stop with: docker rm -f {}", container);
+}
@@ -2,6 +2,18 @@
 // This module is compiled only for tests or when the `test-helpers` feature
 // is explicitly enabled.
 
+mod clock;
+pub use clock::{ClockGuard, FakeClock};
+
+#[cfg(feature = "test-helpers")]
+mod disk_full;
+#[cfg(feature = "test-helpers")]
+pub use disk_full::{simulate_disk_full, DiskFullGuard};
+#[cfg(feature = "test-helpers")]
+mod slow_fs;
+#[cfg(feature = "test-helpers")]
+pub use slow_fs::SlowFs;
+
 #[cfg(test)]
 pub use _test_only::{set_up_temp_home, set_up_temp_xdg_config};
 
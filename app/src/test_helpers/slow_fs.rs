@@ -0,0 +1,104 @@
+//! A `VfsProvider` that answers slowly, for UI responsiveness tests.
+//!
+//! `SlowFs` wraps another provider and sleeps before every metadata call
+//! (`list`, `stat`), every `read` on an opened file and every `copy` or
+//! `remove`, so a test can check that the UI keeps drawing frames and
+//! accepts Esc while a listing or copy is stuck on a slow disk or network
+//! share. The delay can be changed while the provider is in use.
+
+use std::fmt;
+use std::io::{self, Read};
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::app::types::Entry;
+use crate::fs_op::vfs::VfsProvider;
+
+/// Wraps `inner` and delays each call by `delay`.
+pub struct SlowFs {
+    inner: Arc<dyn VfsProvider>,
+    delay_ms: Arc<AtomicU64>,
+    calls: AtomicUsize,
+}
+
+impl SlowFs {
+    pub fn new(inner: Arc<dyn VfsProvider>, delay: Duration) -> Self {
+        SlowFs { inner, delay_ms: Arc::new(AtomicU64::new(delay.as_millis() as u64)), calls: AtomicUsize::new(0) }
+    }
+
+    /// The local filesystem with `delay` added to every call.
+    pub fn local(delay: Duration) -> Arc<SlowFs> {
+        Arc::new(SlowFs::new(crate::fs_op::vfs::local(), delay))
+    }
+
+    /// Change the delay for calls made from now on.
+    pub fn set_delay(&self, delay: Duration) {
+        self.delay_ms.store(delay.as_millis() as u64, Ordering::SeqCst);
+    }
+
+    /// Number of delayed calls so far.
+    pub fn calls(&self) -> usize {
+        self.calls.load(Ordering::SeqCst)
+    }
+
+    fn wait(&self) {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        std::thread::sleep(Duration::from_millis(self.delay_ms.load(Ordering::SeqCst)));
+    }
+}
+
+impl fmt::Debug for SlowFs {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SlowFs")
+            .field("inner", &self.inner.name())
+            .field("delay_ms", &self.delay_ms.load(Ordering::SeqCst))
+            .finish()
+    }
+}
+
+/// Reader that sleeps before every `read`.
+struct SlowReader {
+    inner: Box<dyn Read + Send>,
+    delay_ms: Arc<AtomicU64>,
+}
+
+impl Read for SlowReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        std::thread::sleep(Duration::from_millis(self.delay_ms.load(Ordering::SeqCst)));
+        self.inner.read(buf)
+    }
+}
+
+impl VfsProvider for SlowFs {
+    fn name(&self) -> &str {
+        "slow"
+    }
+
+    fn list(&self, dir: &Path) -> io::Result<Vec<Entry>> {
+        self.wait();
+        self.inner.list(dir)
+    }
+
+    fn stat(&self, path: &Path) -> io::Result<Entry> {
+        self.wait();
+        self.inner.stat(path)
+    }
+
+    fn open(&self, path: &Path) -> io::Result<Box<dyn Read + Send>> {
+        self.wait();
+        let inner = self.inner.open(path)?;
+        Ok(Box::new(SlowReader { inner, delay_ms: self.delay_ms.clone() }))
+    }
+
+    fn copy(&self, from: &Path, to: &Path) -> io::Result<()> {
+        self.wait();
+        self.inner.copy(from, to)
+    }
+
+    fn remove(&self, path: &Path) -> io::Result<()> {
+        self.wait();
+        self.inner.remove(path)
+    }
+}
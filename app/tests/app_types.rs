@@ -18,15 +18,9 @@ fn entry_equality() {
 }
 
 #[test]
-fn synthetic_header_and_parent_detection() {
-    let cwd = PathBuf::from("/tmp");
-    let h = fileZoom::ui::panels::UiEntry::header(cwd.clone());
-    assert!(fileZoom::ui::panels::is_entry_header(&h));
-    assert!(!fileZoom::ui::panels::is_entry_parent(&h));
-
+fn synthetic_parent_detection() {
     let parent = fileZoom::ui::panels::UiEntry::parent(PathBuf::from("/"));
     assert!(fileZoom::ui::panels::is_entry_parent(&parent));
-    assert!(!fileZoom::ui::panels::is_entry_header(&parent));
 }
 
 #[test]
@@ -0,0 +1,77 @@
+use assert_cmd::cargo::cargo_bin_cmd;
+use std::fs;
+
+#[test]
+fn batch_file_runs_headless_through_fs_op() {
+    let tmp = tempfile::tempdir().unwrap();
+    let root = tmp.path();
+    fs::write(root.join("a.txt"), "new").unwrap();
+    fs::create_dir(root.join("out")).unwrap();
+    fs::write(root.join("out").join("a.txt"), "old").unwrap();
+    fs::write(root.join("tmp.log"), "").unwrap();
+    fs::write(
+        root.join("jobs.txt"),
+        "mkdir 'out/logs dir'\ncopy a.txt out\nmove tmp.log \"out/logs dir\"\ndelete a.txt\n",
+    )
+    .unwrap();
+
+    // The existing out/a.txt is a conflict: fail stops the batch there.
+    cargo_bin_cmd!("fileZoom")
+        .current_dir(root)
+        .args(["--batch", "jobs.txt"])
+        .assert()
+        .failure()
+        .stdout(predicates::str::contains("FAILED  copy a.txt -> out"))
+        .stdout(predicates::str::contains("1 done, 0 skipped, 1 failed"));
+    assert!(root.join("tmp.log").exists());
+
+    // Skipping keeps the old file and runs the rest.
+    cargo_bin_cmd!("fileZoom")
+        .current_dir(root)
+        .args(["--batch", "jobs.txt", "--on-conflict", "skip"])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("3 done, 1 skipped, 0 failed"));
+    assert_eq!(fs::read_to_string(root.join("out/a.txt")).unwrap(), "old");
+    assert!(root.join("out/logs dir/tmp.log").exists());
+    assert!(!root.join("a.txt").exists());
+}
+
+#[test]
+fn json_batch_overwrites_and_keeps_going() {
+    let tmp = tempfile::tempdir().unwrap();
+    let root = tmp.path();
+    fs::write(root.join("src.txt"), "new").unwrap();
+    fs::write(root.join("dst.txt"), "old").unwrap();
+    fs::write(
+        root.join("jobs.json"),
+        r#"[{"op": "copy", "src": "missing", "dst": "x"}, {"op": "move", "src": "src.txt", "dst": "dst.txt"}]"#,
+    )
+    .unwrap();
+
+    cargo_bin_cmd!("fileZoom")
+        .current_dir(root)
+        .args(["--batch", "jobs.json", "--on-conflict", "overwrite", "--keep-going"])
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains("1 done, 0 skipped, 1 failed"));
+    assert_eq!(fs::read_to_string(root.join("dst.txt")).unwrap(), "new");
+    assert!(!root.join("src.txt").exists());
+}
+
+#[test]
+fn moving_a_file_onto_itself_keeps_it() {
+    let tmp = tempfile::tempdir().unwrap();
+    let root = tmp.path();
+    fs::write(root.join("a.txt"), "data").unwrap();
+    fs::write(root.join("jobs.txt"), "move a.txt a.txt\n").unwrap();
+
+    cargo_bin_cmd!("fileZoom")
+        .current_dir(root)
+        .args(["--batch", "jobs.txt", "--on-conflict", "overwrite"])
+        .assert()
+        .failure()
+        .stdout(predicates::str::contains("is the source itself"))
+        .stdout(predicates::str::contains("0 done, 0 skipped, 1 failed"));
+    assert_eq!(fs::read_to_string(root.join("a.txt")).unwrap(), "data");
+}
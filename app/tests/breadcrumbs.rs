@@ -0,0 +1,41 @@
+use fileZoom::app::{App, Side, StartOptions};
+use fileZoom::input::mouse::{MouseButton, MouseEvent, MouseEventKind};
+use fileZoom::input::KeyCode;
+use fileZoom::runner::handlers::{handle_key, handle_mouse};
+use fileZoom::ui::layout::ScreenLayout;
+use fileZoom::ui::widgets::breadcrumbs::Breadcrumbs;
+use ratatui::{backend::TestBackend, layout::Rect, Terminal};
+
+#[test]
+fn breadcrumbs_are_drawn_and_jump_by_key_and_click() {
+    let tmp = tempfile::tempdir().unwrap();
+    let deep = tmp.path().join("alpha").join("beta");
+    std::fs::create_dir_all(&deep).unwrap();
+    let opts = StartOptions { start_dir: Some(deep.clone()), ..Default::default() };
+    let mut app = App::with_options(&opts).unwrap();
+    let area = Rect::new(0, 0, 100, 24);
+
+    // The path sits in the panel's top border.
+    let left = ScreenLayout::compute(&app, area).left;
+    let mut terminal = Terminal::new(TestBackend::new(area.width, area.height)).unwrap();
+    terminal.draw(|f| fileZoom::ui::ui(f, &app)).unwrap();
+    let buf = terminal.backend().buffer();
+    let border: String = (left.x..left.x + left.width).map(|x| buf[(x, left.y)].symbol()).collect();
+    assert!(border.contains("alpha › beta"), "{border}");
+
+    // `b` picks the parent, Left one more level up, Enter jumps there.
+    handle_key(&mut app, KeyCode::Char('b'), 10).unwrap();
+    assert_eq!((app.left.selected, app.left.crumb_dir()), (0, deep.parent().unwrap()));
+    handle_key(&mut app, KeyCode::Left, 10).unwrap();
+    handle_key(&mut app, KeyCode::Enter, 10).unwrap();
+    assert_eq!(app.left.cwd, tmp.path());
+    assert_eq!(app.left.crumb, 0);
+
+    // Clicking a segment of the other panel's path switches to it there.
+    let right = ScreenLayout::compute(&app, area).right;
+    let bar = Breadcrumbs::new(&app.right.cwd, right);
+    let alpha = bar.crumbs.iter().find(|c| c.label == "alpha").expect("alpha segment");
+    let me = MouseEvent { column: alpha.x + 1, row: right.y, kind: MouseEventKind::Down(MouseButton::Left) };
+    handle_mouse(&mut app, me, area).unwrap();
+    assert_eq!((app.active, app.right.cwd.clone()), (Side::Right, tmp.path().join("alpha")));
+}
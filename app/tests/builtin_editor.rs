@@ -0,0 +1,73 @@
+use assert_fs::prelude::*;
+use fileZoom::app::{App, Mode, Panel};
+use fileZoom::input::KeyCode;
+use fileZoom::runner::handlers::handle_key;
+use ratatui::backend::TestBackend;
+use ratatui::Terminal;
+
+fn app_at(cwd: std::path::PathBuf, name: &str) -> App {
+    let mut app = App::new().unwrap();
+    app.left = Panel::new(cwd.clone());
+    app.right = Panel::new(cwd);
+    app.refresh().unwrap();
+    let idx = app.left.entries.iter().position(|e| e.name == name).expect("entry present");
+    let parent_count = if app.left.cwd.parent().is_some() { 1usize } else { 0usize };
+    app.left.selected = 1 + parent_count + idx;
+    app
+}
+
+#[test]
+fn f4_edits_and_f2_saves_file() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let f = temp.child("notes.txt");
+    f.write_str("one\ntwo\n").unwrap();
+    let mut app = app_at(temp.path().to_path_buf(), "notes.txt");
+
+    handle_key(&mut app, KeyCode::F(4), 10).unwrap();
+    assert!(matches!(app.mode, Mode::Editor { .. }), "expected Editor mode after F4");
+
+    // Jump to line 2, append text and save.
+    handle_key(&mut app, KeyCode::F(9), 10).unwrap();
+    handle_key(&mut app, KeyCode::Char('2'), 10).unwrap();
+    handle_key(&mut app, KeyCode::Enter, 10).unwrap();
+    handle_key(&mut app, KeyCode::End, 10).unwrap();
+    for c in "!!".chars() {
+        handle_key(&mut app, KeyCode::Char(c), 10).unwrap();
+    }
+
+    // The editor renders without panicking.
+    let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+    terminal.draw(|fr| fileZoom::ui::ui(fr, &app)).unwrap();
+
+    handle_key(&mut app, KeyCode::F(2), 10).unwrap();
+    assert_eq!(std::fs::read_to_string(f.path()).unwrap(), "one\ntwo!!\n");
+
+    handle_key(&mut app, KeyCode::Esc, 10).unwrap();
+    assert!(matches!(app.mode, Mode::Normal));
+}
+
+#[test]
+fn closing_dirty_editor_requires_second_esc() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let f = temp.child("a.txt");
+    f.write_str("x\n").unwrap();
+    let mut app = app_at(temp.path().to_path_buf(), "a.txt");
+
+    handle_key(&mut app, KeyCode::F(4), 10).unwrap();
+    handle_key(&mut app, KeyCode::Char('y'), 10).unwrap();
+    handle_key(&mut app, KeyCode::Esc, 10).unwrap();
+    assert!(matches!(app.mode, Mode::Editor { .. }));
+    handle_key(&mut app, KeyCode::Esc, 10).unwrap();
+    assert!(matches!(app.mode, Mode::Normal));
+    assert_eq!(std::fs::read_to_string(f.path()).unwrap(), "x\n");
+}
+
+#[test]
+fn f4_on_directory_shows_message() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    temp.child("sub").create_dir_all().unwrap();
+    let mut app = app_at(temp.path().to_path_buf(), "sub");
+
+    handle_key(&mut app, KeyCode::F(4), 10).unwrap();
+    assert!(matches!(app.mode, Mode::Message { .. }));
+}
@@ -0,0 +1,55 @@
+#![cfg(unix)]
+
+use std::os::unix::fs::MetadataExt;
+
+use fileZoom::app::{App, InputKind, Mode, StartOptions};
+use fileZoom::input::KeyCode;
+use fileZoom::runner::commands::execute_command;
+use fileZoom::runner::handlers::handle_key;
+
+#[test]
+fn change_owner_applies_to_the_tree_on_request() {
+    let tmp = tempfile::tempdir().unwrap();
+    std::fs::create_dir_all(tmp.path().join("dir/sub")).unwrap();
+    std::fs::write(tmp.path().join("dir/sub/deep.txt"), "").unwrap();
+    let opts = StartOptions { start_dir: Some(tmp.path().to_path_buf()), ..Default::default() };
+    let mut app = App::with_options(&opts).unwrap();
+    assert!(app.left.select_named("dir"));
+    let meta = std::fs::metadata(tmp.path().join("dir")).unwrap();
+
+    // The dialog starts from the current owner; giving the files the group
+    // they already have always succeeds, even without privileges.
+    execute_command(&mut app, "chown").unwrap();
+    let Mode::Input { buffer, kind: InputKind::ChangeOwner, .. } = &mut app.mode else {
+        panic!("expected the change-owner dialog, got {:?}", app.mode);
+    };
+    assert!(buffer.contains(':'), "{}", buffer);
+    *buffer = format!("-R :{}", meta.gid());
+    handle_key(&mut app, KeyCode::Enter, 10).unwrap();
+    assert!(matches!(app.mode, Mode::Normal), "{:?}", app.mode);
+
+    // Unknown users are reported before anything is changed.
+    execute_command(&mut app, "chown no-such-user-here").unwrap();
+    let Mode::Message { content, .. } = &app.mode else { panic!("expected an error, got {:?}", app.mode) };
+    assert!(content.contains("no-such-user-here"), "{}", content);
+}
+
+#[test]
+fn change_owner_reports_files_it_could_not_change() {
+    if nix::unistd::geteuid().is_root() {
+        return;
+    }
+    let tmp = tempfile::tempdir().unwrap();
+    std::fs::create_dir_all(tmp.path().join("dir")).unwrap();
+    std::fs::write(tmp.path().join("dir/a.txt"), "").unwrap();
+    let opts = StartOptions { start_dir: Some(tmp.path().to_path_buf()), ..Default::default() };
+    let mut app = App::with_options(&opts).unwrap();
+    assert!(app.left.select_named("dir"));
+
+    // Only root may give files away.
+    execute_command(&mut app, "chown -R 0").unwrap();
+    let Mode::Message { title, content, .. } = &app.mode else { panic!("expected a report, got {:?}", app.mode) };
+    assert_eq!(title, "Change owner");
+    assert!(content.starts_with("2 entries kept their owner"), "{}", content);
+    assert!(content.contains("a.txt"), "{}", content);
+}
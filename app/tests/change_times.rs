@@ -0,0 +1,42 @@
+use fileZoom::app::{App, InputKind, Mode, StartOptions};
+use fileZoom::input::KeyCode;
+use fileZoom::runner::commands::execute_command;
+use fileZoom::runner::handlers::handle_key;
+use chrono::{Local, TimeZone};
+
+fn mtime(path: &std::path::Path) -> i64 {
+    filetime::FileTime::from_last_modification_time(&std::fs::symlink_metadata(path).unwrap()).unix_seconds()
+}
+
+#[test]
+fn change_times_sets_the_selection_recursively() {
+    let tmp = tempfile::tempdir().unwrap();
+    std::fs::create_dir_all(tmp.path().join("dir/sub")).unwrap();
+    std::fs::write(tmp.path().join("dir/sub/deep.txt"), "").unwrap();
+    std::fs::write(tmp.path().join("other.txt"), "").unwrap();
+    let opts = StartOptions { start_dir: Some(tmp.path().to_path_buf()), ..Default::default() };
+    let mut app = App::with_options(&opts).unwrap();
+    assert!(app.left.select_named("dir"));
+
+    // The dialog starts from the entry's modification time.
+    execute_command(&mut app, "touch").unwrap();
+    let Mode::Input { buffer, kind: InputKind::ChangeTimes, .. } = &mut app.mode else {
+        panic!("expected the change-times dialog, got {:?}", app.mode);
+    };
+    assert_eq!(buffer.len(), "YYYY-MM-DD HH:MM:SS".len());
+    *buffer = "2001-02-03 04:05".to_string();
+    handle_key(&mut app, KeyCode::Enter, 10).unwrap();
+    assert!(matches!(app.mode, Mode::Normal), "{:?}", app.mode);
+
+    let expected = Local.with_ymd_and_hms(2001, 2, 3, 4, 5, 0).unwrap().timestamp();
+    for path in ["dir", "dir/sub", "dir/sub/deep.txt"] {
+        assert_eq!(mtime(&tmp.path().join(path)), expected, "{}", path);
+    }
+    assert_ne!(mtime(&tmp.path().join("other.txt")), expected);
+
+    // `now` brings it back; nonsense is reported and changes nothing.
+    execute_command(&mut app, "touch now").unwrap();
+    assert!(mtime(&tmp.path().join("dir/sub/deep.txt")) > expected);
+    execute_command(&mut app, "touch yesterday-ish").unwrap();
+    assert!(matches!(app.mode, Mode::Message { .. }));
+}
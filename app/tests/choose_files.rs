@@ -0,0 +1,39 @@
+use fileZoom::app::{App, StartOptions};
+use fileZoom::input::KeyCode;
+use fileZoom::runner::handlers::handle_key;
+
+#[test]
+fn enter_picks_files_in_the_picker() {
+    let tmp = tempfile::tempdir().unwrap();
+    std::fs::create_dir(tmp.path().join("sub")).unwrap();
+    for name in ["a.txt", "b.txt", "sub/c.txt"] {
+        std::fs::write(tmp.path().join(name), "").unwrap();
+    }
+    let opts = StartOptions { start_dir: Some(tmp.path().to_path_buf()), choose_files: true, ..Default::default() };
+    let mut app = App::with_options(&opts).unwrap();
+    assert_eq!(app.chooser, Some(Vec::new()));
+
+    // Enter on a directory still enters it.
+    assert!(app.left.select_named("sub"));
+    assert!(!handle_key(&mut app, KeyCode::Enter, 10).unwrap());
+    assert_eq!(app.left.cwd, tmp.path().join("sub"));
+    handle_key(&mut app, KeyCode::Backspace, 10).unwrap();
+
+    // With files marked, Enter picks them all and quits.
+    for name in ["a.txt", "b.txt"] {
+        assert!(app.left.select_named(name));
+        handle_key(&mut app, KeyCode::Char(' '), 10).unwrap();
+    }
+    assert!(handle_key(&mut app, KeyCode::Enter, 10).unwrap());
+    assert_eq!(app.chooser, Some(vec![tmp.path().join("a.txt"), tmp.path().join("b.txt")]));
+}
+
+#[test]
+fn enter_opens_files_outside_the_picker() {
+    let tmp = tempfile::tempdir().unwrap();
+    std::fs::write(tmp.path().join("a.txt"), "").unwrap();
+    let mut app = App::with_options(&StartOptions { start_dir: Some(tmp.path().to_path_buf()), ..Default::default() }).unwrap();
+    assert!(app.left.select_named("a.txt"));
+    assert!(!handle_key(&mut app, KeyCode::Enter, 10).unwrap());
+    assert_eq!(app.chooser, None);
+}
@@ -0,0 +1,60 @@
+use assert_cmd::cargo::cargo_bin_cmd;
+use fileZoom::app::settings::write_settings::{config_file_path, save_settings, set_config_file_override, Settings};
+use fileZoom::app::{App, StartOptions};
+use fileZoom::input::KeyCode;
+use fileZoom::runner::handlers::handle_key;
+use ratatui::backend::TestBackend;
+use ratatui::Terminal;
+
+#[test]
+fn help_lists_panel_dirs_and_layout_flags() {
+    let out = cargo_bin_cmd!("fileZoom").arg("--help").assert().success().get_output().stdout.clone();
+    let out = String::from_utf8_lossy(&out);
+    for needle in ["[LEFT_DIR]", "[RIGHT_DIR]", "--single-pane", "--config <PATH>", "--no-mouse", "--theme"] {
+        assert!(out.contains(needle), "missing {needle} in:\n{out}");
+    }
+}
+
+#[test]
+fn start_options_open_both_dirs_in_single_pane_mode() {
+    let tmp = tempfile::tempdir().unwrap();
+    let (left, right) = (tmp.path().join("l"), tmp.path().join("r"));
+    std::fs::create_dir_all(&left).unwrap();
+    std::fs::create_dir_all(&right).unwrap();
+    std::fs::write(left.join("only-left.txt"), "").unwrap();
+    std::fs::write(right.join("only-right.txt"), "").unwrap();
+
+    let opts = StartOptions {
+        start_dir: Some(left.clone()),
+        right_dir: Some(right.clone()),
+        single_pane: Some(true),
+        ..Default::default()
+    };
+    let mut app = App::with_options(&opts).unwrap();
+    assert_eq!(app.left.cwd, left);
+    assert_eq!(app.right.cwd, right);
+    assert!(app.settings.single_pane);
+
+    let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
+    let screen = |t: &Terminal<TestBackend>| t.backend().buffer().content().iter().map(|c| c.symbol()).collect::<String>();
+    terminal.draw(|f| fileZoom::ui::ui(f, &app)).unwrap();
+    let s = screen(&terminal);
+    assert!(s.contains("only-left.txt") && !s.contains("only-right.txt"));
+
+    handle_key(&mut app, KeyCode::Tab, 10).unwrap();
+    terminal.draw(|f| fileZoom::ui::ui(f, &app)).unwrap();
+    let s = screen(&terminal);
+    assert!(s.contains("only-right.txt") && !s.contains("only-left.txt"));
+}
+
+#[test]
+fn config_override_redirects_the_settings_file() {
+    let tmp = tempfile::tempdir().unwrap();
+    let custom = tmp.path().join("custom").join("fz.toml");
+    set_config_file_override(Some(custom.clone()));
+    assert_eq!(config_file_path().unwrap(), custom);
+    save_settings(&Settings::default()).unwrap();
+    set_config_file_override(None);
+    assert!(custom.exists());
+    assert_ne!(config_file_path().unwrap(), custom);
+}
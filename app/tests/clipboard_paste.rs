@@ -0,0 +1,65 @@
+use assert_fs::prelude::*;
+use fileZoom::app::{App, Mode, Panel, Side, StartOptions};
+use fileZoom::input::{Key, KeyCode, KeyModifiers};
+use fileZoom::runner::handlers::clipboard::handle_clipboard_key;
+use std::time::{Duration, Instant};
+
+fn ctrl(c: char) -> Key {
+    Key { code: KeyCode::Char(c), modifiers: KeyModifiers { ctrl: true, ..Default::default() } }
+}
+
+fn finish(app: &mut App) {
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while app.op_progress_rx.is_some() && Instant::now() < deadline {
+        app.poll_progress();
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    app.mode = Mode::Normal;
+}
+
+#[test]
+fn yank_and_cut_paste_into_the_other_panel() {
+    let tmp = assert_fs::TempDir::new().unwrap();
+    let left = tmp.child("left");
+    let right = tmp.child("right");
+    left.child("a.txt").write_str("a").unwrap();
+    left.child("b.txt").write_str("b").unwrap();
+    right.create_dir_all().unwrap();
+
+    let opts = StartOptions { start_dir: Some(left.path().to_path_buf()), ..Default::default() };
+    let mut app = App::with_options(&opts).unwrap();
+    app.right = Panel::new(right.path().to_path_buf());
+    app.refresh().unwrap();
+
+    // Ctrl+V with an empty clipboard does nothing.
+    assert!(handle_clipboard_key(&mut app, &ctrl('v')).unwrap());
+    assert!(matches!(app.mode, Mode::Normal));
+
+    assert!(app.left.select_named("a.txt"));
+    assert!(handle_clipboard_key(&mut app, &ctrl('c')).unwrap());
+    assert_eq!(app.clipboard.as_ref().map(|c| c.summary()).as_deref(), Some("a.txt yanked"));
+    app.active = Side::Right;
+    handle_clipboard_key(&mut app, &ctrl('v')).unwrap();
+    finish(&mut app);
+    right.child("a.txt").assert("a");
+    left.child("a.txt").assert("a");
+    // A yank can be pasted again.
+    assert!(app.clipboard.is_some());
+
+    app.active = Side::Left;
+    assert!(app.left.select_named("b.txt"));
+    handle_clipboard_key(&mut app, &ctrl('x')).unwrap();
+    assert!(app.clipboard.as_ref().is_some_and(|c| c.cut));
+    // Pasting a cut into its own directory is a no-op.
+    handle_clipboard_key(&mut app, &ctrl('v')).unwrap();
+    assert!(matches!(app.mode, Mode::Normal) && app.clipboard.is_some());
+    app.active = Side::Right;
+    handle_clipboard_key(&mut app, &ctrl('v')).unwrap();
+    finish(&mut app);
+    right.child("b.txt").assert("b");
+    assert!(!left.child("b.txt").path().exists());
+    assert!(app.clipboard.is_none());
+
+    // Other keys are left for normal dispatch.
+    assert!(!handle_clipboard_key(&mut app, &Key { code: KeyCode::Char('c'), modifiers: KeyModifiers::default() }).unwrap());
+}
@@ -0,0 +1,91 @@
+#![cfg(unix)]
+
+use assert_fs::prelude::*;
+use fileZoom::app::{App, Mode, Panel};
+use fileZoom::input::KeyCode;
+use fileZoom::runner::handlers::handle_key;
+
+fn type_str(app: &mut App, s: &str) {
+    for c in s.chars() {
+        handle_key(app, KeyCode::Char(c), 10).unwrap();
+    }
+}
+
+/// Wait for a command that outlived `SHELL_WAIT` to finish.
+fn finish(app: &mut App) {
+    let deadline = std::time::Instant::now() + std::time::Duration::from_secs(10);
+    while app.shell.is_some() {
+        assert!(std::time::Instant::now() < deadline, "the command never finished");
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        fileZoom::runner::shell::poll(app).unwrap();
+    }
+}
+
+#[test]
+fn shell_command_runs_in_cwd_with_placeholders() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    temp.child("picked.txt").write_str("x").unwrap();
+    let mut app = App::new().unwrap();
+    app.left = Panel::new(temp.path().to_path_buf());
+    app.right = Panel::new(temp.path().to_path_buf());
+    app.refresh().unwrap();
+    let idx = app.left.entries.iter().position(|e| e.name == "picked.txt").unwrap();
+    let parent_count = if app.left.cwd.parent().is_some() { 1usize } else { 0usize };
+    app.left.selected = 1 + parent_count + idx;
+
+    handle_key(&mut app, KeyCode::Char(':'), 10).unwrap();
+    assert!(app.command_line.is_some());
+    type_str(&mut app, "basename %f; cp %f copy.txt; pwd");
+    let quit = handle_key(&mut app, KeyCode::Enter, 10).unwrap();
+    assert!(!quit, "running a command must not quit the app");
+    assert!(app.command_line.is_none());
+    finish(&mut app);
+
+    match &app.mode {
+        Mode::CommandOutput { lines, status, .. } => {
+            assert_eq!(*status, Some(0));
+            assert_eq!(lines[0], "picked.txt");
+            let cwd = std::fs::canonicalize(temp.path()).unwrap();
+            assert_eq!(std::fs::canonicalize(&lines[1]).unwrap(), cwd);
+        }
+        other => panic!("expected CommandOutput, got {:?}", other),
+    }
+    assert!(temp.child("copy.txt").path().exists());
+    // The panel is refreshed so the new file is listed.
+    assert!(app.left.entries.iter().any(|e| e.name == "copy.txt"));
+
+    handle_key(&mut app, KeyCode::Esc, 10).unwrap();
+    assert!(matches!(app.mode, Mode::Normal));
+}
+
+#[test]
+fn slow_command_runs_in_the_background_and_esc_kills_it() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let mut app = App::new().unwrap();
+    app.left = Panel::new(temp.path().to_path_buf());
+    app.refresh().unwrap();
+
+    handle_key(&mut app, KeyCode::Char(':'), 10).unwrap();
+    type_str(&mut app, "sleep 30; touch late.txt");
+    let started = std::time::Instant::now();
+    handle_key(&mut app, KeyCode::Enter, 10).unwrap();
+    assert!(started.elapsed() < std::time::Duration::from_secs(5));
+    assert!(matches!(app.mode, Mode::CommandOutput { running: true, .. }));
+    assert!(app.shell.is_some());
+
+    handle_key(&mut app, KeyCode::Esc, 10).unwrap();
+    assert!(matches!(app.mode, Mode::Normal));
+    assert!(app.shell.is_none());
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    assert!(!temp.child("late.txt").path().exists());
+}
+
+#[test]
+fn esc_closes_command_line_without_running() {
+    let mut app = App::new().unwrap();
+    handle_key(&mut app, KeyCode::Char(':'), 10).unwrap();
+    type_str(&mut app, "touch should_not_exist");
+    handle_key(&mut app, KeyCode::Esc, 10).unwrap();
+    assert!(app.command_line.is_none());
+    assert!(matches!(app.mode, Mode::Normal));
+}
@@ -0,0 +1,38 @@
+//! Helpers shared by the integration tests (`mod common;`).
+//!
+//! Every test file compiles its own copy of this module and uses only part
+//! of it, hence the `dead_code` allowance.
+#![allow(dead_code)]
+
+use std::path::Path;
+
+use fileZoom::app::{App, StartOptions};
+use ratatui::{backend::TestBackend, Terminal};
+
+/// An app started in `dir`, with the settings of a fresh install.
+pub fn app_in(dir: &Path) -> App {
+    App::with_options(&StartOptions { start_dir: Some(dir.to_path_buf()), ..Default::default() }).unwrap()
+}
+
+/// The rows of the frame last drawn to `terminal`.
+pub fn rows(terminal: &Terminal<TestBackend>) -> Vec<String> {
+    let buf = terminal.backend().buffer();
+    (0..buf.area.height).map(|y| (0..buf.area.width).map(|x| buf[(x, y)].symbol()).collect()).collect()
+}
+
+/// Draw `app` to `terminal` and return the rows on screen.
+pub fn draw(terminal: &mut Terminal<TestBackend>, app: &App) -> Vec<String> {
+    terminal.draw(|f| fileZoom::ui::ui(f, app)).unwrap();
+    rows(terminal)
+}
+
+/// `app` drawn on a `width` x `height` terminal, one line per row.
+pub fn screen_sized(app: &App, width: u16, height: u16) -> String {
+    let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+    draw(&mut terminal, app).into_iter().map(|row| row + "\n").collect()
+}
+
+/// `app` drawn on a 100 x 30 terminal, one line per row.
+pub fn screen(app: &App) -> String {
+    screen_sized(app, 100, 30)
+}
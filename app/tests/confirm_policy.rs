@@ -0,0 +1,133 @@
+use fileZoom::app::{Action, Mode};
+use fileZoom::input::KeyCode;
+use fileZoom::runner::handlers::handle_key;
+use std::time::Duration;
+
+mod common;
+use common::app_in;
+
+#[test]
+fn delete_skips_the_prompt_without_confirm_delete() {
+    let tmp = tempfile::tempdir().unwrap();
+    std::fs::write(tmp.path().join("a.txt"), "a").unwrap();
+    std::fs::write(tmp.path().join("b.txt"), "b").unwrap();
+    let mut app = app_in(tmp.path());
+
+    assert!(app.left.select_named("a.txt"));
+    handle_key(&mut app, KeyCode::Char('d'), 10).unwrap();
+    assert!(matches!(&app.mode, Mode::Confirm { on_yes: Action::DeleteSelected, .. }));
+    app.mode = Mode::Normal;
+
+    app.settings.confirm_delete = false;
+    assert!(app.left.select_named("b.txt"));
+    handle_key(&mut app, KeyCode::Char('d'), 10).unwrap();
+    assert!(matches!(app.mode, Mode::Normal));
+    assert!(!tmp.path().join("b.txt").exists());
+    assert!(tmp.path().join("a.txt").exists());
+}
+
+#[test]
+fn quitting_with_a_running_job_asks_first() {
+    let tmp = tempfile::tempdir().unwrap();
+    let mut app = app_in(tmp.path());
+    let (_tx, rx) = std::sync::mpsc::channel();
+    app.op_progress_rx = Some(rx);
+
+    // "No" is the default answer.
+    assert!(!handle_key(&mut app, KeyCode::Char('q'), 10).unwrap());
+    assert!(matches!(&app.mode, Mode::Confirm { on_yes: Action::Quit, selected: 1, .. }));
+    handle_key(&mut app, KeyCode::Enter, 10).unwrap();
+    assert!(matches!(app.mode, Mode::Normal));
+
+    handle_key(&mut app, KeyCode::Char('q'), 10).unwrap();
+    assert!(handle_key(&mut app, KeyCode::Char('y'), 10).unwrap());
+
+    app.mode = Mode::Normal;
+    app.settings.confirm_quit_with_jobs = false;
+    assert!(handle_key(&mut app, KeyCode::Char('q'), 10).unwrap());
+}
+
+#[test]
+fn copies_overwrite_without_confirm_overwrite() {
+    let tmp = tempfile::tempdir().unwrap();
+    std::fs::create_dir_all(tmp.path().join("left")).unwrap();
+    std::fs::create_dir_all(tmp.path().join("right")).unwrap();
+    std::fs::write(tmp.path().join("left/f.txt"), "new").unwrap();
+    std::fs::write(tmp.path().join("right/f.txt"), "old").unwrap();
+    let mut app = app_in(&tmp.path().join("left"));
+    app.right.cwd = tmp.path().join("right");
+    app.refresh().unwrap();
+    assert!(app.left.select_named("f.txt"));
+    app.settings.confirm_overwrite = false;
+
+    handle_key(&mut app, KeyCode::F(5), 10).unwrap();
+    let rx = app.op_progress_rx.take().unwrap();
+    loop {
+        let update = rx.recv_timeout(Duration::from_secs(10)).expect("worker update");
+        assert_eq!(update.conflict, None);
+        if update.done {
+            assert_eq!(update.error, None);
+            break;
+        }
+    }
+    assert_eq!(std::fs::read_to_string(tmp.path().join("right/f.txt")).unwrap(), "new");
+}
+
+#[test]
+fn moves_within_a_filesystem_are_not_confirmed() {
+    let tmp = tempfile::tempdir().unwrap();
+    let src = tmp.path().join("f.txt");
+    std::fs::write(&src, "x").unwrap();
+    assert!(!fileZoom::fs_op::helpers::crosses_filesystems(&src, tmp.path()));
+    assert!(!fileZoom::fs_op::helpers::crosses_filesystems(&src, &tmp.path().join("not/yet")));
+    #[cfg(target_os = "linux")]
+    assert!(fileZoom::fs_op::helpers::crosses_filesystems(&src, std::path::Path::new("/proc/self")));
+
+    let mut app = app_in(tmp.path());
+    app.settings.confirm_cross_fs_move = true;
+    app.right.cwd = tmp.path().join("into");
+    std::fs::create_dir(&app.right.cwd).unwrap();
+    assert!(app.left.select_named("f.txt"));
+    handle_key(&mut app, KeyCode::F(6), 10).unwrap();
+    assert!(matches!(app.mode, Mode::Progress { .. }));
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn deleting_marked_files_warns_about_any_of_them_held_open() {
+    let tmp = tempfile::tempdir().unwrap();
+    std::fs::write(tmp.path().join("a.txt"), "a").unwrap();
+    std::fs::write(tmp.path().join("held.log"), "").unwrap();
+    let mut child = std::process::Command::new("sh")
+        .arg("-c")
+        .arg("exec 3>>\"$0\"; exec sleep 10")
+        .arg(tmp.path().join("held.log"))
+        .spawn()
+        .unwrap();
+    let mut app = app_in(tmp.path());
+    app.settings.warn_open_files = true;
+    app.settings.confirm_delete = false;
+    for name in ["a.txt", "held.log"] {
+        let idx = app.left.entries.iter().position(|e| e.name == name).unwrap();
+        app.left.selections.insert(idx);
+    }
+    // The cursor is on the file nobody holds.
+    assert!(app.left.select_named("a.txt"));
+
+    let deadline = std::time::Instant::now() + Duration::from_secs(10);
+    while fileZoom::fs_op::open_files::find_open_files(&[tmp.path().join("held.log")]).is_empty() {
+        assert!(std::time::Instant::now() < deadline, "the child never opened the file");
+        std::thread::sleep(Duration::from_millis(20));
+    }
+    handle_key(&mut app, KeyCode::Char('d'), 10).unwrap();
+    // A slow scan finishes in the background.
+    while app.open_files.is_some() {
+        assert!(std::time::Instant::now() < deadline, "the scan never finished");
+        std::thread::sleep(Duration::from_millis(5));
+        fileZoom::runner::open_files::poll(&mut app);
+    }
+    let _ = child.kill();
+    let _ = child.wait();
+    assert!(matches!(&app.mode, Mode::Confirm { msg, on_yes: Action::DeleteMarked, .. } if msg.contains("held.log")), "{:?}", app.mode);
+    assert!(tmp.path().join("a.txt").exists());
+}
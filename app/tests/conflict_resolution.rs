@@ -34,6 +34,9 @@ fn conflict_overwrite() {
         settings: fileZoom::app::settings::write_settings::Settings::default(),
         op_progress_rx: None,
         op_cancel_flag: None,
+        op_skip_verify: None,
+        op_meter: None,
+        scheduler: Default::default(),
         op_decision_tx: None,
         last_mouse_click_time: None,
         last_mouse_click_pos: None,
@@ -41,6 +44,30 @@ fn conflict_overwrite() {
         drag_start: None,
         drag_current: None,
         drag_button: None,
+        scroll_drag: None,
+        dirty: false,
+        plugins: Default::default(),
+        diagnostics: Default::default(),
+        jobs: Default::default(),
+        pending_keys: Default::default(),
+        path_jump: Default::default(),
+        frecency: Default::default(),
+        tags: Default::default(),
+        searches: Default::default(),
+        clipboard: None,
+        other_panel: Default::default(),
+        info: Default::default(),
+        tree: Default::default(),
+        usage: None,
+        shell: None,
+        compare: None,
+        open_files: None,
+        chooser: None,
+        link: None,
+        templates: Default::default(),
+        owners: Default::default(),
+        preview_focused: Default::default(),
+        layout: Default::default(),
     };
     app.refresh().unwrap();
 
@@ -115,6 +142,9 @@ fn conflict_skip() {
         settings: fileZoom::app::settings::write_settings::Settings::default(),
         op_progress_rx: None,
         op_cancel_flag: None,
+        op_skip_verify: None,
+        op_meter: None,
+        scheduler: Default::default(),
         op_decision_tx: None,
         last_mouse_click_time: None,
         last_mouse_click_pos: None,
@@ -122,6 +152,30 @@ fn conflict_skip() {
         drag_start: None,
         drag_current: None,
         drag_button: None,
+        scroll_drag: None,
+        dirty: false,
+        plugins: Default::default(),
+        diagnostics: Default::default(),
+        jobs: Default::default(),
+        pending_keys: Default::default(),
+        path_jump: Default::default(),
+        frecency: Default::default(),
+        tags: Default::default(),
+        searches: Default::default(),
+        clipboard: None,
+        other_panel: Default::default(),
+        info: Default::default(),
+        tree: Default::default(),
+        usage: None,
+        shell: None,
+        compare: None,
+        open_files: None,
+        chooser: None,
+        link: None,
+        templates: Default::default(),
+        owners: Default::default(),
+        preview_focused: Default::default(),
+        layout: Default::default(),
     };
     app.refresh().unwrap();
 
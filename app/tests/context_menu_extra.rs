@@ -35,6 +35,7 @@ fn unknown_context_menu_label_shows_not_implemented_message() {
         options: vec!["NotARealAction".to_string()],
         selected: 0,
         path: file_path,
+        at: None,
     };
 
     // Press Enter to activate the unknown option.
@@ -83,6 +84,7 @@ fn context_menu_navigation_bounds() {
         options: vec!["OnlyOne".to_string()],
         selected: 0,
         path,
+        at: None,
     };
 
     // Press Left/Up should keep selected at 0
@@ -104,6 +106,7 @@ fn context_menu_navigation_bounds() {
         options: vec!["One".to_string(), "Two".to_string()],
         selected: 1,
         path: app.left.selected_entry().unwrap().path.clone(),
+        at: None,
     };
 
     // Press Right/Down should keep selected at last index (1)
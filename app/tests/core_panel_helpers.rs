@@ -28,6 +28,9 @@ fn selected_index_reflects_active_panel_unit() {
         settings: Settings::default(),
         op_progress_rx: None,
         op_cancel_flag: None,
+        op_skip_verify: None,
+        op_meter: None,
+        scheduler: Default::default(),
         op_decision_tx: None,
         last_mouse_click_time: None,
         last_mouse_click_pos: None,
@@ -35,6 +38,30 @@ fn selected_index_reflects_active_panel_unit() {
         drag_start: None,
         drag_current: None,
         drag_button: None,
+        scroll_drag: None,
+        dirty: false,
+        plugins: Default::default(),
+        diagnostics: Default::default(),
+        jobs: Default::default(),
+        pending_keys: Default::default(),
+        path_jump: Default::default(),
+        frecency: Default::default(),
+        tags: Default::default(),
+        searches: Default::default(),
+        clipboard: None,
+        other_panel: Default::default(),
+        info: Default::default(),
+        tree: Default::default(),
+        usage: None,
+        shell: None,
+        compare: None,
+        open_files: None,
+        chooser: None,
+        link: None,
+        templates: Default::default(),
+        owners: Default::default(),
+        preview_focused: Default::default(),
+        layout: Default::default(),
     };
     app.refresh().unwrap();
 
@@ -101,6 +128,9 @@ fn panel_mut_match() {
         settings: Settings::default(),
         op_progress_rx: None,
         op_cancel_flag: None,
+        op_skip_verify: None,
+        op_meter: None,
+        scheduler: Default::default(),
         op_decision_tx: None,
         last_mouse_click_time: None,
         last_mouse_click_pos: None,
@@ -108,6 +138,30 @@ fn panel_mut_match() {
         drag_start: None,
         drag_current: None,
         drag_button: None,
+        scroll_drag: None,
+        dirty: false,
+        plugins: Default::default(),
+        diagnostics: Default::default(),
+        jobs: Default::default(),
+        pending_keys: Default::default(),
+        path_jump: Default::default(),
+        frecency: Default::default(),
+        tags: Default::default(),
+        searches: Default::default(),
+        clipboard: None,
+        other_panel: Default::default(),
+        info: Default::default(),
+        tree: Default::default(),
+        usage: None,
+        shell: None,
+        compare: None,
+        open_files: None,
+        chooser: None,
+        link: None,
+        templates: Default::default(),
+        owners: Default::default(),
+        preview_focused: Default::default(),
+        layout: Default::default(),
     };
     app.refresh().unwrap();
     // modify left via panel_mut and check read through panel
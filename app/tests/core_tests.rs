@@ -43,6 +43,9 @@ fn sort_name_puts_dirs_first() {
         settings: fileZoom::app::settings::write_settings::Settings::default(),
         op_progress_rx: None,
         op_cancel_flag: None,
+        op_skip_verify: None,
+        op_meter: None,
+        scheduler: Default::default(),
         op_decision_tx: None,
         last_mouse_click_time: None,
         last_mouse_click_pos: None,
@@ -50,6 +53,30 @@ fn sort_name_puts_dirs_first() {
         drag_start: None,
         drag_current: None,
         drag_button: None,
+        scroll_drag: None,
+        dirty: false,
+        plugins: Default::default(),
+        diagnostics: Default::default(),
+        jobs: Default::default(),
+        pending_keys: Default::default(),
+        path_jump: Default::default(),
+        frecency: Default::default(),
+        tags: Default::default(),
+        searches: Default::default(),
+        clipboard: None,
+        other_panel: Default::default(),
+        info: Default::default(),
+        tree: Default::default(),
+        usage: None,
+        shell: None,
+        compare: None,
+        open_files: None,
+        chooser: None,
+        link: None,
+        templates: Default::default(),
+        owners: Default::default(),
+        preview_focused: Default::default(),
+        layout: Default::default(),
     };
     app.refresh().unwrap();
 
@@ -86,6 +113,9 @@ fn preview_truncates_large_file() {
         settings: fileZoom::app::settings::write_settings::Settings::default(),
         op_progress_rx: None,
         op_cancel_flag: None,
+        op_skip_verify: None,
+        op_meter: None,
+        scheduler: Default::default(),
         op_decision_tx: None,
         last_mouse_click_time: None,
         last_mouse_click_pos: None,
@@ -93,6 +123,30 @@ fn preview_truncates_large_file() {
         drag_start: None,
         drag_current: None,
         drag_button: None,
+        scroll_drag: None,
+        dirty: false,
+        plugins: Default::default(),
+        diagnostics: Default::default(),
+        jobs: Default::default(),
+        pending_keys: Default::default(),
+        path_jump: Default::default(),
+        frecency: Default::default(),
+        tags: Default::default(),
+        searches: Default::default(),
+        clipboard: None,
+        other_panel: Default::default(),
+        info: Default::default(),
+        tree: Default::default(),
+        usage: None,
+        shell: None,
+        compare: None,
+        open_files: None,
+        chooser: None,
+        link: None,
+        templates: Default::default(),
+        owners: Default::default(),
+        preview_focused: Default::default(),
+        layout: Default::default(),
     };
     app.refresh().unwrap();
 
@@ -147,6 +201,9 @@ fn preview_shows_directory_entries_limited() {
         settings: fileZoom::app::settings::write_settings::Settings::default(),
         op_progress_rx: None,
         op_cancel_flag: None,
+        op_skip_verify: None,
+        op_meter: None,
+        scheduler: Default::default(),
         op_decision_tx: None,
         last_mouse_click_time: None,
         last_mouse_click_pos: None,
@@ -154,6 +211,30 @@ fn preview_shows_directory_entries_limited() {
         drag_start: None,
         drag_current: None,
         drag_button: None,
+        scroll_drag: None,
+        dirty: false,
+        plugins: Default::default(),
+        diagnostics: Default::default(),
+        jobs: Default::default(),
+        pending_keys: Default::default(),
+        path_jump: Default::default(),
+        frecency: Default::default(),
+        tags: Default::default(),
+        searches: Default::default(),
+        clipboard: None,
+        other_panel: Default::default(),
+        info: Default::default(),
+        tree: Default::default(),
+        usage: None,
+        shell: None,
+        compare: None,
+        open_files: None,
+        chooser: None,
+        link: None,
+        templates: Default::default(),
+        owners: Default::default(),
+        preview_focused: Default::default(),
+        layout: Default::default(),
     };
     app.refresh().unwrap();
 
@@ -211,6 +292,9 @@ fn preview_resets_preview_offset() {
         settings: fileZoom::app::settings::write_settings::Settings::default(),
         op_progress_rx: None,
         op_cancel_flag: None,
+        op_skip_verify: None,
+        op_meter: None,
+        scheduler: Default::default(),
         op_decision_tx: None,
         last_mouse_click_time: None,
         last_mouse_click_pos: None,
@@ -218,6 +302,30 @@ fn preview_resets_preview_offset() {
         drag_start: None,
         drag_current: None,
         drag_button: None,
+        scroll_drag: None,
+        dirty: false,
+        plugins: Default::default(),
+        diagnostics: Default::default(),
+        jobs: Default::default(),
+        pending_keys: Default::default(),
+        path_jump: Default::default(),
+        frecency: Default::default(),
+        tags: Default::default(),
+        searches: Default::default(),
+        clipboard: None,
+        other_panel: Default::default(),
+        info: Default::default(),
+        tree: Default::default(),
+        usage: None,
+        shell: None,
+        compare: None,
+        open_files: None,
+        chooser: None,
+        link: None,
+        templates: Default::default(),
+        owners: Default::default(),
+        preview_focused: Default::default(),
+        layout: Default::default(),
     };
     app.refresh().unwrap();
 
@@ -273,6 +381,9 @@ fn preview_handles_very_long_filename() {
         settings: fileZoom::app::settings::write_settings::Settings::default(),
         op_progress_rx: None,
         op_cancel_flag: None,
+        op_skip_verify: None,
+        op_meter: None,
+        scheduler: Default::default(),
         op_decision_tx: None,
         last_mouse_click_time: None,
         last_mouse_click_pos: None,
@@ -280,13 +391,37 @@ fn preview_handles_very_long_filename() {
         drag_start: None,
         drag_current: None,
         drag_button: None,
+        scroll_drag: None,
+        dirty: false,
+        plugins: Default::default(),
+        diagnostics: Default::default(),
+        jobs: Default::default(),
+        pending_keys: Default::default(),
+        path_jump: Default::default(),
+        frecency: Default::default(),
+        tags: Default::default(),
+        searches: Default::default(),
+        clipboard: None,
+        other_panel: Default::default(),
+        info: Default::default(),
+        tree: Default::default(),
+        usage: None,
+        shell: None,
+        compare: None,
+        open_files: None,
+        chooser: None,
+        link: None,
+        templates: Default::default(),
+        owners: Default::default(),
+        preview_focused: Default::default(),
+        layout: Default::default(),
     };
     app.refresh().unwrap();
 
     // find index of long filename
     let mut idx = None;
     for (i, e) in app.left.entries.iter().enumerate() {
-        if e.name == name {
+        if e.name == *name {
             idx = Some(i);
             break;
         }
@@ -339,6 +474,9 @@ fn preview_unreadable_file_shows_message() {
         settings: fileZoom::app::settings::write_settings::Settings::default(),
         op_progress_rx: None,
         op_cancel_flag: None,
+        op_skip_verify: None,
+        op_meter: None,
+        scheduler: Default::default(),
         op_decision_tx: None,
         last_mouse_click_time: None,
         last_mouse_click_pos: None,
@@ -346,6 +484,30 @@ fn preview_unreadable_file_shows_message() {
         drag_start: None,
         drag_current: None,
         drag_button: None,
+        scroll_drag: None,
+        dirty: false,
+        plugins: Default::default(),
+        diagnostics: Default::default(),
+        jobs: Default::default(),
+        pending_keys: Default::default(),
+        path_jump: Default::default(),
+        frecency: Default::default(),
+        tags: Default::default(),
+        searches: Default::default(),
+        clipboard: None,
+        other_panel: Default::default(),
+        info: Default::default(),
+        tree: Default::default(),
+        usage: None,
+        shell: None,
+        compare: None,
+        open_files: None,
+        chooser: None,
+        link: None,
+        templates: Default::default(),
+        owners: Default::default(),
+        preview_focused: Default::default(),
+        layout: Default::default(),
     };
     app.refresh().unwrap();
 
@@ -376,3 +538,97 @@ fn preview_unreadable_file_shows_message() {
 
     temp.close().unwrap();
 }
+
+#[test]
+fn refresh_side_keeps_selection_on_the_same_entries() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    temp.child("b.txt").write_str("b").unwrap();
+    temp.child("d.txt").write_str("d").unwrap();
+    let opts = fileZoom::app::StartOptions { start_dir: Some(temp.path().to_path_buf()), ..Default::default() };
+    let mut app = App::with_options(&opts).unwrap();
+    let parent = app.left.cwd.parent().is_some() as usize;
+    app.left.selected = 1 + parent + 1;
+    app.left.selections.insert(0);
+
+    // Files created before both entries (as another process would) shift
+    // their indices; the cursor and mark must follow the names.
+    temp.child("a.txt").write_str("a").unwrap();
+    temp.child("c.txt").write_str("c").unwrap();
+    app.refresh_side(Side::Left).unwrap();
+
+    assert_eq!(app.left.selected_entry().unwrap().name, "d.txt");
+    let marked: Vec<&str> = app.left.selections.iter().map(|&i| app.left.entries[i].name.to_str().unwrap()).collect();
+    assert_eq!(marked, vec!["b.txt"]);
+}
+
+#[test]
+fn refresh_side_reports_what_changed() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    temp.child("keep.txt").write_str("k").unwrap();
+    temp.child("gone.txt").write_str("g").unwrap();
+    let opts = fileZoom::app::StartOptions { start_dir: Some(temp.path().to_path_buf()), ..Default::default() };
+    let mut app = App::with_options(&opts).unwrap();
+
+    let unchanged = app.refresh_side(Side::Left).unwrap().unwrap();
+    assert!(unchanged.is_empty());
+
+    std::fs::remove_file(temp.path().join("gone.txt")).unwrap();
+    temp.child("new1.txt").write_str("1").unwrap();
+    temp.child("new2.txt").write_str("2").unwrap();
+    temp.child("keep.txt").write_str("longer").unwrap();
+    let changes = app.refresh_side(Side::Left).unwrap().unwrap();
+    assert_eq!((changes.added, changes.removed, changes.modified), (2, 1, 1));
+    assert_eq!(changes.to_string(), "+2 -1 files, 1 modified");
+
+    app.left.external_change = Some((changes, std::time::Instant::now()));
+    let state = fileZoom::ui::UIState::from_core(&app);
+    assert_eq!(state.change_hint.as_deref(), Some("Left: directory changed externally (+2 -1 files, 1 modified)"));
+}
+
+#[test]
+fn failing_watcher_refresh_backs_off_without_dialogs() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let share = temp.child("share");
+    share.create_dir_all().unwrap();
+    let opts = fileZoom::app::StartOptions { start_dir: Some(share.path().to_path_buf()), ..Default::default() };
+    let mut app = App::with_options(&opts).unwrap();
+
+    // The mount goes away: each failure only updates the banner.
+    std::fs::remove_dir(share.path()).unwrap();
+    let now = std::time::Instant::now();
+    app.refresh_watched(Side::Left, now);
+    assert!(matches!(app.mode, fileZoom::app::Mode::Normal));
+    assert_eq!(app.left.refresh_failure.as_ref().unwrap().failures, 1);
+    app.retry_failed_refreshes(now);
+    assert_eq!(app.left.refresh_failure.as_ref().unwrap().failures, 1);
+    app.retry_failed_refreshes(now + std::time::Duration::from_secs(1));
+    let failure = app.left.refresh_failure.clone().unwrap();
+    assert_eq!(failure.failures, 2);
+    assert_eq!(failure.retry_at, now + std::time::Duration::from_secs(3));
+    let banner = fileZoom::ui::widgets::warning_banner::refresh_warning(&app, now + std::time::Duration::from_secs(1)).unwrap();
+    assert!(banner.contains("cannot refresh") && banner.contains("failed 2 times, retrying in 2s"), "{}", banner);
+    assert!(matches!(app.mode, fileZoom::app::Mode::Normal));
+
+    // Once it is back, a manual refresh clears the banner.
+    share.create_dir_all().unwrap();
+    app.refresh().unwrap();
+    assert!(app.left.refresh_failure.is_none());
+    assert!(fileZoom::ui::widgets::warning_banner::refresh_warning(&app, now).is_none());
+}
+
+#[test]
+fn reload_preview_shows_new_content_and_keeps_scroll() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let log = temp.child("app.log");
+    log.write_str("one\ntwo\nthree\n").unwrap();
+    let opts = fileZoom::app::StartOptions { start_dir: Some(temp.path().to_path_buf()), ..Default::default() };
+    let mut app = App::with_options(&opts).unwrap();
+    app.left.selected = 1 + app.left.cwd.parent().is_some() as usize;
+    app.update_preview_for(Side::Left);
+    app.left.preview_offset = 2;
+
+    log.write_str("one\ntwo\nthree\nfour\n").unwrap();
+    app.reload_preview_for(Side::Left);
+    assert!(app.left.preview.contains("four"));
+    assert_eq!(app.left.preview_offset, 2);
+}
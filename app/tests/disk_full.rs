@@ -0,0 +1,74 @@
+//! Out-of-space handling, using the `test-helpers` ENOSPC simulation.
+//! Run with `cargo test --features test-helpers --test disk_full`.
+#![cfg(feature = "test-helpers")]
+
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+use fileZoom::app::{App, StartOptions};
+use fileZoom::input::KeyCode;
+use fileZoom::test_helpers::simulate_disk_full;
+
+fn leftovers(dir: &Path) -> Vec<String> {
+    fs::read_dir(dir)
+        .unwrap()
+        .map(|e| e.unwrap().file_name().to_string_lossy().into_owned())
+        .filter(|n| n.starts_with(".tmp_atomic_copy"))
+        .collect()
+}
+
+#[test]
+fn atomic_copy_out_of_space_removes_temp_file() {
+    let tmp = tempfile::tempdir().unwrap();
+    let src = tmp.path().join("big.bin");
+    fs::write(&src, vec![7u8; 64 * 1024]).unwrap();
+    let dst_dir = tmp.path().join("dst");
+    fs::create_dir(&dst_dir).unwrap();
+
+    let _full = simulate_disk_full(1024);
+    let err = fileZoom::fs_op::helpers::atomic_copy_file(&src, &dst_dir.join("big.bin")).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::StorageFull);
+    assert!(leftovers(&dst_dir).is_empty(), "temp file left behind");
+    assert!(!dst_dir.join("big.bin").exists());
+    assert!(src.exists());
+}
+
+#[test]
+fn copy_runs_out_of_space_mid_batch_and_reports_it() {
+    let tmp = tempfile::tempdir().unwrap();
+    let (left, right) = (tmp.path().join("left"), tmp.path().join("right"));
+    fs::create_dir_all(&left).unwrap();
+    fs::create_dir_all(&right).unwrap();
+    fs::write(left.join("a_small.txt"), "small").unwrap();
+    fs::write(left.join("b_big.bin"), vec![1u8; 256 * 1024]).unwrap();
+    // An existing target forces the per-item path, which copies a_small
+    // before running out of space on b_big.
+    fs::write(right.join("a_small.txt"), "old").unwrap();
+
+    let mut app = App::with_options(&StartOptions { start_dir: Some(left.clone()), right_dir: Some(right.clone()), ..Default::default() }).unwrap();
+    for i in 0..app.left.entries.len() {
+        app.left.selections.insert(i);
+    }
+
+    let _full = simulate_disk_full(4096);
+    fileZoom::runner::handlers::handle_key(&mut app, KeyCode::F(5), 10).unwrap();
+    let rx = app.op_progress_rx.as_ref().expect("copy started");
+    let mut error = None;
+    while let Ok(upd) = rx.recv_timeout(Duration::from_secs(5)) {
+        if upd.conflict.is_some() {
+            let _ = app.op_decision_tx.as_ref().unwrap().send(fileZoom::runner::progress::OperationDecision::Overwrite);
+            continue;
+        }
+        if upd.done {
+            error = upd.error;
+            break;
+        }
+    }
+
+    let error = error.expect("copy should fail");
+    assert!(error.contains("Not enough space") && error.contains("b_big.bin"), "{}", error);
+    assert_eq!(fs::read_to_string(right.join("a_small.txt")).unwrap(), "small");
+    assert!(!right.join("b_big.bin").exists());
+    assert!(leftovers(&right).is_empty(), "temp file left behind");
+}
@@ -0,0 +1,58 @@
+use fileZoom::app::{App, Mode};
+use fileZoom::input::KeyCode;
+use fileZoom::runner::handlers::handle_key;
+use std::time::{Duration, Instant};
+
+mod common;
+use common::{app_in, screen_sized};
+
+fn wait_for_scan(app: &mut App) {
+    let deadline = Instant::now() + Duration::from_secs(10);
+    while !app.usage.as_ref().unwrap().done {
+        assert!(Instant::now() < deadline, "timed out waiting for the scan");
+        std::thread::sleep(Duration::from_millis(10));
+        app.poll_usage();
+    }
+}
+
+fn names(app: &App) -> Vec<&str> {
+    app.usage.as_ref().unwrap().entries.iter().map(|e| e.name.as_str()).collect()
+}
+
+#[test]
+fn the_analyzer_sorts_by_size_and_deletes() {
+    let tmp = tempfile::tempdir().unwrap();
+    std::fs::create_dir_all(tmp.path().join("big/nested")).unwrap();
+    std::fs::write(tmp.path().join("big/nested/blob"), vec![0u8; 3000]).unwrap();
+    std::fs::write(tmp.path().join("big/more"), vec![0u8; 1000]).unwrap();
+    std::fs::write(tmp.path().join("medium.bin"), vec![0u8; 2000]).unwrap();
+    std::fs::write(tmp.path().join("small.txt"), "x").unwrap();
+    let mut app = app_in(tmp.path());
+
+    handle_key(&mut app, KeyCode::Char('U'), 10).unwrap();
+    assert!(matches!(app.mode, Mode::DiskUsage { selected: 0, .. }));
+    wait_for_scan(&mut app);
+    assert_eq!(names(&app), ["big", "medium.bin", "small.txt"]);
+    assert_eq!(app.usage.as_ref().unwrap().entries[0].bytes, 4000);
+    let text = screen_sized(&app, 100, 24);
+    assert!(text.contains("66.7% [####################] big/") && text.contains("33.3% [##########          ] medium.bin"), "{text}");
+
+    // Enter analyzes a directory, Backspace goes back up.
+    handle_key(&mut app, KeyCode::Enter, 10).unwrap();
+    wait_for_scan(&mut app);
+    assert_eq!(names(&app), ["nested", "more"]);
+    handle_key(&mut app, KeyCode::Backspace, 10).unwrap();
+    wait_for_scan(&mut app);
+
+    // `d` asks first; `y` deletes from disk and from the list.
+    handle_key(&mut app, KeyCode::Down, 10).unwrap();
+    handle_key(&mut app, KeyCode::Char('d'), 10).unwrap();
+    assert!(screen_sized(&app, 100, 24).contains("Delete medium.bin"));
+    handle_key(&mut app, KeyCode::Char('y'), 10).unwrap();
+    assert!(!tmp.path().join("medium.bin").exists());
+    assert_eq!(names(&app), ["big", "small.txt"]);
+    assert!(app.left.entries.iter().all(|e| e.name != "medium.bin"));
+
+    handle_key(&mut app, KeyCode::Esc, 10).unwrap();
+    assert!(matches!(app.mode, Mode::Normal) && app.usage.is_none());
+}
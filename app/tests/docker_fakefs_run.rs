@@ -167,3 +167,76 @@ fn docker_fakefs_run() {
         }
     }
 }
+
+/// Start each remote test server (SFTP, FTP, WebDAV) over a small generated
+/// tree and check it accepts connections. Opt in with `RUN_DOCKER_FAKEFS=1`
+/// like the test above; the containers are removed afterwards.
+#[test]
+#[ignore]
+fn docker_remote_servers_accept_connections() {
+    use fileZoom::building::remote_servers::{start_server, RemoteProtocol};
+
+    if env::var("RUN_DOCKER_FAKEFS").is_err() {
+        eprintln!("Skipping remote server test; set RUN_DOCKER_FAKEFS=1 to run");
+        return;
+    }
+    match Command::new("docker").arg("info").status() {
+        Ok(s) if s.success() => {}
+        _ => {
+            eprintln!("Docker does not appear to be available; skipping test");
+            return;
+        }
+    }
+
+    let fixtures = tempfile::tempdir().expect("tempdir");
+    for i in 0..20usize {
+        let dir = fixtures.path().join(format!("dir_{}", i % 4));
+        fs::create_dir_all(&dir).expect("failed to create dir");
+        fs::write(dir.join(format!("file_{:02}.txt", i)), format!("remote file {}", i)).expect("failed to write");
+    }
+
+    for protocol in RemoteProtocol::ALL {
+        let server = start_server(protocol, fixtures.path(), protocol.default_port() + 10_000)
+            .unwrap_or_else(|e| panic!("failed to start {} server: {:#}", protocol.name(), e));
+        server
+            .wait_ready(std::time::Duration::from_secs(60))
+            .unwrap_or_else(|e| panic!("{:#}", e));
+        println!("{} ready at {}", protocol.name(), server.url());
+    }
+}
+
+/// Copy a file that does not fit into a 256 KiB tmpfs with `fileZoom
+/// --batch` inside the fakefs image (build it first with `make_fakefs
+/// build`). The copy must fail with "No space left" and leave no temp
+/// file. Opt in with `RUN_DOCKER_FAKEFS=1`.
+#[test]
+#[ignore]
+fn docker_tmpfs_out_of_space_copy() {
+    use fileZoom::building::disk_full::{run_with_small_tmpfs, SMALL_MOUNT, SOURCE_MOUNT};
+
+    if env::var("RUN_DOCKER_FAKEFS").is_err() {
+        eprintln!("Skipping out-of-space test; set RUN_DOCKER_FAKEFS=1 to run");
+        return;
+    }
+    match Command::new("docker").arg("info").status() {
+        Ok(s) if s.success() => {}
+        _ => {
+            eprintln!("Docker does not appear to be available; skipping test");
+            return;
+        }
+    }
+
+    let source = tempfile::tempdir().expect("tempdir");
+    fs::write(source.path().join("big.bin"), vec![0u8; 1024 * 1024]).expect("failed to write");
+    let script = format!(
+        "echo 'copy {src}/big.bin {small}/' > /tmp/batch && /work/target/release/fileZoom --batch /tmp/batch; echo \"exit=$?\"; ls -A {small}",
+        src = SOURCE_MOUNT,
+        small = SMALL_MOUNT
+    );
+    let out = run_with_small_tmpfs(256, source.path(), &script).expect("docker run");
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    println!("{}", stdout);
+    assert!(stdout.contains("No space left"), "expected ENOSPC in batch output");
+    assert!(stdout.contains("exit=1"));
+    assert!(!stdout.contains(".tmp_atomic_copy"), "temp file left on the full filesystem");
+}
@@ -46,6 +46,9 @@ fn drag_does_not_select_across_panels() {
         settings: fileZoom::app::settings::write_settings::Settings::default(),
         op_progress_rx: None,
         op_cancel_flag: None,
+        op_skip_verify: None,
+        op_meter: None,
+        scheduler: Default::default(),
         op_decision_tx: None,
         last_mouse_click_time: None,
         last_mouse_click_pos: None,
@@ -53,6 +56,30 @@ fn drag_does_not_select_across_panels() {
         drag_start: None,
         drag_current: None,
         drag_button: None,
+        scroll_drag: None,
+        dirty: false,
+        plugins: Default::default(),
+        diagnostics: Default::default(),
+        jobs: Default::default(),
+        pending_keys: Default::default(),
+        path_jump: Default::default(),
+        frecency: Default::default(),
+        tags: Default::default(),
+        searches: Default::default(),
+        clipboard: None,
+        other_panel: Default::default(),
+        info: Default::default(),
+        tree: Default::default(),
+        usage: None,
+        shell: None,
+        compare: None,
+        open_files: None,
+        chooser: None,
+        link: None,
+        templates: Default::default(),
+        owners: Default::default(),
+        preview_focused: Default::default(),
+        layout: Default::default(),
     };
 
     // populate entries for both panels
@@ -129,6 +156,9 @@ fn drag_with_parent_row_present_selects_correct_domain_indices() {
         settings: fileZoom::app::settings::write_settings::Settings::default(),
         op_progress_rx: None,
         op_cancel_flag: None,
+        op_skip_verify: None,
+        op_meter: None,
+        scheduler: Default::default(),
         op_decision_tx: None,
         last_mouse_click_time: None,
         last_mouse_click_pos: None,
@@ -136,6 +166,30 @@ fn drag_with_parent_row_present_selects_correct_domain_indices() {
         drag_start: None,
         drag_current: None,
         drag_button: None,
+        scroll_drag: None,
+        dirty: false,
+        plugins: Default::default(),
+        diagnostics: Default::default(),
+        jobs: Default::default(),
+        pending_keys: Default::default(),
+        path_jump: Default::default(),
+        frecency: Default::default(),
+        tags: Default::default(),
+        searches: Default::default(),
+        clipboard: None,
+        other_panel: Default::default(),
+        info: Default::default(),
+        tree: Default::default(),
+        usage: None,
+        shell: None,
+        compare: None,
+        open_files: None,
+        chooser: None,
+        link: None,
+        templates: Default::default(),
+        owners: Default::default(),
+        preview_focused: Default::default(),
+        layout: Default::default(),
     };
 
     // populate left entries
@@ -206,6 +260,9 @@ fn drag_with_panel_offset_respects_offset() {
         settings: fileZoom::app::settings::write_settings::Settings::default(),
         op_progress_rx: None,
         op_cancel_flag: None,
+        op_skip_verify: None,
+        op_meter: None,
+        scheduler: Default::default(),
         op_decision_tx: None,
         last_mouse_click_time: None,
         last_mouse_click_pos: None,
@@ -213,6 +270,30 @@ fn drag_with_panel_offset_respects_offset() {
         drag_start: None,
         drag_current: None,
         drag_button: None,
+        scroll_drag: None,
+        dirty: false,
+        plugins: Default::default(),
+        diagnostics: Default::default(),
+        jobs: Default::default(),
+        pending_keys: Default::default(),
+        path_jump: Default::default(),
+        frecency: Default::default(),
+        tags: Default::default(),
+        searches: Default::default(),
+        clipboard: None,
+        other_panel: Default::default(),
+        info: Default::default(),
+        tree: Default::default(),
+        usage: None,
+        shell: None,
+        compare: None,
+        open_files: None,
+        chooser: None,
+        link: None,
+        templates: Default::default(),
+        owners: Default::default(),
+        preview_focused: Default::default(),
+        layout: Default::default(),
     };
 
     // many entries so offset matters
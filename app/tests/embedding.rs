@@ -0,0 +1,58 @@
+//! The `embed::FileBrowser` facade as another ratatui application would
+//! use it.
+
+use fileZoom::embed::{BrowserEvent, FileBrowser};
+use fileZoom::input::KeyCode;
+use fileZoom::Side;
+use ratatui::{backend::TestBackend, Terminal};
+
+#[test]
+fn browse_operate_and_receive_events() {
+    let tmp = tempfile::tempdir().unwrap();
+    std::fs::create_dir(tmp.path().join("docs")).unwrap();
+    std::fs::write(tmp.path().join("docs/readme.txt"), "hi").unwrap();
+    let mut browser = FileBrowser::open(tmp.path()).unwrap();
+    let events = browser.subscribe();
+    assert_eq!(browser.cwd(Side::Left), tmp.path());
+
+    // Select and enter a directory.
+    assert!(browser.select("docs"));
+    assert!(!browser.select("missing"));
+    browser.enter_selected().unwrap();
+    assert_eq!(browser.cwd(Side::Left), tmp.path().join("docs"));
+    let names: Vec<_> = browser.entries(Side::Left).iter().map(|e| e.display_name().into_owned()).collect();
+    assert_eq!(names, ["readme.txt"]);
+    let got: Vec<BrowserEvent> = events.try_iter().collect();
+    assert!(got.contains(&BrowserEvent::SelectionChanged { side: Side::Left, path: Some(tmp.path().join("docs")) }));
+    assert!(got.contains(&BrowserEvent::DirectoryChanged { side: Side::Left, dir: tmp.path().join("docs") }));
+
+    // Operations report the listing change.
+    browser.new_file("todo.txt").unwrap();
+    match events.try_iter().find(|e| matches!(e, BrowserEvent::ListingChanged { side: Side::Left, .. })) {
+        Some(BrowserEvent::ListingChanged { changes, .. }) => assert_eq!(changes.added, 1),
+        other => panic!("expected a listing change, got {:?}", other),
+    }
+    assert!(browser.select("todo.txt"));
+    browser.copy_selected_to(tmp.path()).unwrap();
+    assert!(tmp.path().join("todo.txt").exists());
+
+    // Keys go through fileZoom's own handling; Tab switches panels.
+    browser.handle_key(KeyCode::Tab, 10).unwrap();
+    assert_eq!(browser.active(), Side::Right);
+    assert!(events.try_iter().any(|e| e == BrowserEvent::ActivePanelChanged { side: Side::Right }));
+
+    // A failed navigation leaves the panel where it was.
+    assert!(browser.navigate(Side::Right, tmp.path().join("nope")).is_err());
+    assert_eq!(browser.cwd(Side::Right), tmp.path());
+    browser.navigate(Side::Right, tmp.path().join("docs")).unwrap();
+    assert_eq!(browser.entries(Side::Right).len(), 2);
+
+    let mut terminal = Terminal::new(TestBackend::new(100, 30)).unwrap();
+    browser.tick();
+    terminal.draw(|f| browser.draw(f)).unwrap();
+    let screen: String = terminal.backend().buffer().content().iter().map(|c| c.symbol()).collect();
+    assert!(screen.contains("todo.txt"));
+
+    drop(events);
+    browser.go_up().unwrap();
+}
@@ -38,3 +38,10 @@ fn test_move_error_uses_src_dst() {
             || out.contains("{dst}")
     );
 }
+
+#[test]
+fn test_storage_full_names_the_target() {
+    let e = io::Error::from(io::ErrorKind::StorageFull);
+    let out = render_io_error(&e, Some("/mnt/full/big.bin"), None, None);
+    assert!(out.contains("Not enough space") && out.contains("/mnt/full/big.bin"), "{}", out);
+}
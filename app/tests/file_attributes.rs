@@ -0,0 +1,40 @@
+use fileZoom::app::core::listing::is_hidden;
+use fileZoom::app::{App, Mode, StartOptions};
+use fileZoom::fs_op::attributes::FileAttributes;
+use fileZoom::runner::commands::execute_command;
+use fileZoom::Entry;
+
+#[test]
+fn hidden_attribute_hides_like_a_dot() {
+    let mut entry = Entry::file("notes.txt", "notes.txt".into(), 0, None);
+    assert!(!is_hidden(&entry));
+    entry.attributes = Some(FileAttributes(FileAttributes::HIDDEN | FileAttributes::ARCHIVE));
+    assert!(is_hidden(&entry));
+}
+
+#[test]
+fn attrib_command_changes_the_selection() {
+    let tmp = tempfile::tempdir().unwrap();
+    std::fs::write(tmp.path().join("a.txt"), "").unwrap();
+    let opts = StartOptions { start_dir: Some(tmp.path().to_path_buf()), ..Default::default() };
+    let mut app = App::with_options(&opts).unwrap();
+    assert!(app.left.select_named("a.txt"));
+
+    execute_command(&mut app, "attrib +r").unwrap();
+    if cfg!(windows) {
+        assert!(matches!(app.mode, Mode::Normal), "{:?}", app.mode);
+        let entry = app.left.selected_entry().unwrap();
+        assert!(entry.attributes.is_some_and(|a| a.contains(FileAttributes::READONLY)));
+        execute_command(&mut app, "attrib r").unwrap();
+        assert!(!app.left.selected_entry().unwrap().attributes.unwrap().contains(FileAttributes::READONLY));
+    } else {
+        let Mode::Message { content, .. } = &app.mode else { panic!("expected an error, got {:?}", app.mode) };
+        assert!(content.contains("only supported on Windows"), "{}", content);
+    }
+
+    // Unknown letters are refused on every platform.
+    app.mode = Mode::Normal;
+    execute_command(&mut app, "attrib +q").unwrap();
+    let Mode::Message { content, .. } = &app.mode else { panic!("expected an error, got {:?}", app.mode) };
+    assert!(content.contains("Unknown attribute"), "{}", content);
+}
@@ -0,0 +1,50 @@
+use fileZoom::app::core::tags::Tags;
+use fileZoom::app::{App, StartOptions};
+use fileZoom::runner::commands::execute_command;
+use fileZoom::ui::UIState;
+use ratatui::style::Color;
+
+#[test]
+fn tags_are_marked_selected_and_follow_renames() {
+    let tmp = tempfile::tempdir().unwrap();
+    let root = tmp.path().join("files");
+    std::fs::create_dir_all(&root).unwrap();
+    for file in ["a.txt", "b.txt", "c.txt"] {
+        std::fs::write(root.join(file), "").unwrap();
+    }
+    let db = tmp.path().join("tags.toml");
+    let opts = StartOptions { start_dir: Some(root.clone()), ..Default::default() };
+    let mut app = App::with_options(&opts).unwrap();
+    app.tags = Tags::load_from(db.clone()).unwrap();
+
+    assert!(app.left.select_named("a.txt"));
+    execute_command(&mut app, "tag work #ff0000").unwrap();
+    assert!(app.left.select_named("c.txt"));
+    execute_command(&mut app, "tag work").unwrap();
+    execute_command(&mut app, "tag later").unwrap();
+
+    let state = UIState::from_core(&app);
+    let marks: Vec<Vec<String>> = state.left_marks.iter().map(|m| m.iter().map(|(n, _)| n.clone()).collect()).collect();
+    assert_eq!(marks, [vec!["work"], vec![], vec!["work", "later"]]);
+    assert_eq!(state.left_marks[0][0].1, Color::Rgb(255, 0, 0));
+
+    execute_command(&mut app, "select-tag work").unwrap();
+    let mut selected: Vec<usize> = app.left.selections.iter().copied().collect();
+    selected.sort_unstable();
+    assert_eq!(selected, [0, 2]);
+
+    // Renaming in the app carries the tags along.
+    assert!(app.left.select_named("c.txt"));
+    app.left.clear_selections();
+    app.rename_selected_to("d.txt".to_string()).unwrap();
+    assert_eq!(app.tags.tags_of(&root.join("d.txt")), ["work", "later"]);
+    assert!(app.tags.tags_of(&root.join("c.txt")).is_empty());
+
+    // Saved after every change.
+    let reloaded = Tags::load_from(db).unwrap();
+    assert_eq!(reloaded.tags_of(&root.join("d.txt")), ["work", "later"]);
+    assert_eq!(reloaded.color_of("later"), Color::Yellow);
+
+    execute_command(&mut app, "untag").unwrap();
+    assert!(app.tags.tags_of(&root.join("d.txt")).is_empty());
+}
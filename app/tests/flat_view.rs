@@ -0,0 +1,54 @@
+use fileZoom::app::{App, Mode, StartOptions};
+use fileZoom::input::KeyCode;
+use fileZoom::runner::handlers::handle_key;
+use std::time::{Duration, Instant};
+
+fn names(app: &App) -> Vec<String> {
+    app.left.entries.iter().map(|e| e.display_name().into_owned()).collect()
+}
+
+#[test]
+fn flat_view_lists_sorts_and_copies_nested_files() {
+    let tmp = tempfile::tempdir().unwrap();
+    let root = tmp.path().join("left");
+    std::fs::create_dir_all(root.join("out/deep")).unwrap();
+    std::fs::create_dir_all(root.join(".cache")).unwrap();
+    std::fs::create_dir_all(tmp.path().join("right")).unwrap();
+    std::fs::write(root.join("out/deep/big.bin"), "0123456789").unwrap();
+    std::fs::write(root.join("out/a.log"), "12345").unwrap();
+    std::fs::write(root.join("top.txt"), "1").unwrap();
+    std::fs::write(root.join(".cache/skip"), "").unwrap();
+    let mut app = App::with_options(&StartOptions { start_dir: Some(root.clone()), ..Default::default() }).unwrap();
+    app.right.cwd = tmp.path().join("right");
+    app.refresh().unwrap();
+    assert_eq!(names(&app), ["out", "top.txt"]);
+
+    handle_key(&mut app, KeyCode::Char('F'), 10).unwrap();
+    assert!(app.left.is_flat());
+    assert_eq!(names(&app), ["out/a.log", "out/deep/big.bin", "top.txt"]);
+
+    // Sorting applies to the flattened entries: `s` cycles name -> size.
+    handle_key(&mut app, KeyCode::Char('s'), 10).unwrap();
+    assert_eq!(names(&app), ["top.txt", "out/a.log", "out/deep/big.bin"]);
+
+    // Copies take the nested file itself.
+    assert!(app.left.select_named("out/deep/big.bin"));
+    handle_key(&mut app, KeyCode::F(5), 10).unwrap();
+    let deadline = Instant::now() + Duration::from_secs(10);
+    while !matches!(&app.mode, Mode::Summary { title, .. } if title == "Done") {
+        assert!(Instant::now() < deadline, "timed out waiting for the copy");
+        std::thread::sleep(Duration::from_millis(10));
+        app.poll_progress();
+    }
+    assert_eq!(std::fs::read_to_string(tmp.path().join("right/big.bin")).unwrap(), "0123456789");
+    handle_key(&mut app, KeyCode::Enter, 10).unwrap();
+
+    // `F` again goes back to the plain listing; so does leaving the
+    // directory.
+    handle_key(&mut app, KeyCode::Char('F'), 10).unwrap();
+    assert_eq!(names(&app).len(), 2);
+    handle_key(&mut app, KeyCode::Char('F'), 10).unwrap();
+    app.go_to(root.join("out")).unwrap();
+    assert!(!app.left.is_flat());
+    assert_eq!(names(&app), ["deep", "a.log"]);
+}
@@ -0,0 +1,50 @@
+use fileZoom::app::{App, Mode, StartOptions};
+use fileZoom::runner::commands::execute_command;
+
+#[test]
+fn jump_goes_to_the_most_visited_match() {
+    let tmp = tempfile::tempdir().unwrap();
+    for d in ["work/rust_mc", "old/rust_mc_backup", "music"] {
+        std::fs::create_dir_all(tmp.path().join(d)).unwrap();
+    }
+    let opts = StartOptions { start_dir: Some(tmp.path().to_path_buf()), ..Default::default() };
+    let mut app = App::with_options(&opts).unwrap();
+    for _ in 0..3 {
+        app.go_to(tmp.path().join("work/rust_mc")).unwrap();
+        app.go_to(tmp.path().join("music")).unwrap();
+    }
+    app.go_to(tmp.path().join("old/rust_mc_backup")).unwrap();
+    app.go_to(tmp.path().join("music")).unwrap();
+
+    assert!(execute_command(&mut app, "jump rmc").unwrap());
+    assert_eq!(app.left.cwd, tmp.path().join("work/rust_mc"));
+    execute_command(&mut app, "jump old rmc").unwrap();
+    assert_eq!(app.left.cwd, tmp.path().join("old/rust_mc_backup"));
+
+    execute_command(&mut app, "jump nothing-here").unwrap();
+    assert!(matches!(&app.mode, Mode::Message { content, .. } if content.contains("No visited directory matches")));
+}
+
+#[test]
+fn imports_a_zoxide_database() {
+    let tmp = tempfile::tempdir().unwrap();
+    let srv = tmp.path().join("srv");
+    std::fs::create_dir_all(&srv).unwrap();
+    let path = srv.to_string_lossy().into_owned();
+    let mut db = 3u32.to_le_bytes().to_vec();
+    db.extend(1u64.to_le_bytes());
+    db.extend((path.len() as u64).to_le_bytes());
+    db.extend(path.as_bytes());
+    db.extend(40.0f64.to_le_bytes());
+    db.extend(1_700_000_000u64.to_le_bytes());
+    let db_path = tmp.path().join("db.zo");
+    std::fs::write(&db_path, db).unwrap();
+
+    let opts = StartOptions { start_dir: Some(tmp.path().to_path_buf()), ..Default::default() };
+    let mut app = App::with_options(&opts).unwrap();
+    execute_command(&mut app, &format!("jump-import {}", db_path.display())).unwrap();
+    assert!(matches!(&app.mode, Mode::Message { content, .. } if content.starts_with("Imported 1 directories")));
+    app.mode = Mode::Normal;
+    execute_command(&mut app, "jump srv").unwrap();
+    assert_eq!(app.left.cwd, srv);
+}
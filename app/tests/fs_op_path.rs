@@ -1,9 +1,14 @@
 use fileZoom::fs_op::path::resolve_path;
 use fileZoom::fs_op::path::PathError;
+use fileZoom::fs_op::path::{abbreviate_home, complete_dir, display_path, fuzzy_match, fuzzy_score, names_collide, strip_verbatim};
 use std::fs;
 use std::path::Path;
+use std::sync::Mutex;
 use tempfile::TempDir;
 
+// Tests that modify `HOME` must not run concurrently.
+static HOME_LOCK: Mutex<()> = Mutex::new(());
+
 #[test]
 fn empty_input_is_error() {
     let base = Path::new("/");
@@ -14,6 +19,7 @@ fn empty_input_is_error() {
 
 #[test]
 fn tilde_expands_to_home() {
+    let _guard = HOME_LOCK.lock().unwrap();
     let td = TempDir::new().unwrap();
     std::env::set_var("HOME", td.path());
     let base = Path::new("/irrelevant");
@@ -54,3 +60,90 @@ fn nonexistent_path_errors() {
     let err = resolve_path(&p.to_string_lossy(), td.path()).unwrap_err();
     assert!(matches!(err, PathError::NotFound(q) if q == p));
 }
+
+#[test]
+fn home_is_abbreviated_to_tilde() {
+    let _guard = HOME_LOCK.lock().unwrap();
+    let td = TempDir::new().unwrap();
+    std::env::set_var("HOME", td.path());
+    assert_eq!(abbreviate_home(td.path()), "~");
+    assert_eq!(abbreviate_home(&td.path().join("docs/a.txt")), "~/docs/a.txt");
+    assert_eq!(abbreviate_home(Path::new("/usr/share")), "/usr/share");
+}
+
+#[test]
+fn short_paths_are_not_truncated() {
+    assert_eq!(display_path(Path::new("/usr/share"), 40), "/usr/share");
+}
+
+#[test]
+fn long_paths_are_middle_truncated_keeping_leaf() {
+    let p = Path::new("/usr/share/some/very/deeply/nested/directory/leaf.txt");
+    let got = display_path(p, 24);
+    assert_eq!(got.chars().count(), 24);
+    assert!(got.starts_with("/usr/share"), "head kept: {}", got);
+    assert!(got.ends_with("…/leaf.txt"), "leaf kept: {}", got);
+}
+
+#[test]
+fn oversized_leaf_keeps_its_tail() {
+    let p = Path::new("/usr/a_really_long_file_name_that_does_not_fit.txt");
+    let got = display_path(p, 12);
+    assert_eq!(got, "…not_fit.txt");
+}
+
+#[test]
+fn fuzzy_scores_prefer_word_starts_and_runs() {
+    assert_eq!(fuzzy_score("", "anything"), Some(0));
+    assert_eq!(fuzzy_score("xyz", "projects"), None);
+    assert!(fuzzy_score("pro", "projects") > fuzzy_score("pro", "reprocess"));
+    assert!(fuzzy_score("src", "src") > fuzzy_score("src", "resources"));
+    assert!(fuzzy_score("DL", "downloads").is_some());
+}
+
+#[test]
+fn fuzzy_matches_pick_the_best_placement() {
+    // `mc` is placed on the word starts, not the first `m` and `c`.
+    assert_eq!(fuzzy_match("mc", "emacs_mc.rs", false).map(|(_, p)| p), Some(vec![6, 7]));
+    assert_eq!(fuzzy_match("fb", "fooBar", false).map(|(_, p)| p), Some(vec![0, 3]));
+    assert!(fuzzy_match("fb", "fooBar", true).is_none());
+    assert!(fuzzy_match("cfg", "config.rs", false) > fuzzy_match("cfg", "scaffolding", false));
+    assert_eq!(fuzzy_match("", "x", false), Some((0, Vec::new())));
+    assert!(fuzzy_match("abc", "ab", false).is_none());
+}
+
+#[test]
+fn completes_directories_under_the_typed_prefix() {
+    let td = TempDir::new().unwrap();
+    for d in ["projects", "photos", "music", ".config", "projects/rust_mc"] {
+        fs::create_dir_all(td.path().join(d)).unwrap();
+    }
+    fs::write(td.path().join("pfile"), "x").unwrap();
+    let sep = std::path::MAIN_SEPARATOR;
+
+    assert_eq!(complete_dir("p", td.path()), [format!("photos{}", sep), format!("projects{}", sep)]);
+    assert_eq!(complete_dir("pjs", td.path()), [format!("projects{}", sep)]);
+    assert_eq!(complete_dir(".c", td.path()), [format!(".config{}", sep)]);
+    assert_eq!(complete_dir("projects/r", td.path()), [format!("projects/rust_mc{}", sep)]);
+    assert!(complete_dir("missing/", td.path()).is_empty());
+}
+
+#[test]
+fn names_equal_ignoring_case_collide() {
+    let paths = |names: &[&str]| names.iter().map(|n| Path::new("/src").join(n)).collect::<Vec<_>>();
+    assert!(names_collide(&paths(&["a/Readme.md", "b/README.md"])));
+    assert!(!names_collide(&paths(&["Readme.md", "Readme.txt"])));
+    // Only verbatim paths change.
+    assert_eq!(strip_verbatim(Path::new("/a/b")), Path::new("/a/b"));
+}
+
+#[cfg(windows)]
+#[test]
+fn verbatim_and_drive_paths_are_plain() {
+    assert_eq!(strip_verbatim(Path::new(r"\\?\C:\Users\x")), Path::new(r"C:\Users\x"));
+    assert_eq!(strip_verbatim(Path::new(r"\\?\UNC\server\share\dir")), Path::new(r"\\server\share\dir"));
+    let sys = std::env::var("SystemDrive").unwrap_or_else(|_| "C:".to_string());
+    assert_eq!(resolve_path(&sys, Path::new(r"\\server\share")).unwrap(), Path::new(&format!("{}\\", sys)));
+    let canonical = fs::canonicalize(std::env::temp_dir()).unwrap();
+    assert!(!resolve_path(&canonical.to_string_lossy(), Path::new("C:\\")).unwrap().to_string_lossy().starts_with(r"\\?\"));
+}
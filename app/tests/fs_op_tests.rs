@@ -47,3 +47,58 @@ fn copy_dir_recursive() -> Result<(), Box<dyn std::error::Error>> {
 
     Ok(())
 }
+
+#[test]
+fn copy_options_control_overwrite() -> Result<(), Box<dyn std::error::Error>> {
+    use fileZoom::fs_op::helpers::atomic_copy_file_with;
+    use fileZoom::fs_op::options::CopyOptions;
+
+    let temp = assert_fs::TempDir::new()?;
+    let src = temp.child("src.txt");
+    src.write_str("new")?;
+    let dst = temp.child("dst.txt");
+    dst.write_str("old")?;
+
+    let err = atomic_copy_file_with(src.path(), dst.path(), &CopyOptions::default()).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::AlreadyExists);
+    dst.assert("old");
+
+    let opts = CopyOptions { overwrite: true, buffer_size: 4, ..Default::default() };
+    atomic_copy_file_with(src.path(), dst.path(), &opts)?;
+    dst.assert("new");
+
+    // copy_path_with refuses to clobber files inside an existing tree.
+    let d = temp.child("tree");
+    d.child("f.txt").write_str("tree")?;
+    let out = temp.child("out");
+    out.child("f.txt").write_str("keep")?;
+    assert!(copy_path_with(d.path(), out.path(), &CopyOptions::default()).is_err());
+    out.child("f.txt").assert("keep");
+    copy_path_with(d.path(), out.path(), &CopyOptions { overwrite: true, ..Default::default() })?;
+    out.child("f.txt").assert("tree");
+
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn copy_options_can_copy_symlinks_as_links() -> Result<(), Box<dyn std::error::Error>> {
+    use fileZoom::fs_op::helpers::atomic_copy_file_with;
+    use fileZoom::fs_op::options::CopyOptions;
+
+    let temp = assert_fs::TempDir::new()?;
+    temp.child("target.txt").write_str("t")?;
+    let link = temp.child("link");
+    std::os::unix::fs::symlink("target.txt", link.path())?;
+
+    let as_link = temp.child("as_link");
+    let opts = CopyOptions { follow_symlinks: false, ..Default::default() };
+    atomic_copy_file_with(link.path(), as_link.path(), &opts)?;
+    assert_eq!(std::fs::read_link(as_link.path())?, std::path::PathBuf::from("target.txt"));
+
+    let followed = temp.child("followed");
+    atomic_copy_file_with(link.path(), followed.path(), &CopyOptions::default())?;
+    assert!(!std::fs::symlink_metadata(followed.path())?.file_type().is_symlink());
+    followed.assert("t");
+    Ok(())
+}
@@ -0,0 +1,60 @@
+#![cfg(feature = "git")]
+
+use std::path::Path;
+use std::process::Command;
+
+use fileZoom::app::{App, StartOptions};
+use fileZoom::integrations::git::GitFileStatus;
+use ratatui::{backend::TestBackend, Terminal};
+
+fn git(dir: &Path, args: &[&str]) -> bool {
+    Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(["-c", "user.name=Test", "-c", "user.email=test@example.com", "-c", "init.defaultBranch=main"])
+        .args(args)
+        .output()
+        .is_ok_and(|o| o.status.success())
+}
+
+#[test]
+fn listing_shows_git_status_and_branch() {
+    let tmp = tempfile::tempdir().unwrap();
+    let repo = tmp.path();
+    if !git(repo, &["init"]) {
+        eprintln!("git not available; skipping");
+        return;
+    }
+    std::fs::write(repo.join("tracked.txt"), "one").unwrap();
+    std::fs::write(repo.join(".gitignore"), "build/\n").unwrap();
+    std::fs::create_dir(repo.join("src")).unwrap();
+    std::fs::write(repo.join("src/lib.rs"), "").unwrap();
+    assert!(git(repo, &["add", "."]) && git(repo, &["commit", "-m", "init"]));
+    std::fs::write(repo.join("tracked.txt"), "two").unwrap();
+    std::fs::write(repo.join("src/lib.rs"), "fn main() {}").unwrap();
+    assert!(git(repo, &["add", "src/lib.rs"]));
+    std::fs::write(repo.join("new.txt"), "").unwrap();
+    std::fs::create_dir(repo.join("build")).unwrap();
+    std::fs::write(repo.join("build/out.o"), "").unwrap();
+
+    let opts = StartOptions { start_dir: Some(repo.to_path_buf()), ..Default::default() };
+    let app = App::with_options(&opts).unwrap();
+    let status = app.left.git.as_ref().expect("git status read");
+    assert_eq!(status.branch.as_deref(), Some("main"));
+    let of = |name: &str| status.status_of(&repo.join(name));
+    assert_eq!(of("tracked.txt"), Some(GitFileStatus::Modified));
+    assert_eq!(of("src"), Some(GitFileStatus::Staged));
+    assert_eq!(of("new.txt"), Some(GitFileStatus::Untracked));
+    assert_eq!(of("build"), Some(GitFileStatus::Ignored));
+    assert_eq!(of(".gitignore"), None);
+
+    let mut terminal = Terminal::new(TestBackend::new(100, 30)).unwrap();
+    terminal.draw(|f| fileZoom::ui::ui(f, &app)).unwrap();
+    let screen: String = terminal.backend().buffer().content().iter().map(|c| c.symbol()).collect();
+    assert!(screen.contains("[main]"));
+
+    // Outside a work tree nothing is annotated.
+    let plain = tempfile::tempdir().unwrap();
+    let app = App::with_options(&StartOptions { start_dir: Some(plain.path().to_path_buf()), ..Default::default() }).unwrap();
+    assert!(app.left.git.is_none());
+}
@@ -0,0 +1,30 @@
+use fileZoom::app::{App, StartOptions};
+use fileZoom::runner::commands::execute_command;
+
+#[test]
+fn toggling_hides_gitignored_entries() {
+    let tmp = tempfile::tempdir().unwrap();
+    let root = tmp.path();
+    std::fs::create_dir_all(root.join(".git")).unwrap();
+    std::fs::create_dir_all(root.join("target")).unwrap();
+    std::fs::write(root.join(".gitignore"), "target/\n*.log\n").unwrap();
+    for file in ["main.rs", "debug.log"] {
+        std::fs::write(root.join(file), "").unwrap();
+    }
+    let opts = StartOptions { start_dir: Some(root.to_path_buf()), ..Default::default() };
+    let mut app = App::with_options(&opts).unwrap();
+    let names = |app: &App| app.left.entries.iter().map(|e| e.name.to_string_lossy().into_owned()).collect::<Vec<_>>();
+    assert_eq!(names(&app), ["target", "debug.log", "main.rs"]);
+
+    execute_command(&mut app, "toggle-gitignored").unwrap();
+    assert!(app.settings.hide_gitignored);
+    assert_eq!(names(&app), ["main.rs"]);
+
+    // Inside an ignored directory nothing is listed.
+    std::fs::write(root.join("target/out.o"), "").unwrap();
+    app.go_to(root.join("target")).unwrap();
+    assert!(app.left.entries.is_empty());
+
+    execute_command(&mut app, "toggle-gitignored").unwrap();
+    assert_eq!(names(&app), ["out.o"]);
+}
@@ -0,0 +1,77 @@
+use fileZoom::app::{App, OtherPanel, StartOptions};
+use fileZoom::input::KeyCode;
+use fileZoom::runner::handlers::handle_key;
+use fileZoom::ui::layout::ScreenLayout;
+use ratatui::{backend::TestBackend, layout::Rect, Terminal};
+
+fn text_in(terminal: &mut Terminal<TestBackend>, app: &App, area: Rect) -> String {
+    terminal.draw(|f| fileZoom::ui::ui(f, app)).unwrap();
+    let buf = terminal.backend().buffer();
+    (area.y..area.y + area.height)
+        .map(|y| (area.x..area.x + area.width).map(|x| buf[(x, y)].symbol()).collect::<String>() + "\n")
+        .collect()
+}
+
+#[test]
+fn info_panel_shows_details_and_directory_totals() {
+    let tmp = tempfile::tempdir().unwrap();
+    let sub = tmp.path().join("sub");
+    std::fs::create_dir_all(sub.join("deeper")).unwrap();
+    std::fs::write(sub.join("a.bin"), vec![0u8; 1000]).unwrap();
+    std::fs::write(sub.join("deeper/b.bin"), vec![0u8; 24]).unwrap();
+    let opts = StartOptions { start_dir: Some(tmp.path().to_path_buf()), ..Default::default() };
+    let mut app = App::with_options(&opts).unwrap();
+    assert!(app.left.select_named("sub"));
+    let area = Rect::new(0, 0, 100, 30);
+    let mut terminal = Terminal::new(TestBackend::new(area.width, area.height)).unwrap();
+
+    handle_key(&mut app, KeyCode::Char('i'), 10).unwrap();
+    assert_eq!(app.other_panel, OtherPanel::Info);
+    let layout = ScreenLayout::compute(&app, area);
+    let info = layout.info.expect("info panel");
+    assert_eq!(info.x, 50);
+    assert!(layout.right.is_empty());
+
+    let deadline = std::time::Instant::now() + std::time::Duration::from_secs(10);
+    while app.info.totals.is_none() && std::time::Instant::now() < deadline {
+        app.poll_listings();
+        std::thread::sleep(std::time::Duration::from_millis(10));
+    }
+    let text = text_in(&mut terminal, &app, info);
+    assert!(text.contains("directory"), "{text}");
+    assert!(text.contains("3 entries, 1024 B"), "{text}");
+    #[cfg(unix)]
+    assert!(text.contains("Inode") && text.contains("Links") && text.contains("Xattrs"), "{text}");
+
+    // Quick view replaces it; pressing `i` twice goes back to the listing.
+    app.toggle_quick_view();
+    assert!(ScreenLayout::compute(&app, area).info.is_none());
+    handle_key(&mut app, KeyCode::Char('i'), 10).unwrap();
+    handle_key(&mut app, KeyCode::Char('i'), 10).unwrap();
+    assert_eq!(app.other_panel, OtherPanel::Listing);
+}
+
+#[test]
+fn info_panel_shows_format_details_of_files() {
+    let tmp = tempfile::tempdir().unwrap();
+    let mut png = b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR".to_vec();
+    png.extend_from_slice(&1920u32.to_be_bytes());
+    png.extend_from_slice(&1080u32.to_be_bytes());
+    std::fs::write(tmp.path().join("shot.png"), png).unwrap();
+    let opts = StartOptions { start_dir: Some(tmp.path().to_path_buf()), ..Default::default() };
+    let mut app = App::with_options(&opts).unwrap();
+    assert!(app.left.select_named("shot.png"));
+    let area = Rect::new(0, 0, 100, 30);
+    let mut terminal = Terminal::new(TestBackend::new(area.width, area.height)).unwrap();
+    handle_key(&mut app, KeyCode::Char('i'), 10).unwrap();
+
+    let deadline = std::time::Instant::now() + std::time::Duration::from_secs(10);
+    while app.info.details.is_none() && std::time::Instant::now() < deadline {
+        app.poll_listings();
+        std::thread::sleep(std::time::Duration::from_millis(10));
+    }
+    let info = ScreenLayout::compute(&app, area).info.expect("info panel");
+    let text = text_in(&mut terminal, &app, info);
+    assert!(text.contains("PNG image"), "{text}");
+    assert!(text.contains("1920 × 1080"), "{text}");
+}
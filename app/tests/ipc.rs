@@ -0,0 +1,143 @@
+#![cfg(unix)]
+
+use fileZoom::app::{App, Mode, Side, StartOptions};
+use fileZoom::runner::ipc::{self, IpcCommand, IpcServer};
+use std::time::{Duration, Instant};
+
+fn wait_for_listing(app: &mut App) {
+    let deadline = Instant::now() + Duration::from_secs(10);
+    while app.listing_pending() {
+        assert!(Instant::now() < deadline, "timed out waiting for the listing");
+        std::thread::sleep(Duration::from_millis(10));
+        app.poll_listings();
+    }
+}
+
+/// A directory the socket may be opened in: one only the user can enter.
+fn private_tempdir() -> tempfile::TempDir {
+    use std::os::unix::fs::PermissionsExt;
+    let tmp = tempfile::tempdir().unwrap();
+    std::fs::set_permissions(tmp.path(), std::fs::Permissions::from_mode(0o700)).unwrap();
+    tmp
+}
+
+fn next_command(server: &IpcServer) -> IpcCommand {
+    let deadline = Instant::now() + Duration::from_secs(10);
+    loop {
+        if let Some(command) = server.commands().next() {
+            return command;
+        }
+        assert!(Instant::now() < deadline, "timed out waiting for the command");
+        std::thread::sleep(Duration::from_millis(10));
+    }
+}
+
+#[test]
+fn remote_commands_reach_the_running_instance() {
+    let tmp = private_tempdir();
+    let (home, project) = (tmp.path().join("home"), tmp.path().join("project"));
+    std::fs::create_dir_all(project.join("src")).unwrap();
+    std::fs::create_dir_all(&home).unwrap();
+    for name in ["a.txt", "b.txt", "main.rs"] {
+        std::fs::write(project.join("src").join(name), "").unwrap();
+    }
+    let socket = tmp.path().join("fileZoom.sock");
+    let server = IpcServer::start(&socket).unwrap();
+    // A second instance does not take over the socket.
+    assert_eq!(IpcServer::start(&socket).err().map(|e| e.kind()), Some(std::io::ErrorKind::AddrInUse));
+
+    let mut app = App::with_options(&StartOptions { start_dir: Some(home.clone()), ..Default::default() }).unwrap();
+
+    // `reveal` lists the directory with the file selected.
+    let target = project.join("src/main.rs");
+    assert_eq!(ipc::send(&socket, &IpcCommand::Reveal(target.clone())).unwrap(), Ok(()));
+    ipc::apply(&mut app, next_command(&server));
+    wait_for_listing(&mut app);
+    assert_eq!(app.left.cwd, project.join("src"));
+    assert_eq!(app.left.selected_entry().map(|e| e.path.clone()), Some(target));
+
+    // `open-right` switches the right panel and makes it active.
+    assert_eq!(ipc::send(&socket, &IpcCommand::Open(Some(Side::Right), project.clone())).unwrap(), Ok(()));
+    ipc::apply(&mut app, next_command(&server));
+    wait_for_listing(&mut app);
+    assert_eq!((app.active, &app.right.cwd), (Side::Right, &project));
+
+    // Bad commands are answered, not delivered.
+    let missing = IpcCommand::Open(None, tmp.path().join("missing"));
+    assert!(ipc::send(&socket, &missing).unwrap().unwrap_err().contains("not a directory"));
+    assert!(server.commands().next().is_none());
+
+    drop(server);
+    assert!(!socket.exists());
+    assert!(ipc::send(&socket, &IpcCommand::Focus).is_err());
+}
+
+#[test]
+fn the_socket_is_only_opened_in_a_private_directory() {
+    use std::os::unix::fs::PermissionsExt;
+    let tmp = tempfile::tempdir().unwrap();
+    let shared = tmp.path().join("shared");
+    std::fs::create_dir(&shared).unwrap();
+    std::fs::set_permissions(&shared, std::fs::Permissions::from_mode(0o777)).unwrap();
+    let refused = IpcServer::start(&shared.join("fileZoom.sock")).err().map(|e| e.kind());
+    assert_eq!(refused, Some(std::io::ErrorKind::PermissionDenied));
+
+    // A missing directory is created for the user alone.
+    let socket = tmp.path().join("fileZoom-test/fileZoom.sock");
+    let _server = IpcServer::start(&socket).unwrap();
+    let mode = std::fs::metadata(socket.parent().unwrap()).unwrap().permissions().mode();
+    assert_eq!(mode & 0o777, 0o700);
+    assert_eq!(std::fs::metadata(&socket).unwrap().permissions().mode() & 0o777, 0o600);
+}
+
+#[test]
+fn an_idle_client_does_not_block_the_listener() {
+    let tmp = private_tempdir();
+    let socket = tmp.path().join("fileZoom.sock");
+    let server = IpcServer::start(&socket).unwrap();
+    let _idle = std::os::unix::net::UnixStream::connect(&socket).unwrap();
+
+    let started = Instant::now();
+    assert_eq!(ipc::send(&socket, &IpcCommand::Focus).unwrap(), Ok(()));
+    assert!(started.elapsed() < ipc::CLIENT_TIMEOUT * 2);
+    assert_eq!(next_command(&server), IpcCommand::Focus);
+}
+
+#[test]
+fn commands_wait_until_the_open_dialog_is_closed() {
+    let tmp = private_tempdir();
+    let (home, gone) = (tmp.path().join("home"), tmp.path().join("gone"));
+    std::fs::create_dir_all(&home).unwrap();
+    std::fs::create_dir_all(&gone).unwrap();
+    let socket = tmp.path().join("fileZoom.sock");
+    let server = IpcServer::start(&socket).unwrap();
+    let mut app = App::with_options(&StartOptions { start_dir: Some(home.clone()), ..Default::default() }).unwrap();
+    let dialog = || Mode::Message {
+        title: "Note".to_string(),
+        content: "kept".to_string(),
+        buttons: vec!["OK".to_string()],
+        selected: 0,
+        actions: None,
+    };
+
+    // Accepted while browsing, but the directory is gone by the time the
+    // command is applied.
+    assert_eq!(ipc::send(&socket, &IpcCommand::Open(None, gone.clone())).unwrap(), Ok(()));
+    std::fs::remove_dir(&gone).unwrap();
+    app.mode = dialog();
+    ipc::poll(&server, &mut app);
+    assert!(matches!(&app.mode, Mode::Message { title, .. } if title == "Note"));
+    assert_eq!(ipc::send(&socket, &IpcCommand::Focus).unwrap(), Err(ipc::BUSY.to_string()));
+
+    // Closing the dialog applies the queued command; the failed listing
+    // leaves the panel where it was.
+    app.mode = Mode::Normal;
+    let deadline = Instant::now() + Duration::from_secs(10);
+    while matches!(app.mode, Mode::Normal) {
+        assert!(Instant::now() < deadline, "timed out waiting for the command");
+        std::thread::sleep(Duration::from_millis(10));
+        ipc::poll(&server, &mut app);
+    }
+    assert!(matches!(&app.mode, Mode::Message { title, .. } if title == "Error"));
+    assert_eq!(app.left.cwd, home);
+}
@@ -0,0 +1,51 @@
+use assert_fs::prelude::*;
+use fileZoom::app::core::jobs::JobHistory;
+use fileZoom::app::{App, Mode, Panel, StartOptions};
+use fileZoom::input::KeyCode;
+use fileZoom::runner::handlers::handle_key;
+use std::time::{Duration, Instant};
+
+#[test]
+fn finished_copy_is_recorded_persisted_and_shown() {
+    let tmp = assert_fs::TempDir::new().unwrap();
+    let left = tmp.child("left");
+    let right = tmp.child("right");
+    left.child("a.txt").write_str("a").unwrap();
+    right.create_dir_all().unwrap();
+
+    let opts = StartOptions { start_dir: Some(left.path().to_path_buf()), ..Default::default() };
+    let mut app = App::with_options(&opts).unwrap();
+    app.right = Panel::new(right.path().to_path_buf());
+    app.refresh().unwrap();
+    let history_file = tmp.child("job_history.toml");
+    app.jobs = JobHistory::load_from(history_file.path().to_path_buf()).unwrap();
+
+    let idx = app.left.entries.iter().position(|e| e.name == "a.txt").unwrap();
+    app.left.selections.insert(idx);
+    handle_key(&mut app, KeyCode::F(5), 10).unwrap();
+
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while app.op_progress_rx.is_some() && Instant::now() < deadline {
+        app.poll_progress();
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    right.child("a.txt").assert("a");
+
+    assert_eq!(app.jobs.records.len(), 1);
+    let job = &app.jobs.records[0];
+    assert_eq!((job.operation.as_str(), job.processed, job.total, job.error.as_deref()), ("Copy", 1, 1, None));
+
+    // The record survives a restart.
+    let reloaded = JobHistory::load_from(history_file.path().to_path_buf()).unwrap();
+    assert_eq!(reloaded.records, app.jobs.records);
+
+    app.mode = Mode::Normal;
+    handle_key(&mut app, KeyCode::Char('J'), 10).unwrap();
+    match &app.mode {
+        Mode::Message { title, content, .. } => {
+            assert_eq!(title, "Job history");
+            assert!(content.contains("Copy") && content.contains("1/1 items"), "{}", content);
+        }
+        other => panic!("expected job history dialog, got {:?}", other),
+    }
+}
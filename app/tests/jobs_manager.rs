@@ -0,0 +1,150 @@
+use fileZoom::app::{App, Mode};
+use fileZoom::input::KeyCode;
+use fileZoom::runner::handlers::handle_key;
+use fileZoom::runner::jobs;
+use std::time::{Duration, Instant};
+
+mod common;
+use common::{app_in, screen_sized};
+
+/// An app in `<tmp>/left` copying into `<tmp>/right`, where `taken.txt`
+/// exists on both sides so copying it waits for an answer.
+fn setup(tmp: &std::path::Path) -> App {
+    std::fs::create_dir_all(tmp.join("left")).unwrap();
+    std::fs::create_dir_all(tmp.join("right")).unwrap();
+    for name in ["taken.txt", "free.txt"] {
+        std::fs::write(tmp.join("left").join(name), "new").unwrap();
+    }
+    std::fs::write(tmp.join("right/taken.txt"), "old").unwrap();
+    let mut app = app_in(&tmp.join("left"));
+    app.right.cwd = tmp.join("right");
+    app.refresh().unwrap();
+    app
+}
+
+fn poll_until(app: &mut App, what: &str, done: impl Fn(&App) -> bool) {
+    let deadline = Instant::now() + Duration::from_secs(10);
+    while !done(app) {
+        assert!(Instant::now() < deadline, "timed out waiting for {what}");
+        std::thread::sleep(Duration::from_millis(10));
+        app.poll_progress();
+        jobs::poll(app);
+    }
+}
+
+fn copy(app: &mut App, name: &str) {
+    assert!(app.left.select_named(name));
+    handle_key(app, KeyCode::F(5), 10).unwrap();
+}
+
+#[test]
+fn a_job_sent_to_the_background_asks_when_brought_back() {
+    let tmp = tempfile::tempdir().unwrap();
+    let mut app = setup(tmp.path());
+
+    copy(&mut app, "taken.txt");
+    handle_key(&mut app, KeyCode::Char('b'), 10).unwrap();
+    assert!(matches!(app.mode, Mode::Normal));
+    assert!(app.op_progress_rx.is_none());
+    assert_eq!(app.running_jobs(), 1);
+    poll_until(&mut app, "the conflict", |app| app.scheduler.jobs()[0].status() == "Waiting");
+
+    handle_key(&mut app, KeyCode::Char('B'), 10).unwrap();
+    let text = screen_sized(&app, 100, 24);
+    assert!(text.contains("Waiting") && text.contains("Copy 1 item(s) to"), "{text}");
+
+    // Enter brings it back and asks; Enter again overwrites.
+    handle_key(&mut app, KeyCode::Enter, 10).unwrap();
+    assert!(matches!(&app.mode, Mode::Conflict { path, .. } if path.ends_with("taken.txt")));
+    assert!(app.scheduler.is_empty());
+    handle_key(&mut app, KeyCode::Enter, 10).unwrap();
+    poll_until(&mut app, "the copy", |app| matches!(&app.mode, Mode::Summary { title, .. } if title == "Done"));
+
+    assert_eq!(std::fs::read_to_string(tmp.path().join("right/taken.txt")).unwrap(), "new");
+    assert_eq!(app.jobs.records.len(), 1);
+    assert_eq!(app.jobs.records[0].operation, "Copy");
+}
+
+#[test]
+fn cancelling_a_job_whose_question_was_not_seen_yet_stops_it() {
+    let tmp = tempfile::tempdir().unwrap();
+    let mut app = setup(tmp.path());
+
+    copy(&mut app, "taken.txt");
+    handle_key(&mut app, KeyCode::Char('b'), 10).unwrap();
+    // Let the worker reach the conflict, but do not poll: the scheduler
+    // does not know yet that the job is waiting for an answer.
+    std::thread::sleep(Duration::from_millis(200));
+    assert!(app.scheduler.jobs()[0].waiting.is_none());
+    app.scheduler.cancel(0);
+    poll_until(&mut app, "the cancelled job", |app| app.scheduler.is_empty());
+
+    assert_eq!(std::fs::read_to_string(tmp.path().join("right/taken.txt")).unwrap(), "old");
+    assert_eq!(app.jobs.records.len(), 1);
+    assert!(app.jobs.records[0].error.is_some());
+}
+
+#[test]
+fn jobs_beyond_the_limit_wait_in_the_queue() {
+    let tmp = tempfile::tempdir().unwrap();
+    let mut app = setup(tmp.path());
+    app.settings.max_concurrent_jobs = 1;
+
+    copy(&mut app, "taken.txt");
+    handle_key(&mut app, KeyCode::Char('b'), 10).unwrap();
+    copy(&mut app, "free.txt");
+    assert!(matches!(&app.mode, Mode::Message { title, .. } if title == "Queued"));
+    let statuses = |app: &App| app.scheduler.jobs().iter().map(|j| j.status()).collect::<Vec<_>>();
+    poll_until(&mut app, "the conflict", |app| statuses(app) == ["Waiting", "Queued"]);
+    assert!(!tmp.path().join("right/free.txt").exists());
+
+    // `+` moves the queued job to the top; it still waits for the slot.
+    app.mode = Mode::Jobs { selected: 1 };
+    handle_key(&mut app, KeyCode::Char('+'), 10).unwrap();
+    assert!(matches!(app.mode, Mode::Jobs { selected: 0 }));
+    jobs::poll(&mut app);
+    assert_eq!(statuses(&app), ["Queued", "Waiting"]);
+
+    // Cancelling the waiting job frees the slot for the queued one.
+    handle_key(&mut app, KeyCode::Down, 10).unwrap();
+    handle_key(&mut app, KeyCode::Char('c'), 10).unwrap();
+    poll_until(&mut app, "both jobs", |app| app.scheduler.is_empty());
+
+    assert_eq!(std::fs::read_to_string(tmp.path().join("right/free.txt")).unwrap(), "new");
+    assert_eq!(std::fs::read_to_string(tmp.path().join("right/taken.txt")).unwrap(), "old");
+    let errors: Vec<_> = app.jobs.records.iter().map(|r| r.error.is_some()).collect();
+    assert_eq!(errors, [true, false]);
+}
+
+#[test]
+fn sequential_jobs_wait_their_turn_and_show_their_place() {
+    let tmp = tempfile::tempdir().unwrap();
+    let mut app = setup(tmp.path());
+    std::fs::write(tmp.path().join("left/more.txt"), "new").unwrap();
+    app.refresh().unwrap();
+    app.settings.max_concurrent_jobs = 4;
+    app.settings.sequential_jobs = true;
+
+    copy(&mut app, "taken.txt");
+    handle_key(&mut app, KeyCode::Char('b'), 10).unwrap();
+    copy(&mut app, "free.txt");
+    assert!(matches!(&app.mode, Mode::Message { content, .. } if content.contains("is #1 in the queue")));
+    app.mode = Mode::Normal;
+    copy(&mut app, "more.txt");
+    assert!(matches!(&app.mode, Mode::Message { content, .. } if content.contains("is #2 in the queue")));
+
+    // One slot: the queued copies wait behind the one asking about its conflict.
+    poll_until(&mut app, "the conflict", |app| app.scheduler.jobs()[0].status() == "Waiting");
+    assert!(!tmp.path().join("right/free.txt").exists());
+    let positions: Vec<_> = (0..3).map(|i| app.scheduler.queue_position(i)).collect();
+    assert_eq!(positions, [None, Some(1), Some(2)]);
+    app.mode = Mode::Jobs { selected: 0 };
+    let text = screen_sized(&app, 100, 24);
+    assert!(text.contains("Queued #2") && text.contains("Jobs run one at a time"), "{text}");
+
+    // `s` lets them run side by side.
+    handle_key(&mut app, KeyCode::Char('s'), 10).unwrap();
+    assert!(!app.settings.sequential_jobs);
+    poll_until(&mut app, "the queued copies", |app| app.scheduler.jobs().len() == 1);
+    assert_eq!(std::fs::read_to_string(tmp.path().join("right/more.txt")).unwrap(), "new");
+}
@@ -0,0 +1,87 @@
+//! Multi-key sequences (`g g`, `g h`) in normal mode. They read the global
+//! key table and change `HOME`, so this test lives in its own test binary.
+
+use std::time::{Duration, Instant};
+
+use fileZoom::app::settings::runtime_keybinds::{self, KeybindPreset};
+use fileZoom::app::{App, Mode, StartOptions};
+use fileZoom::input::KeyCode;
+use fileZoom::runner::handlers::{handle_key, sequence::poll_sequence_timeout};
+
+mod common;
+use common::screen;
+
+fn press(app: &mut App, c: char) {
+    handle_key(app, KeyCode::Char(c), 10).unwrap();
+}
+
+/// Pretend the sequence timeout passed and let the app notice.
+fn time_out(app: &mut App) {
+    app.pending_keys.since = Some(Instant::now() - Duration::from_secs(5));
+    poll_sequence_timeout(app, 10).unwrap();
+}
+
+#[test]
+fn sequences_wait_for_their_second_key() {
+    let cfg = tempfile::tempdir().unwrap();
+    std::env::set_var("XDG_CONFIG_HOME", cfg.path());
+    let home = tempfile::tempdir().unwrap();
+    std::env::set_var("HOME", home.path());
+    let tmp = tempfile::tempdir().unwrap();
+    for name in ["a.txt", "b.txt", "c.txt"] {
+        std::fs::write(tmp.path().join(name), "x").unwrap();
+    }
+    let opts = StartOptions { start_dir: Some(tmp.path().to_path_buf()), ..Default::default() };
+    let mut app = App::with_options(&opts).unwrap();
+    let last = app.left.entries.len() + 1;
+
+    // `g` waits and lists the continuations; a second `g` goes to the top.
+    handle_key(&mut app, KeyCode::End, 10).unwrap();
+    assert_eq!(app.left.selected, last);
+    press(&mut app, 'g');
+    assert_eq!(app.pending_keys.keys.len(), 1);
+    let s = screen(&app);
+    assert!(s.contains("first entry") && s.contains("home directory"), "{}", s);
+    press(&mut app, 'g');
+    assert!(app.pending_keys.is_empty());
+    assert_eq!(app.left.selected, 0);
+    assert!(!screen(&app).contains("home directory"));
+
+    // `g e` goes to the end; a key that continues nothing drops the
+    // sequence and then acts on its own (`s` cycles the sort key).
+    press(&mut app, 'g');
+    press(&mut app, 'e');
+    assert_eq!(app.left.selected, last);
+    let sort = app.sort;
+    press(&mut app, 'g');
+    press(&mut app, 's');
+    assert!(app.pending_keys.is_empty());
+    assert_ne!(app.sort, sort);
+    assert_eq!(app.left.selected, last);
+
+    // Esc and the timeout cancel; `g` alone is unbound by default.
+    press(&mut app, 'g');
+    handle_key(&mut app, KeyCode::Esc, 10).unwrap();
+    assert!(app.pending_keys.is_empty());
+    press(&mut app, 'g');
+    time_out(&mut app);
+    assert!(app.pending_keys.is_empty());
+    assert_eq!(app.left.selected, last);
+
+    // `g h` goes to the home directory.
+    press(&mut app, 'g');
+    press(&mut app, 'h');
+    assert!(matches!(app.mode, Mode::Normal));
+    assert_eq!(app.left.cwd, home.path());
+
+    // In the vim preset `g` alone also goes to the top, once the timeout
+    // shows no second key is coming.
+    runtime_keybinds::set_preset(KeybindPreset::Vim);
+    handle_key(&mut app, KeyCode::Tab, 10).unwrap();
+    handle_key(&mut app, KeyCode::End, 10).unwrap();
+    press(&mut app, 'g');
+    assert_ne!(app.right.selected, 0);
+    time_out(&mut app);
+    assert_eq!(app.right.selected, 0);
+    runtime_keybinds::set_preset(KeybindPreset::Default);
+}
@@ -0,0 +1,55 @@
+//! Keybinding presets switch the global key table, so these tests live in
+//! their own test binary.
+
+use assert_fs::prelude::*;
+use fileZoom::app::settings::keybinds;
+use fileZoom::app::settings::KeybindPreset;
+use fileZoom::app::{App, Mode, StartOptions};
+use fileZoom::input::KeyCode;
+use fileZoom::runner::handlers::handle_key;
+
+#[test]
+fn presets_drive_normal_mode_and_cycle_from_settings() {
+    let tmp = assert_fs::TempDir::new().unwrap();
+    tmp.child("sub").create_dir_all().unwrap();
+    tmp.child("sub/inner.txt").write_str("x").unwrap();
+    tmp.child("z.txt").write_str("z").unwrap();
+    let opts = StartOptions { start_dir: Some(tmp.path().to_path_buf()), ..Default::default() };
+    let mut app = App::with_options(&opts).unwrap();
+    let first = 1 + app.left.cwd.parent().is_some() as usize;
+    app.left.selected = first;
+
+    // vim: j moves down, l enters a directory, h goes back up.
+    keybinds::apply_preset("vim");
+    handle_key(&mut app, KeyCode::Char('j'), 10).unwrap();
+    assert_eq!(app.left.selected_entry().unwrap().name, "z.txt");
+    handle_key(&mut app, KeyCode::Char('k'), 10).unwrap();
+    handle_key(&mut app, KeyCode::Char('l'), 10).unwrap();
+    assert_eq!(app.left.cwd, tmp.path().join("sub"));
+    handle_key(&mut app, KeyCode::Char('h'), 10).unwrap();
+    assert_eq!(app.left.cwd, tmp.path());
+
+    // mc: F1 is help, F9 focuses the menu, F8 asks to delete.
+    keybinds::apply_preset("mc");
+    handle_key(&mut app, KeyCode::F(1), 10).unwrap();
+    assert!(matches!(&app.mode, Mode::Message { title, .. } if title == "Help"));
+    app.mode = Mode::Normal;
+    handle_key(&mut app, KeyCode::F(9), 10).unwrap();
+    assert!(app.menu_focused);
+    app.menu_focused = false;
+    app.left.selected = first + 1;
+    handle_key(&mut app, KeyCode::F(8), 10).unwrap();
+    assert!(matches!(app.mode, Mode::Confirm { .. }));
+
+    // Settings row 3 cycles the preset and applies it immediately.
+    app.settings.keybinding_preset = "mc".to_string();
+    app.mode = Mode::Settings { selected: 3 };
+    handle_key(&mut app, KeyCode::Enter, 10).unwrap();
+    assert_eq!(app.settings.keybinding_preset, KeybindPreset::Vim.name());
+    app.mode = Mode::Normal;
+    app.left.selected = first;
+    handle_key(&mut app, KeyCode::Char('j'), 10).unwrap();
+    assert_eq!(app.left.selected_entry().unwrap().name, "z.txt");
+
+    keybinds::apply_preset("default");
+}
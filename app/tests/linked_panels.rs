@@ -0,0 +1,39 @@
+use fileZoom::app::{App, StartOptions};
+use fileZoom::input::KeyCode;
+use fileZoom::runner::handlers::handle_key;
+
+#[test]
+fn the_other_panel_follows_into_the_same_subdirectory() {
+    let tmp = tempfile::tempdir().unwrap();
+    let (a, b) = (tmp.path().join("a"), tmp.path().join("b"));
+    std::fs::create_dir_all(a.join("src/ui")).unwrap();
+    std::fs::create_dir_all(a.join("only-a")).unwrap();
+    std::fs::create_dir_all(b.join("src/ui")).unwrap();
+    let opts = StartOptions { start_dir: Some(a.clone()), right_dir: Some(b.clone()), ..Default::default() };
+    let mut app = App::with_options(&opts).unwrap();
+
+    handle_key(&mut app, KeyCode::Char('K'), 10).unwrap();
+    assert!(app.link.is_some());
+
+    // Into `src/ui` on both sides, and back up.
+    for name in ["src", "ui"] {
+        assert!(app.left.select_named(name));
+        handle_key(&mut app, KeyCode::Enter, 10).unwrap();
+    }
+    assert_eq!((&app.left.cwd, &app.right.cwd), (&a.join("src/ui"), &b.join("src/ui")));
+    handle_key(&mut app, KeyCode::Backspace, 10).unwrap();
+    assert_eq!(app.right.cwd, b.join("src"));
+
+    // A directory missing on the other side leaves it where it is.
+    handle_key(&mut app, KeyCode::Backspace, 10).unwrap();
+    assert!(app.left.select_named("only-a"));
+    handle_key(&mut app, KeyCode::Enter, 10).unwrap();
+    assert_eq!((&app.left.cwd, &app.right.cwd), (&a.join("only-a"), &b));
+
+    // Unlinked, the other panel stays.
+    handle_key(&mut app, KeyCode::Char('K'), 10).unwrap();
+    handle_key(&mut app, KeyCode::Backspace, 10).unwrap();
+    assert!(app.left.select_named("src"));
+    handle_key(&mut app, KeyCode::Enter, 10).unwrap();
+    assert_eq!((&app.left.cwd, &app.right.cwd), (&a.join("src"), &b));
+}
@@ -0,0 +1,35 @@
+use fileZoom::app::{Action, App, Mode, StartOptions};
+use fileZoom::input::KeyCode;
+use fileZoom::runner::handlers::handle_key;
+
+#[test]
+fn failed_operation_offers_the_log() {
+    let tmp = tempfile::tempdir().unwrap();
+    std::fs::write(tmp.path().join("taken"), "x").unwrap();
+    let mut app = App::with_options(&StartOptions { start_dir: Some(tmp.path().to_path_buf()), ..Default::default() }).unwrap();
+
+    app.mode = Mode::Confirm { msg: "Create taken?".into(), on_yes: Action::NewDir("taken".into()), selected: 0 };
+    handle_key(&mut app, KeyCode::Enter, 10).unwrap();
+    let Mode::Message { buttons, selected, .. } = &app.mode else { panic!("expected an error, got {:?}", app.mode) };
+    assert_eq!(buttons[*selected], "OK");
+
+    // `s` presses "Show log".
+    handle_key(&mut app, KeyCode::Char('s'), 10).unwrap();
+    assert!(matches!(app.mode, Mode::LogView { scroll: 0, .. }), "{:?}", app.mode);
+    handle_key(&mut app, KeyCode::Esc, 10).unwrap();
+    assert!(matches!(app.mode, Mode::Normal));
+}
+
+#[test]
+fn log_view_scrolls_back_from_the_end() {
+    let mut app = App::new().unwrap();
+    app.mode = Mode::LogView { path: None, lines: (0..30).map(|i| i.to_string()).collect(), scroll: 0 };
+    handle_key(&mut app, KeyCode::PageUp, 10).unwrap();
+    assert!(matches!(app.mode, Mode::LogView { scroll: 10, .. }));
+    handle_key(&mut app, KeyCode::Home, 10).unwrap();
+    assert!(matches!(app.mode, Mode::LogView { scroll: 20, .. }));
+    handle_key(&mut app, KeyCode::Up, 10).unwrap();
+    assert!(matches!(app.mode, Mode::LogView { scroll: 20, .. }));
+    handle_key(&mut app, KeyCode::End, 10).unwrap();
+    assert!(matches!(app.mode, Mode::LogView { scroll: 0, .. }));
+}
@@ -64,6 +64,9 @@ fn context_menu_enter_opens_preview() {
         settings: fileZoom::app::settings::write_settings::Settings::default(),
         op_progress_rx: None,
         op_cancel_flag: None,
+        op_skip_verify: None,
+        op_meter: None,
+        scheduler: Default::default(),
         op_decision_tx: None,
         last_mouse_click_time: None,
         last_mouse_click_pos: None,
@@ -71,6 +74,30 @@ fn context_menu_enter_opens_preview() {
         drag_start: None,
         drag_current: None,
         drag_button: None,
+        scroll_drag: None,
+        dirty: false,
+        plugins: Default::default(),
+        diagnostics: Default::default(),
+        jobs: Default::default(),
+        pending_keys: Default::default(),
+        path_jump: Default::default(),
+        frecency: Default::default(),
+        tags: Default::default(),
+        searches: Default::default(),
+        clipboard: None,
+        other_panel: Default::default(),
+        info: Default::default(),
+        tree: Default::default(),
+        usage: None,
+        shell: None,
+        compare: None,
+        open_files: None,
+        chooser: None,
+        link: None,
+        templates: Default::default(),
+        owners: Default::default(),
+        preview_focused: Default::default(),
+        layout: Default::default(),
     };
     app.refresh().unwrap();
 
@@ -79,6 +106,7 @@ fn context_menu_enter_opens_preview() {
         options: vec!["View".to_string()],
         selected: 0,
         path: temp.path().join("a"),
+        at: None,
     };
 
     let _ = context_menu::handle_context_menu(&mut app, KeyCode::Enter).unwrap();
@@ -0,0 +1,44 @@
+use fileZoom::app::{App, InputKind, Mode, StartOptions};
+use fileZoom::input::KeyCode;
+use fileZoom::runner::handlers::handle_key;
+use fileZoom::ui::widgets::main_menu;
+use ratatui::{backend::TestBackend, layout::Rect, style::Modifier, Terminal};
+
+#[test]
+fn menu_bar_underlines_mnemonics_and_shows_shortcuts() {
+    let mut term = Terminal::new(TestBackend::new(80, 3)).unwrap();
+    term.draw(|f| main_menu::render(f, Rect::new(0, 0, 80, 3), 0, true)).unwrap();
+    let buf = term.backend().buffer();
+    let row: String = (0..80).map(|x| buf[(x, 1)].symbol()).collect();
+    assert!(row.contains("[File] | Copy F5 | Move F6"), "{row}");
+
+    let settings = row[..row.find("Settings").unwrap()].chars().count() as u16;
+    let underlined = |x: u16| buf[(x, 1)].modifier.contains(Modifier::UNDERLINED);
+    // "S" is taken by Sort, so Settings uses its "e".
+    assert!(!underlined(settings) && underlined(settings + 1));
+}
+
+#[test]
+fn mnemonics_open_menus_and_press_buttons() {
+    let tmp = tempfile::tempdir().unwrap();
+    let opts = StartOptions { start_dir: Some(tmp.path().to_path_buf()), ..Default::default() };
+    let mut app = App::with_options(&opts).unwrap();
+
+    handle_key(&mut app, KeyCode::F(1), 10).unwrap();
+    handle_key(&mut app, KeyCode::Char('n'), 10).unwrap();
+    assert!(app.menu_state.open && app.menu_index == 3);
+    // "New File" has N, "New Dir" the next free letter, e.
+    handle_key(&mut app, KeyCode::Char('e'), 10).unwrap();
+    assert!(matches!(app.mode, Mode::Input { kind: InputKind::NewDir, .. }));
+    assert!(!app.menu_focused);
+
+    app.mode = Mode::Message {
+        title: "Pick".into(),
+        content: "?".into(),
+        buttons: vec!["Keep".into(), "Drop".into()],
+        selected: 0,
+        actions: None,
+    };
+    handle_key(&mut app, KeyCode::Char('d'), 10).unwrap();
+    assert!(matches!(app.mode, Mode::Normal));
+}
@@ -0,0 +1,29 @@
+use fileZoom::app::{App, Mode, StartOptions};
+use fileZoom::input::KeyCode;
+use fileZoom::runner::commands::execute_command;
+use fileZoom::runner::handlers::handle_key;
+
+mod common;
+use common::screen_sized;
+
+#[test]
+fn enter_goes_to_the_mount_holding_the_directory() {
+    let tmp = tempfile::tempdir().unwrap();
+    let opts = StartOptions { start_dir: Some(tmp.path().to_path_buf()), ..Default::default() };
+    let mut app = App::with_options(&opts).unwrap();
+
+    handle_key(&mut app, KeyCode::Char('M'), 10).unwrap();
+    let Mode::MountPicker { mounts, selected } = &app.mode else { panic!("mount picker not open") };
+    let root = mounts[*selected].root.clone();
+    assert!(tmp.path().starts_with(&root), "{} is not on {}", tmp.path().display(), root.display());
+    assert!(screen_sized(&app, 120, 30).contains("Mounts"));
+
+    handle_key(&mut app, KeyCode::Enter, 10).unwrap();
+    assert!(matches!(app.mode, Mode::Normal));
+    assert_eq!(app.left.cwd, root);
+
+    execute_command(&mut app, "mounts").unwrap();
+    assert!(matches!(app.mode, Mode::MountPicker { .. }));
+    handle_key(&mut app, KeyCode::Esc, 10).unwrap();
+    assert!(matches!(app.mode, Mode::Normal));
+}
@@ -56,8 +56,10 @@ fn right_click_opens_context_menu_for_selected_entry() {
             options,
             selected: sel,
             path: _,
+            at,
         } => {
             assert_eq!(*sel, 0);
+            assert_eq!(*at, Some((2, second_item_row)));
             assert!(!options.is_empty());
         }
         other => panic!("expected ContextMenu mode, got: {:?}", other),
@@ -0,0 +1,112 @@
+use fileZoom::app::{Action, App, Mode};
+use fileZoom::input::mouse::{MouseButton, MouseEvent, MouseEventKind};
+use fileZoom::runner::handlers::handle_mouse;
+use fileZoom::ui::layout::ScreenLayout;
+use ratatui::{backend::TestBackend, layout::Rect, Terminal};
+
+mod common;
+use common::{app_in, draw};
+
+/// Cell of the first character of `text` on the screen.
+fn find(rows: &[String], text: &str) -> (u16, u16) {
+    for (y, row) in rows.iter().enumerate() {
+        if let Some(i) = row.find(text) {
+            return (row[..i].chars().count() as u16, y as u16);
+        }
+    }
+    panic!("{:?} not on screen:\n{}", text, rows.join("\n"));
+}
+
+fn click(app: &mut App, kind: MouseEventKind, (column, row): (u16, u16)) {
+    handle_mouse(app, MouseEvent { column, row, kind }, Rect::new(0, 0, 80, 24)).unwrap();
+}
+
+#[test]
+fn clicking_dialog_buttons_presses_them() {
+    let tmp = tempfile::tempdir().unwrap();
+    std::fs::write(tmp.path().join("victim.txt"), "x").unwrap();
+    let mut app = app_in(tmp.path());
+    let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+    assert!(app.left.select_named("victim.txt"));
+    let confirm = || Mode::Confirm { msg: "Delete victim.txt?".into(), on_yes: Action::DeleteSelected, selected: 0 };
+
+    app.mode = confirm();
+    let no = find(&draw(&mut terminal, &app), " No ");
+    click(&mut app, MouseEventKind::Down(MouseButton::Left), (no.0 + 1, no.1));
+    assert!(matches!(app.mode, Mode::Normal));
+    assert!(tmp.path().join("victim.txt").exists());
+
+    app.mode = confirm();
+    let yes = find(&draw(&mut terminal, &app), "[Yes]");
+    // The gap between the buttons presses nothing.
+    click(&mut app, MouseEventKind::Down(MouseButton::Left), (yes.0 + 5, yes.1));
+    assert!(matches!(app.mode, Mode::Confirm { .. }));
+    click(&mut app, MouseEventKind::Down(MouseButton::Left), (yes.0 + 2, yes.1));
+    assert!(!tmp.path().join("victim.txt").exists());
+}
+
+#[test]
+fn clicking_context_menu_entries_and_outside_it() {
+    let tmp = tempfile::tempdir().unwrap();
+    std::fs::write(tmp.path().join("a.txt"), "x").unwrap();
+    let mut app = app_in(tmp.path());
+    let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+    assert!(app.left.select_named("a.txt"));
+    let left = ScreenLayout::compute(&app, Rect::new(0, 0, 80, 24)).left;
+    // Clicks pick the row by its index below the panel's top border, where
+    // the breadcrumb and parent rows come first.
+    let row = left.y + 1 + app.left.selected as u16;
+    let mut open_menu = |app: &mut App| {
+        click(app, MouseEventKind::Down(MouseButton::Right), (4, row));
+        assert!(matches!(app.mode, Mode::ContextMenu { .. }));
+        draw(&mut terminal, app)
+    };
+
+    let rows = open_menu(&mut app);
+    click(&mut app, MouseEventKind::Down(MouseButton::Left), (79, 23));
+    assert!(matches!(app.mode, Mode::Normal));
+
+    let rows_again = open_menu(&mut app);
+    assert_eq!(rows, rows_again);
+    let cancel = find(&rows, " Cancel ");
+    click(&mut app, MouseEventKind::Down(MouseButton::Left), (cancel.0 + 2, cancel.1));
+    assert!(matches!(app.mode, Mode::Normal));
+}
+
+#[test]
+fn scrollbars_follow_clicks_and_drags() {
+    let tmp = tempfile::tempdir().unwrap();
+    for i in 0..100 {
+        std::fs::write(tmp.path().join(format!("f{:03}.txt", i)), "x").unwrap();
+    }
+    let mut app = app_in(tmp.path());
+    let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+    let left = ScreenLayout::compute(&app, Rect::new(0, 0, 80, 24)).left;
+    let bar = left.right() - 1;
+    let mut left_panel = |app: &App| -> Vec<String> {
+        draw(&mut terminal, app).iter().map(|r| r.chars().take(bar as usize).collect()).collect()
+    };
+
+    // Clicking the bottom of the track shows the last page.
+    click(&mut app, MouseEventKind::Down(MouseButton::Left), (bar, left.bottom() - 2));
+    let rows = left_panel(&app);
+    assert!(rows.iter().any(|r| r.contains("f099.txt")), "{}", rows.join("\n"));
+    assert!(!rows.iter().any(|r| r.contains("f000.txt")));
+    // The selection moved along into view.
+    let selected = app.left.selected_entry().unwrap().display_name().into_owned();
+    assert!(rows.iter().any(|r| r.contains(&selected)));
+
+    // Dragging off the bar keeps scrolling until the button is released.
+    click(&mut app, MouseEventKind::Drag(MouseButton::Left), (2, left.y + 1));
+    let rows = left_panel(&app);
+    assert!(rows.iter().any(|r| r.contains("f000.txt")));
+    click(&mut app, MouseEventKind::Up(MouseButton::Left), (2, left.y + 1));
+    assert!(app.scroll_drag.is_none());
+
+    // The preview's scrollbar moves its offset.
+    app.preview_visible = true;
+    app.left.set_preview((0..100).map(|i| format!("line {}", i)).collect::<Vec<_>>().join("\n"));
+    let preview = ScreenLayout::compute(&app, Rect::new(0, 0, 80, 24)).preview.unwrap();
+    click(&mut app, MouseEventKind::Down(MouseButton::Left), (preview.right() - 1, preview.bottom() - 2));
+    assert_eq!(app.left.preview_offset, 99);
+}
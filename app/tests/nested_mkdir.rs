@@ -0,0 +1,47 @@
+use fileZoom::app::{App};
+use fileZoom::input::KeyCode;
+use fileZoom::runner::handlers::handle_key;
+
+mod common;
+use common::{app_in, screen_sized};
+
+fn type_text(app: &mut App, text: &str) {
+    for c in text.chars() {
+        handle_key(app, KeyCode::Char(c), 10).unwrap();
+    }
+}
+
+#[test]
+fn nested_directories_are_previewed_and_created() {
+    let tmp = tempfile::tempdir().unwrap();
+    std::fs::create_dir(tmp.path().join("a")).unwrap();
+    std::fs::write(tmp.path().join("note.txt"), "hi").unwrap();
+    let mut app = app_in(tmp.path());
+
+    handle_key(&mut app, KeyCode::Char('N'), 10).unwrap();
+    type_text(&mut app, "a");
+    assert!(screen_sized(&app, 100, 24).contains("Already exists"));
+    type_text(&mut app, "/b/c");
+    let text = screen_sized(&app, 100, 24);
+    assert!(text.contains("Creates b/c/ in") && text.contains("/a"), "{text}");
+    handle_key(&mut app, KeyCode::Enter, 10).unwrap();
+    assert!(tmp.path().join("a/b/c").is_dir());
+
+    // The copy dialog creates the missing directories of the destination,
+    // which is relative to the panel.
+    assert!(app.left.select_named("note.txt"));
+    handle_key(&mut app, KeyCode::Char('c'), 10).unwrap();
+    type_text(&mut app, "out/x/");
+    assert!(screen_sized(&app, 100, 24).contains("Also creates out/x/ in"));
+    handle_key(&mut app, KeyCode::Enter, 10).unwrap();
+    assert_eq!(std::fs::read_to_string(tmp.path().join("out/x/note.txt")).unwrap(), "hi");
+
+    // Unless that is turned off.
+    app.settings.create_dest_dirs = false;
+    assert!(app.left.select_named("note.txt"));
+    handle_key(&mut app, KeyCode::Char('c'), 10).unwrap();
+    type_text(&mut app, "other/y/");
+    assert!(screen_sized(&app, 100, 24).contains("Missing other/y/ in"));
+    handle_key(&mut app, KeyCode::Enter, 10).unwrap();
+    assert!(!tmp.path().join("other").exists());
+}
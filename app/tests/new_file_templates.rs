@@ -0,0 +1,50 @@
+use fileZoom::app::{App, Mode};
+use fileZoom::input::KeyCode;
+use fileZoom::runner::handlers::handle_key;
+
+mod common;
+use common::{app_in, screen_sized};
+
+fn type_name(app: &mut App, name: &str) {
+    for c in name.chars() {
+        handle_key(app, KeyCode::Char(c), 10).unwrap();
+    }
+}
+
+#[test]
+fn new_files_are_created_from_the_chosen_template() {
+    let tmp = tempfile::tempdir().unwrap();
+    let (work, templates) = (tmp.path().join("work"), tmp.path().join("templates"));
+    std::fs::create_dir_all(&work).unwrap();
+    std::fs::create_dir_all(templates.join("module")).unwrap();
+    std::fs::write(templates.join("module/{{stem}}.md"), "# {{stem}}\n").unwrap();
+    std::fs::write(templates.join("script.sh"), "#!/bin/sh\n# {{name}}\n").unwrap();
+    let mut app = app_in(&work);
+
+    // Without a template the new file is empty.
+    handle_key(&mut app, KeyCode::Char('n'), 10).unwrap();
+    type_name(&mut app, "empty.txt");
+    handle_key(&mut app, KeyCode::Enter, 10).unwrap();
+    assert_eq!(std::fs::read(work.join("empty.txt")).unwrap(), b"");
+
+    // Down picks the next template: the directory, then the script.
+    handle_key(&mut app, KeyCode::Char('n'), 10).unwrap();
+    app.templates.open(&templates);
+    handle_key(&mut app, KeyCode::Down, 10).unwrap();
+    handle_key(&mut app, KeyCode::Down, 10).unwrap();
+    assert!(screen_sized(&app, 100, 24).contains("> script.sh"));
+    type_name(&mut app, "deploy.sh");
+    handle_key(&mut app, KeyCode::Enter, 10).unwrap();
+    assert!(matches!(app.mode, Mode::Normal));
+    assert_eq!(std::fs::read_to_string(work.join("deploy.sh")).unwrap(), "#!/bin/sh\n# deploy.sh\n");
+    assert!(app.templates.templates.is_empty());
+
+    // A directory template creates the whole tree.
+    handle_key(&mut app, KeyCode::Char('n'), 10).unwrap();
+    app.templates.open(&templates);
+    handle_key(&mut app, KeyCode::Down, 10).unwrap();
+    type_name(&mut app, "docs");
+    handle_key(&mut app, KeyCode::Enter, 10).unwrap();
+    assert_eq!(std::fs::read_to_string(work.join("docs/docs.md")).unwrap(), "# docs\n");
+    assert!(app.left.entries.iter().any(|e| e.name == "docs" && e.is_dir));
+}
@@ -0,0 +1,122 @@
+//! Files whose names are not valid UTF-8 can be listed, copied, moved,
+//! renamed and deleted; only the display replaces the invalid bytes.
+#![cfg(unix)]
+
+use std::ffi::OsStr;
+use std::fs;
+use std::os::unix::ffi::OsStrExt;
+
+use fileZoom::app::{App, StartOptions};
+use ratatui::{backend::TestBackend, Terminal};
+
+const RAW: &[u8] = b"caf\xe9-\xff.txt";
+
+fn raw_name() -> &'static OsStr {
+    OsStr::from_bytes(RAW)
+}
+
+fn app_in(dir: &std::path::Path, right: &std::path::Path) -> App {
+    let opts = StartOptions { start_dir: Some(dir.to_path_buf()), right_dir: Some(right.to_path_buf()), ..Default::default() };
+    App::with_options(&opts).unwrap()
+}
+
+#[test]
+fn raw_names_are_kept_and_shown_lossily() {
+    let src = tempfile::tempdir().unwrap();
+    let dst = tempfile::tempdir().unwrap();
+    fs::write(src.path().join(raw_name()), b"latin-1").unwrap();
+    let app = app_in(src.path(), dst.path());
+
+    assert_eq!(app.left.entries.len(), 1);
+    let entry = &app.left.entries[0];
+    assert_eq!(entry.name, raw_name());
+    assert_eq!(entry.path, src.path().join(raw_name()));
+    assert_eq!(entry.display_name(), "caf\u{fffd}-\u{fffd}.txt");
+
+    let mut terminal = Terminal::new(TestBackend::new(100, 20)).unwrap();
+    terminal.draw(|f| fileZoom::ui::ui(f, &app)).unwrap();
+    let screen: String = terminal.backend().buffer().content().iter().map(|c| c.symbol()).collect();
+    assert!(screen.contains("caf\u{fffd}-\u{fffd}.txt"), "{}", screen);
+}
+
+#[test]
+fn raw_names_can_be_copied_moved_renamed_and_deleted() {
+    let src = tempfile::tempdir().unwrap();
+    let dst = tempfile::tempdir().unwrap();
+    fs::write(src.path().join(raw_name()), b"latin-1").unwrap();
+    let mut app = app_in(src.path(), dst.path());
+
+    // Copy keeps the exact bytes of the name.
+    assert!(app.left.select_named(raw_name()));
+    app.copy_selected_to(dst.path().to_path_buf()).unwrap();
+    assert_eq!(fs::read(dst.path().join(raw_name())).unwrap(), b"latin-1");
+
+    // Rename to a UTF-8 name and back through a move.
+    assert!(app.left.select_named(raw_name()));
+    app.rename_selected_to("plain.txt".to_string()).unwrap();
+    assert!(!src.path().join(raw_name()).exists());
+    assert!(app.left.select_named("plain.txt"));
+    app.move_selected_to(src.path().join(raw_name())).unwrap();
+    assert_eq!(fs::read(src.path().join(raw_name())).unwrap(), b"latin-1");
+
+    // Delete removes the right file.
+    fs::write(src.path().join("keep.txt"), b"").unwrap();
+    app.refresh().unwrap();
+    assert!(app.left.select_named(raw_name()));
+    app.delete_selected().unwrap();
+    assert!(!src.path().join(raw_name()).exists());
+    assert!(src.path().join("keep.txt").exists());
+}
+
+#[test]
+fn names_with_the_same_display_stay_distinct() {
+    let src = tempfile::tempdir().unwrap();
+    let dst = tempfile::tempdir().unwrap();
+    let (a, b) = (OsStr::from_bytes(b"x\xe9"), OsStr::from_bytes(b"x\xff"));
+    fs::write(src.path().join(b), b"b").unwrap();
+    fs::write(src.path().join(a), b"a").unwrap();
+    let mut app = app_in(src.path(), dst.path());
+
+    // Both show as `x\u{fffd}`; the raw bytes break the tie.
+    let names: Vec<&OsStr> = app.left.entries.iter().map(|e| e.name.as_os_str()).collect();
+    assert_eq!(names, [a, b]);
+    assert!(app.left.select_named(b));
+    app.copy_selected_to(dst.path().to_path_buf()).unwrap();
+    assert_eq!(fs::read(dst.path().join(b)).unwrap(), b"b");
+    assert!(!dst.path().join(a).exists());
+}
+
+#[test]
+fn shell_placeholders_pass_raw_names() {
+    let src = tempfile::tempdir().unwrap();
+    fs::write(src.path().join(raw_name()), b"latin-1").unwrap();
+    let mut app = app_in(src.path(), src.path());
+    assert!(app.left.select_named(raw_name()));
+    fileZoom::runner::shell::run_for_app(&mut app, "cat %f").unwrap();
+    while app.shell.is_some() {
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        fileZoom::runner::shell::poll(&mut app).unwrap();
+    }
+    match &app.mode {
+        fileZoom::app::Mode::CommandOutput { lines, status, .. } => {
+            assert_eq!(*status, Some(0));
+            assert_eq!(lines, &["latin-1".to_string()]);
+        }
+        other => panic!("expected CommandOutput, got {:?}", other),
+    }
+}
+
+#[test]
+fn session_skips_raw_names_and_paths() {
+    let src = tempfile::tempdir().unwrap();
+    let raw_dir = src.path().join(raw_name());
+    fs::create_dir(&raw_dir).unwrap();
+    fs::write(raw_dir.join(raw_name()), b"").unwrap();
+    let mut app = app_in(&raw_dir, src.path());
+    assert!(app.left.select_named(raw_name()));
+
+    let session = fileZoom::app::settings::session::Session::capture(&app);
+    assert_eq!(session.left.cwd, src.path());
+    assert_eq!(session.left.selected, None);
+    assert!(toml::to_string(&session).is_ok());
+}
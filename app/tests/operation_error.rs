@@ -0,0 +1,48 @@
+use fileZoom::app::{Action, Mode};
+use fileZoom::input::KeyCode;
+use fileZoom::runner::handlers::handle_key;
+use fileZoom::runner::progress::OperationDecision;
+
+mod common;
+use common::app_in;
+
+#[test]
+fn job_errors_send_the_decision_to_the_worker() {
+    let tmp = tempfile::tempdir().unwrap();
+    let mut app = app_in(tmp.path());
+    let (tx, rx) = std::sync::mpsc::channel();
+    app.op_decision_tx = Some(tx);
+    let failed = || Mode::OperationError { path: Some("busy.txt".into()), error: "Permission denied".into(), retry: None, elevated: None, selected: 0 };
+
+    for (key, decision) in [(KeyCode::Enter, OperationDecision::Retry), (KeyCode::Char('s'), OperationDecision::Skip), (KeyCode::Esc, OperationDecision::Cancel)] {
+        app.mode = failed();
+        handle_key(&mut app, key, 10).unwrap();
+        assert_eq!(rx.try_recv(), Ok(decision));
+        assert!(matches!(app.mode, Mode::Progress { .. }));
+    }
+
+    // Right moves along to Skip and Abort, and stops there.
+    app.mode = failed();
+    for _ in 0..3 {
+        handle_key(&mut app, KeyCode::Right, 10).unwrap();
+    }
+    assert!(matches!(app.mode, Mode::OperationError { selected: 2, .. }));
+    assert!(rx.try_recv().is_err());
+}
+
+#[test]
+fn retry_runs_a_failed_action_again() {
+    let tmp = tempfile::tempdir().unwrap();
+    let mut app = app_in(tmp.path());
+    let failed = || Mode::OperationError { path: None, error: "Permission denied".into(), retry: Some(Action::NewDir("made".into())), elevated: None, selected: 0 };
+
+    app.mode = failed();
+    handle_key(&mut app, KeyCode::Char('a'), 10).unwrap();
+    assert!(matches!(app.mode, Mode::Normal));
+    assert!(!tmp.path().join("made").exists());
+
+    app.mode = failed();
+    handle_key(&mut app, KeyCode::Char('r'), 10).unwrap();
+    assert!(matches!(app.mode, Mode::Normal));
+    assert!(tmp.path().join("made").is_dir());
+}
@@ -0,0 +1,115 @@
+//! Operation profiles are read from `XDG_CONFIG_HOME`, so these tests live
+//! in their own test binary and share one config directory.
+
+use fileZoom::app::{App, Mode, StartOptions};
+use fileZoom::runner::commands::execute_command;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+static CONFIG_LOCK: Mutex<()> = Mutex::new(());
+
+fn write_profiles(cfg: &Path, toml: &str) {
+    std::env::set_var("XDG_CONFIG_HOME", cfg);
+    let path = fileZoom::app::settings::profiles::profiles_path();
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    std::fs::write(path, toml).unwrap();
+}
+
+/// App in `left`, with `left/proj` holding a source file, a temp file and
+/// a `target` directory, and `proj` selected.
+fn app_with_project(tmp: &Path) -> App {
+    let proj = tmp.join("left/proj");
+    std::fs::create_dir_all(proj.join("target/debug")).unwrap();
+    std::fs::write(proj.join("main.rs"), "fn main() {}").unwrap();
+    std::fs::write(proj.join("notes.tmp"), "scratch").unwrap();
+    std::fs::write(proj.join("target/debug/app"), "binary").unwrap();
+    let opts = StartOptions { start_dir: Some(tmp.join("left")), ..Default::default() };
+    let mut app = App::with_options(&opts).unwrap();
+    assert!(app.left.select_named("proj"));
+    app
+}
+
+fn wait_for_message(app: &mut App) -> (String, String) {
+    let deadline = Instant::now() + Duration::from_secs(10);
+    loop {
+        app.poll_progress();
+        if let Mode::Message { title, content, .. } = &app.mode {
+            return (title.clone(), content.clone());
+        }
+        assert!(Instant::now() < deadline, "profile never finished");
+        std::thread::sleep(Duration::from_millis(5));
+    }
+}
+
+#[test]
+fn copy_profile_leaves_out_excludes_and_verifies() {
+    let _guard = CONFIG_LOCK.lock().unwrap();
+    let cfg = tempfile::tempdir().unwrap();
+    let tmp = tempfile::tempdir().unwrap();
+    write_profiles(
+        cfg.path(),
+        r#"
+        [[profile]]
+        name = "Backup"
+        destination = "../backup"
+        excludes = ["*.tmp", "target"]
+        verify = true
+        throttle_mb_s = 50
+        "#,
+    );
+    let mut app = app_with_project(tmp.path());
+
+    assert!(execute_command(&mut app, "profile backup").unwrap());
+    assert!(matches!(&app.mode, Mode::Progress { title, verify: Some(_), .. } if title == "Backup"));
+    let (title, content) = wait_for_message(&mut app);
+    assert_eq!(title, "Done");
+    assert!(content.contains("1 files copied, 0 skipped") && content.contains("All 1 copied files verified"), "{}", content);
+
+    let backup = tmp.path().join("backup/proj");
+    assert_eq!(std::fs::read_to_string(backup.join("main.rs")).unwrap(), "fn main() {}");
+    assert!(!backup.join("notes.tmp").exists() && !backup.join("target").exists());
+    assert!(tmp.path().join("left/proj/main.rs").exists());
+}
+
+#[test]
+fn move_profile_keeps_excluded_sources_and_applies_the_conflict_policy() {
+    let _guard = CONFIG_LOCK.lock().unwrap();
+    let cfg = tempfile::tempdir().unwrap();
+    let tmp = tempfile::tempdir().unwrap();
+    let archive = tmp.path().join("archive");
+    std::fs::create_dir_all(archive.join("proj")).unwrap();
+    std::fs::write(archive.join("proj/main.rs"), "old").unwrap();
+    write_profiles(
+        cfg.path(),
+        &format!(
+            "[[profile]]\nname = \"Archive\"\noperation = \"move\"\ndestination = {:?}\nexcludes = [\"target\"]\non_conflict = \"overwrite\"\n",
+            archive
+        ),
+    );
+    let mut app = app_with_project(tmp.path());
+
+    execute_command(&mut app, "profile Archive").unwrap();
+    let (title, content) = wait_for_message(&mut app);
+    assert_eq!(title, "Done", "{}", content);
+
+    assert_eq!(std::fs::read_to_string(archive.join("proj/main.rs")).unwrap(), "fn main() {}");
+    assert!(archive.join("proj/notes.tmp").exists());
+    let left = tmp.path().join("left/proj");
+    assert!(!left.join("main.rs").exists() && !left.join("notes.tmp").exists());
+    assert!(left.join("target/debug/app").exists());
+}
+
+#[test]
+fn unknown_profile_and_listing() {
+    let _guard = CONFIG_LOCK.lock().unwrap();
+    let cfg = tempfile::tempdir().unwrap();
+    let tmp = tempfile::tempdir().unwrap();
+    write_profiles(cfg.path(), "[[profile]]\nname = \"Backup\"\ndestination = \"/mnt/nas\"\n");
+    let mut app = app_with_project(tmp.path());
+
+    execute_command(&mut app, "profile nope").unwrap();
+    assert!(matches!(&app.mode, Mode::Message { content, .. } if content == "No profile named `nope`"));
+    execute_command(&mut app, "profiles").unwrap();
+    assert!(matches!(&app.mode, Mode::Message { content, .. } if content == "Backup -> /mnt/nas"));
+}
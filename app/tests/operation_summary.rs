@@ -0,0 +1,110 @@
+use fileZoom::app::{App, Mode, Panel};
+use fileZoom::input::KeyCode;
+use fileZoom::runner::handlers::handle_key;
+use std::time::{Duration, Instant};
+
+mod common;
+use common::screen;
+
+fn app_in(dir: &std::path::Path) -> App {
+    let mut app = common::app_in(dir);
+    app.settings.confirm_overwrite = true;
+    app.settings.confirm_delete = true;
+    app.settings.verify_after_copy = false;
+    app
+}
+
+fn mark(app: &mut App, names: &[&str]) {
+    for name in names {
+        let idx = app.left.entries.iter().position(|e| e.name == *name).unwrap();
+        app.left.selections.insert(idx);
+    }
+}
+
+#[test]
+fn copy_with_a_skipped_conflict_ends_in_a_summary() {
+    let tmp = tempfile::tempdir().unwrap();
+    let (left, right) = (tmp.path().join("left"), tmp.path().join("right"));
+    std::fs::create_dir_all(&left).unwrap();
+    std::fs::create_dir_all(&right).unwrap();
+    std::fs::write(left.join("new.txt"), "12345").unwrap();
+    std::fs::write(left.join("taken.txt"), "new").unwrap();
+    std::fs::write(right.join("taken.txt"), "old").unwrap();
+    let mut app = app_in(&left);
+    app.right = Panel::new(right.clone());
+    app.refresh().unwrap();
+    mark(&mut app, &["new.txt", "taken.txt"]);
+
+    handle_key(&mut app, KeyCode::F(5), 10).unwrap();
+    let deadline = Instant::now() + Duration::from_secs(10);
+    loop {
+        app.poll_progress();
+        match &app.mode {
+            Mode::Conflict { .. } => {
+                handle_key(&mut app, KeyCode::Char('s'), 10).unwrap();
+            }
+            Mode::Summary { .. } => break,
+            _ => {}
+        }
+        assert!(Instant::now() < deadline, "the copy never finished");
+        std::thread::sleep(Duration::from_millis(5));
+    }
+
+    let Mode::Summary { title, summary, .. } = &app.mode else { unreachable!() };
+    assert_eq!(title, "Done");
+    assert_eq!((summary.succeeded, summary.skipped, summary.failed(), summary.bytes), (1, 1, 0, 5));
+    assert_eq!(std::fs::read_to_string(right.join("taken.txt")).unwrap(), "old");
+    let text = screen(&app);
+    assert!(text.contains("Succeeded 1") && text.contains("Skipped   1") && text.contains("5 B"), "{text}");
+
+    handle_key(&mut app, KeyCode::Enter, 10).unwrap();
+    assert!(matches!(app.mode, Mode::Normal));
+}
+
+#[test]
+fn deleting_marked_entries_lists_the_failures() {
+    let tmp = tempfile::tempdir().unwrap();
+    for name in ["a.txt", "b.txt", "gone.txt"] {
+        std::fs::write(tmp.path().join(name), "xx").unwrap();
+    }
+    let mut app = app_in(tmp.path());
+    mark(&mut app, &["a.txt", "b.txt", "gone.txt"]);
+    // Removed behind the panel's back, so deleting it fails.
+    std::fs::remove_file(tmp.path().join("gone.txt")).unwrap();
+
+    handle_key(&mut app, KeyCode::Char('d'), 10).unwrap();
+    assert!(matches!(&app.mode, Mode::Confirm { msg, .. } if msg.starts_with("Delete 3 marked item(s)?")));
+    handle_key(&mut app, KeyCode::Char('y'), 10).unwrap();
+
+    let Mode::Summary { title, summary, .. } = &app.mode else { panic!("expected a summary, got {:?}", app.mode) };
+    assert_eq!(title, "Done with errors");
+    assert_eq!((summary.succeeded, summary.failed(), summary.bytes), (2, 1, 4));
+    assert!(summary.failures[0].0.ends_with("gone.txt"));
+    assert!(!tmp.path().join("a.txt").exists() && !tmp.path().join("b.txt").exists());
+    assert!(app.left.selections.is_empty());
+    let text = screen(&app);
+    assert!(text.contains("Failures 1-1 of 1") && text.contains("gone.txt: "), "{text}");
+
+    // `l` opens the log the failures were written to.
+    handle_key(&mut app, KeyCode::Char('l'), 10).unwrap();
+    assert!(matches!(app.mode, Mode::LogView { .. }));
+}
+
+#[test]
+fn summary_scrolls_through_the_failures() {
+    let mut app = App::new().unwrap();
+    let mut summary = fileZoom::runner::progress::OperationSummary::default();
+    for i in 0..25 {
+        summary.fail(format!("/tmp/f{i}").into(), "denied");
+    }
+    app.show_summary(summary, None, None);
+    handle_key(&mut app, KeyCode::PageDown, 10).unwrap();
+    assert!(matches!(app.mode, Mode::Summary { scroll: 10, .. }));
+    handle_key(&mut app, KeyCode::End, 10).unwrap();
+    assert!(matches!(app.mode, Mode::Summary { scroll: 15, .. }));
+    assert!(screen(&app).contains("Failures 16-25 of 25"));
+    handle_key(&mut app, KeyCode::Home, 10).unwrap();
+    assert!(matches!(app.mode, Mode::Summary { scroll: 0, .. }));
+    handle_key(&mut app, KeyCode::Esc, 10).unwrap();
+    assert!(matches!(app.mode, Mode::Normal));
+}
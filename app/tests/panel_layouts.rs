@@ -0,0 +1,63 @@
+use fileZoom::app::{App, PanelLayout, StartOptions};
+use fileZoom::input::mouse::{MouseButton, MouseEvent, MouseEventKind};
+use fileZoom::input::KeyCode;
+use fileZoom::runner::handlers::{handle_key, handle_mouse};
+use fileZoom::ui::layout::ScreenLayout;
+use fileZoom::Side;
+use ratatui::{backend::TestBackend, layout::Rect, Terminal};
+
+mod common;
+use common::draw;
+
+#[test]
+fn layouts_cycle_and_clicks_follow_the_drawn_panels() {
+    let tmp = tempfile::tempdir().unwrap();
+    let (left, right) = (tmp.path().join("left"), tmp.path().join("right"));
+    std::fs::create_dir_all(&left).unwrap();
+    std::fs::create_dir_all(&right).unwrap();
+    std::fs::write(left.join("l.txt"), "left").unwrap();
+    for name in ["r1.txt", "r2.txt", "r3.txt"] {
+        std::fs::write(right.join(name), "right").unwrap();
+    }
+    let opts = StartOptions { start_dir: Some(left), right_dir: Some(right), ..Default::default() };
+    let mut app = App::with_options(&opts).unwrap();
+    let area = Rect::new(0, 0, 80, 24);
+    let mut terminal = Terminal::new(TestBackend::new(area.width, area.height)).unwrap();
+    assert_eq!(app.layout, PanelLayout::Horizontal);
+
+    // Vertical: the right panel is drawn below the left one...
+    handle_key(&mut app, KeyCode::Char('L'), 10).unwrap();
+    assert_eq!(app.layout, PanelLayout::Vertical);
+    let layout = ScreenLayout::compute(&app, area);
+    let rows = draw(&mut terminal, &app);
+    let r2_row = rows.iter().position(|r| r.contains("r2.txt")).unwrap() as u16;
+    assert!(r2_row > layout.left.y + layout.left.height);
+    assert!(rows.iter().position(|r| r.contains("l.txt")).unwrap() < r2_row as usize);
+
+    // ...and clicks go to the panel drawn under the pointer.
+    let click = |row| MouseEvent { column: 5, row, kind: MouseEventKind::Down(MouseButton::Left) };
+    handle_mouse(&mut app, click(r2_row), area).unwrap();
+    assert_eq!(app.active, Side::Right);
+    assert_eq!(app.right.selected, (r2_row - layout.right.y - 1) as usize);
+    handle_mouse(&mut app, click(layout.left.y + 1), area).unwrap();
+    assert_eq!(app.active, Side::Left);
+    app.active = Side::Right;
+
+    // Single: only the active panel is drawn.
+    handle_key(&mut app, KeyCode::Char('L'), 10).unwrap();
+    assert_eq!(app.layout, PanelLayout::Single);
+    let text = draw(&mut terminal, &app).concat();
+    assert!(text.contains("r1.txt") && !text.contains("l.txt"));
+
+    // Preview-dominant: the active panel next to a preview of its selection.
+    handle_key(&mut app, KeyCode::Char('L'), 10).unwrap();
+    assert_eq!(app.layout, PanelLayout::PreviewDominant);
+    let layout = ScreenLayout::compute(&app, area);
+    let preview = layout.preview.expect("preview area");
+    assert!(preview.width > layout.right.width);
+    let rows = draw(&mut terminal, &app);
+    assert!(rows[preview.y as usize].contains("Preview"));
+
+    handle_key(&mut app, KeyCode::Char('L'), 10).unwrap();
+    assert_eq!(app.layout, PanelLayout::Horizontal);
+}
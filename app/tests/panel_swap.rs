@@ -0,0 +1,42 @@
+use fileZoom::app::{App, Side, StartOptions};
+use fileZoom::input::{Key, KeyCode, KeyModifiers};
+use fileZoom::runner::handlers::panels::handle_panels_key;
+
+fn key(c: char, ctrl: bool, alt: bool) -> Key {
+    Key { code: KeyCode::Char(c), modifiers: KeyModifiers { ctrl, alt, shift: c.is_ascii_uppercase(), ..Default::default() } }
+}
+
+#[test]
+fn panels_swap_and_follow_each_other() {
+    let tmp = tempfile::tempdir().unwrap();
+    let (a, b) = (tmp.path().join("a"), tmp.path().join("b"));
+    std::fs::create_dir_all(&a).unwrap();
+    std::fs::create_dir_all(&b).unwrap();
+    for name in ["1.txt", "2.txt"] {
+        std::fs::write(a.join(name), "").unwrap();
+    }
+    let opts = StartOptions { start_dir: Some(a.clone()), right_dir: Some(b.clone()), ..Default::default() };
+    let mut app = App::with_options(&opts).unwrap();
+    assert!(app.left.select_named("2.txt"));
+
+    // Ctrl+U swaps the directories, the cursor going along; the left panel
+    // stays active.
+    assert!(handle_panels_key(&mut app, &key('u', true, false)));
+    assert_eq!((&app.left.cwd, &app.right.cwd, app.active), (&b, &a, Side::Left));
+    assert_eq!(app.right.selected_entry().map(|e| e.name.to_string_lossy().into_owned()).as_deref(), Some("2.txt"));
+
+    // Alt+I shows the active panel's directory in the other one...
+    assert!(handle_panels_key(&mut app, &key('i', false, true)));
+    assert_eq!((&app.left.cwd, &app.right.cwd), (&b, &b));
+
+    // ...and Alt+Shift+I the other panel's directory in the active one.
+    app.active = Side::Right;
+    app.go_to(a.clone()).unwrap();
+    app.active = Side::Left;
+    assert!(handle_panels_key(&mut app, &key('I', false, true)));
+    assert_eq!((&app.left.cwd, &app.right.cwd, app.active), (&a, &a, Side::Left));
+    assert!(app.left.entries.iter().any(|e| e.name == "1.txt"));
+
+    // Other chords are left to the other handlers.
+    assert!(!handle_panels_key(&mut app, &key('v', true, false)));
+}
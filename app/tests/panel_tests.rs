@@ -117,3 +117,68 @@ fn ensure_selected_visible_zero_height_and_single_item() {
     q.ensure_selected_visible(1);
     assert_eq!(q.offset, 0);
 }
+
+/// A panel backed by a non-filesystem provider lists and previews through it.
+#[test]
+fn panel_lists_and_previews_through_custom_vfs() {
+    use fileZoom::fs_op::vfs::VfsProvider;
+    use std::io::{self, Read};
+    use std::path::Path;
+    use std::sync::Arc;
+
+    #[derive(Debug)]
+    struct InMemory;
+
+    impl VfsProvider for InMemory {
+        fn name(&self) -> &str { "memory" }
+        fn list(&self, dir: &Path) -> io::Result<Vec<Entry>> {
+            Ok(vec![Entry::file("notes.txt", dir.join("notes.txt"), 5, None)])
+        }
+        fn stat(&self, path: &Path) -> io::Result<Entry> {
+            Ok(Entry::file("notes.txt", path.to_path_buf(), 5, None))
+        }
+        fn open(&self, _path: &Path) -> io::Result<Box<dyn Read + Send>> {
+            Ok(Box::new(io::Cursor::new(b"hello".to_vec())))
+        }
+        fn copy(&self, _from: &Path, _to: &Path) -> io::Result<()> { Err(io::Error::from(io::ErrorKind::Unsupported)) }
+        fn remove(&self, _path: &Path) -> io::Result<()> { Err(io::Error::from(io::ErrorKind::Unsupported)) }
+    }
+
+    let mut app = fileZoom::app::App::new().unwrap();
+    app.left = Panel::with_vfs(PathBuf::from("/memory/root"), Arc::new(InMemory));
+    app.refresh_side(fileZoom::Side::Left).unwrap();
+    assert_eq!(app.left.entries.len(), 1);
+    assert_eq!(app.left.entries[0].path, PathBuf::from("/memory/root/notes.txt"));
+
+    app.left.selected = 2;
+    app.update_preview_for(fileZoom::Side::Left);
+    assert_eq!(app.left.preview, "hello");
+    assert_eq!(app.left.vfs.name(), "memory");
+}
+
+#[test]
+fn only_the_visible_window_of_a_huge_listing_is_materialized() {
+    let mut app = fileZoom::app::App::new().unwrap();
+    app.left = Panel::new(PathBuf::from("/"));
+    app.left.entries = (0..200_000)
+        .map(|i| Entry::file(format!("f{}", i), PathBuf::from(format!("/f{}", i)), 0, None))
+        .collect();
+    // "/" has no parent row, so entry i is UI row 1 + i.
+    app.left.selected = 1 + 150_000;
+    assert_eq!(app.left.visible_range(20), 149_981..150_001);
+
+    let state = fileZoom::ui::UIState::from_core_window(&app, 20);
+    assert_eq!(state.left_list.len(), 20);
+    assert_eq!(state.left_total, 200_000);
+    assert_eq!(state.left_offset, 149_981);
+    assert_eq!(state.left_list[state.left_selected], "f150000");
+
+    // Scrolling back above the window moves it up to the selection.
+    app.left.offset = 1 + 149_990;
+    app.left.selected = 1 + 10;
+    assert_eq!(app.left.visible_range(20), 10..30);
+    // Selecting the header highlights nothing.
+    app.left.selected = 0;
+    let state = fileZoom::ui::UIState::from_core_window(&app, 20);
+    assert!(state.left_selected >= state.left_list.len());
+}
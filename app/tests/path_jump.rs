@@ -0,0 +1,66 @@
+use fileZoom::app::{App, InputKind, Mode, StartOptions};
+use fileZoom::input::KeyCode;
+use fileZoom::runner::handlers::handle_key;
+
+mod common;
+use common::screen;
+
+fn open_change_path(app: &mut App) {
+    app.left.selected = 0;
+    handle_key(app, KeyCode::Enter, 10).unwrap();
+    assert!(matches!(app.mode, Mode::Input { kind: InputKind::ChangePath, .. }));
+}
+
+fn type_text(app: &mut App, text: &str) {
+    for c in text.chars() {
+        handle_key(app, KeyCode::Char(c), 10).unwrap();
+    }
+}
+
+#[test]
+fn tab_completes_and_enter_jumps() {
+    let tmp = tempfile::tempdir().unwrap();
+    for d in ["projects/rust_mc", "photos", "music"] {
+        std::fs::create_dir_all(tmp.path().join(d)).unwrap();
+    }
+    let opts = StartOptions { start_dir: Some(tmp.path().to_path_buf()), ..Default::default() };
+    let mut app = App::with_options(&opts).unwrap();
+    let sep = std::path::MAIN_SEPARATOR;
+
+    open_change_path(&mut app);
+    type_text(&mut app, "pjs");
+    let texts: Vec<&str> = app.path_jump.candidates.iter().map(|c| c.text.as_str()).collect();
+    assert_eq!(texts, [format!("projects{}", sep)]);
+    assert!(screen(&app).contains(&format!("projects{}", sep)));
+
+    handle_key(&mut app, KeyCode::Tab, 10).unwrap();
+    type_text(&mut app, "r");
+    handle_key(&mut app, KeyCode::Tab, 10).unwrap();
+    assert!(matches!(&app.mode, Mode::Input { buffer, .. } if *buffer == format!("projects{}rust_mc{}", sep, sep)));
+    handle_key(&mut app, KeyCode::Enter, 10).unwrap();
+    assert!(matches!(app.mode, Mode::Normal));
+    assert_eq!(app.left.cwd, tmp.path().join("projects").join("rust_mc"));
+
+    // The start directory is now a recent suggestion, chosen with Down.
+    open_change_path(&mut app);
+    assert!(app.path_jump.candidates.iter().all(|c| c.recent));
+    assert!(screen(&app).contains("recent"));
+    handle_key(&mut app, KeyCode::Down, 10).unwrap();
+    assert_eq!(app.path_jump.selected, Some(0));
+    handle_key(&mut app, KeyCode::Enter, 10).unwrap();
+    assert_eq!(app.left.cwd, tmp.path());
+}
+
+#[test]
+fn unknown_path_shows_an_error() {
+    let tmp = tempfile::tempdir().unwrap();
+    let opts = StartOptions { start_dir: Some(tmp.path().to_path_buf()), ..Default::default() };
+    let mut app = App::with_options(&opts).unwrap();
+
+    open_change_path(&mut app);
+    type_text(&mut app, "nowhere");
+    assert!(app.path_jump.candidates.is_empty());
+    handle_key(&mut app, KeyCode::Enter, 10).unwrap();
+    assert!(matches!(&app.mode, Mode::Message { content, .. } if content.starts_with("path does not exist")));
+    assert_eq!(app.left.cwd, tmp.path());
+}
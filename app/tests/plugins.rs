@@ -0,0 +1,63 @@
+#![cfg(unix)]
+
+use std::os::unix::fs::PermissionsExt;
+
+use assert_fs::prelude::*;
+use fileZoom::app::{App, Mode, Panel};
+use fileZoom::input::KeyCode;
+use fileZoom::plugins::PluginRegistry;
+use fileZoom::runner::handlers::handle_key;
+
+const PLUGIN: &str = r#"#!/bin/sh
+read req
+case "$req" in
+  *describe*) echo '{"name":"t","actions":[{"id":"touch","label":"Touch marker"}],"columns":[{"id":"c","title":"C"}]}' ;;
+  *'"type":"action"'*) touch marker.txt; echo '{"message":"done","refresh":true}' ;;
+  *) echo '{"values":["[p]","[p]","[p]"]}' ;;
+esac
+"#;
+
+#[test]
+fn plugin_action_runs_from_context_menu_and_columns_render() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let plugins = assert_fs::TempDir::new().unwrap();
+    plugins.child("touch").write_str(PLUGIN).unwrap();
+    std::fs::set_permissions(plugins.child("touch").path(), std::fs::Permissions::from_mode(0o755)).unwrap();
+    temp.child("a.txt").write_str("a").unwrap();
+
+    let mut app = App::new().unwrap();
+    app.plugins = PluginRegistry::load_from(plugins.path());
+    assert!(app.plugins.errors.is_empty(), "{:?}", app.plugins.errors);
+    app.left = Panel::new(temp.path().to_path_buf());
+    app.right = Panel::new(temp.path().to_path_buf());
+    app.refresh().unwrap();
+    // The plugin answers on a worker thread.
+    let deadline = std::time::Instant::now() + std::time::Duration::from_secs(10);
+    while app.left.plugin_columns_loading.is_some() {
+        assert!(std::time::Instant::now() < deadline, "the plugin never answered");
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        app.poll_listings();
+    }
+    assert_eq!(app.left.plugin_columns, vec!["[p]".to_string()]);
+    let state = fileZoom::ui::UIState::from_core(&app);
+    assert_eq!(state.left_list, vec!["a.txt  [p]".to_string()]);
+
+    let parent_count = if app.left.cwd.parent().is_some() { 1usize } else { 0usize };
+    app.left.selected = 1 + parent_count;
+    handle_key(&mut app, KeyCode::F(3), 10).unwrap();
+    let idx = match &app.mode {
+        Mode::ContextMenu { options, .. } => {
+            assert_eq!(options.last().map(String::as_str), Some("Cancel"));
+            options.iter().position(|o| o == "Touch marker").expect("plugin action listed")
+        }
+        other => panic!("expected ContextMenu, got {:?}", other),
+    };
+    for _ in 0..idx {
+        handle_key(&mut app, KeyCode::Down, 10).unwrap();
+    }
+    handle_key(&mut app, KeyCode::Enter, 10).unwrap();
+    assert!(matches!(&app.mode, Mode::Message { content, .. } if content == "done"));
+    assert!(temp.child("marker.txt").path().exists());
+    // The plugin asked for a refresh, so the new file is listed.
+    assert!(app.left.entries.iter().any(|e| e.name == "marker.txt"));
+}
@@ -0,0 +1,59 @@
+use fileZoom::app::{App, Mode, StartOptions};
+use fileZoom::input::KeyCode;
+use fileZoom::runner::commands::execute_command;
+use fileZoom::runner::handlers::handle_key;
+use fileZoom::Side;
+
+fn app_with(name: &str, bytes: &[u8]) -> (tempfile::TempDir, App) {
+    let tmp = tempfile::tempdir().unwrap();
+    std::fs::write(tmp.path().join(name), bytes).unwrap();
+    let opts = StartOptions { start_dir: Some(tmp.path().to_path_buf()), ..Default::default() };
+    let mut app = App::with_options(&opts).unwrap();
+    assert!(app.left.select_named(name));
+    app.update_preview_for(Side::Left);
+    (tmp, app)
+}
+
+#[test]
+fn legacy_and_utf16_text_is_previewed_not_declared_binary() {
+    let (_tmp, app) = app_with("latin1.txt", b"Caf\xe9 cr\xe8me br\xfbl\xe9e, na\xefve fa\xe7ade\n");
+    assert_eq!(app.left.preview, "Encoding: windows-1252 (detected)\nCafé crème brûlée, naïve façade\n");
+
+    let utf16: Vec<u8> = "\u{feff}hello\n".encode_utf16().flat_map(u16::to_le_bytes).collect();
+    let (_tmp, app) = app_with("utf16.txt", &utf16);
+    assert_eq!(app.left.preview, "Encoding: UTF-16LE (detected)\nhello\n");
+
+    let (_tmp, app) = app_with("plain.txt", b"just utf-8\n");
+    assert_eq!(app.left.preview, "just utf-8\n");
+
+    let (_tmp, app) = app_with("blob.bin", b"\x7fELF\x02\x01\x01\0\0\0\0\0");
+    assert!(app.left.preview.starts_with("Binary file:"), "{}", app.left.preview);
+}
+
+#[test]
+fn encoding_can_be_chosen_in_the_picker_or_by_command() {
+    let (sjis, _, _) = encoding_rs::SHIFT_JIS.encode("テスト");
+    let (_tmp, mut app) = app_with("short.txt", &sjis);
+
+    execute_command(&mut app, "encoding sjis").unwrap();
+    assert_eq!(app.left.preview, "Encoding: Shift_JIS (chosen)\nテスト");
+
+    // `e` in the focused preview opens the picker on the encoding in use.
+    app.preview_visible = true;
+    app.preview_focused = true;
+    handle_key(&mut app, KeyCode::Char('e'), 10).unwrap();
+    let Mode::EncodingPicker { selected } = app.mode else { panic!("expected the encoding picker, got {:?}", app.mode) };
+    assert_eq!(fileZoom::app::core::encoding::CHOICES[selected - 1], "Shift_JIS");
+
+    // Going back to Auto detects the encoding again.
+    for _ in 0..selected {
+        handle_key(&mut app, KeyCode::Up, 10).unwrap();
+    }
+    handle_key(&mut app, KeyCode::Enter, 10).unwrap();
+    assert!(matches!(app.mode, Mode::Normal));
+    assert_eq!(app.left.preview_encoding, None);
+    assert!(!app.left.preview.contains("(chosen)"));
+
+    execute_command(&mut app, "encoding klingon").unwrap();
+    assert!(matches!(app.mode, Mode::Message { .. }));
+}
@@ -0,0 +1,57 @@
+use fileZoom::app::{App, StartOptions};
+use fileZoom::input::KeyCode;
+use fileZoom::runner::handlers::handle_key;
+use fileZoom::ui::layout::ScreenLayout;
+use ratatui::{backend::TestBackend, layout::Rect, Terminal};
+
+#[test]
+fn preview_pane_toggles_focuses_and_scrolls() {
+    let tmp = tempfile::tempdir().unwrap();
+    let text: String = (1..=100).map(|i| format!("line {}\n", i)).collect();
+    std::fs::write(tmp.path().join("long.txt"), text).unwrap();
+    let opts = StartOptions { start_dir: Some(tmp.path().to_path_buf()), ..Default::default() };
+    let mut app = App::with_options(&opts).unwrap();
+    assert!(app.left.select_named("long.txt"));
+    app.update_preview_for(fileZoom::Side::Left);
+    let area = Rect::new(0, 0, 80, 24);
+
+    // `p` adds the pane next to the panels.
+    assert!(ScreenLayout::compute(&app, area).preview.is_none());
+    handle_key(&mut app, KeyCode::Char('p'), 10).unwrap();
+    let preview = ScreenLayout::compute(&app, area).preview.expect("preview pane");
+    let mut terminal = Terminal::new(TestBackend::new(area.width, area.height)).unwrap();
+    terminal.draw(|f| fileZoom::ui::ui(f, &app)).unwrap();
+    let buf = terminal.backend().buffer();
+    let row: String = (preview.x..preview.x + preview.width).map(|x| buf[(x, preview.y + 1)].symbol()).collect();
+    assert!(row.contains("line 1"), "{row}");
+
+    // With the focus on the preview, paging scrolls it, not the list.
+    let selected = app.left.selected;
+    handle_key(&mut app, KeyCode::Char('P'), 10).unwrap();
+    assert!(app.preview_focused);
+    handle_key(&mut app, KeyCode::PageDown, 10).unwrap();
+    handle_key(&mut app, KeyCode::Down, 10).unwrap();
+    assert_eq!((app.left.preview_offset, app.left.selected), (11, selected));
+    handle_key(&mut app, KeyCode::End, 10).unwrap();
+    assert_eq!(app.left.preview_offset, 99);
+
+    // Esc hands the keys back to the panel; hiding the pane drops the focus.
+    handle_key(&mut app, KeyCode::Esc, 10).unwrap();
+    assert!(!app.preview_focused);
+    handle_key(&mut app, KeyCode::Char('P'), 10).unwrap();
+    handle_key(&mut app, KeyCode::Char('p'), 10).unwrap();
+    assert!(!app.preview_visible && !app.preview_focused);
+}
+
+#[test]
+fn preview_goes_by_content_not_extension() {
+    let tmp = tempfile::tempdir().unwrap();
+    // A PDF whose first bytes happen to be printable still is not text.
+    std::fs::write(tmp.path().join("report.txt"), "%PDF-1.4\n1 0 obj << /Type /Catalog >> endobj\n").unwrap();
+    let opts = StartOptions { start_dir: Some(tmp.path().to_path_buf()), ..Default::default() };
+    let mut app = App::with_options(&opts).unwrap();
+    assert!(app.left.select_named("report.txt"));
+    app.update_preview_for(fileZoom::Side::Left);
+    assert!(app.left.preview.starts_with("Binary file:"), "{}", app.left.preview);
+    assert!(app.left.preview.contains("PDF document, application/pdf"), "{}", app.left.preview);
+}
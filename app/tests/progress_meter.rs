@@ -0,0 +1,60 @@
+use fileZoom::app::Mode;
+use fileZoom::fs_op::meter::Meter;
+use fileZoom::input::KeyCode;
+use fileZoom::runner::handlers::handle_key;
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+mod common;
+use common::{app_in, screen_sized};
+
+#[test]
+fn copies_count_the_bytes_of_every_item() {
+    let tmp = tempfile::tempdir().unwrap();
+    let tree = tmp.path().join("left/tree");
+    std::fs::create_dir_all(tree.join("sub")).unwrap();
+    std::fs::create_dir_all(tmp.path().join("right")).unwrap();
+    std::fs::write(tree.join("a"), vec![1u8; 5000]).unwrap();
+    std::fs::write(tree.join("sub/b"), vec![2u8; 3000]).unwrap();
+    std::fs::write(tmp.path().join("left/c"), vec![3u8; 2000]).unwrap();
+    let mut app = app_in(&tmp.path().join("left"));
+    app.right.cwd = tmp.path().join("right");
+    app.refresh().unwrap();
+    // Existing target, so the items are copied one by one.
+    std::fs::write(tmp.path().join("right/c"), "old").unwrap();
+    app.settings.confirm_overwrite = false;
+    for name in ["tree", "c"] {
+        let idx = app.left.entries.iter().position(|e| e.name == name).unwrap();
+        app.left.selections.insert(idx);
+    }
+
+    handle_key(&mut app, KeyCode::F(5), 10).unwrap();
+    let meter = app.op_meter.clone().expect("copies have a meter");
+    let rx = app.op_progress_rx.take().unwrap();
+    while !rx.recv_timeout(Duration::from_secs(10)).expect("worker update").done {}
+
+    let stats = meter.stats(Instant::now());
+    assert_eq!((stats.bytes_done, stats.bytes_total), (10_000, 10_000));
+    assert_eq!(std::fs::read(tmp.path().join("right/c")).unwrap().len(), 2000);
+}
+
+#[test]
+fn p_pauses_and_resumes_the_operation() {
+    let mut app = app_in(&std::env::temp_dir());
+    let meter = Meter::new(Arc::new(AtomicBool::new(false)));
+    meter.set_total(4096);
+    app.op_meter = Some(meter.clone());
+    app.mode = Mode::Progress { title: "Copying".into(), processed: 0, total: 1, message: "Copying".into(), cancelled: false, verify: None };
+
+    let text = screen_sized(&app, 80, 24);
+    assert!(text.contains("0 B/4.0 KiB") && text.contains("p pause"), "{text}");
+
+    handle_key(&mut app, KeyCode::Char('p'), 10).unwrap();
+    assert!(meter.paused());
+    let text = screen_sized(&app, 80, 24);
+    assert!(text.contains("Paused") && text.contains("p resume"), "{text}");
+
+    handle_key(&mut app, KeyCode::Char('p'), 10).unwrap();
+    assert!(!meter.paused());
+}
@@ -0,0 +1,185 @@
+//! Property tests for listing sort and filter and for name handling, fed
+//! arbitrary file names: control characters, odd Unicode and (on Unix)
+//! bytes that are not valid UTF-8.
+
+use std::cmp::Ordering;
+use std::path::PathBuf;
+
+use chrono::{Local, TimeZone};
+use fileZoom::app::core::listing::{filter_hidden, sort_entries};
+use fileZoom::app::types::{Entry, SortKey, SortOrder};
+use fileZoom::fs_op::helpers::resolve_target;
+use fileZoom::fs_op::mv::rename_path;
+use fileZoom::fs_op::path::{abbreviate_home, display_path};
+use proptest::prelude::*;
+
+/// Any name: plain, control characters or arbitrary Unicode.
+fn any_name() -> impl Strategy<Value = String> {
+    prop_oneof![
+        "[a-zA-Z0-9._ -]{0,12}",
+        prop::collection::vec(prop::char::range('\u{0}', '\u{1f}'), 1..6).prop_map(|cs| cs.into_iter().collect()),
+        any::<String>(),
+    ]
+}
+
+/// A name that is a valid single path component on every Unix filesystem.
+fn component_name() -> impl Strategy<Value = String> {
+    prop::collection::vec(any::<char>().prop_filter("no separators or NUL", |c| *c != '/' && *c != '\0'), 1..30)
+        .prop_map(|cs| cs.into_iter().collect::<String>())
+        .prop_filter("not . or ..", |n| n != "." && n != ".." && n.len() <= 255)
+}
+
+/// Entries with few distinct sizes and times so ties are common. The path
+/// records the original position to check stability.
+fn entries() -> impl Strategy<Value = Vec<Entry>> {
+    prop::collection::vec((any_name(), any::<bool>(), 0u64..4, prop::option::of(0i64..3)), 0..24).prop_map(|items| {
+        items
+            .into_iter()
+            .enumerate()
+            .map(|(i, (name, is_dir, size, secs))| {
+                let modified = secs.and_then(|s| Local.timestamp_opt(s * 60, 0).single());
+                let path = PathBuf::from(i.to_string());
+                if is_dir {
+                    Entry::directory(name, path, modified)
+                } else {
+                    Entry::file(name, path, size, modified)
+                }
+            })
+            .collect()
+    })
+}
+
+fn position(e: &Entry) -> usize {
+    e.path.to_str().unwrap().parse().unwrap()
+}
+
+fn compare(key: SortKey, a: &Entry, b: &Entry) -> Ordering {
+    match key {
+        SortKey::Name => (!a.is_dir, a.display_name().to_lowercase()).cmp(&(!b.is_dir, b.display_name().to_lowercase())),
+        SortKey::Size => a.size.cmp(&b.size),
+        SortKey::Modified => a.modified.cmp(&b.modified),
+    }
+}
+
+fn sort_key() -> impl Strategy<Value = SortKey> {
+    prop_oneof![Just(SortKey::Name), Just(SortKey::Size), Just(SortKey::Modified)]
+}
+
+fn sort_order() -> impl Strategy<Value = SortOrder> {
+    prop_oneof![Just(SortOrder::Ascending), Just(SortOrder::Descending)]
+}
+
+proptest! {
+    #[test]
+    fn sorting_is_ordered_and_stable(mut list in entries(), key in sort_key(), order in sort_order()) {
+        let len = list.len();
+        sort_entries(&mut list, key, order);
+        let mut seen: Vec<usize> = list.iter().map(position).collect();
+        seen.sort_unstable();
+        prop_assert_eq!(seen, (0..len).collect::<Vec<_>>());
+        for pair in list.windows(2) {
+            let ord = match order {
+                SortOrder::Ascending => compare(key, &pair[0], &pair[1]),
+                SortOrder::Descending => compare(key, &pair[1], &pair[0]),
+            };
+            prop_assert_ne!(ord, Ordering::Greater);
+            if ord == Ordering::Equal {
+                prop_assert!(position(&pair[0]) < position(&pair[1]), "ties keep listing order");
+            }
+        }
+    }
+
+    #[test]
+    fn hidden_filter_drops_exactly_dotfiles(list in entries()) {
+        let mut shown = list.clone();
+        filter_hidden(&mut shown, false);
+        let expected: Vec<usize> = list.iter().filter(|e| !e.display_name().starts_with('.')).map(position).collect();
+        prop_assert_eq!(shown.iter().map(position).collect::<Vec<_>>(), expected);
+
+        let mut all = list.clone();
+        filter_hidden(&mut all, true);
+        prop_assert_eq!(all, list);
+    }
+
+    #[test]
+    fn display_path_fits_its_budget(parts in prop::collection::vec(any_name(), 0..6), max in 0usize..80) {
+        let path: PathBuf = std::iter::once("/".to_string()).chain(parts).collect();
+        let shown = display_path(&path, max);
+        prop_assert!(shown.chars().count() <= max, "{:?} is longer than {}", shown, max);
+        if abbreviate_home(&path).chars().count() <= max {
+            prop_assert_eq!(shown, abbreviate_home(&path));
+        }
+    }
+
+    #[test]
+    fn resolve_target_never_panics(name in any_name(), trailing in any::<bool>()) {
+        let dst = PathBuf::from(if trailing { "/nonexistent-dst/" } else { "/nonexistent-dst" });
+        let target = resolve_target(&dst, &name);
+        if !trailing {
+            prop_assert_eq!(target, dst);
+        }
+    }
+
+    #[test]
+    fn resolve_target_joins_components(name in component_name()) {
+        let dir = tempfile::tempdir().unwrap();
+        let target = resolve_target(dir.path(), &name);
+        prop_assert_eq!(target.parent(), Some(dir.path()));
+        prop_assert_eq!(target.file_name().and_then(|n| n.to_str()), Some(name.as_str()));
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn rename_round_trips(name in component_name()) {
+        let dir = tempfile::tempdir().unwrap();
+        let start = dir.path().join("start");
+        std::fs::write(&start, name.as_bytes()).unwrap();
+        rename_path(&start, &name).unwrap();
+        let renamed = dir.path().join(&name);
+        prop_assert_eq!(std::fs::read(&renamed).unwrap(), name.as_bytes());
+        prop_assert!(!start.exists() || name == "start");
+        rename_path(&renamed, "start").unwrap();
+        prop_assert_eq!(std::fs::read(&start).unwrap(), name.as_bytes());
+    }
+}
+
+#[cfg(unix)]
+mod non_utf8 {
+    use super::*;
+    use fileZoom::fs_op::vfs;
+    use std::ffi::OsStr;
+    use std::os::unix::ffi::OsStrExt;
+
+    /// Raw file-name bytes, mostly invalid UTF-8.
+    fn raw_name() -> impl Strategy<Value = Vec<u8>> {
+        prop::collection::vec(any::<u8>().prop_filter("no separators or NUL", |b| *b != b'/' && *b != 0), 1..40)
+            .prop_filter("not . or ..", |n| n != b"." && n != b"..")
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(64))]
+
+        #[test]
+        fn listing_sorting_and_display_handle_raw_bytes(raw in raw_name(), max in 0usize..40) {
+            let dir = tempfile::tempdir().unwrap();
+            let name = OsStr::from_bytes(&raw);
+            std::fs::write(dir.path().join(name), b"x").unwrap();
+
+            let mut list = vfs::local().list(dir.path()).unwrap();
+            prop_assert_eq!(list.len(), 1);
+            prop_assert_eq!(list[0].name.as_os_str(), name);
+            prop_assert_eq!(list[0].path.file_name(), Some(name));
+            prop_assert!(list[0].path.exists());
+
+            filter_hidden(&mut list, true);
+            sort_entries(&mut list, SortKey::Name, SortOrder::Descending);
+            let shown = display_path(&list[0].path, max);
+            prop_assert!(shown.chars().count() <= max);
+            prop_assert_eq!(resolve_target(dir.path(), &list[0].name), dir.path().join(name));
+            prop_assert!(list[0].path.starts_with(dir.path()));
+        }
+    }
+}
@@ -0,0 +1,49 @@
+use fileZoom::app::{App, StartOptions};
+use fileZoom::input::{Key, KeyCode, KeyModifiers};
+use fileZoom::runner::handlers::handle_key;
+use fileZoom::ui::layout::ScreenLayout;
+use fileZoom::Side;
+use ratatui::{backend::TestBackend, layout::Rect, Terminal};
+
+fn text_in(terminal: &mut Terminal<TestBackend>, app: &App, area: Rect) -> String {
+    terminal.draw(|f| fileZoom::ui::ui(f, app)).unwrap();
+    let buf = terminal.backend().buffer();
+    (area.y..area.y + area.height)
+        .flat_map(|y| (area.x..area.x + area.width).map(move |x| (x, y)))
+        .map(|(x, y)| buf[(x, y)].symbol())
+        .collect()
+}
+
+#[test]
+fn quick_view_previews_the_selection_in_the_other_panel() {
+    let tmp = tempfile::tempdir().unwrap();
+    std::fs::write(tmp.path().join("a.txt"), "alpha contents").unwrap();
+    std::fs::write(tmp.path().join("b.txt"), "bravo contents").unwrap();
+    let opts = StartOptions { start_dir: Some(tmp.path().to_path_buf()), ..Default::default() };
+    let mut app = App::with_options(&opts).unwrap();
+    assert!(app.left.select_named("a.txt"));
+    app.update_preview_for(Side::Left);
+    let area = Rect::new(0, 0, 80, 24);
+    let mut terminal = Terminal::new(TestBackend::new(area.width, area.height)).unwrap();
+
+    let ctrl_q = Key { code: KeyCode::Char('q'), modifiers: KeyModifiers { ctrl: true, ..Default::default() } };
+    assert!(fileZoom::app::settings::keybinds::is_quick_view(&ctrl_q));
+    app.toggle_quick_view();
+    let layout = ScreenLayout::compute(&app, area);
+    let preview = layout.preview.expect("quick view");
+    assert_eq!((preview.x, layout.right.area()), (40, 0));
+    assert!(text_in(&mut terminal, &app, preview).contains("alpha contents"));
+
+    // The preview follows the selection.
+    handle_key(&mut app, KeyCode::Down, 10).unwrap();
+    assert!(text_in(&mut terminal, &app, preview).contains("bravo contents"));
+
+    // Tab focuses the preview instead of switching panels.
+    handle_key(&mut app, KeyCode::Tab, 10).unwrap();
+    assert_eq!((app.active, app.preview_focused), (Side::Left, true));
+    handle_key(&mut app, KeyCode::Tab, 10).unwrap();
+    assert!(!app.preview_focused);
+
+    app.toggle_quick_view();
+    assert_eq!(ScreenLayout::compute(&app, area).preview, None);
+}
@@ -61,7 +61,7 @@ fn copy_move_rename_delete_actions_work() -> Result<(), Box<dyn std::error::Erro
     let idx = match find_index(&app, "src.txt") {
         Some(i) => i,
         None => {
-            let names: Vec<String> = app.left.entries.iter().map(|e| e.name.clone()).collect();
+            let names: Vec<String> = app.left.entries.iter().map(|e| e.display_name().into_owned()).collect();
             panic!("src.txt entry not found, entries={:?}", names);
         }
     };
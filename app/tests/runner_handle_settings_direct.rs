@@ -1,7 +1,9 @@
 use fileZoom::app::core::App;
+use fileZoom::app::settings::Settings;
 use fileZoom::app::Mode;
 use fileZoom::input::KeyCode;
 use fileZoom::runner::handlers::handle_settings;
+use fileZoom::runner::handlers::settings::{copy_buffer_label, SETTINGS_FIELDS};
 
 /// Integration test that exercises `handle_settings` directly (keyboard
 /// flow) by constructing an `App`, entering Settings mode and simulating
@@ -36,13 +38,85 @@ fn settings_handle_settings_direct() {
     handle_settings(&mut app, KeyCode::Right).unwrap();
     assert_eq!(app.settings.mouse_double_click_ms, (before + 50).min(5000));
 
-    // Move to Save and press Enter; there is now an extra field (Show CLI listing),
-    // so move down twice to land on Save and then press Enter; expect a Message modal announcing save
-    handle_settings(&mut app, KeyCode::Down).unwrap();
-    handle_settings(&mut app, KeyCode::Down).unwrap();
+    // Move past the remaining fields to Save and press Enter; expect a
+    // Message modal announcing save
+    for _ in 1..SETTINGS_FIELDS {
+        handle_settings(&mut app, KeyCode::Down).unwrap();
+    }
     handle_settings(&mut app, KeyCode::Enter).unwrap();
     match &app.mode {
         Mode::Message { title, .. } => assert_eq!(title, "Settings Saved"),
         _ => panic!("Expected Message after saving settings"),
     }
 }
+
+#[test]
+fn confirmation_rows_toggle_their_settings() {
+    let mut app = App::new().unwrap();
+    let confirms = |s: &Settings| [s.confirm_delete, s.confirm_overwrite, s.confirm_cross_fs_move, s.confirm_quit_with_jobs];
+    for (i, row) in (5..9).enumerate() {
+        app.mode = Mode::Settings { selected: row };
+        let before = confirms(&app.settings);
+        handle_settings(&mut app, KeyCode::Enter).unwrap();
+        let mut toggled = before;
+        toggled[i] = !toggled[i];
+        assert_eq!(confirms(&app.settings), toggled, "row {row}");
+        handle_settings(&mut app, KeyCode::Char(' ')).unwrap();
+        assert_eq!(confirms(&app.settings), before, "row {row}");
+    }
+}
+
+#[test]
+fn job_queue_rows_change_the_job_slots() {
+    let mut app = App::new().unwrap();
+    app.settings.max_concurrent_jobs = 2;
+    app.mode = Mode::Settings { selected: 10 };
+    handle_settings(&mut app, KeyCode::Right).unwrap();
+    handle_settings(&mut app, KeyCode::Char('+')).unwrap();
+    assert_eq!(app.settings.job_slots(), 4);
+    for _ in 0..10 {
+        handle_settings(&mut app, KeyCode::Left).unwrap();
+    }
+    assert_eq!(app.settings.max_concurrent_jobs, 1);
+
+    app.settings.max_concurrent_jobs = 3;
+    app.mode = Mode::Settings { selected: 9 };
+    handle_settings(&mut app, KeyCode::Enter).unwrap();
+    assert!(app.settings.sequential_jobs);
+    assert_eq!(app.settings.job_slots(), 1);
+}
+
+#[test]
+fn copy_buffer_row_cycles_the_offered_sizes() {
+    let mut app = App::new().unwrap();
+    app.settings.copy_buffer_kib = 0;
+    app.mode = Mode::Settings { selected: 11 };
+    let mut seen = Vec::new();
+    for _ in 0..5 {
+        handle_settings(&mut app, KeyCode::Enter).unwrap();
+        seen.push(copy_buffer_label(app.settings.copy_buffer_kib));
+    }
+    assert_eq!(seen, ["64K", "256K", "1M", "4M", "Auto"]);
+
+    // A size only the settings file can set goes back to Auto.
+    app.settings.copy_buffer_kib = 128;
+    handle_settings(&mut app, KeyCode::Enter).unwrap();
+    assert_eq!(app.settings.copy_buffer_kib, 0);
+}
+
+#[test]
+fn colour_depth_row_overrides_the_detected_depth() {
+    use fileZoom::ui::colors::{color_depth, ColorDepth};
+    let mut app = App::new().unwrap();
+    app.settings.color_depth = "auto".to_string();
+    app.mode = Mode::Settings { selected: 12 };
+    handle_settings(&mut app, KeyCode::Enter).unwrap();
+    assert_eq!((app.settings.color_depth.as_str(), color_depth()), ("truecolor", ColorDepth::TrueColor));
+    handle_settings(&mut app, KeyCode::Enter).unwrap();
+    assert_eq!((app.settings.color_depth.as_str(), color_depth()), ("256", ColorDepth::Ansi256));
+    handle_settings(&mut app, KeyCode::Enter).unwrap();
+    assert_eq!((app.settings.color_depth.as_str(), color_depth()), ("16", ColorDepth::Ansi16));
+    handle_settings(&mut app, KeyCode::Enter).unwrap();
+    assert_eq!(app.settings.color_depth, "auto");
+    fileZoom::ui::colors::set_color_depth(ColorDepth::TrueColor);
+}
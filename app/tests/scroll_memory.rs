@@ -0,0 +1,42 @@
+use fileZoom::app::{App, StartOptions};
+
+fn selected_name(app: &App) -> String {
+    app.left.selected_entry().map(|e| e.name.to_string_lossy().into_owned()).unwrap_or_default()
+}
+
+#[test]
+fn revisited_directories_keep_their_cursor() {
+    let tmp = tempfile::tempdir().unwrap();
+    for (dir, prefix) in [("one", "x"), ("two", "y")] {
+        std::fs::create_dir(tmp.path().join(dir)).unwrap();
+        for i in 1..=5 {
+            std::fs::write(tmp.path().join(dir).join(format!("{}{}", prefix, i)), "").unwrap();
+        }
+    }
+    let opts = StartOptions { start_dir: Some(tmp.path().to_path_buf()), ..Default::default() };
+    let mut app = App::with_options(&opts).unwrap();
+
+    assert!(app.left.select_named("one"));
+    app.enter().unwrap();
+    assert!(app.left.select_named("x4"));
+    app.left.offset = 2;
+    app.go_up().unwrap();
+    assert_eq!(selected_name(&app), "one");
+
+    assert!(app.left.select_named("two"));
+    app.enter().unwrap();
+    assert!(app.left.select_named("y2"));
+    app.go_up().unwrap();
+    assert_eq!(selected_name(&app), "two");
+
+    assert!(app.left.select_named("one"));
+    app.enter().unwrap();
+    assert_eq!((selected_name(&app).as_str(), app.left.offset), ("x4", 2));
+
+    // An entry that disappeared leaves the cursor on the same row.
+    std::fs::remove_file(tmp.path().join("two/y2")).unwrap();
+    app.go_up().unwrap();
+    assert!(app.left.select_named("two"));
+    app.enter().unwrap();
+    assert_eq!(selected_name(&app), "y3");
+}
@@ -0,0 +1,119 @@
+use fileZoom::app::{App, Mode, StartOptions};
+use fileZoom::input::KeyCode;
+use fileZoom::runner::commands::execute_command;
+use fileZoom::runner::handlers::handle_key;
+
+fn names(app: &App) -> Vec<String> {
+    app.left.entries.iter().map(|e| e.display_name().into_owned()).collect()
+}
+
+fn type_line(app: &mut App, text: &str) {
+    for c in text.chars() {
+        handle_key(app, KeyCode::Char(c), 10).unwrap();
+    }
+    handle_key(app, KeyCode::Enter, 10).unwrap();
+}
+
+#[test]
+fn search_results_list_as_a_panel_of_real_files() {
+    let tmp = tempfile::tempdir().unwrap();
+    let root = tmp.path().to_path_buf();
+    std::fs::create_dir_all(root.join("src/deep")).unwrap();
+    std::fs::write(root.join("src/deep/report.log"), "x").unwrap();
+    std::fs::write(root.join("src/build.log"), "x").unwrap();
+    std::fs::write(root.join("notes.txt"), "x").unwrap();
+    let mut app = App::with_options(&StartOptions { start_dir: Some(root.clone()), ..Default::default() }).unwrap();
+
+    handle_key(&mut app, KeyCode::Char('/'), 10).unwrap();
+    assert!(matches!(app.mode, Mode::Input { .. }));
+    type_line(&mut app, "*.log");
+    assert!(app.left.search_results().is_some());
+    assert_eq!(names(&app), ["src/build.log", "src/deep/report.log"]);
+
+    // Deleting a result deletes the file it stands for.
+    assert!(app.left.select_named("src/build.log"));
+    app.delete_selected().unwrap();
+    assert!(!root.join("src/build.log").exists());
+    assert_eq!(names(&app), ["src/deep/report.log"]);
+
+    // `r` searches again and finds files created since.
+    std::fs::write(root.join("late.log"), "x").unwrap();
+    handle_key(&mut app, KeyCode::Char('r'), 10).unwrap();
+    assert_eq!(names(&app), ["late.log", "src/deep/report.log"]);
+
+    // `/` offers the query for refining and searches the same directory.
+    handle_key(&mut app, KeyCode::Char('/'), 10).unwrap();
+    assert!(matches!(&app.mode, Mode::Input { buffer, .. } if buffer == "*.log"));
+    for _ in 0.."*.log".len() {
+        handle_key(&mut app, KeyCode::Backspace, 10).unwrap();
+    }
+    type_line(&mut app, "report");
+    assert_eq!(app.left.cwd, root);
+    assert_eq!(names(&app), ["src/deep/report.log"]);
+
+    // Backspace leaves the results for the searched directory.
+    handle_key(&mut app, KeyCode::Backspace, 10).unwrap();
+    assert!(app.left.search.is_none());
+    assert_eq!(app.left.cwd, root);
+    assert_eq!(names(&app), ["src", "late.log", "notes.txt"]);
+}
+
+#[test]
+fn find_command_searches_without_the_prompt() {
+    let tmp = tempfile::tempdir().unwrap();
+    std::fs::create_dir_all(tmp.path().join("a/b")).unwrap();
+    std::fs::write(tmp.path().join("a/b/needle.txt"), "x").unwrap();
+    let mut app = App::with_options(&StartOptions { start_dir: Some(tmp.path().to_path_buf()), ..Default::default() }).unwrap();
+    execute_command(&mut app, "find NEEDLE").unwrap();
+    assert_eq!(names(&app), ["a/b/needle.txt"]);
+}
+
+#[test]
+fn searches_are_remembered_saved_and_run_again() {
+    let tmp = tempfile::tempdir().unwrap();
+    let root = tmp.path().to_path_buf();
+    std::fs::create_dir_all(root.join("src")).unwrap();
+    std::fs::write(root.join("src/Makefile"), "x").unwrap();
+    std::fs::write(root.join("src/make.rs"), "x").unwrap();
+    let mut app = App::with_options(&StartOptions { start_dir: Some(root.clone()), ..Default::default() }).unwrap();
+
+    execute_command(&mut app, "find -r -c ^Make").unwrap();
+    assert_eq!(names(&app), ["src/Makefile"]);
+    execute_command(&mut app, "save-search makefiles").unwrap();
+    execute_command(&mut app, "find make").unwrap();
+    assert_eq!(names(&app).len(), 2);
+    assert_eq!(app.searches.recent.len(), 2);
+
+    // The picker lists the saved search first and runs it with its options.
+    handle_key(&mut app, KeyCode::Backspace, 10).unwrap();
+    execute_command(&mut app, "searches").unwrap();
+    assert!(matches!(app.mode, Mode::SearchPicker { selected: 0 }));
+    handle_key(&mut app, KeyCode::Enter, 10).unwrap();
+    assert_eq!(names(&app), ["src/Makefile"]);
+    assert_eq!(app.searches.recent.last().map(|o| o.query()).as_deref(), Some("-r -c ^Make"));
+
+    // An invalid regex is reported instead of searched for.
+    execute_command(&mut app, "find -r (").unwrap();
+    assert!(matches!(&app.mode, Mode::Message { title, .. } if title == "Error"));
+}
+
+#[test]
+fn tab_switches_the_match_mode_and_matches_are_highlighted() {
+    let tmp = tempfile::tempdir().unwrap();
+    std::fs::create_dir_all(tmp.path().join("src")).unwrap();
+    std::fs::write(tmp.path().join("src/file_list.rs"), "x").unwrap();
+    std::fs::write(tmp.path().join("src/flat.rs"), "x").unwrap();
+    let mut app = App::with_options(&StartOptions { start_dir: Some(tmp.path().to_path_buf()), ..Default::default() }).unwrap();
+    app.settings.icons = false;
+
+    handle_key(&mut app, KeyCode::Char('/'), 10).unwrap();
+    handle_key(&mut app, KeyCode::Tab, 10).unwrap();
+    handle_key(&mut app, KeyCode::Tab, 10).unwrap();
+    assert!(matches!(&app.mode, Mode::Input { prompt, buffer, .. } if buffer == "-f " && prompt.contains("[fuzzy]")));
+    type_line(&mut app, "fli");
+    assert_eq!(names(&app), ["src/file_list.rs"]);
+
+    // `fli` is placed on the word starts of `file_list`, after `src/`.
+    let state = fileZoom::ui::ui_state::UIState::from_core(&app);
+    assert_eq!(state.left_matches, [vec![4, 9, 10]]);
+}
@@ -0,0 +1,46 @@
+use fileZoom::app::{App, Mode, StartOptions};
+use fileZoom::input::KeyCode;
+use fileZoom::runner::commands::execute_command;
+use fileZoom::runner::handlers::handle_key;
+use std::time::{Duration, SystemTime};
+
+fn marked(app: &App) -> Vec<String> {
+    let mut names: Vec<String> = app.left.selections.iter().map(|&i| app.left.entries[i].display_name().into_owned()).collect();
+    names.sort();
+    names
+}
+
+#[test]
+fn marks_files_by_age_and_size() {
+    let tmp = tempfile::tempdir().unwrap();
+    let month_ago = SystemTime::now() - Duration::from_secs(40 * 86_400);
+    for (name, size, old) in [("old.log", 10, true), ("old_big.iso", 3 << 20, true), ("new.txt", 10, false)] {
+        let path = tmp.path().join(name);
+        std::fs::write(&path, vec![0u8; size]).unwrap();
+        if old {
+            std::fs::File::options().write(true).open(&path).unwrap().set_modified(month_ago).unwrap();
+        }
+    }
+    std::fs::create_dir(tmp.path().join("dir")).unwrap();
+    let mut app = App::with_options(&StartOptions { start_dir: Some(tmp.path().to_path_buf()), ..Default::default() }).unwrap();
+
+    handle_key(&mut app, KeyCode::Char('+'), 10).unwrap();
+    assert!(matches!(app.mode, Mode::Input { .. }));
+    for c in "older 30d".chars() {
+        handle_key(&mut app, KeyCode::Char(c), 10).unwrap();
+    }
+    handle_key(&mut app, KeyCode::Enter, 10).unwrap();
+    assert_eq!(marked(&app), ["old.log", "old_big.iso"]);
+
+    // Criteria combine, and replace the marks.
+    execute_command(&mut app, "select older 30d size >1M").unwrap();
+    assert_eq!(marked(&app), ["old_big.iso"]);
+    execute_command(&mut app, "select <1k").unwrap();
+    assert_eq!(marked(&app), ["new.txt", "old.log"]);
+
+    execute_command(&mut app, "select >1T").unwrap();
+    assert!(matches!(&app.mode, Mode::Message { title, .. } if title == "Select"));
+    app.mode = Mode::Normal;
+    execute_command(&mut app, "select bigger").unwrap();
+    assert!(matches!(&app.mode, Mode::Message { title, .. } if title == "Error"));
+}
@@ -0,0 +1,40 @@
+use fileZoom::app::core::App;
+use fileZoom::app::settings::session::{load_session, save_session, Session};
+use fileZoom::app::types::{PanelLayout, SortKey, SortOrder};
+use fileZoom::app::StartOptions;
+use fileZoom::Side;
+
+#[test]
+fn session_round_trips_directories_selection_sort_and_layout() {
+    let tmp = tempfile::tempdir().unwrap();
+    std::env::set_var("XDG_CONFIG_HOME", tmp.path().join("config"));
+    let left = tmp.path().join("left");
+    let right = tmp.path().join("right");
+    std::fs::create_dir_all(&left).unwrap();
+    std::fs::create_dir_all(&right).unwrap();
+    for name in ["a.txt", "b.txt", "c.txt"] {
+        std::fs::write(right.join(name), name).unwrap();
+    }
+    assert_eq!(load_session().unwrap(), None);
+
+    let opts = StartOptions { start_dir: Some(left.clone()), ..Default::default() };
+    let mut app = App::with_options(&opts).unwrap();
+    app.right.cwd = right.clone();
+    app.sort_order = SortOrder::Descending;
+    app.active = Side::Right;
+    app.layout = PanelLayout::Vertical;
+    app.refresh().unwrap();
+    assert!(app.right.select_named("b.txt"));
+    save_session(&Session::capture(&app)).unwrap();
+
+    let mut restored = App::with_options(&StartOptions { start_dir: Some(tmp.path().to_path_buf()), ..Default::default() }).unwrap();
+    load_session().unwrap().expect("saved session").apply(&mut restored).unwrap();
+    assert_eq!(restored.left.cwd, left);
+    assert_eq!(restored.right.cwd, right);
+    assert_eq!(restored.active, Side::Right);
+    assert_eq!(restored.sort, SortKey::Name);
+    assert_eq!(restored.sort_order, SortOrder::Descending);
+    assert_eq!(restored.layout, PanelLayout::Vertical);
+    assert_eq!(restored.right.selected_entry().unwrap().name, "b.txt");
+    assert_eq!(restored.right.entries[0].name, "c.txt");
+}
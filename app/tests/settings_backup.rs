@@ -0,0 +1,38 @@
+use fileZoom::app::core::App;
+use fileZoom::app::settings::{backup, save_settings, Settings};
+use fileZoom::app::Mode;
+use fileZoom::input::KeyCode;
+use fileZoom::runner::handlers::handle_settings;
+use fileZoom::runner::handlers::settings::SETTINGS_FIELDS;
+
+#[test]
+fn restore_button_brings_back_the_previous_settings() {
+    let tmp = tempfile::tempdir().unwrap();
+    std::env::set_var("XDG_CONFIG_HOME", tmp.path());
+
+    let first = Settings { mouse_double_click_ms: 300, ..Settings::default() };
+    save_settings(&first).unwrap();
+    save_settings(&Settings { mouse_double_click_ms: 900, ..Settings::default() }).unwrap();
+    let path = fileZoom::app::settings::write_settings::config_file_path().unwrap();
+    assert_eq!(backup::list_backups(&path).unwrap().len(), 1);
+
+    let mut app = App::new().unwrap();
+    app.mode = Mode::Settings { selected: 0 };
+    for _ in 0..SETTINGS_FIELDS + 1 {
+        handle_settings(&mut app, KeyCode::Down).unwrap();
+    }
+    handle_settings(&mut app, KeyCode::Enter).unwrap();
+    match &app.mode {
+        Mode::Message { title, .. } => assert_eq!(title, "Settings Restored"),
+        _ => panic!("Expected Message after restoring settings"),
+    }
+    assert_eq!(app.settings, first);
+
+    // The only backup was consumed; a second restore reports that.
+    app.mode = Mode::Settings { selected: SETTINGS_FIELDS + 1 };
+    handle_settings(&mut app, KeyCode::Enter).unwrap();
+    match &app.mode {
+        Mode::Message { content, .. } => assert!(content.contains("No configuration backup")),
+        _ => panic!("Expected Message"),
+    }
+}
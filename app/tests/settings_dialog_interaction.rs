@@ -2,6 +2,7 @@ use fileZoom::app::core::App;
 use fileZoom::input::KeyCode;
 use fileZoom::runner::handlers;
 use fileZoom::runner::handlers::mouse::handle_mouse;
+use fileZoom::runner::handlers::settings::{SETTINGS_FIELDS, SETTINGS_HEIGHT};
 use ratatui::layout::Rect;
 
 #[test]
@@ -33,9 +34,10 @@ fn settings_keyboard_interaction() {
     handlers::handle_key(&mut app, KeyCode::Right, 10).unwrap();
     assert_eq!(app.settings.mouse_double_click_ms, (before + 50).min(5000));
 
-    // Move to Save and press Enter (there's an extra field now, so move down twice)
-    handlers::handle_key(&mut app, KeyCode::Down, 10).unwrap();
-    handlers::handle_key(&mut app, KeyCode::Down, 10).unwrap();
+    // Move past the remaining fields to Save and press Enter
+    for _ in 1..SETTINGS_FIELDS {
+        handlers::handle_key(&mut app, KeyCode::Down, 10).unwrap();
+    }
     handlers::handle_key(&mut app, KeyCode::Enter, 10).unwrap();
     match &app.mode {
         fileZoom::app::Mode::Message { title, .. } => {
@@ -57,7 +59,7 @@ fn settings_mouse_click_toggle_and_save() {
     app.menu_activate();
 
     let area = Rect::new(0, 0, 80, 24);
-    let rect = fileZoom::ui::modal::centered_rect(area, 60, 10);
+    let rect = fileZoom::ui::modal::centered_rect(area, 60, SETTINGS_HEIGHT);
 
     // Click the first content line (mouse_enabled)
     let me = fileZoom::input::mouse::MouseEvent {
@@ -0,0 +1,63 @@
+//! The Keys page of the Settings dialog writes `keymap.toml` and reloads
+//! the global key table, so this test lives in its own test binary.
+
+use fileZoom::app::settings::keymap::{keymap_file_path, KeyChord};
+use fileZoom::app::settings::runtime_keybinds;
+use fileZoom::app::{App, Mode, StartOptions};
+use fileZoom::input::{Key, KeyCode, KeyModifiers};
+use fileZoom::runner::handlers::{handle_key, keymap_editor::capture_key};
+
+mod common;
+use common::screen;
+
+#[test]
+fn rebinding_an_action_saves_the_keymap() {
+    let cfg = tempfile::tempdir().unwrap();
+    std::env::set_var("XDG_CONFIG_HOME", cfg.path());
+    let tmp = tempfile::tempdir().unwrap();
+    let opts = StartOptions { start_dir: Some(tmp.path().to_path_buf()), ..Default::default() };
+    let mut app = App::with_options(&opts).unwrap();
+
+    // Tab moves from the General page to the Keys page and back.
+    app.mode = Mode::Settings { selected: 0 };
+    handle_key(&mut app, KeyCode::Tab, 10).unwrap();
+    assert!(matches!(app.mode, Mode::KeymapEditor { .. }));
+    assert!(screen(&app).contains("Description"));
+    handle_key(&mut app, KeyCode::Tab, 10).unwrap();
+    assert!(matches!(app.mode, Mode::Settings { selected: 0 }));
+    handle_key(&mut app, KeyCode::Tab, 10).unwrap();
+
+    // Select `refresh` and bind Ctrl+R to it.
+    let refresh = fileZoom::app::settings::keymap::ACTIONS.iter().position(|(a, _)| *a == "refresh").unwrap();
+    for _ in 0..refresh {
+        handle_key(&mut app, KeyCode::Down, 10).unwrap();
+    }
+    handle_key(&mut app, KeyCode::Enter, 10).unwrap();
+    assert!(screen(&app).contains("<press a key>"));
+    let ctrl_r = Key { code: KeyCode::Char('r'), modifiers: KeyModifiers { ctrl: true, ..Default::default() } };
+    assert!(capture_key(&mut app, &ctrl_r));
+    assert!(!capture_key(&mut app, &ctrl_r));
+
+    // Binding `c` asks before taking it from `copy`; Esc keeps Ctrl+R.
+    handle_key(&mut app, KeyCode::Enter, 10).unwrap();
+    handle_key(&mut app, KeyCode::Char('c'), 10).unwrap();
+    assert!(screen(&app).contains("is bound to `copy`"));
+    handle_key(&mut app, KeyCode::Esc, 10).unwrap();
+    assert!(matches!(app.mode, Mode::KeymapEditor { .. }));
+
+    // Up from the first action wraps to Cancel; two more reach Save.
+    for _ in 0..refresh + 3 {
+        handle_key(&mut app, KeyCode::Up, 10).unwrap();
+    }
+    handle_key(&mut app, KeyCode::Enter, 10).unwrap();
+    match &app.mode {
+        Mode::Message { title, .. } => assert_eq!(title, "Keys Saved"),
+        other => panic!("expected a message, got {:?}", other),
+    }
+
+    let written = std::fs::read_to_string(keymap_file_path()).unwrap();
+    assert!(written.contains("refresh = [\"Ctrl+r\"]"), "{}", written);
+    assert!(!written.contains("copy"), "{}", written);
+    assert_eq!(runtime_keybinds::get().chords("refresh"), &[KeyChord::ctrl(KeyCode::Char('r'))]);
+    assert!(runtime_keybinds::get().keymap_error().is_none());
+}
@@ -22,6 +22,29 @@ fn save_and_load_settings_roundtrip() {
         mouse_enabled: true,
         mouse_double_click_ms: 500,
         prefer_integrated_vim: false,
+        warn_open_files: false,
+        copy_buffer_kib: 256,
+        keybinding_preset: "vim".into(),
+        restore_session: false,
+        single_pane: true,
+        key_sequence_timeout_ms: 750,
+        color_depth: "256".into(),
+        icons: true,
+        ls_colors: false,
+        show_clock: true,
+        preview_width: 30,
+        verify_after_copy: true,
+        hide_gitignored: true,
+        max_fps: 10,
+        confirm_delete: false,
+        confirm_overwrite: false,
+        confirm_cross_fs_move: true,
+        confirm_quit_with_jobs: false,
+        max_concurrent_jobs: 3,
+        sequential_jobs: true,
+        notify_after_secs: 0,
+        ipc_server: false,
+        create_dest_dirs: false,
     };
 
     save_settings(&s).expect("save should succeed");
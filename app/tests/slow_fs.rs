@@ -0,0 +1,120 @@
+//! UI responsiveness on a slow filesystem, using the `test-helpers`
+//! latency-injecting `SlowFs` provider.
+//! Run with `cargo test --features test-helpers --test slow_fs`.
+#![cfg(feature = "test-helpers")]
+
+use std::fs;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use fileZoom::app::{App, Mode, StartOptions};
+use fileZoom::fs_op::vfs::VfsProvider;
+use fileZoom::input::KeyCode;
+use fileZoom::runner::handlers::handle_key;
+use fileZoom::test_helpers::SlowFs;
+use ratatui::{backend::TestBackend, Terminal};
+
+fn draw(terminal: &mut Terminal<TestBackend>, app: &App) -> String {
+    terminal.draw(|f| fileZoom::ui::ui(f, app)).unwrap();
+    terminal.backend().buffer().content().iter().map(|c| c.symbol()).collect()
+}
+
+/// Run one event-loop tick: pick up background work and draw a frame.
+/// Returns how long the frame took.
+fn tick(terminal: &mut Terminal<TestBackend>, app: &mut App) -> Duration {
+    let started = Instant::now();
+    app.poll_progress();
+    app.poll_listings();
+    draw(terminal, app);
+    started.elapsed()
+}
+
+#[test]
+fn slow_listing_keeps_drawing_and_can_be_cancelled() {
+    let tmp = tempfile::tempdir().unwrap();
+    fs::create_dir(tmp.path().join("sub")).unwrap();
+    fs::write(tmp.path().join("sub/inner.txt"), "x").unwrap();
+    let opts = StartOptions { start_dir: Some(tmp.path().to_path_buf()), ..Default::default() };
+    let mut app = App::with_options(&opts).unwrap();
+    let slow = SlowFs::local(Duration::from_millis(800));
+    app.left.vfs = slow.clone() as Arc<dyn VfsProvider>;
+    let mut terminal = Terminal::new(TestBackend::new(100, 30)).unwrap();
+
+    // Entering the directory returns long before the listing finishes.
+    assert!(app.left.select_named("sub"));
+    let started = Instant::now();
+    handle_key(&mut app, KeyCode::Enter, 10).unwrap();
+    assert!(started.elapsed() < Duration::from_millis(600), "{:?}", started.elapsed());
+    assert!(app.left.loading.is_some());
+    assert!(draw(&mut terminal, &app).contains("Loading"));
+    assert!(tick(&mut terminal, &mut app) < Duration::from_millis(100));
+
+    // Other keys wait; Esc goes back to the previous directory.
+    handle_key(&mut app, KeyCode::Down, 10).unwrap();
+    handle_key(&mut app, KeyCode::Esc, 10).unwrap();
+    assert!(app.left.loading.is_none());
+    assert_eq!(app.left.cwd, tmp.path());
+    assert!(app.left.select_named("sub"));
+
+    // Left alone, the listing arrives while frames keep being drawn.
+    slow.set_delay(Duration::from_millis(400));
+    handle_key(&mut app, KeyCode::Enter, 10).unwrap();
+    let deadline = Instant::now() + Duration::from_secs(10);
+    let mut frames = 0;
+    while app.left.loading.is_some() {
+        assert!(Instant::now() < deadline, "listing never finished");
+        assert!(tick(&mut terminal, &mut app) < Duration::from_millis(100));
+        frames += 1;
+        std::thread::sleep(Duration::from_millis(20));
+    }
+    assert!(frames > 1);
+    assert_eq!(app.left.cwd, tmp.path().join("sub"));
+    assert_eq!(app.left.entries.iter().map(|e| e.name.to_str().unwrap()).collect::<Vec<_>>(), vec!["inner.txt"]);
+
+    // The preview of the selected entry follows in the background too.
+    while app.left.preview_loading.is_some() {
+        assert!(Instant::now() < deadline, "preview never finished");
+        assert!(tick(&mut terminal, &mut app) < Duration::from_millis(100));
+        std::thread::sleep(Duration::from_millis(20));
+    }
+    assert!(!app.left.preview.starts_with("Loading preview"));
+}
+
+#[test]
+fn slow_copy_keeps_drawing_and_can_be_cancelled() {
+    let cfg = tempfile::tempdir().unwrap();
+    std::env::set_var("XDG_CONFIG_HOME", cfg.path());
+    let src = tempfile::tempdir().unwrap();
+    let dst = tempfile::tempdir().unwrap();
+    for i in 0..8 {
+        fs::write(src.path().join(format!("f{}.txt", i)), "data").unwrap();
+    }
+    let opts = StartOptions { start_dir: Some(src.path().to_path_buf()), right_dir: Some(dst.path().to_path_buf()), ..Default::default() };
+    let mut app = App::with_options(&opts).unwrap();
+    app.left.selections = (0..app.left.entries.len()).collect();
+    app.left.vfs = SlowFs::local(Duration::from_millis(150)) as Arc<dyn VfsProvider>;
+    let mut terminal = Terminal::new(TestBackend::new(100, 30)).unwrap();
+
+    let started = Instant::now();
+    handle_key(&mut app, KeyCode::F(5), 10).unwrap();
+    assert!(started.elapsed() < Duration::from_millis(100));
+    assert!(matches!(app.mode, Mode::Progress { .. }));
+    for _ in 0..20 {
+        assert!(tick(&mut terminal, &mut app) < Duration::from_millis(100));
+        std::thread::sleep(Duration::from_millis(30));
+    }
+
+    handle_key(&mut app, KeyCode::Esc, 10).unwrap();
+    let deadline = Instant::now() + Duration::from_secs(10);
+    while !matches!(app.mode, Mode::Message { .. }) {
+        assert!(Instant::now() < deadline, "copy never stopped");
+        tick(&mut terminal, &mut app);
+        std::thread::sleep(Duration::from_millis(20));
+    }
+    match &app.mode {
+        Mode::Message { content, .. } => assert!(content.contains("Cancelled"), "{}", content),
+        _ => unreachable!(),
+    }
+    let copied = fs::read_dir(dst.path()).unwrap().count();
+    assert!(copied > 0 && copied < 8, "copied {} of 8", copied);
+}
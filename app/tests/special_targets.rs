@@ -0,0 +1,49 @@
+#![cfg(unix)]
+
+use fileZoom::app::{App, Mode, StartOptions};
+use fileZoom::fs_op::app_ops::SpecialTarget;
+use fileZoom::input::KeyCode;
+use fileZoom::runner::handlers::handle_key;
+
+#[test]
+fn deleting_a_directory_symlink_asks_link_or_target() {
+    let tmp = tempfile::tempdir().unwrap();
+    let real = tmp.path().join("real");
+    std::fs::create_dir(&real).unwrap();
+    std::fs::write(real.join("keep.txt"), "x").unwrap();
+    let link = tmp.path().join("link");
+    std::os::unix::fs::symlink("real", &link).unwrap();
+    assert_eq!(SpecialTarget::of(&link), Some(SpecialTarget::DirSymlink(real.clone())));
+    assert_eq!(SpecialTarget::of(&real), None);
+
+    let opts = StartOptions { start_dir: Some(tmp.path().to_path_buf()), ..Default::default() };
+    let mut app = App::with_options(&opts).unwrap();
+
+    // "Delete link only" is the default button.
+    assert!(app.left.select_named("link"));
+    handle_key(&mut app, KeyCode::Char('d'), 10).unwrap();
+    match &app.mode {
+        Mode::Message { content, buttons, .. } => {
+            assert!(content.contains("is a symlink to"), "{content}");
+            assert_eq!(buttons, &["Delete link only", "Delete link and target", "Cancel"]);
+        }
+        other => panic!("expected the symlink dialog, got {:?}", other),
+    }
+    handle_key(&mut app, KeyCode::Enter, 10).unwrap();
+    assert!(!link.exists() && real.join("keep.txt").exists());
+
+    // The second button removes the directory it points to as well.
+    std::os::unix::fs::symlink("real", &link).unwrap();
+    app.refresh().unwrap();
+    assert!(app.left.select_named("link"));
+    handle_key(&mut app, KeyCode::Char('d'), 10).unwrap();
+    handle_key(&mut app, KeyCode::Right, 10).unwrap();
+    handle_key(&mut app, KeyCode::Enter, 10).unwrap();
+    assert!(std::fs::symlink_metadata(&link).is_err() && !real.exists());
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn proc_is_a_mount_point() {
+    assert_eq!(SpecialTarget::of(std::path::Path::new("/proc")), Some(SpecialTarget::MountPoint));
+}
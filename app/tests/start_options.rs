@@ -14,6 +14,12 @@ fn app_with_options_applies_settings() -> Result<()> {
         theme: Some("dark".to_string()),
         show_hidden: Some(true),
         verbosity: Some(2),
+        right_dir: None,
+        single_pane: None,
+        config_path: None,
+        profile_startup: false,
+        reveal: None,
+        choose_files: false,
     };
 
     let app = fileZoom::app::App::with_options(&opts)?;
@@ -0,0 +1,34 @@
+use fileZoom::app::core::App;
+use fileZoom::app::settings::session::Session;
+use fileZoom::app::StartOptions;
+
+#[test]
+fn restored_session_is_listed_in_the_background() {
+    let tmp = tempfile::tempdir().unwrap();
+    let right = tmp.path().join("right");
+    std::fs::create_dir_all(&right).unwrap();
+    for name in ["a.txt", "b.txt", "c.txt"] {
+        std::fs::write(right.join(name), name).unwrap();
+    }
+    let opts = StartOptions { start_dir: Some(tmp.path().to_path_buf()), ..Default::default() };
+    let mut saved = App::with_options(&opts).unwrap();
+    saved.right.cwd = right.clone();
+    saved.refresh().unwrap();
+    assert!(saved.right.select_named("b.txt"));
+    let session = Session::capture(&saved);
+
+    let mut app = App::unlisted(&opts).unwrap();
+    assert!(app.left.entries.is_empty() && app.right.entries.is_empty());
+    session.restore(&mut app);
+    app.start_listing().unwrap();
+    let deadline = std::time::Instant::now() + std::time::Duration::from_secs(10);
+    while app.listing_pending() {
+        assert!(std::time::Instant::now() < deadline, "listing did not finish");
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        app.poll_listings();
+    }
+    assert_eq!(app.right.cwd, right);
+    assert_eq!(app.right.entries.len(), 3);
+    assert_eq!(app.right.selected_entry().unwrap().name, "b.txt");
+    assert!(app.left.entries.iter().any(|e| e.name == "right"));
+}
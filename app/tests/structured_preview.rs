@@ -0,0 +1,67 @@
+use ratatui::backend::TestBackend;
+use ratatui::Terminal;
+
+use fileZoom::app::{App, StartOptions};
+use fileZoom::input::KeyCode;
+use fileZoom::runner::handlers::handle_key;
+
+fn app_in(dir: &std::path::Path, select: &str) -> App {
+    let opts = StartOptions { start_dir: Some(dir.to_path_buf()), ..Default::default() };
+    let mut app = App::with_options(&opts).unwrap();
+    assert!(app.left.select_named(select));
+    app.preview_visible = true;
+    app.preview_focused = true;
+    app.update_preview_for(app.active);
+    app
+}
+
+#[test]
+fn json_preview_is_reindented_and_folds() {
+    let tmp = tempfile::tempdir().unwrap();
+    std::fs::write(tmp.path().join("data.json"), r#"{"z":1,"list":[1,2,{"k":"v"}],"ok":true}"#).unwrap();
+    let mut app = app_in(tmp.path(), "data.json");
+    let full = "Format: JSON\n{\n  \"z\": 1,\n  \"list\": [\n    1,\n    2,\n    {\n      \"k\": \"v\"\n    }\n  ],\n  \"ok\": true\n}\n";
+    assert_eq!(app.left.preview, full);
+
+    // `-` folds every inner block, `+` opens them again.
+    handle_key(&mut app, KeyCode::Char('-'), 10).unwrap();
+    assert!(app.left.preview.contains("  \"list\": [ … ],  (5 lines)\n"));
+    handle_key(&mut app, KeyCode::Char('+'), 10).unwrap();
+    assert_eq!(app.left.preview, full);
+
+    // Enter folds the block at the top of the view.
+    app.left.preview_offset = 3;
+    handle_key(&mut app, KeyCode::Enter, 10).unwrap();
+    assert!(app.left.preview.contains("\"list\": [ … ],"));
+    assert_eq!(app.left.preview_offset, 3);
+    handle_key(&mut app, KeyCode::Enter, 10).unwrap();
+    assert_eq!(app.left.preview, full);
+}
+
+#[test]
+fn csv_preview_is_an_aligned_table() {
+    let tmp = tempfile::tempdir().unwrap();
+    std::fs::write(tmp.path().join("people.csv"), "name,city\nAda,\"London, UK\"\nLinus,Helsinki\n").unwrap();
+    let app = app_in(tmp.path(), "people.csv");
+    let lines: Vec<&str> = app.left.preview.lines().collect();
+    assert_eq!(lines[0], "Format: CSV");
+    assert_eq!(lines[1].trim_end(), "name  │ city");
+    assert!(lines[2].starts_with("──────┼─"));
+    assert_eq!(lines[3].trim_end(), "Ada   │ London, UK");
+    assert_eq!(lines[4].trim_end(), "Linus │ Helsinki");
+}
+
+#[test]
+fn markdown_preview_renders_headings_and_bullets() {
+    let tmp = tempfile::tempdir().unwrap();
+    std::fs::write(tmp.path().join("README.md"), "# Title\n\n- one **bold** item\n").unwrap();
+    let app = app_in(tmp.path(), "README.md");
+    let mut terminal = Terminal::new(TestBackend::new(40, 8)).unwrap();
+    terminal.draw(|f| fileZoom::ui::widgets::preview::render_panel(f, f.area(), &app.left, true)).unwrap();
+    let buf = terminal.backend().buffer();
+    let rows: Vec<String> = (0..buf.area.height)
+        .map(|y| (0..buf.area.width).map(|x| buf[(x, y)].symbol()).collect::<String>())
+        .collect();
+    assert!(rows.iter().any(|r| r.starts_with("║Title ")), "{:#?}", rows);
+    assert!(rows.iter().any(|r| r.starts_with("║• one bold item ")), "{:#?}", rows);
+}
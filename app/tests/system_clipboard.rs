@@ -0,0 +1,42 @@
+use fileZoom::app::{App, InputKind, Mode, StartOptions};
+use fileZoom::runner::handlers::clipboard::{paste_into_input, selection_text, CopyWhat};
+use fileZoom::ui::command_line::CommandLineState;
+
+#[test]
+fn selection_text_copies_paths_names_and_contents() {
+    let tmp = tempfile::tempdir().unwrap();
+    let root = tmp.path();
+    std::fs::write(root.join("a.txt"), "hello\n").unwrap();
+    std::fs::write(root.join("b.bin"), [0xff, 0xfe, 0x00]).unwrap();
+    let opts = StartOptions { start_dir: Some(root.to_path_buf()), ..Default::default() };
+    let mut app = App::with_options(&opts).unwrap();
+
+    assert!(app.left.select_named("a.txt"));
+    assert_eq!(selection_text(&app, CopyWhat::Path).unwrap(), root.join("a.txt").display().to_string());
+    assert_eq!(selection_text(&app, CopyWhat::Contents).unwrap(), "hello\n");
+
+    app.left.selections.extend(0..app.left.entries.len());
+    assert_eq!(selection_text(&app, CopyWhat::Name).unwrap(), "a.txt\nb.bin");
+
+    app.left.selections.clear();
+    assert!(app.left.select_named("b.bin"));
+    let err = selection_text(&app, CopyWhat::Contents).unwrap_err().to_string();
+    assert!(err.contains("not a text file"), "{}", err);
+}
+
+#[test]
+fn clipboard_text_is_pasted_into_prompts() {
+    let opts = StartOptions { start_dir: Some(std::env::temp_dir()), ..Default::default() };
+    let mut app = App::with_options(&opts).unwrap();
+    assert!(!paste_into_input(&mut app, "/tmp"));
+
+    app.mode = Mode::Input { prompt: "Copy to:".to_string(), buffer: "x".to_string(), kind: InputKind::Copy };
+    assert!(paste_into_input(&mut app, "/srv/data\r\nsecond line"));
+    assert!(matches!(&app.mode, Mode::Input { buffer, .. } if buffer == "x/srv/data"));
+
+    app.mode = Mode::Normal;
+    app.command_line = Some(CommandLineState { visible: true, buffer: "cd ".to_string(), cursor: 3 });
+    assert!(paste_into_input(&mut app, "/home"));
+    let cmd = app.command_line.as_ref().unwrap();
+    assert_eq!((cmd.buffer.as_str(), cmd.cursor), ("cd /home", 8));
+}
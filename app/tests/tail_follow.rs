@@ -0,0 +1,75 @@
+use std::io::Write;
+use std::time::Instant;
+
+use fileZoom::app::core::follow::{EARLIER_LINES, FOLLOW_POLL};
+use fileZoom::app::{App, StartOptions};
+use fileZoom::input::KeyCode;
+use fileZoom::runner::commands::execute_command;
+use fileZoom::runner::handlers::handle_key;
+
+fn append(path: &std::path::Path, text: &str) {
+    std::fs::OpenOptions::new().append(true).open(path).unwrap().write_all(text.as_bytes()).unwrap();
+}
+
+#[test]
+fn followed_preview_tracks_appends_until_scrolled_up() {
+    let tmp = tempfile::tempdir().unwrap();
+    let log = tmp.path().join("app.log");
+    let lines: String = (1..=50).map(|i| format!("line {}\n", i)).collect();
+    std::fs::write(&log, lines).unwrap();
+    std::fs::write(tmp.path().join("other.txt"), "other\n").unwrap();
+    let opts = StartOptions { start_dir: Some(tmp.path().to_path_buf()), ..Default::default() };
+    let mut app = App::with_options(&opts).unwrap();
+    assert!(app.left.select_named("app.log"));
+    app.preview_visible = true;
+    app.preview_focused = true;
+
+    handle_key(&mut app, KeyCode::Char('F'), 10).unwrap();
+    assert!(app.left.follow.as_ref().is_some_and(|f| f.pinned));
+    assert_eq!(app.left.preview_offset, 49);
+
+    // New lines show up on the next poll, still scrolled to the end.
+    append(&log, "line 51\n");
+    app.poll_follow(Instant::now() + FOLLOW_POLL);
+    assert!(app.left.preview.ends_with("line 51\n"));
+    assert_eq!(app.left.preview_offset, 50);
+
+    // Scrolling up pauses the auto-scroll; End resumes it.
+    handle_key(&mut app, KeyCode::Up, 10).unwrap();
+    assert!(!app.left.follow.as_ref().unwrap().pinned);
+    let offset = app.left.preview_offset;
+    // One line above the last page of 10 of the 51 lines.
+    assert_eq!(offset, 51 - 10 - 1);
+    append(&log, "line 52\n");
+    app.poll_follow(Instant::now() + FOLLOW_POLL * 2);
+    assert!(app.left.preview.ends_with("line 52\n"));
+    assert_eq!(app.left.preview_offset, offset);
+    handle_key(&mut app, KeyCode::End, 10).unwrap();
+    assert!(app.left.follow.as_ref().unwrap().pinned);
+
+    // `:follow` stops following; so does moving to another file.
+    execute_command(&mut app, "follow").unwrap();
+    assert!(app.left.follow.is_none());
+    execute_command(&mut app, "follow").unwrap();
+    assert!(app.left.select_named("other.txt"));
+    app.update_preview_for(fileZoom::Side::Left);
+    assert!(app.left.follow.is_none());
+}
+
+#[test]
+fn followed_preview_shows_the_end_of_large_files() {
+    let tmp = tempfile::tempdir().unwrap();
+    let log = tmp.path().join("big.log");
+    let lines: String = (1..=20_000).map(|i| format!("entry {:05}\n", i)).collect();
+    std::fs::write(&log, lines).unwrap();
+    let opts = StartOptions { start_dir: Some(tmp.path().to_path_buf()), ..Default::default() };
+    let mut app = App::with_options(&opts).unwrap();
+    assert!(app.left.select_named("big.log"));
+
+    app.toggle_follow();
+    let preview = &app.left.preview;
+    assert!(preview.starts_with(&format!("{}\nentry ", EARLIER_LINES)), "{}", &preview[..80]);
+    assert!(preview.ends_with("entry 20000\n"));
+    // Every line after the marker is whole.
+    assert!(preview.lines().skip(1).all(|l| l.len() == "entry 00000".len()));
+}
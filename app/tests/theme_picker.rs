@@ -0,0 +1,57 @@
+//! The theme picker reads user themes from `XDG_CONFIG_HOME` and changes
+//! the global colours, so this test lives in its own test binary.
+
+use fileZoom::app::{App, Mode, StartOptions};
+use fileZoom::input::KeyCode;
+use fileZoom::runner::handlers::handle_key;
+use ratatui::style::Color;
+
+mod common;
+use common::screen;
+
+#[test]
+fn picker_previews_saves_and_cancels() {
+    let cfg = tempfile::tempdir().unwrap();
+    std::env::set_var("XDG_CONFIG_HOME", cfg.path());
+    let themes = fileZoom::ui::themes::user_themes_dir();
+    std::fs::create_dir_all(&themes).unwrap();
+    std::fs::write(themes.join("ocean.toml"), "palette = { bg = \"#002b36\", fg = \"#93a1a1\", accent = \"#268bd2\" }\n").unwrap();
+    std::fs::write(themes.join("broken.toml"), "palette = 3\n").unwrap();
+    std::fs::write(themes.join("dark.toml"), "").unwrap();
+    assert_eq!(fileZoom::ui::themes::theme_names(), ["default", "dark", "light", "broken", "ocean"]);
+
+    let tmp = tempfile::tempdir().unwrap();
+    let opts = StartOptions { start_dir: Some(tmp.path().to_path_buf()), ..Default::default() };
+    let mut app = App::with_options(&opts).unwrap();
+    fileZoom::ui::colors::set_theme(&app.settings.theme);
+
+    handle_key(&mut app, KeyCode::Char('T'), 10).unwrap();
+    assert!(matches!(app.mode, Mode::ThemePicker { selected: 0, .. }));
+    let s = screen(&app);
+    assert!(s.contains("ocean") && s.contains("user") && s.contains("built-in"), "{}", s);
+
+    // A theme that fails to load shows its error and cannot be chosen.
+    for _ in 0..3 {
+        handle_key(&mut app, KeyCode::Down, 10).unwrap();
+    }
+    handle_key(&mut app, KeyCode::Enter, 10).unwrap();
+    assert!(matches!(&app.mode, Mode::ThemePicker { error: Some(e), .. } if e.contains("broken.toml")));
+
+    // Moving previews the user theme; Esc restores the previous colours.
+    handle_key(&mut app, KeyCode::Down, 10).unwrap();
+    assert_eq!(fileZoom::ui::colors::current().panel_block_style.bg, Some(Color::Rgb(0x00, 0x2b, 0x36)));
+    handle_key(&mut app, KeyCode::Esc, 10).unwrap();
+    assert!(matches!(app.mode, Mode::Normal));
+    assert_eq!(fileZoom::ui::colors::current().panel_block_style.bg, None);
+    assert_eq!(app.settings.theme, "default");
+
+    // Enter keeps the theme and saves its name.
+    fileZoom::runner::commands::execute_command(&mut app, "theme").unwrap();
+    for _ in 0..4 {
+        handle_key(&mut app, KeyCode::Down, 10).unwrap();
+    }
+    handle_key(&mut app, KeyCode::Enter, 10).unwrap();
+    assert!(matches!(app.mode, Mode::Normal), "{:?}", app.mode);
+    assert_eq!(app.settings.theme, "ocean");
+    assert_eq!(fileZoom::app::settings::load_settings().unwrap().theme, "ocean");
+}
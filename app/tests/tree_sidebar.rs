@@ -0,0 +1,40 @@
+use fileZoom::input::KeyCode;
+use fileZoom::runner::handlers::handle_key;
+
+mod common;
+use common::{app_in, screen_sized};
+
+#[test]
+fn the_tree_expands_and_moves_the_panel() {
+    let tmp = tempfile::tempdir().unwrap();
+    for dir in ["alpha/inner", "beta"] {
+        std::fs::create_dir_all(tmp.path().join(dir)).unwrap();
+    }
+    let mut app = app_in(tmp.path());
+
+    handle_key(&mut app, KeyCode::Char('e'), 10).unwrap();
+    assert!(app.tree.visible && app.tree.focused);
+    assert_eq!(app.tree.selected_path(), Some(tmp.path()));
+
+    // Right reads the directory, Right again steps to `alpha`, and once
+    // more expands it.
+    for _ in 0..3 {
+        handle_key(&mut app, KeyCode::Right, 10).unwrap();
+    }
+    let text = screen_sized(&app, 100, 24);
+    assert!(text.contains("Tree") && text.contains("▾ alpha") && text.contains("▸ inner") && text.contains("▸ beta"), "{text}");
+
+    handle_key(&mut app, KeyCode::Down, 10).unwrap();
+    handle_key(&mut app, KeyCode::Enter, 10).unwrap();
+    assert_eq!(app.left.cwd, tmp.path().join("alpha/inner"));
+    assert!(app.tree.focused);
+
+    // Other keys reach the panel once Tab hands the focus back; `e` then
+    // focuses the tree again and, focused, hides it.
+    handle_key(&mut app, KeyCode::Tab, 10).unwrap();
+    assert!(app.tree.visible && !app.tree.focused);
+    handle_key(&mut app, KeyCode::Char('e'), 10).unwrap();
+    handle_key(&mut app, KeyCode::Char('e'), 10).unwrap();
+    assert!(!app.tree.visible);
+    assert!(!screen_sized(&app, 100, 24).contains("Tree"));
+}
@@ -0,0 +1,53 @@
+use assert_fs::prelude::*;
+use fileZoom::app::{App, Panel};
+use fileZoom::input::KeyCode;
+use fileZoom::runner::handlers::handle_key;
+use ratatui::backend::TestBackend;
+use ratatui::Terminal;
+
+#[test]
+fn f12_toggles_diagnostics_overlay_with_load_stats() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    temp.child("a.txt").write_str("a").unwrap();
+    temp.child("b.txt").write_str("b").unwrap();
+    let mut app = App::new().unwrap();
+    app.left = Panel::new(temp.path().to_path_buf());
+    app.refresh().unwrap();
+    assert!(app.diagnostics.last_load.is_some());
+
+    let mut terminal = Terminal::new(TestBackend::new(100, 30)).unwrap();
+    let screen = |t: &Terminal<TestBackend>| t.backend().buffer().content().iter().map(|c| c.symbol()).collect::<String>();
+
+    terminal.draw(|f| fileZoom::ui::ui(f, &app)).unwrap();
+    assert!(!screen(&terminal).contains("Diagnostics"));
+
+    handle_key(&mut app, KeyCode::F(12), 10).unwrap();
+    assert!(app.diagnostics.visible);
+    terminal.draw(|f| fileZoom::ui::ui(f, &app)).unwrap();
+    let s = screen(&terminal);
+    assert!(s.contains("Diagnostics"));
+    assert!(s.contains("entries: 2 left"));
+
+    handle_key(&mut app, KeyCode::F(12), 10).unwrap();
+    assert!(!app.diagnostics.visible);
+}
+
+#[test]
+fn reloading_a_directory_is_served_from_the_metadata_cache() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    temp.child("a.txt").write_str("a").unwrap();
+    temp.child("b.txt").write_str("b").unwrap();
+    let mut app = App::new().unwrap();
+    app.left = Panel::new(temp.path().to_path_buf());
+    app.refresh().unwrap();
+    let hits = app.diagnostics.cache_hits;
+
+    app.refresh().unwrap();
+    assert!(app.diagnostics.cache_hits >= hits + 2, "second load reuses both entries");
+
+    temp.child("a.txt").write_str("changed").unwrap();
+    fileZoom::fs_op::cache::global().invalidate(temp.path());
+    app.refresh().unwrap();
+    let a = app.left.entries.iter().find(|e| e.name == "a.txt").unwrap();
+    assert_eq!(a.size, 7);
+}
@@ -20,3 +20,134 @@ fn render_dialog_with_test_backend() {
     // assertions are intentionally omitted to keep the test resilient to
     // layout changes in CI.
 }
+
+fn screen(t: &Terminal<TestBackend>) -> String {
+    let buf = t.backend().buffer();
+    let w = buf.area.width as usize;
+    let cells: Vec<&str> = buf.content().iter().map(|c| c.symbol()).collect();
+    cells.chunks(w).map(|row| row.concat()).collect::<Vec<_>>().join("\n")
+}
+
+#[test]
+fn conflict_dialog_compares_both_sides_and_highlights_newer() {
+    use fileZoom::app::types::Entry;
+    use ratatui::style::Color;
+
+    let older = chrono::Local::now() - chrono::Duration::days(3);
+    let newer = chrono::Local::now();
+    let mut src = Entry::file("a.txt", "/src/a.txt".into(), 2048, Some(newer));
+    src.unix_mode = Some(0o644);
+    let mut dst = Entry::file("a.txt", "/dst/a.txt".into(), 10, Some(older));
+    dst.unix_mode = Some(0o600);
+
+    let mut terminal = Terminal::new(TestBackend::new(100, 30)).unwrap();
+    terminal
+        .draw(|f| {
+            fileZoom::ui::widgets::dialog::render_conflict(
+                f,
+                f.area(),
+                std::path::Path::new("/dst/a.txt"),
+                Some(&src),
+                Some(&dst),
+                Some("In use by: vim (pid 42)"),
+                None,
+                1,
+                true,
+            )
+        })
+        .unwrap();
+    let s = screen(&terminal);
+    assert!(s.contains("Target exists: /dst/a.txt"));
+    assert!(s.contains("2048 B") && s.contains("10 B"));
+    assert!(s.contains("rw-r--r--") && s.contains("rw-------"));
+    assert!(s.contains("In use by: vim"));
+    assert!(s.contains("[x] apply to all") && s.contains("compare contents (d)"));
+    assert!(s.contains("[Skip]"));
+
+    // The newer modification time (source side) is drawn in green.
+    let stamp = newer.format("%Y-%m-%d %H:%M:%S").to_string();
+    let row = s.lines().position(|l| l.contains(&stamp)).unwrap();
+    let col = s.lines().nth(row).unwrap().find(&stamp).unwrap();
+    let cell = &terminal.backend().buffer()[(col as u16, row as u16)];
+    assert_eq!(cell.fg, Color::Green);
+}
+
+#[test]
+fn d_in_the_conflict_dialog_compares_the_contents() {
+    use fileZoom::app::{App, Mode};
+    use fileZoom::input::KeyCode;
+
+    let temp = tempfile::tempdir().unwrap();
+    let (src, dst) = (temp.path().join("src.txt"), temp.path().join("dst.txt"));
+    std::fs::write(&src, "same\nnew line\n").unwrap();
+    std::fs::write(&dst, "same\nold line\n").unwrap();
+    let stat = |p: &std::path::Path| fileZoom::app::core::panel::stat_entry(p).ok().map(Box::new);
+    let mut app = App::new().unwrap();
+    app.mode = Mode::Conflict { path: dst.clone(), selected: 0, apply_all: false, in_use: None, src: stat(&src), dst: stat(&dst), compare: None };
+
+    fileZoom::runner::handlers::handle_key(&mut app, KeyCode::Char('d'), 10).unwrap();
+    // A comparison slower than `COMPARE_WAIT` finishes on a worker thread.
+    let deadline = std::time::Instant::now() + std::time::Duration::from_secs(10);
+    while app.compare.is_some() {
+        assert!(std::time::Instant::now() < deadline, "the comparison never finished");
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        fileZoom::runner::handlers::conflict::poll(&mut app);
+    }
+    let mut terminal = Terminal::new(TestBackend::new(100, 30)).unwrap();
+    terminal.draw(|f| fileZoom::ui::ui(f, &app)).unwrap();
+    let s = screen(&terminal);
+    assert!(s.contains("Contents differ from byte 5 (line 2)"), "{s}");
+    assert!(s.contains("Source: new line") && s.contains("Target: old line"), "{s}");
+    assert!(matches!(app.mode, Mode::Conflict { .. }));
+}
+
+#[test]
+fn ui_renders_delete_confirm_with_entry_attributes() {
+    use fileZoom::app::{Action, App, Mode, Panel};
+
+    let temp = tempfile::tempdir().unwrap();
+    std::fs::write(temp.path().join("victim.txt"), "12345").unwrap();
+    let mut app = App::new().unwrap();
+    app.left = Panel::new(temp.path().to_path_buf());
+    app.refresh().unwrap();
+    let parent_count = if app.left.cwd.parent().is_some() { 1usize } else { 0usize };
+    app.left.selected = 1 + parent_count;
+    app.mode = Mode::Confirm { msg: "Delete victim.txt? (y/n)".into(), on_yes: Action::DeleteSelected, selected: 0 };
+
+    let mut terminal = Terminal::new(TestBackend::new(100, 30)).unwrap();
+    terminal.draw(|f| fileZoom::ui::ui(f, &app)).unwrap();
+    let s = screen(&terminal);
+    assert!(s.contains("Delete victim.txt?"));
+    assert!(s.contains("5 B"));
+    assert!(s.contains("[Yes]"));
+}
+
+#[test]
+fn ui_draws_context_menu_next_to_the_click() {
+    use fileZoom::app::{App, Mode};
+
+    let mut app = App::new().unwrap();
+    let menu = |at| Mode::ContextMenu {
+        title: "Actions: a.txt".into(),
+        options: vec!["View".into(), "Edit".into(), "Cancel".into()],
+        selected: 1,
+        path: "/tmp/a.txt".into(),
+        at,
+    };
+    app.mode = menu(Some((10, 6)));
+    let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+    terminal.draw(|f| fileZoom::ui::ui(f, &app)).unwrap();
+    let s = screen(&terminal);
+    let rows: Vec<String> = s.lines().map(|l| l.chars().skip(10).collect()).collect();
+    // Drawn below the clicked row, starting at the clicked column.
+    assert!(rows[7].starts_with("┌ Actions: a.txt"), "{}", rows[7]);
+    assert!(rows[9].starts_with("│ Edit"), "{}", rows[9]);
+
+    // Near the bottom-right corner it flips above and to the left.
+    app.mode = menu(Some((78, 22)));
+    terminal.draw(|f| fileZoom::ui::ui(f, &app)).unwrap();
+    let s = screen(&terminal);
+    let rows: Vec<&str> = s.lines().collect();
+    let bottom: String = rows[21].chars().skip(58).collect();
+    assert!(bottom.trim_end().ends_with('┘'), "{}", bottom);
+}
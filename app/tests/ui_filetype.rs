@@ -0,0 +1,59 @@
+//! File-type colours and icons in the panel listings. The theme and
+//! `LS_COLORS` rules are global, so these tests live in their own binary.
+
+use fileZoom::app::{App, StartOptions};
+use fileZoom::ui::filetype::{self, LsColors};
+use ratatui::style::Color;
+use ratatui::{backend::TestBackend, buffer::Buffer, Terminal};
+
+fn draw(app: &App) -> Buffer {
+    let mut terminal = Terminal::new(TestBackend::new(100, 20)).unwrap();
+    terminal.draw(|f| fileZoom::ui::ui(f, app)).unwrap();
+    terminal.backend().buffer().clone()
+}
+
+/// Foreground colour of the first cell of `text` on screen.
+fn fg_of(buf: &Buffer, text: &str) -> Option<Color> {
+    let width = buf.area.width as usize;
+    let symbols: Vec<&str> = buf.content().iter().map(|c| c.symbol()).collect();
+    (0..symbols.len()).find(|&i| symbols[i..].iter().take(text.len()).copied().collect::<String>() == text).map(|i| {
+        let (x, y) = ((i % width) as u16, (i / width) as u16);
+        buf[(x, y)].fg
+    })
+}
+
+#[test]
+fn listing_rows_follow_the_theme_ls_colors_and_icons() {
+    let tmp = tempfile::tempdir().unwrap();
+    std::fs::create_dir(tmp.path().join("subdir")).unwrap();
+    std::fs::write(tmp.path().join("backup.tar"), "x").unwrap();
+    std::fs::write(tmp.path().join("notes.txt"), "x").unwrap();
+    std::fs::write(tmp.path().join("snapshot"), b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR").unwrap();
+    let opts = StartOptions { start_dir: Some(tmp.path().to_path_buf()), ..Default::default() };
+    let mut app = App::with_options(&opts).unwrap();
+    filetype::set_ls_colors(None);
+    fileZoom::ui::colors::reset();
+
+    let buf = draw(&app);
+    assert_eq!(fg_of(&buf, "subdir"), Some(Color::Blue));
+    assert_eq!(fg_of(&buf, "backup.tar"), Some(Color::Red));
+    assert_eq!(fg_of(&buf, "notes.txt"), Some(Color::Reset));
+    // Without an extension the content decides.
+    assert_eq!(fg_of(&buf, "snapshot"), Some(Color::Magenta));
+
+    // Themes can recolour a kind.
+    let theme = fileZoom::ui::Theme::from_toml("palette = { bg = \"#000000\", fg = \"#ffffff\", accent = \"#00ff00\" }\n[panels]\ndir_fg = \"#ffaa00\"\n").unwrap();
+    fileZoom::ui::colors::set_from_theme(&theme);
+    assert_eq!(fg_of(&draw(&app), "subdir"), Some(Color::Rgb(0xff, 0xaa, 0x00)));
+
+    // LS_COLORS rules win over the theme.
+    filetype::set_ls_colors(Some(LsColors::parse("di=01;33:*.tar=35")));
+    let buf = draw(&app);
+    assert_eq!(fg_of(&buf, "subdir"), Some(Color::Yellow));
+    assert_eq!(fg_of(&buf, "backup.tar"), Some(Color::Magenta));
+
+    app.settings.icons = true;
+    let screen: String = draw(&app).content().iter().map(|c| c.symbol()).collect();
+    assert!(screen.contains(&format!("{} subdir", filetype::icon(filetype::FileKind::Directory))), "{}", screen);
+    filetype::set_ls_colors(None);
+}
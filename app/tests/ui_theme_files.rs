@@ -8,3 +8,12 @@ fn load_dark_theme_from_file() {
     // expect accent not reset
     assert_ne!(format!("{:?}", t.accent), format!("{:?}", fileZoom::ui::themes::Theme::dark().accent));
 }
+
+#[test]
+fn malformed_user_theme_colours_fall_back_to_reset() {
+    // "#aé123" is six bytes but not six hex digits.
+    let t = Theme::from_toml("palette = { bg = \"#aé123\", fg = \"#12345\", accent = \"#268bd2\" }\n").expect("parse");
+    assert_eq!(t.bg, ratatui::style::Color::Reset);
+    assert_eq!(t.fg, ratatui::style::Color::Reset);
+    assert_eq!(t.accent, ratatui::style::Color::Rgb(0x26, 0x8b, 0xd2));
+}
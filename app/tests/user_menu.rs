@@ -0,0 +1,70 @@
+#![cfg(unix)]
+
+use assert_fs::prelude::*;
+use fileZoom::app::settings::user_menu::parse_user_menu;
+use fileZoom::app::{App, Mode, Panel};
+use fileZoom::input::KeyCode;
+use fileZoom::runner::handlers::handle_key;
+
+const MENU: &str = r#"
+[[entry]]
+title = "Touch one"
+key = "1"
+command = "touch one.txt"
+
+[[entry]]
+title = "Copy selection"
+command = "cp %f copy.txt"
+"#;
+
+fn app_with_menu(temp: &assert_fs::TempDir) -> App {
+    let mut app = App::new().unwrap();
+    app.left = Panel::new(temp.path().to_path_buf());
+    app.right = Panel::new(temp.path().to_path_buf());
+    app.refresh().unwrap();
+    app.mode = Mode::UserMenu { entries: parse_user_menu(MENU).unwrap().entries, selected: 0 };
+    app
+}
+
+/// Wait for a command that outlived `SHELL_WAIT` to finish.
+fn finish(app: &mut App) {
+    let deadline = std::time::Instant::now() + std::time::Duration::from_secs(10);
+    while app.shell.is_some() {
+        assert!(std::time::Instant::now() < deadline, "the command never finished");
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        fileZoom::runner::shell::poll(app).unwrap();
+    }
+}
+
+#[test]
+fn enter_runs_selected_entry_with_placeholders() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    temp.child("picked.txt").write_str("x").unwrap();
+    let mut app = app_with_menu(&temp);
+    let idx = app.left.entries.iter().position(|e| e.name == "picked.txt").unwrap();
+    let parent_count = if app.left.cwd.parent().is_some() { 1usize } else { 0usize };
+    app.left.selected = 1 + parent_count + idx;
+
+    handle_key(&mut app, KeyCode::Down, 10).unwrap();
+    handle_key(&mut app, KeyCode::Down, 10).unwrap();
+    assert!(matches!(app.mode, Mode::UserMenu { selected: 1, .. }));
+    handle_key(&mut app, KeyCode::Enter, 10).unwrap();
+    finish(&mut app);
+
+    assert!(matches!(app.mode, Mode::CommandOutput { status: Some(0), .. }));
+    assert!(temp.child("copy.txt").path().exists());
+    assert!(app.left.entries.iter().any(|e| e.name == "copy.txt"));
+}
+
+#[test]
+fn hotkey_runs_entry_and_esc_closes() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let mut app = app_with_menu(&temp);
+    handle_key(&mut app, KeyCode::Char('1'), 10).unwrap();
+    finish(&mut app);
+    assert!(temp.child("one.txt").path().exists());
+
+    app.mode = Mode::UserMenu { entries: parse_user_menu(MENU).unwrap().entries, selected: 0 };
+    handle_key(&mut app, KeyCode::Esc, 10).unwrap();
+    assert!(matches!(app.mode, Mode::Normal));
+}
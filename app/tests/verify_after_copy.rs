@@ -0,0 +1,81 @@
+use fileZoom::app::{App, Mode, StartOptions};
+use fileZoom::input::KeyCode;
+use fileZoom::runner::handlers::handle_key;
+use fileZoom::runner::progress::ProgressPhase;
+use ratatui::{backend::TestBackend, Terminal};
+use std::time::{Duration, Instant};
+
+/// App with `left/tree` (holding `files` files) selected in the left panel
+/// and `right` open in the right one.
+fn app_with_tree(tmp: &std::path::Path, files: usize) -> App {
+    let tree = tmp.join("left/tree/sub");
+    std::fs::create_dir_all(&tree).unwrap();
+    std::fs::create_dir_all(tmp.join("right")).unwrap();
+    for i in 0..files {
+        std::fs::write(tree.join(format!("f{}", i)), format!("content {}", i)).unwrap();
+    }
+    let opts = StartOptions { start_dir: Some(tmp.join("left")), ..Default::default() };
+    let mut app = App::with_options(&opts).unwrap();
+    app.right.cwd = tmp.join("right");
+    app.refresh().unwrap();
+    assert!(app.left.select_named("tree"));
+    app.settings.verify_after_copy = true;
+    app
+}
+
+fn wait_for_message(app: &mut App) -> String {
+    let deadline = Instant::now() + Duration::from_secs(10);
+    loop {
+        app.poll_progress();
+        if let Mode::Summary { note, .. } = &app.mode {
+            return note.clone().unwrap_or_default();
+        }
+        assert!(Instant::now() < deadline, "copy never finished");
+        std::thread::sleep(Duration::from_millis(5));
+    }
+}
+
+#[test]
+fn verified_copy_reports_a_verifying_phase() {
+    let tmp = tempfile::tempdir().unwrap();
+    let mut app = app_with_tree(tmp.path(), 3);
+    handle_key(&mut app, KeyCode::F(5), 10).unwrap();
+    assert!(matches!(app.mode, Mode::Progress { verify: Some((0, 0)), .. }));
+
+    let rx = app.op_progress_rx.take().unwrap();
+    let mut phases = Vec::new();
+    let last = loop {
+        let update = rx.recv_timeout(Duration::from_secs(10)).expect("worker update");
+        phases.push(update.phase);
+        if update.done {
+            break update;
+        }
+    };
+    assert!(phases.contains(&ProgressPhase::Copying) && phases.contains(&ProgressPhase::Verifying));
+    assert_eq!((last.error, last.message.as_deref()), (None, Some("All 3 copied files verified")));
+    assert!(tmp.path().join("right/tree/sub/f2").exists());
+}
+
+#[test]
+fn s_skips_the_verification_pass() {
+    let tmp = tempfile::tempdir().unwrap();
+    let mut app = app_with_tree(tmp.path(), 200);
+    handle_key(&mut app, KeyCode::F(5), 10).unwrap();
+    handle_key(&mut app, KeyCode::Char('s'), 10).unwrap();
+    assert!(app.op_skip_verify.is_none());
+    let content = wait_for_message(&mut app);
+    assert!(content.contains("Verification skipped"), "{content}");
+    assert!(tmp.path().join("right/tree/sub/f199").exists());
+}
+
+#[test]
+fn progress_dialog_shows_both_bars() {
+    let mut app = App::with_options(&StartOptions { start_dir: Some(std::env::temp_dir()), ..Default::default() }).unwrap();
+    app.mode = Mode::Progress { title: "Copying".into(), processed: 2, total: 2, message: "Verifying x".into(), cancelled: false, verify: Some((1, 4)) };
+    let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+    terminal.draw(|f| fileZoom::ui::ui(f, &app)).unwrap();
+    let buf = terminal.backend().buffer();
+    let text: String = (0..24).map(|y| (0..80).map(|x| buf[(x, y)].symbol()).collect::<String>() + "\n").collect();
+    assert!(text.contains("] 2/2") && text.contains("] 1/4"), "{text}");
+    assert!(text.contains("s skip verification"), "{text}");
+}
@@ -0,0 +1,57 @@
+//! File names with wide (CJK, emoji) and combining characters are measured
+//! in terminal cells, so rows, breadcrumbs and previews keep their layout.
+
+use fileZoom::app::{App, StartOptions};
+use fileZoom::ui::panels::{display_width, expand_tabs, pad_to_width, truncate_to_width};
+use fileZoom::ui::widgets::breadcrumbs::Breadcrumbs;
+use ratatui::{backend::TestBackend, layout::Rect, Terminal};
+
+#[test]
+fn widths_count_cells_and_keep_graphemes_whole() {
+    assert_eq!(display_width("漢字.txt"), 8);
+    assert_eq!(display_width("e\u{301}te\u{301}"), 3);
+    assert_eq!(truncate_to_width("漢字漢字", 6), "漢字…");
+    assert_eq!(truncate_to_width("漢字漢字", 5), "漢字…");
+    assert_eq!(truncate_to_width("e\u{301}e\u{301}e\u{301}", 2), "e\u{301}…");
+    assert_eq!(pad_to_width("字", 4), "字  ");
+    assert_eq!(expand_tabs("字\tb\n\tc"), "字      b\n        c");
+}
+
+#[test]
+fn breadcrumb_columns_follow_wide_labels() {
+    let tmp = tempfile::tempdir().unwrap();
+    let deep = tmp.path().join("日本語").join("beta");
+    std::fs::create_dir_all(&deep).unwrap();
+    let opts = StartOptions { start_dir: Some(deep.clone()), ..Default::default() };
+    let app = App::with_options(&opts).unwrap();
+    let area = Rect::new(0, 0, 100, 24);
+    let left = fileZoom::ui::layout::ScreenLayout::compute(&app, area).left;
+
+    let mut terminal = Terminal::new(TestBackend::new(area.width, area.height)).unwrap();
+    terminal.draw(|f| fileZoom::ui::ui(f, &app)).unwrap();
+    let buf = terminal.backend().buffer();
+    let bar = Breadcrumbs::new(&deep, left);
+    let beta = bar.crumbs.iter().find(|c| c.label == "beta").unwrap();
+    assert_eq!(buf[(beta.x, left.y)].symbol(), "b");
+    let wide = bar.crumbs.iter().find(|c| c.label == "日本語").unwrap();
+    assert_eq!(buf[(wide.x, left.y)].symbol(), "日");
+    assert_eq!(bar.at(wide.x + 5).map(|c| c.label.as_str()), Some("日本語"));
+}
+
+#[test]
+fn long_wide_names_end_in_an_ellipsis() {
+    let tmp = tempfile::tempdir().unwrap();
+    let name = "漢字".repeat(40);
+    std::fs::write(tmp.path().join(&name), "").unwrap();
+    let opts = StartOptions { start_dir: Some(tmp.path().to_path_buf()), ..Default::default() };
+    let app = App::with_options(&opts).unwrap();
+    let area = Rect::new(0, 0, 80, 24);
+    let left = fileZoom::ui::layout::ScreenLayout::compute(&app, area).left;
+
+    let mut terminal = Terminal::new(TestBackend::new(area.width, area.height)).unwrap();
+    terminal.draw(|f| fileZoom::ui::ui(f, &app)).unwrap();
+    let buf = terminal.backend().buffer();
+    let row = (left.y..left.bottom()).find(|&y| buf[(left.x + 1, y)].symbol() == "漢").expect("name row");
+    let text: String = (left.x + 1..left.right() - 1).map(|x| buf[(x, row)].symbol()).collect();
+    assert!(text.trim_end().ends_with('…'), "{text}");
+}
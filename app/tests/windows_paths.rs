@@ -0,0 +1,37 @@
+#![cfg(windows)]
+
+use fileZoom::app::{App, Mode, StartOptions};
+use fileZoom::input::KeyCode;
+use fileZoom::runner::handlers::handle_key;
+
+#[test]
+fn parent_of_a_drive_root_lists_the_drives() {
+    let root = std::path::PathBuf::from(format!("{}\\", std::env::var("SystemDrive").unwrap_or_else(|_| "C:".to_string())));
+    let opts = StartOptions { start_dir: Some(root.clone()), ..Default::default() };
+    let mut app = App::with_options(&opts).unwrap();
+
+    app.go_up().unwrap();
+    let Mode::MountPicker { mounts, selected } = &app.mode else { panic!("expected the drive list, got {:?}", app.mode) };
+    assert_eq!(mounts[*selected].root, root);
+
+    // Enter goes to the chosen drive.
+    handle_key(&mut app, KeyCode::Enter, 10).unwrap();
+    assert_eq!(app.left.cwd, root);
+}
+
+#[test]
+fn names_differing_in_case_are_not_copied_over_each_other() {
+    let tmp = tempfile::tempdir().unwrap();
+    let (a, b, dst) = (tmp.path().join("a"), tmp.path().join("b"), tmp.path().join("dst"));
+    for dir in [&a, &b, &dst] {
+        std::fs::create_dir_all(dir).unwrap();
+    }
+    std::fs::write(a.join("Readme.md"), "first").unwrap();
+    std::fs::write(b.join("README.md"), "second").unwrap();
+
+    // The second copy finds the first one there instead of replacing it.
+    assert!(fileZoom::fs_op::path::names_collide(&[a.join("Readme.md"), b.join("README.md")]));
+    fileZoom::fs_op::mv::copy_path_with(a.join("Readme.md"), dst.join("Readme.md"), &Default::default()).unwrap();
+    assert!(fileZoom::fs_op::mv::copy_path_with(b.join("README.md"), dst.join("README.md"), &Default::default()).is_err());
+    assert_eq!(std::fs::read_to_string(dst.join("readme.md")).unwrap(), "first");
+}